@@ -12,11 +12,23 @@ use openmls_traits::{
     types::{CryptoError, SignatureScheme},
 };
 
+use ecdsa::hazmat::{bits2field, SignPrimitive};
+use ed448_goldilocks_plus::{EdwardsPoint, Scalar, WideScalarBytes};
+#[cfg(feature = "post-quantum")]
+use ml_dsa::{KeyGen, MlDsa44, MlDsa65, MlDsa87, MlDsaParams};
 use p256::ecdsa::{signature::Signer as P256Signer, Signature, SigningKey};
+use p521::NistP521;
+use rfc6979::HmacDrbg;
 
-use rand::rngs::OsRng;
+use rand::{rngs::OsRng, RngCore};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha512};
+use sha3::{
+    digest::{ExtendableOutput, Update, XofReader},
+    Shake256,
+};
 use tls_codec::{TlsDeserialize, TlsDeserializeBytes, TlsSerialize, TlsSize};
+use zeroize::{Zeroize, Zeroizing};
 
 /// A signature key pair for the basic credential.
 ///
@@ -51,13 +63,34 @@ impl Signer for SignatureKeyPair {
                 let signature: Signature = k.sign(payload);
                 Ok(signature.to_der().to_bytes().into())
             }
+            SignatureScheme::ECDSA_SECP384R1_SHA384 => {
+                let k = p384::ecdsa::SigningKey::from_slice(&self.private)
+                    .map_err(|_| SignerError::SigningError)?;
+                let signature: p384::ecdsa::Signature = k.sign(payload);
+                Ok(signature.to_der().to_bytes().into())
+            }
+            SignatureScheme::ECDSA_SECP521R1_SHA512 => {
+                let signature =
+                    sign_p521(&self.private, payload).map_err(|_| SignerError::SigningError)?;
+                Ok(signature.to_der().to_bytes().into())
+            }
             SignatureScheme::ED25519 => {
                 let k = ed25519_dalek::SigningKey::try_from(self.private.as_slice())
                     .map_err(|_| SignerError::SigningError)?;
                 let signature = k.sign(payload);
                 Ok(signature.to_bytes().into())
             }
-            _ => Err(SignerError::SigningError),
+            SignatureScheme::ED448 => sign_ed448(&self.private, payload),
+            #[cfg(feature = "post-quantum")]
+            SignatureScheme::ML_DSA_44 => sign_ml_dsa::<MlDsa44>(&self.private, payload),
+            #[cfg(feature = "post-quantum")]
+            SignatureScheme::ML_DSA_65 => sign_ml_dsa::<MlDsa65>(&self.private, payload),
+            #[cfg(feature = "post-quantum")]
+            SignatureScheme::ML_DSA_87 => sign_ml_dsa::<MlDsa87>(&self.private, payload),
+            #[cfg(not(feature = "post-quantum"))]
+            SignatureScheme::ML_DSA_44
+            | SignatureScheme::ML_DSA_65
+            | SignatureScheme::ML_DSA_87 => Err(SignerError::SigningError),
         }
     }
 
@@ -66,6 +99,127 @@ impl Signer for SignatureKeyPair {
     }
 }
 
+/// Signs the `data` with a P-521 signing `key`.
+///
+/// The signing key of `p521` picks random nonces. Like for the other ECDSA
+/// schemes, the nonce is derived deterministically as in RFC 6979 instead.
+/// The `rfc6979` crate requires the digest to be as long as the field, which
+/// doesn't hold for SHA-512 and P-521, so `k` is generated here.
+fn sign_p521(key: &[u8], data: &[u8]) -> Result<p521::ecdsa::Signature, ecdsa::Error> {
+    let key = p521::ecdsa::SigningKey::from_slice(key)?;
+    let x = key.as_nonzero_scalar().as_ref();
+    let z = bits2field::<NistP521>(&Sha512::digest(data))?;
+
+    let mut drbg = HmacDrbg::<Sha512>::new(&x.to_bytes(), &z, &[]);
+    loop {
+        // bits2int: keep the leftmost 521 of the 528 generated bits.
+        let mut t = p521::FieldBytes::default();
+        drbg.fill_bytes(&mut t);
+        let mut k = p521::FieldBytes::default();
+        for i in (1..k.len()).rev() {
+            k[i] = (t[i - 1] << 1) | (t[i] >> 7);
+        }
+        k[0] = t[0] >> 7;
+
+        let k = Option::<p521::NonZeroScalar>::from(p521::NonZeroScalar::from_repr(k));
+        if let Some(k) = k {
+            let (signature, _) = x.try_sign_prehashed(*k, &z)?;
+            return Ok(signature);
+        }
+    }
+}
+
+/// The length of an Ed448 private or public key.
+const ED448_KEY_LENGTH: usize = 57;
+
+/// `dom4(0, "")`, the prefix of all hashes for pure Ed448 without context.
+const ED448_DOM4: &[u8] = b"SigEd448\x00\x00";
+
+/// `SHAKE256(dom4 || parts, 114)` for Ed448, reduced modulo the group order.
+fn ed448_hash_to_scalar(parts: &[&[u8]]) -> Scalar {
+    let mut hasher = Shake256::default();
+    hasher.update(ED448_DOM4);
+    for part in parts {
+        hasher.update(part);
+    }
+    let mut digest = WideScalarBytes::default();
+    hasher.finalize_xof().read(&mut digest);
+    let scalar = Scalar::from_bytes_mod_order_wide(&digest);
+    digest.as_mut_slice().zeroize();
+    scalar
+}
+
+/// Expands the Ed448 private `key` into the secret scalar and the prefix for
+/// the nonces, as in RFC 8032, Section 5.2.5.
+fn expand_ed448(
+    key: &[u8],
+) -> Result<(Zeroizing<Scalar>, Zeroizing<[u8; ED448_KEY_LENGTH]>), SignerError> {
+    if key.len() != ED448_KEY_LENGTH {
+        return Err(SignerError::SigningError);
+    }
+    let mut h = Zeroizing::new([0u8; 2 * ED448_KEY_LENGTH]);
+    let mut hasher = Shake256::default();
+    hasher.update(key);
+    hasher.finalize_xof().read(h.as_mut_slice());
+
+    let mut s = WideScalarBytes::default();
+    s[..ED448_KEY_LENGTH].copy_from_slice(&h[..ED448_KEY_LENGTH]);
+    s[0] &= 0xfc;
+    s[55] |= 0x80;
+    s[56] = 0;
+    let scalar = Zeroizing::new(Scalar::from_bytes_mod_order_wide(&s));
+    s.as_mut_slice().zeroize();
+
+    let mut prefix = Zeroizing::new([0u8; ED448_KEY_LENGTH]);
+    prefix.copy_from_slice(&h[ED448_KEY_LENGTH..]);
+    Ok((scalar, prefix))
+}
+
+/// Signs the `payload` with an Ed448 signing `key`.
+///
+/// `ed448-goldilocks-plus` only provides the curve arithmetic, so the
+/// signature is computed here as in RFC 8032, Section 5.2.6, with an empty
+/// context string. The secrets are only used in the constant-time scalar
+/// multiplication and scalar arithmetic of `ed448-goldilocks-plus`, and are
+/// zeroized when this function returns.
+fn sign_ed448(key: &[u8], payload: &[u8]) -> Result<Vec<u8>, SignerError> {
+    let (s, prefix) = expand_ed448(key)?;
+    let public_key = (EdwardsPoint::GENERATOR * *s).compress();
+
+    let r = Zeroizing::new(ed448_hash_to_scalar(&[prefix.as_slice(), payload]));
+    let big_r = (EdwardsPoint::GENERATOR * *r).compress();
+    let k = ed448_hash_to_scalar(&[big_r.as_bytes(), public_key.as_bytes(), payload]);
+
+    let mut signature = big_r.to_bytes().to_vec();
+    signature.extend_from_slice(&(*r + k * *s).to_bytes_rfc_8032());
+    Ok(signature)
+}
+
+/// Signs the `payload` with an ML-DSA signing `key`.
+///
+/// The key is the 32-byte seed of the key generation. The signature is
+/// deterministic and uses an empty context string. The seed is expanded into
+/// the signing key again for every signature, which roughly doubles its cost.
+#[cfg(feature = "post-quantum")]
+fn sign_ml_dsa<P: MlDsaParams>(key: &[u8], payload: &[u8]) -> Result<Vec<u8>, SignerError> {
+    let seed = ml_dsa::B32::try_from(key).map_err(|_| SignerError::SigningError)?;
+    let signature: ml_dsa::Signature<P> = P::key_gen_internal(&seed)
+        .signing_key()
+        .try_sign(payload)
+        .map_err(|_| SignerError::SigningError)?;
+    Ok(signature.encode().to_vec())
+}
+
+/// Generates a fresh ML-DSA key pair. Returns the seed of the key generation
+/// as private key, and the public key.
+#[cfg(feature = "post-quantum")]
+fn ml_dsa_key_gen<P: MlDsaParams>() -> (Vec<u8>, Vec<u8>) {
+    let mut seed = ml_dsa::B32::default();
+    OsRng.fill_bytes(&mut seed);
+    let pk = P::key_gen_internal(&seed).verifying_key().encode().to_vec();
+    (seed.to_vec(), pk)
+}
+
 /// Compute the ID for a [`Signature`] in the key store.
 fn id(public_key: &[u8], signature_scheme: SignatureScheme) -> Vec<u8> {
     const LABEL: &[u8; 22] = b"RustCryptoSignatureKey";
@@ -85,12 +239,44 @@ impl SignatureKeyPair {
                 let pk = k.verifying_key().to_encoded_point(false).as_bytes().into();
                 (k.to_bytes().as_slice().into(), pk)
             }
+            SignatureScheme::ECDSA_SECP384R1_SHA384 => {
+                let k = p384::ecdsa::SigningKey::random(&mut OsRng);
+                let pk = k.verifying_key().to_encoded_point(false).as_bytes().into();
+                (k.to_bytes().as_slice().into(), pk)
+            }
+            SignatureScheme::ECDSA_SECP521R1_SHA512 => {
+                let k = p521::ecdsa::SigningKey::random(&mut OsRng);
+                let pk = p521::ecdsa::VerifyingKey::from(&k)
+                    .to_encoded_point(false)
+                    .as_bytes()
+                    .into();
+                (k.to_bytes().as_slice().into(), pk)
+            }
             SignatureScheme::ED25519 => {
                 let sk = ed25519_dalek::SigningKey::generate(&mut OsRng);
                 let pk = sk.verifying_key().to_bytes().into();
                 (sk.to_bytes().into(), pk)
             }
-            _ => return Err(CryptoError::UnsupportedSignatureScheme),
+            SignatureScheme::ED448 => {
+                let mut sk = [0u8; ED448_KEY_LENGTH];
+                OsRng.fill_bytes(&mut sk);
+                let (s, _) = expand_ed448(&sk).map_err(|_| CryptoError::CryptoLibraryError)?;
+                let pk = (EdwardsPoint::GENERATOR * *s)
+                    .compress()
+                    .to_bytes()
+                    .to_vec();
+                (sk.to_vec(), pk)
+            }
+            #[cfg(feature = "post-quantum")]
+            SignatureScheme::ML_DSA_44 => ml_dsa_key_gen::<MlDsa44>(),
+            #[cfg(feature = "post-quantum")]
+            SignatureScheme::ML_DSA_65 => ml_dsa_key_gen::<MlDsa65>(),
+            #[cfg(feature = "post-quantum")]
+            SignatureScheme::ML_DSA_87 => ml_dsa_key_gen::<MlDsa87>(),
+            #[cfg(not(feature = "post-quantum"))]
+            SignatureScheme::ML_DSA_44
+            | SignatureScheme::ML_DSA_65
+            | SignatureScheme::ML_DSA_87 => return Err(CryptoError::UnsupportedSignatureScheme),
         };
 
         Ok(Self {
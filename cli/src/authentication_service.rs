@@ -0,0 +1,48 @@
+use std::str;
+
+use openmls::prelude::*;
+
+/// The authentication service of the CLI.
+///
+/// Clients are identified by the name in their basic credential, which is how
+/// the CLI addresses group members and looks up contacts. Credentials are
+/// therefore only accepted if they are basic credentials with a UTF-8 name,
+/// and a member may not change its name when updating its credential.
+pub struct CliAuthenticationService;
+
+impl CliAuthenticationService {
+    fn user_name(credential: &Credential) -> Result<String, String> {
+        let credential = BasicCredential::try_from(credential.clone())
+            .map_err(|e| format!("Unsupported credential: {e}"))?;
+        str::from_utf8(credential.identity())
+            .map(str::to_owned)
+            .map_err(|_| "The user name is not valid UTF-8".to_string())
+    }
+}
+
+impl AuthenticationService for CliAuthenticationService {
+    fn authenticate(
+        &self,
+        credential: &CredentialWithKey,
+        source: CredentialSource,
+    ) -> Result<(), String> {
+        let user_name = Self::user_name(&credential.credential)?;
+        log::trace!("Authenticated {user_name:?} from {source:?}");
+        Ok(())
+    }
+
+    fn authenticate_succession(
+        &self,
+        old: &CredentialWithKey,
+        new: &CredentialWithKey,
+    ) -> Result<(), String> {
+        let old_name = Self::user_name(&old.credential)?;
+        let new_name = Self::user_name(&new.credential)?;
+        if old_name != new_name {
+            return Err(format!(
+                "{old_name:?} may not change its user name to {new_name:?}"
+            ));
+        }
+        Ok(())
+    }
+}
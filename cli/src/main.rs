@@ -2,9 +2,12 @@
 // extern crate clap;
 // use clap::App;
 
+use std::fs;
 use std::io::{stdin, stdout, StdoutLock, Write};
+use std::path::PathBuf;
 use termion::input::TermRead;
 
+mod authentication_service;
 mod backend;
 mod conversation;
 mod identity;
@@ -31,6 +34,17 @@ const HELP: &str = "
 
 ";
 
+/// The directory in which the CLI keeps the state of its users, in the data
+/// directory of the user running it. The directory is created if it doesn't
+/// exist yet.
+pub(crate) fn data_dir() -> Result<PathBuf, String> {
+    let dir = dirs::data_local_dir()
+        .ok_or("There is no data directory for the current user")?
+        .join("openmls_cli");
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir)
+}
+
 fn update(client: &mut user::User, group_id: Option<String>, stdout: &mut StdoutLock) {
     let messages = client.update(group_id).unwrap();
     stdout.write_all(b" >>> Updated client :)\n").unwrap();
@@ -1,21 +1,25 @@
 //! # OpenMLS Default Crypto Provider
 //!
 //! This is an implementation of the [`OpenMlsProvider`] trait to use with
-//! OpenMLS.
+//! OpenMLS. The key store is persisted to a file with the
+//! [`FileStorage`], which writes every change to disk.
 
-use openmls_rust_crypto::{MemoryStorage, RustCrypto};
+use std::{fs, io, path::PathBuf};
+
+use openmls_file_storage::FileStorage;
+use openmls_rust_crypto::RustCrypto;
 use openmls_traits::OpenMlsProvider;
 
-#[derive(Default, Debug)]
+#[derive(Debug)]
 pub struct OpenMlsRustPersistentCrypto {
     crypto: RustCrypto,
-    storage: MemoryStorage,
+    storage: FileStorage,
 }
 
 impl OpenMlsProvider for OpenMlsRustPersistentCrypto {
     type CryptoProvider = RustCrypto;
     type RandProvider = RustCrypto;
-    type StorageProvider = MemoryStorage;
+    type StorageProvider = FileStorage;
 
     fn crypto(&self) -> &Self::CryptoProvider {
         &self.crypto
@@ -31,11 +35,27 @@ impl OpenMlsProvider for OpenMlsRustPersistentCrypto {
 }
 
 impl OpenMlsRustPersistentCrypto {
-    pub fn save_keystore(&self, user_name: String) -> Result<(), String> {
-        self.storage.save(user_name)
+    /// Create a provider with a new, empty key store for the user.
+    /// An existing key store of a user with the same name is removed.
+    pub fn new(user_name: &str) -> Result<Self, String> {
+        match fs::remove_file(Self::keystore_path(user_name)?) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e.to_string()),
+            _ => (),
+        }
+        Self::open(user_name)
+    }
+
+    /// Create a provider with the existing key store of the user.
+    pub fn open(user_name: &str) -> Result<Self, String> {
+        let storage =
+            FileStorage::open(Self::keystore_path(user_name)?).map_err(|e| e.to_string())?;
+        Ok(Self {
+            crypto: RustCrypto::default(),
+            storage,
+        })
     }
 
-    pub fn load_keystore(&mut self, user_name: String) -> Result<(), String> {
-        self.storage.load(user_name)
+    fn keystore_path(user_name: &str) -> Result<PathBuf, String> {
+        Ok(super::data_dir()?.join(format!("{user_name}_ks.log")))
     }
 }
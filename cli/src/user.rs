@@ -11,9 +11,9 @@ use openmls::prelude::{tls_codec::*, *};
 use openmls_traits::OpenMlsProvider;
 
 use super::{
-    backend::Backend, conversation::Conversation, conversation::ConversationMessage,
-    identity::Identity, openmls_rust_persistent_crypto::OpenMlsRustPersistentCrypto,
-    serialize_any_hashmap,
+    authentication_service::CliAuthenticationService, backend::Backend, conversation::Conversation,
+    conversation::ConversationMessage, identity::Identity,
+    openmls_rust_persistent_crypto::OpenMlsRustPersistentCrypto, serialize_any_hashmap,
 };
 
 const CIPHERSUITE: Ciphersuite = Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519;
@@ -35,12 +35,9 @@ pub struct Group {
     mls_group: RefCell<MlsGroup>,
 }
 
-#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(serde::Serialize)]
 pub struct User {
-    #[serde(
-        serialize_with = "serialize_any_hashmap::serialize_hashmap",
-        deserialize_with = "serialize_any_hashmap::deserialize_hashmap"
-    )]
+    #[serde(serialize_with = "serialize_any_hashmap::serialize_hashmap")]
     pub(crate) contacts: HashMap<Vec<u8>, Contact>,
     #[serde(skip)]
     pub(crate) groups: RefCell<HashMap<String, Group>>,
@@ -54,6 +51,18 @@ pub struct User {
     auth_token: Option<AuthToken>,
 }
 
+/// The state of a [`User`] as it is saved to its file. The groups are loaded
+/// from the key store.
+#[derive(serde::Deserialize)]
+struct SavedUser {
+    #[serde(deserialize_with = "serialize_any_hashmap::deserialize_hashmap")]
+    contacts: HashMap<Vec<u8>, Contact>,
+    group_list: HashSet<String>,
+    identity: RefCell<Identity>,
+    autosave_enabled: bool,
+    auth_token: Option<AuthToken>,
+}
+
 #[derive(PartialEq)]
 pub enum PostUpdateActions {
     None,
@@ -63,7 +72,8 @@ pub enum PostUpdateActions {
 impl User {
     /// Create a new user with the given name and a fresh set of credentials.
     pub fn new(username: String) -> Self {
-        let crypto = OpenMlsRustPersistentCrypto::default();
+        let crypto =
+            OpenMlsRustPersistentCrypto::new(&username).expect("Error creating the key store");
         let out = Self {
             groups: RefCell::new(HashMap::new()),
             group_list: HashSet::new(),
@@ -77,60 +87,57 @@ impl User {
         out
     }
 
-    fn get_file_path(user_name: &String) -> PathBuf {
-        openmls_memory_storage::persistence::get_file_path(
-            &("openmls_cli_".to_owned() + user_name + ".json"),
-        )
+    fn get_file_path(user_name: &str) -> Result<PathBuf, String> {
+        Ok(super::data_dir()?.join(format!("{user_name}.json")))
     }
 
-    fn load_from_file(input_file: &File) -> Result<Self, String> {
+    fn load_from_file(input_file: &File) -> Result<SavedUser, String> {
         // Prepare file reader.
         let reader = BufReader::new(input_file);
 
-        // Read the JSON contents of the file as an instance of `User`.
-        match serde_json::from_reader::<BufReader<&File>, User>(reader) {
+        // Read the JSON contents of the file as an instance of `SavedUser`.
+        match serde_json::from_reader::<BufReader<&File>, SavedUser>(reader) {
             Ok(user) => Ok(user),
             Err(e) => Result::Err(e.to_string()),
         }
     }
 
     pub fn load(user_name: String) -> Result<Self, String> {
-        let input_path = User::get_file_path(&user_name);
-
-        match File::open(input_path) {
-            Err(e) => {
-                log::error!("Error loading user state: {:?}", e.to_string());
-                Err(e.to_string())
-            }
-            Ok(input_file) => {
-                let user_result = User::load_from_file(&input_file);
-
-                if user_result.is_ok() {
-                    let mut user = user_result.ok().unwrap();
-                    match user.provider.load_keystore(user_name) {
-                        Ok(_) => {
-                            let groups = user.groups.get_mut();
-                            for group_name in &user.group_list {
-                                let mlsgroup = MlsGroup::load(
-                                    user.provider.storage(),
-                                    &GroupId::from_slice(group_name.as_bytes()),
-                                );
-                                let grp = Group {
-                                    mls_group: RefCell::new(mlsgroup.unwrap().unwrap()),
-                                    group_name: group_name.clone(),
-                                    conversation: Conversation::default(),
-                                };
-                                groups.insert(group_name.clone(), grp);
-                            }
-                            Ok(user)
-                        }
-                        Err(e) => Err(e),
-                    }
-                } else {
-                    user_result
-                }
-            }
+        let input_path = User::get_file_path(&user_name)?;
+
+        let input_file = File::open(input_path).map_err(|e| {
+            log::error!("Error loading user state: {:?}", e.to_string());
+            e.to_string()
+        })?;
+        let saved_user = User::load_from_file(&input_file)?;
+        let provider = OpenMlsRustPersistentCrypto::open(&user_name)?;
+
+        let mut groups = HashMap::new();
+        for group_name in &saved_user.group_list {
+            let mlsgroup = MlsGroup::load(
+                provider.storage(),
+                &GroupId::from_slice(group_name.as_bytes()),
+            )
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("The group {group_name:?} is missing in the key store"))?;
+            let grp = Group {
+                mls_group: RefCell::new(mlsgroup),
+                group_name: group_name.clone(),
+                conversation: Conversation::default(),
+            };
+            groups.insert(group_name.clone(), grp);
         }
+
+        Ok(Self {
+            contacts: saved_user.contacts,
+            groups: RefCell::new(groups),
+            group_list: saved_user.group_list,
+            identity: saved_user.identity,
+            backend: Backend::default(),
+            provider,
+            autosave_enabled: saved_user.autosave_enabled,
+            auth_token: saved_user.auth_token,
+        })
     }
 
     fn save_to_file(&self, output_file: &File) {
@@ -142,16 +149,19 @@ impl User {
     }
 
     pub fn save(&mut self) {
-        let output_path = User::get_file_path(&self.identity.borrow().identity_as_string());
+        let output_path = match User::get_file_path(&self.identity.borrow().identity_as_string()) {
+            Ok(path) => path,
+            Err(e) => {
+                log::error!("Error saving user state: {:?}", e);
+                return;
+            }
+        };
         match File::create(output_path) {
             Err(e) => log::error!("Error saving user state: {:?}", e.to_string()),
             Ok(output_file) => {
+                // The key store is written to its file on every change.
                 self.save_to_file(&output_file);
-
-                match self.provider.save_keystore(self.username()) {
-                    Ok(_) => log::info!("User state saved"),
-                    Err(e) => log::error!("Error saving user state : {:?}", e.to_string()),
-                }
+                log::info!("User state saved");
             }
         }
     }
@@ -383,16 +393,17 @@ impl User {
         };
         let mut mls_group = group.mls_group.borrow_mut();
 
-        processed_message = match mls_group.process_message(&self.provider, message) {
-            Ok(msg) => msg,
-            Err(e) => {
-                log::error!(
-                    "Error processing unverified message: {:?} -  Dropping message.",
-                    e
-                );
-                return Err("error".to_string());
-            }
-        };
+        processed_message =
+            match mls_group.process_message(&self.provider, message, &CliAuthenticationService) {
+                Ok(msg) => msg,
+                Err(e) => {
+                    log::error!(
+                        "Error processing unverified message: {:?} -  Dropping message.",
+                        e
+                    );
+                    return Err("error".to_string());
+                }
+            };
 
         let processed_message_credential: Credential = processed_message.credential().clone();
 
@@ -702,11 +713,16 @@ impl User {
         let group_config = MlsGroupJoinConfig::builder()
             .use_ratchet_tree_extension(true)
             .build();
-        let mls_group =
-            StagedWelcome::new_from_welcome(&self.provider, &group_config, welcome, None)
-                .expect("Failed to create staged join")
-                .into_group(&self.provider)
-                .expect("Failed to create MlsGroup");
+        let mls_group = StagedWelcome::new_from_welcome(
+            &self.provider,
+            &group_config,
+            welcome,
+            None,
+            &CliAuthenticationService,
+        )
+        .expect("Failed to create staged join")
+        .into_group(&self.provider)
+        .expect("Failed to create MlsGroup");
 
         let group_id = mls_group.group_id().to_vec();
         // XXX: Use Welcome's encrypted_group_info field to store group_name.
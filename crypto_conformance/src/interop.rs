@@ -0,0 +1,247 @@
+//! Interoperability with a reference provider.
+//!
+//! Each check runs an operation with one provider and its counterpart with
+//! the other one, in both directions.
+
+use openmls_traits::{crypto::OpenMlsCrypto, random::OpenMlsRand, types::Ciphersuite};
+
+use crate::{expect_eq, expect_ok, random_vec, CheckResult};
+
+pub(crate) fn hash(
+    crypto: &impl OpenMlsCrypto,
+    reference: &impl OpenMlsCrypto,
+    ciphersuite: Ciphersuite,
+) -> CheckResult {
+    let hash = ciphersuite.hash_algorithm();
+    for data in [&b""[..], &b"abc"[..], &[0x5a; 1000][..]] {
+        expect_eq(
+            expect_ok(crypto.hash(hash, data), "hash")?,
+            expect_ok(reference.hash(hash, data), "reference hash")?,
+            "hash",
+        )?;
+    }
+    Ok(())
+}
+
+pub(crate) fn hkdf(
+    crypto: &impl OpenMlsCrypto,
+    reference: &impl OpenMlsCrypto,
+    ciphersuite: Ciphersuite,
+) -> CheckResult {
+    let hash = ciphersuite.hash_algorithm();
+    let (salt, ikm, info) = (b"salt", b"input keying material", b"info");
+
+    let prk = expect_ok(crypto.hkdf_extract(hash, salt, ikm), "hkdf_extract")?;
+    let reference_prk = expect_ok(
+        reference.hkdf_extract(hash, salt, ikm),
+        "reference hkdf_extract",
+    )?;
+    expect_eq(prk.as_slice(), reference_prk.as_slice(), "hkdf_extract")?;
+
+    for length in [1, hash.size(), 3 * hash.size() + 1] {
+        let okm = expect_ok(
+            crypto.hkdf_expand(hash, prk.as_slice(), info, length),
+            "hkdf_expand",
+        )?;
+        let reference_okm = expect_ok(
+            reference.hkdf_expand(hash, prk.as_slice(), info, length),
+            "reference hkdf_expand",
+        )?;
+        expect_eq(okm.as_slice(), reference_okm.as_slice(), "hkdf_expand")?;
+    }
+    Ok(())
+}
+
+pub(crate) fn aead(
+    crypto: &impl OpenMlsCrypto,
+    reference: &impl OpenMlsCrypto,
+    rand: &impl OpenMlsRand,
+    ciphersuite: Ciphersuite,
+) -> CheckResult {
+    let alg = ciphersuite.aead_algorithm();
+    let key = random_vec(rand, alg.key_size())?;
+    let nonce = random_vec(rand, alg.nonce_size())?;
+    let (aad, plaintext) = (b"associated data", b"plaintext");
+
+    let ciphertext = expect_ok(
+        crypto.aead_encrypt(alg, &key, plaintext, &nonce, aad),
+        "aead_encrypt",
+    )?;
+    let reference_ciphertext = expect_ok(
+        reference.aead_encrypt(alg, &key, plaintext, &nonce, aad),
+        "reference aead_encrypt",
+    )?;
+    expect_eq(&ciphertext, &reference_ciphertext, "aead_encrypt")?;
+
+    let decrypted = expect_ok(
+        reference.aead_decrypt(alg, &key, &ciphertext, &nonce, aad),
+        "reference aead_decrypt",
+    )?;
+    expect_eq(
+        decrypted.as_slice(),
+        plaintext.as_slice(),
+        "decrypted plaintext",
+    )?;
+    let decrypted = expect_ok(
+        crypto.aead_decrypt(alg, &key, &reference_ciphertext, &nonce, aad),
+        "aead_decrypt",
+    )?;
+    expect_eq(
+        decrypted.as_slice(),
+        plaintext.as_slice(),
+        "decrypted plaintext",
+    )
+}
+
+pub(crate) fn hpke(
+    crypto: &impl OpenMlsCrypto,
+    reference: &impl OpenMlsCrypto,
+    rand: &impl OpenMlsRand,
+    ciphersuite: Ciphersuite,
+) -> CheckResult {
+    // DeriveKeyPair is deterministic, so both providers derive the same public
+    // key. The private keys are only used by the provider that derived them.
+    let ikm = random_vec(rand, ciphersuite.hash_length())?;
+    let key_pair = expect_ok(
+        crypto.derive_hpke_keypair(ciphersuite.hpke_config(), &ikm),
+        "derive_hpke_keypair",
+    )?;
+    let reference_key_pair = expect_ok(
+        reference.derive_hpke_keypair(ciphersuite.hpke_config(), &ikm),
+        "reference derive_hpke_keypair",
+    )?;
+    expect_eq(
+        &key_pair.public,
+        &reference_key_pair.public,
+        "derive_hpke_keypair",
+    )?;
+
+    let (info, aad, plaintext) = (b"info", b"aad", b"plaintext");
+    let ciphertext = expect_ok(
+        crypto.hpke_seal(
+            ciphersuite.hpke_config(),
+            &key_pair.public,
+            info,
+            aad,
+            plaintext,
+        ),
+        "hpke_seal",
+    )?;
+    let decrypted = expect_ok(
+        reference.hpke_open(
+            ciphersuite.hpke_config(),
+            &ciphertext,
+            &reference_key_pair.private,
+            info,
+            aad,
+        ),
+        "reference hpke_open",
+    )?;
+    expect_eq(
+        decrypted.as_slice(),
+        plaintext.as_slice(),
+        "decrypted plaintext",
+    )?;
+
+    let reference_ciphertext = expect_ok(
+        reference.hpke_seal(
+            ciphersuite.hpke_config(),
+            &key_pair.public,
+            info,
+            aad,
+            plaintext,
+        ),
+        "reference hpke_seal",
+    )?;
+    let decrypted = expect_ok(
+        crypto.hpke_open(
+            ciphersuite.hpke_config(),
+            &reference_ciphertext,
+            &key_pair.private,
+            info,
+            aad,
+        ),
+        "hpke_open",
+    )?;
+    expect_eq(
+        decrypted.as_slice(),
+        plaintext.as_slice(),
+        "decrypted plaintext",
+    )?;
+
+    let (exporter_context, length) = (b"exporter context", ciphersuite.hash_length());
+    let (enc, secret) = expect_ok(
+        crypto.hpke_setup_sender_and_export(
+            ciphersuite.hpke_config(),
+            &key_pair.public,
+            info,
+            exporter_context,
+            length,
+        ),
+        "hpke_setup_sender_and_export",
+    )?;
+    let reference_secret = expect_ok(
+        reference.hpke_setup_receiver_and_export(
+            ciphersuite.hpke_config(),
+            &enc,
+            &reference_key_pair.private,
+            info,
+            exporter_context,
+            length,
+        ),
+        "reference hpke_setup_receiver_and_export",
+    )?;
+    expect_eq(&*secret, &*reference_secret, "exported secret")?;
+
+    let (reference_enc, reference_secret) = expect_ok(
+        reference.hpke_setup_sender_and_export(
+            ciphersuite.hpke_config(),
+            &key_pair.public,
+            info,
+            exporter_context,
+            length,
+        ),
+        "reference hpke_setup_sender_and_export",
+    )?;
+    let secret = expect_ok(
+        crypto.hpke_setup_receiver_and_export(
+            ciphersuite.hpke_config(),
+            &reference_enc,
+            &key_pair.private,
+            info,
+            exporter_context,
+            length,
+        ),
+        "hpke_setup_receiver_and_export",
+    )?;
+    expect_eq(&*secret, &*reference_secret, "exported secret")
+}
+
+pub(crate) fn signature(
+    crypto: &impl OpenMlsCrypto,
+    reference: &impl OpenMlsCrypto,
+    ciphersuite: Ciphersuite,
+) -> CheckResult {
+    let alg = ciphersuite.signature_algorithm();
+    let data = b"data";
+
+    let (private, public) = expect_ok(crypto.signature_key_gen(alg), "signature_key_gen")?;
+    let signature = expect_ok(crypto.sign(alg, data, &private), "sign")?;
+    expect_ok(
+        reference.verify_signature(alg, data, &public, &signature),
+        "reference verify_signature",
+    )?;
+
+    let (reference_private, reference_public) = expect_ok(
+        reference.signature_key_gen(alg),
+        "reference signature_key_gen",
+    )?;
+    let reference_signature = expect_ok(
+        reference.sign(alg, data, &reference_private),
+        "reference sign",
+    )?;
+    expect_ok(
+        crypto.verify_signature(alg, data, &reference_public, &reference_signature),
+        "verify_signature",
+    )
+}
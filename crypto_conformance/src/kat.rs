@@ -0,0 +1,255 @@
+//! The crypto basics test vectors of the [MLS test vectors].
+//!
+//! [MLS test vectors]: https://github.com/mlswg/mls-implementations/blob/main/test-vectors.md
+
+use openmls_traits::{
+    crypto::OpenMlsCrypto,
+    types::{Ciphersuite, HpkeCiphertext},
+};
+use serde::Deserialize;
+
+use crate::{expect_eq, expect_ok, labeled, CheckResult};
+
+// A copy of `openmls/test_vectors/crypto-basics.json`. The file has to be in
+// this crate to be packaged with it.
+const CRYPTO_BASICS: &str = include_str!("../test_vectors/crypto-basics.json");
+
+#[derive(Deserialize)]
+struct RefHash {
+    label: String,
+    value: String,
+    out: String,
+}
+
+#[derive(Deserialize)]
+struct ExpandWithLabel {
+    secret: String,
+    label: String,
+    context: String,
+    length: u16,
+    out: String,
+}
+
+#[derive(Deserialize)]
+struct DeriveSecret {
+    secret: String,
+    label: String,
+    out: String,
+}
+
+#[derive(Deserialize)]
+struct DeriveTreeSecret {
+    secret: String,
+    label: String,
+    generation: u32,
+    length: u16,
+    out: String,
+}
+
+#[derive(Deserialize)]
+struct SignWithLabel {
+    r#priv: String,
+    r#pub: String,
+    content: String,
+    label: String,
+    signature: String,
+}
+
+#[derive(Deserialize)]
+struct EncryptWithLabel {
+    r#priv: String,
+    r#pub: String,
+    label: String,
+    context: String,
+    plaintext: String,
+    kem_output: String,
+    ciphertext: String,
+}
+
+#[derive(Deserialize)]
+struct CryptoBasicsTestCase {
+    cipher_suite: u16,
+    ref_hash: RefHash,
+    expand_with_label: ExpandWithLabel,
+    derive_secret: DeriveSecret,
+    derive_tree_secret: DeriveTreeSecret,
+    sign_with_label: SignWithLabel,
+    encrypt_with_label: EncryptWithLabel,
+}
+
+fn hex(value: &str) -> Result<Vec<u8>, String> {
+    hex::decode(value).map_err(|e| format!("invalid hex in the test vector: {e}"))
+}
+
+/// Checks the crypto basics test vector of the `ciphersuite`, if there is
+/// one.
+pub(crate) fn crypto_basics(crypto: &impl OpenMlsCrypto, ciphersuite: Ciphersuite) -> CheckResult {
+    let tests: Vec<CryptoBasicsTestCase> = serde_json::from_str(CRYPTO_BASICS)
+        .map_err(|e| format!("error parsing the test vectors: {e}"))?;
+    let Some(test) = tests
+        .into_iter()
+        .find(|test| test.cipher_suite == u16::from(ciphersuite))
+    else {
+        // There are no vectors for this ciphersuite.
+        return Ok(());
+    };
+
+    // ref_hash
+    {
+        let test = test.ref_hash;
+        let out = expect_ok(
+            labeled::ref_hash(crypto, ciphersuite, &test.label, &hex(&test.value)?),
+            "RefHash",
+        )?;
+        expect_eq(out, hex(&test.out)?, "RefHash")?;
+    }
+
+    // expand_with_label
+    {
+        let test = test.expand_with_label;
+        let out = expect_ok(
+            labeled::expand_with_label(
+                crypto,
+                ciphersuite,
+                &hex(&test.secret)?,
+                &test.label,
+                &hex(&test.context)?,
+                test.length,
+            ),
+            "ExpandWithLabel",
+        )?;
+        expect_eq(out, hex(&test.out)?, "ExpandWithLabel")?;
+    }
+
+    // derive_secret
+    {
+        let test = test.derive_secret;
+        let out = expect_ok(
+            labeled::derive_secret(crypto, ciphersuite, &hex(&test.secret)?, &test.label),
+            "DeriveSecret",
+        )?;
+        expect_eq(out, hex(&test.out)?, "DeriveSecret")?;
+    }
+
+    // derive_tree_secret
+    {
+        let test = test.derive_tree_secret;
+        let out = expect_ok(
+            labeled::derive_tree_secret(
+                crypto,
+                ciphersuite,
+                &hex(&test.secret)?,
+                &test.label,
+                test.generation,
+                test.length,
+            ),
+            "DeriveTreeSecret",
+        )?;
+        expect_eq(out, hex(&test.out)?, "DeriveTreeSecret")?;
+    }
+
+    // sign_with_label
+    {
+        let test = test.sign_with_label;
+        let private = hex(&test.r#priv)?;
+        let public = hex(&test.r#pub)?;
+        let content = hex(&test.content)?;
+
+        // The P-521 and P-384 vectors are signed over a SHA-256 digest rather
+        // than one with the scheme's hash, so only the round trip below
+        // applies to them.
+        if !matches!(
+            ciphersuite,
+            Ciphersuite::MLS_256_DHKEMP521_AES256GCM_SHA512_P521
+                | Ciphersuite::MLS_256_DHKEMP384_AES256GCM_SHA384_P384
+        ) {
+            expect_ok(
+                labeled::verify_with_label(
+                    crypto,
+                    ciphersuite,
+                    &public,
+                    &test.label,
+                    &content,
+                    &hex(&test.signature)?,
+                ),
+                "VerifyWithLabel of the test vector signature",
+            )?;
+        }
+
+        let signature = expect_ok(
+            labeled::sign_with_label(crypto, ciphersuite, &private, &test.label, &content),
+            "SignWithLabel",
+        )?;
+        expect_ok(
+            labeled::verify_with_label(
+                crypto,
+                ciphersuite,
+                &public,
+                &test.label,
+                &content,
+                &signature,
+            ),
+            "VerifyWithLabel of our own signature",
+        )?;
+    }
+
+    // encrypt_with_label
+    {
+        let test = test.encrypt_with_label;
+        let private = hex(&test.r#priv)?;
+        let public = hex(&test.r#pub)?;
+        let context = hex(&test.context)?;
+        let plaintext = hex(&test.plaintext)?;
+
+        let ciphertext = HpkeCiphertext {
+            kem_output: hex(&test.kem_output)?.into(),
+            ciphertext: hex(&test.ciphertext)?.into(),
+        };
+        let decrypted = expect_ok(
+            labeled::decrypt_with_label(
+                crypto,
+                ciphersuite,
+                &private,
+                &test.label,
+                &context,
+                &ciphertext,
+            ),
+            "DecryptWithLabel of the test vector ciphertext",
+        )?;
+        expect_eq(
+            &decrypted,
+            &plaintext,
+            "DecryptWithLabel of the test vector ciphertext",
+        )?;
+
+        let ciphertext = expect_ok(
+            labeled::encrypt_with_label(
+                crypto,
+                ciphersuite,
+                &public,
+                &test.label,
+                &context,
+                &plaintext,
+            ),
+            "EncryptWithLabel",
+        )?;
+        let decrypted = expect_ok(
+            labeled::decrypt_with_label(
+                crypto,
+                ciphersuite,
+                &private,
+                &test.label,
+                &context,
+                &ciphertext,
+            ),
+            "DecryptWithLabel of our own ciphertext",
+        )?;
+        expect_eq(
+            &decrypted,
+            &plaintext,
+            "DecryptWithLabel of our own ciphertext",
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,194 @@
+//! The labeled functions of [RFC 9420], Section 5, on top of a crypto provider.
+//!
+//! [RFC 9420]: https://www.rfc-editor.org/rfc/rfc9420.html#section-5
+
+use openmls_traits::{
+    crypto::OpenMlsCrypto,
+    types::{Ciphersuite, CryptoError, HpkeCiphertext},
+};
+use tls_codec::{Serialize, TlsSerialize, TlsSize, VLBytes};
+
+/// The prefix of all labels.
+const LABEL_PREFIX: &str = "MLS 1.0 ";
+
+#[derive(TlsSerialize, TlsSize)]
+struct RefHashInput {
+    label: VLBytes,
+    value: VLBytes,
+}
+
+#[derive(TlsSerialize, TlsSize)]
+struct KdfLabel {
+    length: u16,
+    label: VLBytes,
+    context: VLBytes,
+}
+
+#[derive(TlsSerialize, TlsSize)]
+struct SignContent {
+    label: VLBytes,
+    content: VLBytes,
+}
+
+#[derive(TlsSerialize, TlsSize)]
+struct EncryptContext {
+    label: VLBytes,
+    context: VLBytes,
+}
+
+fn serialize(value: &impl Serialize) -> Result<Vec<u8>, CryptoError> {
+    value
+        .tls_serialize_detached()
+        .map_err(|_| CryptoError::TlsSerializationError)
+}
+
+fn prefixed_label(label: &str) -> VLBytes {
+    format!("{LABEL_PREFIX}{label}").into_bytes().into()
+}
+
+/// `RefHash(label, value)`. The label isn't prefixed.
+pub(crate) fn ref_hash(
+    crypto: &impl OpenMlsCrypto,
+    ciphersuite: Ciphersuite,
+    label: &str,
+    value: &[u8],
+) -> Result<Vec<u8>, CryptoError> {
+    let input = serialize(&RefHashInput {
+        label: label.as_bytes().into(),
+        value: value.into(),
+    })?;
+    crypto.hash(ciphersuite.hash_algorithm(), &input)
+}
+
+/// `ExpandWithLabel(secret, label, context, length)`.
+pub(crate) fn expand_with_label(
+    crypto: &impl OpenMlsCrypto,
+    ciphersuite: Ciphersuite,
+    secret: &[u8],
+    label: &str,
+    context: &[u8],
+    length: u16,
+) -> Result<Vec<u8>, CryptoError> {
+    let info = serialize(&KdfLabel {
+        length,
+        label: prefixed_label(label),
+        context: context.into(),
+    })?;
+    Ok(crypto
+        .hkdf_expand(ciphersuite.hash_algorithm(), secret, &info, length.into())?
+        .as_slice()
+        .to_vec())
+}
+
+/// `DeriveSecret(secret, label)`.
+pub(crate) fn derive_secret(
+    crypto: &impl OpenMlsCrypto,
+    ciphersuite: Ciphersuite,
+    secret: &[u8],
+    label: &str,
+) -> Result<Vec<u8>, CryptoError> {
+    let length =
+        u16::try_from(ciphersuite.hash_length()).map_err(|_| CryptoError::InvalidLength)?;
+    expand_with_label(crypto, ciphersuite, secret, label, &[], length)
+}
+
+/// `DeriveTreeSecret(secret, label, generation, length)`.
+pub(crate) fn derive_tree_secret(
+    crypto: &impl OpenMlsCrypto,
+    ciphersuite: Ciphersuite,
+    secret: &[u8],
+    label: &str,
+    generation: u32,
+    length: u16,
+) -> Result<Vec<u8>, CryptoError> {
+    expand_with_label(
+        crypto,
+        ciphersuite,
+        secret,
+        label,
+        &generation.to_be_bytes(),
+        length,
+    )
+}
+
+fn sign_content(label: &str, content: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    serialize(&SignContent {
+        label: prefixed_label(label),
+        content: content.into(),
+    })
+}
+
+/// `SignWithLabel(private_key, label, content)`.
+pub(crate) fn sign_with_label(
+    crypto: &impl OpenMlsCrypto,
+    ciphersuite: Ciphersuite,
+    private_key: &[u8],
+    label: &str,
+    content: &[u8],
+) -> Result<Vec<u8>, CryptoError> {
+    crypto.sign(
+        ciphersuite.signature_algorithm(),
+        &sign_content(label, content)?,
+        private_key,
+    )
+}
+
+/// `VerifyWithLabel(public_key, label, content, signature)`.
+pub(crate) fn verify_with_label(
+    crypto: &impl OpenMlsCrypto,
+    ciphersuite: Ciphersuite,
+    public_key: &[u8],
+    label: &str,
+    content: &[u8],
+    signature: &[u8],
+) -> Result<(), CryptoError> {
+    crypto.verify_signature(
+        ciphersuite.signature_algorithm(),
+        &sign_content(label, content)?,
+        public_key,
+        signature,
+    )
+}
+
+fn encrypt_context(label: &str, context: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    serialize(&EncryptContext {
+        label: prefixed_label(label),
+        context: context.into(),
+    })
+}
+
+/// `EncryptWithLabel(public_key, label, context, plaintext)`.
+pub(crate) fn encrypt_with_label(
+    crypto: &impl OpenMlsCrypto,
+    ciphersuite: Ciphersuite,
+    public_key: &[u8],
+    label: &str,
+    context: &[u8],
+    plaintext: &[u8],
+) -> Result<HpkeCiphertext, CryptoError> {
+    crypto.hpke_seal(
+        ciphersuite.hpke_config(),
+        public_key,
+        &encrypt_context(label, context)?,
+        &[],
+        plaintext,
+    )
+}
+
+/// `DecryptWithLabel(private_key, label, context, kem_output, ciphertext)`.
+pub(crate) fn decrypt_with_label(
+    crypto: &impl OpenMlsCrypto,
+    ciphersuite: Ciphersuite,
+    private_key: &[u8],
+    label: &str,
+    context: &[u8],
+    ciphertext: &HpkeCiphertext,
+) -> Result<Vec<u8>, CryptoError> {
+    crypto.hpke_open(
+        ciphersuite.hpke_config(),
+        ciphertext,
+        private_key,
+        &encrypt_context(label, context)?,
+        &[],
+    )
+}
@@ -0,0 +1,193 @@
+//! # OpenMLS Crypto Conformance
+//!
+//! Conformance tests for implementations of the [`OpenMlsCrypto`] and
+//! [`OpenMlsRand`] traits.
+//!
+//! [`check`] runs all tests against every ciphersuite the provider reports in
+//! [`OpenMlsCrypto::supported_ciphersuites`] and collects the failures in a
+//! [`Report`]. The tests cover
+//!
+//! * the crypto basics test vectors of the MLS test vectors,
+//! * round trips and error paths of the HKDF, AEAD, HPKE and signature
+//!   functions,
+//! * interoperability with the RustCrypto provider.
+//!
+//! ```
+//! let provider = openmls_rust_crypto::OpenMlsRustCrypto::default();
+//! openmls_crypto_conformance::check(&provider).assert_ok();
+//! ```
+
+use std::fmt::{Display, Formatter};
+
+use openmls_rust_crypto::OpenMlsRustCrypto;
+use openmls_traits::{
+    crypto::OpenMlsCrypto, random::OpenMlsRand, types::Ciphersuite, OpenMlsProvider,
+};
+
+mod interop;
+mod kat;
+mod labeled;
+mod primitives;
+
+/// The result of a single check.
+type CheckResult = Result<(), String>;
+
+/// A check that failed for a ciphersuite.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    /// The ciphersuite the check failed for.
+    pub ciphersuite: Ciphersuite,
+    /// The name of the check.
+    pub check: &'static str,
+    /// Why the check failed.
+    pub reason: String,
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}: {}: {}", self.ciphersuite, self.check, self.reason)
+    }
+}
+
+/// The outcome of running the conformance tests against a provider.
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    /// The ciphersuites that were tested.
+    pub ciphersuites: Vec<Ciphersuite>,
+    /// The checks that failed.
+    pub failures: Vec<Failure>,
+}
+
+impl Report {
+    /// Returns `true` if all checks passed.
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Panics with the list of failures if a check failed.
+    pub fn assert_ok(&self) {
+        if !self.is_ok() {
+            let failures = self
+                .failures
+                .iter()
+                .map(|failure| format!("  {failure}"))
+                .collect::<Vec<_>>()
+                .join("\n");
+            panic!(
+                "{} of the conformance checks failed:\n{failures}",
+                self.failures.len()
+            );
+        }
+    }
+
+    fn record(&mut self, ciphersuite: Ciphersuite, check: &'static str, result: CheckResult) {
+        if let Err(reason) = result {
+            self.failures.push(Failure {
+                ciphersuite,
+                check,
+                reason,
+            });
+        }
+    }
+}
+
+/// Runs the conformance tests against the `provider`.
+///
+/// Every ciphersuite in [`OpenMlsCrypto::supported_ciphersuites`] is tested.
+/// Interoperability is only checked for ciphersuites the RustCrypto provider
+/// supports as well.
+pub fn check(provider: &impl OpenMlsProvider) -> Report {
+    let crypto = provider.crypto();
+    let rand = provider.rand();
+    let reference = OpenMlsRustCrypto::default();
+    let reference = reference.crypto();
+
+    let mut report = Report {
+        ciphersuites: crypto.supported_ciphersuites(),
+        ..Default::default()
+    };
+    for ciphersuite in report.ciphersuites.clone() {
+        let mut record = |check, result| report.record(ciphersuite, check, result);
+
+        record(
+            "supports",
+            crypto
+                .supports(ciphersuite)
+                .map_err(|e| format!("listed as supported, but supports returned {e:?}")),
+        );
+        record("rand", primitives::rand(rand));
+        record("crypto-basics", kat::crypto_basics(crypto, ciphersuite));
+        record("hkdf", primitives::hkdf(crypto, ciphersuite));
+        record("aead", primitives::aead(crypto, rand, ciphersuite));
+        record("hpke", primitives::hpke(crypto, rand, ciphersuite));
+        record(
+            "hpke-export",
+            primitives::hpke_export(crypto, rand, ciphersuite),
+        );
+        record("signature", primitives::signature(crypto, ciphersuite));
+
+        if reference.supports(ciphersuite).is_ok() {
+            record(
+                "interop-hash",
+                interop::hash(crypto, reference, ciphersuite),
+            );
+            record(
+                "interop-hkdf",
+                interop::hkdf(crypto, reference, ciphersuite),
+            );
+            record(
+                "interop-aead",
+                interop::aead(crypto, reference, rand, ciphersuite),
+            );
+            record(
+                "interop-hpke",
+                interop::hpke(crypto, reference, rand, ciphersuite),
+            );
+            record(
+                "interop-signature",
+                interop::signature(crypto, reference, ciphersuite),
+            );
+        }
+    }
+
+    report
+}
+
+/// Returns an error describing the failed `operation` if `result` is an error.
+fn expect_ok<T, E: std::fmt::Debug>(result: Result<T, E>, operation: &str) -> Result<T, String> {
+    result.map_err(|e| format!("{operation} failed: {e:?}"))
+}
+
+/// Returns an error if `result` isn't an error. `operation` describes what
+/// should have failed.
+fn expect_err<T, E>(result: Result<T, E>, operation: &str) -> CheckResult {
+    match result {
+        Ok(_) => Err(format!("{operation} succeeded, but should have failed")),
+        Err(_) => Ok(()),
+    }
+}
+
+/// Returns an error with the `message` if the values differ.
+fn expect_eq<T: PartialEq + std::fmt::Debug>(actual: T, expected: T, message: &str) -> CheckResult {
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(format!(
+            "{message}: expected {expected:x?}, got {actual:x?}"
+        ))
+    }
+}
+
+/// Returns a copy of the `bytes` with the last byte flipped.
+fn flip_last_byte(bytes: &[u8]) -> Vec<u8> {
+    let mut bytes = bytes.to_vec();
+    if let Some(last) = bytes.last_mut() {
+        *last ^= 0xff;
+    }
+    bytes
+}
+
+/// Returns `len` random bytes.
+fn random_vec(rand: &impl OpenMlsRand, len: usize) -> Result<Vec<u8>, String> {
+    expect_ok(rand.random_vec(len), "random_vec")
+}
@@ -0,0 +1,358 @@
+//! Round trips and error paths of the individual primitives.
+
+use openmls_traits::{
+    crypto::OpenMlsCrypto,
+    random::OpenMlsRand,
+    types::{Ciphersuite, HashType, HpkeCiphertext},
+};
+
+use crate::{expect_eq, expect_err, expect_ok, flip_last_byte, random_vec, CheckResult};
+
+/// Test case 1 of [RFC 5869], Appendix A, for HKDF-SHA256.
+///
+/// [RFC 5869]: https://www.rfc-editor.org/rfc/rfc5869.html#appendix-A.1
+mod rfc5869 {
+    pub(super) const IKM: [u8; 22] = [0x0b; 22];
+    pub(super) const SALT: &str = "000102030405060708090a0b0c";
+    pub(super) const INFO: &str = "f0f1f2f3f4f5f6f7f8f9";
+    pub(super) const PRK: &str = "077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5";
+    pub(super) const OKM: &str =
+        "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865";
+}
+
+fn hex(value: &str) -> Vec<u8> {
+    hex::decode(value).expect("invalid hex constant")
+}
+
+pub(crate) fn rand(rand: &impl OpenMlsRand) -> CheckResult {
+    for len in [1, 32, 1000] {
+        expect_eq(random_vec(rand, len)?.len(), len, "length of random_vec")?;
+    }
+    let first: [u8; 32] = expect_ok(rand.random_array(), "random_array")?;
+    let second: [u8; 32] = expect_ok(rand.random_array(), "random_array")?;
+    if first == second {
+        return Err("two calls to random_array returned the same bytes".to_string());
+    }
+    Ok(())
+}
+
+pub(crate) fn hkdf(crypto: &impl OpenMlsCrypto, ciphersuite: Ciphersuite) -> CheckResult {
+    let hash = ciphersuite.hash_algorithm();
+
+    if hash == HashType::Sha2_256 {
+        let prk = expect_ok(
+            crypto.hkdf_extract(hash, &hex(rfc5869::SALT), &rfc5869::IKM),
+            "hkdf_extract",
+        )?;
+        expect_eq(prk.as_slice(), hex(rfc5869::PRK).as_slice(), "RFC 5869 PRK")?;
+        let okm = expect_ok(
+            crypto.hkdf_expand(hash, prk.as_slice(), &hex(rfc5869::INFO), 42),
+            "hkdf_expand",
+        )?;
+        expect_eq(okm.as_slice(), hex(rfc5869::OKM).as_slice(), "RFC 5869 OKM")?;
+    }
+
+    let prk = expect_ok(crypto.hkdf_extract(hash, b"salt", b"ikm"), "hkdf_extract")?;
+    expect_eq(prk.as_slice().len(), hash.size(), "length of the PRK")?;
+
+    // The output of HKDF-Expand is limited to 255 hash lengths.
+    let max_length = 255 * hash.size();
+    let okm = expect_ok(
+        crypto.hkdf_expand(hash, prk.as_slice(), b"info", max_length),
+        "hkdf_expand with the maximum length",
+    )?;
+    expect_eq(okm.as_slice().len(), max_length, "length of the OKM")?;
+    expect_err(
+        crypto.hkdf_expand(hash, prk.as_slice(), b"info", max_length + 1),
+        "hkdf_expand with more than the maximum length",
+    )
+}
+
+pub(crate) fn aead(
+    crypto: &impl OpenMlsCrypto,
+    rand: &impl OpenMlsRand,
+    ciphersuite: Ciphersuite,
+) -> CheckResult {
+    let alg = ciphersuite.aead_algorithm();
+    let key = random_vec(rand, alg.key_size())?;
+    let nonce = random_vec(rand, alg.nonce_size())?;
+    let aad = b"associated data";
+    let plaintext = b"plaintext";
+
+    let ciphertext = expect_ok(
+        crypto.aead_encrypt(alg, &key, plaintext, &nonce, aad),
+        "aead_encrypt",
+    )?;
+    expect_eq(
+        ciphertext.len(),
+        plaintext.len() + alg.tag_size(),
+        "length of the ciphertext",
+    )?;
+    let decrypted = expect_ok(
+        crypto.aead_decrypt(alg, &key, &ciphertext, &nonce, aad),
+        "aead_decrypt",
+    )?;
+    expect_eq(
+        decrypted.as_slice(),
+        plaintext.as_slice(),
+        "decrypted plaintext",
+    )?;
+
+    // An empty plaintext only has the tag.
+    let ciphertext_of_empty = expect_ok(
+        crypto.aead_encrypt(alg, &key, &[], &nonce, aad),
+        "aead_encrypt of an empty plaintext",
+    )?;
+    expect_eq(
+        ciphertext_of_empty.len(),
+        alg.tag_size(),
+        "length of the ciphertext of an empty plaintext",
+    )?;
+
+    expect_err(
+        crypto.aead_decrypt(alg, &key, &flip_last_byte(&ciphertext), &nonce, aad),
+        "aead_decrypt of a tampered ciphertext",
+    )?;
+    expect_err(
+        crypto.aead_decrypt(alg, &key, &ciphertext, &nonce, b"other data"),
+        "aead_decrypt with different associated data",
+    )?;
+    expect_err(
+        crypto.aead_decrypt(alg, &flip_last_byte(&key), &ciphertext, &nonce, aad),
+        "aead_decrypt with a different key",
+    )?;
+    expect_err(
+        crypto.aead_decrypt(alg, &key, &ciphertext[..alg.tag_size() - 1], &nonce, aad),
+        "aead_decrypt of a ciphertext shorter than the tag",
+    )?;
+    expect_err(
+        crypto.aead_encrypt(alg, &key[1..], plaintext, &nonce, aad),
+        "aead_encrypt with a short key",
+    )?;
+    expect_err(
+        crypto.aead_decrypt(alg, &key[1..], &ciphertext, &nonce, aad),
+        "aead_decrypt with a short key",
+    )
+}
+
+pub(crate) fn hpke(
+    crypto: &impl OpenMlsCrypto,
+    rand: &impl OpenMlsRand,
+    ciphersuite: Ciphersuite,
+) -> CheckResult {
+    let ikm = random_vec(rand, ciphersuite.hash_length())?;
+    let key_pair = expect_ok(
+        crypto.derive_hpke_keypair(ciphersuite.hpke_config(), &ikm),
+        "derive_hpke_keypair",
+    )?;
+    let same_key_pair = expect_ok(
+        crypto.derive_hpke_keypair(ciphersuite.hpke_config(), &ikm),
+        "derive_hpke_keypair",
+    )?;
+    expect_eq(
+        &same_key_pair.public,
+        &key_pair.public,
+        "public key derived from the same input",
+    )?;
+    let other_key_pair = expect_ok(
+        crypto.derive_hpke_keypair(ciphersuite.hpke_config(), &flip_last_byte(&ikm)),
+        "derive_hpke_keypair",
+    )?;
+    if other_key_pair.public == key_pair.public {
+        return Err("different inputs derived the same public key".to_string());
+    }
+
+    let (info, aad, plaintext) = (b"info", b"aad", b"plaintext");
+    let ciphertext = expect_ok(
+        crypto.hpke_seal(
+            ciphersuite.hpke_config(),
+            &key_pair.public,
+            info,
+            aad,
+            plaintext,
+        ),
+        "hpke_seal",
+    )?;
+    expect_eq(
+        ciphertext.kem_output.as_slice().len(),
+        ciphersuite.hpke_kem_algorithm().enc_length(),
+        "length of the KEM output",
+    )?;
+    let decrypted = expect_ok(
+        crypto.hpke_open(
+            ciphersuite.hpke_config(),
+            &ciphertext,
+            &key_pair.private,
+            info,
+            aad,
+        ),
+        "hpke_open",
+    )?;
+    expect_eq(
+        decrypted.as_slice(),
+        plaintext.as_slice(),
+        "decrypted plaintext",
+    )?;
+
+    let tampered_ciphertext = HpkeCiphertext {
+        kem_output: ciphertext.kem_output.clone(),
+        ciphertext: flip_last_byte(ciphertext.ciphertext.as_slice()).into(),
+    };
+    expect_err(
+        crypto.hpke_open(
+            ciphersuite.hpke_config(),
+            &tampered_ciphertext,
+            &key_pair.private,
+            info,
+            aad,
+        ),
+        "hpke_open of a tampered ciphertext",
+    )?;
+    let tampered_kem_output = HpkeCiphertext {
+        kem_output: flip_last_byte(ciphertext.kem_output.as_slice()).into(),
+        ciphertext: ciphertext.ciphertext.clone(),
+    };
+    expect_err(
+        crypto.hpke_open(
+            ciphersuite.hpke_config(),
+            &tampered_kem_output,
+            &key_pair.private,
+            info,
+            aad,
+        ),
+        "hpke_open of a tampered KEM output",
+    )?;
+    expect_err(
+        crypto.hpke_open(
+            ciphersuite.hpke_config(),
+            &ciphertext,
+            &other_key_pair.private,
+            info,
+            aad,
+        ),
+        "hpke_open with a different private key",
+    )?;
+    expect_err(
+        crypto.hpke_open(
+            ciphersuite.hpke_config(),
+            &ciphertext,
+            &key_pair.private,
+            b"other",
+            aad,
+        ),
+        "hpke_open with a different info",
+    )?;
+    expect_err(
+        crypto.hpke_open(
+            ciphersuite.hpke_config(),
+            &ciphertext,
+            &key_pair.private,
+            info,
+            b"other",
+        ),
+        "hpke_open with different associated data",
+    )?;
+    expect_err(
+        crypto.hpke_seal(
+            ciphersuite.hpke_config(),
+            &key_pair.public[1..],
+            info,
+            aad,
+            plaintext,
+        ),
+        "hpke_seal to a short public key",
+    )
+}
+
+pub(crate) fn hpke_export(
+    crypto: &impl OpenMlsCrypto,
+    rand: &impl OpenMlsRand,
+    ciphersuite: Ciphersuite,
+) -> CheckResult {
+    let ikm = random_vec(rand, ciphersuite.hash_length())?;
+    let key_pair = expect_ok(
+        crypto.derive_hpke_keypair(ciphersuite.hpke_config(), &ikm),
+        "derive_hpke_keypair",
+    )?;
+    let (info, exporter_context) = (b"info", b"exporter context");
+    let length = ciphersuite.hash_length();
+
+    let (enc, sender_secret) = expect_ok(
+        crypto.hpke_setup_sender_and_export(
+            ciphersuite.hpke_config(),
+            &key_pair.public,
+            info,
+            exporter_context,
+            length,
+        ),
+        "hpke_setup_sender_and_export",
+    )?;
+    expect_eq(
+        enc.len(),
+        ciphersuite.hpke_kem_algorithm().enc_length(),
+        "length of the KEM output",
+    )?;
+    expect_eq(sender_secret.len(), length, "length of the exported secret")?;
+
+    let receiver_secret = expect_ok(
+        crypto.hpke_setup_receiver_and_export(
+            ciphersuite.hpke_config(),
+            &enc,
+            &key_pair.private,
+            info,
+            exporter_context,
+            length,
+        ),
+        "hpke_setup_receiver_and_export",
+    )?;
+    expect_eq(
+        &*receiver_secret,
+        &*sender_secret,
+        "secret exported by the receiver",
+    )?;
+
+    let other_secret = expect_ok(
+        crypto.hpke_setup_receiver_and_export(
+            ciphersuite.hpke_config(),
+            &enc,
+            &key_pair.private,
+            info,
+            b"other context",
+            length,
+        ),
+        "hpke_setup_receiver_and_export",
+    )?;
+    if *other_secret == *sender_secret {
+        return Err("different exporter contexts exported the same secret".to_string());
+    }
+    Ok(())
+}
+
+pub(crate) fn signature(crypto: &impl OpenMlsCrypto, ciphersuite: Ciphersuite) -> CheckResult {
+    let alg = ciphersuite.signature_algorithm();
+    let (private, public) = expect_ok(crypto.signature_key_gen(alg), "signature_key_gen")?;
+    let (_, other_public) = expect_ok(crypto.signature_key_gen(alg), "signature_key_gen")?;
+    let data = b"data";
+
+    let signature = expect_ok(crypto.sign(alg, data, &private), "sign")?;
+    expect_ok(
+        crypto.verify_signature(alg, data, &public, &signature),
+        "verify_signature",
+    )?;
+    expect_err(
+        crypto.verify_signature(alg, data, &public, &flip_last_byte(&signature)),
+        "verify_signature of a tampered signature",
+    )?;
+    expect_err(
+        crypto.verify_signature(alg, b"other data", &public, &signature),
+        "verify_signature over different data",
+    )?;
+    expect_err(
+        crypto.verify_signature(alg, data, &other_public, &signature),
+        "verify_signature with a different public key",
+    )?;
+    expect_err(
+        crypto.verify_signature(alg, data, &public, &signature[..signature.len() - 1]),
+        "verify_signature of a truncated signature",
+    )
+}
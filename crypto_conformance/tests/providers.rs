@@ -0,0 +1,21 @@
+//! Runs the conformance tests against the providers in this repository.
+
+#[test]
+fn rust_crypto() {
+    let provider = openmls_rust_crypto::OpenMlsRustCrypto::default();
+    let report = openmls_crypto_conformance::check(&provider);
+    assert!(!report.ciphersuites.is_empty());
+    report.assert_ok();
+}
+
+#[cfg(not(any(
+    target_arch = "wasm32",
+    all(target_arch = "x86", target_os = "windows")
+)))]
+#[test]
+fn libcrux() {
+    let provider = openmls_libcrux_crypto::Provider::default();
+    let report = openmls_crypto_conformance::check(&provider);
+    assert!(!report.ciphersuites.is_empty());
+    report.assert_ok();
+}
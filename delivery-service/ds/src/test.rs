@@ -1,5 +1,6 @@
 use super::*;
 use actix_web::{body::MessageBody, http::StatusCode, test, web, web::Bytes, App};
+use openmls::credentials::test_utils::AcceptAllCredentials;
 use openmls_basic_credential::SignatureKeyPair;
 use openmls_rust_crypto::OpenMlsRustCrypto;
 use openmls_traits::types::SignatureScheme;
@@ -366,6 +367,7 @@ async fn test_group() {
         mls_group_create_config.join_config(),
         welcome.clone(),
         Some(group.export_ratchet_tree().into()), // delivered out of band
+        &AcceptAllCredentials,
     )
     .expect("Error creating staged join from Welcome")
     .into_group(crypto)
@@ -429,7 +431,7 @@ async fn test_group() {
 
     // Decrypt the message on Client1
     let processed_message = group
-        .process_message(crypto, protocol_message)
+        .process_message(crypto, protocol_message, &AcceptAllCredentials)
         .expect("Could not process unverified message.");
     if let ProcessedMessageContent::ApplicationMessage(application_message) =
         processed_message.into_content()
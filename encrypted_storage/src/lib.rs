@@ -0,0 +1,1147 @@
+//! # OpenMLS Encrypted Storage
+//!
+//! A [`StorageProvider`] that wraps another storage provider and encrypts all
+//! keys and values before they reach it, so that the inner storage never sees
+//! group ids, public keys or secrets in the clear.
+//!
+//! All keys are derived with HKDF from a key that the application provides,
+//! e.g. one that is kept in the key store of the platform.
+//!
+//! - Values are encrypted with ChaCha20Poly1305 under a fresh random nonce.
+//!   The additional data binds a value to the kind of entity and the key it
+//!   is stored under, so values can't be swapped in the inner storage.
+//! - Keys are replaced by a keyed hash of the key, followed by the key
+//!   encrypted with the keyed hash as nonce. The same key always results in
+//!   the same stored key, which allows point lookups, and the listings of the
+//!   storage can still return the original keys.
+//!
+//! The leaf indices of encryption epoch key pairs and the indices of tree
+//! nodes are passed through unchanged.
+
+use openmls_traits::{
+    crypto::OpenMlsCrypto,
+    random::OpenMlsRand,
+    storage::*,
+    types::{AeadType, CryptoError, HashType},
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tls_codec::SecretVLBytes;
+
+const AEAD: AeadType = AeadType::ChaCha20Poly1305;
+const HASH: HashType = HashType::Sha2_256;
+const KEY_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 12;
+
+/// The salt for deriving the keys from the key of the application.
+const SALT: &[u8] = b"OpenMLS encrypted storage";
+
+/// A storage that encrypts all keys and values before passing them to the
+/// inner storage.
+pub struct EncryptedStorage<Storage, Crypto> {
+    storage: Storage,
+    crypto: Crypto,
+    key_hash_key: SecretVLBytes,
+    key_encryption_key: SecretVLBytes,
+    value_encryption_key: SecretVLBytes,
+}
+
+/// Errors thrown by the encrypted storage.
+#[derive(thiserror::Error, Debug)]
+pub enum EncryptedStorageError<StorageError> {
+    /// An error of the inner storage.
+    #[error("Error in the inner storage: {0}")]
+    Storage(StorageError),
+    /// A key or value could not be encrypted or decrypted.
+    #[error("Crypto error: {0}")]
+    Crypto(#[from] CryptoError),
+    /// A key or value could not be (de)serialized.
+    #[error("Error serializing value: {0}")]
+    SerializationError(#[from] serde_json::Error),
+    /// A stored key or value is too short to be a ciphertext.
+    #[error("Malformed ciphertext.")]
+    MalformedCiphertext,
+}
+
+impl<Storage, Crypto: OpenMlsCrypto> EncryptedStorage<Storage, Crypto> {
+    /// Wrap `storage` and encrypt everything with keys derived from `key`.
+    ///
+    /// The `key` must be at least 32 bytes of uniformly random key material.
+    /// The same key has to be used every time the storage is opened.
+    pub fn new(storage: Storage, crypto: Crypto, key: &[u8]) -> Result<Self, CryptoError> {
+        if key.len() < KEY_LENGTH {
+            return Err(CryptoError::InvalidLength);
+        }
+
+        let prk = crypto.hkdf_extract(HASH, SALT, key)?;
+        let derive = |label: &[u8]| crypto.hkdf_expand(HASH, prk.as_slice(), label, KEY_LENGTH);
+        let key_hash_key = derive(b"key hashing")?;
+        let key_encryption_key = derive(b"key encryption")?;
+        let value_encryption_key = derive(b"value encryption")?;
+
+        Ok(Self {
+            storage,
+            crypto,
+            key_hash_key,
+            key_encryption_key,
+            value_encryption_key,
+        })
+    }
+
+    /// Returns the inner storage.
+    pub fn storage(&self) -> &Storage {
+        &self.storage
+    }
+
+    /// Consume the encrypted storage and return the inner storage.
+    pub fn into_inner(self) -> Storage {
+        self.storage
+    }
+}
+
+impl<Storage, Crypto: OpenMlsCrypto + OpenMlsRand> EncryptedStorage<Storage, Crypto> {
+    /// Encrypt a key deterministically, using its keyed hash as nonce.
+    fn seal_key<E>(&self, key: &impl Serialize) -> Result<SealedKey, EncryptedStorageError<E>> {
+        let key = serde_json::to_vec(key)?;
+        let hash = self
+            .crypto
+            .hkdf_extract(HASH, self.key_hash_key.as_slice(), &key)?;
+        let nonce = &hash.as_slice()[..NONCE_LENGTH];
+        let ciphertext =
+            self.crypto
+                .aead_encrypt(AEAD, self.key_encryption_key.as_slice(), &key, nonce, &[])?;
+
+        let mut sealed = nonce.to_vec();
+        sealed.extend_from_slice(&ciphertext);
+        Ok(SealedKey(sealed))
+    }
+
+    /// Decrypt a key that was encrypted with [`Self::seal_key`].
+    fn open_key<K: DeserializeOwned, E>(
+        &self,
+        sealed: &SealedKey,
+    ) -> Result<K, EncryptedStorageError<E>> {
+        let (nonce, ciphertext) = split_nonce(&sealed.0)?;
+        let key = self.crypto.aead_decrypt(
+            AEAD,
+            self.key_encryption_key.as_slice(),
+            ciphertext,
+            nonce,
+            &[],
+        )?;
+        Ok(serde_json::from_slice(&key)?)
+    }
+
+    /// Encrypt a value of the given kind that is stored under `key`.
+    fn seal_value<E>(
+        &self,
+        label: &[u8],
+        key: &[u8],
+        value: &impl Serialize,
+    ) -> Result<SealedValue, EncryptedStorageError<E>> {
+        let value = serde_json::to_vec(value)?;
+        let nonce = self
+            .crypto
+            .random_vec(NONCE_LENGTH)
+            .map_err(|_| CryptoError::InsufficientRandomness)?;
+        let ciphertext = self.crypto.aead_encrypt(
+            AEAD,
+            self.value_encryption_key.as_slice(),
+            &value,
+            &nonce,
+            &aad(label, key),
+        )?;
+
+        let mut sealed = nonce;
+        sealed.extend_from_slice(&ciphertext);
+        Ok(SealedValue(sealed))
+    }
+
+    /// Decrypt a value that was encrypted with [`Self::seal_value`].
+    fn open_value<V: DeserializeOwned, E>(
+        &self,
+        label: &[u8],
+        key: &[u8],
+        sealed: &SealedValue,
+    ) -> Result<V, EncryptedStorageError<E>> {
+        let (nonce, ciphertext) = split_nonce(&sealed.0)?;
+        let value = self.crypto.aead_decrypt(
+            AEAD,
+            self.value_encryption_key.as_slice(),
+            ciphertext,
+            nonce,
+            &aad(label, key),
+        )?;
+        Ok(serde_json::from_slice(&value)?)
+    }
+
+    /// Decrypt an optional value.
+    fn open_optional_value<V: DeserializeOwned, E>(
+        &self,
+        label: &[u8],
+        key: &[u8],
+        sealed: Option<SealedValue>,
+    ) -> Result<Option<V>, EncryptedStorageError<E>> {
+        sealed
+            .map(|sealed| self.open_value(label, key, &sealed))
+            .transpose()
+    }
+}
+
+/// The additional data of a value.
+fn aad(label: &[u8], key: &[u8]) -> Vec<u8> {
+    let mut aad = (label.len() as u16).to_be_bytes().to_vec();
+    aad.extend_from_slice(label);
+    aad.extend_from_slice(key);
+    aad
+}
+
+fn split_nonce<E>(sealed: &[u8]) -> Result<(&[u8], &[u8]), EncryptedStorageError<E>> {
+    if sealed.len() < NONCE_LENGTH {
+        return Err(EncryptedStorageError::MalformedCiphertext);
+    }
+    Ok(sealed.split_at(NONCE_LENGTH))
+}
+
+/// The key of epoch key pairs, which are stored under three keys.
+fn epoch_key_pairs_key(group_id: &SealedKey, epoch: &SealedKey, leaf_index: u32) -> Vec<u8> {
+    let mut key = aad(&group_id.0, &epoch.0);
+    key.extend_from_slice(&leaf_index.to_be_bytes());
+    key
+}
+
+/// The key of a tree node, which is stored under the group id and its index.
+fn tree_node_key(group_id: &SealedKey, node_index: u32) -> Vec<u8> {
+    let mut key = group_id.0.clone();
+    key.extend_from_slice(&node_index.to_be_bytes());
+    key
+}
+
+/// An encrypted key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+struct SealedKey(Vec<u8>);
+
+impl<const VERSION: u16> Key<VERSION> for SealedKey {}
+impl<const VERSION: u16> Entity<VERSION> for SealedKey {}
+impl<const VERSION: u16> traits::GroupId<VERSION> for SealedKey {}
+impl<const VERSION: u16> traits::SignaturePublicKey<VERSION> for SealedKey {}
+impl<const VERSION: u16> traits::HashReference<VERSION> for SealedKey {}
+impl<const VERSION: u16> traits::PskId<VERSION> for SealedKey {}
+impl<const VERSION: u16> traits::EncryptionKey<VERSION> for SealedKey {}
+impl<const VERSION: u16> traits::EpochKey<VERSION> for SealedKey {}
+impl<const VERSION: u16> traits::ProposalRef<VERSION> for SealedKey {}
+
+/// An encrypted value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+struct SealedValue(Vec<u8>);
+
+impl<const VERSION: u16> Entity<VERSION> for SealedValue {}
+impl<const VERSION: u16> traits::QueuedProposal<VERSION> for SealedValue {}
+impl<const VERSION: u16> traits::TreeSync<VERSION> for SealedValue {}
+impl<const VERSION: u16> traits::GroupContext<VERSION> for SealedValue {}
+impl<const VERSION: u16> traits::InterimTranscriptHash<VERSION> for SealedValue {}
+impl<const VERSION: u16> traits::ConfirmationTag<VERSION> for SealedValue {}
+impl<const VERSION: u16> traits::SignatureKeyPair<VERSION> for SealedValue {}
+impl<const VERSION: u16> traits::PskBundle<VERSION> for SealedValue {}
+impl<const VERSION: u16> traits::HpkeKeyPair<VERSION> for SealedValue {}
+impl<const VERSION: u16> traits::GroupState<VERSION> for SealedValue {}
+impl<const VERSION: u16> traits::GroupEpochSecrets<VERSION> for SealedValue {}
+impl<const VERSION: u16> traits::LeafNodeIndex<VERSION> for SealedValue {}
+impl<const VERSION: u16> traits::MessageSecrets<VERSION> for SealedValue {}
+impl<const VERSION: u16> traits::ResumptionPskStore<VERSION> for SealedValue {}
+impl<const VERSION: u16> traits::KeyPackage<VERSION> for SealedValue {}
+impl<const VERSION: u16> traits::MlsGroupJoinConfig<VERSION> for SealedValue {}
+impl<const VERSION: u16> traits::LeafNode<VERSION> for SealedValue {}
+impl<const VERSION: u16> traits::TreeNode<VERSION> for SealedValue {}
+
+// The labels of the values, which are part of their additional data.
+const JOIN_CONFIG_LABEL: &[u8] = b"MlsGroupJoinConfig";
+const OWN_LEAF_NODE_LABEL: &[u8] = b"OwnLeafNode";
+const QUEUED_PROPOSAL_LABEL: &[u8] = b"QueuedProposal";
+const TREE_LABEL: &[u8] = b"Tree";
+const TREE_NODE_LABEL: &[u8] = b"TreeNode";
+const INTERIM_TRANSCRIPT_HASH_LABEL: &[u8] = b"InterimTranscriptHash";
+const GROUP_CONTEXT_LABEL: &[u8] = b"GroupContext";
+const CONFIRMATION_TAG_LABEL: &[u8] = b"ConfirmationTag";
+const GROUP_STATE_LABEL: &[u8] = b"GroupState";
+const MESSAGE_SECRETS_LABEL: &[u8] = b"MessageSecrets";
+const RESUMPTION_PSK_STORE_LABEL: &[u8] = b"ResumptionPsk";
+const OWN_LEAF_INDEX_LABEL: &[u8] = b"OwnLeafNodeIndex";
+const EPOCH_SECRETS_LABEL: &[u8] = b"EpochSecrets";
+const SIGNATURE_KEY_PAIR_LABEL: &[u8] = b"SignatureKeyPair";
+const ENCRYPTION_KEY_PAIR_LABEL: &[u8] = b"EncryptionKeyPair";
+const EPOCH_KEY_PAIRS_LABEL: &[u8] = b"EpochKeyPairs";
+const KEY_PACKAGE_LABEL: &[u8] = b"KeyPackage";
+const PSK_LABEL: &[u8] = b"Psk";
+
+impl<const VERSION: u16, Storage, Crypto> StorageProvider<VERSION>
+    for EncryptedStorage<Storage, Crypto>
+where
+    Storage: StorageProvider<VERSION>,
+    Crypto: OpenMlsCrypto + OpenMlsRand,
+{
+    type Error = EncryptedStorageError<Storage::Error>;
+
+    fn begin_transaction(&self) -> Result<(), Self::Error> {
+        self.storage
+            .begin_transaction()
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn commit_transaction(&self) -> Result<(), Self::Error> {
+        self.storage
+            .commit_transaction()
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn rollback_transaction(&self) -> Result<(), Self::Error> {
+        self.storage
+            .rollback_transaction()
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn write_mls_join_config<
+        GroupId: traits::GroupId<VERSION>,
+        MlsGroupJoinConfig: traits::MlsGroupJoinConfig<VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        config: &MlsGroupJoinConfig,
+    ) -> Result<(), Self::Error> {
+        let group_id = self.seal_key(group_id)?;
+        let config = self.seal_value(JOIN_CONFIG_LABEL, &group_id.0, config)?;
+        self.storage
+            .write_mls_join_config(&group_id, &config)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn append_own_leaf_node<
+        GroupId: traits::GroupId<VERSION>,
+        LeafNode: traits::LeafNode<VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        leaf_node: &LeafNode,
+    ) -> Result<(), Self::Error> {
+        let group_id = self.seal_key(group_id)?;
+        let leaf_node = self.seal_value(OWN_LEAF_NODE_LABEL, &group_id.0, leaf_node)?;
+        self.storage
+            .append_own_leaf_node(&group_id, &leaf_node)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn queue_proposal<
+        GroupId: traits::GroupId<VERSION>,
+        ProposalRef: traits::ProposalRef<VERSION>,
+        QueuedProposal: traits::QueuedProposal<VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        proposal_ref: &ProposalRef,
+        proposal: &QueuedProposal,
+    ) -> Result<(), Self::Error> {
+        let group_id = self.seal_key(group_id)?;
+        let proposal_ref = self.seal_key(proposal_ref)?;
+        let proposal = self.seal_value(QUEUED_PROPOSAL_LABEL, &proposal_ref.0, proposal)?;
+        self.storage
+            .queue_proposal(&group_id, &proposal_ref, &proposal)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn write_tree<GroupId: traits::GroupId<VERSION>, TreeSync: traits::TreeSync<VERSION>>(
+        &self,
+        group_id: &GroupId,
+        tree: &TreeSync,
+    ) -> Result<(), Self::Error> {
+        let group_id = self.seal_key(group_id)?;
+        let tree = self.seal_value(TREE_LABEL, &group_id.0, tree)?;
+        self.storage
+            .write_tree(&group_id, &tree)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn write_tree_node<GroupId: traits::GroupId<VERSION>, TreeNode: traits::TreeNode<VERSION>>(
+        &self,
+        group_id: &GroupId,
+        node_index: u32,
+        node: &TreeNode,
+    ) -> Result<(), Self::Error> {
+        let group_id = self.seal_key(group_id)?;
+        let node = self.seal_value(TREE_NODE_LABEL, &tree_node_key(&group_id, node_index), node)?;
+        self.storage
+            .write_tree_node(&group_id, node_index, &node)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn write_interim_transcript_hash<
+        GroupId: traits::GroupId<VERSION>,
+        InterimTranscriptHash: traits::InterimTranscriptHash<VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        interim_transcript_hash: &InterimTranscriptHash,
+    ) -> Result<(), Self::Error> {
+        let group_id = self.seal_key(group_id)?;
+        let interim_transcript_hash = self.seal_value(
+            INTERIM_TRANSCRIPT_HASH_LABEL,
+            &group_id.0,
+            interim_transcript_hash,
+        )?;
+        self.storage
+            .write_interim_transcript_hash(&group_id, &interim_transcript_hash)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn write_context<
+        GroupId: traits::GroupId<VERSION>,
+        GroupContext: traits::GroupContext<VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_context: &GroupContext,
+    ) -> Result<(), Self::Error> {
+        let group_id = self.seal_key(group_id)?;
+        let group_context = self.seal_value(GROUP_CONTEXT_LABEL, &group_id.0, group_context)?;
+        self.storage
+            .write_context(&group_id, &group_context)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn write_confirmation_tag<
+        GroupId: traits::GroupId<VERSION>,
+        ConfirmationTag: traits::ConfirmationTag<VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        confirmation_tag: &ConfirmationTag,
+    ) -> Result<(), Self::Error> {
+        let group_id = self.seal_key(group_id)?;
+        let confirmation_tag =
+            self.seal_value(CONFIRMATION_TAG_LABEL, &group_id.0, confirmation_tag)?;
+        self.storage
+            .write_confirmation_tag(&group_id, &confirmation_tag)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn write_group_state<
+        GroupState: traits::GroupState<VERSION>,
+        GroupId: traits::GroupId<VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_state: &GroupState,
+    ) -> Result<(), Self::Error> {
+        let group_id = self.seal_key(group_id)?;
+        let group_state = self.seal_value(GROUP_STATE_LABEL, &group_id.0, group_state)?;
+        self.storage
+            .write_group_state(&group_id, &group_state)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn write_message_secrets<
+        GroupId: traits::GroupId<VERSION>,
+        MessageSecrets: traits::MessageSecrets<VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        message_secrets: &MessageSecrets,
+    ) -> Result<(), Self::Error> {
+        let group_id = self.seal_key(group_id)?;
+        let message_secrets =
+            self.seal_value(MESSAGE_SECRETS_LABEL, &group_id.0, message_secrets)?;
+        self.storage
+            .write_message_secrets(&group_id, &message_secrets)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn write_resumption_psk_store<
+        GroupId: traits::GroupId<VERSION>,
+        ResumptionPskStore: traits::ResumptionPskStore<VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        resumption_psk_store: &ResumptionPskStore,
+    ) -> Result<(), Self::Error> {
+        let group_id = self.seal_key(group_id)?;
+        let resumption_psk_store = self.seal_value(
+            RESUMPTION_PSK_STORE_LABEL,
+            &group_id.0,
+            resumption_psk_store,
+        )?;
+        self.storage
+            .write_resumption_psk_store(&group_id, &resumption_psk_store)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn write_own_leaf_index<
+        GroupId: traits::GroupId<VERSION>,
+        LeafNodeIndex: traits::LeafNodeIndex<VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        own_leaf_index: &LeafNodeIndex,
+    ) -> Result<(), Self::Error> {
+        let group_id = self.seal_key(group_id)?;
+        let own_leaf_index = self.seal_value(OWN_LEAF_INDEX_LABEL, &group_id.0, own_leaf_index)?;
+        self.storage
+            .write_own_leaf_index(&group_id, &own_leaf_index)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn write_group_epoch_secrets<
+        GroupId: traits::GroupId<VERSION>,
+        GroupEpochSecrets: traits::GroupEpochSecrets<VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_epoch_secrets: &GroupEpochSecrets,
+    ) -> Result<(), Self::Error> {
+        let group_id = self.seal_key(group_id)?;
+        let group_epoch_secrets =
+            self.seal_value(EPOCH_SECRETS_LABEL, &group_id.0, group_epoch_secrets)?;
+        self.storage
+            .write_group_epoch_secrets(&group_id, &group_epoch_secrets)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn write_signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<VERSION>,
+        SignatureKeyPair: traits::SignatureKeyPair<VERSION>,
+    >(
+        &self,
+        public_key: &SignaturePublicKey,
+        signature_key_pair: &SignatureKeyPair,
+    ) -> Result<(), Self::Error> {
+        let public_key = self.seal_key(public_key)?;
+        let signature_key_pair =
+            self.seal_value(SIGNATURE_KEY_PAIR_LABEL, &public_key.0, signature_key_pair)?;
+        self.storage
+            .write_signature_key_pair(&public_key, &signature_key_pair)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn write_encryption_key_pair<
+        EncryptionKey: traits::EncryptionKey<VERSION>,
+        HpkeKeyPair: traits::HpkeKeyPair<VERSION>,
+    >(
+        &self,
+        public_key: &EncryptionKey,
+        key_pair: &HpkeKeyPair,
+    ) -> Result<(), Self::Error> {
+        let public_key = self.seal_key(public_key)?;
+        let key_pair = self.seal_value(ENCRYPTION_KEY_PAIR_LABEL, &public_key.0, key_pair)?;
+        self.storage
+            .write_encryption_key_pair(&public_key, &key_pair)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn write_encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<VERSION>,
+        EpochKey: traits::EpochKey<VERSION>,
+        HpkeKeyPair: traits::HpkeKeyPair<VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+        key_pairs: &[HpkeKeyPair],
+    ) -> Result<(), Self::Error> {
+        let group_id = self.seal_key(group_id)?;
+        let epoch = self.seal_key(epoch)?;
+        let key = epoch_key_pairs_key(&group_id, &epoch, leaf_index);
+        let key_pairs = key_pairs
+            .iter()
+            .map(|key_pair| self.seal_value(EPOCH_KEY_PAIRS_LABEL, &key, key_pair))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.storage
+            .write_encryption_epoch_key_pairs(&group_id, &epoch, leaf_index, &key_pairs)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn write_key_package<
+        HashReference: traits::HashReference<VERSION>,
+        KeyPackage: traits::KeyPackage<VERSION>,
+    >(
+        &self,
+        hash_ref: &HashReference,
+        key_package: &KeyPackage,
+    ) -> Result<(), Self::Error> {
+        let hash_ref = self.seal_key(hash_ref)?;
+        let key_package = self.seal_value(KEY_PACKAGE_LABEL, &hash_ref.0, key_package)?;
+        self.storage
+            .write_key_package(&hash_ref, &key_package)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn write_psk<PskId: traits::PskId<VERSION>, PskBundle: traits::PskBundle<VERSION>>(
+        &self,
+        psk_id: &PskId,
+        psk: &PskBundle,
+    ) -> Result<(), Self::Error> {
+        let psk_id = self.seal_key(psk_id)?;
+        let psk = self.seal_value(PSK_LABEL, &psk_id.0, psk)?;
+        self.storage
+            .write_psk(&psk_id, &psk)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn mls_group_join_config<
+        GroupId: traits::GroupId<VERSION>,
+        MlsGroupJoinConfig: traits::MlsGroupJoinConfig<VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<MlsGroupJoinConfig>, Self::Error> {
+        let group_id = self.seal_key(group_id)?;
+        let config: Option<SealedValue> = self
+            .storage
+            .mls_group_join_config(&group_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_optional_value(JOIN_CONFIG_LABEL, &group_id.0, config)
+    }
+
+    fn own_leaf_nodes<GroupId: traits::GroupId<VERSION>, LeafNode: traits::LeafNode<VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<LeafNode>, Self::Error> {
+        let group_id = self.seal_key(group_id)?;
+        let leaf_nodes: Vec<SealedValue> = self
+            .storage
+            .own_leaf_nodes(&group_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        leaf_nodes
+            .iter()
+            .map(|leaf_node| self.open_value(OWN_LEAF_NODE_LABEL, &group_id.0, leaf_node))
+            .collect()
+    }
+
+    fn queued_proposal_refs<
+        GroupId: traits::GroupId<VERSION>,
+        ProposalRef: traits::ProposalRef<VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<ProposalRef>, Self::Error> {
+        let group_id = self.seal_key(group_id)?;
+        let proposal_refs: Vec<SealedKey> = self
+            .storage
+            .queued_proposal_refs(&group_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        proposal_refs
+            .iter()
+            .map(|proposal_ref| self.open_key(proposal_ref))
+            .collect()
+    }
+
+    fn queued_proposals<
+        GroupId: traits::GroupId<VERSION>,
+        ProposalRef: traits::ProposalRef<VERSION>,
+        QueuedProposal: traits::QueuedProposal<VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<(ProposalRef, QueuedProposal)>, Self::Error> {
+        let group_id = self.seal_key(group_id)?;
+        let proposals: Vec<(SealedKey, SealedValue)> = self
+            .storage
+            .queued_proposals(&group_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        proposals
+            .iter()
+            .map(|(proposal_ref, proposal)| {
+                Ok((
+                    self.open_key(proposal_ref)?,
+                    self.open_value(QUEUED_PROPOSAL_LABEL, &proposal_ref.0, proposal)?,
+                ))
+            })
+            .collect()
+    }
+
+    fn tree<GroupId: traits::GroupId<VERSION>, TreeSync: traits::TreeSync<VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<TreeSync>, Self::Error> {
+        let group_id = self.seal_key(group_id)?;
+        let tree: Option<SealedValue> = self
+            .storage
+            .tree(&group_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_optional_value(TREE_LABEL, &group_id.0, tree)
+    }
+
+    fn tree_nodes<GroupId: traits::GroupId<VERSION>, TreeNode: traits::TreeNode<VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<(u32, TreeNode)>, Self::Error> {
+        let group_id = self.seal_key(group_id)?;
+        let nodes: Vec<(u32, SealedValue)> = self
+            .storage
+            .tree_nodes(&group_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        nodes
+            .iter()
+            .map(|(node_index, node)| {
+                let key = tree_node_key(&group_id, *node_index);
+                Ok((*node_index, self.open_value(TREE_NODE_LABEL, &key, node)?))
+            })
+            .collect()
+    }
+
+    fn group_context<
+        GroupId: traits::GroupId<VERSION>,
+        GroupContext: traits::GroupContext<VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<GroupContext>, Self::Error> {
+        let group_id = self.seal_key(group_id)?;
+        let group_context: Option<SealedValue> = self
+            .storage
+            .group_context(&group_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_optional_value(GROUP_CONTEXT_LABEL, &group_id.0, group_context)
+    }
+
+    fn interim_transcript_hash<
+        GroupId: traits::GroupId<VERSION>,
+        InterimTranscriptHash: traits::InterimTranscriptHash<VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<InterimTranscriptHash>, Self::Error> {
+        let group_id = self.seal_key(group_id)?;
+        let interim_transcript_hash: Option<SealedValue> = self
+            .storage
+            .interim_transcript_hash(&group_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_optional_value(
+            INTERIM_TRANSCRIPT_HASH_LABEL,
+            &group_id.0,
+            interim_transcript_hash,
+        )
+    }
+
+    fn confirmation_tag<
+        GroupId: traits::GroupId<VERSION>,
+        ConfirmationTag: traits::ConfirmationTag<VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<ConfirmationTag>, Self::Error> {
+        let group_id = self.seal_key(group_id)?;
+        let confirmation_tag: Option<SealedValue> = self
+            .storage
+            .confirmation_tag(&group_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_optional_value(CONFIRMATION_TAG_LABEL, &group_id.0, confirmation_tag)
+    }
+
+    fn group_state<GroupState: traits::GroupState<VERSION>, GroupId: traits::GroupId<VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<GroupState>, Self::Error> {
+        let group_id = self.seal_key(group_id)?;
+        let group_state: Option<SealedValue> = self
+            .storage
+            .group_state(&group_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_optional_value(GROUP_STATE_LABEL, &group_id.0, group_state)
+    }
+
+    fn message_secrets<
+        GroupId: traits::GroupId<VERSION>,
+        MessageSecrets: traits::MessageSecrets<VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<MessageSecrets>, Self::Error> {
+        let group_id = self.seal_key(group_id)?;
+        let message_secrets: Option<SealedValue> = self
+            .storage
+            .message_secrets(&group_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_optional_value(MESSAGE_SECRETS_LABEL, &group_id.0, message_secrets)
+    }
+
+    fn resumption_psk_store<
+        GroupId: traits::GroupId<VERSION>,
+        ResumptionPskStore: traits::ResumptionPskStore<VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<ResumptionPskStore>, Self::Error> {
+        let group_id = self.seal_key(group_id)?;
+        let resumption_psk_store: Option<SealedValue> = self
+            .storage
+            .resumption_psk_store(&group_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_optional_value(
+            RESUMPTION_PSK_STORE_LABEL,
+            &group_id.0,
+            resumption_psk_store,
+        )
+    }
+
+    fn own_leaf_index<
+        GroupId: traits::GroupId<VERSION>,
+        LeafNodeIndex: traits::LeafNodeIndex<VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<LeafNodeIndex>, Self::Error> {
+        let group_id = self.seal_key(group_id)?;
+        let own_leaf_index: Option<SealedValue> = self
+            .storage
+            .own_leaf_index(&group_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_optional_value(OWN_LEAF_INDEX_LABEL, &group_id.0, own_leaf_index)
+    }
+
+    fn group_epoch_secrets<
+        GroupId: traits::GroupId<VERSION>,
+        GroupEpochSecrets: traits::GroupEpochSecrets<VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<GroupEpochSecrets>, Self::Error> {
+        let group_id = self.seal_key(group_id)?;
+        let group_epoch_secrets: Option<SealedValue> = self
+            .storage
+            .group_epoch_secrets(&group_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_optional_value(EPOCH_SECRETS_LABEL, &group_id.0, group_epoch_secrets)
+    }
+
+    fn signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<VERSION>,
+        SignatureKeyPair: traits::SignatureKeyPair<VERSION>,
+    >(
+        &self,
+        public_key: &SignaturePublicKey,
+    ) -> Result<Option<SignatureKeyPair>, Self::Error> {
+        let public_key = self.seal_key(public_key)?;
+        let signature_key_pair: Option<SealedValue> = self
+            .storage
+            .signature_key_pair(&public_key)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_optional_value(SIGNATURE_KEY_PAIR_LABEL, &public_key.0, signature_key_pair)
+    }
+
+    fn encryption_key_pair<
+        HpkeKeyPair: traits::HpkeKeyPair<VERSION>,
+        EncryptionKey: traits::EncryptionKey<VERSION>,
+    >(
+        &self,
+        public_key: &EncryptionKey,
+    ) -> Result<Option<HpkeKeyPair>, Self::Error> {
+        let public_key = self.seal_key(public_key)?;
+        let key_pair: Option<SealedValue> = self
+            .storage
+            .encryption_key_pair(&public_key)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_optional_value(ENCRYPTION_KEY_PAIR_LABEL, &public_key.0, key_pair)
+    }
+
+    fn encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<VERSION>,
+        EpochKey: traits::EpochKey<VERSION>,
+        HpkeKeyPair: traits::HpkeKeyPair<VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+    ) -> Result<Vec<HpkeKeyPair>, Self::Error> {
+        let group_id = self.seal_key(group_id)?;
+        let epoch = self.seal_key(epoch)?;
+        let key = epoch_key_pairs_key(&group_id, &epoch, leaf_index);
+        let key_pairs: Vec<SealedValue> = self
+            .storage
+            .encryption_epoch_key_pairs(&group_id, &epoch, leaf_index)
+            .map_err(EncryptedStorageError::Storage)?;
+        key_pairs
+            .iter()
+            .map(|key_pair| self.open_value(EPOCH_KEY_PAIRS_LABEL, &key, key_pair))
+            .collect()
+    }
+
+    fn key_package<
+        KeyPackageRef: traits::HashReference<VERSION>,
+        KeyPackage: traits::KeyPackage<VERSION>,
+    >(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> Result<Option<KeyPackage>, Self::Error> {
+        let hash_ref = self.seal_key(hash_ref)?;
+        let key_package: Option<SealedValue> = self
+            .storage
+            .key_package(&hash_ref)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_optional_value(KEY_PACKAGE_LABEL, &hash_ref.0, key_package)
+    }
+
+    fn psk<PskBundle: traits::PskBundle<VERSION>, PskId: traits::PskId<VERSION>>(
+        &self,
+        psk_id: &PskId,
+    ) -> Result<Option<PskBundle>, Self::Error> {
+        let psk_id = self.seal_key(psk_id)?;
+        let psk: Option<SealedValue> = self
+            .storage
+            .psk(&psk_id)
+            .map_err(EncryptedStorageError::Storage)?;
+        self.open_optional_value(PSK_LABEL, &psk_id.0, psk)
+    }
+
+    fn group_ids<GroupId: traits::GroupId<VERSION> + DeserializeOwned>(
+        &self,
+    ) -> Result<Vec<GroupId>, Self::Error> {
+        let group_ids: Vec<SealedKey> = self
+            .storage
+            .group_ids()
+            .map_err(EncryptedStorageError::Storage)?;
+        group_ids
+            .iter()
+            .map(|group_id| self.open_key(group_id))
+            .collect()
+    }
+
+    fn encryption_epoch_key_pair_ids<
+        GroupId: traits::GroupId<VERSION> + DeserializeOwned,
+        EpochKey: traits::EpochKey<VERSION> + DeserializeOwned,
+    >(
+        &self,
+    ) -> Result<Vec<(GroupId, EpochKey, u32)>, Self::Error> {
+        let ids: Vec<(SealedKey, SealedKey, u32)> = self
+            .storage
+            .encryption_epoch_key_pair_ids()
+            .map_err(EncryptedStorageError::Storage)?;
+        ids.iter()
+            .map(|(group_id, epoch, leaf_index)| {
+                Ok((self.open_key(group_id)?, self.open_key(epoch)?, *leaf_index))
+            })
+            .collect()
+    }
+
+    fn key_package_refs<KeyPackageRef: traits::HashReference<VERSION> + DeserializeOwned>(
+        &self,
+    ) -> Result<Vec<KeyPackageRef>, Self::Error> {
+        let hash_refs: Vec<SealedKey> = self
+            .storage
+            .key_package_refs()
+            .map_err(EncryptedStorageError::Storage)?;
+        hash_refs
+            .iter()
+            .map(|hash_ref| self.open_key(hash_ref))
+            .collect()
+    }
+
+    fn psk_ids<PskId: traits::PskId<VERSION> + DeserializeOwned>(
+        &self,
+    ) -> Result<Vec<PskId>, Self::Error> {
+        let psk_ids: Vec<SealedKey> = self
+            .storage
+            .psk_ids()
+            .map_err(EncryptedStorageError::Storage)?;
+        psk_ids.iter().map(|psk_id| self.open_key(psk_id)).collect()
+    }
+
+    fn remove_proposal<
+        GroupId: traits::GroupId<VERSION>,
+        ProposalRef: traits::ProposalRef<VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        proposal_ref: &ProposalRef,
+    ) -> Result<(), Self::Error> {
+        let group_id = self.seal_key(group_id)?;
+        let proposal_ref = self.seal_key(proposal_ref)?;
+        self.storage
+            .remove_proposal(&group_id, &proposal_ref)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_own_leaf_nodes<GroupId: traits::GroupId<VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_own_leaf_nodes(&self.seal_key(group_id)?)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_group_config<GroupId: traits::GroupId<VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_group_config(&self.seal_key(group_id)?)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_tree<GroupId: traits::GroupId<VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_tree(&self.seal_key(group_id)?)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_tree_node<GroupId: traits::GroupId<VERSION>>(
+        &self,
+        group_id: &GroupId,
+        node_index: u32,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_tree_node(&self.seal_key(group_id)?, node_index)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_tree_nodes<GroupId: traits::GroupId<VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_tree_nodes(&self.seal_key(group_id)?)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_confirmation_tag<GroupId: traits::GroupId<VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_confirmation_tag(&self.seal_key(group_id)?)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_group_state<GroupId: traits::GroupId<VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_group_state(&self.seal_key(group_id)?)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_context<GroupId: traits::GroupId<VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_context(&self.seal_key(group_id)?)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_interim_transcript_hash<GroupId: traits::GroupId<VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_interim_transcript_hash(&self.seal_key(group_id)?)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_message_secrets<GroupId: traits::GroupId<VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_message_secrets(&self.seal_key(group_id)?)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_all_resumption_psk_secrets<GroupId: traits::GroupId<VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_all_resumption_psk_secrets(&self.seal_key(group_id)?)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_own_leaf_index<GroupId: traits::GroupId<VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_own_leaf_index(&self.seal_key(group_id)?)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_group_epoch_secrets<GroupId: traits::GroupId<VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_group_epoch_secrets(&self.seal_key(group_id)?)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn clear_proposal_queue<
+        GroupId: traits::GroupId<VERSION>,
+        ProposalRef: traits::ProposalRef<VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .clear_proposal_queue::<SealedKey, SealedKey>(&self.seal_key(group_id)?)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_signature_key_pair<SignaturePublicKey: traits::SignaturePublicKey<VERSION>>(
+        &self,
+        public_key: &SignaturePublicKey,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_signature_key_pair(&self.seal_key(public_key)?)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_encryption_key_pair<EncryptionKey: traits::EncryptionKey<VERSION>>(
+        &self,
+        public_key: &EncryptionKey,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_encryption_key_pair(&self.seal_key(public_key)?)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<VERSION>,
+        EpochKey: traits::EpochKey<VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_encryption_epoch_key_pairs(
+                &self.seal_key(group_id)?,
+                &self.seal_key(epoch)?,
+                leaf_index,
+            )
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_key_package<KeyPackageRef: traits::HashReference<VERSION>>(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_key_package(&self.seal_key(hash_ref)?)
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    fn delete_psk<PskKey: traits::PskId<VERSION>>(
+        &self,
+        psk_id: &PskKey,
+    ) -> Result<(), Self::Error> {
+        self.storage
+            .delete_psk(&self.seal_key(psk_id)?)
+            .map_err(EncryptedStorageError::Storage)
+    }
+}
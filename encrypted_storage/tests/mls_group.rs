@@ -0,0 +1,178 @@
+//! Runs groups on top of an encrypted memory storage and checks that the inner
+//! storage never sees the group id in the clear.
+
+use openmls::{
+    credentials::test_utils::AcceptAllCredentials,
+    prelude::{test_utils::new_credential, *},
+};
+use openmls_encrypted_storage::{EncryptedStorage, EncryptedStorageError};
+use openmls_memory_storage::MemoryStorage;
+use openmls_rust_crypto::RustCrypto;
+use openmls_traits::{
+    storage::{StorageProvider, CURRENT_VERSION},
+    types::CryptoError,
+    OpenMlsProvider,
+};
+
+const KEY: [u8; 32] = [42; 32];
+
+struct EncryptedProvider {
+    crypto: RustCrypto,
+    storage: EncryptedStorage<MemoryStorage, RustCrypto>,
+}
+
+impl EncryptedProvider {
+    fn new(storage: MemoryStorage, key: &[u8]) -> Self {
+        Self {
+            crypto: RustCrypto::default(),
+            storage: EncryptedStorage::new(storage, RustCrypto::default(), key).unwrap(),
+        }
+    }
+}
+
+impl OpenMlsProvider for EncryptedProvider {
+    type CryptoProvider = RustCrypto;
+    type RandProvider = RustCrypto;
+    type StorageProvider = EncryptedStorage<MemoryStorage, RustCrypto>;
+
+    fn storage(&self) -> &Self::StorageProvider {
+        &self.storage
+    }
+
+    fn crypto(&self) -> &Self::CryptoProvider {
+        &self.crypto
+    }
+
+    fn rand(&self) -> &Self::RandProvider {
+        &self.crypto
+    }
+}
+
+/// Returns true if any key or value of the storage contains `needle` the way
+/// the memory storage serializes bytes.
+fn contains_in_clear(storage: &MemoryStorage, needle: &[u8]) -> bool {
+    let needle = serde_json::to_string(needle).unwrap();
+    let needle = needle.trim_start_matches('[').trim_end_matches(']');
+    let values = storage.values.read().unwrap();
+    values.iter().any(|(key, value)| {
+        String::from_utf8_lossy(key).contains(needle)
+            || String::from_utf8_lossy(value).contains(needle)
+    })
+}
+
+#[test]
+fn mls_group() {
+    let ciphersuite = Ciphersuite::MLS_128_DHKEMX25519_CHACHA20POLY1305_SHA256_Ed25519;
+    let group_id = GroupId::from_slice(b"a group id that must stay private");
+
+    let alice_provider = EncryptedProvider::new(MemoryStorage::default(), &KEY);
+    let (alice_cwk, alice_signer) =
+        new_credential(&alice_provider, b"alice", ciphersuite.signature_algorithm());
+    let bob_provider = EncryptedProvider::new(MemoryStorage::default(), &KEY);
+    let (bob_cwk, bob_signer) =
+        new_credential(&bob_provider, b"bob", ciphersuite.signature_algorithm());
+    let charlie_provider = EncryptedProvider::new(MemoryStorage::default(), &KEY);
+    let (charlie_cwk, charlie_signer) = new_credential(
+        &charlie_provider,
+        b"charlie",
+        ciphersuite.signature_algorithm(),
+    );
+
+    let mut alice_group = MlsGroup::builder()
+        .ciphersuite(ciphersuite)
+        .with_group_id(group_id.clone())
+        .use_ratchet_tree_extension(true)
+        .build(&alice_provider, &alice_signer, alice_cwk)
+        .unwrap();
+
+    let bob_kpb = KeyPackage::builder()
+        .build(ciphersuite, &bob_provider, &bob_signer, bob_cwk)
+        .unwrap();
+    let (_commit, welcome, _group_info) = alice_group
+        .add_members(
+            &alice_provider,
+            &alice_signer,
+            &[bob_kpb.key_package().clone()],
+        )
+        .unwrap();
+    alice_group.merge_pending_commit(&alice_provider).unwrap();
+
+    let mut bob_group = StagedWelcome::new_from_welcome(
+        &bob_provider,
+        &MlsGroupJoinConfig::default(),
+        welcome.into_welcome().unwrap(),
+        None,
+        &AcceptAllCredentials,
+    )
+    .and_then(|staged_join| staged_join.into_group(&bob_provider))
+    .unwrap();
+
+    // A commit by Bob needs Alice's encryption keys of the current epoch.
+    let (commit, _welcome, _group_info) = bob_group
+        .self_update(&bob_provider, &bob_signer, LeafNodeParameters::default())
+        .unwrap();
+    bob_group.merge_pending_commit(&bob_provider).unwrap();
+    let processed_message = alice_group
+        .process_message(
+            &alice_provider,
+            commit.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
+        .unwrap();
+    let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
+        processed_message.into_content()
+    else {
+        panic!("Expected a StagedCommit.");
+    };
+    alice_group
+        .merge_staged_commit(&alice_provider, *staged_commit)
+        .unwrap();
+    assert_eq!(
+        alice_group.epoch_authenticator(),
+        bob_group.epoch_authenticator()
+    );
+
+    // A queued proposal.
+    let charlie_kpb = KeyPackage::builder()
+        .build(ciphersuite, &charlie_provider, &charlie_signer, charlie_cwk)
+        .unwrap();
+    alice_group
+        .propose_add_member(&alice_provider, &alice_signer, charlie_kpb.key_package())
+        .unwrap();
+
+    let loaded_group = MlsGroup::load(alice_provider.storage(), &group_id)
+        .unwrap()
+        .unwrap();
+    assert_eq!(loaded_group, alice_group);
+    assert_eq!(
+        StorageProvider::<CURRENT_VERSION>::group_ids::<GroupId>(alice_provider.storage()).unwrap(),
+        vec![group_id.clone()]
+    );
+
+    let inner = alice_provider.storage.into_inner();
+    assert!(!inner.values.read().unwrap().is_empty());
+    assert!(!contains_in_clear(&inner, group_id.as_slice()));
+
+    // With the wrong key, the group is not found and nothing can be decrypted.
+    let storage = EncryptedStorage::new(inner, RustCrypto::default(), &[7; 32]).unwrap();
+    assert!(MlsGroup::load(&storage, &group_id).unwrap().is_none());
+    assert!(matches!(
+        StorageProvider::<CURRENT_VERSION>::group_ids::<GroupId>(&storage),
+        Err(EncryptedStorageError::Crypto(_))
+    ));
+
+    // With the right key, it is.
+    let storage = EncryptedStorage::new(storage.into_inner(), RustCrypto::default(), &KEY).unwrap();
+    assert_eq!(
+        MlsGroup::load(&storage, &group_id).unwrap().unwrap(),
+        alice_group
+    );
+}
+
+#[test]
+fn short_key() {
+    assert_eq!(
+        EncryptedStorage::new(MemoryStorage::default(), RustCrypto::default(), &[0; 31]).err(),
+        Some(CryptoError::InvalidLength)
+    );
+}
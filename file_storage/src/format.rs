@@ -0,0 +1,206 @@
+//! The on-disk format of the storage.
+//!
+//! A log file starts with [`MAGIC`], followed by records. Every record is
+//!
+//! ```text
+//! payload length: u32 (big endian)
+//! checksum:       u32 (big endian), the CRC-32 of the payload
+//! payload:        one or more changes
+//! ```
+//!
+//! and every change is
+//!
+//! ```text
+//! operation:    u8, DELETE or WRITE
+//! table length: u8
+//! table:        [u8; table length]
+//! key length:   u32 (big endian)
+//! key:          [u8; key length]
+//! value length: u32 (big endian), only for WRITE
+//! value:        [u8; value length], only for WRITE
+//! ```
+//!
+//! All changes of a record are applied together. A record that was cut off
+//! at the end of the file, because the process crashed while writing it, is
+//! discarded when the log is read.
+
+use std::collections::HashMap;
+
+use crate::FileStorageError;
+
+/// The first bytes of every log file. The last byte is the version of the
+/// format.
+pub(crate) const MAGIC: &[u8; 8] = b"OpenMLS\x01";
+
+/// The length of the header of a record.
+pub(crate) const RECORD_HEADER_LEN: u64 = 8;
+
+const DELETE: u8 = 0;
+const WRITE: u8 = 1;
+
+/// The key of an entry: the table and the serialized key.
+pub(crate) type EntryKey = (&'static str, Vec<u8>);
+
+/// The entries of the storage, indexed by table and key.
+pub(crate) type Entries = HashMap<EntryKey, Vec<u8>>;
+
+/// A change of a single entry. A value of `None` deletes the entry.
+pub(crate) type Change<'a> = (&'a EntryKey, Option<&'a [u8]>);
+
+/// Returns the length of the change in a record.
+pub(crate) fn change_len(key: &EntryKey, value: Option<&[u8]>) -> u64 {
+    let value_len = value.map_or(0, |value| 4 + value.len());
+    (1 + 1 + key.0.len() + 4 + key.1.len() + value_len) as u64
+}
+
+/// Encodes the changes as a record.
+pub(crate) fn encode_record<'a>(changes: impl IntoIterator<Item = Change<'a>>) -> Vec<u8> {
+    let mut payload = Vec::new();
+    for ((table, key), value) in changes {
+        payload.push(if value.is_some() { WRITE } else { DELETE });
+        payload.push(table.len() as u8);
+        payload.extend_from_slice(table.as_bytes());
+        payload.extend_from_slice(&(key.len() as u32).to_be_bytes());
+        payload.extend_from_slice(key);
+        if let Some(value) = value {
+            payload.extend_from_slice(&(value.len() as u32).to_be_bytes());
+            payload.extend_from_slice(value);
+        }
+    }
+
+    let mut record = Vec::with_capacity(RECORD_HEADER_LEN as usize + payload.len());
+    record.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+    record.extend_from_slice(&crc32(&payload).to_be_bytes());
+    record.extend_from_slice(&payload);
+    record
+}
+
+/// The result of reading a log.
+pub(crate) struct Log {
+    /// The entries after applying all records.
+    pub(crate) entries: Entries,
+    /// The length of the log up to the end of the last complete record.
+    pub(crate) len: u64,
+}
+
+/// Reads the entries from the `bytes` of a log file.
+///
+/// Returns an error if the log is corrupted. A record that is incomplete or
+/// has a wrong checksum at the end of the log is treated as an interrupted
+/// write, and is not part of [`Log::len`].
+pub(crate) fn read(bytes: &[u8], tables: &[&'static str]) -> Result<Log, FileStorageError> {
+    if bytes.get(..MAGIC.len()) != Some(MAGIC) {
+        return Err(FileStorageError::Corrupted { offset: 0 });
+    }
+
+    let mut entries = Entries::new();
+    let mut offset = MAGIC.len();
+    while let Some(header) = bytes.get(offset..offset + RECORD_HEADER_LEN as usize) {
+        let payload_len = u32::from_be_bytes(header[..4].try_into().unwrap()) as usize;
+        let checksum = u32::from_be_bytes(header[4..].try_into().unwrap());
+        let payload_start = offset + RECORD_HEADER_LEN as usize;
+        let Some(payload) = bytes.get(payload_start..payload_start + payload_len) else {
+            break;
+        };
+
+        if crc32(payload) != checksum {
+            if payload_start + payload_len == bytes.len() {
+                break;
+            }
+            return Err(FileStorageError::Corrupted {
+                offset: offset as u64,
+            });
+        }
+
+        apply_payload(&mut entries, payload, tables).ok_or(FileStorageError::Corrupted {
+            offset: offset as u64,
+        })?;
+        offset = payload_start + payload_len;
+    }
+
+    Ok(Log {
+        entries,
+        len: offset as u64,
+    })
+}
+
+/// Applies the changes of a record to the `entries`. Returns `None` if the
+/// payload is malformed.
+fn apply_payload(entries: &mut Entries, payload: &[u8], tables: &[&'static str]) -> Option<()> {
+    let mut reader = Reader(payload);
+    while !reader.0.is_empty() {
+        let operation = reader.take(1)?[0];
+        let table_len = reader.take(1)?[0] as usize;
+        let table = reader.take(table_len)?;
+        let table = *tables.iter().find(|name| name.as_bytes() == table)?;
+        let key_len = reader.take_u32()? as usize;
+        let key = (table, reader.take(key_len)?.to_vec());
+
+        match operation {
+            DELETE => {
+                entries.remove(&key);
+            }
+            WRITE => {
+                let value_len = reader.take_u32()? as usize;
+                entries.insert(key, reader.take(value_len)?.to_vec());
+            }
+            _ => return None,
+        }
+    }
+    Some(())
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.0.len() < len {
+            return None;
+        }
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Some(bytes)
+    }
+
+    fn take_u32(&mut self) -> Option<u32> {
+        self.take(4)
+            .map(|bytes| u32::from_be_bytes(bytes.try_into().unwrap()))
+    }
+}
+
+/// The CRC-32 (IEEE 802.3) of the `bytes`.
+fn crc32(bytes: &[u8]) -> u32 {
+    const TABLE: [u32; 256] = {
+        let mut table = [0u32; 256];
+        let mut i = 0;
+        while i < 256 {
+            let mut crc = i as u32;
+            let mut bit = 0;
+            while bit < 8 {
+                crc = if crc & 1 == 1 {
+                    (crc >> 1) ^ 0xEDB8_8320
+                } else {
+                    crc >> 1
+                };
+                bit += 1;
+            }
+            table[i] = crc;
+            i += 1;
+        }
+        table
+    };
+
+    !bytes.iter().fold(!0u32, |crc, byte| {
+        TABLE[((crc ^ *byte as u32) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+}
@@ -0,0 +1,1139 @@
+//! # OpenMLS File Storage
+//!
+//! A [`StorageProvider`] for OpenMLS that keeps all values in memory and
+//! persists every change to an append-only log file.
+//!
+//! Every write, and every transaction as a whole, is appended to the log as a
+//! single record with a checksum, and the file is synced before the write
+//! returns. When the storage is opened, the log is replayed. A record that was
+//! only partially written because the process crashed is discarded, so the
+//! storage is in the state after the last complete write. A record with a
+//! wrong checksum anywhere else means that the file was damaged, and the
+//! storage refuses to open it.
+//!
+//! When the log grows much larger than the values it contains, it is
+//! compacted: the current values are written to a new file, which then
+//! atomically replaces the log. See [`FileStorage::compact()`].
+//!
+//! Keys and values are serialized with `serde_json`, like in the memory
+//! storage. Only one [`FileStorage`] may write to a file at a time.
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard},
+};
+
+use openmls_traits::storage::*;
+use serde::{
+    de::{DeserializeOwned, IgnoredAny},
+    Serialize,
+};
+
+mod format;
+
+use format::{Entries, EntryKey, MAGIC, RECORD_HEADER_LEN};
+
+/// The log is compacted when it is larger than this and more than twice as
+/// large as the values it contains.
+const COMPACTION_THRESHOLD: u64 = 1 << 20;
+
+/// A storage that persists all values in a log file.
+#[derive(Debug)]
+pub struct FileStorage {
+    path: PathBuf,
+    state: Mutex<State>,
+}
+
+/// Errors thrown by the file storage.
+#[derive(thiserror::Error, Debug)]
+pub enum FileStorageError {
+    /// Reading or writing the file failed.
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    /// A key or value could not be (de)serialized.
+    #[error("Error serializing value: {0}")]
+    SerializationError(#[from] serde_json::Error),
+    /// The file is not a log of the file storage or was damaged.
+    #[error("The log is corrupted at offset {offset}.")]
+    Corrupted {
+        /// The offset of the first invalid record in the file.
+        offset: u64,
+    },
+    /// No transaction is in progress.
+    #[error("No transaction is in progress.")]
+    NoTransaction,
+    /// The log can't be compacted while a transaction is in progress.
+    #[error("A transaction is in progress.")]
+    TransactionInProgress,
+    /// A write failed and the partially written record could not be removed
+    /// from the log. The storage has to be reopened.
+    #[error("A failed write could not be undone. The storage has to be reopened.")]
+    Poisoned,
+}
+
+#[derive(Debug)]
+struct State {
+    path: PathBuf,
+    file: File,
+    entries: Entries,
+    /// The length of the log file.
+    len: u64,
+    /// The length the log file would have after compaction.
+    live_len: u64,
+    /// The changes of the open transactions.
+    changes: Vec<TransactionChange>,
+    /// The index into `changes` at which each open transaction begins.
+    transactions: Vec<usize>,
+    poisoned: bool,
+}
+
+/// A change made in a transaction, with the previous value to undo it.
+#[derive(Debug)]
+struct TransactionChange {
+    key: EntryKey,
+    value: Option<Vec<u8>>,
+    previous: Option<Vec<u8>>,
+}
+
+impl FileStorage {
+    /// Open the log at the given `path`, creating it if it doesn't exist.
+    ///
+    /// Returns [`FileStorageError::Corrupted`] if the file is not a log or was
+    /// damaged. An incomplete record at the end of the log, left by an
+    /// interrupted write, is removed.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, FileStorageError> {
+        let path = path.as_ref().to_path_buf();
+
+        // A compaction that was interrupted before it replaced the log.
+        match fs::remove_file(compaction_path(&path)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e.into()),
+            _ => (),
+        }
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(&path)?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)?;
+
+        let (entries, len) = if bytes.is_empty() {
+            file.write_all(MAGIC)?;
+            file.sync_all()?;
+            sync_parent_dir(&path)?;
+            (Entries::new(), MAGIC.len() as u64)
+        } else {
+            let log = format::read(&bytes, TABLES)?;
+            if log.len < bytes.len() as u64 {
+                log::warn!(
+                    "Discarding {} bytes of an interrupted write at the end of {}.",
+                    bytes.len() as u64 - log.len,
+                    path.display()
+                );
+                file.set_len(log.len)?;
+                file.sync_all()?;
+            }
+            (log.entries, log.len)
+        };
+
+        let live_len = entries
+            .iter()
+            .fold(MAGIC.len() as u64, |len, (key, value)| {
+                len + RECORD_HEADER_LEN + format::change_len(key, Some(value))
+            });
+
+        Ok(Self {
+            path: path.clone(),
+            state: Mutex::new(State {
+                path,
+                file,
+                entries,
+                len,
+                live_len,
+                changes: Vec::new(),
+                transactions: Vec::new(),
+                poisoned: false,
+            }),
+        })
+    }
+
+    /// Returns the path of the log.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Compact the log.
+    ///
+    /// The current values are written to a new file next to the log, which
+    /// then replaces the log. If the process crashes before the log is
+    /// replaced, the new file is removed the next time the storage is
+    /// opened. This happens automatically when the log becomes too large.
+    pub fn compact(&self) -> Result<(), FileStorageError> {
+        let mut state = self.state();
+        if !state.transactions.is_empty() {
+            return Err(FileStorageError::TransactionInProgress);
+        }
+        state.compact()
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap()
+    }
+
+    /// Internal helper to abstract write operations.
+    fn write(
+        &self,
+        table: &'static str,
+        key: &impl Serialize,
+        value: &impl Serialize,
+    ) -> Result<(), FileStorageError> {
+        let key = serde_json::to_vec(key)?;
+        let value = serde_json::to_vec(value)?;
+        self.state().apply(vec![((table, key), Some(value))])
+    }
+
+    /// Internal helper to abstract read operations.
+    fn read<V: DeserializeOwned>(
+        &self,
+        table: &'static str,
+        key: &impl Serialize,
+    ) -> Result<Option<V>, FileStorageError> {
+        let key = (table, serde_json::to_vec(key)?);
+        self.state()
+            .entries
+            .get(&key)
+            .map(|value| serde_json::from_slice(value))
+            .transpose()
+            .map_err(FileStorageError::from)
+    }
+
+    /// Internal helper to list the keys of a table.
+    fn keys<K: DeserializeOwned>(&self, table: &'static str) -> Result<Vec<K>, FileStorageError> {
+        self.state()
+            .entries
+            .keys()
+            .filter(|(entry_table, _)| *entry_table == table)
+            .map(|(_, key)| serde_json::from_slice(key))
+            .collect::<Result<_, _>>()
+            .map_err(FileStorageError::from)
+    }
+
+    /// Internal helper to find the nodes of the tree of a group. Returns the
+    /// entry key and the node index of each node.
+    fn tree_node_keys(
+        state: &State,
+        group_id: &impl Serialize,
+    ) -> Result<Vec<(EntryKey, u32)>, FileStorageError> {
+        let group_id = serde_json::to_value(group_id)?;
+        Ok(state
+            .entries
+            .keys()
+            .filter(|(table, _)| *table == TREE_NODES)
+            .filter_map(|key| {
+                let (node_group_id, node_index): (serde_json::Value, u32) =
+                    serde_json::from_slice(&key.1).ok()?;
+                (node_group_id == group_id).then(|| (key.clone(), node_index))
+            })
+            .collect())
+    }
+
+    /// Internal helper to abstract delete operations.
+    fn delete(&self, table: &'static str, key: &impl Serialize) -> Result<(), FileStorageError> {
+        let key = serde_json::to_vec(key)?;
+        self.state().apply(vec![((table, key), None)])
+    }
+
+    /// Internal helper to read a value that is a list.
+    fn read_list<V: DeserializeOwned>(
+        &self,
+        table: &'static str,
+        key: &impl Serialize,
+    ) -> Result<Vec<V>, FileStorageError> {
+        Ok(self.read(table, key)?.unwrap_or_default())
+    }
+
+    /// Internal helper to update a value that is a list. The value is deleted
+    /// when the list becomes empty.
+    fn update_list(
+        &self,
+        table: &'static str,
+        key: &impl Serialize,
+        f: impl FnOnce(&mut Vec<serde_json::Value>) -> Result<(), serde_json::Error>,
+    ) -> Result<(), FileStorageError> {
+        let key = (table, serde_json::to_vec(key)?);
+        let mut state = self.state();
+        let mut list: Vec<serde_json::Value> = state
+            .entries
+            .get(&key)
+            .map(|value| serde_json::from_slice(value))
+            .transpose()?
+            .unwrap_or_default();
+        f(&mut list)?;
+        let value = if list.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_vec(&list)?)
+        };
+        state.apply(vec![(key, value)])
+    }
+}
+
+impl State {
+    /// Apply the changes, and append them to the log unless a transaction is
+    /// in progress.
+    fn apply(&mut self, changes: Vec<(EntryKey, Option<Vec<u8>>)>) -> Result<(), FileStorageError> {
+        if self.poisoned {
+            return Err(FileStorageError::Poisoned);
+        }
+
+        if !self.transactions.is_empty() {
+            for (key, value) in changes {
+                let previous = self.set(key.clone(), value.clone());
+                self.changes.push(TransactionChange {
+                    key,
+                    value,
+                    previous,
+                });
+            }
+            return Ok(());
+        }
+
+        self.append(&format::encode_record(
+            changes.iter().map(|(key, value)| (key, value.as_deref())),
+        ))?;
+        for (key, value) in changes {
+            self.set(key, value);
+        }
+        self.compact_if_needed();
+        Ok(())
+    }
+
+    /// Set the value of an entry in memory and return the previous value.
+    fn set(&mut self, key: EntryKey, value: Option<Vec<u8>>) -> Option<Vec<u8>> {
+        if let Some(value) = &value {
+            self.live_len += RECORD_HEADER_LEN + format::change_len(&key, Some(value));
+        }
+        let previous = match value {
+            Some(value) => self.entries.insert(key.clone(), value),
+            None => self.entries.remove(&key),
+        };
+        if let Some(previous) = &previous {
+            self.live_len -= RECORD_HEADER_LEN + format::change_len(&key, Some(previous));
+        }
+        previous
+    }
+
+    /// Append a record to the log and sync it to the disk.
+    fn append(&mut self, record: &[u8]) -> Result<(), FileStorageError> {
+        match self
+            .file
+            .write_all(record)
+            .and_then(|()| self.file.sync_data())
+        {
+            Ok(()) => {
+                self.len += record.len() as u64;
+                Ok(())
+            }
+            Err(e) => {
+                // Remove what was written of the record, so that the next
+                // record doesn't follow a broken one.
+                if self.file.set_len(self.len).is_err() {
+                    self.poisoned = true;
+                }
+                Err(e.into())
+            }
+        }
+    }
+
+    /// Undo the changes from `start` on, in reverse order.
+    fn undo(&mut self, start: usize) {
+        let changes = self.changes.split_off(start);
+        for change in changes.into_iter().rev() {
+            self.set(change.key, change.previous);
+        }
+    }
+
+    fn compact_if_needed(&mut self) {
+        if self.len > COMPACTION_THRESHOLD && self.len > 2 * self.live_len {
+            if let Err(e) = self.compact() {
+                log::error!("Failed to compact {}: {e}", self.path.display());
+            }
+        }
+    }
+
+    fn compact(&mut self) -> Result<(), FileStorageError> {
+        if self.poisoned {
+            return Err(FileStorageError::Poisoned);
+        }
+
+        let compaction_path = compaction_path(&self.path);
+        let file = File::create(&compaction_path)?;
+        let mut writer = BufWriter::new(file);
+        writer.write_all(MAGIC)?;
+        for (key, value) in &self.entries {
+            writer.write_all(&format::encode_record([(key, Some(value.as_slice()))]))?;
+        }
+        let file = writer.into_inner().map_err(|e| e.into_error())?;
+        file.sync_all()?;
+        drop(file);
+
+        fs::rename(&compaction_path, &self.path)?;
+        // The log was replaced. From here on, the handle of the old log must
+        // not be used anymore.
+        let file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|file| sync_parent_dir(&self.path).map(|()| file));
+        match file {
+            Ok(file) => {
+                self.file = file;
+                self.len = self.live_len;
+                Ok(())
+            }
+            Err(e) => {
+                self.poisoned = true;
+                Err(e.into())
+            }
+        }
+    }
+}
+
+/// The path of the new file that is written during compaction.
+fn compaction_path(path: &Path) -> PathBuf {
+    let mut compaction_path = path.as_os_str().to_owned();
+    compaction_path.push(".compact");
+    compaction_path.into()
+}
+
+/// Sync the directory of the file at `path`, so that the creation or
+/// replacement of the file is persisted.
+#[cfg(unix)]
+fn sync_parent_dir(path: &Path) -> io::Result<()> {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => File::open(parent)?.sync_all(),
+        _ => File::open(".")?.sync_all(),
+    }
+}
+
+#[cfg(not(unix))]
+fn sync_parent_dir(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+const KEY_PACKAGES: &str = "key_packages";
+const PSKS: &str = "psks";
+const ENCRYPTION_KEY_PAIRS: &str = "encryption_key_pairs";
+const SIGNATURE_KEY_PAIRS: &str = "signature_key_pairs";
+const EPOCH_KEY_PAIRS: &str = "epoch_key_pairs";
+
+// related to PublicGroup
+const TREES: &str = "trees";
+const TREE_NODES: &str = "tree_nodes";
+const GROUP_CONTEXTS: &str = "group_contexts";
+const INTERIM_TRANSCRIPT_HASHES: &str = "interim_transcript_hashes";
+const CONFIRMATION_TAGS: &str = "confirmation_tags";
+
+// related to MlsGroup
+const JOIN_CONFIGS: &str = "join_configs";
+const OWN_LEAF_NODES: &str = "own_leaf_nodes";
+const PROPOSALS: &str = "proposals";
+const GROUP_STATES: &str = "group_states";
+const MESSAGE_SECRETS: &str = "message_secrets";
+const RESUMPTION_PSK_STORES: &str = "resumption_psk_stores";
+const OWN_LEAF_INDICES: &str = "own_leaf_indices";
+const GROUP_EPOCH_SECRETS: &str = "group_epoch_secrets";
+
+const TABLES: &[&str] = &[
+    KEY_PACKAGES,
+    PSKS,
+    ENCRYPTION_KEY_PAIRS,
+    SIGNATURE_KEY_PAIRS,
+    EPOCH_KEY_PAIRS,
+    TREES,
+    TREE_NODES,
+    GROUP_CONTEXTS,
+    INTERIM_TRANSCRIPT_HASHES,
+    CONFIRMATION_TAGS,
+    JOIN_CONFIGS,
+    OWN_LEAF_NODES,
+    PROPOSALS,
+    GROUP_STATES,
+    MESSAGE_SECRETS,
+    RESUMPTION_PSK_STORES,
+    OWN_LEAF_INDICES,
+    GROUP_EPOCH_SECRETS,
+];
+
+impl StorageProvider<CURRENT_VERSION> for FileStorage {
+    type Error = FileStorageError;
+
+    fn begin_transaction(&self) -> Result<(), Self::Error> {
+        let mut state = self.state();
+        let start = state.changes.len();
+        state.transactions.push(start);
+        Ok(())
+    }
+
+    fn commit_transaction(&self) -> Result<(), Self::Error> {
+        let mut state = self.state();
+        state
+            .transactions
+            .pop()
+            .ok_or(FileStorageError::NoTransaction)?;
+        if !state.transactions.is_empty() || state.changes.is_empty() {
+            return Ok(());
+        }
+
+        let record = format::encode_record(
+            state
+                .changes
+                .iter()
+                .map(|change| (&change.key, change.value.as_deref())),
+        );
+        let result = if state.poisoned {
+            Err(FileStorageError::Poisoned)
+        } else {
+            state.append(&record)
+        };
+        match result {
+            Ok(()) => {
+                state.changes.clear();
+                state.compact_if_needed();
+                Ok(())
+            }
+            Err(e) => {
+                state.undo(0);
+                Err(e)
+            }
+        }
+    }
+
+    fn rollback_transaction(&self) -> Result<(), Self::Error> {
+        let mut state = self.state();
+        let start = state
+            .transactions
+            .pop()
+            .ok_or(FileStorageError::NoTransaction)?;
+        state.undo(start);
+        Ok(())
+    }
+
+    fn write_mls_join_config<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        MlsGroupJoinConfig: traits::MlsGroupJoinConfig<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        config: &MlsGroupJoinConfig,
+    ) -> Result<(), Self::Error> {
+        self.write(JOIN_CONFIGS, group_id, config)
+    }
+
+    fn append_own_leaf_node<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        LeafNode: traits::LeafNode<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        leaf_node: &LeafNode,
+    ) -> Result<(), Self::Error> {
+        self.update_list(OWN_LEAF_NODES, group_id, |leaf_nodes| {
+            leaf_nodes.push(serde_json::to_value(leaf_node)?);
+            Ok(())
+        })
+    }
+
+    fn queue_proposal<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
+        QueuedProposal: traits::QueuedProposal<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        proposal_ref: &ProposalRef,
+        proposal: &QueuedProposal,
+    ) -> Result<(), Self::Error> {
+        let proposal_ref = serde_json::to_value(proposal_ref)?;
+        let entry = serde_json::to_value((&proposal_ref, proposal))?;
+        self.update_list(PROPOSALS, group_id, |proposals| {
+            match proposals
+                .iter_mut()
+                .find(|stored| stored.get(0) == Some(&proposal_ref))
+            {
+                Some(stored) => *stored = entry,
+                None => proposals.push(entry),
+            }
+            Ok(())
+        })
+    }
+
+    fn write_tree<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        TreeSync: traits::TreeSync<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        tree: &TreeSync,
+    ) -> Result<(), Self::Error> {
+        self.write(TREES, group_id, tree)
+    }
+
+    fn write_tree_node<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        TreeNode: traits::TreeNode<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        node_index: u32,
+        node: &TreeNode,
+    ) -> Result<(), Self::Error> {
+        self.write(TREE_NODES, &(group_id, node_index), node)
+    }
+
+    fn write_interim_transcript_hash<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        InterimTranscriptHash: traits::InterimTranscriptHash<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        interim_transcript_hash: &InterimTranscriptHash,
+    ) -> Result<(), Self::Error> {
+        self.write(INTERIM_TRANSCRIPT_HASHES, group_id, interim_transcript_hash)
+    }
+
+    fn write_context<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        GroupContext: traits::GroupContext<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_context: &GroupContext,
+    ) -> Result<(), Self::Error> {
+        self.write(GROUP_CONTEXTS, group_id, group_context)
+    }
+
+    fn write_confirmation_tag<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ConfirmationTag: traits::ConfirmationTag<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        confirmation_tag: &ConfirmationTag,
+    ) -> Result<(), Self::Error> {
+        self.write(CONFIRMATION_TAGS, group_id, confirmation_tag)
+    }
+
+    fn write_group_state<
+        GroupState: traits::GroupState<CURRENT_VERSION>,
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_state: &GroupState,
+    ) -> Result<(), Self::Error> {
+        self.write(GROUP_STATES, group_id, group_state)
+    }
+
+    fn write_message_secrets<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        MessageSecrets: traits::MessageSecrets<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        message_secrets: &MessageSecrets,
+    ) -> Result<(), Self::Error> {
+        self.write(MESSAGE_SECRETS, group_id, message_secrets)
+    }
+
+    fn write_resumption_psk_store<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ResumptionPskStore: traits::ResumptionPskStore<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        resumption_psk_store: &ResumptionPskStore,
+    ) -> Result<(), Self::Error> {
+        self.write(RESUMPTION_PSK_STORES, group_id, resumption_psk_store)
+    }
+
+    fn write_own_leaf_index<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        LeafNodeIndex: traits::LeafNodeIndex<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        own_leaf_index: &LeafNodeIndex,
+    ) -> Result<(), Self::Error> {
+        self.write(OWN_LEAF_INDICES, group_id, own_leaf_index)
+    }
+
+    fn write_group_epoch_secrets<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        GroupEpochSecrets: traits::GroupEpochSecrets<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_epoch_secrets: &GroupEpochSecrets,
+    ) -> Result<(), Self::Error> {
+        self.write(GROUP_EPOCH_SECRETS, group_id, group_epoch_secrets)
+    }
+
+    fn write_signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<CURRENT_VERSION>,
+        SignatureKeyPair: traits::SignatureKeyPair<CURRENT_VERSION>,
+    >(
+        &self,
+        public_key: &SignaturePublicKey,
+        signature_key_pair: &SignatureKeyPair,
+    ) -> Result<(), Self::Error> {
+        self.write(SIGNATURE_KEY_PAIRS, public_key, signature_key_pair)
+    }
+
+    fn write_encryption_key_pair<
+        EncryptionKey: traits::EncryptionKey<CURRENT_VERSION>,
+        HpkeKeyPair: traits::HpkeKeyPair<CURRENT_VERSION>,
+    >(
+        &self,
+        public_key: &EncryptionKey,
+        key_pair: &HpkeKeyPair,
+    ) -> Result<(), Self::Error> {
+        self.write(ENCRYPTION_KEY_PAIRS, public_key, key_pair)
+    }
+
+    fn write_encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        EpochKey: traits::EpochKey<CURRENT_VERSION>,
+        HpkeKeyPair: traits::HpkeKeyPair<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+        key_pairs: &[HpkeKeyPair],
+    ) -> Result<(), Self::Error> {
+        self.write(EPOCH_KEY_PAIRS, &(group_id, epoch, leaf_index), &key_pairs)
+    }
+
+    fn write_key_package<
+        HashReference: traits::HashReference<CURRENT_VERSION>,
+        KeyPackage: traits::KeyPackage<CURRENT_VERSION>,
+    >(
+        &self,
+        hash_ref: &HashReference,
+        key_package: &KeyPackage,
+    ) -> Result<(), Self::Error> {
+        self.write(KEY_PACKAGES, hash_ref, key_package)
+    }
+
+    fn write_psk<
+        PskId: traits::PskId<CURRENT_VERSION>,
+        PskBundle: traits::PskBundle<CURRENT_VERSION>,
+    >(
+        &self,
+        psk_id: &PskId,
+        psk: &PskBundle,
+    ) -> Result<(), Self::Error> {
+        self.write(PSKS, psk_id, psk)
+    }
+
+    fn mls_group_join_config<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        MlsGroupJoinConfig: traits::MlsGroupJoinConfig<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<MlsGroupJoinConfig>, Self::Error> {
+        self.read(JOIN_CONFIGS, group_id)
+    }
+
+    fn own_leaf_nodes<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        LeafNode: traits::LeafNode<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<LeafNode>, Self::Error> {
+        self.read_list(OWN_LEAF_NODES, group_id)
+    }
+
+    fn queued_proposal_refs<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<ProposalRef>, Self::Error> {
+        let proposals: Vec<(ProposalRef, IgnoredAny)> = self.read_list(PROPOSALS, group_id)?;
+        Ok(proposals
+            .into_iter()
+            .map(|(proposal_ref, _)| proposal_ref)
+            .collect())
+    }
+
+    fn queued_proposals<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
+        QueuedProposal: traits::QueuedProposal<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<(ProposalRef, QueuedProposal)>, Self::Error> {
+        self.read_list(PROPOSALS, group_id)
+    }
+
+    fn tree<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        TreeSync: traits::TreeSync<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<TreeSync>, Self::Error> {
+        self.read(TREES, group_id)
+    }
+
+    fn tree_nodes<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        TreeNode: traits::TreeNode<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<(u32, TreeNode)>, Self::Error> {
+        let state = self.state();
+        Self::tree_node_keys(&state, group_id)?
+            .into_iter()
+            .map(|(key, node_index)| {
+                Ok((node_index, serde_json::from_slice(&state.entries[&key])?))
+            })
+            .collect()
+    }
+
+    fn group_context<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        GroupContext: traits::GroupContext<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<GroupContext>, Self::Error> {
+        self.read(GROUP_CONTEXTS, group_id)
+    }
+
+    fn interim_transcript_hash<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        InterimTranscriptHash: traits::InterimTranscriptHash<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<InterimTranscriptHash>, Self::Error> {
+        self.read(INTERIM_TRANSCRIPT_HASHES, group_id)
+    }
+
+    fn confirmation_tag<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ConfirmationTag: traits::ConfirmationTag<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<ConfirmationTag>, Self::Error> {
+        self.read(CONFIRMATION_TAGS, group_id)
+    }
+
+    fn group_state<
+        GroupState: traits::GroupState<CURRENT_VERSION>,
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<GroupState>, Self::Error> {
+        self.read(GROUP_STATES, group_id)
+    }
+
+    fn message_secrets<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        MessageSecrets: traits::MessageSecrets<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<MessageSecrets>, Self::Error> {
+        self.read(MESSAGE_SECRETS, group_id)
+    }
+
+    fn resumption_psk_store<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ResumptionPskStore: traits::ResumptionPskStore<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<ResumptionPskStore>, Self::Error> {
+        self.read(RESUMPTION_PSK_STORES, group_id)
+    }
+
+    fn own_leaf_index<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        LeafNodeIndex: traits::LeafNodeIndex<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<LeafNodeIndex>, Self::Error> {
+        self.read(OWN_LEAF_INDICES, group_id)
+    }
+
+    fn group_epoch_secrets<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        GroupEpochSecrets: traits::GroupEpochSecrets<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<GroupEpochSecrets>, Self::Error> {
+        self.read(GROUP_EPOCH_SECRETS, group_id)
+    }
+
+    fn signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<CURRENT_VERSION>,
+        SignatureKeyPair: traits::SignatureKeyPair<CURRENT_VERSION>,
+    >(
+        &self,
+        public_key: &SignaturePublicKey,
+    ) -> Result<Option<SignatureKeyPair>, Self::Error> {
+        self.read(SIGNATURE_KEY_PAIRS, public_key)
+    }
+
+    fn encryption_key_pair<
+        HpkeKeyPair: traits::HpkeKeyPair<CURRENT_VERSION>,
+        EncryptionKey: traits::EncryptionKey<CURRENT_VERSION>,
+    >(
+        &self,
+        public_key: &EncryptionKey,
+    ) -> Result<Option<HpkeKeyPair>, Self::Error> {
+        self.read(ENCRYPTION_KEY_PAIRS, public_key)
+    }
+
+    fn encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        EpochKey: traits::EpochKey<CURRENT_VERSION>,
+        HpkeKeyPair: traits::HpkeKeyPair<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+    ) -> Result<Vec<HpkeKeyPair>, Self::Error> {
+        self.read_list(EPOCH_KEY_PAIRS, &(group_id, epoch, leaf_index))
+    }
+
+    fn key_package<
+        KeyPackageRef: traits::HashReference<CURRENT_VERSION>,
+        KeyPackage: traits::KeyPackage<CURRENT_VERSION>,
+    >(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> Result<Option<KeyPackage>, Self::Error> {
+        self.read(KEY_PACKAGES, hash_ref)
+    }
+
+    fn psk<PskBundle: traits::PskBundle<CURRENT_VERSION>, PskId: traits::PskId<CURRENT_VERSION>>(
+        &self,
+        psk_id: &PskId,
+    ) -> Result<Option<PskBundle>, Self::Error> {
+        self.read(PSKS, psk_id)
+    }
+
+    fn group_ids<GroupId: traits::GroupId<CURRENT_VERSION> + DeserializeOwned>(
+        &self,
+    ) -> Result<Vec<GroupId>, Self::Error> {
+        self.keys(GROUP_STATES)
+    }
+
+    fn encryption_epoch_key_pair_ids<
+        GroupId: traits::GroupId<CURRENT_VERSION> + DeserializeOwned,
+        EpochKey: traits::EpochKey<CURRENT_VERSION> + DeserializeOwned,
+    >(
+        &self,
+    ) -> Result<Vec<(GroupId, EpochKey, u32)>, Self::Error> {
+        self.keys(EPOCH_KEY_PAIRS)
+    }
+
+    fn key_package_refs<
+        KeyPackageRef: traits::HashReference<CURRENT_VERSION> + DeserializeOwned,
+    >(
+        &self,
+    ) -> Result<Vec<KeyPackageRef>, Self::Error> {
+        self.keys(KEY_PACKAGES)
+    }
+
+    fn psk_ids<PskId: traits::PskId<CURRENT_VERSION> + DeserializeOwned>(
+        &self,
+    ) -> Result<Vec<PskId>, Self::Error> {
+        self.keys(PSKS)
+    }
+
+    fn remove_proposal<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        proposal_ref: &ProposalRef,
+    ) -> Result<(), Self::Error> {
+        let proposal_ref = serde_json::to_value(proposal_ref)?;
+        self.update_list(PROPOSALS, group_id, |proposals| {
+            proposals.retain(|stored| stored.get(0) != Some(&proposal_ref));
+            Ok(())
+        })
+    }
+
+    fn delete_own_leaf_nodes<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(OWN_LEAF_NODES, group_id)
+    }
+
+    fn delete_group_config<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(JOIN_CONFIGS, group_id)
+    }
+
+    fn delete_tree<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(TREES, group_id)
+    }
+
+    fn delete_tree_node<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+        node_index: u32,
+    ) -> Result<(), Self::Error> {
+        self.delete(TREE_NODES, &(group_id, node_index))
+    }
+
+    fn delete_tree_nodes<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        let mut state = self.state();
+        let changes = Self::tree_node_keys(&state, group_id)?
+            .into_iter()
+            .map(|(key, _)| (key, None))
+            .collect::<Vec<_>>();
+        if changes.is_empty() {
+            return Ok(());
+        }
+        state.apply(changes)
+    }
+
+    fn delete_confirmation_tag<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(CONFIRMATION_TAGS, group_id)
+    }
+
+    fn delete_group_state<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(GROUP_STATES, group_id)
+    }
+
+    fn delete_context<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(GROUP_CONTEXTS, group_id)
+    }
+
+    fn delete_interim_transcript_hash<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(INTERIM_TRANSCRIPT_HASHES, group_id)
+    }
+
+    fn delete_message_secrets<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(MESSAGE_SECRETS, group_id)
+    }
+
+    fn delete_all_resumption_psk_secrets<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(RESUMPTION_PSK_STORES, group_id)
+    }
+
+    fn delete_own_leaf_index<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(OWN_LEAF_INDICES, group_id)
+    }
+
+    fn delete_group_epoch_secrets<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(GROUP_EPOCH_SECRETS, group_id)
+    }
+
+    fn clear_proposal_queue<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(PROPOSALS, group_id)
+    }
+
+    fn delete_signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<CURRENT_VERSION>,
+    >(
+        &self,
+        public_key: &SignaturePublicKey,
+    ) -> Result<(), Self::Error> {
+        self.delete(SIGNATURE_KEY_PAIRS, public_key)
+    }
+
+    fn delete_encryption_key_pair<EncryptionKey: traits::EncryptionKey<CURRENT_VERSION>>(
+        &self,
+        public_key: &EncryptionKey,
+    ) -> Result<(), Self::Error> {
+        self.delete(ENCRYPTION_KEY_PAIRS, public_key)
+    }
+
+    fn delete_encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        EpochKey: traits::EpochKey<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+    ) -> Result<(), Self::Error> {
+        self.delete(EPOCH_KEY_PAIRS, &(group_id, epoch, leaf_index))
+    }
+
+    fn delete_key_package<KeyPackageRef: traits::HashReference<CURRENT_VERSION>>(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> Result<(), Self::Error> {
+        self.delete(KEY_PACKAGES, hash_ref)
+    }
+
+    fn delete_psk<PskKey: traits::PskId<CURRENT_VERSION>>(
+        &self,
+        psk_id: &PskKey,
+    ) -> Result<(), Self::Error> {
+        self.delete(PSKS, psk_id)
+    }
+}
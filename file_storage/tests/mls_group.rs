@@ -0,0 +1,74 @@
+//! Runs the group operations of the storage stability KAT with the file
+//! storage and checks after every step that the group loaded from a reopened
+//! log matches the group in memory.
+
+use std::path::Path;
+
+use openmls::{prelude::*, test_utils::storage_stability::run_storage_stability};
+use openmls_file_storage::FileStorage;
+use openmls_rust_crypto::RustCrypto;
+use openmls_traits::OpenMlsProvider;
+
+struct FileProvider {
+    crypto: RustCrypto,
+    storage: FileStorage,
+}
+
+impl FileProvider {
+    fn open(path: &Path) -> Self {
+        Self {
+            crypto: RustCrypto::default(),
+            storage: FileStorage::open(path).unwrap(),
+        }
+    }
+}
+
+impl OpenMlsProvider for FileProvider {
+    type CryptoProvider = RustCrypto;
+    type RandProvider = RustCrypto;
+    type StorageProvider = FileStorage;
+
+    fn storage(&self) -> &Self::StorageProvider {
+        &self.storage
+    }
+
+    fn crypto(&self) -> &Self::CryptoProvider {
+        &self.crypto
+    }
+
+    fn rand(&self) -> &Self::RandProvider {
+        &self.crypto
+    }
+}
+
+fn check_stored_group_equality(path: &Path, group: &MlsGroup) {
+    let provider = FileProvider::open(path);
+    let loaded_group = MlsGroup::load(provider.storage(), group.group_id())
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(group, &loaded_group);
+}
+
+#[test]
+fn storage_stability() {
+    let ciphersuite = Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519;
+    let dir = tempfile::tempdir().unwrap();
+    let alice_path = dir.path().join("alice.log");
+
+    let mut alice_group = run_storage_stability(
+        ciphersuite,
+        &FileProvider::open(&alice_path),
+        &FileProvider::open(&dir.path().join("bob.log")),
+        &FileProvider::open(&dir.path().join("charlie.log")),
+        |group| check_stored_group_equality(&alice_path, group),
+    );
+
+    // Deleting the group removes it from the log.
+    let provider = FileProvider::open(&alice_path);
+    alice_group.delete(provider.storage()).unwrap();
+    let provider = FileProvider::open(&alice_path);
+    assert!(MlsGroup::load(provider.storage(), alice_group.group_id())
+        .unwrap()
+        .is_none());
+}
@@ -0,0 +1,352 @@
+use std::{
+    cell::Cell,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::Path,
+};
+
+use openmls_file_storage::{FileStorage, FileStorageError};
+use openmls_memory_storage::test_suite;
+use openmls_traits::storage::{
+    traits::{self},
+    Entity, Key, StorageProvider, CURRENT_VERSION,
+};
+use serde::{Deserialize, Serialize};
+
+// Test types
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+struct TestGroupId(Vec<u8>);
+impl traits::GroupId<CURRENT_VERSION> for TestGroupId {}
+impl Key<CURRENT_VERSION> for TestGroupId {}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+struct TestTree(Vec<u8>);
+impl traits::TreeSync<CURRENT_VERSION> for TestTree {}
+impl Entity<CURRENT_VERSION> for TestTree {}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+struct LeafNode(Vec<u8>);
+impl traits::LeafNode<CURRENT_VERSION> for LeafNode {}
+impl Entity<CURRENT_VERSION> for LeafNode {}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+struct ProposalRef(u8);
+impl traits::ProposalRef<CURRENT_VERSION> for ProposalRef {}
+impl Key<CURRENT_VERSION> for ProposalRef {}
+impl Entity<CURRENT_VERSION> for ProposalRef {}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+struct Proposal(Vec<u8>);
+impl traits::QueuedProposal<CURRENT_VERSION> for Proposal {}
+impl Entity<CURRENT_VERSION> for Proposal {}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+struct GroupState(u8);
+impl traits::GroupState<CURRENT_VERSION> for GroupState {}
+impl Entity<CURRENT_VERSION> for GroupState {}
+
+fn file_len(path: &Path) -> u64 {
+    fs::metadata(path).unwrap().len()
+}
+
+/// The storage passes the storage test suite of `openmls_memory_storage`.
+#[test]
+fn storage_provider() {
+    let dir = tempfile::tempdir().unwrap();
+    let storages = Cell::new(0);
+    test_suite::run(|| {
+        storages.set(storages.get() + 1);
+        FileStorage::open(dir.path().join(format!("openmls{}.log", storages.get()))).unwrap()
+    });
+}
+
+/// Values survive closing and reopening the log.
+#[test]
+fn persistence() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("openmls.log");
+    let group_id = TestGroupId(b"TestGroupId".to_vec());
+    let tree = TestTree(b"TestTree".to_vec());
+
+    let storage = FileStorage::open(&path).unwrap();
+    assert_eq!(storage.path(), path);
+    storage.write_tree(&group_id, &tree).unwrap();
+    drop(storage);
+
+    let storage = FileStorage::open(&path).unwrap();
+    let tree_read: Option<TestTree> = storage.tree(&group_id).unwrap();
+    assert_eq!(Some(tree.clone()), tree_read);
+
+    // Overwrite and delete the value.
+    let new_tree = TestTree(b"NewTestTree".to_vec());
+    storage.write_tree(&group_id, &new_tree).unwrap();
+    let storage = FileStorage::open(&path).unwrap();
+    let tree_read: Option<TestTree> = storage.tree(&group_id).unwrap();
+    assert_eq!(Some(new_tree), tree_read);
+
+    storage.delete_tree(&group_id).unwrap();
+    let storage = FileStorage::open(&path).unwrap();
+    let tree_read: Option<TestTree> = storage.tree(&group_id).unwrap();
+    assert_eq!(None, tree_read);
+}
+
+/// Only the changed values are appended to the log.
+#[test]
+fn appends_changes() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("openmls.log");
+    let storage = FileStorage::open(&path).unwrap();
+
+    let large_tree = TestTree(vec![0; 10_000]);
+    storage
+        .write_tree(&TestGroupId(b"Large".to_vec()), &large_tree)
+        .unwrap();
+    let len = file_len(&path);
+
+    storage
+        .write_group_state(&TestGroupId(b"Small".to_vec()), &GroupState(1))
+        .unwrap();
+    assert!(file_len(&path) - len < 100);
+}
+
+/// Own leaf nodes and queued proposals are returned in the order they were
+/// added.
+#[test]
+fn lists() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("openmls.log");
+    let group_id = TestGroupId(b"TestGroupId".to_vec());
+    let storage = FileStorage::open(&path).unwrap();
+
+    let leaf_nodes = (0..5)
+        .map(|i| LeafNode(format!("LeafNode{i}").into_bytes()))
+        .collect::<Vec<_>>();
+    for leaf_node in &leaf_nodes {
+        storage.append_own_leaf_node(&group_id, leaf_node).unwrap();
+    }
+    let proposals = (0..5)
+        .map(|i| {
+            (
+                ProposalRef(i),
+                Proposal(format!("Proposal{i}").into_bytes()),
+            )
+        })
+        .collect::<Vec<_>>();
+    for (proposal_ref, proposal) in &proposals {
+        storage
+            .queue_proposal(&group_id, proposal_ref, proposal)
+            .unwrap();
+    }
+    storage.remove_proposal(&group_id, &ProposalRef(2)).unwrap();
+
+    let storage = FileStorage::open(&path).unwrap();
+    let leaf_nodes_read: Vec<LeafNode> = storage.own_leaf_nodes(&group_id).unwrap();
+    assert_eq!(leaf_nodes, leaf_nodes_read);
+    let proposals_read: Vec<(ProposalRef, Proposal)> = storage.queued_proposals(&group_id).unwrap();
+    let expected = proposals
+        .iter()
+        .filter(|(proposal_ref, _)| proposal_ref.0 != 2)
+        .cloned()
+        .collect::<Vec<_>>();
+    assert_eq!(expected, proposals_read);
+    let refs_read: Vec<ProposalRef> = storage.queued_proposal_refs(&group_id).unwrap();
+    assert_eq!(
+        expected
+            .into_iter()
+            .map(|(proposal_ref, _)| proposal_ref)
+            .collect::<Vec<_>>(),
+        refs_read
+    );
+
+    storage.delete_own_leaf_nodes(&group_id).unwrap();
+    storage
+        .clear_proposal_queue::<TestGroupId, ProposalRef>(&group_id)
+        .unwrap();
+    let leaf_nodes_read: Vec<LeafNode> = storage.own_leaf_nodes(&group_id).unwrap();
+    assert!(leaf_nodes_read.is_empty());
+    let proposals_read: Vec<(ProposalRef, Proposal)> = storage.queued_proposals(&group_id).unwrap();
+    assert!(proposals_read.is_empty());
+}
+
+/// Transactions are committed and rolled back atomically and can be nested.
+#[test]
+fn transactions() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("openmls.log");
+    let group_id = TestGroupId(b"TestGroupId".to_vec());
+    let tree = TestTree(b"TestTree".to_vec());
+    let leaf_node = LeafNode(b"LeafNode".to_vec());
+
+    let storage = FileStorage::open(&path).unwrap();
+    storage.begin_transaction().unwrap();
+    storage.write_tree(&group_id, &tree).unwrap();
+    storage
+        .write_group_state(&group_id, &GroupState(1))
+        .unwrap();
+
+    // Nothing is written before the transaction is committed.
+    let len = file_len(&path);
+    let tree_read: Option<TestTree> = FileStorage::open(&path).unwrap().tree(&group_id).unwrap();
+    assert_eq!(None, tree_read);
+    storage.commit_transaction().unwrap();
+    assert!(file_len(&path) > len);
+
+    // A rolled back transaction discards its writes, including those of a
+    // committed nested transaction.
+    storage.begin_transaction().unwrap();
+    storage.delete_tree(&group_id).unwrap();
+    storage.begin_transaction().unwrap();
+    storage.append_own_leaf_node(&group_id, &leaf_node).unwrap();
+    storage.commit_transaction().unwrap();
+    assert!(matches!(
+        storage.compact(),
+        Err(FileStorageError::TransactionInProgress)
+    ));
+    storage.rollback_transaction().unwrap();
+
+    let tree_read: Option<TestTree> = storage.tree(&group_id).unwrap();
+    assert_eq!(Some(tree.clone()), tree_read);
+    let leaf_nodes_read: Vec<LeafNode> = storage.own_leaf_nodes(&group_id).unwrap();
+    assert!(leaf_nodes_read.is_empty());
+
+    let storage = FileStorage::open(&path).unwrap();
+    let tree_read: Option<TestTree> = storage.tree(&group_id).unwrap();
+    assert_eq!(Some(tree), tree_read);
+    let leaf_nodes_read: Vec<LeafNode> = storage.own_leaf_nodes(&group_id).unwrap();
+    assert!(leaf_nodes_read.is_empty());
+
+    assert!(matches!(
+        storage.commit_transaction(),
+        Err(FileStorageError::NoTransaction)
+    ));
+}
+
+/// A record that was cut off by a crash is discarded, and the log can be
+/// written again.
+#[test]
+fn interrupted_write() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("openmls.log");
+    let group_id = TestGroupId(b"TestGroupId".to_vec());
+    let tree = TestTree(b"TestTree".to_vec());
+
+    let storage = FileStorage::open(&path).unwrap();
+    storage.write_tree(&group_id, &tree).unwrap();
+    let len = file_len(&path);
+    storage
+        .write_tree(&group_id, &TestTree(b"NewTestTree".to_vec()))
+        .unwrap();
+    drop(storage);
+
+    // Cut off the last record at every possible length.
+    let bytes = fs::read(&path).unwrap();
+    for cut in len..bytes.len() as u64 {
+        fs::write(&path, &bytes[..cut as usize]).unwrap();
+        let storage = FileStorage::open(&path).unwrap();
+        assert_eq!(file_len(&path), len);
+        let tree_read: Option<TestTree> = storage.tree(&group_id).unwrap();
+        assert_eq!(Some(tree.clone()), tree_read);
+    }
+
+    // A last record with garbage instead of the payload is discarded as well.
+    let mut garbage = bytes.clone();
+    for byte in &mut garbage[len as usize + 8..] {
+        *byte = 0;
+    }
+    fs::write(&path, &garbage).unwrap();
+    let storage = FileStorage::open(&path).unwrap();
+    let tree_read: Option<TestTree> = storage.tree(&group_id).unwrap();
+    assert_eq!(Some(tree.clone()), tree_read);
+
+    let new_tree = TestTree(b"NewerTestTree".to_vec());
+    storage.write_tree(&group_id, &new_tree).unwrap();
+    let storage = FileStorage::open(&path).unwrap();
+    let tree_read: Option<TestTree> = storage.tree(&group_id).unwrap();
+    assert_eq!(Some(new_tree), tree_read);
+}
+
+/// A damaged record that is followed by other records is reported.
+#[test]
+fn corruption() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("openmls.log");
+    let group_id = TestGroupId(b"TestGroupId".to_vec());
+
+    let storage = FileStorage::open(&path).unwrap();
+    storage
+        .write_tree(&group_id, &TestTree(b"TestTree".to_vec()))
+        .unwrap();
+    storage
+        .write_group_state(&group_id, &GroupState(1))
+        .unwrap();
+    drop(storage);
+
+    let mut bytes = fs::read(&path).unwrap();
+    bytes[20] ^= 0xff;
+    fs::write(&path, &bytes).unwrap();
+    assert!(matches!(
+        FileStorage::open(&path),
+        Err(FileStorageError::Corrupted { offset: 8 })
+    ));
+
+    // Not a log at all.
+    fs::write(&path, b"{\"values\": {}}").unwrap();
+    assert!(matches!(
+        FileStorage::open(&path),
+        Err(FileStorageError::Corrupted { offset: 0 })
+    ));
+}
+
+/// Compaction shrinks the log to the current values, and a compaction that
+/// was interrupted is cleaned up.
+#[test]
+fn compaction() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("openmls.log");
+    let group_id = TestGroupId(b"TestGroupId".to_vec());
+
+    let storage = FileStorage::open(&path).unwrap();
+    for i in 0..100u8 {
+        storage
+            .write_tree(&group_id, &TestTree(vec![i; 1000]))
+            .unwrap();
+    }
+    let len = file_len(&path);
+    storage.compact().unwrap();
+    assert!(file_len(&path) < len / 50);
+
+    // The storage keeps working with the new file.
+    storage
+        .write_group_state(&group_id, &GroupState(7))
+        .unwrap();
+    drop(storage);
+
+    let mut compaction_path = path.clone().into_os_string();
+    compaction_path.push(".compact");
+    OpenOptions::new()
+        .create(true)
+        .truncate(true)
+        .write(true)
+        .open(&compaction_path)
+        .unwrap()
+        .write_all(b"partial")
+        .unwrap();
+
+    let storage = FileStorage::open(&path).unwrap();
+    assert!(!Path::new(&compaction_path).exists());
+    let tree_read: Option<TestTree> = storage.tree(&group_id).unwrap();
+    assert_eq!(Some(TestTree(vec![99; 1000])), tree_read);
+    let group_state_read: Option<GroupState> = storage.group_state(&group_id).unwrap();
+    assert_eq!(Some(GroupState(7)), group_state_read);
+
+    // The log is compacted automatically when it grows too large.
+    for i in 0..200u8 {
+        storage
+            .write_tree(&group_id, &TestTree(vec![i; 10_000]))
+            .unwrap();
+    }
+    assert!(file_len(&path) < 2 << 20);
+    let storage = FileStorage::open(&path).unwrap();
+    let tree_read: Option<TestTree> = storage.tree(&group_id).unwrap();
+    assert_eq!(Some(TestTree(vec![199; 10_000])), tree_read);
+}
@@ -14,13 +14,18 @@ use mls_client::{
 };
 use mls_interop_proto::mls_client;
 use openmls::{
-    credentials::{BasicCredential, Credential, CredentialType, CredentialWithKey},
+    credentials::{
+        AuthenticationService, BasicCredential, Credential, CredentialSource, CredentialType,
+        CredentialWithKey,
+    },
+    extensions::{Extension, Extensions},
     framing::{MlsMessageBodyIn, MlsMessageIn, MlsMessageOut, ProcessedMessageContent},
     group::{
         GroupEpoch, GroupId, MlsGroup, MlsGroupCreateConfig, MlsGroupJoinConfig, StagedWelcome,
         WireFormatPolicy, PURE_CIPHERTEXT_WIRE_FORMAT_POLICY, PURE_PLAINTEXT_WIRE_FORMAT_POLICY,
     },
     key_packages::{KeyPackage, KeyPackageBundle},
+    messages::proposals::ReInitProposal,
     prelude::{Capabilities, ExtensionType, SenderRatchetConfiguration},
     schedule::{psk::ResumptionPskUsage, ExternalPsk, PreSharedKeyId, Psk},
     treesync::{LeafNodeParameters, RatchetTreeIn},
@@ -29,7 +34,7 @@ use openmls::{
 use openmls_basic_credential::SignatureKeyPair;
 use openmls_rust_crypto::OpenMlsRustCrypto;
 use openmls_traits::{random::OpenMlsRand, types::Ciphersuite, OpenMlsProvider};
-use tls_codec::{Deserialize, Serialize};
+use tls_codec::{Deserialize, Serialize, VLBytes};
 use tonic::{async_trait, transport::Server, Code, Request, Response, Status};
 use tracing::{debug, error, info, instrument, trace, Span};
 use tracing_subscriber::EnvFilter;
@@ -44,6 +49,21 @@ const EXTENSION_TYPES: [ExtensionType; 5] = [
     ExtensionType::RatchetTree,
 ];
 
+/// The authentication service of the interop client. Credentials are not
+/// validated, because the test harness only uses basic credentials with
+/// identities it made up.
+struct AcceptAllCredentials;
+
+impl AuthenticationService for AcceptAllCredentials {
+    fn authenticate(
+        &self,
+        _credential: &CredentialWithKey,
+        _source: CredentialSource,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+}
+
 /// This struct contains the state for a single MLS client. The interop client
 /// doesn't consider scenarios where a credential is re-used across groups, so
 /// this simple structure is sufficient.
@@ -62,6 +82,17 @@ type PendingState = (
     OpenMlsRustCrypto,
 );
 
+/// The state of a member of a reinitialized group until it created or joined
+/// the successor group. Its `reinit_id` is the index in
+/// [`MlsClientImpl::pending_reinits`].
+struct PendingReInit {
+    /// The `state_id` of the reinitialized group.
+    state_id: u32,
+    /// The credential and signature keys of the member in the successor group.
+    credential_with_key: CredentialWithKey,
+    signature_keys: SignatureKeyPair,
+}
+
 /// This is the main state struct of the interop client. It keeps track of the
 /// individual MLS clients, as well as pending key packages that it was told to
 /// create. It also contains a transaction id map, that maps the `u32`
@@ -70,6 +101,7 @@ pub struct MlsClientImpl {
     groups: Mutex<Vec<InteropGroup>>,
     pending_state: Mutex<HashMap<Vec<u8>, PendingState>>,
     transaction_id_map: Mutex<HashMap<u32, Vec<u8>>>, // Indirection, linking to pending key packages
+    pending_reinits: Mutex<Vec<PendingReInit>>,
 }
 
 impl MlsClientImpl {
@@ -79,8 +111,96 @@ impl MlsClientImpl {
             groups: Mutex::new(Vec::new()),
             pending_state: Mutex::new(HashMap::new()),
             transaction_id_map: Mutex::new(HashMap::new()),
+            pending_reinits: Mutex::new(Vec::new()),
         }
     }
+
+    /// Prepares the successor of the reinitialized group `state_id`. This
+    /// creates the key package of this client for the successor group and
+    /// returns it together with the `reinit_id` for creating or joining it.
+    fn prepare_reinit(
+        &self,
+        state_id: u32,
+        interop_group: &InteropGroup,
+    ) -> Result<HandleReInitCommitResponse, Status> {
+        let group = &interop_group.group;
+        let reinit_proposal = group
+            .reinit_proposal()
+            .ok_or_else(|| Status::aborted("the commit did not reinitialize the group"))?;
+        let ciphersuite = reinit_proposal.ciphersuite();
+
+        let signature_keys =
+            SignatureKeyPair::new(ciphersuite.signature_algorithm()).map_err(into_status)?;
+        let credential_with_key = CredentialWithKey {
+            credential: group.credential().map_err(into_status)?.clone(),
+            signature_key: signature_keys.public().into(),
+        };
+
+        // The key package is stored next to the reinitialized group, which
+        // the successor group is joined from.
+        let key_package = KeyPackage::builder()
+            .leaf_node_capabilities(leaf_node_capabilities())
+            .build(
+                ciphersuite,
+                &interop_group.crypto_provider,
+                &signature_keys,
+                credential_with_key.clone(),
+            )
+            .map_err(into_status)?;
+        let key_package: MlsMessageOut = key_package.key_package().clone().into();
+
+        let mut pending_reinits = self.pending_reinits.lock().unwrap();
+        let reinit_id = pending_reinits.len() as u32;
+        pending_reinits.push(PendingReInit {
+            state_id,
+            credential_with_key,
+            signature_keys,
+        });
+
+        Ok(HandleReInitCommitResponse {
+            reinit_id,
+            key_package: key_package
+                .tls_serialize_detached()
+                .map_err(|_| Status::aborted("failed to serialize key package"))?,
+            epoch_authenticator: group.epoch_authenticator().as_slice().to_vec(),
+        })
+    }
+}
+
+impl InteropGroup {
+    /// Processes the proposals by reference of a commit and stores them as
+    /// pending proposals.
+    fn store_proposals(&mut self, proposals: &[Vec<u8>]) -> Result<(), Status> {
+        for proposal in proposals {
+            let message = MlsMessageIn::tls_deserialize(&mut proposal.as_slice())
+                .map_err(|_| Status::aborted("failed to deserialize proposal"))?;
+            if self.messages_out.contains(&message) {
+                trace!("Skipping processing of own proposal");
+                continue;
+            }
+            let processed_message = self
+                .group
+                .process_message(
+                    &self.crypto_provider,
+                    message.try_into_protocol_message().map_err(|_| {
+                        Status::invalid_argument("proposal is not a protocol message")
+                    })?,
+                    &AcceptAllCredentials,
+                )
+                .map_err(into_status)?;
+            match processed_message.into_content() {
+                ProcessedMessageContent::ProposalMessage(proposal) => {
+                    self.group
+                        .store_pending_proposal(self.crypto_provider.storage(), *proposal)
+                        .map_err(|err| {
+                            tonic::Status::internal(format!("error storing proposal: {err}"))
+                        })?;
+                }
+                _ => return Err(Status::invalid_argument("message is not a proposal")),
+            }
+        }
+        Ok(())
+    }
 }
 
 fn into_status<E: Display>(e: E) -> Status {
@@ -150,6 +270,48 @@ fn ratchet_tree_from_config(bytes: Vec<u8>) -> Option<RatchetTreeIn> {
     }
 }
 
+/// The capabilities of the leaf nodes of this client.
+fn leaf_node_capabilities() -> Capabilities {
+    Capabilities::new(
+        Some(&[ProtocolVersion::Mls10, ProtocolVersion::Other(999)]),
+        Some(&[
+            Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519,
+            Ciphersuite::MLS_128_DHKEMP256_AES128GCM_SHA256_P256,
+            Ciphersuite::MLS_128_DHKEMX25519_CHACHA20POLY1305_SHA256_Ed25519,
+        ]),
+        Some(&EXTENSION_TYPES),
+        None,
+        Some(&CREDENTIAL_TYPES),
+    )
+}
+
+/// Parses the key packages in a request.
+fn to_key_packages(key_packages: &[Vec<u8>]) -> Result<Vec<KeyPackage>, Status> {
+    key_packages
+        .iter()
+        .map(|key_package| {
+            MlsMessageIn::tls_deserialize_exact(key_package)
+                .map_err(|_| Status::invalid_argument("Invalid key package"))?
+                .into_keypackage()
+                .ok_or(Status::invalid_argument("Message was not a key package"))
+        })
+        .collect()
+}
+
+/// Parses the extensions in a request.
+fn to_extensions(extensions: &[mls_client::Extension]) -> Result<Extensions, Status> {
+    let extensions = extensions
+        .iter()
+        .map(|extension| {
+            let mut bytes = (extension.extension_type as u16).to_be_bytes().to_vec();
+            VLBytes::new(extension.extension_data.clone()).tls_serialize(&mut bytes)?;
+            Extension::tls_deserialize_exact(bytes)
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| Status::invalid_argument("failed to deserialize extension"))?;
+    Extensions::from_vec(extensions).map_err(into_status)
+}
+
 fn bytes_to_string<B>(bytes: B) -> String
 where
     B: AsRef<[u8]>,
@@ -281,17 +443,7 @@ impl MlsClient for MlsClientImpl {
         let signature_keys = SignatureKeyPair::new(ciphersuite.signature_algorithm()).unwrap();
 
         let key_package = KeyPackage::builder()
-            .leaf_node_capabilities(Capabilities::new(
-                Some(&[ProtocolVersion::Mls10, ProtocolVersion::Other(999)]),
-                Some(&[
-                    Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519,
-                    Ciphersuite::MLS_128_DHKEMP256_AES128GCM_SHA256_P256,
-                    Ciphersuite::MLS_128_DHKEMX25519_CHACHA20POLY1305_SHA256_Ed25519,
-                ]),
-                Some(&EXTENSION_TYPES),
-                None,
-                Some(&CREDENTIAL_TYPES),
-            ))
+            .leaf_node_capabilities(leaf_node_capabilities())
             .build(
                 ciphersuite,
                 &crypto_provider,
@@ -399,6 +551,7 @@ impl MlsClient for MlsClientImpl {
             &mls_group_config,
             welcome,
             ratchet_tree,
+            &AcceptAllCredentials,
         )
         .map_err(into_status)?
         .into_group(&crypto_provider)
@@ -499,6 +652,7 @@ impl MlsClient for MlsClientImpl {
                 None,
                 b"",
                 credential_with_key,
+                &AcceptAllCredentials,
             )
             .unwrap();
 
@@ -651,6 +805,7 @@ impl MlsClient for MlsClientImpl {
             .process_message(
                 &interop_group.crypto_provider,
                 message.try_into_protocol_message().unwrap(),
+                &AcceptAllCredentials,
             )
             .map_err(into_status)?;
         debug!("Processed.");
@@ -912,10 +1067,36 @@ impl MlsClient for MlsClientImpl {
         let request = request.get_ref();
         info!(?request, "Request");
 
-        let response = Status::unimplemented("Re-init is not implemented");
+        let mut groups = self.groups.lock().unwrap();
+        let interop_group = groups
+            .get_mut(request.state_id as usize)
+            .ok_or_else(|| Status::new(Code::InvalidArgument, "unknown state_id"))?;
+
+        let reinit_proposal = ReInitProposal::new(
+            GroupId::from_slice(&request.group_id),
+            ProtocolVersion::Mls10,
+            *to_ciphersuite(request.cipher_suite)?,
+            to_extensions(&request.extensions)?,
+        );
+        let (proposal, _) = interop_group
+            .group
+            .propose_reinit(
+                &interop_group.crypto_provider,
+                &interop_group.signature_keys,
+                reinit_proposal,
+            )
+            .map_err(into_status)?;
+
+        // Store the proposal for potential future use.
+        interop_group.messages_out.push(proposal.clone().into());
+
+        let proposal = proposal.to_bytes().unwrap();
+        trace!("   generated reinit proposal");
+
+        let response = ProposalResponse { proposal };
 
         info!(?response, "Response");
-        Err(response)
+        Ok(Response::new(response))
     }
 
     #[instrument(skip_all, fields(actor))]
@@ -950,6 +1131,7 @@ impl MlsClient for MlsClientImpl {
                 .process_message(
                     &interop_group.crypto_provider,
                     message.try_into_protocol_message().unwrap(),
+                    &AcceptAllCredentials,
                 )
                 .map_err(into_status)?;
             trace!("... done");
@@ -1133,6 +1315,7 @@ impl MlsClient for MlsClientImpl {
                 .process_message(
                     &interop_group.crypto_provider,
                     message.try_into_protocol_message().unwrap(),
+                    &AcceptAllCredentials,
                 )
                 .map_err(into_status)?;
             trace!("       done");
@@ -1166,6 +1349,7 @@ impl MlsClient for MlsClientImpl {
             .process_message(
                 &interop_group.crypto_provider,
                 message.try_into_protocol_message().unwrap(),
+                &AcceptAllCredentials,
             )
             .map_err(into_status)?;
         debug!("Processed.");
@@ -1369,53 +1553,443 @@ impl MlsClient for MlsClientImpl {
         ))
     }
 
+    #[instrument(skip_all, fields(actor))]
     async fn re_init_commit(
         &self,
-        _: Request<CommitRequest>,
+        request: Request<CommitRequest>,
     ) -> Result<Response<CommitResponse>, Status> {
-        todo!()
+        let request = request.get_ref();
+        info!(?request, "Request");
+
+        if !request.by_value.is_empty() {
+            return Err(Status::invalid_argument(
+                "a reinit commit can't contain proposals by value",
+            ));
+        }
+
+        let mut groups = self.groups.lock().unwrap();
+        let interop_group = groups
+            .get_mut(request.state_id as usize)
+            .ok_or_else(|| Status::new(Code::InvalidArgument, "unknown state_id"))?;
+
+        trace!(epoch=?interop_group.group.epoch(), "Current group state.");
+
+        interop_group.store_proposals(&request.by_reference)?;
+
+        // The commit is merged by `handle_pending_re_init_commit`.
+        let (commit, _welcome_option, _group_info) = interop_group
+            .group
+            .commit_to_pending_proposals(
+                &interop_group.crypto_provider,
+                &interop_group.signature_keys,
+            )
+            .map_err(into_status)?;
+        debug!(commit=?interop_group.group.pending_commit(), "Pending commit created.");
+
+        let response = CommitResponse {
+            commit: commit.to_bytes().unwrap(),
+            welcome: vec![],
+            ratchet_tree: vec![],
+        };
+
+        info!(?response, "Response");
+        Ok(Response::new(response))
     }
 
+    #[instrument(skip_all, fields(actor))]
     async fn handle_pending_re_init_commit(
         &self,
-        _: Request<HandlePendingCommitRequest>,
+        request: Request<HandlePendingCommitRequest>,
     ) -> Result<Response<HandleReInitCommitResponse>, Status> {
-        todo!()
+        let request = request.get_ref();
+        info!(?request, "Request");
+
+        let mut groups = self.groups.lock().unwrap();
+        let interop_group = groups
+            .get_mut(request.state_id as usize)
+            .ok_or_else(|| Status::new(Code::InvalidArgument, "unknown state_id"))?;
+
+        trace!(commit=?interop_group.group.pending_commit(), "Merging pending commit.");
+        interop_group
+            .group
+            .merge_pending_commit(&interop_group.crypto_provider)
+            .map_err(into_status)?;
+        trace!(epoch=?interop_group.group.epoch(), "New group state.");
+
+        let response = self.prepare_reinit(request.state_id, interop_group)?;
+
+        info!(?response, "Response");
+        Ok(Response::new(response))
     }
 
+    #[instrument(skip_all, fields(actor))]
     async fn handle_re_init_commit(
         &self,
-        _: Request<HandleCommitRequest>,
+        request: Request<HandleCommitRequest>,
     ) -> Result<Response<HandleReInitCommitResponse>, Status> {
-        todo!()
+        let request = request.get_ref();
+        info!(?request, "Request");
+
+        let mut groups = self.groups.lock().unwrap();
+        let interop_group = groups
+            .get_mut(request.state_id as usize)
+            .ok_or_else(|| Status::new(Code::InvalidArgument, "unknown state_id"))?;
+
+        trace!(epoch=?interop_group.group.epoch(), "Current group state.");
+
+        interop_group.store_proposals(&request.proposal)?;
+
+        let message = MlsMessageIn::tls_deserialize(&mut request.commit.as_slice())
+            .map_err(|_| Status::aborted("failed to deserialize commit"))?;
+        let processed_message = interop_group
+            .group
+            .process_message(
+                &interop_group.crypto_provider,
+                message
+                    .try_into_protocol_message()
+                    .map_err(|_| Status::invalid_argument("commit is not a protocol message"))?,
+                &AcceptAllCredentials,
+            )
+            .map_err(into_status)?;
+        match processed_message.into_content() {
+            ProcessedMessageContent::StagedCommitMessage(staged_commit) => {
+                debug!(commit=?staged_commit, "Merging staged commit.");
+                interop_group
+                    .group
+                    .merge_staged_commit(&interop_group.crypto_provider, *staged_commit)
+                    .map_err(into_status)?;
+            }
+            _ => return Err(Status::invalid_argument("message is not a commit")),
+        }
+        trace!(epoch=?interop_group.group.epoch(), "New group state.");
+
+        let response = self.prepare_reinit(request.state_id, interop_group)?;
+
+        info!(?response, "Response");
+        Ok(Response::new(response))
     }
 
+    #[instrument(skip_all, fields(actor))]
     async fn re_init_welcome(
         &self,
-        _: Request<ReInitWelcomeRequest>,
+        request: Request<ReInitWelcomeRequest>,
     ) -> Result<Response<CreateSubgroupResponse>, Status> {
-        todo!()
+        let request = request.get_ref();
+        info!(?request, "Request");
+
+        let mut groups = self.groups.lock().unwrap();
+        let pending_reinits = self.pending_reinits.lock().unwrap();
+        let pending_reinit = pending_reinits
+            .get(request.reinit_id as usize)
+            .ok_or_else(|| Status::new(Code::InvalidArgument, "unknown reinit_id"))?;
+        let interop_group = groups
+            .get(pending_reinit.state_id as usize)
+            .ok_or_else(|| Status::new(Code::InvalidArgument, "unknown state_id"))?;
+
+        let key_packages = to_key_packages(&request.key_package)?;
+
+        let mls_group_config = MlsGroupJoinConfig::builder()
+            .max_past_epochs(32)
+            .number_of_resumption_psks(32)
+            .sender_ratchet_configuration(SenderRatchetConfiguration::default())
+            .use_ratchet_tree_extension(true)
+            .wire_format_policy(interop_group.wire_format_policy)
+            .build();
+
+        // The successor group is kept in a copy of the storage of the
+        // reinitialized group, which is where the members take the reinit
+        // PSK from.
+        let crypto_provider = interop_group.crypto_provider.clone();
+        let (group, welcome, _group_info) = interop_group
+            .group
+            .create_reinit_group(
+                &crypto_provider,
+                &pending_reinit.signature_keys,
+                &mls_group_config,
+                Some(leaf_node_capabilities()),
+                pending_reinit.credential_with_key.clone(),
+                &key_packages,
+            )
+            .map_err(into_status)?;
+        debug!("Successor group created.");
+
+        let welcome = welcome
+            .tls_serialize_detached()
+            .map_err(|_| Status::aborted("failed to serialize welcome"))?;
+        let ratchet_tree = if request.external_tree {
+            group
+                .export_ratchet_tree()
+                .tls_serialize_detached()
+                .map_err(|_| Status::aborted("failed to serialize ratchet tree"))?
+        } else {
+            vec![]
+        };
+        let epoch_authenticator = group.epoch_authenticator().as_slice().to_vec();
+
+        let interop_group = InteropGroup {
+            group,
+            wire_format_policy: interop_group.wire_format_policy,
+            signature_keys: pending_reinit.signature_keys.clone(),
+            messages_out: Vec::new(),
+            crypto_provider,
+        };
+        let state_id = groups.len() as u32;
+        groups.push(interop_group);
+
+        let response = CreateSubgroupResponse {
+            state_id,
+            welcome,
+            ratchet_tree,
+            epoch_authenticator,
+        };
+
+        info!(?response, "Response");
+        Ok(Response::new(response))
     }
 
+    #[instrument(skip_all, fields(actor))]
     async fn handle_re_init_welcome(
         &self,
-        _: Request<HandleReInitWelcomeRequest>,
+        request: Request<HandleReInitWelcomeRequest>,
     ) -> Result<Response<JoinGroupResponse>, Status> {
-        todo!()
+        let request = request.get_ref();
+        info!(?request, "Request");
+
+        let mut groups = self.groups.lock().unwrap();
+        let pending_reinits = self.pending_reinits.lock().unwrap();
+        let pending_reinit = pending_reinits
+            .get(request.reinit_id as usize)
+            .ok_or_else(|| Status::new(Code::InvalidArgument, "unknown reinit_id"))?;
+        let interop_group = groups
+            .get(pending_reinit.state_id as usize)
+            .ok_or_else(|| Status::new(Code::InvalidArgument, "unknown state_id"))?;
+
+        let welcome = MlsMessageIn::tls_deserialize(&mut request.welcome.as_slice())
+            .map_err(|_| Status::aborted("failed to deserialize MlsMessage with a Welcome"))?
+            .into_welcome()
+            .ok_or(Status::invalid_argument("Message was not a welcome"))?;
+        let ratchet_tree = ratchet_tree_from_config(request.ratchet_tree.clone());
+
+        let mls_group_config = MlsGroupJoinConfig::builder()
+            .max_past_epochs(32)
+            .number_of_resumption_psks(32)
+            .sender_ratchet_configuration(SenderRatchetConfiguration::default())
+            .use_ratchet_tree_extension(true)
+            .wire_format_policy(interop_group.wire_format_policy)
+            .build();
+
+        // The storage of the reinitialized group contains the key package
+        // and the reinit PSK.
+        let crypto_provider = interop_group.crypto_provider.clone();
+        let group = StagedWelcome::new_from_welcome(
+            &crypto_provider,
+            &mls_group_config,
+            welcome,
+            ratchet_tree,
+            &AcceptAllCredentials,
+        )
+        .map_err(into_status)?
+        .into_group(&crypto_provider)
+        .map_err(into_status)?;
+        trace!(epoch=?group.epoch(), "Joined successor group.");
+
+        let epoch_authenticator = group.epoch_authenticator().as_slice().to_vec();
+
+        let interop_group = InteropGroup {
+            group,
+            wire_format_policy: interop_group.wire_format_policy,
+            signature_keys: pending_reinit.signature_keys.clone(),
+            messages_out: Vec::new(),
+            crypto_provider,
+        };
+        let state_id = groups.len() as u32;
+        groups.push(interop_group);
+
+        let response = JoinGroupResponse {
+            state_id,
+            epoch_authenticator,
+        };
+
+        info!(?response, "Response");
+        Ok(Response::new(response))
     }
 
+    #[instrument(skip_all, fields(actor))]
     async fn create_branch(
         &self,
-        _: Request<CreateBranchRequest>,
+        request: Request<CreateBranchRequest>,
     ) -> Result<Response<CreateSubgroupResponse>, Status> {
-        todo!()
+        let request = request.get_ref();
+        info!(?request, "Request");
+
+        let mut groups = self.groups.lock().unwrap();
+        let interop_group = groups
+            .get(request.state_id as usize)
+            .ok_or_else(|| Status::new(Code::InvalidArgument, "unknown state_id"))?;
+        let group = &interop_group.group;
+
+        trace!(epoch=?group.epoch(), "Current group state.");
+
+        let key_packages = to_key_packages(&request.key_packages)?;
+
+        let mls_group_config = MlsGroupCreateConfig::builder()
+            .ciphersuite(group.ciphersuite())
+            .capabilities(leaf_node_capabilities())
+            .max_past_epochs(32)
+            .number_of_resumption_psks(32)
+            .sender_ratchet_configuration(SenderRatchetConfiguration::default())
+            .use_ratchet_tree_extension(true)
+            .wire_format_policy(interop_group.wire_format_policy)
+            .with_group_context_extensions(to_extensions(&request.extensions)?)
+            .map_err(into_status)?
+            .build();
+        let credential_with_key = CredentialWithKey {
+            credential: group.credential().map_err(into_status)?.clone(),
+            signature_key: interop_group.signature_keys.public().into(),
+        };
+
+        // The subgroup is kept in a copy of the storage of the parent group,
+        // which is where the members take the branch PSK from.
+        let crypto_provider = interop_group.crypto_provider.clone();
+        let (subgroup, welcome, _group_info) = group
+            .branch(
+                &crypto_provider,
+                &interop_group.signature_keys,
+                GroupId::from_slice(&request.group_id),
+                &mls_group_config,
+                credential_with_key,
+                &key_packages,
+            )
+            .map_err(into_status)?;
+        debug!("Subgroup created.");
+
+        let welcome = welcome
+            .tls_serialize_detached()
+            .map_err(|_| Status::aborted("failed to serialize welcome"))?;
+        let ratchet_tree = if request.external_tree {
+            subgroup
+                .export_ratchet_tree()
+                .tls_serialize_detached()
+                .map_err(|_| Status::aborted("failed to serialize ratchet tree"))?
+        } else {
+            vec![]
+        };
+        let epoch_authenticator = subgroup.epoch_authenticator().as_slice().to_vec();
+
+        let interop_group = InteropGroup {
+            group: subgroup,
+            wire_format_policy: interop_group.wire_format_policy,
+            signature_keys: interop_group.signature_keys.clone(),
+            messages_out: Vec::new(),
+            crypto_provider,
+        };
+        let state_id = groups.len() as u32;
+        groups.push(interop_group);
+
+        let response = CreateSubgroupResponse {
+            state_id,
+            welcome,
+            ratchet_tree,
+            epoch_authenticator,
+        };
+
+        info!(?response, "Response");
+        Ok(Response::new(response))
     }
 
+    #[instrument(skip_all, fields(actor))]
     async fn handle_branch(
         &self,
-        _: Request<HandleBranchRequest>,
+        request: Request<HandleBranchRequest>,
     ) -> Result<Response<HandleBranchResponse>, Status> {
-        todo!()
+        let request = request.get_ref();
+        info!(?request, "Request");
+
+        let identity = self
+            .transaction_id_map
+            .lock()
+            .unwrap()
+            .get(&request.transaction_id)
+            .cloned()
+            .ok_or_else(|| Status::new(Code::InvalidArgument, "unknown transaction_id"))?;
+        let (my_key_package, _my_credential, my_signature_keys, _crypto_provider) = self
+            .pending_state
+            .lock()
+            .unwrap()
+            .remove(&identity)
+            .ok_or(Status::aborted(format!(
+                "failed to find key package for identity {:x?}",
+                identity
+            )))?;
+
+        let mut groups = self.groups.lock().unwrap();
+        let interop_group = groups
+            .get(request.state_id as usize)
+            .ok_or_else(|| Status::new(Code::InvalidArgument, "unknown state_id"))?;
+
+        trace!(epoch=?interop_group.group.epoch(), "Current group state.");
+
+        // The branch PSK is taken from the parent group, so the subgroup is
+        // joined with a copy of its storage, next to the key package.
+        let crypto_provider = interop_group.crypto_provider.clone();
+
+        use openmls_traits::storage::StorageProvider as _;
+
+        crypto_provider
+            .storage()
+            .write_key_package(
+                &my_key_package
+                    .key_package()
+                    .hash_ref(crypto_provider.crypto())
+                    .map_err(into_status)?,
+                &my_key_package,
+            )
+            .map_err(into_status)?;
+
+        let welcome = MlsMessageIn::tls_deserialize(&mut request.welcome.as_slice())
+            .map_err(|_| Status::aborted("failed to deserialize MlsMessage with a Welcome"))?
+            .into_welcome()
+            .ok_or(Status::invalid_argument("Message was not a welcome"))?;
+        let ratchet_tree = ratchet_tree_from_config(request.ratchet_tree.clone());
+
+        let mls_group_config = MlsGroupJoinConfig::builder()
+            .max_past_epochs(32)
+            .number_of_resumption_psks(32)
+            .sender_ratchet_configuration(SenderRatchetConfiguration::default())
+            .use_ratchet_tree_extension(true)
+            .wire_format_policy(interop_group.wire_format_policy)
+            .build();
+        let subgroup = StagedWelcome::new_from_welcome(
+            &crypto_provider,
+            &mls_group_config,
+            welcome,
+            ratchet_tree,
+            &AcceptAllCredentials,
+        )
+        .map_err(into_status)?
+        .into_group(&crypto_provider)
+        .map_err(into_status)?;
+        trace!(epoch=?subgroup.epoch(), "Joined subgroup.");
+
+        let epoch_authenticator = subgroup.epoch_authenticator().as_slice().to_vec();
+
+        let interop_group = InteropGroup {
+            group: subgroup,
+            wire_format_policy: interop_group.wire_format_policy,
+            signature_keys: my_signature_keys,
+            messages_out: Vec::new(),
+            crypto_provider,
+        };
+        let state_id = groups.len() as u32;
+        groups.push(interop_group);
+
+        let response = HandleBranchResponse {
+            state_id,
+            epoch_authenticator,
+        };
+
+        info!(?response, "Response");
+        Ok(Response::new(response))
     }
 
     async fn new_member_add_proposal(
@@ -11,6 +11,8 @@ use openmls_traits::types::{
 use rand::CryptoRng;
 use tls_codec::SecretVLBytes;
 
+use crate::ml_kem;
+
 /// The libcrux-backed cryptography provider for OpenMLS
 pub struct CryptoProvider {
     drbg: Mutex<Drbg>,
@@ -33,6 +35,14 @@ impl CryptoProvider {
     fn aes_support(&self) -> bool {
         libcrux::aes_ni_support() && cfg!(target_arch = "x86_64")
     }
+
+    /// Locks the DRBG for use as a [`RngCore`].
+    pub(crate) fn rng(&self) -> Result<GuardedRng<'_, Drbg>, CryptoError> {
+        self.drbg
+            .lock()
+            .map_err(|_| CryptoError::CryptoLibraryError)
+            .map(GuardedRng)
+    }
 }
 
 impl OpenMlsCrypto for CryptoProvider {
@@ -70,6 +80,8 @@ impl OpenMlsCrypto for CryptoProvider {
                 Ciphersuite::MLS_128_DHKEMX25519_CHACHA20POLY1305_SHA256_Ed25519,
                 Ciphersuite::MLS_128_DHKEMP256_AES128GCM_SHA256_P256,
                 Ciphersuite::MLS_256_XWING_CHACHA20POLY1305_SHA256_Ed25519,
+                Ciphersuite::MLS_128_MLKEM768_AES128GCM_SHA256_Ed25519,
+                Ciphersuite::MLS_256_MLKEM1024_AES256GCM_SHA512_Ed25519,
             ]
         } else {
             vec![
@@ -183,7 +195,17 @@ impl OpenMlsCrypto for CryptoProvider {
             .map_err(|_| CryptoError::CryptoLibraryError)
             .map(GuardedRng)?;
 
-        libcrux::signature::key_gen(alg, &mut rng).map_err(|_| CryptoError::SigningError)
+        let (private, public) =
+            libcrux::signature::key_gen(alg, &mut rng).map_err(|_| CryptoError::SigningError)?;
+
+        // ECDSA public keys are encoded as uncompressed points, but libcrux
+        // returns the concatenated coordinates.
+        match alg {
+            libcrux::signature::Algorithm::EcDsaP256(_) => {
+                Ok((private, [&[0x04], public.as_slice()].concat()))
+            }
+            _ => Ok((private, public)),
+        }
     }
 
     fn verify_signature(
@@ -204,9 +226,15 @@ impl OpenMlsCrypto for CryptoProvider {
             .lock()
             .map_err(|_| CryptoError::CryptoLibraryError)?;
 
-        libcrux::signature::sign(alg, data, key, &mut GuardedRng(drbg))
-            .map_err(|_| CryptoError::SigningError)
-            .map(|sig| sig.into_vec())
+        let signature = libcrux::signature::sign(alg, data, key, &mut GuardedRng(drbg))
+            .map_err(|_| CryptoError::SigningError)?
+            .into_vec();
+
+        // ECDSA signatures are DER encoded, but libcrux returns `r || s`.
+        match alg {
+            libcrux::signature::Algorithm::EcDsaP256(_) => Ok(der_encode(&signature)),
+            _ => Ok(signature),
+        }
     }
 
     fn hpke_seal(
@@ -217,6 +245,9 @@ impl OpenMlsCrypto for CryptoProvider {
         aad: &[u8],
         ptxt: &[u8],
     ) -> Result<HpkeCiphertext, CryptoError> {
+        if let Some(alg) = ml_kem::algorithm(config.0) {
+            return ml_kem::seal(self, alg, config, pk_r, info, aad, ptxt);
+        }
         let config = hpke_config(config);
         let randomness = {
             let mut rng = self
@@ -227,6 +258,10 @@ impl OpenMlsCrypto for CryptoProvider {
                 .map_err(|_| CryptoError::CryptoLibraryError)?
         };
 
+        // libcrux doesn't check the length of the public key on its own.
+        if pk_r.len() != libcrux::hpke::kem::Npk(config.1) {
+            return Err(CryptoError::InvalidPublicKey);
+        }
         let pk_r = libcrux::hpke::kem::DeserializePublicKey(config.1, pk_r)
             .map_err(|_| CryptoError::CryptoLibraryError)?;
 
@@ -258,6 +293,9 @@ impl OpenMlsCrypto for CryptoProvider {
         info: &[u8],
         aad: &[u8],
     ) -> Result<Vec<u8>, CryptoError> {
+        if let Some(alg) = ml_kem::algorithm(config.0) {
+            return ml_kem::open(self, alg, config, input, sk_r, info, aad);
+        }
         let config = hpke_config(config);
         let ctxt = libcrux::hpke::HPKECiphertext(
             input.kem_output.as_ref().to_vec(),
@@ -284,6 +322,18 @@ impl OpenMlsCrypto for CryptoProvider {
         exporter_context: &[u8],
         exporter_length: usize,
     ) -> Result<(KemOutput, ExporterSecret), CryptoError> {
+        if let Some(alg) = ml_kem::algorithm(config.0) {
+            return ml_kem::setup_sender_and_export(
+                self,
+                alg,
+                config,
+                pk_r,
+                info,
+                exporter_context,
+                exporter_length,
+            )
+            .map(|(enc, exported)| (enc, exported.into()));
+        }
         let config = hpke_config(config);
         let randomness = self
             .drbg
@@ -292,6 +342,10 @@ impl OpenMlsCrypto for CryptoProvider {
             .generate_vec(libcrux::hpke::kem::Nsk(config.1))
             .map_err(|_| CryptoError::CryptoLibraryError)?;
 
+        // libcrux doesn't check the length of the public key on its own.
+        if pk_r.len() != libcrux::hpke::kem::Npk(config.1) {
+            return Err(CryptoError::InvalidPublicKey);
+        }
         let pk_r = libcrux::hpke::kem::DeserializePublicKey(config.1, pk_r)
             .map_err(|_| CryptoError::InvalidPublicKey)?;
 
@@ -312,6 +366,19 @@ impl OpenMlsCrypto for CryptoProvider {
         exporter_context: &[u8],
         exporter_length: usize,
     ) -> Result<ExporterSecret, CryptoError> {
+        if let Some(alg) = ml_kem::algorithm(config.0) {
+            return ml_kem::setup_receiver_and_export(
+                self,
+                alg,
+                config,
+                enc,
+                sk_r,
+                info,
+                exporter_context,
+                exporter_length,
+            )
+            .map(ExporterSecret::from);
+        }
         let config = hpke_config(config);
 
         let ctx = libcrux::hpke::SetupBaseR(config, enc, sk_r, info)
@@ -327,6 +394,12 @@ impl OpenMlsCrypto for CryptoProvider {
         config: HpkeConfig,
         ikm: &[u8],
     ) -> Result<HpkeKeyPair, CryptoError> {
+        if let Some(alg) = ml_kem::algorithm(config.0) {
+            return ml_kem::derive_key_pair(alg, ikm);
+        }
+        if config.0 == HpkeKemType::XWingKemDraft2 {
+            return ml_kem::x_wing_derive_key_pair(ikm);
+        }
         let config = hpke_config(config);
         let HPKEConfig(_, alg, _, _) = config;
         let (sk, pk) = hpke::kem::DeriveKeyPair(alg, ikm).map_err(|e| match e {
@@ -435,6 +508,9 @@ fn hpke_kem(kem: HpkeKemType) -> libcrux::hpke::kem::KEM {
         HpkeKemType::DhKem25519 => libcrux::hpke::kem::KEM::DHKEM_X25519_HKDF_SHA256,
         HpkeKemType::DhKem448 => libcrux::hpke::kem::KEM::DHKEM_X448_HKDF_SHA512,
         HpkeKemType::XWingKemDraft2 => libcrux::hpke::kem::KEM::XWingDraft02,
+        HpkeKemType::MlKem768 | HpkeKemType::MlKem1024 => {
+            unreachable!("{kem:?} is implemented in the ml_kem module.")
+        }
     }
 }
 
@@ -569,7 +645,42 @@ fn der_decode(mut signature_bytes: &[u8]) -> Result<Vec<u8>, CryptoError> {
     Ok(out)
 }
 
-struct GuardedRng<'a, Rng: RngCore>(MutexGuard<'a, Rng>);
+/// This function takes the bytes representing the concatenated scalars of an
+/// ECDSA signature and DER encodes them.
+fn der_encode(raw_signature: &[u8]) -> Vec<u8> {
+    // A small function to DER encode a single scalar.
+    fn encode_scalar(mut scalar: &[u8]) -> Vec<u8> {
+        // Strip the leading zeroes, but keep at least one byte.
+        while scalar.len() > 1 && scalar[0] == 0x00 {
+            scalar = &scalar[1..];
+        }
+
+        let mut encoded = vec![INTEGER_TAG];
+        // If the first bit is set, the integer would be read as negative, so
+        // it is prefixed with 0x00.
+        if scalar[0] & 0x80 != 0 {
+            encoded.push(scalar.len() as u8 + 1);
+            encoded.push(0x00);
+        } else {
+            encoded.push(scalar.len() as u8);
+        }
+        encoded.extend_from_slice(scalar);
+        encoded
+    }
+
+    let (r, s) = raw_signature.split_at(P256_SCALAR_LENGTH);
+    let mut r = encode_scalar(r);
+    let mut s = encode_scalar(s);
+
+    // The length is at most 70 and thus fits the short form DER length
+    // encoding.
+    let mut out = vec![SEQUENCE_TAG, (r.len() + s.len()) as u8];
+    out.append(&mut r);
+    out.append(&mut s);
+    out
+}
+
+pub(crate) struct GuardedRng<'a, Rng: RngCore>(MutexGuard<'a, Rng>);
 
 impl<'a, Rng: RngCore> RngCore for GuardedRng<'a, Rng> {
     fn next_u32(&mut self) -> u32 {
@@ -1,6 +1,7 @@
 use openmls_traits::OpenMlsProvider;
 
 mod crypto;
+mod ml_kem;
 mod rand;
 
 pub use crypto::CryptoProvider;
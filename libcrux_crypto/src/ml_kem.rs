@@ -0,0 +1,360 @@
+//! HPKE ([RFC 9180]) in base mode with ML-KEM-768 and ML-KEM-1024 ([FIPS 203]),
+//! and `DeriveKeyPair` for X-Wing ([draft 02]).
+//!
+//! The HPKE of libcrux doesn't support ML-KEM on its own. This module
+//! implements the HPKE key schedule on top of the KEM, HKDF and AEAD functions
+//! of libcrux.
+//!
+//! The private key of a key pair is the 64-byte seed `d || z` of the key
+//! generation. `DeriveKeyPair` expands the input keying material to that seed
+//! with SHAKE256.
+//!
+//! [RFC 9180]: https://www.rfc-editor.org/rfc/rfc9180.html
+//! [FIPS 203]: https://csrc.nist.gov/pubs/fips/203/final
+//! [draft 02]: https://datatracker.ietf.org/doc/draft-connolly-cfrg-xwing-kem/02/
+
+use ml_kem::{EncodedSizeUser, KemCore, MlKem1024, MlKem768, B32};
+use openmls_traits::{
+    crypto::OpenMlsCrypto,
+    types::{
+        AeadType, CryptoError, HashType, HpkeAeadType, HpkeCiphertext, HpkeConfig, HpkeKdfType,
+        HpkeKemType, HpkeKeyPair,
+    },
+};
+use zeroize::Zeroizing;
+
+use crate::CryptoProvider;
+
+const VERSION_LABEL: &[u8] = b"HPKE-v1";
+const MODE_BASE: u8 = 0x00;
+const NONCE_LENGTH: usize = 12;
+
+/// The length of the seed of a key pair.
+const SEED_LENGTH: usize = 64;
+
+/// The length of the seed of an X-Wing key pair, which adds the X25519 private
+/// key to the seed of ML-KEM-768.
+const X_WING_SEED_LENGTH: usize = SEED_LENGTH + 32;
+
+/// Returns the libcrux algorithm for the ML-KEM `kem`, or `None` if it isn't
+/// an ML-KEM.
+pub(crate) fn algorithm(kem: HpkeKemType) -> Option<libcrux::kem::Algorithm> {
+    match kem {
+        HpkeKemType::MlKem768 => Some(libcrux::kem::Algorithm::MlKem768),
+        HpkeKemType::MlKem1024 => Some(libcrux::kem::Algorithm::MlKem1024),
+        _ => None,
+    }
+}
+
+/// Generates the key pair for the 64-byte `seed` `d || z`.
+///
+/// The key generation of the pinned libcrux release follows the FIPS 203 draft
+/// and lacks the domain separator of the final standard. The key pair is
+/// therefore generated with `ml-kem` and decoded into the keys of libcrux,
+/// which encapsulates and decapsulates as in the final standard.
+fn key_pair(
+    alg: libcrux::kem::Algorithm,
+    seed: &[u8],
+) -> Result<(libcrux::kem::PrivateKey, libcrux::kem::PublicKey), CryptoError> {
+    if seed.len() != SEED_LENGTH {
+        return Err(CryptoError::InvalidLength);
+    }
+    let (d, z) = seed.split_at(SEED_LENGTH / 2);
+    let d = B32::try_from(d).map_err(|_| CryptoError::InvalidLength)?;
+    let z = B32::try_from(z).map_err(|_| CryptoError::InvalidLength)?;
+    let (private, public) = match alg {
+        libcrux::kem::Algorithm::MlKem768 => encoded_key_pair::<MlKem768>(&d, &z),
+        libcrux::kem::Algorithm::MlKem1024 => encoded_key_pair::<MlKem1024>(&d, &z),
+        _ => return Err(CryptoError::UnsupportedCiphersuite),
+    };
+    let private = libcrux::kem::PrivateKey::decode(alg, &private)
+        .map_err(|_| CryptoError::CryptoLibraryError)?;
+    let public = libcrux::kem::PublicKey::decode(alg, &public)
+        .map_err(|_| CryptoError::CryptoLibraryError)?;
+    Ok((private, public))
+}
+
+/// Returns the encoded private and public key of the ML-KEM `K` for the seed
+/// `d || z`.
+fn encoded_key_pair<K: KemCore>(d: &B32, z: &B32) -> (Zeroizing<Vec<u8>>, Vec<u8>) {
+    let (dk, ek) = K::generate_deterministic(d, z);
+    (
+        Zeroizing::new(dk.as_bytes().to_vec()),
+        ek.as_bytes().to_vec(),
+    )
+}
+
+/// `DeriveKeyPair` for ML-KEM.
+pub(crate) fn derive_key_pair(
+    alg: libcrux::kem::Algorithm,
+    ikm: &[u8],
+) -> Result<HpkeKeyPair, CryptoError> {
+    let seed = libcrux::digest::shake256::<SEED_LENGTH>(ikm);
+    let (_, public) = key_pair(alg, &seed)?;
+    Ok(HpkeKeyPair {
+        private: seed.to_vec().into(),
+        public: public.encode(),
+    })
+}
+
+/// `DeriveKeyPair` for X-Wing.
+///
+/// The X-Wing KEM of libcrux implements encapsulation and decapsulation as in
+/// draft 02, but expands the seed with SHAKE128 and derives the X25519 key pair
+/// with DHKEM. The key pair is derived here instead: the input keying material
+/// is expanded with SHAKE256 to the seed of ML-KEM-768 and the X25519 private
+/// key. The private key is the expanded ML-KEM-768 decapsulation key, followed
+/// by the X25519 private and public key, as the KEM of libcrux expects.
+pub(crate) fn x_wing_derive_key_pair(ikm: &[u8]) -> Result<HpkeKeyPair, CryptoError> {
+    let seed = Zeroizing::new(libcrux::digest::shake256::<X_WING_SEED_LENGTH>(ikm));
+    let (sk_m, pk_m) = key_pair(libcrux::kem::Algorithm::MlKem768, &seed[..SEED_LENGTH])?;
+    let sk_x = &seed[SEED_LENGTH..];
+    let pk_x = libcrux::ecdh::secret_to_public(libcrux::ecdh::Algorithm::X25519, sk_x)
+        .map_err(|_| CryptoError::CryptoLibraryError)?;
+    Ok(HpkeKeyPair {
+        private: [sk_m.encode().as_slice(), sk_x, &pk_x].concat().into(),
+        public: [pk_m.encode(), pk_x].concat(),
+    })
+}
+
+/// Returns the shared secret and the encapsulated key.
+fn encap(
+    crypto: &CryptoProvider,
+    alg: libcrux::kem::Algorithm,
+    pk_r: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), CryptoError> {
+    let pk_r =
+        libcrux::kem::PublicKey::decode(alg, pk_r).map_err(|_| CryptoError::InvalidPublicKey)?;
+    let (shared_secret, enc) = pk_r
+        .encapsulate(&mut crypto.rng()?)
+        .map_err(|_| CryptoError::CryptoLibraryError)?;
+    Ok((shared_secret.encode(), enc.encode()))
+}
+
+fn decap(alg: libcrux::kem::Algorithm, enc: &[u8], sk_r: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    let (sk_r, _) = key_pair(alg, sk_r)?;
+    let enc = libcrux::kem::Ct::decode(alg, enc).map_err(|_| CryptoError::InvalidLength)?;
+    let shared_secret = enc
+        .decapsulate(&sk_r)
+        .map_err(|_| CryptoError::CryptoLibraryError)?;
+    Ok(shared_secret.encode())
+}
+
+fn labeled_extract(
+    crypto: &CryptoProvider,
+    hash: HashType,
+    suite_id: &[u8],
+    salt: &[u8],
+    label: &[u8],
+    ikm: &[u8],
+) -> Result<Vec<u8>, CryptoError> {
+    let labeled_ikm = [VERSION_LABEL, suite_id, label, ikm].concat();
+    Ok(crypto
+        .hkdf_extract(hash, salt, &labeled_ikm)?
+        .as_slice()
+        .to_vec())
+}
+
+fn labeled_expand(
+    crypto: &CryptoProvider,
+    hash: HashType,
+    suite_id: &[u8],
+    prk: &[u8],
+    label: &[u8],
+    info: &[u8],
+    length: usize,
+) -> Result<Vec<u8>, CryptoError> {
+    let length_bytes = u16::try_from(length)
+        .map_err(|_| CryptoError::InvalidLength)?
+        .to_be_bytes();
+    let labeled_info = [&length_bytes, VERSION_LABEL, suite_id, label, info].concat();
+    Ok(crypto
+        .hkdf_expand(hash, prk, &labeled_info, length)?
+        .as_slice()
+        .to_vec())
+}
+
+/// The context of RFC 9180, Section 5.1, for a single message.
+struct Context {
+    aead: Option<AeadType>,
+    key: Vec<u8>,
+    base_nonce: Vec<u8>,
+    exporter_secret: Vec<u8>,
+    hash: HashType,
+    suite_id: Vec<u8>,
+}
+
+impl Context {
+    /// `KeyScheduleS` and `KeyScheduleR` in base mode.
+    fn new(
+        crypto: &CryptoProvider,
+        config: &HpkeConfig,
+        shared_secret: &[u8],
+        info: &[u8],
+    ) -> Result<Self, CryptoError> {
+        let HpkeConfig(kem, kdf, aead) = *config;
+        let suite_id = [
+            b"HPKE".as_slice(),
+            &(kem as u16).to_be_bytes(),
+            &(kdf as u16).to_be_bytes(),
+            &(aead as u16).to_be_bytes(),
+        ]
+        .concat();
+        let hash = match kdf {
+            HpkeKdfType::HkdfSha256 => HashType::Sha2_256,
+            HpkeKdfType::HkdfSha384 => HashType::Sha2_384,
+            HpkeKdfType::HkdfSha512 => HashType::Sha2_512,
+        };
+        let aead = match aead {
+            HpkeAeadType::AesGcm128 => Some(AeadType::Aes128Gcm),
+            HpkeAeadType::AesGcm256 => Some(AeadType::Aes256Gcm),
+            HpkeAeadType::ChaCha20Poly1305 => Some(AeadType::ChaCha20Poly1305),
+            HpkeAeadType::Export => None,
+        };
+
+        let psk_id_hash = labeled_extract(crypto, hash, &suite_id, &[], b"psk_id_hash", &[])?;
+        let info_hash = labeled_extract(crypto, hash, &suite_id, &[], b"info_hash", info)?;
+        let key_schedule_context = [&[MODE_BASE], psk_id_hash.as_slice(), &info_hash].concat();
+        let secret = labeled_extract(crypto, hash, &suite_id, shared_secret, b"secret", &[])?;
+        let expand = |label: &[u8], length| {
+            labeled_expand(
+                crypto,
+                hash,
+                &suite_id,
+                &secret,
+                label,
+                &key_schedule_context,
+                length,
+            )
+        };
+
+        let (key, base_nonce) = match aead {
+            Some(aead) => (
+                expand(b"key", aead.key_size())?,
+                expand(b"base_nonce", NONCE_LENGTH)?,
+            ),
+            None => (Vec::new(), Vec::new()),
+        };
+        let exporter_secret = expand(b"exp", hash.size())?;
+
+        Ok(Self {
+            aead,
+            key,
+            base_nonce,
+            exporter_secret,
+            hash,
+            suite_id,
+        })
+    }
+
+    /// Seals the first and only message of the context, which uses the base
+    /// nonce.
+    fn seal(
+        &self,
+        crypto: &CryptoProvider,
+        aad: &[u8],
+        ptxt: &[u8],
+    ) -> Result<Vec<u8>, CryptoError> {
+        let aead = self.aead.ok_or(CryptoError::UnsupportedAeadAlgorithm)?;
+        crypto.aead_encrypt(aead, &self.key, ptxt, &self.base_nonce, aad)
+    }
+
+    /// Opens the first and only message of the context, which uses the base
+    /// nonce.
+    fn open(
+        &self,
+        crypto: &CryptoProvider,
+        aad: &[u8],
+        ctxt: &[u8],
+    ) -> Result<Vec<u8>, CryptoError> {
+        let aead = self.aead.ok_or(CryptoError::UnsupportedAeadAlgorithm)?;
+        crypto.aead_decrypt(aead, &self.key, ctxt, &self.base_nonce, aad)
+    }
+
+    fn export(
+        &self,
+        crypto: &CryptoProvider,
+        exporter_context: &[u8],
+        length: usize,
+    ) -> Result<Vec<u8>, CryptoError> {
+        labeled_expand(
+            crypto,
+            self.hash,
+            &self.suite_id,
+            &self.exporter_secret,
+            b"sec",
+            exporter_context,
+            length,
+        )
+    }
+}
+
+pub(crate) fn seal(
+    crypto: &CryptoProvider,
+    alg: libcrux::kem::Algorithm,
+    config: HpkeConfig,
+    pk_r: &[u8],
+    info: &[u8],
+    aad: &[u8],
+    ptxt: &[u8],
+) -> Result<HpkeCiphertext, CryptoError> {
+    let (shared_secret, enc) = encap(crypto, alg, pk_r)?;
+    let ciphertext =
+        Context::new(crypto, &config, &shared_secret, info)?.seal(crypto, aad, ptxt)?;
+    Ok(HpkeCiphertext {
+        kem_output: enc.into(),
+        ciphertext: ciphertext.into(),
+    })
+}
+
+pub(crate) fn open(
+    crypto: &CryptoProvider,
+    alg: libcrux::kem::Algorithm,
+    config: HpkeConfig,
+    input: &HpkeCiphertext,
+    sk_r: &[u8],
+    info: &[u8],
+    aad: &[u8],
+) -> Result<Vec<u8>, CryptoError> {
+    let shared_secret = decap(alg, input.kem_output.as_slice(), sk_r)
+        .map_err(|_| CryptoError::HpkeDecryptionError)?;
+    Context::new(crypto, &config, &shared_secret, info)?
+        .open(crypto, aad, input.ciphertext.as_slice())
+        .map_err(|_| CryptoError::HpkeDecryptionError)
+}
+
+pub(crate) fn setup_sender_and_export(
+    crypto: &CryptoProvider,
+    alg: libcrux::kem::Algorithm,
+    config: HpkeConfig,
+    pk_r: &[u8],
+    info: &[u8],
+    exporter_context: &[u8],
+    exporter_length: usize,
+) -> Result<(Vec<u8>, Vec<u8>), CryptoError> {
+    let (shared_secret, enc) =
+        encap(crypto, alg, pk_r).map_err(|_| CryptoError::SenderSetupError)?;
+    let exported_secret = Context::new(crypto, &config, &shared_secret, info)
+        .map_err(|_| CryptoError::SenderSetupError)?
+        .export(crypto, exporter_context, exporter_length)
+        .map_err(|_| CryptoError::ExporterError)?;
+    Ok((enc, exported_secret))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn setup_receiver_and_export(
+    crypto: &CryptoProvider,
+    alg: libcrux::kem::Algorithm,
+    config: HpkeConfig,
+    enc: &[u8],
+    sk_r: &[u8],
+    info: &[u8],
+    exporter_context: &[u8],
+    exporter_length: usize,
+) -> Result<Vec<u8>, CryptoError> {
+    let shared_secret = decap(alg, enc, sk_r).map_err(|_| CryptoError::ReceiverSetupError)?;
+    Context::new(crypto, &config, &shared_secret, info)
+        .map_err(|_| CryptoError::ReceiverSetupError)?
+        .export(crypto, exporter_context, exporter_length)
+        .map_err(|_| CryptoError::ExporterError)
+}
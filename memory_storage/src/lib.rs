@@ -1,5 +1,5 @@
 use openmls_traits::storage::*;
-use serde::Serialize;
+use serde::{de::DeserializeOwned, Serialize};
 use std::{collections::HashMap, sync::RwLock};
 
 #[cfg(feature = "test-utils")]
@@ -12,11 +12,21 @@ mod test_store;
 #[cfg(feature = "persistence")]
 pub mod persistence;
 
+/// A test suite that checks a storage against the [`StorageProvider`] trait.
+#[cfg(feature = "test-utils")]
+pub mod test_suite;
+
 #[derive(Debug, Default)]
 pub struct MemoryStorage {
     pub values: RwLock<HashMap<Vec<u8>, Vec<u8>>>,
+    /// The undo logs of the open transactions, innermost last.
+    transactions: RwLock<Vec<UndoLog>>,
 }
 
+/// The value every key changed in a transaction had before it, or `None` if
+/// it had none.
+type UndoLog = HashMap<Vec<u8>, Option<Vec<u8>>>;
+
 // For testing we want to clone.
 #[cfg(feature = "test-utils")]
 impl Clone for MemoryStorage {
@@ -24,6 +34,7 @@ impl Clone for MemoryStorage {
         let values = self.values.read().unwrap();
         Self {
             values: RwLock::new(values.clone()),
+            transactions: RwLock::default(),
         }
     }
 }
@@ -80,11 +91,34 @@ impl MemoryStorage {
 
         Ok(Self {
             values: RwLock::new(map),
+            transactions: RwLock::default(),
         })
     }
 }
 
 impl MemoryStorage {
+    /// Internal helper that records the current value of `key` in the undo
+    /// log of the innermost transaction before the value is changed.
+    fn record_undo(&self, values: &HashMap<Vec<u8>, Vec<u8>>, key: &[u8]) {
+        if let Some(undo_log) = self.transactions.write().unwrap().last_mut() {
+            undo_log
+                .entry(key.to_vec())
+                .or_insert_with(|| values.get(key).cloned());
+        }
+    }
+
+    /// Internal helper to insert a value and record the change.
+    fn insert_value(&self, values: &mut HashMap<Vec<u8>, Vec<u8>>, key: Vec<u8>, value: Vec<u8>) {
+        self.record_undo(values, &key);
+        values.insert(key, value);
+    }
+
+    /// Internal helper to remove a value and record the change.
+    fn remove_value(&self, values: &mut HashMap<Vec<u8>, Vec<u8>>, key: &[u8]) {
+        self.record_undo(values, key);
+        values.remove(key);
+    }
+
     /// Internal helper to abstract write operations.
     #[inline(always)]
     fn write<const VERSION: u16>(
@@ -100,7 +134,7 @@ impl MemoryStorage {
         log::debug!("  write key: {}", hex::encode(&storage_key));
         log::trace!("{}", std::backtrace::Backtrace::capture());
 
-        values.insert(storage_key, value.to_vec());
+        self.insert_value(&mut values, storage_key, value.to_vec());
         Ok(())
     }
 
@@ -118,6 +152,7 @@ impl MemoryStorage {
         log::trace!("{}", std::backtrace::Backtrace::capture());
 
         // fetch value from db, falling back to an empty list if doens't exist
+        self.record_undo(&values, &storage_key);
         let list_bytes = values.entry(storage_key).or_insert(b"[]".to_vec());
 
         // parse old value and push new data
@@ -145,6 +180,7 @@ impl MemoryStorage {
         log::trace!("{}", std::backtrace::Backtrace::capture());
 
         // fetch value from db, falling back to an empty list if doens't exist
+        self.record_undo(&values, &storage_key);
         let list_bytes = values.entry(storage_key).or_insert(b"[]".to_vec());
 
         // parse old value, find value to delete and remove it from list
@@ -160,6 +196,46 @@ impl MemoryStorage {
         Ok(())
     }
 
+    /// Internal helper to list the keys of all values with the given label.
+    ///
+    /// Keys that don't deserialize to `K` are skipped. They belong to another label that
+    /// starts with `label`.
+    fn keys<const VERSION: u16, K: DeserializeOwned>(&self, label: &[u8]) -> Vec<K> {
+        let version = u16::to_be_bytes(VERSION);
+        let values = self.values.read().unwrap();
+
+        values
+            .keys()
+            .filter_map(|storage_key| {
+                let key = storage_key.strip_prefix(label)?.strip_suffix(&version)?;
+                serde_json::from_slice(key).ok()
+            })
+            .collect()
+    }
+
+    /// Internal helper to find the nodes of the tree of a group. Returns the
+    /// storage key and the node index of each node.
+    fn tree_node_keys<const VERSION: u16>(
+        &self,
+        group_id: &impl Serialize,
+    ) -> Result<Vec<(Vec<u8>, u32)>, <Self as StorageProvider<CURRENT_VERSION>>::Error> {
+        let group_id = serde_json::to_value(group_id)?;
+        let version = u16::to_be_bytes(VERSION);
+        let values = self.values.read().unwrap();
+
+        Ok(values
+            .keys()
+            .filter_map(|storage_key| {
+                let key = storage_key
+                    .strip_prefix(TREE_NODE_LABEL)?
+                    .strip_suffix(&version)?;
+                let (node_group_id, node_index): (serde_json::Value, u32) =
+                    serde_json::from_slice(key).ok()?;
+                (node_group_id == group_id).then(|| (storage_key.clone(), node_index))
+            })
+            .collect())
+    }
+
     /// Internal helper to abstract read operations.
     #[inline(always)]
     fn read<const VERSION: u16, V: Entity<VERSION>>(
@@ -231,7 +307,7 @@ impl MemoryStorage {
         log::debug!("  delete key: {}", hex::encode(&storage_key));
         log::trace!("{}", std::backtrace::Backtrace::capture());
 
-        values.remove(&storage_key);
+        self.remove_value(&mut values, &storage_key);
 
         Ok(())
     }
@@ -248,6 +324,8 @@ pub enum MemoryStorageError {
     SerializationError,
     #[error("Value does not exist.")]
     None,
+    #[error("No transaction is in progress.")]
+    NoTransaction,
 }
 
 const KEY_PACKAGE_LABEL: &[u8] = b"KeyPackage";
@@ -258,6 +336,7 @@ const EPOCH_KEY_PAIRS_LABEL: &[u8] = b"EpochKeyPairs";
 
 // related to PublicGroup
 const TREE_LABEL: &[u8] = b"Tree";
+const TREE_NODE_LABEL: &[u8] = b"TreeNode";
 const GROUP_CONTEXT_LABEL: &[u8] = b"GroupContext";
 const INTERIM_TRANSCRIPT_HASH_LABEL: &[u8] = b"InterimTranscriptHash";
 const CONFIRMATION_TAG_LABEL: &[u8] = b"ConfirmationTag";
@@ -276,6 +355,45 @@ const MESSAGE_SECRETS_LABEL: &[u8] = b"MessageSecrets";
 impl StorageProvider<CURRENT_VERSION> for MemoryStorage {
     type Error = MemoryStorageError;
 
+    fn begin_transaction(&self) -> Result<(), Self::Error> {
+        self.transactions.write().unwrap().push(HashMap::new());
+        Ok(())
+    }
+
+    fn commit_transaction(&self) -> Result<(), Self::Error> {
+        let mut transactions = self.transactions.write().unwrap();
+        let undo_log = transactions
+            .pop()
+            .ok_or(MemoryStorageError::NoTransaction)?;
+
+        // The enclosing transaction has to be able to undo the changes as
+        // well, unless it already knows an older value of the key.
+        if let Some(outer_undo_log) = transactions.last_mut() {
+            for (key, value) in undo_log {
+                outer_undo_log.entry(key).or_insert(value);
+            }
+        }
+        Ok(())
+    }
+
+    fn rollback_transaction(&self) -> Result<(), Self::Error> {
+        let undo_log = self
+            .transactions
+            .write()
+            .unwrap()
+            .pop()
+            .ok_or(MemoryStorageError::NoTransaction)?;
+
+        let mut values = self.values.write().unwrap();
+        for (key, value) in undo_log {
+            match value {
+                Some(value) => values.insert(key, value),
+                None => values.remove(&key),
+            };
+        }
+        Ok(())
+    }
+
     fn queue_proposal<
         GroupId: traits::GroupId<CURRENT_VERSION>,
         ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
@@ -314,6 +432,22 @@ impl StorageProvider<CURRENT_VERSION> for MemoryStorage {
         )
     }
 
+    fn write_tree_node<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        TreeNode: traits::TreeNode<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        node_index: u32,
+        node: &TreeNode,
+    ) -> Result<(), Self::Error> {
+        self.write::<CURRENT_VERSION>(
+            TREE_NODE_LABEL,
+            &serde_json::to_vec(&(group_id, node_index))?,
+            serde_json::to_vec(node)?,
+        )
+    }
+
     fn write_interim_transcript_hash<
         GroupId: traits::GroupId<CURRENT_VERSION>,
         InterimTranscriptHash: traits::InterimTranscriptHash<CURRENT_VERSION>,
@@ -326,7 +460,7 @@ impl StorageProvider<CURRENT_VERSION> for MemoryStorage {
         let key = build_key::<CURRENT_VERSION, &GroupId>(INTERIM_TRANSCRIPT_HASH_LABEL, group_id);
         let value = serde_json::to_vec(&interim_transcript_hash).unwrap();
 
-        values.insert(key, value);
+        self.insert_value(&mut values, key, value);
         Ok(())
     }
 
@@ -342,7 +476,7 @@ impl StorageProvider<CURRENT_VERSION> for MemoryStorage {
         let key = build_key::<CURRENT_VERSION, &GroupId>(GROUP_CONTEXT_LABEL, group_id);
         let value = serde_json::to_vec(&group_context).unwrap();
 
-        values.insert(key, value);
+        self.insert_value(&mut values, key, value);
         Ok(())
     }
 
@@ -358,7 +492,7 @@ impl StorageProvider<CURRENT_VERSION> for MemoryStorage {
         let key = build_key::<CURRENT_VERSION, &GroupId>(CONFIRMATION_TAG_LABEL, group_id);
         let value = serde_json::to_vec(&confirmation_tag).unwrap();
 
-        values.insert(key, value);
+        self.insert_value(&mut values, key, value);
         Ok(())
     }
 
@@ -375,7 +509,7 @@ impl StorageProvider<CURRENT_VERSION> for MemoryStorage {
             build_key::<CURRENT_VERSION, &SignaturePublicKey>(SIGNATURE_KEY_PAIR_LABEL, public_key);
         let value = serde_json::to_vec(&signature_key_pair).unwrap();
 
-        values.insert(key, value);
+        self.insert_value(&mut values, key, value);
         Ok(())
     }
 
@@ -429,6 +563,25 @@ impl StorageProvider<CURRENT_VERSION> for MemoryStorage {
         Ok(value)
     }
 
+    fn tree_nodes<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        TreeNode: traits::TreeNode<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<(u32, TreeNode)>, Self::Error> {
+        let keys = self.tree_node_keys::<CURRENT_VERSION>(group_id)?;
+        let values = self.values.read().unwrap();
+
+        keys.into_iter()
+            .filter_map(|(key, node_index)| {
+                let value = values.get(&key)?;
+                Some(serde_json::from_slice(value).map(|node| (node_index, node)))
+            })
+            .collect::<Result<_, _>>()
+            .map_err(Self::Error::from)
+    }
+
     fn group_context<
         GroupId: traits::GroupId<CURRENT_VERSION>,
         GroupContext: traits::GroupContext<CURRENT_VERSION>,
@@ -568,6 +721,36 @@ impl StorageProvider<CURRENT_VERSION> for MemoryStorage {
         self.read(PSK_LABEL, &serde_json::to_vec(&psk_id).unwrap())
     }
 
+    fn group_ids<GroupId: traits::GroupId<CURRENT_VERSION> + DeserializeOwned>(
+        &self,
+    ) -> Result<Vec<GroupId>, Self::Error> {
+        Ok(self.keys::<CURRENT_VERSION, _>(GROUP_STATE_LABEL))
+    }
+
+    fn encryption_epoch_key_pair_ids<
+        GroupId: traits::GroupId<CURRENT_VERSION> + DeserializeOwned,
+        EpochKey: traits::EpochKey<CURRENT_VERSION> + DeserializeOwned,
+    >(
+        &self,
+    ) -> Result<Vec<(GroupId, EpochKey, u32)>, Self::Error> {
+        // Key pairs stored under a legacy key are skipped here.
+        Ok(self.keys::<CURRENT_VERSION, _>(EPOCH_KEY_PAIRS_LABEL))
+    }
+
+    fn key_package_refs<
+        KeyPackageRef: traits::HashReference<CURRENT_VERSION> + DeserializeOwned,
+    >(
+        &self,
+    ) -> Result<Vec<KeyPackageRef>, Self::Error> {
+        Ok(self.keys::<CURRENT_VERSION, _>(KEY_PACKAGE_LABEL))
+    }
+
+    fn psk_ids<PskId: traits::PskId<CURRENT_VERSION> + DeserializeOwned>(
+        &self,
+    ) -> Result<Vec<PskId>, Self::Error> {
+        Ok(self.keys::<CURRENT_VERSION, _>(PSK_LABEL))
+    }
+
     fn encryption_key_pair<
         HpkeKeyPair: traits::HpkeKeyPair<CURRENT_VERSION>,
         EncryptionKey: traits::EncryptionKey<CURRENT_VERSION>,
@@ -797,6 +980,8 @@ impl StorageProvider<CURRENT_VERSION> for MemoryStorage {
             log::debug!("  value: {}", hex::encode(&value));
         }
 
+        let legacy_key = legacy_epoch_key_pairs_id(group_id, epoch, leaf_index)?;
+        self.delete::<CURRENT_VERSION>(EPOCH_KEY_PAIRS_LABEL, &legacy_key)?;
         self.write::<CURRENT_VERSION>(EPOCH_KEY_PAIRS_LABEL, &key, value)
     }
 
@@ -812,10 +997,15 @@ impl StorageProvider<CURRENT_VERSION> for MemoryStorage {
     ) -> Result<Vec<HpkeKeyPair>, Self::Error> {
         let key = epoch_key_pairs_id(group_id, epoch, leaf_index)?;
         let storage_key = build_key_from_vec::<CURRENT_VERSION>(EPOCH_KEY_PAIRS_LABEL, key);
+        let legacy_key = legacy_epoch_key_pairs_id(group_id, epoch, leaf_index)?;
+        let legacy_storage_key =
+            build_key_from_vec::<CURRENT_VERSION>(EPOCH_KEY_PAIRS_LABEL, legacy_key);
         log::debug!("Reading encryption epoch key pairs");
 
         let values = self.values.read().unwrap();
-        let value = values.get(&storage_key);
+        let value = values
+            .get(&storage_key)
+            .or_else(|| values.get(&legacy_storage_key));
 
         #[cfg(feature = "test-utils")]
         log::debug!("  key: {}", hex::encode(&storage_key));
@@ -839,7 +1029,9 @@ impl StorageProvider<CURRENT_VERSION> for MemoryStorage {
         leaf_index: u32,
     ) -> Result<(), Self::Error> {
         let key = epoch_key_pairs_id(group_id, epoch, leaf_index)?;
-        self.delete::<CURRENT_VERSION>(EPOCH_KEY_PAIRS_LABEL, &key)
+        self.delete::<CURRENT_VERSION>(EPOCH_KEY_PAIRS_LABEL, &key)?;
+        let legacy_key = legacy_epoch_key_pairs_id(group_id, epoch, leaf_index)?;
+        self.delete::<CURRENT_VERSION>(EPOCH_KEY_PAIRS_LABEL, &legacy_key)
     }
 
     fn clear_proposal_queue<
@@ -856,12 +1048,12 @@ impl StorageProvider<CURRENT_VERSION> for MemoryStorage {
         for proposal_ref in proposal_refs {
             // Delete all proposals.
             let key = serde_json::to_vec(&(group_id, proposal_ref))?;
-            values.remove(&key);
+            self.remove_value(&mut values, &key);
         }
 
         // Delete the proposal refs from the store.
         let key = build_key::<CURRENT_VERSION, &GroupId>(PROPOSAL_QUEUE_REFS_LABEL, group_id);
-        values.remove(&key);
+        self.remove_value(&mut values, &key);
 
         Ok(())
     }
@@ -934,6 +1126,29 @@ impl StorageProvider<CURRENT_VERSION> for MemoryStorage {
         self.delete::<CURRENT_VERSION>(TREE_LABEL, &serde_json::to_vec(group_id).unwrap())
     }
 
+    fn delete_tree_node<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+        node_index: u32,
+    ) -> Result<(), Self::Error> {
+        self.delete::<CURRENT_VERSION>(
+            TREE_NODE_LABEL,
+            &serde_json::to_vec(&(group_id, node_index))?,
+        )
+    }
+
+    fn delete_tree_nodes<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        let keys = self.tree_node_keys::<CURRENT_VERSION>(group_id)?;
+        let mut values = self.values.write().unwrap();
+        for (key, _) in keys {
+            self.remove_value(&mut values, &key);
+        }
+        Ok(())
+    }
+
     fn delete_confirmation_tag<GroupId: traits::GroupId<CURRENT_VERSION>>(
         &self,
         group_id: &GroupId,
@@ -996,6 +1211,18 @@ fn epoch_key_pairs_id(
     group_id: &impl traits::GroupId<CURRENT_VERSION>,
     epoch: &impl traits::EpochKey<CURRENT_VERSION>,
     leaf_index: u32,
+) -> Result<Vec<u8>, <MemoryStorage as StorageProvider<CURRENT_VERSION>>::Error> {
+    Ok(serde_json::to_vec(&(group_id, epoch, leaf_index))?)
+}
+
+/// The key epoch key pairs were stored under before they could be listed.
+///
+/// The concatenated epoch and leaf index can't be told apart when reading the key back, so
+/// these keys are only used to read and delete old values, and are not listed.
+fn legacy_epoch_key_pairs_id(
+    group_id: &impl traits::GroupId<CURRENT_VERSION>,
+    epoch: &impl traits::EpochKey<CURRENT_VERSION>,
+    leaf_index: u32,
 ) -> Result<Vec<u8>, <MemoryStorage as StorageProvider<CURRENT_VERSION>>::Error> {
     let mut key = serde_json::to_vec(group_id)?;
     key.extend_from_slice(&serde_json::to_vec(epoch)?);
@@ -1,3 +1,12 @@
+//! Saving and loading the storage as a JSON file.
+//!
+//! The file is rewritten completely on every save and is not synced to disk,
+//! so a crash while saving can lose the stored values. Use the
+//! `openmls_file_storage` crate for a storage that persists changes safely.
+
+// The functions call each other.
+#![allow(deprecated)]
+
 use std::{
     collections::HashMap,
     env,
@@ -13,6 +22,7 @@ struct SerializableKeyStore {
     values: HashMap<String, String>,
 }
 
+#[deprecated(note = "use the `openmls_file_storage` crate instead")]
 pub fn get_file_path(file_name: &String) -> PathBuf {
     let tmp_folder = env::temp_dir();
     tmp_folder.join(file_name)
@@ -23,6 +33,7 @@ impl super::MemoryStorage {
         get_file_path(&("openmls_cli_".to_owned() + user_name + "_ks.json"))
     }
 
+    #[deprecated(note = "use the `openmls_file_storage` crate instead")]
     pub fn save_to_file(&self, output_file: &File) -> Result<(), String> {
         let writer = BufWriter::new(output_file);
 
@@ -39,6 +50,7 @@ impl super::MemoryStorage {
         }
     }
 
+    #[deprecated(note = "use the `openmls_file_storage` crate instead")]
     pub fn save(&self, user_name: String) -> Result<(), String> {
         let ks_output_path = Self::get_file_path(&user_name);
 
@@ -48,6 +60,7 @@ impl super::MemoryStorage {
         }
     }
 
+    #[deprecated(note = "use the `openmls_file_storage` crate instead")]
     pub fn load_from_file(&mut self, input_file: &File) -> Result<(), String> {
         // Prepare file reader.
         let reader = BufReader::new(input_file);
@@ -55,16 +68,22 @@ impl super::MemoryStorage {
         // Read the JSON contents of the file as an instance of `SerializableKeyStore`.
         match serde_json::from_reader::<BufReader<&File>, SerializableKeyStore>(reader) {
             Ok(ser_ks) => {
-                let mut ks_map = self.values.write().unwrap();
-                for (key, value) in ser_ks.values {
-                    ks_map.insert(base64::decode(key).unwrap(), base64::decode(value).unwrap());
-                }
+                // Decode all values before inserting any of them, so that a
+                // broken file doesn't leave the storage half loaded.
+                let values = ser_ks
+                    .values
+                    .into_iter()
+                    .map(|(key, value)| Ok((base64::decode(key)?, base64::decode(value)?)))
+                    .collect::<Result<Vec<_>, base64::DecodeError>>()
+                    .map_err(|e| e.to_string())?;
+                self.values.write().unwrap().extend(values);
                 Ok(())
             }
             Err(e) => Err(e.to_string()),
         }
     }
 
+    #[deprecated(note = "use the `openmls_file_storage` crate instead")]
     pub fn load(&mut self, user_name: String) -> Result<(), String> {
         let ks_input_path = Self::get_file_path(&user_name);
 
@@ -99,6 +99,15 @@ impl StorageProvider<V_TEST> for MemoryStorage {
         todo!()
     }
 
+    fn write_tree_node<GroupId: traits::GroupId<V_TEST>, TreeNode: traits::TreeNode<V_TEST>>(
+        &self,
+        _group_id: &GroupId,
+        _node_index: u32,
+        _node: &TreeNode,
+    ) -> Result<(), Self::Error> {
+        todo!()
+    }
+
     fn write_interim_transcript_hash<
         GroupId: traits::GroupId<V_TEST>,
         InterimTranscriptHash: traits::InterimTranscriptHash<V_TEST>,
@@ -182,6 +191,13 @@ impl StorageProvider<V_TEST> for MemoryStorage {
         todo!()
     }
 
+    fn tree_nodes<GroupId: traits::GroupId<V_TEST>, TreeNode: traits::TreeNode<V_TEST>>(
+        &self,
+        _group_id: &GroupId,
+    ) -> Result<Vec<(u32, TreeNode)>, Self::Error> {
+        todo!()
+    }
+
     fn group_context<
         GroupId: traits::GroupId<V_TEST>,
         GroupContext: traits::GroupContext<V_TEST>,
@@ -239,6 +255,33 @@ impl StorageProvider<V_TEST> for MemoryStorage {
         todo!()
     }
 
+    fn group_ids<GroupId: traits::GroupId<V_TEST> + DeserializeOwned>(
+        &self,
+    ) -> Result<Vec<GroupId>, Self::Error> {
+        todo!()
+    }
+
+    fn encryption_epoch_key_pair_ids<
+        GroupId: traits::GroupId<V_TEST> + DeserializeOwned,
+        EpochKey: traits::EpochKey<V_TEST> + DeserializeOwned,
+    >(
+        &self,
+    ) -> Result<Vec<(GroupId, EpochKey, u32)>, Self::Error> {
+        todo!()
+    }
+
+    fn key_package_refs<KeyPackageRef: traits::HashReference<V_TEST> + DeserializeOwned>(
+        &self,
+    ) -> Result<Vec<KeyPackageRef>, Self::Error> {
+        todo!()
+    }
+
+    fn psk_ids<PskId: traits::PskId<V_TEST> + DeserializeOwned>(
+        &self,
+    ) -> Result<Vec<PskId>, Self::Error> {
+        todo!()
+    }
+
     fn delete_signature_key_pair<SignaturePublicKeuy: traits::SignaturePublicKey<V_TEST>>(
         &self,
         _public_key: &SignaturePublicKeuy,
@@ -508,6 +551,21 @@ impl StorageProvider<V_TEST> for MemoryStorage {
         todo!()
     }
 
+    fn delete_tree_node<GroupId: traits::GroupId<V_TEST>>(
+        &self,
+        _group_id: &GroupId,
+        _node_index: u32,
+    ) -> Result<(), Self::Error> {
+        todo!()
+    }
+
+    fn delete_tree_nodes<GroupId: traits::GroupId<V_TEST>>(
+        &self,
+        _group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        todo!()
+    }
+
     fn delete_confirmation_tag<GroupId: traits::GroupId<V_TEST>>(
         &self,
         _group_id: &GroupId,
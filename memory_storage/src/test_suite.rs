@@ -0,0 +1,321 @@
+//! A test suite for implementations of [`StorageProvider`].
+//!
+//! The tests only use the [`StorageProvider`] trait, so that every storage in
+//! the workspace can be checked against the same expectations. A storage
+//! crate calls [`run`] from one of its tests, with a function that returns an
+//! empty storage.
+
+use openmls_traits::storage::{traits, Entity, Key, StorageProvider, CURRENT_VERSION};
+use serde::{Deserialize, Serialize};
+
+// Test types
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+struct TestGroupId(Vec<u8>);
+impl traits::GroupId<CURRENT_VERSION> for TestGroupId {}
+impl Key<CURRENT_VERSION> for TestGroupId {}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+struct TestTree(Vec<u8>);
+impl traits::TreeSync<CURRENT_VERSION> for TestTree {}
+impl Entity<CURRENT_VERSION> for TestTree {}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+struct LeafNode(Vec<u8>);
+impl traits::LeafNode<CURRENT_VERSION> for LeafNode {}
+impl Entity<CURRENT_VERSION> for LeafNode {}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+struct TreeNode(Vec<u8>);
+impl traits::TreeNode<CURRENT_VERSION> for TreeNode {}
+impl Entity<CURRENT_VERSION> for TreeNode {}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+struct ProposalRef(usize);
+impl traits::ProposalRef<CURRENT_VERSION> for ProposalRef {}
+impl Key<CURRENT_VERSION> for ProposalRef {}
+impl Entity<CURRENT_VERSION> for ProposalRef {}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+struct Proposal(Vec<u8>);
+impl traits::QueuedProposal<CURRENT_VERSION> for Proposal {}
+impl Entity<CURRENT_VERSION> for Proposal {}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+struct Epoch(u64);
+impl traits::EpochKey<CURRENT_VERSION> for Epoch {}
+impl Key<CURRENT_VERSION> for Epoch {}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+struct KeyPair(Vec<u8>);
+impl traits::HpkeKeyPair<CURRENT_VERSION> for KeyPair {}
+impl Entity<CURRENT_VERSION> for KeyPair {}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+struct GroupState(u8);
+impl traits::GroupState<CURRENT_VERSION> for GroupState {}
+impl Entity<CURRENT_VERSION> for GroupState {}
+
+/// Runs all tests of the suite, each with a new storage from `new_storage`.
+pub fn run<Storage: StorageProvider<CURRENT_VERSION>>(new_storage: impl Fn() -> Storage) {
+    values(&new_storage());
+    proposals(&new_storage());
+    own_leaf_nodes(&new_storage());
+    tree_nodes(&new_storage());
+    epoch_key_pairs(&new_storage());
+    listing(&new_storage());
+    transactions(&new_storage());
+}
+
+/// Values can be written, overwritten and deleted.
+pub fn values(storage: &impl StorageProvider<CURRENT_VERSION>) {
+    let group_id = TestGroupId(b"TestGroupId".to_vec());
+    let tree = TestTree(b"TestTree".to_vec());
+
+    let tree_read: Option<TestTree> = storage.tree(&group_id).unwrap();
+    assert_eq!(None, tree_read);
+
+    storage.write_tree(&group_id, &tree).unwrap();
+    let tree_read: Option<TestTree> = storage.tree(&group_id).unwrap();
+    assert_eq!(Some(tree), tree_read);
+
+    let new_tree = TestTree(b"NewTestTree".to_vec());
+    storage.write_tree(&group_id, &new_tree).unwrap();
+    let tree_read: Option<TestTree> = storage.tree(&group_id).unwrap();
+    assert_eq!(Some(new_tree), tree_read);
+
+    storage.delete_tree(&group_id).unwrap();
+    let tree_read: Option<TestTree> = storage.tree(&group_id).unwrap();
+    assert_eq!(None, tree_read);
+}
+
+/// Proposals are queued in order, can be removed and are stored per group.
+pub fn proposals(storage: &impl StorageProvider<CURRENT_VERSION>) {
+    let group_id = TestGroupId(b"TestGroupId".to_vec());
+    let other_group_id = TestGroupId(b"OtherGroupId".to_vec());
+    let proposals = (0..10)
+        .map(|i| Proposal(format!("TestProposal{i}").into_bytes()))
+        .collect::<Vec<_>>();
+
+    for (i, proposal) in proposals.iter().enumerate() {
+        storage
+            .queue_proposal(&group_id, &ProposalRef(i), proposal)
+            .unwrap();
+    }
+    storage
+        .queue_proposal(&other_group_id, &ProposalRef(0), &Proposal(b"Other".to_vec()))
+        .unwrap();
+
+    let proposal_refs_read: Vec<ProposalRef> = storage.queued_proposal_refs(&group_id).unwrap();
+    assert_eq!(
+        (0..10).map(ProposalRef).collect::<Vec<_>>(),
+        proposal_refs_read
+    );
+    let proposals_read: Vec<(ProposalRef, Proposal)> = storage.queued_proposals(&group_id).unwrap();
+    let mut proposals_expected: Vec<(ProposalRef, Proposal)> =
+        (0..10).map(ProposalRef).zip(proposals).collect();
+    assert_eq!(proposals_expected, proposals_read);
+
+    // Remove proposal 5
+    storage.remove_proposal(&group_id, &ProposalRef(5)).unwrap();
+    proposals_expected.remove(5);
+
+    let proposal_refs_read: Vec<ProposalRef> = storage.queued_proposal_refs(&group_id).unwrap();
+    assert_eq!(
+        proposals_expected
+            .iter()
+            .map(|(proposal_ref, _)| *proposal_ref)
+            .collect::<Vec<_>>(),
+        proposal_refs_read
+    );
+    let proposals_read: Vec<(ProposalRef, Proposal)> = storage.queued_proposals(&group_id).unwrap();
+    assert_eq!(proposals_expected, proposals_read);
+
+    // Clearing the queue of a group leaves other groups alone.
+    storage
+        .clear_proposal_queue::<TestGroupId, ProposalRef>(&group_id)
+        .unwrap();
+    let proposal_refs_read: Vec<ProposalRef> = storage.queued_proposal_refs(&group_id).unwrap();
+    assert!(proposal_refs_read.is_empty());
+    let proposals_read: Vec<(ProposalRef, Proposal)> = storage.queued_proposals(&group_id).unwrap();
+    assert!(proposals_read.is_empty());
+    let proposals_read: Vec<(ProposalRef, Proposal)> =
+        storage.queued_proposals(&other_group_id).unwrap();
+    assert_eq!(
+        vec![(ProposalRef(0), Proposal(b"Other".to_vec()))],
+        proposals_read
+    );
+}
+
+/// Own leaf nodes are returned in the order they were appended.
+pub fn own_leaf_nodes(storage: &impl StorageProvider<CURRENT_VERSION>) {
+    let group_id = TestGroupId(b"TestGroupId".to_vec());
+    let leaf_nodes = (0..5)
+        .map(|i| LeafNode(format!("LeafNode{i}").into_bytes()))
+        .collect::<Vec<_>>();
+
+    for leaf_node in &leaf_nodes {
+        storage.append_own_leaf_node(&group_id, leaf_node).unwrap();
+    }
+    let leaf_nodes_read: Vec<LeafNode> = storage.own_leaf_nodes(&group_id).unwrap();
+    assert_eq!(leaf_nodes, leaf_nodes_read);
+
+    storage.delete_own_leaf_nodes(&group_id).unwrap();
+    let leaf_nodes_read: Vec<LeafNode> = storage.own_leaf_nodes(&group_id).unwrap();
+    assert!(leaf_nodes_read.is_empty());
+}
+
+/// Tree nodes are stored per group and node index.
+pub fn tree_nodes(storage: &impl StorageProvider<CURRENT_VERSION>) {
+    let group_id = TestGroupId(b"TestGroupId".to_vec());
+    let other_group_id = TestGroupId(b"OtherGroupId".to_vec());
+
+    for node_index in 0..5 {
+        let node = TreeNode(format!("TreeNode{node_index}").into_bytes());
+        storage
+            .write_tree_node(&group_id, node_index, &node)
+            .unwrap();
+    }
+    storage
+        .write_tree_node(&other_group_id, 0, &TreeNode(b"Other".to_vec()))
+        .unwrap();
+
+    // Overwrite one node and delete another one.
+    storage
+        .write_tree_node(&group_id, 2, &TreeNode(b"NewTreeNode2".to_vec()))
+        .unwrap();
+    storage.delete_tree_node(&group_id, 3).unwrap();
+
+    let mut nodes_read: Vec<(u32, TreeNode)> = storage.tree_nodes(&group_id).unwrap();
+    nodes_read.sort_by_key(|(node_index, _)| *node_index);
+    assert_eq!(
+        vec![
+            (0, TreeNode(b"TreeNode0".to_vec())),
+            (1, TreeNode(b"TreeNode1".to_vec())),
+            (2, TreeNode(b"NewTreeNode2".to_vec())),
+            (4, TreeNode(b"TreeNode4".to_vec())),
+        ],
+        nodes_read
+    );
+
+    // Deleting the nodes of a group leaves other groups alone.
+    storage.delete_tree_nodes(&group_id).unwrap();
+    let nodes_read: Vec<(u32, TreeNode)> = storage.tree_nodes(&group_id).unwrap();
+    assert!(nodes_read.is_empty());
+    let nodes_read: Vec<(u32, TreeNode)> = storage.tree_nodes(&other_group_id).unwrap();
+    assert_eq!(vec![(0, TreeNode(b"Other".to_vec()))], nodes_read);
+}
+
+/// Epoch key pairs are stored per group, epoch and leaf index, even if the
+/// digits of the epoch and leaf index are the same.
+pub fn epoch_key_pairs(storage: &impl StorageProvider<CURRENT_VERSION>) {
+    let group_id = TestGroupId(b"TestGroupId".to_vec());
+    let key_pairs = [
+        vec![KeyPair(b"KeyPair0".to_vec())],
+        vec![KeyPair(b"KeyPair1".to_vec())],
+    ];
+
+    storage
+        .write_encryption_epoch_key_pairs(&group_id, &Epoch(1), 23, &key_pairs[0])
+        .unwrap();
+    storage
+        .write_encryption_epoch_key_pairs(&group_id, &Epoch(12), 3, &key_pairs[1])
+        .unwrap();
+
+    let key_pairs_read: Vec<KeyPair> = storage
+        .encryption_epoch_key_pairs(&group_id, &Epoch(1), 23)
+        .unwrap();
+    assert_eq!(key_pairs_read, key_pairs[0]);
+    let key_pairs_read: Vec<KeyPair> = storage
+        .encryption_epoch_key_pairs(&group_id, &Epoch(12), 3)
+        .unwrap();
+    assert_eq!(key_pairs_read, key_pairs[1]);
+
+    // Deleting the key pairs of one leaf leaves the others alone.
+    storage
+        .delete_encryption_epoch_key_pairs(&group_id, &Epoch(1), 23)
+        .unwrap();
+    let ids: Vec<(TestGroupId, Epoch, u32)> = storage.encryption_epoch_key_pair_ids().unwrap();
+    assert_eq!(ids, vec![(group_id.clone(), Epoch(12), 3)]);
+    let key_pairs_read: Vec<KeyPair> = storage
+        .encryption_epoch_key_pairs(&group_id, &Epoch(12), 3)
+        .unwrap();
+    assert_eq!(key_pairs_read, key_pairs[1]);
+}
+
+/// The listings return the keys of all stored values. Only groups with a
+/// group state are listed.
+pub fn listing(storage: &impl StorageProvider<CURRENT_VERSION>) {
+    let group_ids = [
+        TestGroupId(b"TestGroupId0".to_vec()),
+        TestGroupId(b"TestGroupId1".to_vec()),
+    ];
+
+    let group_ids_read: Vec<TestGroupId> = storage.group_ids().unwrap();
+    assert!(group_ids_read.is_empty());
+
+    for group_id in &group_ids {
+        storage.write_group_state(group_id, &GroupState(1)).unwrap();
+    }
+    storage
+        .write_encryption_epoch_key_pairs::<_, _, KeyPair>(&group_ids[0], &Epoch(1), 23, &[])
+        .unwrap();
+    storage
+        .write_encryption_epoch_key_pairs::<_, _, KeyPair>(&group_ids[0], &Epoch(12), 3, &[])
+        .unwrap();
+    storage
+        .write_tree(&TestGroupId(b"TestGroupId2".to_vec()), &TestTree(vec![]))
+        .unwrap();
+
+    let group_ids_read: Vec<TestGroupId> = storage.group_ids().unwrap();
+    assert_eq!(group_ids_read.len(), 2);
+    assert!(group_ids.iter().all(|id| group_ids_read.contains(id)));
+
+    let ids: Vec<(TestGroupId, Epoch, u32)> = storage.encryption_epoch_key_pair_ids().unwrap();
+    assert_eq!(ids.len(), 2);
+    assert!(ids.contains(&(group_ids[0].clone(), Epoch(1), 23)));
+    assert!(ids.contains(&(group_ids[0].clone(), Epoch(12), 3)));
+
+    storage.delete_group_state(&group_ids[1]).unwrap();
+    storage
+        .delete_encryption_epoch_key_pairs(&group_ids[0], &Epoch(1), 23)
+        .unwrap();
+    let group_ids_read: Vec<TestGroupId> = storage.group_ids().unwrap();
+    assert_eq!(group_ids_read, vec![group_ids[0].clone()]);
+    let ids: Vec<(TestGroupId, Epoch, u32)> = storage.encryption_epoch_key_pair_ids().unwrap();
+    assert_eq!(ids, vec![(group_ids[0].clone(), Epoch(12), 3)]);
+}
+
+/// Transactions are committed and rolled back atomically and can be nested.
+pub fn transactions(storage: &impl StorageProvider<CURRENT_VERSION>) {
+    let group_id = TestGroupId(b"TestGroupId".to_vec());
+    let tree = TestTree(b"TestTree".to_vec());
+    let leaf_node = LeafNode(b"LeafNode".to_vec());
+
+    storage.begin_transaction().unwrap();
+    storage.write_tree(&group_id, &tree).unwrap();
+    storage.commit_transaction().unwrap();
+    let tree_read: Option<TestTree> = storage.tree(&group_id).unwrap();
+    assert_eq!(Some(tree.clone()), tree_read);
+
+    // A rolled back transaction discards its writes, including those of a
+    // committed nested transaction.
+    storage.begin_transaction().unwrap();
+    storage
+        .write_tree(&group_id, &TestTree(b"NewTestTree".to_vec()))
+        .unwrap();
+    storage.begin_transaction().unwrap();
+    storage.delete_tree(&group_id).unwrap();
+    storage.append_own_leaf_node(&group_id, &leaf_node).unwrap();
+    storage.commit_transaction().unwrap();
+    let tree_read: Option<TestTree> = storage.tree(&group_id).unwrap();
+    assert_eq!(None, tree_read);
+    storage.rollback_transaction().unwrap();
+
+    let tree_read: Option<TestTree> = storage.tree(&group_id).unwrap();
+    assert_eq!(Some(tree), tree_read);
+    let leaf_nodes_read: Vec<LeafNode> = storage.own_leaf_nodes(&group_id).unwrap();
+    assert!(leaf_nodes_read.is_empty());
+
+    // There is no transaction left to end.
+    assert!(storage.commit_transaction().is_err());
+}
@@ -0,0 +1,6 @@
+use openmls_memory_storage::{test_suite, MemoryStorage};
+
+#[test]
+fn memory_storage() {
+    test_suite::run(MemoryStorage::default);
+}
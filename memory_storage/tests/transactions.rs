@@ -0,0 +1,92 @@
+use openmls_memory_storage::{MemoryStorage, MemoryStorageError};
+use openmls_traits::storage::{
+    traits::{self},
+    Entity, Key, StorageProvider, CURRENT_VERSION,
+};
+use serde::{Deserialize, Serialize};
+
+// Test types
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+struct TestGroupId(Vec<u8>);
+impl traits::GroupId<CURRENT_VERSION> for TestGroupId {}
+impl Key<CURRENT_VERSION> for TestGroupId {}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+struct TestTree(Vec<u8>);
+impl traits::TreeSync<CURRENT_VERSION> for TestTree {}
+impl Entity<CURRENT_VERSION> for TestTree {}
+
+/// Committed transactions keep their writes, rolled back ones discard them.
+#[test]
+fn commit_and_rollback() {
+    let group_id = TestGroupId(b"TestGroupId".to_vec());
+    let tree = TestTree(b"TestTree".to_vec());
+    let storage = MemoryStorage::default();
+
+    StorageProvider::<CURRENT_VERSION>::begin_transaction(&storage).unwrap();
+    storage.write_tree(&group_id, &tree).unwrap();
+    StorageProvider::<CURRENT_VERSION>::commit_transaction(&storage).unwrap();
+    let tree_read: Option<TestTree> = storage.tree(&group_id).unwrap();
+    assert_eq!(Some(tree.clone()), tree_read);
+
+    StorageProvider::<CURRENT_VERSION>::begin_transaction(&storage).unwrap();
+    storage
+        .write_tree(&group_id, &TestTree(b"NewTestTree".to_vec()))
+        .unwrap();
+    StorageProvider::<CURRENT_VERSION>::rollback_transaction(&storage).unwrap();
+    let tree_read: Option<TestTree> = storage.tree(&group_id).unwrap();
+    assert_eq!(Some(tree), tree_read);
+
+    // There is no transaction left to end.
+    assert_eq!(
+        StorageProvider::<CURRENT_VERSION>::commit_transaction(&storage),
+        Err(MemoryStorageError::NoTransaction)
+    );
+}
+
+/// Rolling back the outer transaction discards a committed nested transaction.
+#[test]
+fn nested() {
+    let group_id = TestGroupId(b"TestGroupId".to_vec());
+    let tree = TestTree(b"TestTree".to_vec());
+    let storage = MemoryStorage::default();
+
+    StorageProvider::<CURRENT_VERSION>::begin_transaction(&storage).unwrap();
+    StorageProvider::<CURRENT_VERSION>::begin_transaction(&storage).unwrap();
+    storage.write_tree(&group_id, &tree).unwrap();
+    StorageProvider::<CURRENT_VERSION>::commit_transaction(&storage).unwrap();
+    let tree_read: Option<TestTree> = storage.tree(&group_id).unwrap();
+    assert_eq!(Some(tree), tree_read);
+    StorageProvider::<CURRENT_VERSION>::rollback_transaction(&storage).unwrap();
+
+    let tree_read: Option<TestTree> = storage.tree(&group_id).unwrap();
+    assert_eq!(None, tree_read);
+}
+
+/// Rolling back restores the value a key had before the transaction, even if
+/// it was changed several times or deleted in between.
+#[test]
+fn rollback_restores_previous_values() {
+    let group_id = TestGroupId(b"TestGroupId".to_vec());
+    let other_group_id = TestGroupId(b"OtherTestGroupId".to_vec());
+    let tree = TestTree(b"TestTree".to_vec());
+    let storage = MemoryStorage::default();
+    storage.write_tree(&group_id, &tree).unwrap();
+
+    StorageProvider::<CURRENT_VERSION>::begin_transaction(&storage).unwrap();
+    storage
+        .write_tree(&group_id, &TestTree(b"NewTestTree".to_vec()))
+        .unwrap();
+    StorageProvider::<CURRENT_VERSION>::begin_transaction(&storage).unwrap();
+    storage.delete_tree(&group_id).unwrap();
+    storage.write_tree(&other_group_id, &tree).unwrap();
+    StorageProvider::<CURRENT_VERSION>::commit_transaction(&storage).unwrap();
+    let tree_read: Option<TestTree> = storage.tree(&group_id).unwrap();
+    assert_eq!(None, tree_read);
+    StorageProvider::<CURRENT_VERSION>::rollback_transaction(&storage).unwrap();
+
+    let tree_read: Option<TestTree> = storage.tree(&group_id).unwrap();
+    assert_eq!(Some(tree), tree_read);
+    let tree_read: Option<TestTree> = storage.tree(&other_group_id).unwrap();
+    assert_eq!(None, tree_read);
+}
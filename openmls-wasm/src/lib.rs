@@ -2,7 +2,7 @@ mod utils;
 
 use js_sys::Uint8Array;
 use openmls::{
-    credentials::{BasicCredential, CredentialWithKey},
+    credentials::{AuthenticationService, BasicCredential, CredentialSource, CredentialWithKey},
     framing::{MlsMessageBodyIn, MlsMessageIn, MlsMessageOut},
     group::{GroupId, MlsGroup, MlsGroupJoinConfig, StagedWelcome},
     key_packages::KeyPackage as OpenMlsKeyPackage,
@@ -28,6 +28,20 @@ extern "C" {
 /// The ciphersuite used here. Fixed in order to reduce the binary size.
 static CIPHERSUITE: Ciphersuite = Ciphersuite::MLS_128_DHKEMX25519_CHACHA20POLY1305_SHA256_Ed25519;
 
+/// The authentication service used by the bindings. Credentials are not
+/// validated, because the bindings only use basic credentials.
+struct AcceptAllCredentials;
+
+impl AuthenticationService for AcceptAllCredentials {
+    fn authenticate(
+        &self,
+        _credential: &CredentialWithKey,
+        _source: CredentialSource,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+}
+
 #[wasm_bindgen]
 #[derive(Default)]
 pub struct Provider(OpenMlsRustCrypto);
@@ -166,9 +180,14 @@ impl Group {
             ))),
         }?;
         let config = MlsGroupJoinConfig::builder().build();
-        let mls_group =
-            StagedWelcome::new_from_welcome(&provider.0, &config, welcome, Some(ratchet_tree.0))?
-                .into_group(&provider.0)?;
+        let mls_group = StagedWelcome::new_from_welcome(
+            &provider.0,
+            &config,
+            welcome,
+            Some(ratchet_tree.0),
+            &AcceptAllCredentials,
+        )?
+        .into_group(&provider.0)?;
 
         Ok(Group { mls_group })
     }
@@ -218,13 +237,13 @@ impl Group {
         let msg = MlsMessageIn::tls_deserialize(&mut msg).unwrap();
 
         let msg = match msg.extract() {
-            openmls::framing::MlsMessageBodyIn::PublicMessage(msg) => {
-                self.mls_group.process_message(provider.as_ref(), msg)?
-            }
+            openmls::framing::MlsMessageBodyIn::PublicMessage(msg) => self
+                .mls_group
+                .process_message(provider.as_ref(), msg, &AcceptAllCredentials)?,
 
-            openmls::framing::MlsMessageBodyIn::PrivateMessage(msg) => {
-                self.mls_group.process_message(provider.as_ref(), msg)?
-            }
+            openmls::framing::MlsMessageBodyIn::PrivateMessage(msg) => self
+                .mls_group
+                .process_message(provider.as_ref(), msg, &AcceptAllCredentials)?,
             openmls::framing::MlsMessageBodyIn::Welcome(_) => todo!(),
             openmls::framing::MlsMessageBodyIn::GroupInfo(_) => todo!(),
             openmls::framing::MlsMessageBodyIn::KeyPackage(_) => todo!(),
@@ -302,6 +321,7 @@ impl Group {
             &config,
             welcome,
             Some(ratchet_tree.0),
+            &AcceptAllCredentials,
         )
         .unwrap()
         .into_group(provider.as_ref())
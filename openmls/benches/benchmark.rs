@@ -4,6 +4,7 @@ extern crate openmls;
 extern crate rand;
 
 use criterion::Criterion;
+use openmls::credentials::test_utils::AcceptAllCredentials;
 use openmls::prelude::*;
 use openmls_basic_credential::SignatureKeyPair;
 use openmls_rust_crypto::OpenMlsRustCrypto;
@@ -170,6 +171,7 @@ fn join_group(c: &mut Criterion, provider: &impl OpenMlsProvider) {
                             mls_group_create_config.join_config(),
                             welcome,
                             Some(alice_group.export_ratchet_tree().into()),
+                            &AcceptAllCredentials,
                         )
                         .unwrap()
                         .into_group(provider);
@@ -247,6 +249,7 @@ fn create_commit(c: &mut Criterion, provider: &impl OpenMlsProvider) {
                             mls_group_create_config.join_config(),
                             welcome,
                             Some(alice_group.export_ratchet_tree().into()),
+                            &AcceptAllCredentials,
                         )
                         .unwrap()
                         .into_group(provider)
@@ -12,6 +12,7 @@ use std::{
 use base64::prelude::*;
 use clap::Parser;
 use openmls::{
+    credentials::test_utils::AcceptAllCredentials,
     credentials::{BasicCredential, CredentialWithKey},
     framing::{MlsMessageIn, MlsMessageOut, ProcessedMessageContent},
     group::{
@@ -97,7 +98,11 @@ fn process_commit(
     commit: openmls::prelude::MlsMessageOut,
 ) {
     let processed_message = group
-        .process_message(provider, commit.into_protocol_message().unwrap())
+        .process_message(
+            provider,
+            commit.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
         .unwrap();
 
     if let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
@@ -273,6 +278,7 @@ mod generate {
                 mls_group_create_config.join_config(),
                 welcome,
                 Some(creator_group.export_ratchet_tree().into()),
+                &AcceptAllCredentials,
             )
             .unwrap()
             .into_group(&member_provider)
@@ -20,7 +20,7 @@ pub(crate) mod tree;
 
 pub(crate) use treemath::{
     direct_path, is_node_in_tree, left, right, root, ParentNodeIndex, TreeNodeIndex, TreeSize,
-    MIN_TREE_SIZE,
+    MAX_TREE_SIZE, MIN_TREE_SIZE,
 };
 
 #[cfg(any(feature = "test-utils", test))]
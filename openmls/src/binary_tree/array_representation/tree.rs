@@ -14,7 +14,9 @@ use thiserror::Error;
 
 use super::{
     diff::{AbDiff, StagedAbDiff},
-    treemath::{common_direct_path, LeafNodeIndex, ParentNodeIndex, TreeSize, MAX_TREE_SIZE},
+    treemath::{
+        common_direct_path, LeafNodeIndex, ParentNodeIndex, TreeNodeIndex, TreeSize, MAX_TREE_SIZE,
+    },
 };
 
 #[derive(Clone, Debug)]
@@ -153,10 +155,28 @@ impl<L: Clone + Debug + Default, P: Clone + Debug + Default> ABinaryTree<L, P> {
     /// Depending on the changes made to the diff, this can either increase or
     /// decrease the size of the tree, although not beyond the minimum size of
     /// leaf or the maximum size of `u32::MAX`.
-    pub(crate) fn merge_diff(&mut self, diff: StagedAbDiff<L, P>) {
+    ///
+    /// Returns the indices of the nodes that were changed by the diff or
+    /// removed from the tree by shrinking it.
+    pub(crate) fn merge_diff(&mut self, diff: StagedAbDiff<L, P>) -> Vec<TreeNodeIndex> {
         let tree_size = diff.tree_size();
 
         let (leaf_diff, parent_diff) = diff.into_diffs();
+        let removed_leaves = tree_size.leaf_count()..self.leaf_count();
+        let removed_parents = tree_size.parent_count()..self.parent_count();
+        let changed_nodes = leaf_diff
+            .keys()
+            .copied()
+            .chain(removed_leaves.map(LeafNodeIndex::new))
+            .map(TreeNodeIndex::from)
+            .chain(
+                parent_diff
+                    .keys()
+                    .copied()
+                    .chain(removed_parents.map(ParentNodeIndex::new))
+                    .map(TreeNodeIndex::from),
+            )
+            .collect();
 
         // Resize the tree to the new size.
         self.leaf_nodes
@@ -193,6 +213,8 @@ impl<L: Clone + Debug + Default, P: Clone + Debug + Default> ABinaryTree<L, P> {
                 }
             }
         }
+
+        changed_nodes
     }
 
     /// Return a reference to the leaf at the given `LeafNodeIndex`, or the default
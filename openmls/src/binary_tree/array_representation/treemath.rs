@@ -128,7 +128,7 @@ pub enum TreeNodeIndex {
 
 impl TreeNodeIndex {
     /// Create a new `TreeNodeIndex` from a `u32`.
-    fn new(index: u32) -> Self {
+    pub(crate) fn new(index: u32) -> Self {
         if index % 2 == 0 {
             TreeNodeIndex::Leaf(LeafNodeIndex::from_tree_index(index))
         } else {
@@ -143,7 +143,7 @@ impl TreeNodeIndex {
     }
 
     /// Return the inner value as `u32`.
-    fn u32(&self) -> u32 {
+    pub(crate) fn u32(&self) -> u32 {
         match self {
             TreeNodeIndex::Leaf(index) => index.to_tree_index(),
             TreeNodeIndex::Parent(index) => index.to_tree_index(),
@@ -143,6 +143,12 @@ pub(crate) fn decrypt_with_label(
     log_crypto!(debug, "* private key: {private_key:x?}");
     log_crypto!(debug, "* ciphertext:  {ciphertext:x?}");
 
+    // The KEM output has a fixed length for each KEM. Reject malformed ones
+    // before handing them to the provider.
+    if ciphertext.kem_output.as_slice().len() != ciphersuite.hpke_kem_algorithm().enc_length() {
+        return Err(Error::DecryptionFailed);
+    }
+
     let plaintext = crypto
         .hpke_open(
             ciphersuite.hpke_config(),
@@ -304,17 +304,26 @@ pub fn run_test_vector(
         let my_signature = parsed.clone().sign(&parsed.key).unwrap();
 
         // verify signature
-        parsed
-            .clone()
-            .verify(
-                provider.crypto(),
-                &OpenMlsSignaturePublicKey::new(
-                    public.clone().into(),
-                    ciphersuite.signature_algorithm(),
+        // The signatures in the vectors for the P-521 and P-384 ciphersuites are
+        // computed over SHA-256 instead of the hash of the signature scheme.
+        // For these, only our own signature is verified.
+        if !matches!(
+            ciphersuite,
+            Ciphersuite::MLS_256_DHKEMP521_AES256GCM_SHA512_P521
+                | Ciphersuite::MLS_256_DHKEMP384_AES256GCM_SHA384_P384
+        ) {
+            parsed
+                .clone()
+                .verify(
+                    provider.crypto(),
+                    &OpenMlsSignaturePublicKey::new(
+                        public.clone().into(),
+                        ciphersuite.signature_algorithm(),
+                    )
+                    .unwrap(),
                 )
-                .unwrap(),
-            )
-            .expect("Signature verification failed");
+                .expect("Signature verification failed");
+        }
 
         // verify own signature
         parsed.signature = my_signature.0;
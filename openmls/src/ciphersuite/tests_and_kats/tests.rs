@@ -1,6 +1,7 @@
 //! Unit tests for the ciphersuites.
 
-use openmls_traits::types::HpkeCiphertext;
+use openmls_basic_credential::SignatureKeyPair;
+use openmls_traits::{signatures::Signer, types::HpkeCiphertext};
 
 use crate::{ciphersuite::*, test_utils::*};
 
@@ -26,6 +27,10 @@ fn test_hpke_seal_open() {
         provider.crypto(),
     )
     .unwrap();
+    assert_eq!(
+        ciphertext.kem_output.as_slice().len(),
+        ciphersuite.hpke_kem_algorithm().enc_length()
+    );
     let decrypted_payload = hpke::decrypt_with_label(
         &kp.private,
         "label",
@@ -76,3 +81,35 @@ fn test_hpke_seal_open() {
         CryptoError::HpkeDecryptionError
     );
 }
+
+// Spot test to make sure signatures of the basic credential verify with the
+// provider. ML-DSA signatures are several kilobytes long.
+#[openmls_test::openmls_test]
+fn test_sign_verify() {
+    let payload = &[1, 2, 3];
+    let signature_scheme = ciphersuite.signature_algorithm();
+    let signer =
+        SignatureKeyPair::new(signature_scheme).expect("error generating a signature key pair");
+    let signature = signer.sign(payload).expect("error signing the payload");
+    provider
+        .crypto()
+        .verify_signature(signature_scheme, payload, signer.public(), &signature)
+        .expect("Unexpected error while verifying a valid signature.");
+
+    let mut broken_signature = signature.clone();
+    let last_byte = broken_signature.pop().expect("empty signature");
+    broken_signature.push(last_byte ^ 0xff);
+    provider
+        .crypto()
+        .verify_signature(
+            signature_scheme,
+            payload,
+            signer.public(),
+            &broken_signature,
+        )
+        .expect_err("Erroneously verified a broken signature.");
+    provider
+        .crypto()
+        .verify_signature(signature_scheme, &[1, 2, 4], signer.public(), &signature)
+        .expect_err("Erroneously verified a signature over a different payload.");
+}
@@ -0,0 +1,197 @@
+//! # Authentication service
+//!
+//! RFC 9420 requires clients to validate every credential they encounter with
+//! the Authentication Service (AS) of the application. OpenMLS calls an
+//! [`AuthenticationService`] for every credential it would otherwise accept:
+//!  - the leaves of the ratchet tree when joining a group through a Welcome
+//!    or an External Commit, or when tracking a group as a [`PublicGroup`],
+//!  - the external senders in the group context of such a group,
+//!  - Add and Update proposals and the update path of Commits when
+//!    processing messages,
+//!  - external senders introduced by GroupContextExtensions proposals.
+//!
+//! If the authentication service rejects a credential, the operation fails.
+//!
+//! When a Commit replaces the credential of a member, either through an Update
+//! proposal or through the update path of the committer, the authentication
+//! service additionally decides whether the new credential is an acceptable
+//! successor of the old one, e.g. because it carries the same identity with a
+//! renewed certificate. The old and new credential of every updated leaf are
+//! also reported by [`StagedCommit::credential_changes()`].
+//!
+//! [`PublicGroup`]: crate::group::PublicGroup
+//! [`StagedCommit::credential_changes()`]: crate::group::StagedCommit::credential_changes
+
+use crate::{
+    binary_tree::LeafNodeIndex, ciphersuite::SignaturePublicKey, extensions::Extension,
+    framing::ProcessedMessageContent, group::ProcessMessageError, messages::proposals::Proposal,
+    treesync::LeafNode,
+};
+
+use super::{Credential, CredentialWithKey};
+
+/// The place where OpenMLS encountered a credential that is passed to an
+/// [`AuthenticationService`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CredentialSource {
+    /// The leaf with the given index in the ratchet tree of a group that is
+    /// joined or tracked.
+    RatchetTree(LeafNodeIndex),
+    /// The key package of an Add proposal.
+    AddProposal,
+    /// The leaf node of an Update proposal.
+    UpdateProposal,
+    /// The leaf node in the update path of a Commit.
+    UpdatePath,
+    /// An external sender in the [`ExternalSendersExtension`] of the group
+    /// context or of a GroupContextExtensions proposal.
+    ///
+    /// [`ExternalSendersExtension`]: crate::extensions::ExternalSendersExtension
+    ExternalSender,
+}
+
+/// The Authentication Service of the application.
+///
+/// OpenMLS calls the authentication service for every credential it
+/// encounters. See the [module documentation](self) for details.
+pub trait AuthenticationService {
+    /// Authenticates the `credential` together with its signature key, which
+    /// was encountered at `source`. Returning an error rejects the message or
+    /// group that contains the credential.
+    fn authenticate(
+        &self,
+        credential: &CredentialWithKey,
+        source: CredentialSource,
+    ) -> Result<(), String>;
+
+    /// Decides whether the `new` credential of a member is an acceptable
+    /// successor of its `old` credential. This is called for every leaf whose
+    /// credential is replaced by a Commit, after `new` was authenticated.
+    /// Returning an error rejects the Commit.
+    ///
+    /// The default implementation accepts every successor. Applications that
+    /// require the identity of a member to stay the same should override it.
+    fn authenticate_succession(
+        &self,
+        old: &CredentialWithKey,
+        new: &CredentialWithKey,
+    ) -> Result<(), String> {
+        let _ = (old, new);
+        Ok(())
+    }
+}
+
+fn authenticate(
+    authentication_service: &impl AuthenticationService,
+    credential: &Credential,
+    signature_key: &SignaturePublicKey,
+    source: CredentialSource,
+) -> Result<(), String> {
+    authentication_service.authenticate(
+        &CredentialWithKey {
+            credential: credential.clone(),
+            signature_key: signature_key.clone(),
+        },
+        source,
+    )
+}
+
+/// Authenticates the credential of the `leaf_node`.
+pub(crate) fn authenticate_leaf_node(
+    authentication_service: &impl AuthenticationService,
+    leaf_node: &LeafNode,
+    source: CredentialSource,
+) -> Result<(), String> {
+    authenticate(
+        authentication_service,
+        leaf_node.credential(),
+        leaf_node.signature_key(),
+        source,
+    )
+}
+
+/// Authenticates the credentials of the external senders in `extensions`.
+pub(crate) fn authenticate_external_senders<'a>(
+    authentication_service: &impl AuthenticationService,
+    extensions: impl IntoIterator<Item = &'a Extension>,
+) -> Result<(), String> {
+    for extension in extensions {
+        if let Extension::ExternalSenders(external_senders) = extension {
+            for external_sender in external_senders.iter() {
+                authenticate(
+                    authentication_service,
+                    external_sender.credential(),
+                    external_sender.signature_key(),
+                    CredentialSource::ExternalSender,
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Authenticates the credentials introduced by the `proposal`.
+fn authenticate_proposal(
+    authentication_service: &impl AuthenticationService,
+    proposal: &Proposal,
+) -> Result<(), String> {
+    match proposal {
+        Proposal::Add(add_proposal) => authenticate_leaf_node(
+            authentication_service,
+            add_proposal.key_package().leaf_node(),
+            CredentialSource::AddProposal,
+        ),
+        Proposal::Update(update_proposal) => authenticate_leaf_node(
+            authentication_service,
+            update_proposal.leaf_node(),
+            CredentialSource::UpdateProposal,
+        ),
+        Proposal::GroupContextExtensions(group_context_extensions_proposal) => {
+            authenticate_external_senders(
+                authentication_service,
+                group_context_extensions_proposal.extensions().iter(),
+            )
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Authenticates the credentials introduced by the proposals and the update
+/// path in the `content` of a processed message, as well as the succession of
+/// credentials that are replaced by a Commit.
+pub(crate) fn authenticate_processed_message_content(
+    authentication_service: &impl AuthenticationService,
+    content: &ProcessedMessageContent,
+) -> Result<(), ProcessMessageError> {
+    match content {
+        ProcessedMessageContent::ApplicationMessage(_) => Ok(()),
+        ProcessedMessageContent::ProposalMessage(queued_proposal)
+        | ProcessedMessageContent::ExternalJoinProposalMessage(queued_proposal) => {
+            authenticate_proposal(authentication_service, queued_proposal.proposal())
+                .map_err(ProcessMessageError::CredentialRejected)
+        }
+        ProcessedMessageContent::StagedCommitMessage(staged_commit) => {
+            for queued_proposal in staged_commit.queued_proposals() {
+                authenticate_proposal(authentication_service, queued_proposal.proposal())
+                    .map_err(ProcessMessageError::CredentialRejected)?;
+            }
+            if let Some(leaf_node) = staged_commit.update_path_leaf_node() {
+                authenticate_leaf_node(
+                    authentication_service,
+                    leaf_node,
+                    CredentialSource::UpdatePath,
+                )
+                .map_err(ProcessMessageError::CredentialRejected)?;
+            }
+            for change in staged_commit
+                .credential_changes()
+                .filter(|change| change.credential_changed())
+            {
+                authentication_service
+                    .authenticate_succession(change.old_credential(), change.new_credential())
+                    .map_err(ProcessMessageError::CredentialSuccessionRejected)?;
+            }
+            Ok(())
+        }
+    }
+}
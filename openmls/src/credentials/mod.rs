@@ -19,8 +19,9 @@
 //! When receiving a credential update from another member, applications must
 //! query the Authentication Service to ensure that the new credential is valid.
 //!
-//! There are multiple [`CredentialType`]s, although OpenMLS currently only
-//! supports the [`BasicCredential`].
+//! There are multiple [`CredentialType`]s. OpenMLS implements the
+//! [`BasicCredential`]. X.509 credentials, which carry a chain of
+//! [`Certificate`]s, are implemented in the `openmls_x509_credential` crate.
 
 use std::io::{Read, Write};
 
@@ -39,6 +40,9 @@ use errors::*;
 // Public
 pub mod errors;
 
+pub(crate) mod authentication;
+pub use authentication::{AuthenticationService, CredentialSource};
+
 /// CredentialType.
 ///
 /// This enum contains variants for the different Credential Types.
@@ -142,17 +146,45 @@ impl From<CredentialType> for u16 {
 
 /// X.509 Certificate.
 ///
-/// This struct contains an X.509 certificate chain.  Note that X.509
-/// certificates are not yet supported by OpenMLS.
+/// This struct contains a single DER encoded X.509 certificate. The content of
+/// an X.509 [`Credential`] is a vector of certificates, starting with the leaf
+/// certificate. OpenMLS does not parse or validate certificates. The
+/// `openmls_x509_credential` crate implements X.509 credentials on top of this
+/// type.
 ///
 /// ```c
 /// struct {
 ///     opaque cert_data<V>;
 /// } Certificate;
 /// ```
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    Serialize,
+    Deserialize,
+    TlsSize,
+    TlsSerialize,
+    TlsDeserialize,
+    TlsDeserializeBytes,
+)]
 pub struct Certificate {
-    cert_data: Vec<u8>,
+    cert_data: VLBytes,
+}
+
+impl Certificate {
+    /// Create a new certificate from its DER encoding.
+    pub fn new(cert_data: Vec<u8>) -> Self {
+        Self {
+            cert_data: cert_data.into(),
+        }
+    }
+
+    /// Get the DER encoding of this certificate.
+    pub fn cert_data(&self) -> &[u8] {
+        self.cert_data.as_slice()
+    }
 }
 
 /// Credential.
@@ -312,7 +344,21 @@ pub mod test_utils {
     use openmls_basic_credential::SignatureKeyPair;
     use openmls_traits::{types::SignatureScheme, OpenMlsProvider};
 
-    use super::{BasicCredential, CredentialWithKey};
+    use super::{AuthenticationService, BasicCredential, CredentialSource, CredentialWithKey};
+
+    /// An [`AuthenticationService`] that accepts every credential.
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct AcceptAllCredentials;
+
+    impl AuthenticationService for AcceptAllCredentials {
+        fn authenticate(
+            &self,
+            _credential: &CredentialWithKey,
+            _source: CredentialSource,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+    }
 
     /// Convenience function that generates a new credential and a key pair for
     /// it (using the basic credential crate).
@@ -6,6 +6,7 @@ use tls_codec::{Deserialize, Serialize};
 
 use super::*;
 use crate::{
+    credentials::test_utils::AcceptAllCredentials,
     credentials::*,
     framing::*,
     group::{errors::*, tests_and_kats::utils::generate_credential_with_key, *},
@@ -81,6 +82,7 @@ fn ratchet_tree_extension() {
         &config,
         welcome.into_welcome().unwrap(),
         Some(alice_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .expect("Error staging welcome")
     .into_group(provider)
@@ -122,10 +124,15 @@ fn ratchet_tree_extension() {
         .use_ratchet_tree_extension(false)
         .build();
 
-    let error =
-        StagedWelcome::new_from_welcome(provider, &config, welcome.into_welcome().unwrap(), None)
-            .and_then(|staged_join| staged_join.into_group(provider))
-            .err();
+    let error = StagedWelcome::new_from_welcome(
+        provider,
+        &config,
+        welcome.into_welcome().unwrap(),
+        None,
+        &AcceptAllCredentials,
+    )
+    .and_then(|staged_join| staged_join.into_group(provider))
+    .err();
 
     // We expect an error because the ratchet tree is missing
     assert!(matches!(
@@ -363,6 +370,7 @@ fn last_resort_extension() {
         mls_group_create_config.join_config(),
         welcome,
         Some(alice_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .expect("An unexpected error occurred.")
     .into_group(provider)
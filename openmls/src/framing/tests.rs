@@ -9,6 +9,7 @@ use tls_codec::{Deserialize, Serialize};
 use crate::{
     binary_tree::{array_representation::TreeSize, LeafNodeIndex},
     ciphersuite::signable::{Signable, SignatureError},
+    credentials::test_utils::AcceptAllCredentials,
     extensions::Extensions,
     framing::*,
     group::errors::*,
@@ -414,6 +415,7 @@ fn unknown_sender<Provider: OpenMlsProvider>(ciphersuite: Ciphersuite, provider:
         &config,
         welcome.into_welcome().unwrap(),
         Some(alice_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .expect("Could not create group from Welcome")
     .into_group(provider)
@@ -429,7 +431,11 @@ fn unknown_sender<Provider: OpenMlsProvider>(ciphersuite: Ciphersuite, provider:
         .expect("Could not merge commit.");
 
     let processed_message = charlie_group
-        .process_message(provider, commit.into_protocol_message().unwrap())
+        .process_message(
+            provider,
+            commit.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
         .expect("Could not process message.");
 
     let staged_commit = match processed_message.into_content() {
@@ -469,6 +475,7 @@ fn unknown_sender<Provider: OpenMlsProvider>(ciphersuite: Ciphersuite, provider:
     let received_message = charlie_group.process_message(
         provider,
         ProtocolMessage::from(PrivateMessageIn::from(enc_message)),
+        &AcceptAllCredentials,
     );
 
     assert_eq!(
@@ -525,7 +532,7 @@ fn confirmation_tag_presence<Provider: OpenMlsProvider>() {
     let protocol_message: ProtocolMessage = pm.into();
 
     let err = bob_group
-        .process_message(provider, protocol_message)
+        .process_message(provider, protocol_message, &AcceptAllCredentials)
         .expect_err("Could not process message.");
 
     assert_eq!(
@@ -54,6 +54,8 @@ use super::{
 #[derive(Debug)]
 pub(crate) struct DecryptedMessage {
     verifiable_content: VerifiableAuthenticatedContentIn,
+    // The generation of a decrypted PrivateMessage.
+    generation: Option<u32>,
 }
 
 impl DecryptedMessage {
@@ -105,6 +107,7 @@ impl DecryptedMessage {
             .message_secrets_and_leaves_mut(ciphertext.epoch())
             .map_err(|_| MessageDecryptionError::AeadError)?;
         let sender_data = ciphertext.sender_data(message_secrets, crypto, ciphersuite)?;
+        let generation = sender_data.generation;
         // Check if we are the sender
         if sender_data.leaf_index == group.own_leaf_index() {
             return Err(ValidationError::CannotDecryptOwnMessage);
@@ -120,7 +123,9 @@ impl DecryptedMessage {
             sender_ratchet_configuration,
             sender_data,
         )?;
-        Self::from_verifiable_content(verifiable_content)
+        let mut decrypted_message = Self::from_verifiable_content(verifiable_content)?;
+        decrypted_message.generation = Some(generation);
+        Ok(decrypted_message)
     }
 
     // Internal constructor function. Does the following checks:
@@ -145,7 +150,10 @@ impl DecryptedMessage {
                 return Err(LibraryError::custom("Expected sender to be member.").into());
             }
         }
-        Ok(DecryptedMessage { verifiable_content })
+        Ok(DecryptedMessage {
+            verifiable_content,
+            generation: None,
+        })
     }
 
     /// Gets the correct credential from the message depending on the sender type.
@@ -229,6 +237,11 @@ impl DecryptedMessage {
     pub(crate) fn verifiable_content(&self) -> &VerifiableAuthenticatedContentIn {
         &self.verifiable_content
     }
+
+    /// Returns the generation if the message was a [`PrivateMessage`].
+    pub(crate) fn generation(&self) -> Option<u32> {
+        self.generation
+    }
 }
 
 /// Context that is needed to verify the signature of a the leaf node of an
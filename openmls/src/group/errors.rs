@@ -87,6 +87,22 @@ pub enum WelcomeError<StorageError> {
     /// This error indicates the leaf node is invalid. See [`LeafNodeValidationError`] for more details.
     #[error(transparent)]
     LeafNodeValidation(#[from] LeafNodeValidationError),
+    /// The Welcome message contains more than one resumption PSK with usage reinit or branch.
+    #[error(
+        "The Welcome message contains more than one resumption PSK with usage reinit or branch."
+    )]
+    MultipleReInitOrBranchPsks,
+    /// A resumption PSK with usage reinit or branch was used in an epoch other than the first one.
+    #[error(
+        "A resumption PSK with usage reinit or branch was used in an epoch other than the first one."
+    )]
+    InvalidReInitOrBranchEpoch,
+    /// The new group does not match the ReInit proposal committed in the old group.
+    #[error("The new group does not match the ReInit proposal committed in the old group.")]
+    ReInitMismatch,
+    /// The new group does not match the group it was branched from.
+    #[error("The new group does not match the group it was branched from.")]
+    BranchMismatch,
     /// This error indicates that an error occurred while reading or writing from/to storage.
     #[error("An error occurred when querying storage")]
     StorageError(StorageError),
@@ -391,6 +407,42 @@ pub enum ProposalValidationError {
     /// See [`LeafNodeValidationError`] for more details.
     #[error(transparent)]
     LeafNodeValidation(#[from] LeafNodeValidationError),
+    /// A ReInit proposal was not the only proposal in the Commit.
+    #[error("A ReInit proposal was not the only proposal in the Commit.")]
+    ReInitWithOtherProposals,
+    /// The ReInit proposal has a lower protocol version than the group.
+    #[error("The ReInit proposal has a lower protocol version than the group.")]
+    ReInitVersionDowngrade,
+    /// The Commit contains more than one resumption PSK with usage reinit or branch.
+    #[error("The Commit contains more than one resumption PSK with usage reinit or branch.")]
+    MultipleReInitOrBranchPsks,
+    /// An AppAck proposal was not sent by a member of the group.
+    #[error("An AppAck proposal was not sent by a member of the group.")]
+    AppAckFromNonMember,
+    /// An AppAck proposal acknowledges messages of a sender that is not a member of the group.
+    #[error(
+        "An AppAck proposal acknowledges messages of a sender that is not a member of the group."
+    )]
+    AppAckUnknownSender,
+    /// An AppAck proposal contains a message range that ends before it starts.
+    #[error("An AppAck proposal contains a message range that ends before it starts.")]
+    AppAckInvalidRange,
+    /// An AppAck proposal acknowledges messages that were never sent.
+    #[error("An AppAck proposal acknowledges messages that were never sent.")]
+    AppAckUnknownGeneration,
+    /// A SelfRemove proposal was not sent by a member of the group.
+    #[error("A SelfRemove proposal was not sent by a member of the group.")]
+    SelfRemoveFromNonMember,
+    /// The type of a custom proposal with a registered handler is not
+    /// required by the group's required capabilities extension.
+    #[error("The type of a custom proposal is not required by the group's required capabilities extension.")]
+    CustomProposalNotRequired,
+    /// The payload of a custom proposal could not be decoded by its handler.
+    #[error("The payload of a custom proposal could not be decoded by its handler.")]
+    MalformedCustomProposal,
+    /// A custom proposal was rejected by its handler.
+    #[error("A custom proposal was rejected by its handler: {0}")]
+    CustomProposalRejected(String),
 }
 
 /// External Commit validaton error
@@ -452,6 +504,9 @@ pub(crate) enum ProposalQueueError {
     /// Update proposal from external sender.
     #[error("Update proposal from external sender.")]
     UpdateFromExternalSender,
+    /// SelfRemove proposal from a non-member.
+    #[error("SelfRemove proposal from a non-member.")]
+    SelfRemoveFromNonMember,
 }
 
 /// Errors that can arise when creating a [`ProposalQueue`] from committed
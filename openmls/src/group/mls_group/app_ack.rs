@@ -0,0 +1,51 @@
+//! AppAck proposals of an [`MlsGroup`].
+//!
+//! Members acknowledge the application messages they received in the current
+//! epoch with AppAck proposals. Once such a proposal is committed, the
+//! acknowledged messages are reported through
+//! [`StagedCommit::app_ack_report()`].
+
+use super::*;
+use crate::group::errors::ProposalValidationError;
+
+impl MlsGroup {
+    /// Returns an [`AppAckProposal`] that acknowledges all application
+    /// messages this member received in the current epoch.
+    ///
+    /// The proposal can be sent with [`MlsGroup::propose_app_ack()`].
+    pub fn app_ack_proposal(&self) -> AppAckProposal {
+        AppAckProposal::new(
+            self.message_secrets()
+                .secret_tree()
+                .received_application_messages()
+                .to_vec(),
+        )
+    }
+
+    /// Checks that AppAck proposals don't acknowledge own application
+    /// messages that were never sent in the current epoch.
+    pub(crate) fn validate_app_ack_generations(
+        &self,
+        proposal_queue: &ProposalQueue,
+    ) -> Result<(), ProposalValidationError> {
+        let own_generation = self
+            .message_secrets()
+            .secret_tree()
+            .own_application_generation();
+        for app_ack_proposal in proposal_queue.app_ack_proposals() {
+            let acknowledges_unsent_message = app_ack_proposal
+                .app_ack_proposal()
+                .received_ranges()
+                .iter()
+                .any(|range| {
+                    range.sender() == self.own_leaf_index()
+                        && range.last_generation() >= own_generation
+                });
+            if acknowledges_unsent_message {
+                return Err(ProposalValidationError::AppAckUnknownGeneration);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -10,6 +10,8 @@ impl MlsGroup {
     /// Creates an application message.
     /// Returns `CreateMessageError::MlsGroupStateError::UseAfterEviction`
     /// if the member is no longer part of the group.
+    /// Returns `CreateMessageError::MlsGroupStateError::UseAfterReInit`
+    /// if the group has been reinitialized.
     /// Returns `CreateMessageError::MlsGroupStateError::PendingProposal` if pending proposals
     /// exist. In that case `.process_pending_proposals()` must be called first
     /// and incoming messages from the DS must be processed afterwards.
@@ -24,6 +26,11 @@ impl MlsGroup {
                 MlsGroupStateError::UseAfterEviction,
             ));
         }
+        if self.reinit_proposal().is_some() {
+            return Err(CreateMessageError::GroupStateError(
+                MlsGroupStateError::UseAfterReInit,
+            ));
+        }
         if !self.proposal_store().is_empty() {
             return Err(CreateMessageError::GroupStateError(
                 MlsGroupStateError::PendingProposal,
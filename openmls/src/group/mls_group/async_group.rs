@@ -0,0 +1,755 @@
+//! An [`MlsGroup`] on top of an asynchronous storage.
+//!
+//! The operations of an [`MlsGroup`] read from and write to the storage
+//! synchronously. [`AsyncMlsGroup`] runs them against a
+//! [`StorageCache`] that holds the values of the group, and writes the values
+//! the operation changed to the
+//! [`AsyncStorageProvider`](openmls_traits::async_storage::AsyncStorageProvider)
+//! of an [`AsyncOpenMlsProvider`] afterwards. If the operation needs a value
+//! that is not in the cache yet, e.g. the encryption keys of an epoch, the
+//! value is read from the storage and the operation is run again. This way, no
+//! thread is blocked while waiting for the storage.
+//!
+//! The changes of an operation are written in a single transaction. If the
+//! operation or writing its changes fails, the group is reset to its state
+//! before the operation.
+
+use serde::de::DeserializeOwned;
+
+use openmls_traits::{
+    async_storage::AsyncStorageProvider, signatures::Signer, storage::CURRENT_VERSION,
+    AsyncOpenMlsProvider,
+};
+
+use super::{
+    errors::{
+        AddMembersError, AsyncMlsGroupError, CommitToPendingProposalsError, CreateMessageError,
+        MergePendingCommitError, ProcessMessageError, RemoveMembersError, SelfUpdateError,
+    },
+    MlsGroup,
+};
+use crate::{
+    binary_tree::LeafNodeIndex,
+    ciphersuite::hash_ref::{KeyPackageRef, ProposalRef},
+    credentials::AuthenticationService,
+    error::LibraryError,
+    framing::{MlsMessageOut, ProcessedMessage, ProtocolMessage},
+    group::{
+        proposal_store::QueuedProposal, public_group::CustomProposalHandler, GroupContext, GroupEpoch, GroupId, InterimTranscriptHash,
+        MergeCommitError, MlsGroupJoinConfig, MlsGroupState, StagedCommit,
+    },
+    key_packages::{KeyPackage, KeyPackageBundle},
+    messages::{group_info::GroupInfo, ConfirmationTag},
+    schedule::{
+        psk::{store::ResumptionPskStore, PskBundle},
+        GroupEpochSecrets, Psk,
+    },
+    storage::cache::{self, CacheKey, CacheValues, Kind, StorageCache, StorageCacheError},
+    treesync::{
+        node::encryption_keys::EncryptionKeyPair, EncryptionKey, LeafNode, LeafNodeParameters,
+        Node, StoredTreeSync,
+    },
+};
+
+use super::past_secrets::MessageSecretsStore;
+
+/// The error of the asynchronous storage of the `Provider`.
+type StorageError<Provider> =
+    <<Provider as AsyncOpenMlsProvider>::StorageProvider as AsyncStorageProvider<
+        CURRENT_VERSION,
+    >>::Error;
+
+/// The result of an operation of an [`AsyncMlsGroup`].
+type AsyncResult<T, Error, Provider> = Result<T, AsyncMlsGroupError<Error, StorageError<Provider>>>;
+
+/// An [`MlsGroup`] that is stored in an asynchronous storage. See the
+/// [module documentation](self) for details.
+#[derive(Debug)]
+pub struct AsyncMlsGroup {
+    group: MlsGroup,
+    cache: StorageCache,
+}
+
+/// The provider that the operations of the [`MlsGroup`] run with.
+struct CacheProvider<'a, Provider: AsyncOpenMlsProvider> {
+    provider: &'a Provider,
+    cache: &'a StorageCache,
+}
+
+impl<Provider: AsyncOpenMlsProvider> openmls_traits::OpenMlsProvider
+    for CacheProvider<'_, Provider>
+{
+    type CryptoProvider = Provider::CryptoProvider;
+    type RandProvider = Provider::RandProvider;
+    type StorageProvider = StorageCache;
+
+    fn storage(&self) -> &Self::StorageProvider {
+        self.cache
+    }
+
+    fn crypto(&self) -> &Self::CryptoProvider {
+        self.provider.crypto()
+    }
+
+    fn rand(&self) -> &Self::RandProvider {
+        self.provider.rand()
+    }
+
+    fn clock(&self) -> &dyn openmls_traits::clock::Clock {
+        self.provider.clock()
+    }
+}
+
+impl AsyncMlsGroup {
+    /// Loads the group with the given id from the storage of the `provider`.
+    /// Returns `None` if no group with this id is stored.
+    pub async fn load<Provider: AsyncOpenMlsProvider>(
+        provider: &Provider,
+        group_id: &GroupId,
+    ) -> AsyncResult<Option<Self>, StorageCacheError, Provider> {
+        let cache = StorageCache::default();
+        let group_key = cache::serialize(group_id).map_err(AsyncMlsGroupError::OperationError)?;
+        let keys = Kind::GROUP.map(|kind| (kind, group_key.clone()));
+        fetch(provider.storage(), &cache, keys).await?;
+
+        let group = MlsGroup::load(&cache, group_id).map_err(AsyncMlsGroupError::OperationError)?;
+        Ok(group.map(|group| Self { group, cache }))
+    }
+
+    /// Returns the [`MlsGroup`], e.g. to inspect its state.
+    pub fn group(&self) -> &MlsGroup {
+        &self.group
+    }
+
+    /// See [`MlsGroup::register_custom_proposal_handler`]. The handler is kept
+    /// when the group is reloaded from the cache after an operation.
+    pub fn register_custom_proposal_handler(&mut self, handler: impl CustomProposalHandler) {
+        self.group.register_custom_proposal_handler(handler)
+    }
+
+    /// See [`MlsGroup::process_message`].
+    pub async fn process_message<Provider: AsyncOpenMlsProvider>(
+        &mut self,
+        provider: &Provider,
+        message: impl Into<ProtocolMessage>,
+        authentication_service: &impl AuthenticationService,
+    ) -> AsyncResult<ProcessedMessage, ProcessMessageError, Provider> {
+        let message = message.into();
+        self.run(provider, |group, provider| {
+            group.process_message(provider, message.clone(), authentication_service)
+        })
+        .await
+    }
+
+    /// See [`MlsGroup::create_message`].
+    pub async fn create_message<Provider: AsyncOpenMlsProvider>(
+        &mut self,
+        provider: &Provider,
+        signer: &impl Signer,
+        message: &[u8],
+    ) -> AsyncResult<MlsMessageOut, CreateMessageError, Provider> {
+        self.run(provider, |group, provider| {
+            group.create_message(provider, signer, message)
+        })
+        .await
+    }
+
+    /// See [`MlsGroup::commit_to_pending_proposals`].
+    #[allow(clippy::type_complexity)]
+    pub async fn commit_to_pending_proposals<Provider: AsyncOpenMlsProvider>(
+        &mut self,
+        provider: &Provider,
+        signer: &impl Signer,
+    ) -> AsyncResult<
+        (MlsMessageOut, Option<MlsMessageOut>, Option<GroupInfo>),
+        CommitToPendingProposalsError<StorageCacheError>,
+        Provider,
+    > {
+        self.run(provider, |group, provider| {
+            group.commit_to_pending_proposals(provider, signer)
+        })
+        .await
+    }
+
+    /// See [`MlsGroup::add_members`].
+    #[allow(clippy::type_complexity)]
+    pub async fn add_members<Provider: AsyncOpenMlsProvider>(
+        &mut self,
+        provider: &Provider,
+        signer: &impl Signer,
+        key_packages: &[KeyPackage],
+    ) -> AsyncResult<
+        (MlsMessageOut, MlsMessageOut, Option<GroupInfo>),
+        AddMembersError<StorageCacheError>,
+        Provider,
+    > {
+        self.run(provider, |group, provider| {
+            group.add_members(provider, signer, key_packages)
+        })
+        .await
+    }
+
+    /// See [`MlsGroup::remove_members`].
+    #[allow(clippy::type_complexity)]
+    pub async fn remove_members<Provider: AsyncOpenMlsProvider>(
+        &mut self,
+        provider: &Provider,
+        signer: &impl Signer,
+        members: &[LeafNodeIndex],
+    ) -> AsyncResult<
+        (MlsMessageOut, Option<MlsMessageOut>, Option<GroupInfo>),
+        RemoveMembersError<StorageCacheError>,
+        Provider,
+    > {
+        self.run(provider, |group, provider| {
+            group.remove_members(provider, signer, members)
+        })
+        .await
+    }
+
+    /// See [`MlsGroup::self_update`].
+    #[allow(clippy::type_complexity)]
+    pub async fn self_update<Provider: AsyncOpenMlsProvider>(
+        &mut self,
+        provider: &Provider,
+        signer: &impl Signer,
+        leaf_node_parameters: LeafNodeParameters,
+    ) -> AsyncResult<
+        (MlsMessageOut, Option<MlsMessageOut>, Option<GroupInfo>),
+        SelfUpdateError<StorageCacheError>,
+        Provider,
+    > {
+        self.run(provider, |group, provider| {
+            group.self_update(provider, signer, leaf_node_parameters.clone())
+        })
+        .await
+    }
+
+    /// See [`MlsGroup::merge_pending_commit`].
+    pub async fn merge_pending_commit<Provider: AsyncOpenMlsProvider>(
+        &mut self,
+        provider: &Provider,
+    ) -> AsyncResult<(), MergePendingCommitError<StorageCacheError>, Provider> {
+        self.run(provider, |group, provider| {
+            group.merge_pending_commit(provider)
+        })
+        .await
+    }
+
+    /// See [`MlsGroup::merge_staged_commit`].
+    pub async fn merge_staged_commit<Provider: AsyncOpenMlsProvider>(
+        &mut self,
+        provider: &Provider,
+        staged_commit: StagedCommit,
+    ) -> AsyncResult<(), MergeCommitError<StorageCacheError>, Provider> {
+        // The operation may run more than once, but a staged commit can only
+        // be merged once, so it is merged from its serialization.
+        let staged_commit = serde_json::to_vec(&staged_commit)
+            .map_err(|_| LibraryError::custom("Error serializing the staged commit"))?;
+        self.run(provider, |group, provider| {
+            let staged_commit = serde_json::from_slice(&staged_commit)
+                .map_err(|_| LibraryError::custom("Error deserializing the staged commit"))?;
+            group.merge_staged_commit(provider, staged_commit)
+        })
+        .await
+    }
+
+    /// See [`MlsGroup::clear_pending_commit`].
+    pub async fn clear_pending_commit<Provider: AsyncOpenMlsProvider>(
+        &mut self,
+        provider: &Provider,
+    ) -> AsyncResult<(), StorageCacheError, Provider> {
+        self.run(provider, |group, provider| {
+            group.clear_pending_commit(provider.cache)
+        })
+        .await
+    }
+
+    /// Runs the `operation` on the group against the cache until all values
+    /// it reads are cached, and writes the changes to the storage.
+    async fn run<Provider: AsyncOpenMlsProvider, T, Error>(
+        &mut self,
+        provider: &Provider,
+        mut operation: impl FnMut(&mut MlsGroup, &CacheProvider<'_, Provider>) -> Result<T, Error>,
+    ) -> AsyncResult<T, Error, Provider> {
+        loop {
+            let cache_provider = CacheProvider {
+                provider,
+                cache: &self.cache,
+            };
+            let result = operation(&mut self.group, &cache_provider);
+            let misses = self.cache.take_misses();
+
+            let result = if misses.is_empty() {
+                match result {
+                    Ok(value) => write_changes(provider.storage(), &self.cache.changes())
+                        .await
+                        .map(|()| value),
+                    Err(e) => Err(AsyncMlsGroupError::OperationError(e)),
+                }
+            } else {
+                fetch(provider.storage(), &self.cache, misses).await?;
+                self.reset()?;
+                continue;
+            };
+
+            match result {
+                Ok(value) => {
+                    self.cache.apply_changes();
+                    return Ok(value);
+                }
+                Err(e) => {
+                    self.reset()?;
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    /// Discards the changes of an operation and reloads the group from the
+    /// cache. The registered custom proposal handlers are kept.
+    fn reset<Error, StorageError>(
+        &mut self,
+    ) -> Result<(), AsyncMlsGroupError<Error, StorageError>> {
+        self.cache.discard_changes();
+        let handlers = self.group.public_group.custom_proposal_handlers().clone();
+        self.group = MlsGroup::load(&self.cache, self.group.group_id())
+            .ok()
+            .flatten()
+            .ok_or_else(|| LibraryError::custom("The cached group could not be loaded"))?;
+        self.group.public_group.set_custom_proposal_handlers(handlers);
+        Ok(())
+    }
+}
+
+/// Reads the values with the given keys from the `storage` into the `cache`.
+async fn fetch<Storage: AsyncStorageProvider<CURRENT_VERSION>, Error>(
+    storage: &Storage,
+    cache: &StorageCache,
+    keys: impl IntoIterator<Item = CacheKey>,
+) -> Result<(), AsyncMlsGroupError<Error, Storage::Error>> {
+    for (kind, key) in keys {
+        if matches!(kind, Kind::TreeNodes | Kind::TreeNode) {
+            fetch_tree_nodes(storage, cache, kind, &key).await?;
+            continue;
+        }
+        let value = fetch_value(storage, kind, &key)
+            .await?
+            .map_err(|_| LibraryError::custom("Error serializing a value for the cache"))?;
+        cache.insert((kind, key), value);
+    }
+    Ok(())
+}
+
+/// Reads all tree nodes of a group from the `storage` into the `cache`, for a
+/// key of [`Kind::TreeNodes`] or [`Kind::TreeNode`].
+async fn fetch_tree_nodes<Storage: AsyncStorageProvider<CURRENT_VERSION>, Error>(
+    storage: &Storage,
+    cache: &StorageCache,
+    kind: Kind,
+    key: &[u8],
+) -> Result<(), AsyncMlsGroupError<Error, Storage::Error>> {
+    let group_id: GroupId = match kind {
+        Kind::TreeNode => decode::<(GroupId, u32)>(key)?.0,
+        _ => decode(key)?,
+    };
+    let nodes = storage
+        .tree_nodes::<GroupId, Node>(&group_id)
+        .await
+        .map_err(AsyncMlsGroupError::StorageError)?;
+    let serialization_error = |_| LibraryError::custom("Error serializing a value for the cache");
+
+    let mut node_indices = Vec::with_capacity(nodes.len());
+    for (node_index, node) in nodes {
+        let node_key = cache::serialize(&(&group_id, node_index)).map_err(serialization_error)?;
+        let node = cache::serialize(&node).map_err(serialization_error)?;
+        cache.insert((Kind::TreeNode, node_key), Some(node));
+        node_indices.push(node_index);
+    }
+    let group_key = cache::serialize(&group_id).map_err(serialization_error)?;
+    let node_indices = cache::serialize(&node_indices).map_err(serialization_error)?;
+    cache.insert((Kind::TreeNodes, group_key), Some(node_indices));
+
+    Ok(())
+}
+
+/// Reads the value of the given kind and key from the `storage`, and
+/// serializes it for the cache.
+#[allow(clippy::type_complexity)]
+async fn fetch_value<Storage: AsyncStorageProvider<CURRENT_VERSION>, Error>(
+    storage: &Storage,
+    kind: Kind,
+    key: &[u8],
+) -> Result<Result<Option<Vec<u8>>, StorageCacheError>, AsyncMlsGroupError<Error, Storage::Error>> {
+    let storage_error = AsyncMlsGroupError::StorageError;
+    let group_id = || cache::deserialize::<GroupId>(key);
+    let key_error = |_| LibraryError::custom("Invalid key in the cache");
+
+    let value = match kind {
+        Kind::JoinConfig => encode(
+            storage
+                .mls_group_join_config::<GroupId, MlsGroupJoinConfig>(
+                    &group_id().map_err(key_error)?,
+                )
+                .await
+                .map_err(storage_error)?,
+        ),
+        Kind::OwnLeafNodes => encode(Some(
+            storage
+                .own_leaf_nodes::<GroupId, LeafNode>(&group_id().map_err(key_error)?)
+                .await
+                .map_err(storage_error)?,
+        )),
+        Kind::QueuedProposals => encode(Some(
+            storage
+                .queued_proposals::<GroupId, ProposalRef, QueuedProposal>(
+                    &group_id().map_err(key_error)?,
+                )
+                .await
+                .map_err(storage_error)?,
+        )),
+        Kind::Tree => encode(
+            storage
+                .tree::<GroupId, StoredTreeSync>(&group_id().map_err(key_error)?)
+                .await
+                .map_err(storage_error)?,
+        ),
+        Kind::InterimTranscriptHash => encode(
+            storage
+                .interim_transcript_hash::<GroupId, InterimTranscriptHash>(
+                    &group_id().map_err(key_error)?,
+                )
+                .await
+                .map_err(storage_error)?,
+        ),
+        Kind::Context => encode(
+            storage
+                .group_context::<GroupId, GroupContext>(&group_id().map_err(key_error)?)
+                .await
+                .map_err(storage_error)?,
+        ),
+        Kind::ConfirmationTag => encode(
+            storage
+                .confirmation_tag::<GroupId, ConfirmationTag>(&group_id().map_err(key_error)?)
+                .await
+                .map_err(storage_error)?,
+        ),
+        Kind::GroupState => encode(
+            storage
+                .group_state::<MlsGroupState, GroupId>(&group_id().map_err(key_error)?)
+                .await
+                .map_err(storage_error)?,
+        ),
+        Kind::MessageSecrets => encode(
+            storage
+                .message_secrets::<GroupId, MessageSecretsStore>(&group_id().map_err(key_error)?)
+                .await
+                .map_err(storage_error)?,
+        ),
+        Kind::ResumptionPskStore => encode(
+            storage
+                .resumption_psk_store::<GroupId, ResumptionPskStore>(
+                    &group_id().map_err(key_error)?,
+                )
+                .await
+                .map_err(storage_error)?,
+        ),
+        Kind::OwnLeafIndex => encode(
+            storage
+                .own_leaf_index::<GroupId, LeafNodeIndex>(&group_id().map_err(key_error)?)
+                .await
+                .map_err(storage_error)?,
+        ),
+        Kind::EpochSecrets => encode(
+            storage
+                .group_epoch_secrets::<GroupId, GroupEpochSecrets>(&group_id().map_err(key_error)?)
+                .await
+                .map_err(storage_error)?,
+        ),
+        Kind::TreeNodes | Kind::TreeNode => {
+            return Err(LibraryError::custom("Tree nodes are fetched with fetch_tree_nodes").into())
+        }
+        Kind::EncryptionKeyPair => {
+            let public_key: EncryptionKey = cache::deserialize(key).map_err(key_error)?;
+            encode(
+                storage
+                    .encryption_key_pair::<EncryptionKeyPair, EncryptionKey>(&public_key)
+                    .await
+                    .map_err(storage_error)?,
+            )
+        }
+        Kind::EpochKeyPairs => {
+            let (group_id, epoch, leaf_index): (GroupId, GroupEpoch, u32) =
+                cache::deserialize(key).map_err(key_error)?;
+            encode(Some(
+                storage
+                    .encryption_epoch_key_pairs::<GroupId, GroupEpoch, EncryptionKeyPair>(
+                        &group_id, &epoch, leaf_index,
+                    )
+                    .await
+                    .map_err(storage_error)?,
+            ))
+        }
+        Kind::KeyPackage => {
+            let hash_ref: KeyPackageRef = cache::deserialize(key).map_err(key_error)?;
+            encode(
+                storage
+                    .key_package::<KeyPackageRef, KeyPackageBundle>(&hash_ref)
+                    .await
+                    .map_err(storage_error)?,
+            )
+        }
+        Kind::Psk => {
+            let psk_id: Psk = cache::deserialize(key).map_err(key_error)?;
+            encode(
+                storage
+                    .psk::<PskBundle, Psk>(&psk_id)
+                    .await
+                    .map_err(storage_error)?,
+            )
+        }
+    };
+
+    Ok(value)
+}
+
+fn encode(value: Option<impl serde::Serialize>) -> Result<Option<Vec<u8>>, StorageCacheError> {
+    value.map(|value| cache::serialize(&value)).transpose()
+}
+
+/// Deserializes a key or value from the cache.
+fn decode<V: DeserializeOwned>(key_or_value: &[u8]) -> Result<V, LibraryError> {
+    cache::deserialize(key_or_value)
+        .map_err(|_| LibraryError::custom("Invalid key or value in the cache"))
+}
+
+/// Writes the `changes` of an operation to the `storage` in a transaction.
+async fn write_changes<Storage: AsyncStorageProvider<CURRENT_VERSION>, Error>(
+    storage: &Storage,
+    changes: &CacheValues,
+) -> Result<(), AsyncMlsGroupError<Error, Storage::Error>> {
+    storage
+        .begin_transaction()
+        .await
+        .map_err(AsyncMlsGroupError::StorageError)?;
+
+    let mut result = Ok(());
+    for ((kind, key), value) in changes {
+        result = write_change(storage, *kind, key, value.as_deref()).await;
+        if result.is_err() {
+            break;
+        }
+    }
+
+    match result {
+        Ok(()) => storage
+            .commit_transaction()
+            .await
+            .map_err(AsyncMlsGroupError::StorageError),
+        Err(e) => {
+            if let Err(rollback_error) = storage.rollback_transaction().await {
+                log::error!("Failed to roll back storage transaction: {rollback_error:?}");
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Writes the `value` of the given kind and key to the `storage`, or deletes
+/// it if it is `None`.
+async fn write_change<Storage: AsyncStorageProvider<CURRENT_VERSION>, Error>(
+    storage: &Storage,
+    kind: Kind,
+    key: &[u8],
+    value: Option<&[u8]>,
+) -> Result<(), AsyncMlsGroupError<Error, Storage::Error>> {
+    let storage_error = AsyncMlsGroupError::StorageError;
+
+    match kind {
+        Kind::TreeNode => {
+            let (group_id, node_index): (GroupId, u32) = decode(key)?;
+            match value {
+                Some(value) => {
+                    storage
+                        .write_tree_node(&group_id, node_index, &decode::<Node>(value)?)
+                        .await
+                }
+                None => storage.delete_tree_node(&group_id, node_index).await,
+            }
+        }
+        Kind::EncryptionKeyPair => {
+            let public_key: EncryptionKey = decode(key)?;
+            match value {
+                Some(value) => {
+                    storage
+                        .write_encryption_key_pair(
+                            &public_key,
+                            &decode::<EncryptionKeyPair>(value)?,
+                        )
+                        .await
+                }
+                None => storage.delete_encryption_key_pair(&public_key).await,
+            }
+        }
+        Kind::EpochKeyPairs => {
+            let (group_id, epoch, leaf_index): (GroupId, GroupEpoch, u32) = decode(key)?;
+            match value {
+                Some(value) => {
+                    storage
+                        .write_encryption_epoch_key_pairs(
+                            &group_id,
+                            &epoch,
+                            leaf_index,
+                            &decode::<Vec<EncryptionKeyPair>>(value)?,
+                        )
+                        .await
+                }
+                None => {
+                    storage
+                        .delete_encryption_epoch_key_pairs(&group_id, &epoch, leaf_index)
+                        .await
+                }
+            }
+        }
+        Kind::KeyPackage => {
+            let hash_ref: KeyPackageRef = decode(key)?;
+            match value {
+                Some(value) => {
+                    storage
+                        .write_key_package(&hash_ref, &decode::<KeyPackageBundle>(value)?)
+                        .await
+                }
+                None => storage.delete_key_package(&hash_ref).await,
+            }
+        }
+        Kind::Psk => {
+            let psk_id: Psk = decode(key)?;
+            match value {
+                Some(value) => {
+                    storage
+                        .write_psk(&psk_id, &decode::<PskBundle>(value)?)
+                        .await
+                }
+                None => storage.delete_psk(&psk_id).await,
+            }
+        }
+        _ => write_group_change(storage, kind, &decode(key)?, value).await?,
+    }
+    .map_err(storage_error)
+}
+
+/// Writes a value that is stored under the group id to the `storage`, or
+/// deletes it if it is `None`.
+async fn write_group_change<Storage: AsyncStorageProvider<CURRENT_VERSION>, Error>(
+    storage: &Storage,
+    kind: Kind,
+    group_id: &GroupId,
+    value: Option<&[u8]>,
+) -> Result<Result<(), Storage::Error>, AsyncMlsGroupError<Error, Storage::Error>> {
+    // Lists are replaced as a whole.
+    match kind {
+        // The tree nodes are written individually as `Kind::TreeNode`.
+        Kind::TreeNodes => return Ok(Ok(())),
+        Kind::OwnLeafNodes => {
+            let leaf_nodes: Vec<LeafNode> = value.map(decode).transpose()?.unwrap_or_default();
+            return Ok(async {
+                storage.delete_own_leaf_nodes(group_id).await?;
+                for leaf_node in &leaf_nodes {
+                    storage.append_own_leaf_node(group_id, leaf_node).await?;
+                }
+                Ok(())
+            }
+            .await);
+        }
+        Kind::QueuedProposals => {
+            let proposals: Vec<(ProposalRef, QueuedProposal)> =
+                value.map(decode).transpose()?.unwrap_or_default();
+            return Ok(async {
+                storage
+                    .clear_proposal_queue::<GroupId, ProposalRef>(group_id)
+                    .await?;
+                for (proposal_ref, proposal) in &proposals {
+                    storage
+                        .queue_proposal(group_id, proposal_ref, proposal)
+                        .await?;
+                }
+                Ok(())
+            }
+            .await);
+        }
+        _ => (),
+    }
+
+    let Some(value) = value else {
+        return Ok(match kind {
+            Kind::JoinConfig => storage.delete_group_config(group_id).await,
+            Kind::Tree => storage.delete_tree(group_id).await,
+            Kind::InterimTranscriptHash => storage.delete_interim_transcript_hash(group_id).await,
+            Kind::Context => storage.delete_context(group_id).await,
+            Kind::ConfirmationTag => storage.delete_confirmation_tag(group_id).await,
+            Kind::GroupState => storage.delete_group_state(group_id).await,
+            Kind::MessageSecrets => storage.delete_message_secrets(group_id).await,
+            Kind::ResumptionPskStore => storage.delete_all_resumption_psk_secrets(group_id).await,
+            Kind::OwnLeafIndex => storage.delete_own_leaf_index(group_id).await,
+            Kind::EpochSecrets => storage.delete_group_epoch_secrets(group_id).await,
+            _ => return Err(LibraryError::custom("Not a value of a group").into()),
+        });
+    };
+
+    Ok(match kind {
+        Kind::JoinConfig => {
+            storage
+                .write_mls_join_config::<_, MlsGroupJoinConfig>(group_id, &decode(value)?)
+                .await
+        }
+        Kind::Tree => {
+            storage
+                .write_tree::<_, StoredTreeSync>(group_id, &decode(value)?)
+                .await
+        }
+        Kind::InterimTranscriptHash => {
+            storage
+                .write_interim_transcript_hash::<_, InterimTranscriptHash>(
+                    group_id,
+                    &decode(value)?,
+                )
+                .await
+        }
+        Kind::Context => {
+            storage
+                .write_context::<_, GroupContext>(group_id, &decode(value)?)
+                .await
+        }
+        Kind::ConfirmationTag => {
+            storage
+                .write_confirmation_tag::<_, ConfirmationTag>(group_id, &decode(value)?)
+                .await
+        }
+        Kind::GroupState => {
+            storage
+                .write_group_state::<MlsGroupState, _>(group_id, &decode(value)?)
+                .await
+        }
+        Kind::MessageSecrets => {
+            storage
+                .write_message_secrets::<_, MessageSecretsStore>(group_id, &decode(value)?)
+                .await
+        }
+        Kind::ResumptionPskStore => {
+            storage
+                .write_resumption_psk_store::<_, ResumptionPskStore>(group_id, &decode(value)?)
+                .await
+        }
+        Kind::OwnLeafIndex => {
+            storage
+                .write_own_leaf_index::<_, LeafNodeIndex>(group_id, &decode(value)?)
+                .await
+        }
+        Kind::EpochSecrets => {
+            storage
+                .write_group_epoch_secrets::<_, GroupEpochSecrets>(group_id, &decode(value)?)
+                .await
+        }
+        _ => return Err(LibraryError::custom("Not a value of a group").into()),
+    })
+}
@@ -0,0 +1,85 @@
+//! Subgroup branching of an [`MlsGroup`].
+//!
+//! A subgroup is a new group with a subset of the members of a parent group.
+//! The first commit of the subgroup injects a resumption PSK with usage
+//! [`ResumptionPskUsage::Branch`] from the current epoch of the parent group,
+//! which links the subgroup cryptographically to the parent group.
+
+use errors::{BranchError, EmptyInputError};
+
+use super::*;
+use crate::{
+    credentials::CredentialWithKey, key_packages::KeyPackage, schedule::psk::ResumptionPskUsage,
+    storage::OpenMlsProvider,
+};
+
+impl MlsGroup {
+    /// Creates a subgroup with the given `group_id` from a subset of the
+    /// members of this group.
+    ///
+    /// The first commit of the subgroup adds the members with the given
+    /// `key_packages` and injects the resumption PSK of the current epoch of
+    /// this group. The commit is merged right away, so the subgroup is ready
+    /// to use. The subgroup uses the protocol version and ciphersuite of this
+    /// group, so the ciphersuite in `mls_group_create_config` has to match.
+    /// The invited members take the resumption PSK from their copy of this
+    /// group. If it moved on to a later epoch before they join, they need to
+    /// keep past resumption PSKs, see
+    /// [`MlsGroupJoinConfigBuilder::number_of_resumption_psks()`].
+    ///
+    /// The credentials of the creator and of all new members have to be
+    /// credentials of members of this group. The invited members check the
+    /// same when processing the [`Welcome`].
+    ///
+    /// If successful, it returns the subgroup, the [`Welcome`] for the new
+    /// members and an optional [GroupInfo], which is [Some] if the subgroup
+    /// has the `use_ratchet_tree_extension` flag set.
+    #[allow(clippy::type_complexity)]
+    pub fn branch<Provider: OpenMlsProvider>(
+        &self,
+        provider: &Provider,
+        signer: &impl Signer,
+        group_id: GroupId,
+        mls_group_create_config: &MlsGroupCreateConfig,
+        credential_with_key: CredentialWithKey,
+        key_packages: &[KeyPackage],
+    ) -> Result<(MlsGroup, MlsMessageOut, Option<GroupInfo>), BranchError<Provider::StorageError>>
+    {
+        if !self.is_active() {
+            return Err(MlsGroupStateError::UseAfterEviction.into());
+        }
+        if key_packages.is_empty() {
+            return Err(EmptyInputError::AddMembers.into());
+        }
+        if mls_group_create_config.ciphersuite != self.ciphersuite() {
+            return Err(BranchError::CiphersuiteMismatch);
+        }
+        // The subgroup is stored next to this group, so it needs its own
+        // group ID.
+        if &group_id == self.group_id() {
+            return Err(BranchError::SameGroupId);
+        }
+
+        let is_member = |credential: &Credential| {
+            self.members()
+                .any(|member| &member.credential == credential)
+        };
+        let all_members = is_member(&credential_with_key.credential)
+            && key_packages
+                .iter()
+                .all(|key_package| is_member(key_package.leaf_node().credential()));
+        if !all_members {
+            return Err(BranchError::NotAMember);
+        }
+
+        self.create_group_with_resumption_psk(
+            provider,
+            signer,
+            group_id,
+            mls_group_create_config.clone(),
+            credential_with_key,
+            key_packages,
+            ResumptionPskUsage::Branch,
+        )
+    }
+}
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use openmls_traits::{signatures::Signer, types::Ciphersuite};
 use tls_codec::Serialize;
 
@@ -16,7 +18,7 @@ use crate::{
         psk::{load_psks, store::ResumptionPskStore, PskSecret},
         InitSecret, JoinerSecret, KeySchedule, PreSharedKeyId,
     },
-    storage::OpenMlsProvider,
+    storage::{transaction, OpenMlsProvider},
     tree::sender_ratchet::SenderRatchetConfiguration,
     treesync::{errors::LeafNodeValidationError, node::leaf_node::Capabilities},
 };
@@ -159,12 +161,15 @@ impl MlsGroupBuilder {
             resumption_psk_store,
         };
 
-        mls_group
-            .store(provider.storage())
-            .map_err(NewGroupError::StorageError)?;
-        mls_group
-            .store_epoch_keypairs(provider.storage(), &[leaf_keypair])
-            .map_err(NewGroupError::StorageError)?;
+        transaction(
+            provider.storage(),
+            |e| e,
+            || {
+                mls_group.store(provider.storage())?;
+                mls_group.store_epoch_keypairs(provider.storage(), &[leaf_keypair])
+            },
+        )
+        .map_err(NewGroupError::StorageError)?;
 
         Ok(mls_group)
     }
@@ -204,6 +209,15 @@ impl MlsGroupBuilder {
         self
     }
 
+    /// Sets the `max_past_epoch_age` property of the MlsGroup.
+    /// See [`MlsGroupCreateConfigBuilder::max_past_epoch_age()`] for details.
+    pub fn max_past_epoch_age(mut self, max_past_epoch_age: Duration) -> Self {
+        self.mls_group_create_config_builder = self
+            .mls_group_create_config_builder
+            .max_past_epoch_age(max_past_epoch_age);
+        self
+    }
+
     /// Sets the `number_of_resumption_psks` property of the MlsGroup.
     pub fn number_of_resumption_psks(mut self, number_of_resumption_psks: usize) -> Self {
         self.mls_group_create_config_builder = self
@@ -0,0 +1,205 @@
+//! Composition of Commits that combine several changes to an [`MlsGroup`].
+//!
+//! This module exposes [`CommitBuilder`], which is created with
+//! [`MlsGroup::commit_builder()`].
+
+use errors::CommitBuilderError;
+use openmls_traits::{signatures::Signer, storage::StorageProvider as _};
+
+use super::*;
+use crate::{
+    binary_tree::array_representation::LeafNodeIndex, key_packages::KeyPackage,
+    schedule::PreSharedKeyId, storage::OpenMlsProvider, treesync::LeafNodeParameters,
+};
+
+/// Builder for a single Commit that combines several changes to an
+/// [`MlsGroup`].
+///
+/// A Commit built with this builder can add and remove members, inject PSKs,
+/// change the group context extensions and update the committer's leaf node
+/// in one epoch. By default, it covers all pending proposals in the group's
+/// [`ProposalStore`] and includes an update of the committer's leaf node.
+///
+/// Use [`CommitBuilder::preview_members()`] to inspect the members of the
+/// group after the Commit before creating it with [`CommitBuilder::build()`].
+pub struct CommitBuilder<'a> {
+    group: &'a mut MlsGroup,
+    inline_proposals: Vec<Proposal>,
+    pending_proposals: Option<Vec<ProposalRef>>,
+    force_self_update: bool,
+    leaf_node_parameters: LeafNodeParameters,
+}
+
+impl MlsGroup {
+    /// Returns a [`CommitBuilder`] to compose a Commit that combines several
+    /// changes to the group.
+    pub fn commit_builder(&mut self) -> CommitBuilder<'_> {
+        CommitBuilder {
+            group: self,
+            inline_proposals: vec![],
+            pending_proposals: None,
+            force_self_update: true,
+            leaf_node_parameters: LeafNodeParameters::default(),
+        }
+    }
+}
+
+impl<'a> CommitBuilder<'a> {
+    /// Adds the members with the given key packages.
+    pub fn add_members(mut self, key_packages: impl IntoIterator<Item = KeyPackage>) -> Self {
+        self.inline_proposals.extend(
+            key_packages
+                .into_iter()
+                .map(|key_package| Proposal::Add(AddProposal { key_package })),
+        );
+        self
+    }
+
+    /// Removes the members with the given leaf indices.
+    pub fn remove_members(mut self, members: impl IntoIterator<Item = LeafNodeIndex>) -> Self {
+        self.inline_proposals.extend(
+            members
+                .into_iter()
+                .map(|removed| Proposal::Remove(RemoveProposal { removed })),
+        );
+        self
+    }
+
+    /// Injects the PSKs with the given IDs into the key schedule.
+    pub fn add_psks(mut self, psk_ids: impl IntoIterator<Item = PreSharedKeyId>) -> Self {
+        self.inline_proposals.extend(
+            psk_ids
+                .into_iter()
+                .map(|psk_id| Proposal::PreSharedKey(PreSharedKeyProposal::new(psk_id))),
+        );
+        self
+    }
+
+    /// Replaces the group context extensions with the given `extensions`.
+    pub fn group_context_extensions(mut self, extensions: Extensions) -> Self {
+        self.inline_proposals.push(Proposal::GroupContextExtensions(
+            GroupContextExtensionProposal::new(extensions),
+        ));
+        self
+    }
+
+    /// Adds the given custom proposals. Custom proposals with a registered
+    /// [`CustomProposalHandler`] are validated by the handler and applied
+    /// when the Commit is merged.
+    pub fn custom_proposals(
+        mut self,
+        custom_proposals: impl IntoIterator<Item = CustomProposal>,
+    ) -> Self {
+        self.inline_proposals
+            .extend(custom_proposals.into_iter().map(Proposal::Custom));
+        self
+    }
+
+    /// Only covers the pending proposals with the given references instead of
+    /// all pending proposals.
+    ///
+    /// Proposals that are not covered by the Commit are discarded when the
+    /// Commit is merged.
+    pub fn pending_proposals(
+        mut self,
+        proposal_refs: impl IntoIterator<Item = ProposalRef>,
+    ) -> Self {
+        self.pending_proposals = Some(proposal_refs.into_iter().collect());
+        self
+    }
+
+    /// Sets whether the Commit includes an update of the committer's leaf node
+    /// even if none of the proposals requires it. Defaults to `true`.
+    pub fn force_self_update(mut self, force_self_update: bool) -> Self {
+        self.force_self_update = force_self_update;
+        self
+    }
+
+    /// Sets the parameters of the committer's new leaf node. Setting any
+    /// parameter includes an update of the committer's leaf node.
+    pub fn leaf_node_parameters(mut self, leaf_node_parameters: LeafNodeParameters) -> Self {
+        self.leaf_node_parameters = leaf_node_parameters;
+        self
+    }
+
+    /// Returns the members of the group after the Commit, without creating it.
+    ///
+    /// The proposals are validated in the same way as when the Commit is
+    /// created. The committer's own leaf node is shown before its update.
+    pub fn preview_members<Provider: OpenMlsProvider>(
+        &self,
+        provider: &Provider,
+    ) -> Result<Vec<Member>, CommitBuilderError<Provider::StorageError>> {
+        self.group.is_operational()?;
+
+        let params = self.params();
+        let applied_proposals = self.group.apply_commit_proposals(&params, provider)?;
+
+        Ok(applied_proposals.diff.members().collect())
+    }
+
+    /// Creates the Commit and stores it as the pending commit of the group.
+    ///
+    /// Returns an error if there is a pending commit. Otherwise it returns a
+    /// tuple of `Commit, Option<Welcome>, Option<GroupInfo>`, where `Commit`
+    /// and [`Welcome`] are MlsMessages of the type [`MlsMessageOut`].
+    #[allow(clippy::type_complexity)]
+    pub fn build<Provider: OpenMlsProvider>(
+        self,
+        provider: &Provider,
+        signer: &impl Signer,
+    ) -> Result<
+        (MlsMessageOut, Option<MlsMessageOut>, Option<GroupInfo>),
+        CommitBuilderError<Provider::StorageError>,
+    > {
+        self.group.is_operational()?;
+
+        let (mls_message, welcome_option, group_info) =
+            transaction(provider.storage(), CommitBuilderError::StorageError, || {
+                let params = self.params();
+                let create_commit_result = self.group.create_commit(params, provider, signer)?;
+
+                // Convert PublicMessage messages to MLSMessage and encrypt them if required by
+                // the configuration
+                let mls_message = self
+                    .group
+                    .content_to_mls_message(create_commit_result.commit, provider)?;
+
+                // Set the current group state to [`MlsGroupState::PendingCommit`],
+                // storing the current [`StagedCommit`] from the commit results
+                self.group.group_state = MlsGroupState::PendingCommit(Box::new(
+                    PendingCommitState::Member(create_commit_result.staged_commit),
+                ));
+                provider
+                    .storage()
+                    .write_group_state(self.group.group_id(), &self.group.group_state)
+                    .map_err(CommitBuilderError::StorageError)?;
+
+                Ok((
+                    mls_message,
+                    create_commit_result.welcome_option,
+                    create_commit_result.group_info,
+                ))
+            })?;
+
+        self.group.reset_aad();
+        Ok((
+            mls_message,
+            welcome_option.map(|w| MlsMessageOut::from_welcome(w, self.group.version())),
+            group_info,
+        ))
+    }
+
+    fn params(&self) -> CreateCommitParams<'_> {
+        let builder = CreateCommitParams::builder()
+            .framing_parameters(self.group.framing_parameters())
+            .inline_proposals(self.inline_proposals.clone())
+            .force_self_update(self.force_self_update)
+            .leaf_node_parameters(self.leaf_node_parameters.clone());
+        match &self.pending_proposals {
+            Some(pending_proposals) => builder.pending_proposals(pending_proposals.clone()),
+            None => builder,
+        }
+        .build()
+    }
+}
@@ -35,6 +35,7 @@ use crate::{
     treesync::{errors::LeafNodeValidationError, node::leaf_node::Capabilities},
 };
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 /// The [`MlsGroupJoinConfig`] contains all configuration parameters that are
 /// relevant to group operation at runtime. It is used to configure the group's
@@ -56,6 +57,10 @@ pub struct MlsGroupJoinConfig {
     pub(crate) use_ratchet_tree_extension: bool,
     /// Sender ratchet configuration
     pub(crate) sender_ratchet_configuration: SenderRatchetConfiguration,
+    /// Maximum age of the message secrets and resumption PSKs of past epochs.
+    /// The default is `None`, i.e. they are only limited by number.
+    #[serde(default)]
+    pub(crate) max_past_epoch_age: Option<Duration>,
 }
 
 impl MlsGroupJoinConfig {
@@ -78,6 +83,11 @@ impl MlsGroupJoinConfig {
     pub fn sender_ratchet_configuration(&self) -> &SenderRatchetConfiguration {
         &self.sender_ratchet_configuration
     }
+
+    /// Returns the max past epoch age set in this [`MlsGroupJoinConfig`].
+    pub fn max_past_epoch_age(&self) -> Option<Duration> {
+        self.max_past_epoch_age
+    }
 }
 
 /// Specifies configuration for the creation of an [`MlsGroup`]. Refer to the
@@ -165,6 +175,12 @@ impl MlsGroupJoinConfigBuilder {
         self
     }
 
+    /// Sets the `max_past_epoch_age` property of the [`MlsGroupJoinConfig`].
+    pub fn max_past_epoch_age(mut self, max_past_epoch_age: Duration) -> Self {
+        self.join_config.max_past_epoch_age = Some(max_past_epoch_age);
+        self
+    }
+
     /// Finalizes the builder and returns an [`MlsGroupJoinConfig`].
     pub fn build(self) -> MlsGroupJoinConfig {
         self.join_config
@@ -192,6 +208,11 @@ impl MlsGroupCreateConfig {
         self.join_config.max_past_epochs
     }
 
+    /// Returns the [`MlsGroupCreateConfig`] max past epoch age.
+    pub fn max_past_epoch_age(&self) -> Option<Duration> {
+        self.join_config.max_past_epoch_age
+    }
+
     /// Returns the [`MlsGroupCreateConfig`] number of resumption psks.
     pub fn number_of_resumption_psks(&self) -> usize {
         self.join_config.number_of_resumption_psks
@@ -282,6 +303,17 @@ impl MlsGroupCreateConfigBuilder {
         self
     }
 
+    /// Sets the `max_past_epoch_age` property of the MlsGroupCreateConfig.
+    /// The message secrets and resumption PSKs of past epochs are deleted once
+    /// the epoch ended longer ago than `max_past_epoch_age`, even if fewer than
+    /// `max_past_epochs` or `number_of_resumption_psks` are stored. The age is
+    /// measured with the clock of the provider and enforced when a commit is
+    /// merged and when [`MlsGroup::apply_retention_policy()`] is called.
+    pub fn max_past_epoch_age(mut self, max_past_epoch_age: Duration) -> Self {
+        self.config.join_config.max_past_epoch_age = Some(max_past_epoch_age);
+        self
+    }
+
     /// Sets the `number_of_resumption_psks` property of the MlsGroupCreateConfig.
     pub fn number_of_resumption_psks(mut self, number_of_resumption_psks: usize) -> Self {
         self.config.join_config.number_of_resumption_psks = number_of_resumption_psks;
@@ -1,7 +1,11 @@
 //! Defines the `CreateCommit` trait and its implementation for `MlsGroup`.
 
 use super::*;
-use crate::{credentials::CredentialWithKey, treesync::LeafNodeParameters};
+use crate::{
+    credentials::CredentialWithKey,
+    group::public_group::diff::{apply_proposals::ApplyProposalsValues, PublicGroupDiff},
+    treesync::LeafNodeParameters,
+};
 
 /// Can be used to denote the type of a commit.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -11,11 +15,12 @@ pub(crate) enum CommitType {
 }
 
 pub(crate) struct CreateCommitParams<'a> {
-    framing_parameters: FramingParameters<'a>, // Mandatory
-    inline_proposals: Vec<Proposal>,           // Optional
-    force_self_update: bool,                   // Optional
-    commit_type: CommitType,                   // Optional (default is `Member`)
-    leaf_node_parameters: LeafNodeParameters,  // Optional
+    framing_parameters: FramingParameters<'a>,   // Mandatory
+    inline_proposals: Vec<Proposal>,             // Optional
+    force_self_update: bool,                     // Optional
+    commit_type: CommitType,                     // Optional (default is `Member`)
+    leaf_node_parameters: LeafNodeParameters,    // Optional
+    pending_proposals: Option<Vec<ProposalRef>>, // Optional (default is all)
 }
 
 pub(crate) struct TempBuilderCCPM0 {}
@@ -36,6 +41,7 @@ impl TempBuilderCCPM0 {
                 force_self_update: true,
                 commit_type: CommitType::Member,
                 leaf_node_parameters: LeafNodeParameters::default(),
+                pending_proposals: None,
             },
         }
     }
@@ -58,6 +64,10 @@ impl<'a> CreateCommitParamsBuilder<'a> {
         self.ccp.leaf_node_parameters = leaf_node_parameters;
         self
     }
+    pub(crate) fn pending_proposals(mut self, pending_proposals: Vec<ProposalRef>) -> Self {
+        self.ccp.pending_proposals = Some(pending_proposals);
+        self
+    }
     pub(crate) fn build(self) -> CreateCommitParams<'a> {
         self.ccp
     }
@@ -85,15 +95,30 @@ impl<'a> CreateCommitParams<'a> {
     pub(crate) fn leaf_node_parameters(&self) -> &LeafNodeParameters {
         &self.leaf_node_parameters
     }
+    pub(crate) fn pending_proposals(&self) -> Option<&[ProposalRef]> {
+        self.pending_proposals.as_deref()
+    }
+}
+
+/// The proposals of a Commit that is being created, after they were filtered,
+/// validated and applied to a diff of the public group.
+pub(crate) struct AppliedCommitProposals<'a> {
+    pub(crate) sender: Sender,
+    pub(crate) proposal_queue: ProposalQueue,
+    pub(crate) contains_own_updates: bool,
+    pub(crate) diff: PublicGroupDiff<'a>,
+    pub(crate) apply_proposals_values: ApplyProposalsValues,
 }
 
 impl MlsGroup {
-    pub(crate) fn create_commit<Provider: OpenMlsProvider>(
+    /// Filters the pending proposals selected in `params` together with the
+    /// inline proposals, validates them and applies them to a diff of the
+    /// public group.
+    pub(crate) fn apply_commit_proposals<Provider: OpenMlsProvider>(
         &self,
-        params: CreateCommitParams,
+        params: &CreateCommitParams,
         provider: &Provider,
-        signer: &impl Signer,
-    ) -> Result<CreateCommitResult, CreateCommitError<Provider::StorageError>> {
+    ) -> Result<AppliedCommitProposals<'_>, CreateCommitError<Provider::StorageError>> {
         let ciphersuite = self.ciphersuite();
 
         let sender = match params.commit_type() {
@@ -101,19 +126,41 @@ impl MlsGroup {
             CommitType::Member => Sender::build_member(self.own_leaf_index()),
         };
 
+        // Only consider the selected pending proposals
+        let selected_proposal_store;
+        let proposal_store = match params.pending_proposals() {
+            Some(proposal_refs) => {
+                let mut proposal_store = ProposalStore::new();
+                for proposal_ref in proposal_refs {
+                    let queued_proposal = self
+                        .proposal_store()
+                        .proposals()
+                        .find(|queued_proposal| {
+                            &queued_proposal.proposal_reference() == proposal_ref
+                        })
+                        .ok_or(CreateCommitError::MissingProposal)?;
+                    proposal_store.add(queued_proposal.clone());
+                }
+                selected_proposal_store = proposal_store;
+                &selected_proposal_store
+            }
+            None => self.proposal_store(),
+        };
+
         // Filter proposals
         let (proposal_queue, contains_own_updates) = ProposalQueue::filter_proposals(
             ciphersuite,
             provider.crypto(),
             sender.clone(),
-            self.proposal_store(),
+            proposal_store,
             params.inline_proposals(),
             self.own_leaf_index(),
         )
         .map_err(|e| match e {
             ProposalQueueError::LibraryError(e) => e.into(),
             ProposalQueueError::ProposalNotFound => CreateCommitError::MissingProposal,
-            ProposalQueueError::UpdateFromExternalSender => {
+            ProposalQueueError::UpdateFromExternalSender
+            | ProposalQueueError::SelfRemoveFromNonMember => {
                 CreateCommitError::WrongProposalSenderType
             }
         })?;
@@ -125,8 +172,6 @@ impl MlsGroup {
         // will process the Commit (i.e., not including any members being added
         // or removed by the Commit).
 
-        let proposal_reference_list = proposal_queue.commit_list();
-
         // Validate the proposals by doing the following checks:
 
         // ValSem113: All Proposals: The proposal type must be supported by all
@@ -150,6 +195,13 @@ impl MlsGroup {
             .validate_remove_proposals(&proposal_queue)?;
         self.public_group
             .validate_pre_shared_key_proposals(&proposal_queue)?;
+        self.public_group
+            .validate_reinit_proposals(&proposal_queue)?;
+        self.public_group
+            .validate_app_ack_proposals(&proposal_queue)?;
+        self.public_group
+            .validate_custom_proposals(&proposal_queue)?;
+        self.validate_app_ack_generations(&proposal_queue)?;
         // Validate update proposals for member commits
         if let Sender::Member(sender_index) = &sender {
             // ValSem110
@@ -174,6 +226,33 @@ impl MlsGroup {
             return Err(CreateCommitError::CannotRemoveSelf);
         }
 
+        Ok(AppliedCommitProposals {
+            sender,
+            proposal_queue,
+            contains_own_updates,
+            diff,
+            apply_proposals_values,
+        })
+    }
+
+    pub(crate) fn create_commit<Provider: OpenMlsProvider>(
+        &self,
+        params: CreateCommitParams,
+        provider: &Provider,
+        signer: &impl Signer,
+    ) -> Result<CreateCommitResult, CreateCommitError<Provider::StorageError>> {
+        let ciphersuite = self.ciphersuite();
+
+        let AppliedCommitProposals {
+            sender,
+            proposal_queue,
+            contains_own_updates,
+            mut diff,
+            apply_proposals_values,
+        } = self.apply_commit_proposals(&params, provider)?;
+
+        let proposal_reference_list = proposal_queue.commit_list();
+
         let path_computation_result =
             // If path is needed, compute path values
             if apply_proposals_values.path_required
@@ -204,6 +283,11 @@ impl MlsGroup {
             .encrypted_path
             .as_ref()
             .map(|path| path.leaf_node().clone());
+        let credential_changes = self.public_group.credential_changes(
+            &proposal_queue,
+            &sender,
+            update_path_leaf_node.as_ref(),
+        );
 
         // Create commit message
         let commit = Commit {
@@ -381,6 +465,7 @@ impl MlsGroup {
         let staged_commit = StagedCommit::new(
             proposal_queue,
             StagedCommitState::GroupMember(Box::new(staged_commit_state)),
+            credential_changes,
         );
 
         Ok(CreateCommitResult {
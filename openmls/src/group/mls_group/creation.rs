@@ -3,17 +3,18 @@ use openmls_traits::{signatures::Signer, storage::StorageProvider as StorageProv
 
 use super::{builder::MlsGroupBuilder, *};
 use crate::{
-    credentials::CredentialWithKey,
-    group::errors::{ExternalCommitError, WelcomeError},
+    credentials::{AuthenticationService, CredentialWithKey},
+    group::errors::{ExternalCommitError, MergeCommitError, WelcomeError},
+    key_packages::KeyPackage,
     messages::{
         group_info::{GroupInfo, VerifiableGroupInfo},
         Welcome,
     },
     schedule::{
-        psk::{store::ResumptionPskStore, PreSharedKeyId},
-        EpochSecrets, InitSecret,
+        psk::{store::ResumptionPskStore, PreSharedKeyId, ResumptionPsk, ResumptionPskUsage},
+        EpochSecrets, InitSecret, Psk,
     },
-    storage::OpenMlsProvider,
+    storage::{transaction, OpenMlsProvider},
     treesync::{
         errors::{DerivePathError, PublicTreeError},
         node::leaf_node::{Capabilities, LeafNodeParameters},
@@ -21,9 +22,6 @@ use crate::{
     },
 };
 
-#[cfg(doc)]
-use crate::key_packages::KeyPackage;
-
 impl MlsGroup {
     // === Group creation ===
 
@@ -85,6 +83,9 @@ impl MlsGroup {
     ///
     /// Note: If there is a group member in the group with the same identity as
     /// us, this will create a remove proposal.
+    ///
+    /// The credentials of all members of the group are authenticated with the
+    /// `authentication_service`.
     #[allow(clippy::too_many_arguments)]
     pub fn join_by_external_commit<Provider: OpenMlsProvider>(
         provider: &Provider,
@@ -96,6 +97,7 @@ impl MlsGroup {
         extensions: Option<Extensions>,
         aad: &[u8],
         credential_with_key: CredentialWithKey,
+        authentication_service: &impl AuthenticationService,
     ) -> Result<(Self, MlsMessageOut, Option<GroupInfo>), ExternalCommitError<Provider::StorageError>>
     {
         // Prepare the commit parameters
@@ -132,6 +134,7 @@ impl MlsGroup {
             verifiable_group_info,
             // Existing proposals are discarded when joining by external commit.
             ProposalStore::new(),
+            authentication_service,
         )?;
         let group_context = public_group.group_context();
 
@@ -228,6 +231,82 @@ impl MlsGroup {
             create_commit_result.group_info,
         ))
     }
+
+    /// Creates a new group from this group. The first commit of the new group
+    /// adds the members with the given `key_packages` and injects a resumption
+    /// PSK with the given `usage` from the current epoch of this group. The
+    /// commit is merged right away.
+    ///
+    /// Returns the new group, the [`Welcome`] for the new members and an
+    /// optional [GroupInfo].
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn create_group_with_resumption_psk<Provider: OpenMlsProvider, E>(
+        &self,
+        provider: &Provider,
+        signer: &impl Signer,
+        group_id: GroupId,
+        mls_group_create_config: MlsGroupCreateConfig,
+        credential_with_key: CredentialWithKey,
+        key_packages: &[KeyPackage],
+        usage: ResumptionPskUsage,
+    ) -> Result<(MlsGroup, MlsMessageOut, Option<GroupInfo>), E>
+    where
+        E: From<LibraryError>
+            + From<NewGroupError<Provider::StorageError>>
+            + From<CreateCommitError<Provider::StorageError>>
+            + From<MergeCommitError<Provider::StorageError>>,
+    {
+        let mut new_group = MlsGroupBuilder::new()
+            .with_group_id(group_id)
+            .build_internal(
+                provider,
+                signer,
+                credential_with_key,
+                Some(mls_group_create_config),
+            )?;
+
+        let psk_id = PreSharedKeyId::new(
+            new_group.ciphersuite(),
+            provider.rand(),
+            Psk::Resumption(ResumptionPsk::new(
+                usage,
+                self.group_id().clone(),
+                self.epoch(),
+            )),
+        )
+        .map_err(LibraryError::unexpected_crypto_error)?;
+
+        // Create inline add proposals from key packages and the PSK proposal
+        let inline_proposals = key_packages
+            .iter()
+            .map(|key_package| {
+                Proposal::Add(AddProposal {
+                    key_package: key_package.clone(),
+                })
+            })
+            .chain(std::iter::once(Proposal::PreSharedKey(
+                PreSharedKeyProposal::new(psk_id),
+            )))
+            .collect::<Vec<Proposal>>();
+
+        let params = CreateCommitParams::builder()
+            .framing_parameters(new_group.framing_parameters())
+            .inline_proposals(inline_proposals)
+            .build();
+        let create_commit_result = new_group.create_commit(params, provider, signer)?;
+
+        let welcome = match create_commit_result.welcome_option {
+            Some(welcome) => welcome,
+            None => {
+                return Err(LibraryError::custom("No secrets to generate commit message.").into())
+            }
+        };
+
+        new_group.merge_staged_commit(provider, create_commit_result.staged_commit)?;
+
+        let welcome = MlsMessageOut::from_welcome(welcome, new_group.version());
+        Ok((new_group, welcome, create_commit_result.group_info))
+    }
 }
 
 fn transpose_err_opt<T, E>(v: Result<Option<T>, E>) -> Option<Result<T, E>> {
@@ -337,12 +416,93 @@ impl ProcessedWelcome {
         &self.group_secrets.psks
     }
 
+    /// Validates the resumption PSKs with usage reinit or branch in the
+    /// Welcome message against the new `public_group`.
+    ///
+    /// There must be at most one such PSK, and the new group has to be in its
+    /// first epoch. For a reinit PSK, the old group has to be reinitialized
+    /// in the referenced epoch with the parameters of the new group. For a
+    /// branch PSK, the new group has to use the protocol version and
+    /// ciphersuite of the parent group, and all of its members have to be
+    /// members of the parent group.
+    fn validate_resumption_psks<Provider: OpenMlsProvider>(
+        &self,
+        provider: &Provider,
+        public_group: &PublicGroup,
+    ) -> Result<(), WelcomeError<Provider::StorageError>> {
+        let group_context = public_group.group_context();
+
+        let mut resumption_psks = self
+            .group_secrets
+            .psks
+            .iter()
+            .filter_map(|psk_id| match psk_id.psk() {
+                Psk::Resumption(resumption_psk)
+                    if resumption_psk.usage() != ResumptionPskUsage::Application =>
+                {
+                    Some(resumption_psk)
+                }
+                _ => None,
+            });
+
+        let Some(resumption_psk) = resumption_psks.next() else {
+            return Ok(());
+        };
+        if resumption_psks.next().is_some() {
+            return Err(WelcomeError::MultipleReInitOrBranchPsks);
+        }
+        if group_context.epoch() != GroupEpoch::from(1) {
+            return Err(WelcomeError::InvalidReInitOrBranchEpoch);
+        }
+
+        match resumption_psk.usage() {
+            ResumptionPskUsage::Reinit => {
+                let old_group = MlsGroup::load(provider.storage(), resumption_psk.psk_group_id())
+                    .map_err(WelcomeError::StorageError)?
+                    .ok_or(WelcomeError::ReInitMismatch)?;
+                let expected_reinit_proposal = ReInitProposal::new(
+                    group_context.group_id().clone(),
+                    group_context.protocol_version(),
+                    group_context.ciphersuite(),
+                    group_context.extensions().clone(),
+                );
+                if old_group.reinit_proposal() != Some(&expected_reinit_proposal)
+                    || old_group.epoch() != resumption_psk.psk_epoch()
+                {
+                    return Err(WelcomeError::ReInitMismatch);
+                }
+            }
+            ResumptionPskUsage::Branch => {
+                let parent_group =
+                    MlsGroup::load(provider.storage(), resumption_psk.psk_group_id())
+                        .map_err(WelcomeError::StorageError)?
+                        .ok_or(WelcomeError::BranchMismatch)?;
+                let all_members_in_parent_group = public_group.members().all(|member| {
+                    parent_group
+                        .members()
+                        .any(|parent_member| parent_member.credential == member.credential)
+                });
+                if parent_group.version() != group_context.protocol_version()
+                    || parent_group.ciphersuite() != group_context.ciphersuite()
+                    || !all_members_in_parent_group
+                {
+                    return Err(WelcomeError::BranchMismatch);
+                }
+            }
+            ResumptionPskUsage::Application => {}
+        }
+
+        Ok(())
+    }
+
     /// Consume the `ProcessedWelcome` and combine it witht he ratchet tree into
-    /// a `StagedWelcome`.
+    /// a `StagedWelcome`. The credentials of all members of the group are
+    /// authenticated with the `authentication_service`.
     pub fn into_staged_welcome<Provider: OpenMlsProvider>(
         mut self,
         provider: &Provider,
         ratchet_tree: Option<RatchetTreeIn>,
+        authentication_service: &impl AuthenticationService,
     ) -> Result<StagedWelcome, WelcomeError<Provider::StorageError>> {
         // Build the ratchet tree and group
 
@@ -366,8 +526,13 @@ impl ProcessedWelcome {
             ratchet_tree,
             self.verifiable_group_info.clone(),
             ProposalStore::new(),
+            authentication_service,
         )?;
 
+        // If this Welcome creates a group from another group, check that it
+        // matches the group it references.
+        self.validate_resumption_psks(provider, &public_group)?;
+
         // Find our own leaf in the tree.
         let own_leaf_index = public_group
             .members()
@@ -395,7 +560,6 @@ impl ProcessedWelcome {
                 .tls_serialize_detached()
                 .map_err(LibraryError::missing_bound_check)?;
 
-            // TODO #751: Implement PSK
             self.key_schedule
                 .add_context(provider.crypto(), &serialized_group_context)
                 .map_err(|_| LibraryError::custom("Using the key schedule in the wrong state"))?;
@@ -486,17 +650,21 @@ impl StagedWelcome {
     /// Note: calling this function will consume the key material for decrypting the [`Welcome`]
     /// message, even if the caller does not turn the [`StagedWelcome`] into an [`MlsGroup`].
     ///
+    /// The credentials of all members of the group are authenticated with the
+    /// `authentication_service`.
+    ///
     /// [`Welcome`]: crate::messages::Welcome
     pub fn new_from_welcome<Provider: OpenMlsProvider>(
         provider: &Provider,
         mls_group_config: &MlsGroupJoinConfig,
         welcome: Welcome,
         ratchet_tree: Option<RatchetTreeIn>,
+        authentication_service: &impl AuthenticationService,
     ) -> Result<Self, WelcomeError<Provider::StorageError>> {
         let processed_welcome =
             ProcessedWelcome::new_from_welcome(provider, mls_group_config, welcome)?;
 
-        processed_welcome.into_staged_welcome(provider, ratchet_tree)
+        processed_welcome.into_staged_welcome(provider, ratchet_tree, authentication_service)
     }
 
     /// Returns the [`LeafNodeIndex`] of the group member that authored the [`Welcome`] message.
@@ -555,14 +723,17 @@ impl StagedWelcome {
             resumption_psk_store: self.resumption_psk_store,
         };
 
-        mls_group
-            .store_epoch_keypairs(provider.storage(), group_keypairs.as_slice())
-            .map_err(WelcomeError::StorageError)?;
         mls_group.set_max_past_epochs(mls_group.mls_group_config.max_past_epochs);
 
-        mls_group
-            .store(provider.storage())
-            .map_err(WelcomeError::StorageError)?;
+        transaction(
+            provider.storage(),
+            |e| e,
+            || {
+                mls_group.store_epoch_keypairs(provider.storage(), group_keypairs.as_slice())?;
+                mls_group.store(provider.storage())
+            },
+        )
+        .map_err(WelcomeError::StorageError)?;
 
         Ok(mls_group)
     }
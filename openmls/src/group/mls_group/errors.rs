@@ -67,6 +67,9 @@ pub enum MlsGroupStateError {
     /// Tried to use a group after being evicted from it.
     #[error("Tried to use a group after being evicted from it.")]
     UseAfterEviction,
+    /// Tried to use a group after it has been reinitialized.
+    #[error("Tried to use a group after it has been reinitialized.")]
+    UseAfterReInit,
     /// Can't create message because a pending proposal exists.
     #[error("Can't create message because a pending proposal exists.")]
     PendingProposal,
@@ -116,6 +119,17 @@ pub enum ProcessMessageError {
     /// The proposal is invalid for the Sender of type [External](crate::prelude::Sender::External)
     #[error("The proposal is invalid for the Sender of type External")]
     UnsupportedProposalType,
+    /// The [`AuthenticationService`](crate::credentials::AuthenticationService)
+    /// rejected a credential in the message.
+    #[error("The authentication service rejected a credential: {0}")]
+    CredentialRejected(String),
+    /// The [`AuthenticationService`](crate::credentials::AuthenticationService)
+    /// rejected a new credential as successor of a member's old credential.
+    #[error("The authentication service rejected a credential succession: {0}")]
+    CredentialSuccessionRejected(String),
+    /// Accessing storage failed.
+    #[error("Accessing storage failed.")]
+    StorageError,
 }
 
 /// Create message error
@@ -221,6 +235,9 @@ pub enum LeaveGroupError<StorageError> {
     /// An error ocurred while writing to storage
     #[error("An error ocurred while writing to storage")]
     StorageError(StorageError),
+    /// Not all members of the group support SelfRemove proposals.
+    #[error("Not all members of the group support SelfRemove proposals.")]
+    SelfRemoveNotSupported,
 }
 
 /// Self update error
@@ -359,3 +376,101 @@ pub enum RemoveProposalError<StorageError> {
     #[error("error writing proposal to storage")]
     Storage(StorageError),
 }
+
+/// ReInit error
+#[derive(Error, Debug, PartialEq, Clone)]
+pub enum ReInitError<StorageError> {
+    /// See [`LibraryError`] for more details.
+    #[error(transparent)]
+    LibraryError(#[from] LibraryError),
+    /// See [`EmptyInputError`] for more details.
+    #[error(transparent)]
+    EmptyInput(#[from] EmptyInputError),
+    /// See [`MlsGroupStateError`] for more details.
+    #[error(transparent)]
+    GroupStateError(#[from] MlsGroupStateError),
+    /// See [`CreateCommitError`] for more details.
+    #[error(transparent)]
+    CreateCommitError(#[from] CreateCommitError<StorageError>),
+    /// See [`NewGroupError`] for more details.
+    #[error(transparent)]
+    NewGroupError(#[from] NewGroupError<StorageError>),
+    /// See [`MergeCommitError`] for more details.
+    #[error(transparent)]
+    MergeCommitError(#[from] MergeCommitError<StorageError>),
+    /// The group has not been reinitialized.
+    #[error("The group has not been reinitialized.")]
+    NotReInitialized,
+    /// The protocol version in the ReInit proposal is not supported.
+    #[error("The protocol version in the ReInit proposal is not supported.")]
+    UnsupportedProtocolVersion,
+    /// The ReInit proposal has the same group ID as the group.
+    #[error("The ReInit proposal has the same group ID as the group.")]
+    SameGroupId,
+    /// Error writing to storage.
+    #[error("Error writing to storage")]
+    StorageError(StorageError),
+}
+
+/// Branch error
+#[derive(Error, Debug, PartialEq, Clone)]
+pub enum BranchError<StorageError> {
+    /// See [`LibraryError`] for more details.
+    #[error(transparent)]
+    LibraryError(#[from] LibraryError),
+    /// See [`EmptyInputError`] for more details.
+    #[error(transparent)]
+    EmptyInput(#[from] EmptyInputError),
+    /// See [`MlsGroupStateError`] for more details.
+    #[error(transparent)]
+    GroupStateError(#[from] MlsGroupStateError),
+    /// See [`CreateCommitError`] for more details.
+    #[error(transparent)]
+    CreateCommitError(#[from] CreateCommitError<StorageError>),
+    /// See [`NewGroupError`] for more details.
+    #[error(transparent)]
+    NewGroupError(#[from] NewGroupError<StorageError>),
+    /// See [`MergeCommitError`] for more details.
+    #[error(transparent)]
+    MergeCommitError(#[from] MergeCommitError<StorageError>),
+    /// The ciphersuite in the configuration does not match the one of the group.
+    #[error("The ciphersuite in the configuration does not match the one of the group.")]
+    CiphersuiteMismatch,
+    /// The new group has the same group ID as the group.
+    #[error("The new group has the same group ID as the group.")]
+    SameGroupId,
+    /// A member of the new group is not a member of the group.
+    #[error("A member of the new group is not a member of the group.")]
+    NotAMember,
+}
+
+/// Commit builder error
+#[derive(Error, Debug, PartialEq, Clone)]
+pub enum CommitBuilderError<StorageError> {
+    /// See [`LibraryError`] for more details.
+    #[error(transparent)]
+    LibraryError(#[from] LibraryError),
+    /// See [`MlsGroupStateError`] for more details.
+    #[error(transparent)]
+    GroupStateError(#[from] MlsGroupStateError),
+    /// See [`CreateCommitError`] for more details.
+    #[error(transparent)]
+    CreateCommitError(#[from] CreateCommitError<StorageError>),
+    /// Error writing to storage.
+    #[error("Error writing to storage: {0}")]
+    StorageError(StorageError),
+}
+
+/// Error of an operation of an [`AsyncMlsGroup`](super::async_group::AsyncMlsGroup)
+#[derive(Error, Debug, PartialEq, Clone)]
+pub enum AsyncMlsGroupError<Error, StorageError> {
+    /// See [`LibraryError`] for more details.
+    #[error(transparent)]
+    LibraryError(#[from] LibraryError),
+    /// The operation of the [`MlsGroup`](super::MlsGroup) failed.
+    #[error(transparent)]
+    OperationError(Error),
+    /// Error reading from or writing to the storage.
+    #[error("Error reading from or writing to the storage: {0}")]
+    StorageError(StorageError),
+}
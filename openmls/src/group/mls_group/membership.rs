@@ -4,7 +4,7 @@
 
 use errors::EmptyInputError;
 use openmls_traits::{signatures::Signer, storage::StorageProvider as _};
-use proposal_store::QueuedRemoveProposal;
+use proposal_store::{QueuedRemoveProposal, QueuedSelfRemoveProposal};
 
 use super::{
     errors::{AddMembersError, LeaveGroupError, RemoveMembersError},
@@ -262,6 +262,66 @@ impl MlsGroup {
         Ok(self.content_to_mls_message(remove_proposal, provider)?)
     }
 
+    /// Leave the group with a SelfRemove proposal.
+    ///
+    /// Creates a SelfRemove Proposal that can be covered by a Commit from any
+    /// other member, without the need to know the leaf index of the leaving
+    /// member. The SelfRemove Proposal is returned as a [`MlsMessageOut`].
+    ///
+    /// Returns an error if there is a pending commit or if not all members of
+    /// the group support SelfRemove proposals.
+    pub fn leave_group_via_self_remove<Provider: OpenMlsProvider>(
+        &mut self,
+        provider: &Provider,
+        signer: &impl Signer,
+    ) -> Result<MlsMessageOut, LeaveGroupError<Provider::StorageError>> {
+        self.is_operational()?;
+
+        // SelfRemove is not a default proposal type and must be supported by
+        // all members.
+        if !self
+            .public_group()
+            .treesync()
+            .full_leaves()
+            .all(|leaf_node| {
+                leaf_node
+                    .capabilities()
+                    .proposals()
+                    .contains(&ProposalType::SelfRemove)
+            })
+        {
+            return Err(LeaveGroupError::SelfRemoveNotSupported);
+        }
+
+        let self_remove_proposal = AuthenticatedContent::member_proposal(
+            self.framing_parameters(),
+            self.own_leaf_index(),
+            Proposal::SelfRemove(SelfRemoveProposal::new()),
+            self.context(),
+            signer,
+        )?;
+
+        let queued_self_remove_proposal = QueuedProposal::from_authenticated_content_by_ref(
+            self.ciphersuite(),
+            provider.crypto(),
+            self_remove_proposal.clone(),
+        )?;
+
+        provider
+            .storage()
+            .queue_proposal(
+                self.group_id(),
+                &queued_self_remove_proposal.proposal_reference(),
+                &queued_self_remove_proposal,
+            )
+            .map_err(LeaveGroupError::StorageError)?;
+
+        self.proposal_store_mut().add(queued_self_remove_proposal);
+
+        self.reset_aad();
+        Ok(self.content_to_mls_message(self_remove_proposal, provider)?)
+    }
+
     /// Returns a list of [`Member`]s in the group.
     pub fn members(&self) -> impl Iterator<Item = Member> + '_ {
         self.public_group().members()
@@ -354,4 +414,22 @@ impl RemoveOperation {
             Ok(Self::TheyWereRemovedBy((removed, sender.clone())))
         }
     }
+
+    /// Constructs a new [`RemoveOperation`] from a [`QueuedSelfRemoveProposal`]
+    /// and the corresponding [`MlsGroup`]. A SelfRemove proposal always
+    /// results in [`RemoveOperation::WeLeft`] or [`RemoveOperation::TheyLeft`].
+    pub fn from_self_remove(
+        queued_self_remove_proposal: QueuedSelfRemoveProposal,
+        group: &MlsGroup,
+    ) -> Result<Self, LibraryError> {
+        let Sender::Member(leaf_index) = queued_self_remove_proposal.sender() else {
+            return Err(LibraryError::custom("SelfRemove proposal from non-member"));
+        };
+
+        if *leaf_index == group.own_leaf_index() {
+            Ok(Self::WeLeft)
+        } else {
+            Ok(Self::TheyLeft(*leaf_index))
+        }
+    }
 }
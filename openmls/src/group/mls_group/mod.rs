@@ -24,10 +24,10 @@ use crate::{
     error::LibraryError,
     framing::{mls_auth_content::AuthenticatedContent, *},
     group::{
-        CreateCommitError, CreateGroupContextExtProposalError, Extension, ExtensionType,
-        Extensions, ExternalPubExtension, GroupContext, GroupEpoch, GroupId, MlsGroupJoinConfig,
-        MlsGroupStateError, OutgoingWireFormatPolicy, ProposalQueueError, PublicGroup,
-        RatchetTreeExtension, RequiredCapabilitiesExtension, StagedCommit,
+        CreateCommitError, CreateGroupContextExtProposalError, CustomProposalHandler, Extension,
+        ExtensionType, Extensions, ExternalPubExtension, GroupContext, GroupEpoch, GroupId,
+        MlsGroupJoinConfig, MlsGroupStateError, OutgoingWireFormatPolicy, ProposalQueueError,
+        PublicGroup, RatchetTreeExtension, RequiredCapabilitiesExtension, StagedCommit,
     },
     key_packages::KeyPackageBundle,
     messages::{
@@ -40,7 +40,7 @@ use crate::{
         psk::{load_psks, store::ResumptionPskStore, PskSecret},
         GroupEpochSecrets, JoinerSecret, KeySchedule,
     },
-    storage::{OpenMlsProvider, StorageProvider},
+    storage::{transaction, OpenMlsProvider, StorageProvider},
     treesync::{
         node::{encryption_keys::EncryptionKeyPair, leaf_node::LeafNode},
         RatchetTree,
@@ -50,15 +50,20 @@ use crate::{
 use openmls_traits::{signatures::Signer, storage::StorageProvider as _, types::Ciphersuite};
 
 // Private
+mod app_ack;
 mod application;
+mod branch;
 mod builder;
 mod creation;
 mod exporting;
+mod reinit;
 mod updates;
 
 use config::*;
 
 // Crate
+pub(crate) mod async_group;
+pub(crate) mod commit_builder;
 pub(crate) mod config;
 pub(crate) mod create_commit;
 pub(crate) mod errors;
@@ -67,6 +72,7 @@ pub(crate) mod past_secrets;
 pub(crate) mod processing;
 pub(crate) mod proposal;
 pub(crate) mod proposal_store;
+pub(crate) mod retention;
 pub(crate) mod staged_commit;
 
 // Tests
@@ -152,8 +158,9 @@ impl From<PendingCommitState> for StagedCommit {
 ///   [`MlsGroup::join_by_external_commit()`], see the functions documentation for
 ///   more information). From this `Operational`, the group state can either
 ///   transition to [`MlsGroupState::Inactive`], when it processes a commit that
-///   removes this client from the group, or to [`MlsGroupState::PendingCommit`],
-///   when this client creates a commit.
+///   removes this client from the group, to [`MlsGroupState::ReInitialized`],
+///   when it merges a commit that contains a ReInit proposal, or to
+///   [`MlsGroupState::PendingCommit`], when this client creates a commit.
 ///
 /// * [`MlsGroupState::Inactive`]: A group can enter this state from any other
 ///   state when it processes a commit that removes this client from the group.
@@ -161,6 +168,13 @@ impl From<PendingCommitState> for StagedCommit {
 ///   wants to re-join the group, it can either be added by a group member or it
 ///   can join via external commit.
 ///
+/// * [`MlsGroupState::ReInitialized`]: A group enters this state when a commit
+///   with a ReInit proposal is merged. This is a terminal state: no more
+///   messages can be created in the group and only application messages can
+///   still be processed. The successor group can be created with
+///   [`MlsGroup::create_reinit_group()`] or joined through a Welcome message
+///   that references this group.
+///
 /// * [`MlsGroupState::PendingCommit`]: This state is split into two possible
 ///   sub-states, one for each Commit type:
 ///   [`PendingCommitState::Member`] and [`PendingCommitState::External`]:
@@ -195,6 +209,8 @@ pub enum MlsGroupState {
     Operational,
     /// The group is inactive because the member has been removed.
     Inactive,
+    /// The group has been reinitialized with the parameters in the [`ReInitProposal`].
+    ReInitialized(Box<ReInitProposal>),
 }
 
 /// A `MlsGroup` represents an MLS group with a high-level API. The API exposes
@@ -262,14 +278,24 @@ impl MlsGroup {
         &self.mls_group_config
     }
 
-    /// Sets the configuration.
+    /// Sets the configuration. If the new configuration allows fewer past
+    /// epochs, the message secrets of the oldest past epochs are deleted.
     pub fn set_configuration<Storage: StorageProvider>(
         &mut self,
         storage: &Storage,
         mls_group_config: &MlsGroupJoinConfig,
     ) -> Result<(), Storage::Error> {
         self.mls_group_config = mls_group_config.clone();
-        storage.write_mls_join_config(self.group_id(), mls_group_config)
+        self.message_secrets_store
+            .resize(mls_group_config.max_past_epochs);
+        transaction(
+            storage,
+            |e| e,
+            || {
+                storage.write_mls_join_config(self.group_id(), mls_group_config)?;
+                storage.write_message_secrets(self.group_id(), &self.message_secrets_store)
+            },
+        )
     }
 
     /// Sets the additional authenticated data (AAD) for the next outgoing
@@ -315,6 +341,15 @@ impl MlsGroup {
         self.own_leaf_index
     }
 
+    /// Registers a [`CustomProposalHandler`] that validates and applies custom
+    /// proposals of its type. See [`PublicGroup::register_custom_proposal_handler()`].
+    ///
+    /// Handlers are not persisted and need to be registered again after the
+    /// group was loaded from storage.
+    pub fn register_custom_proposal_handler(&mut self, handler: impl CustomProposalHandler) {
+        self.public_group.register_custom_proposal_handler(handler)
+    }
+
     /// Returns the leaf node of the client in the tree owning this group.
     pub fn own_leaf_node(&self) -> Option<&LeafNode> {
         self.public_group().leaf(self.own_leaf_index())
@@ -345,6 +380,16 @@ impl MlsGroup {
             }
             MlsGroupState::Operational => None,
             MlsGroupState::Inactive => None,
+            MlsGroupState::ReInitialized(_) => None,
+        }
+    }
+
+    /// Returns the [`ReInitProposal`] that was committed in this group, if the
+    /// group has been reinitialized.
+    pub fn reinit_proposal(&self) -> Option<&ReInitProposal> {
+        match self.group_state {
+            MlsGroupState::ReInitialized(ref reinit_proposal) => Some(reinit_proposal),
+            _ => None,
         }
     }
 
@@ -372,7 +417,9 @@ impl MlsGroup {
                     Ok(())
                 }
             }
-            MlsGroupState::Operational | MlsGroupState::Inactive => Ok(()),
+            MlsGroupState::Operational
+            | MlsGroupState::Inactive
+            | MlsGroupState::ReInitialized(_) => Ok(()),
         }
     }
 
@@ -451,26 +498,32 @@ impl MlsGroup {
         &mut self,
         storage: &Storage,
     ) -> Result<(), Storage::Error> {
-        PublicGroup::delete(storage, self.group_id())?;
-        storage.delete_own_leaf_index(self.group_id())?;
-        storage.delete_group_epoch_secrets(self.group_id())?;
-        storage.delete_message_secrets(self.group_id())?;
-        storage.delete_all_resumption_psk_secrets(self.group_id())?;
-        storage.delete_group_config(self.group_id())?;
-        storage.delete_own_leaf_nodes(self.group_id())?;
-        storage.delete_group_state(self.group_id())?;
-        storage.clear_proposal_queue::<GroupId, ProposalRef>(self.group_id())?;
-
-        self.proposal_store_mut().empty();
-        storage.delete_encryption_epoch_key_pairs(
-            self.group_id(),
-            &self.epoch(),
-            self.own_leaf_index().u32(),
-        )?;
-
-        self.proposal_store_mut().empty();
-
-        Ok(())
+        transaction(
+            storage,
+            |e| e,
+            || {
+                PublicGroup::delete(storage, self.group_id())?;
+                storage.delete_own_leaf_index(self.group_id())?;
+                storage.delete_group_epoch_secrets(self.group_id())?;
+                storage.delete_message_secrets(self.group_id())?;
+                storage.delete_all_resumption_psk_secrets(self.group_id())?;
+                storage.delete_group_config(self.group_id())?;
+                storage.delete_own_leaf_nodes(self.group_id())?;
+                storage.delete_group_state(self.group_id())?;
+                storage.clear_proposal_queue::<GroupId, ProposalRef>(self.group_id())?;
+
+                self.proposal_store_mut().empty();
+                storage.delete_encryption_epoch_key_pairs(
+                    self.group_id(),
+                    &self.epoch(),
+                    self.own_leaf_index().u32(),
+                )?;
+
+                self.proposal_store_mut().empty();
+
+                Ok(())
+            },
+        )
     }
 
     // === Extensions ===
@@ -713,7 +766,23 @@ impl MlsGroup {
         &self,
         storage: &Storage,
     ) -> Result<(), Storage::Error> {
-        self.public_group.store(storage)?;
+        transaction(
+            storage,
+            |e| e,
+            || {
+                self.public_group.store(storage)?;
+                self.store_own_state(storage)
+            },
+        )
+    }
+
+    /// Stores the state of this group that is not part of the
+    /// [`PublicGroup`], e.g. after an operation that did not change the
+    /// public state.
+    pub(super) fn store_own_state<Storage: crate::storage::StorageProvider>(
+        &self,
+        storage: &Storage,
+    ) -> Result<(), Storage::Error> {
         storage.write_group_epoch_secrets(self.group_id(), &self.group_epoch_secrets)?;
         storage.write_own_leaf_index(self.group_id(), &self.own_leaf_index)?;
         storage.write_message_secrets(self.group_id(), &self.message_secrets_store)?;
@@ -758,11 +827,12 @@ impl MlsGroup {
     }
 
     /// Check if the group is operational. Throws an error if the group is
-    /// inactive or if there is a pending commit.
+    /// inactive, has been reinitialized or if there is a pending commit.
     fn is_operational(&self) -> Result<(), MlsGroupStateError> {
         match self.group_state {
             MlsGroupState::PendingCommit(_) => Err(MlsGroupStateError::PendingCommit),
             MlsGroupState::Inactive => Err(MlsGroupStateError::UseAfterEviction),
+            MlsGroupState::ReInitialized(_) => Err(MlsGroupStateError::UseAfterReInit),
             MlsGroupState::Operational => Ok(()),
         }
     }
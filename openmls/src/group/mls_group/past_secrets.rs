@@ -12,6 +12,11 @@ struct EpochTree {
     epoch: u64,
     message_secrets: MessageSecrets,
     leaves: Vec<Member>,
+    // The time at which the epoch was first seen to be over, in seconds since
+    // the UNIX epoch. Only recorded if the group has a maximum age for past
+    // epochs.
+    #[serde(default)]
+    retired_at: Option<u64>,
 }
 
 /// Can store message secrets for up to `max_epochs`. The trees are added with [`self::add()`] and can be queried
@@ -82,6 +87,7 @@ impl MessageSecretsStore {
             epoch: group_epoch.into().as_u64(),
             message_secrets,
             leaves,
+            retired_at: None,
         });
         debug_assert!(
             self.max_epochs >= self.past_epoch_trees.len(),
@@ -91,6 +97,28 @@ impl MessageSecretsStore {
         );
     }
 
+    /// Delete the secret trees of epochs that ended more than `max_age`
+    /// seconds before `now`. Epochs whose end hasn't been recorded yet are
+    /// recorded as ended at `now`.
+    pub(crate) fn expire(&mut self, now: u64, max_age: u64) {
+        for epoch_tree in self.past_epoch_trees.iter_mut() {
+            epoch_tree.retired_at.get_or_insert(now);
+        }
+        self.past_epoch_trees.retain(|epoch_tree| {
+            epoch_tree
+                .retired_at
+                .is_some_and(|retired_at| now.saturating_sub(retired_at) <= max_age)
+        });
+    }
+
+    /// Returns the epochs for which secret trees are stored, together with the
+    /// time at which they ended, if it was recorded.
+    pub(crate) fn past_epochs(&self) -> impl Iterator<Item = (GroupEpoch, Option<u64>)> + '_ {
+        self.past_epoch_trees
+            .iter()
+            .map(|epoch_tree| (GroupEpoch::from(epoch_tree.epoch), epoch_tree.retired_at))
+    }
+
     /// Get a mutable reference to a secret tree for a given epoch `group_epoch`.
     /// If no message secrets are found for that epoch, `None` is returned.
     pub(crate) fn secrets_for_epoch_mut(
@@ -6,10 +6,11 @@ use errors::{CommitToPendingProposalsError, MergePendingCommitError};
 use openmls_traits::{crypto::OpenMlsCrypto, signatures::Signer, storage::StorageProvider as _};
 
 use crate::{
+    credentials::{authentication::authenticate_processed_message_content, AuthenticationService},
     framing::mls_content::FramedContentBody,
     group::{errors::MergeCommitError, StageCommitError, ValidationError},
     messages::group_info::GroupInfo,
-    storage::OpenMlsProvider,
+    storage::{transaction, OpenMlsProvider},
     tree::sender_ratchet::SenderRatchetConfiguration,
 };
 
@@ -22,6 +23,10 @@ impl MlsGroup {
     /// and semantic validation of the message. It returns a [ProcessedMessage]
     /// enum.
     ///
+    /// The credentials introduced by the message, i.e. in Add and Update
+    /// proposals and in the update path of a Commit, are authenticated with
+    /// the `authentication_service`.
+    ///
     /// # Errors:
     /// Returns an [`ProcessMessageError`] when the validation checks fail
     /// with the exact reason of the failure.
@@ -29,6 +34,7 @@ impl MlsGroup {
         &mut self,
         provider: &Provider,
         message: impl Into<ProtocolMessage>,
+        authentication_service: &impl AuthenticationService,
     ) -> Result<ProcessedMessage, ProcessMessageError> {
         // Make sure we are still a member of the group
         if !self.is_active() {
@@ -38,6 +44,13 @@ impl MlsGroup {
         }
         let message = message.into();
 
+        // A reinitialized group only accepts late application messages
+        if self.reinit_proposal().is_some() && message.is_handshake_message() {
+            return Err(ProcessMessageError::GroupStateError(
+                MlsGroupStateError::UseAfterReInit,
+            ));
+        }
+
         // Check that handshake messages are compatible with the incoming wire format policy
         if !message.is_external()
             && message.is_handshake_message()
@@ -61,6 +74,7 @@ impl MlsGroup {
         //  - ValSem007 MembershipTag presence
         let decrypted_message =
             self.decrypt_message(provider.crypto(), message, &sender_ratchet_configuration)?;
+        let generation = decrypted_message.generation();
 
         let unverified_message = self
             .public_group
@@ -75,12 +89,40 @@ impl MlsGroup {
                 (vec![], vec![])
             };
 
-        self.process_unverified_message(
+        let processed_message = self.process_unverified_message(
             provider,
             unverified_message,
             old_epoch_keypairs,
             leaf_node_keypairs,
-        )
+        )?;
+
+        authenticate_processed_message_content(
+            authentication_service,
+            processed_message.content(),
+        )?;
+
+        // Remember accepted application messages so that they can be
+        // acknowledged with an AppAck proposal.
+        if let (
+            ProcessedMessageContent::ApplicationMessage(_),
+            Sender::Member(sender_index),
+            Some(generation),
+        ) = (
+            processed_message.content(),
+            processed_message.sender(),
+            generation,
+        ) {
+            self.message_secrets_mut(processed_message.epoch())
+                .map_err(|_| LibraryError::custom("Message secrets of the epoch are missing"))?
+                .secret_tree_mut()
+                .record_received_application_message(*sender_index, generation);
+            provider
+                .storage()
+                .write_message_secrets(self.group_id(), &self.message_secrets_store)
+                .map_err(|_| ProcessMessageError::StorageError)?;
+        }
+
+        Ok(processed_message)
     }
 
     /// Stores a standalone proposal in the internal [ProposalStore]
@@ -155,37 +197,58 @@ impl MlsGroup {
         provider: &Provider,
         staged_commit: StagedCommit,
     ) -> Result<(), MergeCommitError<Provider::StorageError>> {
-        // Check if we were removed from the group
-        if staged_commit.self_removed() {
-            self.group_state = MlsGroupState::Inactive;
-        }
-        provider
-            .storage()
-            .write_group_state(self.group_id(), &self.group_state)
-            .map_err(MergeCommitError::StorageError)?;
+        let proposal_queue =
+            transaction(provider.storage(), MergeCommitError::StorageError, || {
+                // Check if we were removed from the group or if the group was
+                // reinitialized
+                if staged_commit.self_removed() {
+                    self.group_state = MlsGroupState::Inactive;
+                } else if let Some(reinit_proposal) = staged_commit.reinit_proposal() {
+                    self.group_state =
+                        MlsGroupState::ReInitialized(Box::new(reinit_proposal.clone()));
+                }
+                provider
+                    .storage()
+                    .write_group_state(self.group_id(), &self.group_state)
+                    .map_err(MergeCommitError::StorageError)?;
+
+                // Merge staged commit
+                let proposal_queue = self.merge_commit(provider, staged_commit)?;
+
+                // Extract and store the resumption psk for the current epoch
+                let resumption_psk = self.group_epoch_secrets().resumption_psk();
+                self.resumption_psk_store
+                    .add(self.context().epoch(), resumption_psk.clone());
+
+                // Delete the secrets of past epochs that are too old
+                if self.expire_past_epochs(provider.clock()) {
+                    provider
+                        .storage()
+                        .write_message_secrets(self.group_id(), &self.message_secrets_store)
+                        .map_err(MergeCommitError::StorageError)?;
+                }
+                provider
+                    .storage()
+                    .write_resumption_psk_store(self.group_id(), &self.resumption_psk_store)
+                    .map_err(MergeCommitError::StorageError)?;
 
-        // Merge staged commit
-        self.merge_commit(provider, staged_commit)?;
+                // Delete own KeyPackageBundles
+                self.own_leaf_nodes.clear();
+                provider
+                    .storage()
+                    .delete_own_leaf_nodes(self.group_id())
+                    .map_err(MergeCommitError::StorageError)?;
 
-        // Extract and store the resumption psk for the current epoch
-        let resumption_psk = self.group_epoch_secrets().resumption_psk();
-        self.resumption_psk_store
-            .add(self.context().epoch(), resumption_psk.clone());
-        provider
-            .storage()
-            .write_resumption_psk_store(self.group_id(), &self.resumption_psk_store)
-            .map_err(MergeCommitError::StorageError)?;
+                // Delete a potential pending commit
+                self.clear_pending_commit(provider.storage())
+                    .map_err(MergeCommitError::StorageError)?;
 
-        // Delete own KeyPackageBundles
-        self.own_leaf_nodes.clear();
-        provider
-            .storage()
-            .delete_own_leaf_nodes(self.group_id())
-            .map_err(MergeCommitError::StorageError)?;
+                Ok(proposal_queue)
+            })?;
 
-        // Delete a potential pending commit
-        self.clear_pending_commit(provider.storage())
-            .map_err(MergeCommitError::StorageError)?;
+        // Let the application apply its custom proposals to the new epoch,
+        // but only once the new epoch is stored.
+        self.public_group.apply_custom_proposals(&proposal_queue)?;
 
         Ok(())
     }
@@ -205,7 +268,7 @@ impl MlsGroup {
                 Ok(())
             }
             MlsGroupState::Inactive => Err(MlsGroupStateError::UseAfterEviction)?,
-            MlsGroupState::Operational => Ok(()),
+            MlsGroupState::Operational | MlsGroupState::ReInitialized(_) => Ok(()),
         }
     }
 
@@ -325,7 +388,9 @@ impl MlsGroup {
                     FramedContentBody::Application(_) => {
                         Err(ProcessMessageError::UnauthorizedExternalApplicationMessage)
                     }
-                    FramedContentBody::Proposal(Proposal::Remove(_)) => {
+                    FramedContentBody::Proposal(
+                        Proposal::Add(_) | Proposal::Remove(_) | Proposal::ReInit(_),
+                    ) => {
                         let content = ProcessedMessageContent::ProposalMessage(Box::new(
                             QueuedProposal::from_authenticated_content_by_ref(
                                 self.ciphersuite(),
@@ -3,9 +3,9 @@ use openmls_traits::{signatures::Signer, storage::StorageProvider as _, types::C
 use super::{
     create_commit::CreateCommitParams,
     errors::{ProposalError, ProposeAddMemberError, ProposeRemoveMemberError, RemoveProposalError},
-    AddProposal, CreateGroupContextExtProposalError, CustomProposal, FramingParameters,
-    GroupContextExtensionProposal, MlsGroup, MlsGroupState, PendingCommitState,
-    PreSharedKeyProposal, Proposal, QueuedProposal, RemoveProposal, UpdateProposal,
+    AddProposal, AppAckProposal, CreateGroupContextExtProposalError, CustomProposal,
+    FramingParameters, GroupContextExtensionProposal, MlsGroup, MlsGroupState, PendingCommitState,
+    PreSharedKeyProposal, Proposal, QueuedProposal, ReInitProposal, RemoveProposal, UpdateProposal,
 };
 use crate::{
     binary_tree::LeafNodeIndex,
@@ -55,6 +55,9 @@ pub enum Propose {
     /// Propose adding new group context extensions.
     GroupContextExtensions(Extensions),
 
+    /// An AppAck proposal gets the ranges of received application messages.
+    AppAck(AppAckProposal),
+
     /// A custom proposal with semantics to be implemented by the application.
     Custom(CustomProposal),
 }
@@ -127,6 +130,34 @@ impl MlsGroup {
         ProposalOrRefType::Proposal
     );
 
+    impl_propose_fun!(
+        propose_reinit,
+        ReInitProposal,
+        create_reinit_proposal,
+        ProposalOrRefType::Reference
+    );
+
+    impl_propose_fun!(
+        propose_reinit_by_value,
+        ReInitProposal,
+        create_reinit_proposal,
+        ProposalOrRefType::Proposal
+    );
+
+    impl_propose_fun!(
+        propose_app_ack,
+        AppAckProposal,
+        create_app_ack_proposal,
+        ProposalOrRefType::Reference
+    );
+
+    impl_propose_fun!(
+        propose_app_ack_by_value,
+        AppAckProposal,
+        create_app_ack_proposal,
+        ProposalOrRefType::Proposal
+    );
+
     impl_propose_fun!(
         propose_custom_proposal_by_value,
         CustomProposal,
@@ -201,19 +232,36 @@ impl MlsGroup {
                 )),
             },
             Propose::ReInit {
-                group_id: _,
-                version: _,
-                ciphersuite: _,
-                extensions: _,
-            } => Err(ProposalError::LibraryError(LibraryError::custom(
-                "Unsupported proposal type ReInit",
-            ))),
+                group_id,
+                version,
+                ciphersuite,
+                extensions,
+            } => {
+                let reinit_proposal =
+                    ReInitProposal::new(group_id, version, ciphersuite, extensions);
+                match ref_or_value {
+                    ProposalOrRefType::Proposal => {
+                        self.propose_reinit_by_value(provider, signer, reinit_proposal)
+                    }
+                    ProposalOrRefType::Reference => {
+                        self.propose_reinit(provider, signer, reinit_proposal)
+                    }
+                }
+            }
             Propose::ExternalInit(_) => Err(ProposalError::LibraryError(LibraryError::custom(
                 "Unsupported proposal type ExternalInit",
             ))),
             Propose::GroupContextExtensions(_) => Err(ProposalError::LibraryError(
                 LibraryError::custom("Unsupported proposal type GroupContextExtensions"),
             )),
+            Propose::AppAck(app_ack_proposal) => match ref_or_value {
+                ProposalOrRefType::Proposal => {
+                    self.propose_app_ack_by_value(provider, signer, app_ack_proposal)
+                }
+                ProposalOrRefType::Reference => {
+                    self.propose_app_ack(provider, signer, app_ack_proposal)
+                }
+            },
             Propose::Custom(custom_proposal) => match ref_or_value {
                 ProposalOrRefType::Proposal => {
                     self.propose_custom_proposal_by_value(provider, signer, custom_proposal)
@@ -556,6 +604,49 @@ impl MlsGroup {
         )
     }
 
+    // 12.1.5. ReInit
+    // struct {
+    //     opaque group_id<V>;
+    //     ProtocolVersion version;
+    //     CipherSuite cipher_suite;
+    //     Extension extensions<V>;
+    // } ReInit;
+    pub(crate) fn create_reinit_proposal(
+        &self,
+        framing_parameters: FramingParameters,
+        reinit_proposal: ReInitProposal,
+        signer: &impl Signer,
+    ) -> Result<AuthenticatedContent, LibraryError> {
+        let proposal = Proposal::ReInit(reinit_proposal);
+        AuthenticatedContent::member_proposal(
+            framing_parameters,
+            self.own_leaf_index(),
+            proposal,
+            self.context(),
+            signer,
+        )
+    }
+
+    // draft-ietf-mls-extensions-00
+    // struct {
+    //     MessageRange received_ranges<V>;
+    // } AppAck;
+    pub(crate) fn create_app_ack_proposal(
+        &self,
+        framing_parameters: FramingParameters,
+        app_ack_proposal: AppAckProposal,
+        signer: &impl Signer,
+    ) -> Result<AuthenticatedContent, LibraryError> {
+        let proposal = Proposal::AppAck(app_ack_proposal);
+        AuthenticatedContent::member_proposal(
+            framing_parameters,
+            self.own_leaf_index(),
+            proposal,
+            self.context(),
+            signer,
+        )
+    }
+
     pub(crate) fn create_custom_proposal(
         &self,
         framing_parameters: FramingParameters,
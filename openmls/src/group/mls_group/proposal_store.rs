@@ -1,4 +1,4 @@
-use std::collections::{hash_map::Entry, HashMap, HashSet};
+use std::collections::{hash_map::Entry, BTreeMap, HashMap, HashSet};
 
 use openmls_traits::crypto::OpenMlsCrypto;
 use openmls_traits::types::Ciphersuite;
@@ -11,8 +11,9 @@ use crate::{
     framing::{mls_auth_content::AuthenticatedContent, mls_content::FramedContentBody, Sender},
     group::errors::*,
     messages::proposals::{
-        AddProposal, PreSharedKeyProposal, Proposal, ProposalOrRef, ProposalOrRefType,
-        ProposalType, RemoveProposal, UpdateProposal,
+        AddProposal, AppAckProposal, MessageRange, PreSharedKeyProposal, Proposal, ProposalOrRef,
+        ProposalOrRefType, ProposalType, ReInitProposal, RemoveProposal, SelfRemoveProposal,
+        UpdateProposal,
     },
     utils::vector_converter,
 };
@@ -268,6 +269,14 @@ impl ProposalQueue {
                     }
                 }
             };
+            // ValSem200
+            if let (Proposal::SelfRemove(_), Sender::Member(_)) =
+                (&queued_proposal.proposal, sender)
+            {
+                if queued_proposal.sender == *sender {
+                    return Err(FromCommittedProposalsError::SelfRemoval);
+                }
+            }
             proposal_queue.add(queued_proposal);
         }
 
@@ -348,6 +357,24 @@ impl ProposalQueue {
         })
     }
 
+    /// Returns an iterator over all SelfRemove proposals in the queue
+    /// in the order of the the Commit message
+    pub(crate) fn self_remove_proposals(
+        &self,
+    ) -> impl Iterator<Item = QueuedSelfRemoveProposal<'_>> {
+        self.queued_proposals().filter_map(|queued_proposal| {
+            if let Proposal::SelfRemove(self_remove_proposal) = queued_proposal.proposal() {
+                let sender = queued_proposal.sender();
+                Some(QueuedSelfRemoveProposal {
+                    self_remove_proposal,
+                    sender,
+                })
+            } else {
+                None
+            }
+        })
+    }
+
     /// Returns an iterator over all Update in the queue
     /// in the order of the the Commit message
     pub(crate) fn update_proposals(&self) -> impl Iterator<Item = QueuedUpdateProposal> {
@@ -364,6 +391,15 @@ impl ProposalQueue {
         })
     }
 
+    /// Returns the ReInit proposal in the queue, if there is one
+    pub(crate) fn reinit_proposal(&self) -> Option<&ReInitProposal> {
+        self.queued_proposals()
+            .find_map(|queued_proposal| match queued_proposal.proposal() {
+                Proposal::ReInit(reinit_proposal) => Some(reinit_proposal),
+                _ => None,
+            })
+    }
+
     /// Returns an iterator over all PresharedKey proposals in the queue
     /// in the order of the the Commit message
     pub(crate) fn psk_proposals(&self) -> impl Iterator<Item = QueuedPskProposal> {
@@ -380,6 +416,22 @@ impl ProposalQueue {
         })
     }
 
+    /// Returns an iterator over all AppAck proposals in the queue
+    /// in the order of the the Commit message
+    pub(crate) fn app_ack_proposals(&self) -> impl Iterator<Item = QueuedAppAckProposal<'_>> {
+        self.queued_proposals().filter_map(|queued_proposal| {
+            if let Proposal::AppAck(app_ack_proposal) = queued_proposal.proposal() {
+                let sender = queued_proposal.sender();
+                Some(QueuedAppAckProposal {
+                    app_ack_proposal,
+                    sender,
+                })
+            } else {
+                None
+            }
+        })
+    }
+
     /// Filters received proposals
     ///
     /// 11.2 Commit
@@ -398,6 +450,7 @@ impl ProposalQueue {
     /// - Check for invalid indexes and drop proposal
     /// - Check for presence of Removes and delete Updates
     /// - Only keep the last Update
+    /// - Only keep the last ReInit and drop all other proposals if there is one
     ///
     /// Return a [`ProposalQueue`] and a bool that indicates whether Updates for
     /// the own node were included
@@ -422,6 +475,7 @@ impl ProposalQueue {
         let mut proposal_pool: HashMap<ProposalRef, QueuedProposal> = HashMap::new();
         let mut contains_own_updates = false;
         let mut contains_external_init = false;
+        let mut reinit: Option<ProposalRef> = None;
 
         // Aggregate both proposal types to a common iterator
         // We checked earlier that only proposals can end up here
@@ -483,7 +537,8 @@ impl ProposalQueue {
                     proposal_pool.insert(queued_proposal.proposal_reference(), queued_proposal);
                 }
                 Proposal::ReInit(_) => {
-                    // TODO #751: Only keep one ReInit
+                    // Only keep the most recent ReInit
+                    reinit = Some(queued_proposal.proposal_reference());
                     proposal_pool.insert(queued_proposal.proposal_reference(), queued_proposal);
                 }
                 Proposal::ExternalInit(_) => {
@@ -498,7 +553,28 @@ impl ProposalQueue {
                     valid_proposals.add(queued_proposal.proposal_reference());
                     proposal_pool.insert(queued_proposal.proposal_reference(), queued_proposal);
                 }
-                Proposal::AppAck(_) => unimplemented!("See #291"),
+                Proposal::AppAck(_) => {
+                    valid_proposals.add(queued_proposal.proposal_reference());
+                    proposal_pool.insert(queued_proposal.proposal_reference(), queued_proposal);
+                }
+                Proposal::SelfRemove(_) => {
+                    // Only members can send SelfRemove proposals
+                    let leaf_index = match queued_proposal.sender {
+                        Sender::Member(leaf_index) => leaf_index,
+                        _ => return Err(ProposalQueueError::SelfRemoveFromNonMember),
+                    };
+                    // The committer can't remove itself, so its own SelfRemove
+                    // proposals are not covered.
+                    if leaf_index != own_index {
+                        members
+                            .entry(leaf_index)
+                            .or_default()
+                            .removes
+                            .push(queued_proposal.clone());
+                        let proposal_reference = queued_proposal.proposal_reference();
+                        proposal_pool.insert(proposal_reference, queued_proposal);
+                    }
+                }
                 Proposal::Custom(_) => {
                     // Other/unknown proposals are always considered valid and
                     // have to be checked by the application instead.
@@ -507,6 +583,16 @@ impl ProposalQueue {
                 }
             }
         }
+        // A ReInit has to be the only proposal in a Commit, so it supersedes
+        // all other proposals.
+        if let Some(reinit) = reinit {
+            let queued_proposal = proposal_pool
+                .remove(&reinit)
+                .ok_or(ProposalQueueError::ProposalNotFound)?;
+            let mut proposal_queue = ProposalQueue::default();
+            proposal_queue.add(queued_proposal);
+            return Ok((proposal_queue, false));
+        }
         // Check for presence of Removes and delete Updates
         for (_, member) in members.iter_mut() {
             // Check if there are Removes
@@ -603,6 +689,26 @@ impl<'a> QueuedRemoveProposal<'a> {
     }
 }
 
+/// A queued SelfRemove proposal
+#[derive(PartialEq, Eq, Debug)]
+pub struct QueuedSelfRemoveProposal<'a> {
+    self_remove_proposal: &'a SelfRemoveProposal,
+    sender: &'a Sender,
+}
+
+impl<'a> QueuedSelfRemoveProposal<'a> {
+    /// Returns a reference to the proposal
+    pub fn self_remove_proposal(&self) -> &SelfRemoveProposal {
+        self.self_remove_proposal
+    }
+
+    /// Returns a reference to the sender, i.e. the member that leaves the
+    /// group
+    pub fn sender(&self) -> &Sender {
+        self.sender
+    }
+}
+
 /// A queued Update proposal
 #[derive(PartialEq, Eq, Debug)]
 pub struct QueuedUpdateProposal<'a> {
@@ -640,3 +746,94 @@ impl<'a> QueuedPskProposal<'a> {
         self.sender
     }
 }
+
+/// A queued AppAck proposal
+#[derive(PartialEq, Eq, Debug)]
+pub struct QueuedAppAckProposal<'a> {
+    app_ack_proposal: &'a AppAckProposal,
+    sender: &'a Sender,
+}
+
+impl<'a> QueuedAppAckProposal<'a> {
+    /// Returns a reference to the proposal
+    pub fn app_ack_proposal(&self) -> &AppAckProposal {
+        self.app_ack_proposal
+    }
+
+    /// Returns a reference to the sender
+    pub fn sender(&self) -> &Sender {
+        self.sender
+    }
+}
+
+/// A report of the application messages acknowledged by the AppAck proposals
+/// in a Commit.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct AppAckReport {
+    received_ranges: BTreeMap<LeafNodeIndex, Vec<MessageRange>>,
+}
+
+impl AppAckReport {
+    /// Builds the report from the AppAck proposals in the queue.
+    pub(crate) fn new<'a>(
+        app_ack_proposals: impl Iterator<Item = QueuedAppAckProposal<'a>>,
+    ) -> Self {
+        let mut received_ranges: BTreeMap<LeafNodeIndex, Vec<MessageRange>> = BTreeMap::new();
+        for app_ack_proposal in app_ack_proposals {
+            // AppAck proposals are only valid if they were sent by a member.
+            if let Sender::Member(member) = app_ack_proposal.sender() {
+                received_ranges
+                    .entry(*member)
+                    .or_default()
+                    .extend_from_slice(app_ack_proposal.app_ack_proposal().received_ranges());
+            }
+        }
+        Self { received_ranges }
+    }
+
+    /// Returns `true` if the Commit doesn't contain any AppAck proposals.
+    pub fn is_empty(&self) -> bool {
+        self.received_ranges.is_empty()
+    }
+
+    /// Returns the leaf indices of the members that acknowledged messages.
+    pub fn acknowledging_members(&self) -> impl Iterator<Item = LeafNodeIndex> + '_ {
+        self.received_ranges.keys().copied()
+    }
+
+    /// Returns the ranges of messages acknowledged by the given `member`.
+    pub fn received_ranges(&self, member: LeafNodeIndex) -> &[MessageRange] {
+        self.received_ranges
+            .get(&member)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Returns `true` if the given `member` acknowledged the message with the
+    /// given `sender` and `generation`.
+    pub fn has_received(
+        &self,
+        member: LeafNodeIndex,
+        sender: LeafNodeIndex,
+        generation: u32,
+    ) -> bool {
+        self.received_ranges(member)
+            .iter()
+            .any(|range| range.contains(sender, generation))
+    }
+
+    /// Returns `true` if all of the given `members`, except for the `sender`
+    /// itself, acknowledged the message with the given `sender` and
+    /// `generation`.
+    pub fn received_by_all(
+        &self,
+        members: impl IntoIterator<Item = LeafNodeIndex>,
+        sender: LeafNodeIndex,
+        generation: u32,
+    ) -> bool {
+        members
+            .into_iter()
+            .filter(|member| *member != sender)
+            .all(|member| self.has_received(member, sender, generation))
+    }
+}
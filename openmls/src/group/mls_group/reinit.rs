@@ -0,0 +1,133 @@
+//! Reinitialization of an [`MlsGroup`].
+//!
+//! A group is reinitialized by committing a ReInit proposal. Once that commit
+//! is merged, the group is in the [`MlsGroupState::ReInitialized`] state and
+//! a successor group is created with the parameters from the
+//! [`ReInitProposal`]. The first commit of the successor group adds the
+//! members and injects a resumption PSK with usage
+//! [`ResumptionPskUsage::Reinit`] from the last epoch of the old group.
+
+use errors::{EmptyInputError, ReInitError};
+use openmls_traits::{signatures::Signer, storage::StorageProvider as _};
+
+use super::*;
+use crate::{
+    credentials::CredentialWithKey,
+    key_packages::{KeyPackage, Lifetime},
+    schedule::psk::ResumptionPskUsage,
+    storage::OpenMlsProvider,
+    treesync::node::leaf_node::Capabilities,
+};
+
+impl MlsGroup {
+    /// Reinitializes the group by committing to an inline ReInit proposal.
+    ///
+    /// A ReInit proposal has to be the only proposal in a Commit, so all
+    /// pending proposals are left out of the Commit.
+    ///
+    /// If successful, it returns a tuple of [`MlsMessageOut`] (containing the
+    /// commit) and an optional [GroupInfo], which is [Some] if the group has
+    /// the `use_ratchet_tree_extension` flag set. After the pending commit is
+    /// merged, the group is in the [`MlsGroupState::ReInitialized`] state and
+    /// the successor group can be created with
+    /// [`MlsGroup::create_reinit_group()`].
+    ///
+    /// Returns an error if there is a pending commit or if the ReInit proposal
+    /// has the same group ID as this group.
+    pub fn reinit<Provider: OpenMlsProvider>(
+        &mut self,
+        provider: &Provider,
+        signer: &impl Signer,
+        reinit_proposal: ReInitProposal,
+    ) -> Result<(MlsMessageOut, Option<GroupInfo>), ReInitError<Provider::StorageError>> {
+        self.is_operational()?;
+
+        // The successor group is stored next to this group, so it needs its
+        // own group ID.
+        if reinit_proposal.group_id() == self.group_id() {
+            return Err(ReInitError::SameGroupId);
+        }
+
+        let params = CreateCommitParams::builder()
+            .framing_parameters(self.framing_parameters())
+            .inline_proposals(vec![Proposal::ReInit(reinit_proposal)])
+            .build();
+        let create_commit_result = self.create_commit(params, provider, signer)?;
+
+        let mls_message = self.content_to_mls_message(create_commit_result.commit, provider)?;
+
+        // Set the current group state to [`MlsGroupState::PendingCommit`],
+        // storing the current [`StagedCommit`] from the commit results
+        self.group_state = MlsGroupState::PendingCommit(Box::new(PendingCommitState::Member(
+            create_commit_result.staged_commit,
+        )));
+
+        provider
+            .storage()
+            .write_group_state(self.group_id(), &self.group_state)
+            .map_err(ReInitError::StorageError)?;
+
+        self.reset_aad();
+        Ok((mls_message, create_commit_result.group_info))
+    }
+
+    /// Creates the successor of this reinitialized group.
+    ///
+    /// The new group gets the group ID, protocol version, ciphersuite and
+    /// group context extensions of the committed [`ReInitProposal`]. Its first
+    /// commit adds the members with the given `key_packages` and injects the
+    /// resumption PSK of the current epoch of this group. The commit is merged
+    /// right away, so the new group is ready to use. The invited members take
+    /// the resumption PSK from the current epoch of their copy of this group,
+    /// so they have to keep it in storage until they joined.
+    ///
+    /// If successful, it returns the new group, the [`Welcome`] for the new
+    /// members and an optional [GroupInfo], which is [Some] if the new group
+    /// has the `use_ratchet_tree_extension` flag set.
+    ///
+    /// Returns an error if this group has not been reinitialized.
+    #[allow(clippy::type_complexity)]
+    pub fn create_reinit_group<Provider: OpenMlsProvider>(
+        &self,
+        provider: &Provider,
+        signer: &impl Signer,
+        mls_group_config: &MlsGroupJoinConfig,
+        capabilities: Option<Capabilities>,
+        credential_with_key: CredentialWithKey,
+        key_packages: &[KeyPackage],
+    ) -> Result<(MlsGroup, MlsMessageOut, Option<GroupInfo>), ReInitError<Provider::StorageError>>
+    {
+        let reinit_proposal = self
+            .reinit_proposal()
+            .ok_or(ReInitError::NotReInitialized)?;
+
+        if reinit_proposal.version() != ProtocolVersion::Mls10 {
+            return Err(ReInitError::UnsupportedProtocolVersion);
+        }
+        if reinit_proposal.group_id() == self.group_id() {
+            return Err(ReInitError::SameGroupId);
+        }
+        if key_packages.is_empty() {
+            return Err(EmptyInputError::AddMembers.into());
+        }
+
+        let mls_group_create_config = MlsGroupCreateConfig {
+            capabilities: capabilities.unwrap_or_default(),
+            lifetime: Lifetime::default(),
+            ciphersuite: reinit_proposal.ciphersuite(),
+            join_config: mls_group_config.clone(),
+            group_context_extensions: reinit_proposal.extensions().clone(),
+            leaf_node_extensions: Extensions::empty(),
+        };
+
+        self.create_group_with_resumption_psk(
+            provider,
+            signer,
+            reinit_proposal.group_id().clone(),
+            mls_group_create_config,
+            credential_with_key,
+            key_packages,
+            ResumptionPskUsage::Reinit,
+        )
+    }
+}
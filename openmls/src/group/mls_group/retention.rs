@@ -0,0 +1,158 @@
+//! Retention of key material from past epochs.
+//!
+//! A group keeps key material of past epochs so that it can decrypt messages
+//! that arrive late and use resumption PSKs of earlier epochs:
+//!
+//! - the message secrets of up to `max_past_epochs` past epochs,
+//! - the resumption PSKs of up to `number_of_resumption_psks` epochs, and
+//! - the encryption epoch key pairs of the current epoch.
+//!
+//! With a `max_past_epoch_age`, message secrets and resumption PSKs are
+//! additionally deleted once their epoch ended longer ago than that age,
+//! measured with the [`Clock`] of the provider. Together, the limits bound the
+//! forward-secrecy window of the group. [`MlsGroup::retained_key_material()`]
+//! reports what is currently retained.
+
+use openmls_traits::clock::Clock;
+
+use crate::storage::{transaction, OpenMlsProvider, StorageProvider};
+
+use super::*;
+
+/// A past or current epoch for which key material is retained.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetainedEpoch {
+    /// The epoch.
+    pub epoch: GroupEpoch,
+    /// The time at which the epoch was first seen to be over, in seconds since
+    /// the UNIX epoch. This is only recorded if the group has a
+    /// `max_past_epoch_age`.
+    pub retired_at: Option<u64>,
+}
+
+/// The key material that is retained for a group, as returned by
+/// [`MlsGroup::retained_key_material()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetainedKeyMaterial {
+    /// The current epoch of the group.
+    pub current_epoch: GroupEpoch,
+    /// The past epochs for which message secrets are retained.
+    pub message_secrets: Vec<RetainedEpoch>,
+    /// The epochs for which resumption PSKs are retained, including the
+    /// current epoch.
+    pub resumption_psks: Vec<RetainedEpoch>,
+    /// The epochs for which encryption key pairs of the group are stored.
+    pub epoch_key_pairs: Vec<GroupEpoch>,
+}
+
+impl RetainedKeyMaterial {
+    /// Returns the oldest past epoch for which any key material is retained,
+    /// or `None` if only key material of the current epoch is retained.
+    pub fn oldest_past_epoch(&self) -> Option<GroupEpoch> {
+        self.message_secrets
+            .iter()
+            .chain(self.resumption_psks.iter())
+            .map(|retained| retained.epoch)
+            .chain(self.epoch_key_pairs.iter().copied())
+            .filter(|epoch| *epoch < self.current_epoch)
+            .min()
+    }
+}
+
+impl MlsGroup {
+    /// Returns the key material that is currently retained for this group.
+    ///
+    /// The epochs of the encryption key pairs are read with the listing
+    /// functions of the `storage`.
+    pub fn retained_key_material<Storage: StorageProvider>(
+        &self,
+        storage: &Storage,
+    ) -> Result<RetainedKeyMaterial, Storage::Error> {
+        let mut epoch_key_pairs: Vec<GroupEpoch> = storage
+            .encryption_epoch_key_pair_ids::<GroupId, GroupEpoch>()?
+            .into_iter()
+            .filter(|(group_id, _, _)| group_id == self.group_id())
+            .map(|(_, epoch, _)| epoch)
+            .collect();
+        epoch_key_pairs.sort();
+        epoch_key_pairs.dedup();
+
+        let retained = |(epoch, retired_at)| RetainedEpoch { epoch, retired_at };
+        let mut message_secrets: Vec<RetainedEpoch> = self
+            .message_secrets_store
+            .past_epochs()
+            .map(retained)
+            .collect();
+        message_secrets.sort_by_key(|retained| retained.epoch);
+        let mut resumption_psks: Vec<RetainedEpoch> =
+            self.resumption_psk_store.epochs().map(retained).collect();
+        resumption_psks.sort_by_key(|retained| retained.epoch);
+
+        Ok(RetainedKeyMaterial {
+            current_epoch: self.epoch(),
+            message_secrets,
+            resumption_psks,
+            epoch_key_pairs,
+        })
+    }
+
+    /// Deletes the key material that the configuration of this group doesn't
+    /// allow to retain anymore:
+    ///
+    /// - message secrets and resumption PSKs of epochs that ended longer ago
+    ///   than the `max_past_epoch_age`, and
+    /// - encryption key pairs of this group from epochs other than the current
+    ///   one, which are otherwise only deleted for the previous epoch when a
+    ///   commit is merged.
+    ///
+    /// Merging a commit enforces the age limit as well, but the age of past
+    /// epochs also grows while no commits arrive. Applications that set a
+    /// `max_past_epoch_age` should therefore call this function periodically.
+    ///
+    /// The encryption key pairs are found with the listing functions of the
+    /// storage provider.
+    pub fn apply_retention_policy<Provider: OpenMlsProvider>(
+        &mut self,
+        provider: &Provider,
+    ) -> Result<(), Provider::StorageError> {
+        let storage = provider.storage();
+        transaction(
+            storage,
+            |e| e,
+            || {
+                if self.expire_past_epochs(provider.clock()) {
+                    storage.write_message_secrets(self.group_id(), &self.message_secrets_store)?;
+                    storage
+                        .write_resumption_psk_store(self.group_id(), &self.resumption_psk_store)?;
+                }
+
+                let current = (self.epoch(), self.own_leaf_index().u32());
+                for (group_id, epoch, leaf_index) in
+                    storage.encryption_epoch_key_pair_ids::<GroupId, GroupEpoch>()?
+                {
+                    if &group_id == self.group_id() && (epoch, leaf_index) != current {
+                        storage.delete_encryption_epoch_key_pairs(&group_id, &epoch, leaf_index)?;
+                    }
+                }
+
+                Ok(())
+            },
+        )
+    }
+
+    /// Deletes the message secrets and resumption PSKs of past epochs that
+    /// ended longer ago than the `max_past_epoch_age` of the group, according
+    /// to the `clock`. Returns `false` without reading the clock if the group
+    /// has no maximum age.
+    pub(super) fn expire_past_epochs(&mut self, clock: &dyn Clock) -> bool {
+        let Some(max_age) = self.mls_group_config.max_past_epoch_age else {
+            return false;
+        };
+        let now = clock.now();
+        let current_epoch = self.epoch();
+        self.message_secrets_store.expire(now, max_age.as_secs());
+        self.resumption_psk_store
+            .expire(current_epoch, now, max_age.as_secs());
+        true
+    }
+}
@@ -6,22 +6,26 @@ use serde::{Deserialize, Serialize};
 use tls_codec::Serialize as _;
 
 use super::proposal_store::{
-    QueuedAddProposal, QueuedPskProposal, QueuedRemoveProposal, QueuedUpdateProposal,
+    AppAckReport, QueuedAddProposal, QueuedAppAckProposal, QueuedPskProposal, QueuedRemoveProposal,
+    QueuedSelfRemoveProposal, QueuedUpdateProposal,
 };
 
 use super::{
     super::errors::*, load_psks, Credential, Extension, GroupContext, GroupEpochSecrets, GroupId,
     JoinerSecret, KeySchedule, LeafNode, LibraryError, MessageSecrets, MlsGroup, OpenMlsProvider,
-    Proposal, ProposalQueue, PskSecret, QueuedProposal, Sender,
+    Proposal, ProposalQueue, PskSecret, QueuedProposal, ReInitProposal, Sender,
 };
 use crate::{
+    binary_tree::LeafNodeIndex,
     ciphersuite::{hash_ref::ProposalRef, Secret},
+    credentials::CredentialWithKey,
     framing::mls_auth_content::AuthenticatedContent,
     group::public_group::{
         diff::{apply_proposals::ApplyProposalsValues, StagedPublicGroupDiff},
         staged_commit::PublicStagedCommitState,
     },
     schedule::{CommitSecret, EpochAuthenticator, EpochSecrets, InitSecret, PreSharedKeyId},
+    storage::transaction,
     treesync::node::encryption_keys::EncryptionKeyPair,
 };
 
@@ -152,6 +156,12 @@ impl MlsGroup {
         let (commit, proposal_queue, sender_index) = self
             .public_group
             .validate_commit(mls_content, provider.crypto())?;
+        self.validate_app_ack_generations(&proposal_queue)?;
+        let credential_changes = self.public_group.credential_changes(
+            &proposal_queue,
+            mls_content.sender(),
+            commit.path.as_ref().map(|path| path.leaf_node()),
+        );
 
         // Create the provisional public group state (including the tree and
         // group context) and apply proposals.
@@ -189,6 +199,7 @@ impl MlsGroup {
                     return Ok(StagedCommit::new(
                         proposal_queue,
                         StagedCommitState::PublicState(Box::new(staged_state)),
+                        credential_changes,
                     ));
                 }
 
@@ -318,29 +329,44 @@ impl MlsGroup {
                 update_path_leaf_node,
             )));
 
-        Ok(StagedCommit::new(proposal_queue, staged_commit_state))
+        Ok(StagedCommit::new(
+            proposal_queue,
+            staged_commit_state,
+            credential_changes,
+        ))
     }
 
     /// Merges a [StagedCommit] into the group state and optionally return a [`SecretTree`]
     /// from the previous epoch. The secret tree is returned if the Commit does not contain a self removal.
     ///
+    /// Returns the proposals of the Commit. The caller applies its custom
+    /// proposals with [`PublicGroup::apply_custom_proposals`] once the merged
+    /// state is stored.
+    ///
     /// This function should not fail and only returns a [`Result`], because it
     /// might throw a `LibraryError`.
     pub(crate) fn merge_commit<Provider: OpenMlsProvider>(
         &mut self,
         provider: &Provider,
         staged_commit: StagedCommit,
-    ) -> Result<(), MergeCommitError<Provider::StorageError>> {
+    ) -> Result<ProposalQueue, MergeCommitError<Provider::StorageError>> {
         // Get all keypairs from the old epoch, so we can later store the ones
         // that are still relevant in the new epoch.
         let old_epoch_keypairs = self.read_epoch_keypairs(provider.storage());
-        match staged_commit.state {
+        let (staged_proposal_queue, state) = staged_commit.into_parts();
+        match state {
             StagedCommitState::PublicState(staged_state) => {
-                self.public_group
+                let changed_nodes = self
+                    .public_group
                     .merge_diff(staged_state.into_staged_diff());
-                self.store(provider.storage())
-                    .map_err(MergeCommitError::StorageError)?;
-                Ok(())
+                let storage = provider.storage();
+                transaction(storage, MergeCommitError::StorageError, || {
+                    self.public_group
+                        .store_merged(storage, &changed_nodes)
+                        .map_err(MergeCommitError::StorageError)?;
+                    self.store_own_state(storage)
+                        .map_err(MergeCommitError::StorageError)
+                })?;
             }
             StagedCommitState::GroupMember(state) => {
                 // Save the past epoch
@@ -360,7 +386,7 @@ impl MlsGroup {
                 self.message_secrets_store
                     .add(past_epoch, message_secrets, leaves);
 
-                self.public_group.merge_diff(state.staged_diff);
+                let changed_nodes = self.public_group.merge_diff(state.staged_diff);
 
                 // TODO #1194: Group storage and key storage should be
                 // correlated s.t. there is no divergence between key material
@@ -398,7 +424,7 @@ impl MlsGroup {
                 let group_id = self.group_id();
 
                 self.public_group
-                    .store(storage)
+                    .store_merged(storage, &changed_nodes)
                     .map_err(MergeCommitError::StorageError)?;
                 storage
                     .write_group_epoch_secrets(group_id, &self.group_epoch_secrets)
@@ -425,10 +451,10 @@ impl MlsGroup {
                     .clear_proposal_queue::<GroupId, ProposalRef>(group_id)
                     .map_err(MergeCommitError::StorageError)?;
                 self.proposal_store_mut().empty();
-
-                Ok(())
             }
         }
+
+        Ok(staged_proposal_queue)
     }
 }
 
@@ -439,21 +465,77 @@ pub(crate) enum StagedCommitState {
     GroupMember(Box<MemberStagedCommitState>),
 }
 
+/// The credential of a member before and after a Commit that updates the
+/// member's leaf, either through an Update proposal or through the update path
+/// of the committer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CredentialChange {
+    leaf_index: LeafNodeIndex,
+    old: CredentialWithKey,
+    new: CredentialWithKey,
+}
+
+impl CredentialChange {
+    pub(crate) fn new(
+        leaf_index: LeafNodeIndex,
+        old: CredentialWithKey,
+        new: CredentialWithKey,
+    ) -> Self {
+        Self {
+            leaf_index,
+            old,
+            new,
+        }
+    }
+
+    /// Returns the index of the updated leaf.
+    pub fn leaf_index(&self) -> LeafNodeIndex {
+        self.leaf_index
+    }
+
+    /// Returns the credential and signature key of the leaf before the Commit.
+    pub fn old_credential(&self) -> &CredentialWithKey {
+        &self.old
+    }
+
+    /// Returns the credential and signature key of the leaf after the Commit.
+    pub fn new_credential(&self) -> &CredentialWithKey {
+        &self.new
+    }
+
+    /// Returns `true` if the credential of the leaf changed.
+    pub fn credential_changed(&self) -> bool {
+        self.old.credential != self.new.credential
+    }
+
+    /// Returns `true` if the signature key of the leaf changed.
+    pub fn signature_key_changed(&self) -> bool {
+        self.old.signature_key != self.new.signature_key
+    }
+}
+
 /// Contains the changes from a commit to the group state.
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(any(test, feature = "test-utils"), derive(Clone, PartialEq))]
 pub struct StagedCommit {
     staged_proposal_queue: ProposalQueue,
     state: StagedCommitState,
+    #[serde(default)]
+    credential_changes: Vec<CredentialChange>,
 }
 
 impl StagedCommit {
     /// Create a new [`StagedCommit`] from the provisional group state created
     /// during the commit process.
-    pub(crate) fn new(staged_proposal_queue: ProposalQueue, state: StagedCommitState) -> Self {
+    pub(crate) fn new(
+        staged_proposal_queue: ProposalQueue,
+        state: StagedCommitState,
+        credential_changes: Vec<CredentialChange>,
+    ) -> Self {
         StagedCommit {
             staged_proposal_queue,
             state,
+            credential_changes,
         }
     }
 
@@ -467,6 +549,11 @@ impl StagedCommit {
         self.staged_proposal_queue.remove_proposals()
     }
 
+    /// Returns the SelfRemove proposals that are covered by the Commit message as in iterator over [QueuedSelfRemoveProposal].
+    pub fn self_remove_proposals(&self) -> impl Iterator<Item = QueuedSelfRemoveProposal<'_>> {
+        self.staged_proposal_queue.self_remove_proposals()
+    }
+
     /// Returns the Update proposals that are covered by the Commit message as in iterator over [QueuedUpdateProposal].
     pub fn update_proposals(&self) -> impl Iterator<Item = QueuedUpdateProposal> {
         self.staged_proposal_queue.update_proposals()
@@ -477,6 +564,23 @@ impl StagedCommit {
         self.staged_proposal_queue.psk_proposals()
     }
 
+    /// Returns the ReInit proposal that is covered by the Commit message, if
+    /// there is one.
+    pub fn reinit_proposal(&self) -> Option<&ReInitProposal> {
+        self.staged_proposal_queue.reinit_proposal()
+    }
+
+    /// Returns the AppAck proposals that are covered by the Commit message as in iterator over [QueuedAppAckProposal].
+    pub fn app_ack_proposals(&self) -> impl Iterator<Item = QueuedAppAckProposal<'_>> {
+        self.staged_proposal_queue.app_ack_proposals()
+    }
+
+    /// Returns an [`AppAckReport`] of the application messages that were
+    /// acknowledged by the AppAck proposals in the Commit.
+    pub fn app_ack_report(&self) -> AppAckReport {
+        AppAckReport::new(self.app_ack_proposals())
+    }
+
     /// Returns an iterator over all [`QueuedProposal`]s.
     pub fn queued_proposals(&self) -> impl Iterator<Item = &QueuedProposal> {
         self.staged_proposal_queue.queued_proposals()
@@ -494,6 +598,15 @@ impl StagedCommit {
         }
     }
 
+    /// Returns the old and new credential of every leaf that is updated by the
+    /// Commit, either through an Update proposal or through the update path.
+    ///
+    /// A change is reported even if only the signature key of the leaf
+    /// changed, so that every key rotation can be inspected.
+    pub fn credential_changes(&self) -> impl Iterator<Item = &CredentialChange> {
+        self.credential_changes.iter()
+    }
+
     /// Returns the credentials that the caller needs to verify are valid.
     pub fn credentials_to_verify(&self) -> impl Iterator<Item = &Credential> {
         let update_path_leaf_node_cred = if let Some(node) = self.update_path_leaf_node() {
@@ -555,9 +668,10 @@ impl StagedCommit {
         }
     }
 
-    /// Consume this [`StagedCommit`] and return the internal [`StagedCommitState`].
-    pub(crate) fn into_state(self) -> StagedCommitState {
-        self.state
+    /// Consume this [`StagedCommit`] and return the covered proposals and
+    /// the internal [`StagedCommitState`].
+    pub(crate) fn into_parts(self) -> (ProposalQueue, StagedCommitState) {
+        (self.staged_proposal_queue, self.state)
     }
 
     /// Returns the [`EpochAuthenticator`] of the staged commit state if the
@@ -4,6 +4,7 @@ use serde::{self, Deserialize, Serialize};
 use tls_codec::{Deserialize as TlsDeserialize, Serialize as TlsSerialize};
 
 use crate::{
+    credentials::test_utils::AcceptAllCredentials,
     framing::{MlsMessageBodyIn, MlsMessageIn, MlsMessageOut, ProcessedMessageContent},
     group::{
         HpkePrivateKey, IncomingWireFormatPolicy, Member, MlsGroup, MlsGroupCreateConfig,
@@ -282,6 +283,7 @@ impl PassiveClient {
             &self.group_config,
             welcome,
             ratchet_tree,
+            &AcceptAllCredentials,
         )
         .unwrap()
         .into_group(&self.provider)
@@ -296,7 +298,11 @@ impl PassiveClient {
             .group
             .as_mut()
             .unwrap()
-            .process_message(&self.provider, message.into_protocol_message().unwrap())
+            .process_message(
+                &self.provider,
+                message.into_protocol_message().unwrap(),
+                &AcceptAllCredentials,
+            )
             .unwrap();
 
         match processed_message.into_content() {
@@ -0,0 +1,319 @@
+//! This module contains tests for AppAck proposals.
+
+use mls_group::tests_and_kats::utils::setup_client;
+use openmls_basic_credential::SignatureKeyPair;
+use openmls_test::openmls_test;
+use openmls_traits::{types::Ciphersuite, OpenMlsProvider as _};
+
+use crate::{
+    credentials::test_utils::AcceptAllCredentials,
+    framing::*,
+    group::{errors::*, *},
+    key_packages::*,
+    messages::proposals::*,
+    storage::OpenMlsProvider,
+    treesync::node::leaf_node::Capabilities,
+};
+
+// Creates a group of Alice and Bob that both support AppAck proposals.
+fn setup_alice_bob<Provider: OpenMlsProvider>(
+    ciphersuite: Ciphersuite,
+    alice_provider: &Provider,
+    bob_provider: &Provider,
+) -> (MlsGroup, SignatureKeyPair, MlsGroup, SignatureKeyPair) {
+    let (alice_credential_with_key, _alice_kpb, alice_signer, _alice_pk) =
+        setup_client("Alice", ciphersuite, alice_provider);
+    let (bob_credential_with_key, _bob_kpb, bob_signer, _bob_pk) =
+        setup_client("Bob", ciphersuite, bob_provider);
+
+    let capabilities = Capabilities::new(None, None, None, Some(&[ProposalType::AppAck]), None);
+
+    let bob_kpb = KeyPackage::builder()
+        .leaf_node_capabilities(capabilities.clone())
+        .build(
+            ciphersuite,
+            bob_provider,
+            &bob_signer,
+            bob_credential_with_key,
+        )
+        .expect("Could not create key package.");
+
+    let mut alice_group = MlsGroup::builder()
+        .ciphersuite(ciphersuite)
+        .with_capabilities(capabilities)
+        .use_ratchet_tree_extension(true)
+        .build(alice_provider, &alice_signer, alice_credential_with_key)
+        .expect("Error creating group.");
+
+    let (_commit, welcome, _group_info) = alice_group
+        .add_members(
+            alice_provider,
+            &alice_signer,
+            &[bob_kpb.key_package().clone()],
+        )
+        .expect("Could not add member to group.");
+    alice_group
+        .merge_pending_commit(alice_provider)
+        .expect("error merging pending commit");
+
+    let bob_group = StagedWelcome::new_from_welcome(
+        bob_provider,
+        &MlsGroupJoinConfig::default(),
+        welcome.into_welcome().expect("expected a welcome"),
+        None,
+        &AcceptAllCredentials,
+    )
+    .and_then(|staged_join| staged_join.into_group(bob_provider))
+    .expect("error creating group from welcome");
+
+    (alice_group, alice_signer, bob_group, bob_signer)
+}
+
+#[openmls_test]
+fn app_ack_report() {
+    let alice_provider = provider;
+    let bob_provider = &Provider::default();
+
+    let (mut alice_group, alice_signer, mut bob_group, bob_signer) =
+        setup_alice_bob(ciphersuite, alice_provider, bob_provider);
+    let alice_index = alice_group.own_leaf_index();
+    let bob_index = bob_group.own_leaf_index();
+
+    // === Alice sends three messages, Bob receives them out of order ===
+    let messages: Vec<_> = (0..3)
+        .map(|_| {
+            alice_group
+                .create_message(alice_provider, &alice_signer, b"Hello Bob")
+                .expect("Could not create message.")
+                .into_protocol_message()
+                .expect("Unexpected message type")
+        })
+        .collect();
+
+    for message in [&messages[0], &messages[2]] {
+        bob_group
+            .process_message(bob_provider, message.clone(), &AcceptAllCredentials)
+            .expect("Could not process message.");
+    }
+    assert_eq!(
+        bob_group.app_ack_proposal().received_ranges(),
+        &[
+            MessageRange::new(alice_index, 0, 0),
+            MessageRange::new(alice_index, 2, 2)
+        ]
+    );
+
+    bob_group
+        .process_message(bob_provider, messages[1].clone(), &AcceptAllCredentials)
+        .expect("Could not process message.");
+    let app_ack_proposal = bob_group.app_ack_proposal();
+    assert_eq!(
+        app_ack_proposal.received_ranges(),
+        &[MessageRange::new(alice_index, 0, 2)]
+    );
+
+    // === Bob proposes the AppAck and Alice commits it ===
+    let (proposal, _proposal_ref) = bob_group
+        .propose_app_ack(bob_provider, &bob_signer, app_ack_proposal)
+        .expect("Could not create proposal.");
+    let processed_message = alice_group
+        .process_message(
+            alice_provider,
+            proposal
+                .into_protocol_message()
+                .expect("Unexpected message type"),
+            &AcceptAllCredentials,
+        )
+        .expect("Could not process proposal.");
+    let ProcessedMessageContent::ProposalMessage(queued_proposal) =
+        processed_message.into_content()
+    else {
+        panic!("Expected a proposal.");
+    };
+    alice_group
+        .store_pending_proposal(alice_provider.storage(), *queued_proposal)
+        .expect("Could not store proposal.");
+
+    let (commit, _welcome, _group_info) = alice_group
+        .commit_to_pending_proposals(alice_provider, &alice_signer)
+        .expect("Could not commit to pending proposals.");
+
+    let processed_message = bob_group
+        .process_message(
+            bob_provider,
+            commit
+                .into_protocol_message()
+                .expect("Unexpected message type"),
+            &AcceptAllCredentials,
+        )
+        .expect("Could not process commit.");
+    let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
+        processed_message.into_content()
+    else {
+        panic!("Expected a StagedCommit.");
+    };
+
+    let report = staged_commit.app_ack_report();
+    assert_eq!(
+        report,
+        alice_group
+            .pending_commit()
+            .expect("No pending commit.")
+            .app_ack_report()
+    );
+    assert_eq!(
+        report.acknowledging_members().collect::<Vec<_>>(),
+        [bob_index]
+    );
+    let members: Vec<_> = bob_group.members().map(|member| member.index).collect();
+    assert!(report.received_by_all(members.clone(), alice_index, 2));
+    assert!(!report.received_by_all(members, alice_index, 3));
+    assert!(!report.has_received(alice_index, alice_index, 0));
+}
+
+// The received application messages are persisted, so that a group loaded from
+// the storage still acknowledges them.
+#[openmls_test]
+fn app_ack_after_loading_the_group() {
+    let alice_provider = provider;
+    let bob_provider = &Provider::default();
+
+    let (mut alice_group, alice_signer, mut bob_group, _bob_signer) =
+        setup_alice_bob(ciphersuite, alice_provider, bob_provider);
+    let alice_index = alice_group.own_leaf_index();
+
+    for _ in 0..2 {
+        let message = alice_group
+            .create_message(alice_provider, &alice_signer, b"Hello Bob")
+            .expect("Could not create message.")
+            .into_protocol_message()
+            .expect("Unexpected message type");
+        bob_group
+            .process_message(bob_provider, message, &AcceptAllCredentials)
+            .expect("Could not process message.");
+    }
+
+    let bob_group = MlsGroup::load(bob_provider.storage(), bob_group.group_id())
+        .expect("Could not load group.")
+        .expect("Group is missing from the storage.");
+    assert_eq!(
+        bob_group.app_ack_proposal().received_ranges(),
+        &[MessageRange::new(alice_index, 0, 1)]
+    );
+}
+
+#[openmls_test]
+fn app_ack_of_unsent_messages_is_rejected() {
+    let alice_provider = provider;
+    let bob_provider = &Provider::default();
+
+    let (mut alice_group, alice_signer, mut bob_group, bob_signer) =
+        setup_alice_bob(ciphersuite, alice_provider, bob_provider);
+    let alice_index = alice_group.own_leaf_index();
+
+    // Alice sends a single message
+    let message = alice_group
+        .create_message(alice_provider, &alice_signer, b"Hello Bob")
+        .expect("Could not create message.");
+    bob_group
+        .process_message(
+            bob_provider,
+            message
+                .into_protocol_message()
+                .expect("Unexpected message type"),
+            &AcceptAllCredentials,
+        )
+        .expect("Could not process message.");
+
+    // Bob acknowledges messages that Alice never sent
+    let (proposal, _proposal_ref) = bob_group
+        .propose_app_ack(
+            bob_provider,
+            &bob_signer,
+            AppAckProposal::new(vec![MessageRange::new(alice_index, 0, 5)]),
+        )
+        .expect("Could not create proposal.");
+    let (commit, _welcome, _group_info) = bob_group
+        .commit_to_pending_proposals(bob_provider, &bob_signer)
+        .expect("Could not commit to pending proposals.");
+
+    let processed_message = alice_group
+        .process_message(
+            alice_provider,
+            proposal
+                .into_protocol_message()
+                .expect("Unexpected message type"),
+            &AcceptAllCredentials,
+        )
+        .expect("Could not process proposal.");
+    let ProcessedMessageContent::ProposalMessage(queued_proposal) =
+        processed_message.into_content()
+    else {
+        panic!("Expected a proposal.");
+    };
+    alice_group
+        .store_pending_proposal(alice_provider.storage(), *queued_proposal)
+        .expect("Could not store proposal.");
+
+    let err = alice_group
+        .process_message(
+            alice_provider,
+            commit
+                .into_protocol_message()
+                .expect("Unexpected message type"),
+            &AcceptAllCredentials,
+        )
+        .expect_err("Processed a commit with an invalid AppAck.");
+    assert!(matches!(
+        err,
+        ProcessMessageError::InvalidCommit(StageCommitError::ProposalValidationError(
+            ProposalValidationError::AppAckUnknownGeneration
+        ))
+    ));
+}
+
+#[openmls_test]
+fn app_ack_skips_unverified_messages() {
+    let alice_provider = provider;
+    let bob_provider = &Provider::default();
+
+    let (mut alice_group, alice_signer, mut bob_group, _bob_signer) =
+        setup_alice_bob(ciphersuite, alice_provider, bob_provider);
+    let alice_index = alice_group.own_leaf_index();
+
+    // Alice signs her first message with a key that isn't in her leaf. Bob can
+    // decrypt it, but its signature doesn't verify.
+    let wrong_signer = SignatureKeyPair::new(ciphersuite.signature_algorithm())
+        .expect("Could not create signature key pair.");
+    let message = alice_group
+        .create_message(alice_provider, &wrong_signer, b"Hello Bob")
+        .expect("Could not create message.");
+    bob_group
+        .process_message(
+            bob_provider,
+            message
+                .into_protocol_message()
+                .expect("Unexpected message type"),
+            &AcceptAllCredentials,
+        )
+        .expect_err("Processed a message with an invalid signature.");
+    assert!(bob_group.app_ack_proposal().received_ranges().is_empty());
+
+    // Only the second message is acknowledged
+    let message = alice_group
+        .create_message(alice_provider, &alice_signer, b"Hello Bob")
+        .expect("Could not create message.");
+    bob_group
+        .process_message(
+            bob_provider,
+            message
+                .into_protocol_message()
+                .expect("Unexpected message type"),
+            &AcceptAllCredentials,
+        )
+        .expect("Could not process message.");
+    assert_eq!(
+        bob_group.app_ack_proposal().received_ranges(),
+        &[MessageRange::new(alice_index, 1, 1)]
+    );
+}
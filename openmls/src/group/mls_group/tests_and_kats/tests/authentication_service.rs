@@ -0,0 +1,349 @@
+//! This module contains tests for the authentication of credentials with an
+//! [`AuthenticationService`].
+
+use std::cell::RefCell;
+
+use mls_group::tests_and_kats::utils::{setup_alice_bob_group, setup_client};
+use openmls_test::openmls_test;
+use openmls_traits::OpenMlsProvider as _;
+
+use crate::{
+    binary_tree::LeafNodeIndex,
+    credentials::{
+        test_utils::AcceptAllCredentials, AuthenticationService, BasicCredential, CredentialSource,
+        CredentialWithKey,
+    },
+    framing::ProcessedMessageContent,
+    group::{errors::*, public_group::errors::CreationFromExternalError, *},
+    key_packages::KeyPackageBundle,
+    treesync::LeafNodeParameters,
+};
+
+/// An authentication service that rejects the credential with the given
+/// identity and records all credentials it is called with.
+struct RejectIdentity {
+    identity: &'static [u8],
+    seen: RefCell<Vec<(Vec<u8>, CredentialSource)>>,
+}
+
+impl RejectIdentity {
+    fn new(identity: &'static [u8]) -> Self {
+        Self {
+            identity,
+            seen: RefCell::default(),
+        }
+    }
+}
+
+impl AuthenticationService for RejectIdentity {
+    fn authenticate(
+        &self,
+        credential: &CredentialWithKey,
+        source: CredentialSource,
+    ) -> Result<(), String> {
+        let identity = BasicCredential::try_from(credential.credential.clone())
+            .map_err(|e| e.to_string())?
+            .identity()
+            .to_vec();
+        self.seen.borrow_mut().push((identity.clone(), source));
+        if identity == self.identity {
+            return Err("rejected".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// An authentication service that only accepts successors with the same
+/// identity.
+struct SameIdentity;
+
+impl AuthenticationService for SameIdentity {
+    fn authenticate(
+        &self,
+        _credential: &CredentialWithKey,
+        _source: CredentialSource,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn authenticate_succession(
+        &self,
+        old: &CredentialWithKey,
+        new: &CredentialWithKey,
+    ) -> Result<(), String> {
+        let identity = |credential: &CredentialWithKey| {
+            BasicCredential::try_from(credential.credential.clone())
+                .map(|basic_credential| basic_credential.identity().to_vec())
+                .map_err(|e| e.to_string())
+        };
+        if identity(old)? != identity(new)? {
+            return Err("identity changed".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[openmls_test]
+fn authenticate_welcome() {
+    let alice_provider = &Provider::default();
+    let bob_provider = &Provider::default();
+
+    let (alice_credential_with_key, _alice_kpb, alice_signer, _alice_pk) =
+        setup_client("Alice", ciphersuite, alice_provider);
+    let (bob_credential_with_key, bob_kpb, bob_signer, _bob_pk) =
+        setup_client("Bob", ciphersuite, bob_provider);
+
+    let mut alice_group = MlsGroup::builder()
+        .ciphersuite(ciphersuite)
+        .use_ratchet_tree_extension(true)
+        .build(alice_provider, &alice_signer, alice_credential_with_key.clone())
+        .expect("Error creating group.");
+    let (_commit, welcome, _group_info) = alice_group
+        .add_members(
+            alice_provider,
+            &alice_signer,
+            &[bob_kpb.key_package().clone()],
+        )
+        .expect("Could not add member to group.");
+    alice_group
+        .merge_pending_commit(alice_provider)
+        .expect("error merging pending commit");
+    let welcome = welcome.into_welcome().expect("expected a welcome");
+
+    // Bob rejects Alice's credential.
+    let authentication_service = RejectIdentity::new(b"Alice");
+    let err = StagedWelcome::new_from_welcome(
+        bob_provider,
+        &MlsGroupJoinConfig::default(),
+        welcome.clone(),
+        None,
+        &authentication_service,
+    )
+    .expect_err("Alice's credential was accepted");
+    assert!(matches!(
+        err,
+        WelcomeError::PublicGroupError(CreationFromExternalError::CredentialRejected(_))
+    ));
+    assert_eq!(
+        authentication_service.seen.borrow()[0],
+        (
+            b"Alice".to_vec(),
+            CredentialSource::RatchetTree(LeafNodeIndex::new(0))
+        )
+    );
+
+    // The rejected Welcome consumed Bob's key package, so Alice invites Bob
+    // to a new group with a new one.
+    let bob_kpb = KeyPackageBundle::generate(
+        bob_provider,
+        &bob_signer,
+        ciphersuite,
+        bob_credential_with_key,
+    );
+    let mut alice_group = MlsGroup::builder()
+        .ciphersuite(ciphersuite)
+        .use_ratchet_tree_extension(true)
+        .build(alice_provider, &alice_signer, alice_credential_with_key)
+        .expect("Error creating group.");
+    let (_commit, welcome, _group_info) = alice_group
+        .add_members(
+            alice_provider,
+            &alice_signer,
+            &[bob_kpb.key_package().clone()],
+        )
+        .expect("Could not add member to group.");
+    alice_group
+        .merge_pending_commit(alice_provider)
+        .expect("error merging pending commit");
+
+    // Bob accepts all other credentials and sees both leaves of the tree.
+    let authentication_service = RejectIdentity::new(b"Mallory");
+    StagedWelcome::new_from_welcome(
+        bob_provider,
+        &MlsGroupJoinConfig::default(),
+        welcome.into_welcome().expect("expected a welcome"),
+        None,
+        &authentication_service,
+    )
+    .expect("error joining the group");
+    assert_eq!(
+        *authentication_service.seen.borrow(),
+        vec![
+            (
+                b"Alice".to_vec(),
+                CredentialSource::RatchetTree(LeafNodeIndex::new(0))
+            ),
+            (
+                b"Bob".to_vec(),
+                CredentialSource::RatchetTree(LeafNodeIndex::new(1))
+            ),
+        ]
+    );
+}
+
+#[openmls_test]
+fn authenticate_add_proposal() {
+    let alice_provider = &Provider::default();
+    let bob_provider = &Provider::default();
+    let charlie_provider = &Provider::default();
+
+    let (alice_credential_with_key, _alice_kpb, alice_signer, _alice_pk) =
+        setup_client("Alice", ciphersuite, alice_provider);
+    let (_bob_credential_with_key, bob_kpb, _bob_signer, _bob_pk) =
+        setup_client("Bob", ciphersuite, bob_provider);
+    let (_charlie_credential_with_key, charlie_kpb, _charlie_signer, _charlie_pk) =
+        setup_client("Charlie", ciphersuite, charlie_provider);
+
+    // Bob processes the same commit twice below. That only works with
+    // plaintext messages, because decrypting a message consumes its key.
+    let join_config = MlsGroupJoinConfig::builder()
+        .wire_format_policy(PURE_PLAINTEXT_WIRE_FORMAT_POLICY)
+        .build();
+
+    let mut alice_group = MlsGroup::builder()
+        .ciphersuite(ciphersuite)
+        .use_ratchet_tree_extension(true)
+        .with_wire_format_policy(PURE_PLAINTEXT_WIRE_FORMAT_POLICY)
+        .build(alice_provider, &alice_signer, alice_credential_with_key)
+        .expect("Error creating group.");
+    let (_commit, welcome, _group_info) = alice_group
+        .add_members(
+            alice_provider,
+            &alice_signer,
+            &[bob_kpb.key_package().clone()],
+        )
+        .expect("Could not add member to group.");
+    alice_group
+        .merge_pending_commit(alice_provider)
+        .expect("error merging pending commit");
+    let mut bob_group = StagedWelcome::new_from_welcome(
+        bob_provider,
+        &join_config,
+        welcome.into_welcome().expect("expected a welcome"),
+        None,
+        &AcceptAllCredentials,
+    )
+    .and_then(|staged_join| staged_join.into_group(bob_provider))
+    .expect("error creating group from welcome");
+
+    // Alice proposes to add Charlie, whose credential Bob rejects.
+    let (proposal, _proposal_ref) = alice_group
+        .propose_add_member(alice_provider, &alice_signer, charlie_kpb.key_package())
+        .expect("Could not create proposal.");
+    alice_group
+        .clear_pending_proposals(alice_provider.storage())
+        .expect("Could not clear proposals.");
+
+    let authentication_service = RejectIdentity::new(b"Charlie");
+    let err = bob_group
+        .process_message(
+            bob_provider,
+            proposal.into_protocol_message().unwrap(),
+            &authentication_service,
+        )
+        .expect_err("Charlie's credential was accepted");
+    assert!(matches!(err, ProcessMessageError::CredentialRejected(_)));
+
+    // The same holds for a commit that adds Charlie.
+    let (commit, _welcome, _group_info) = alice_group
+        .add_members(
+            alice_provider,
+            &alice_signer,
+            &[charlie_kpb.key_package().clone()],
+        )
+        .expect("Could not add member to group.");
+    let err = bob_group
+        .process_message(
+            bob_provider,
+            commit.clone().into_protocol_message().unwrap(),
+            &authentication_service,
+        )
+        .expect_err("Charlie's credential was accepted");
+    assert!(matches!(err, ProcessMessageError::CredentialRejected(_)));
+    assert!(authentication_service
+        .seen
+        .borrow()
+        .iter()
+        .all(
+            |(identity, source)| identity == b"Charlie" && *source == CredentialSource::AddProposal
+        ));
+
+    // The commit is processed once Charlie's credential is accepted.
+    bob_group
+        .process_message(
+            bob_provider,
+            commit.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
+        .expect("error processing commit");
+}
+
+#[openmls_test]
+fn credential_succession() {
+    let (mut alice_group, _alice_signer, mut bob_group, bob_signer, bob_credential_with_key) =
+        setup_alice_bob_group(ciphersuite, provider);
+
+    // Bob replaces his credential with one for a different identity, but
+    // keeps his signature key.
+    let new_credential_with_key = CredentialWithKey {
+        credential: BasicCredential::new(b"Robert".to_vec()).into(),
+        signature_key: bob_credential_with_key.signature_key.clone(),
+    };
+    let (commit, _welcome, _group_info) = bob_group
+        .self_update(
+            provider,
+            &bob_signer,
+            LeafNodeParameters::builder()
+                .with_credential_with_key(new_credential_with_key.clone())
+                .build(),
+        )
+        .expect("error updating credential");
+
+    // Bob's own pending commit reports the change as well.
+    let changes: Vec<_> = bob_group
+        .pending_commit()
+        .expect("no pending commit")
+        .credential_changes()
+        .cloned()
+        .collect();
+    assert_eq!(changes.len(), 1);
+
+    // Alice rejects the change of identity.
+    let err = alice_group
+        .process_message(
+            provider,
+            commit.clone().into_protocol_message().unwrap(),
+            &SameIdentity,
+        )
+        .expect_err("the change of identity was accepted");
+    assert!(matches!(
+        err,
+        ProcessMessageError::CredentialSuccessionRejected(_)
+    ));
+
+    // Without a succession policy, the commit is accepted and reports the
+    // old and new credential of Bob's leaf.
+    let processed_message = alice_group
+        .process_message(
+            provider,
+            commit.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
+        .expect("error processing commit");
+    let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
+        processed_message.into_content()
+    else {
+        panic!("expected a commit");
+    };
+    let change = staged_commit
+        .credential_changes()
+        .next()
+        .expect("no credential change");
+    assert_eq!(change, &changes[0]);
+    assert_eq!(change.leaf_index(), bob_group.own_leaf_index());
+    assert_eq!(change.old_credential(), &bob_credential_with_key);
+    assert_eq!(change.new_credential(), &new_credential_with_key);
+    assert!(change.credential_changed());
+    assert!(!change.signature_key_changed());
+    assert_eq!(staged_commit.credential_changes().count(), 1);
+}
@@ -0,0 +1,149 @@
+//! This module contains tests for branching subgroups from an [`MlsGroup`].
+
+use mls_group::tests_and_kats::utils::setup_client;
+use openmls_test::openmls_test;
+
+use crate::{
+    credentials::test_utils::AcceptAllCredentials,
+    framing::*,
+    group::{errors::*, *},
+    key_packages::*,
+};
+
+#[openmls_test]
+fn branch_group() {
+    let alice_provider = provider;
+    let bob_provider = &Provider::default();
+    let charlie_provider = &Provider::default();
+
+    let (alice_credential_with_key, _alice_kpb, alice_signer, _alice_pk) =
+        setup_client("Alice", ciphersuite, alice_provider);
+    let (bob_credential_with_key, bob_kpb, bob_signer, _bob_pk) =
+        setup_client("Bob", ciphersuite, bob_provider);
+    let (_charlie_credential_with_key, charlie_kpb, _charlie_signer, _charlie_pk) =
+        setup_client("Charlie", ciphersuite, charlie_provider);
+
+    // Bob takes the resumption PSK of the current epoch of the parent group
+    // from its epoch secrets, so he doesn't need to keep past resumption PSKs.
+    let mls_group_create_config = MlsGroupCreateConfig::builder()
+        .ciphersuite(ciphersuite)
+        .use_ratchet_tree_extension(true)
+        .build();
+
+    // === Alice creates a group and adds Bob ===
+    let mut alice_group = MlsGroup::new(
+        alice_provider,
+        &alice_signer,
+        &mls_group_create_config,
+        alice_credential_with_key.clone(),
+    )
+    .expect("Error creating group.");
+
+    let (_commit, welcome, _group_info) = alice_group
+        .add_members(
+            alice_provider,
+            &alice_signer,
+            &[bob_kpb.key_package().clone()],
+        )
+        .expect("Could not add member to group.");
+    alice_group
+        .merge_pending_commit(alice_provider)
+        .expect("error merging pending commit");
+
+    let bob_group = StagedWelcome::new_from_welcome(
+        bob_provider,
+        mls_group_create_config.join_config(),
+        welcome.into_welcome().expect("expected a welcome"),
+        None,
+        &AcceptAllCredentials,
+    )
+    .and_then(|staged_join| staged_join.into_group(bob_provider))
+    .expect("error creating group from welcome");
+
+    // Charlie is not a member of the group and can't be part of a subgroup
+    let subgroup_id = GroupId::from_slice(b"Subgroup");
+    let err = alice_group
+        .branch(
+            alice_provider,
+            &alice_signer,
+            subgroup_id.clone(),
+            &mls_group_create_config,
+            alice_credential_with_key.clone(),
+            &[charlie_kpb.key_package().clone()],
+        )
+        .expect_err("Branched with a non-member.");
+    assert!(matches!(err, BranchError::NotAMember));
+
+    // The subgroup needs its own group ID
+    let bob_kpb = KeyPackageBundle::generate(
+        bob_provider,
+        &bob_signer,
+        ciphersuite,
+        bob_credential_with_key,
+    );
+    let err = alice_group
+        .branch(
+            alice_provider,
+            &alice_signer,
+            alice_group.group_id().clone(),
+            &mls_group_create_config,
+            alice_credential_with_key.clone(),
+            &[bob_kpb.key_package().clone()],
+        )
+        .expect_err("Branched with the same group ID.");
+    assert!(matches!(err, BranchError::SameGroupId));
+
+    // === Alice branches a subgroup with Bob ===
+    let (mut alice_subgroup, welcome, _group_info) = alice_group
+        .branch(
+            alice_provider,
+            &alice_signer,
+            subgroup_id.clone(),
+            &mls_group_create_config,
+            alice_credential_with_key,
+            &[bob_kpb.key_package().clone()],
+        )
+        .expect("Could not branch the group.");
+    assert_eq!(alice_subgroup.group_id(), &subgroup_id);
+    assert_eq!(alice_subgroup.epoch(), GroupEpoch::from(1));
+
+    let mut bob_subgroup = StagedWelcome::new_from_welcome(
+        bob_provider,
+        mls_group_create_config.join_config(),
+        welcome.into_welcome().expect("expected a welcome"),
+        None,
+        &AcceptAllCredentials,
+    )
+    .and_then(|staged_join| staged_join.into_group(bob_provider))
+    .expect("error creating group from welcome");
+
+    assert_eq!(
+        alice_subgroup.epoch_authenticator(),
+        bob_subgroup.epoch_authenticator()
+    );
+
+    // The parent group is not affected by the branch
+    assert_eq!(alice_group.epoch(), bob_group.epoch());
+    assert_eq!(
+        alice_group.epoch_authenticator(),
+        bob_group.epoch_authenticator()
+    );
+
+    // Alice and Bob can communicate in the subgroup
+    let message = alice_subgroup
+        .create_message(alice_provider, &alice_signer, b"Hello Bob")
+        .expect("Could not create message.");
+    let processed_message = bob_subgroup
+        .process_message(
+            bob_provider,
+            message
+                .into_protocol_message()
+                .expect("Unexpected message type"),
+            &AcceptAllCredentials,
+        )
+        .expect("Could not process message.");
+    assert!(matches!(
+        processed_message.into_content(),
+        ProcessedMessageContent::ApplicationMessage(_)
+    ));
+}
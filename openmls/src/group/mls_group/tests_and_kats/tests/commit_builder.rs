@@ -0,0 +1,188 @@
+//! This module contains tests for composing commits with the [`CommitBuilder`].
+
+use mls_group::tests_and_kats::utils::setup_client;
+use openmls_test::openmls_test;
+use openmls_traits::OpenMlsProvider as _;
+
+use crate::{
+    binary_tree::LeafNodeIndex,
+    credentials::test_utils::AcceptAllCredentials,
+    framing::*,
+    group::{errors::*, *},
+};
+
+#[openmls_test]
+fn commit_builder() {
+    let alice_provider = provider;
+    let bob_provider = &Provider::default();
+    let charlie_provider = &Provider::default();
+    let dave_provider = &Provider::default();
+    let eve_provider = &Provider::default();
+
+    let (alice_credential_with_key, _alice_kpb, alice_signer, _alice_pk) =
+        setup_client("Alice", ciphersuite, alice_provider);
+    let (_bob_credential_with_key, bob_kpb, bob_signer, _bob_pk) =
+        setup_client("Bob", ciphersuite, bob_provider);
+    let (_charlie_credential_with_key, charlie_kpb, _charlie_signer, _charlie_pk) =
+        setup_client("Charlie", ciphersuite, charlie_provider);
+    let (_dave_credential_with_key, dave_kpb, _dave_signer, _dave_pk) =
+        setup_client("Dave", ciphersuite, dave_provider);
+    let (_eve_credential_with_key, eve_kpb, _eve_signer, _eve_pk) =
+        setup_client("Eve", ciphersuite, eve_provider);
+
+    let mls_group_create_config = MlsGroupCreateConfig::builder()
+        .ciphersuite(ciphersuite)
+        .use_ratchet_tree_extension(true)
+        .build();
+
+    // === Alice creates a group with Bob and Charlie ===
+    let mut alice_group = MlsGroup::new(
+        alice_provider,
+        &alice_signer,
+        &mls_group_create_config,
+        alice_credential_with_key,
+    )
+    .expect("Error creating group.");
+
+    let (_commit, welcome, _group_info) = alice_group
+        .add_members(
+            alice_provider,
+            &alice_signer,
+            &[
+                bob_kpb.key_package().clone(),
+                charlie_kpb.key_package().clone(),
+            ],
+        )
+        .expect("Could not add members to group.");
+    alice_group
+        .merge_pending_commit(alice_provider)
+        .expect("error merging pending commit");
+
+    let mut bob_group = StagedWelcome::new_from_welcome(
+        bob_provider,
+        mls_group_create_config.join_config(),
+        welcome.into_welcome().expect("expected a welcome"),
+        None,
+        &AcceptAllCredentials,
+    )
+    .and_then(|staged_join| staged_join.into_group(bob_provider))
+    .expect("error creating group from welcome");
+
+    // === Bob proposes to remove Charlie and to add Dave ===
+    let charlie_index = LeafNodeIndex::new(2);
+    let (remove_proposal, remove_proposal_ref) = bob_group
+        .propose_remove_member(bob_provider, &bob_signer, charlie_index)
+        .expect("Could not create proposal.");
+    let (add_proposal, _add_proposal_ref) = bob_group
+        .propose_add_member(bob_provider, &bob_signer, dave_kpb.key_package())
+        .expect("Could not create proposal.");
+
+    for proposal in [remove_proposal, add_proposal] {
+        let processed_message = alice_group
+            .process_message(
+                alice_provider,
+                proposal
+                    .into_protocol_message()
+                    .expect("Unexpected message type"),
+                &AcceptAllCredentials,
+            )
+            .expect("Could not process proposal.");
+        let ProcessedMessageContent::ProposalMessage(queued_proposal) =
+            processed_message.into_content()
+        else {
+            panic!("Expected a proposal.");
+        };
+        alice_group
+            .store_pending_proposal(alice_provider.storage(), *queued_proposal)
+            .expect("Could not store proposal.");
+    }
+
+    // === Alice commits to the removal of Charlie only and adds Eve ===
+    let commit_builder = alice_group
+        .commit_builder()
+        .pending_proposals([remove_proposal_ref.clone()])
+        .add_members([eve_kpb.key_package().clone()]);
+
+    let preview = commit_builder
+        .preview_members(alice_provider)
+        .expect("Could not preview members.");
+    assert_eq!(
+        preview
+            .iter()
+            .map(|member| member.index.u32())
+            .collect::<Vec<_>>(),
+        [0, 1, 2]
+    );
+    assert_eq!(
+        &preview[2].credential,
+        eve_kpb.key_package().leaf_node().credential()
+    );
+
+    let (commit, welcome, _group_info) = commit_builder
+        .build(alice_provider, &alice_signer)
+        .expect("Could not build commit.");
+    alice_group
+        .merge_pending_commit(alice_provider)
+        .expect("error merging pending commit");
+
+    let members: Vec<_> = alice_group.members().collect();
+    assert_eq!(members.len(), preview.len());
+    for (member, previewed_member) in members.iter().zip(preview.iter()) {
+        assert_eq!(member.index, previewed_member.index);
+        assert_eq!(member.credential, previewed_member.credential);
+    }
+    // Dave's Add proposal was not committed and is discarded.
+    assert_eq!(alice_group.pending_proposals().count(), 0);
+
+    // === Bob and Eve arrive at the same group state ===
+    let processed_message = bob_group
+        .process_message(
+            bob_provider,
+            commit
+                .into_protocol_message()
+                .expect("Unexpected message type"),
+            &AcceptAllCredentials,
+        )
+        .expect("Could not process commit.");
+    let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
+        processed_message.into_content()
+    else {
+        panic!("Expected a StagedCommit.");
+    };
+    bob_group
+        .merge_staged_commit(bob_provider, *staged_commit)
+        .expect("error merging staged commit");
+
+    let eve_group = StagedWelcome::new_from_welcome(
+        eve_provider,
+        mls_group_create_config.join_config(),
+        welcome
+            .expect("expected a welcome")
+            .into_welcome()
+            .expect("expected a welcome"),
+        None,
+        &AcceptAllCredentials,
+    )
+    .and_then(|staged_join| staged_join.into_group(eve_provider))
+    .expect("error creating group from welcome");
+
+    assert_eq!(
+        bob_group.export_secret(bob_provider, "test", &[], 32),
+        alice_group.export_secret(alice_provider, "test", &[], 32)
+    );
+    assert_eq!(
+        eve_group.export_secret(eve_provider, "test", &[], 32),
+        alice_group.export_secret(alice_provider, "test", &[], 32)
+    );
+
+    // === Committed proposals can't be selected again ===
+    let err = alice_group
+        .commit_builder()
+        .pending_proposals([remove_proposal_ref])
+        .build(alice_provider, &alice_signer)
+        .expect_err("Committed to a missing proposal.");
+    assert!(matches!(
+        err,
+        CommitBuilderError::CreateCommitError(CreateCommitError::MissingProposal)
+    ));
+}
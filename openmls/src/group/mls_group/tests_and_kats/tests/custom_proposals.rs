@@ -0,0 +1,357 @@
+//! This module contains tests for custom proposals with registered
+//! [`CustomProposalHandler`]s.
+
+use std::sync::{Arc, Mutex};
+
+use mls_group::tests_and_kats::utils::setup_client;
+use openmls_basic_credential::SignatureKeyPair;
+use openmls_test::openmls_test;
+use openmls_traits::types::Ciphersuite;
+use tls_codec::{Serialize as _, TlsDeserializeBytes, TlsSerialize, TlsSize, VLBytes};
+
+use crate::{
+    credentials::test_utils::AcceptAllCredentials,
+    framing::*,
+    group::{errors::*, tests_and_kats::utils::block_on, *},
+    key_packages::*,
+    messages::proposals::*,
+    storage::OpenMlsProvider,
+    treesync::node::leaf_node::Capabilities,
+};
+
+const TOPIC_PROPOSAL_TYPE: u16 = 0xF001;
+
+#[derive(Debug, Clone, PartialEq, TlsSerialize, TlsDeserializeBytes, TlsSize)]
+struct TopicProposal {
+    topic: VLBytes,
+}
+
+impl TopicProposal {
+    fn custom_proposal(topic: &[u8]) -> CustomProposal {
+        let payload = TopicProposal {
+            topic: topic.into(),
+        }
+        .tls_serialize_detached()
+        .expect("Could not serialize topic.");
+        CustomProposal::new(TOPIC_PROPOSAL_TYPE, payload)
+    }
+}
+
+// Keeps track of the topic of the group and rejects topics that are empty or
+// longer than `max_length`.
+#[derive(Clone)]
+struct TopicHandler {
+    max_length: usize,
+    topic: Arc<Mutex<Vec<u8>>>,
+}
+
+impl TopicHandler {
+    fn new(max_length: usize) -> Self {
+        Self {
+            max_length,
+            topic: Arc::default(),
+        }
+    }
+
+    fn topic(&self) -> Vec<u8> {
+        self.topic.lock().unwrap().clone()
+    }
+}
+
+impl CustomProposalHandler for TopicHandler {
+    type Proposal = TopicProposal;
+
+    fn proposal_type(&self) -> u16 {
+        TOPIC_PROPOSAL_TYPE
+    }
+
+    fn validate(
+        &self,
+        proposal: &TopicProposal,
+        _sender: &Sender,
+        _group_context: &GroupContext,
+    ) -> Result<(), String> {
+        let length = proposal.topic.as_slice().len();
+        if length == 0 || length > self.max_length {
+            return Err("invalid topic length".to_owned());
+        }
+        Ok(())
+    }
+
+    fn apply(&self, proposal: &TopicProposal, _sender: &Sender, _group_context: &GroupContext) {
+        *self.topic.lock().unwrap() = proposal.topic.as_slice().to_vec();
+    }
+}
+
+// Creates a group of Alice and Bob that both support topic proposals. The
+// topic proposal type is required by the group if `required` is set.
+fn setup_alice_bob<Provider: OpenMlsProvider>(
+    ciphersuite: Ciphersuite,
+    alice_provider: &Provider,
+    bob_provider: &Provider,
+    required: bool,
+) -> (MlsGroup, SignatureKeyPair, MlsGroup, SignatureKeyPair) {
+    let (alice_credential_with_key, _alice_kpb, alice_signer, _alice_pk) =
+        setup_client("Alice", ciphersuite, alice_provider);
+    let (bob_credential_with_key, _bob_kpb, bob_signer, _bob_pk) =
+        setup_client("Bob", ciphersuite, bob_provider);
+
+    let proposal_types = [ProposalType::Custom(TOPIC_PROPOSAL_TYPE)];
+    let capabilities = Capabilities::new(None, None, None, Some(&proposal_types), None);
+    let group_context_extensions = if required {
+        Extensions::single(Extension::RequiredCapabilities(
+            RequiredCapabilitiesExtension::new(&[], &proposal_types, &[]),
+        ))
+    } else {
+        Extensions::empty()
+    };
+
+    let bob_kpb = KeyPackage::builder()
+        .leaf_node_capabilities(capabilities.clone())
+        .build(
+            ciphersuite,
+            bob_provider,
+            &bob_signer,
+            bob_credential_with_key,
+        )
+        .expect("Could not create key package.");
+
+    let mut alice_group = MlsGroup::builder()
+        .ciphersuite(ciphersuite)
+        .with_capabilities(capabilities)
+        .with_group_context_extensions(group_context_extensions)
+        .expect("Invalid group context extensions.")
+        .use_ratchet_tree_extension(true)
+        .build(alice_provider, &alice_signer, alice_credential_with_key)
+        .expect("Error creating group.");
+
+    let (_commit, welcome, _group_info) = alice_group
+        .add_members(
+            alice_provider,
+            &alice_signer,
+            &[bob_kpb.key_package().clone()],
+        )
+        .expect("Could not add member to group.");
+    alice_group
+        .merge_pending_commit(alice_provider)
+        .expect("error merging pending commit");
+
+    let bob_group = StagedWelcome::new_from_welcome(
+        bob_provider,
+        &MlsGroupJoinConfig::default(),
+        welcome.into_welcome().expect("expected a welcome"),
+        None,
+        &AcceptAllCredentials,
+    )
+    .and_then(|staged_join| staged_join.into_group(bob_provider))
+    .expect("error creating group from welcome");
+
+    (alice_group, alice_signer, bob_group, bob_signer)
+}
+
+#[openmls_test]
+fn custom_proposal_handler() {
+    let alice_provider = provider;
+    let bob_provider = &Provider::default();
+    let charlie_provider = &Provider::default();
+
+    let (mut alice_group, alice_signer, mut bob_group, _bob_signer) =
+        setup_alice_bob(ciphersuite, alice_provider, bob_provider, true);
+
+    let alice_handler = TopicHandler::new(16);
+    let bob_handler = TopicHandler::new(4);
+    alice_group.register_custom_proposal_handler(alice_handler.clone());
+    bob_group.register_custom_proposal_handler(bob_handler.clone());
+
+    // === Alice can't commit a topic that her handler rejects ===
+    let err = alice_group
+        .commit_builder()
+        .custom_proposals([TopicProposal::custom_proposal(b"")])
+        .build(alice_provider, &alice_signer)
+        .expect_err("Committed an invalid topic.");
+    assert!(matches!(
+        err,
+        CommitBuilderError::CreateCommitError(CreateCommitError::ProposalValidationError(
+            ProposalValidationError::CustomProposalRejected(_)
+        ))
+    ));
+
+    // === Alice sets the topic and adds Charlie in the same Commit ===
+    let (charlie_credential_with_key, _charlie_kpb, charlie_signer, _charlie_pk) =
+        setup_client("Charlie", ciphersuite, charlie_provider);
+    let charlie_kpb = KeyPackage::builder()
+        .leaf_node_capabilities(Capabilities::new(
+            None,
+            None,
+            None,
+            Some(&[ProposalType::Custom(TOPIC_PROPOSAL_TYPE)]),
+            None,
+        ))
+        .build(
+            ciphersuite,
+            charlie_provider,
+            &charlie_signer,
+            charlie_credential_with_key,
+        )
+        .expect("Could not create key package.");
+
+    let (commit, _welcome, _group_info) = alice_group
+        .commit_builder()
+        .custom_proposals([TopicProposal::custom_proposal(b"rust")])
+        .add_members([charlie_kpb.key_package().clone()])
+        .build(alice_provider, &alice_signer)
+        .expect("Could not build commit.");
+
+    let processed_message = bob_group
+        .process_message(
+            bob_provider,
+            commit
+                .into_protocol_message()
+                .expect("Unexpected message type"),
+            &AcceptAllCredentials,
+        )
+        .expect("Could not process commit.");
+    let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
+        processed_message.into_content()
+    else {
+        panic!("Expected a StagedCommit.");
+    };
+
+    // The topic only changes when the Commit is merged
+    assert!(bob_handler.topic().is_empty());
+    bob_group
+        .merge_staged_commit(bob_provider, *staged_commit)
+        .expect("error merging staged commit");
+    assert_eq!(bob_handler.topic(), b"rust");
+    assert_eq!(bob_group.members().count(), 3);
+
+    assert!(alice_handler.topic().is_empty());
+    alice_group
+        .merge_pending_commit(alice_provider)
+        .expect("error merging pending commit");
+    assert_eq!(alice_handler.topic(), b"rust");
+
+    // === Bob rejects a topic that only Alice's handler accepts ===
+    let (commit, _welcome, _group_info) = alice_group
+        .commit_builder()
+        .custom_proposals([TopicProposal::custom_proposal(b"openmls")])
+        .build(alice_provider, &alice_signer)
+        .expect("Could not build commit.");
+
+    let err = bob_group
+        .process_message(
+            bob_provider,
+            commit
+                .into_protocol_message()
+                .expect("Unexpected message type"),
+            &AcceptAllCredentials,
+        )
+        .expect_err("Accepted a topic that was rejected by the handler.");
+    assert!(matches!(
+        err,
+        ProcessMessageError::InvalidCommit(StageCommitError::ProposalValidationError(
+            ProposalValidationError::CustomProposalRejected(_)
+        ))
+    ));
+    assert_eq!(bob_handler.topic(), b"rust");
+}
+
+#[openmls_test]
+fn custom_proposal_handler_requires_capability() {
+    let alice_provider = provider;
+    let bob_provider = &Provider::default();
+
+    let (mut alice_group, alice_signer, _bob_group, _bob_signer) =
+        setup_alice_bob(ciphersuite, alice_provider, bob_provider, false);
+
+    // Without a handler, the custom proposal is only checked against the
+    // capabilities of the members.
+    alice_group
+        .commit_builder()
+        .custom_proposals([TopicProposal::custom_proposal(b"rust")])
+        .preview_members(alice_provider)
+        .expect("Custom proposal without handler was rejected.");
+
+    // With a handler, the proposal type has to be required by the group.
+    alice_group.register_custom_proposal_handler(TopicHandler::new(16));
+    let err = alice_group
+        .commit_builder()
+        .custom_proposals([TopicProposal::custom_proposal(b"rust")])
+        .build(alice_provider, &alice_signer)
+        .expect_err("Committed a custom proposal type that is not required.");
+    assert!(matches!(
+        err,
+        CommitBuilderError::CreateCommitError(CreateCommitError::ProposalValidationError(
+            ProposalValidationError::CustomProposalNotRequired
+        ))
+    ));
+}
+
+#[openmls_test]
+fn async_group_keeps_custom_proposal_handler() {
+    let alice_provider = provider;
+    let bob_provider = &Provider::default();
+
+    let (mut alice_group, alice_signer, bob_group, _bob_signer) =
+        setup_alice_bob(ciphersuite, alice_provider, bob_provider, true);
+    let group_id = bob_group.group_id().clone();
+
+    let bob_handler = TopicHandler::new(4);
+    let mut bob_group = block_on(AsyncMlsGroup::load(bob_provider, &group_id))
+        .expect("Error loading the group")
+        .expect("The group is not stored");
+    bob_group.register_custom_proposal_handler(bob_handler.clone());
+
+    // Processing the Commit needs values that are not cached yet, so the
+    // group is reloaded from the cache in between.
+    let (commit, _welcome, _group_info) = alice_group
+        .commit_builder()
+        .custom_proposals([TopicProposal::custom_proposal(b"rust")])
+        .build(alice_provider, &alice_signer)
+        .expect("Could not build commit.");
+    alice_group
+        .merge_pending_commit(alice_provider)
+        .expect("error merging pending commit");
+
+    let processed_message = block_on(bob_group.process_message(
+        bob_provider,
+        commit
+            .into_protocol_message()
+            .expect("Unexpected message type"),
+        &AcceptAllCredentials,
+    ))
+    .expect("Could not process commit.");
+    let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
+        processed_message.into_content()
+    else {
+        panic!("Expected a StagedCommit.");
+    };
+    block_on(bob_group.merge_staged_commit(bob_provider, *staged_commit))
+        .expect("error merging staged commit");
+    assert_eq!(bob_handler.topic(), b"rust");
+
+    // === Bob's handler still rejects topics that are too long ===
+    let (commit, _welcome, _group_info) = alice_group
+        .commit_builder()
+        .custom_proposals([TopicProposal::custom_proposal(b"openmls")])
+        .build(alice_provider, &alice_signer)
+        .expect("Could not build commit.");
+
+    let err = block_on(bob_group.process_message(
+        bob_provider,
+        commit
+            .into_protocol_message()
+            .expect("Unexpected message type"),
+        &AcceptAllCredentials,
+    ))
+    .expect_err("Accepted a topic that was rejected by the handler.");
+    assert!(matches!(
+        err,
+        AsyncMlsGroupError::OperationError(ProcessMessageError::InvalidCommit(
+            StageCommitError::ProposalValidationError(
+                ProposalValidationError::CustomProposalRejected(_)
+            )
+        ))
+    ));
+    assert_eq!(bob_handler.topic(), b"rust");
+}
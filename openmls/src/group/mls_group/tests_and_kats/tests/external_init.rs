@@ -1,4 +1,5 @@
 use crate::{
+    credentials::test_utils::AcceptAllCredentials,
     group::{
         errors::ExternalCommitError,
         mls_group::tests_and_kats::utils::{setup_alice_bob_group, setup_client},
@@ -37,6 +38,7 @@ fn test_external_init_broken_signature() {
         None,
         &[],
         charlie_credential,
+        &AcceptAllCredentials,
     )
     .expect_err("Signature was corrupted. This should have failed.");
     assert!(matches!(
@@ -1,5 +1,5 @@
 use mls_group::tests_and_kats::utils::{
-    flip_last_byte, setup_alice_bob, setup_alice_bob_group, setup_client,
+    flip_last_byte, setup_alice_bob, setup_alice_bob_group, setup_alice_group, setup_client,
 };
 use openmls_basic_credential::SignatureKeyPair;
 use openmls_rust_crypto::MemoryStorage;
@@ -11,6 +11,7 @@ use tls_codec::{Deserialize, Serialize};
 use crate::{
     binary_tree::LeafNodeIndex,
     credentials::test_utils::new_credential,
+    credentials::test_utils::AcceptAllCredentials,
     framing::*,
     group::{errors::*, *},
     key_packages::*,
@@ -30,7 +31,7 @@ use crate::{
     treesync::{
         errors::{ApplyUpdatePathError, LeafNodeValidationError},
         node::leaf_node::Capabilities,
-        LeafNodeParameters,
+        LeafNodeParameters, Node, StoredTreeSync,
     },
 };
 
@@ -74,6 +75,132 @@ fn test_mls_group_persistence<Provider: OpenMlsProvider>() {
     );
 }
 
+/// Returns the tree nodes of the group in `storage`, sorted by their index.
+fn stored_tree_nodes(storage: &MemoryStorage, group_id: &GroupId) -> Vec<(u32, Node)> {
+    let mut nodes: Vec<(u32, Node)> = <MemoryStorage as openmls_traits::storage::StorageProvider<
+        CURRENT_VERSION,
+    >>::tree_nodes(storage, group_id)
+    .unwrap();
+    nodes.sort_by_key(|(node_index, _)| *node_index);
+    nodes
+}
+
+/// Returns the non-blank nodes of the group's tree, sorted by their index.
+fn full_tree_nodes(group: &MlsGroup) -> Vec<(u32, Node)> {
+    let mut nodes: Vec<(u32, Node)> = group.public_group().treesync().full_nodes().collect();
+    nodes.sort_by_key(|(node_index, _)| *node_index);
+    nodes
+}
+
+// Tests that the nodes of the tree are stored individually, that merging a
+// commit only writes the nodes it changed, and that groups with a tree that
+// was stored as a whole are still loaded and stored node by node afterwards.
+#[openmls_test]
+fn tree_nodes_persistence() {
+    let alice_provider = provider;
+    let (alice_credential_with_key, _alice_kpb, alice_signer, _alice_pk) =
+        setup_client("Alice", ciphersuite, alice_provider);
+    let key_packages = (0..7)
+        .map(|i| {
+            let (_credential, kpb, _signer, _pk) =
+                setup_client(&format!("Member{i}"), ciphersuite, &Provider::default());
+            kpb.key_package().clone()
+        })
+        .collect::<Vec<_>>();
+
+    // === Alice creates a group with seven other members ===
+    let mut alice_group = MlsGroup::builder()
+        .ciphersuite(ciphersuite)
+        .build(alice_provider, &alice_signer, alice_credential_with_key)
+        .expect("error creating group");
+    alice_group
+        .add_members(alice_provider, &alice_signer, &key_packages)
+        .expect("error adding members");
+    alice_group
+        .merge_pending_commit(alice_provider)
+        .expect("error merging pending commit");
+    let group_id = alice_group.group_id().clone();
+
+    assert_eq!(
+        stored_tree_nodes(alice_provider.storage(), &group_id),
+        full_tree_nodes(&alice_group)
+    );
+
+    // Remove the leaf of the last member, which is not on Alice's direct path,
+    // from the storage. A self update must not write it again.
+    let last_leaf = stored_tree_nodes(alice_provider.storage(), &group_id)
+        .into_iter()
+        .find(|(node_index, _)| *node_index == 14)
+        .expect("missing leaf of the last member");
+    <MemoryStorage as openmls_traits::storage::StorageProvider<CURRENT_VERSION>>::delete_tree_node(
+        alice_provider.storage(),
+        &group_id,
+        last_leaf.0,
+    )
+    .unwrap();
+
+    alice_group
+        .self_update(alice_provider, &alice_signer, LeafNodeParameters::default())
+        .expect("error creating self update");
+    alice_group
+        .merge_pending_commit(alice_provider)
+        .expect("error merging pending commit");
+
+    let expected_nodes = full_tree_nodes(&alice_group)
+        .into_iter()
+        .filter(|(node_index, _)| *node_index != last_leaf.0)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        stored_tree_nodes(alice_provider.storage(), &group_id),
+        expected_nodes
+    );
+
+    <MemoryStorage as openmls_traits::storage::StorageProvider<CURRENT_VERSION>>::write_tree_node(
+        alice_provider.storage(),
+        &group_id,
+        last_leaf.0,
+        &last_leaf.1,
+    )
+    .unwrap();
+    let alice_group_loaded = MlsGroup::load(alice_provider.storage(), &group_id)
+        .expect("error loading group")
+        .expect("group not stored");
+    assert_eq!(alice_group_loaded, alice_group);
+
+    // === Store the tree as a whole, as earlier releases did ===
+    <MemoryStorage as openmls_traits::storage::StorageProvider<CURRENT_VERSION>>::delete_tree_nodes(
+        alice_provider.storage(),
+        &group_id,
+    )
+    .unwrap();
+    <MemoryStorage as openmls_traits::storage::StorageProvider<CURRENT_VERSION>>::write_tree(
+        alice_provider.storage(),
+        &group_id,
+        &StoredTreeSync::Complete(alice_group.public_group().treesync().clone()),
+    )
+    .unwrap();
+    let mut alice_group = MlsGroup::load(alice_provider.storage(), &group_id)
+        .expect("error loading group")
+        .expect("group not stored");
+    assert_eq!(alice_group, alice_group_loaded);
+
+    // The next commit stores all nodes.
+    alice_group
+        .self_update(alice_provider, &alice_signer, LeafNodeParameters::default())
+        .expect("error creating self update");
+    alice_group
+        .merge_pending_commit(alice_provider)
+        .expect("error merging pending commit");
+    assert_eq!(
+        stored_tree_nodes(alice_provider.storage(), &group_id),
+        full_tree_nodes(&alice_group)
+    );
+    let alice_group_loaded = MlsGroup::load(alice_provider.storage(), &group_id)
+        .expect("error loading group")
+        .expect("group not stored");
+    assert_eq!(alice_group_loaded, alice_group);
+}
+
 // This tests if the remover is correctly passed to the callback when one member
 // issues a RemoveProposal and another members issues the next Commit.
 #[openmls_test]
@@ -119,6 +246,7 @@ fn remover() {
         mls_group_create_config.join_config(),
         welcome,
         Some(alice_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .expect("Error creating staged join from Welcome")
     .into_group(provider)
@@ -135,6 +263,7 @@ fn remover() {
             queued_messages
                 .into_protocol_message()
                 .expect("Unexpected message type"),
+            &AcceptAllCredentials,
         )
         .expect("Could not process messages.");
     if let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
@@ -159,6 +288,7 @@ fn remover() {
         mls_group_create_config.join_config(),
         welcome,
         Some(bob_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .expect("Error creating group from Welcome")
     .into_group(provider)
@@ -176,6 +306,7 @@ fn remover() {
             queued_messages
                 .into_protocol_message()
                 .expect("Unexpected message type"),
+            &AcceptAllCredentials,
         )
         .expect("Could not process messages.");
 
@@ -307,6 +438,7 @@ fn staged_join() {
         join_config,
         welcome,
         Some(alice_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .expect("error creating staged mls group");
 
@@ -477,7 +609,11 @@ fn test_verify_staged_commit_credentials(
         .expect("error creating self-update proposal");
 
     let alice_processed_message = alice_group
-        .process_message(provider, proposal.into_protocol_message().unwrap())
+        .process_message(
+            provider,
+            proposal.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
         .expect("Could not process messages.");
     assert!(alice_group.pending_commit().is_none());
 
@@ -513,6 +649,7 @@ fn test_verify_staged_commit_credentials(
         mls_group_config.join_config(),
         welcome,
         Some(alice_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .expect("error creating group from welcome")
     .into_group(provider)
@@ -576,7 +713,11 @@ fn test_verify_staged_commit_credentials(
 
     // further process the deserialized message
     let processed_message = bob_group
-        .process_message(provider, msg_in.try_into_protocol_message().unwrap())
+        .process_message(
+            provider,
+            msg_in.try_into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
         .expect("bob failed processing alice's message");
 
     // the processed message must be a staged commit message
@@ -656,7 +797,11 @@ fn test_commit_with_update_path_leaf_node(
         .expect("error creating self-update proposal");
 
     let alice_processed_message = alice_group
-        .process_message(provider, proposal.into_protocol_message().unwrap())
+        .process_message(
+            provider,
+            proposal.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
         .expect("Could not process messages.");
     assert!(alice_group.pending_commit().is_none());
 
@@ -694,6 +839,7 @@ fn test_commit_with_update_path_leaf_node(
         mls_group_create_config.join_config(),
         welcome,
         Some(alice_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .expect("error creating group from welcome")
     .into_group(provider)
@@ -765,7 +911,11 @@ fn test_commit_with_update_path_leaf_node(
 
     // further process the deserialized message
     let processed_message = bob_group
-        .process_message(provider, msg_in.try_into_protocol_message().unwrap())
+        .process_message(
+            provider,
+            msg_in.try_into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
         .expect("bob failed processing alice's message");
 
     // the processed message must be a staged commit message
@@ -849,7 +999,11 @@ fn test_pending_commit_logic(
         .expect("error creating add-bob proposal");
 
     let alice_processed_message = alice_group
-        .process_message(provider, proposal.into_protocol_message().unwrap())
+        .process_message(
+            provider,
+            proposal.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
         .expect("Could not process messages.");
     assert!(alice_group.pending_commit().is_none());
 
@@ -955,6 +1109,7 @@ fn test_pending_commit_logic(
         mls_group_create_config.join_config(),
         welcome,
         Some(alice_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .expect("error creating group from welcome")
     .into_group(provider)
@@ -983,7 +1138,11 @@ fn test_pending_commit_logic(
         .expect("error creating self-update commit");
 
     let alice_processed_message = alice_group
-        .process_message(provider, msg.into_protocol_message().unwrap())
+        .process_message(
+            provider,
+            msg.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
         .expect("Could not process messages.");
     assert!(alice_group.pending_commit().is_some());
 
@@ -1044,6 +1203,7 @@ fn key_package_deletion() {
         mls_group_create_config.join_config(),
         welcome,
         Some(alice_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .expect("Error creating staged join from Welcome")
     .into_group(provider)
@@ -1109,6 +1269,7 @@ fn remove_prosposal_by_ref(
         mls_group_create_config.join_config(),
         welcome,
         Some(alice_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .unwrap()
     .into_group(provider)
@@ -1141,6 +1302,7 @@ fn remove_prosposal_by_ref(
             MlsMessageIn::from(commit)
                 .try_into_protocol_message()
                 .unwrap(),
+            &AcceptAllCredentials,
         )
         .unwrap();
     match msg.into_content() {
@@ -1387,6 +1549,7 @@ fn update_group_context_with_unknown_extension<Provider: OpenMlsProvider + Defau
         &MlsGroupJoinConfig::default(),
         welcome,
         Some(alice_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .expect("Error creating staged join from Welcome")
     .into_group(&bob_provider)
@@ -1445,6 +1608,7 @@ fn update_group_context_with_unknown_extension<Provider: OpenMlsProvider + Defau
         .process_message(
             &bob_provider,
             update_proposal.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
         )
         .expect("bob failed processing the update");
 
@@ -1467,6 +1631,7 @@ fn update_group_context_with_unknown_extension<Provider: OpenMlsProvider + Defau
         .process_message(
             &bob_provider,
             update_commit.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
         )
         .expect("bob failed processing the update");
 
@@ -1561,6 +1726,7 @@ fn update_proposal_bob() {
         &MlsGroupJoinConfig::default(),
         welcome,
         Some(alice_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .expect("Error creating staged join from Welcome")
     .into_group(&bob_provider)
@@ -1580,6 +1746,7 @@ fn update_proposal_bob() {
         .process_message(
             &alice_provider,
             update_proposal.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
         )
         .unwrap();
 
@@ -1602,7 +1769,11 @@ fn update_proposal_bob() {
 
     // === Bob processes the commit  ===
     let processed_message = bob_group
-        .process_message(&bob_provider, commit.into_protocol_message().unwrap())
+        .process_message(
+            &bob_provider,
+            commit.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
         .expect("bob failed processing the update");
 
     let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
@@ -1667,6 +1838,7 @@ fn update_proposal_alice() {
         &MlsGroupJoinConfig::default(),
         welcome,
         Some(alice_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .expect("Error creating staged join from Welcome")
     .into_group(&bob_provider)
@@ -1686,6 +1858,7 @@ fn update_proposal_alice() {
         .process_message(
             &bob_provider,
             update_proposal.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
         )
         .unwrap();
 
@@ -1708,7 +1881,11 @@ fn update_proposal_alice() {
 
     // === Alice processes the commit  ===
     let processed_message = alice_group
-        .process_message(&alice_provider, commit.into_protocol_message().unwrap())
+        .process_message(
+            &alice_provider,
+            commit.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
         .expect("bob failed processing the update");
 
     let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
@@ -1939,6 +2116,7 @@ fn unknown_extensions() {
         &MlsGroupJoinConfig::default(),
         welcome,
         Some(alice_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .expect("Error creating staged join from Welcome")
     .into_group(provider)
@@ -2006,6 +2184,7 @@ fn join_multiple_groups_last_resort_extension(
         &MlsGroupJoinConfig::default(),
         alice_welcome,
         None,
+        &AcceptAllCredentials,
     )
     .expect("error creating staged join from welcome")
     .into_group(provider)
@@ -2027,10 +2206,16 @@ fn join_multiple_groups_last_resort_extension(
     let bob_welcome = bob_welcome
         .into_welcome()
         .expect("expected message to be a welcome");
-    StagedWelcome::new_from_welcome(provider, &MlsGroupJoinConfig::default(), bob_welcome, None)
-        .expect("error creating staged join from welcome")
-        .into_group(provider)
-        .expect("error creating group from staged join");
+    StagedWelcome::new_from_welcome(
+        provider,
+        &MlsGroupJoinConfig::default(),
+        bob_welcome,
+        None,
+        &AcceptAllCredentials,
+    )
+    .expect("error creating staged join from welcome")
+    .into_group(provider)
+    .expect("error creating group from staged join");
     // done :-)
 }
 
@@ -2171,6 +2356,7 @@ fn failed_groupinfo_decryption(
         &MlsGroupJoinConfig::default(),
         broken_welcome,
         None,
+        &AcceptAllCredentials,
     )
     .and_then(|staged_join| staged_join.into_group(provider))
     .expect_err("Creation of mls group from a broken Welcome was successful.");
@@ -2252,7 +2438,7 @@ fn update_path() {
 
     let protocol_message = ProtocolMessage::from(PublicMessage::from(broken_message));
 
-    let result = group_alice.process_message(provider, protocol_message);
+    let result = group_alice.process_message(provider, protocol_message, &AcceptAllCredentials);
     assert_eq!(
         result.expect_err("Successful processing of a broken commit."),
         ProcessMessageError::InvalidCommit(StageCommitError::UpdatePathError(
@@ -2315,6 +2501,7 @@ fn psks() {
         &MlsGroupJoinConfig::default(),
         welcome.into_welcome().unwrap(),
         Some(ratchet_tree.into()),
+        &AcceptAllCredentials,
     )
     .expect("Could not stage welcome")
     .into_group(provider)
@@ -2363,6 +2550,7 @@ fn staged_commit_creation(
         &MlsGroupJoinConfig::default(),
         welcome.into_welcome().unwrap(),
         Some(ratchet_tree.into()),
+        &AcceptAllCredentials,
     )
     .expect("Could not stage welcome")
     .into_group(provider)
@@ -2409,7 +2597,11 @@ fn own_commit_processing(
 
     // Alice attempts to process her own commit
     let error = alice_group
-        .process_message(provider, commit_in.into_protocol_message().unwrap())
+        .process_message(
+            provider,
+            commit_in.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
         .expect_err("no error while processing own commit");
     assert_eq!(
         error,
@@ -2463,6 +2655,7 @@ fn proposal_application_after_self_was_removed(
         &join_group_config,
         welcome.into_welcome().unwrap(),
         Some(ratchet_tree.into()),
+        &AcceptAllCredentials,
     )
     .expect("Could not stage welcome")
     .into_group(provider)
@@ -2493,6 +2686,7 @@ fn proposal_application_after_self_was_removed(
         .process_message(
             provider,
             bob_remove_proposal.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
         )
         .unwrap();
 
@@ -2522,7 +2716,11 @@ fn proposal_application_after_self_was_removed(
     // Bob processes the commit
     println!("Bob processes the commit");
     let processed_message = bob_group
-        .process_message(provider, commit.into_protocol_message().unwrap())
+        .process_message(
+            provider,
+            commit.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
         .unwrap();
 
     let staged_commit = match processed_message.into_content() {
@@ -2543,6 +2741,7 @@ fn proposal_application_after_self_was_removed(
         &join_group_config,
         welcome.into_welcome().unwrap(),
         Some(ratchet_tree.into()),
+        &AcceptAllCredentials,
     )
     .expect("Error staging welcome.")
     .into_group(provider)
@@ -2632,6 +2831,7 @@ fn proposal_application_after_self_was_removed_ref(
         &join_group_config,
         welcome.into_welcome().unwrap(),
         Some(ratchet_tree.into()),
+        &AcceptAllCredentials,
     )
     .expect("Could not stage welcome")
     .into_group(provider)
@@ -2666,6 +2866,7 @@ fn proposal_application_after_self_was_removed_ref(
         .process_message(
             provider,
             bob_remove_proposal.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
         )
         .unwrap();
 
@@ -2682,6 +2883,7 @@ fn proposal_application_after_self_was_removed_ref(
         .process_message(
             provider,
             charlie_add_proposal.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
         )
         .unwrap();
 
@@ -2713,7 +2915,11 @@ fn proposal_application_after_self_was_removed_ref(
     let bob_rt_before = bob_group.export_ratchet_tree();
     assert_eq!(alice_rt_before, bob_rt_before);
     let processed_message = bob_group
-        .process_message(provider, commit.into_protocol_message().unwrap())
+        .process_message(
+            provider,
+            commit.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
         .unwrap();
     println!("Bob finished processesing the commit");
 
@@ -2735,6 +2941,7 @@ fn proposal_application_after_self_was_removed_ref(
         &join_group_config,
         welcome.unwrap().into_welcome().unwrap(),
         Some(ratchet_tree.into()),
+        &AcceptAllCredentials,
     )
     .expect("Error staging welcome.")
     .into_group(provider)
@@ -2777,3 +2984,95 @@ fn proposal_application_after_self_was_removed_ref(
     let bob_next_id = member.credential.serialized_content();
     assert_eq!(bob_next_id, b"Charlie");
 }
+
+// Test that the KEM outputs in Welcome messages and update paths have the
+// length of the ciphersuite's KEM, which is in the kilobytes for the
+// post-quantum KEMs.
+#[openmls_test]
+fn kem_output_lengths() {
+    let alice_provider = provider;
+    let bob_provider = &Provider::default();
+    let enc_length = ciphersuite.hpke_kem_algorithm().enc_length();
+
+    let (mut alice_group, _alice_credential_with_key, alice_signer, _alice_pk) =
+        setup_alice_group(ciphersuite, alice_provider);
+    let (_bob_credential_with_key, bob_kpb, bob_signer, _bob_pk) =
+        setup_client("Bob", ciphersuite, bob_provider);
+
+    let (_commit, welcome, _group_info) = alice_group
+        .add_members(
+            alice_provider,
+            &alice_signer,
+            &[bob_kpb.key_package().clone()],
+        )
+        .expect("Could not add Bob.");
+    alice_group
+        .merge_pending_commit(alice_provider)
+        .expect("error merging pending commit");
+
+    let welcome = welcome.into_welcome().expect("expected a welcome");
+    for secrets in welcome.secrets() {
+        assert_eq!(
+            secrets
+                .encrypted_group_secrets()
+                .kem_output
+                .as_slice()
+                .len(),
+            enc_length
+        );
+    }
+
+    // The Welcome survives a serialization round trip.
+    let welcome = Welcome::tls_deserialize_exact(
+        welcome
+            .tls_serialize_detached()
+            .expect("error serializing the welcome"),
+    )
+    .expect("error deserializing the welcome");
+
+    let mut bob_group = StagedWelcome::new_from_welcome(
+        bob_provider,
+        &MlsGroupJoinConfig::default(),
+        welcome,
+        Some(alice_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
+    )
+    .expect("Error creating staged join from Welcome")
+    .into_group(bob_provider)
+    .expect("Error creating group from staged join");
+
+    // Bob commits with an update path, which Alice has to decrypt.
+    let (commit, _welcome, _group_info) = bob_group
+        .self_update(bob_provider, &bob_signer, LeafNodeParameters::default())
+        .expect("Could not create commit");
+    bob_group
+        .merge_pending_commit(bob_provider)
+        .expect("error merging pending commit");
+
+    let commit = MlsMessageIn::tls_deserialize_exact(
+        commit
+            .tls_serialize_detached()
+            .expect("error serializing the commit"),
+    )
+    .expect("error deserializing the commit");
+    let processed_message = alice_group
+        .process_message(
+            alice_provider,
+            commit.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
+        .expect("error processing the commit");
+    let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
+        processed_message.into_content()
+    else {
+        panic!("Expected a staged commit.");
+    };
+    alice_group
+        .merge_staged_commit(alice_provider, *staged_commit)
+        .expect("error merging staged commit");
+
+    assert_eq!(
+        alice_group.epoch_authenticator(),
+        bob_group.epoch_authenticator()
+    );
+}
@@ -1,7 +1,15 @@
 //! Test and Known Answer Test (KAT) modules for the MLS group.
 
+mod app_ack;
+mod authentication_service;
+mod branch;
+mod commit_builder;
 mod create_commit_params;
+mod custom_proposals;
 mod external_init;
 mod mls_group;
 mod past_secrets;
 mod proposals;
+mod reinit;
+mod retention;
+mod self_remove;
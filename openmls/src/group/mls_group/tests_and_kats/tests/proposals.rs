@@ -1,7 +1,7 @@
 use crate::{
     binary_tree::LeafNodeIndex,
     ciphersuite::hash_ref::ProposalRef,
-    credentials::CredentialType,
+    credentials::{test_utils::AcceptAllCredentials, CredentialType},
     extensions::{Extension, ExtensionType, Extensions, RequiredCapabilitiesExtension},
     framing::{
         mls_auth_content::AuthenticatedContent, sender::Sender, FramingParameters, WireFormat,
@@ -368,6 +368,7 @@ fn group_context_extensions(
         &MlsGroupJoinConfig::default(),
         welcome.into_welcome().unwrap(),
         Some(ratchet_tree.into()),
+        &AcceptAllCredentials,
     )
     .expect("Error joining group.");
 }
@@ -418,6 +419,7 @@ fn group_context_extension_proposal_fails(
         &MlsGroupJoinConfig::default(),
         welcome.into_welcome().unwrap(),
         Some(ratchet_tree.into()),
+        &AcceptAllCredentials,
     )
     .and_then(|staged_join| staged_join.into_group(provider))
     .expect("Error joining group.");
@@ -468,7 +470,11 @@ fn group_context_extension_proposal(
         .expect("Error proposing gce.");
 
     let processed_message = bob_group
-        .process_message(provider, gce_proposal.into_protocol_message().unwrap())
+        .process_message(
+            provider,
+            gce_proposal.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
         .expect("Error processing gce proposal.");
 
     match processed_message.into_content() {
@@ -488,7 +494,11 @@ fn group_context_extension_proposal(
     bob_group.merge_pending_commit(provider).unwrap();
 
     let processed_message = alice_group
-        .process_message(provider, commit.into_protocol_message().unwrap())
+        .process_message(
+            provider,
+            commit.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
         .expect("Error processing commit.");
 
     match processed_message.into_content() {
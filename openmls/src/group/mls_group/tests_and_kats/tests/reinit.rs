@@ -0,0 +1,235 @@
+//! This module contains tests for the reinitialization of an [`MlsGroup`].
+
+use mls_group::tests_and_kats::utils::setup_client;
+use openmls_test::openmls_test;
+
+use crate::{
+    credentials::test_utils::AcceptAllCredentials,
+    framing::*,
+    group::{errors::*, *},
+    key_packages::*,
+    messages::proposals::*,
+    schedule::errors::PskError,
+    versions::ProtocolVersion,
+};
+
+#[openmls_test]
+fn reinit_group() {
+    let alice_provider = provider;
+    let bob_provider = &Provider::default();
+
+    let (alice_credential_with_key, _alice_kpb, alice_signer, _alice_pk) =
+        setup_client("Alice", ciphersuite, alice_provider);
+    let (bob_credential_with_key, bob_kpb, bob_signer, _bob_pk) =
+        setup_client("Bob", ciphersuite, bob_provider);
+
+    // Bob takes the resumption PSK of the last epoch of the old group from its
+    // epoch secrets, so he doesn't need to keep past resumption PSKs.
+    let mls_group_create_config = MlsGroupCreateConfig::builder()
+        .ciphersuite(ciphersuite)
+        .use_ratchet_tree_extension(true)
+        .build();
+
+    // === Alice creates a group and adds Bob ===
+    let mut alice_group = MlsGroup::new(
+        alice_provider,
+        &alice_signer,
+        &mls_group_create_config,
+        alice_credential_with_key.clone(),
+    )
+    .expect("Error creating group.");
+
+    let (_commit, welcome, _group_info) = alice_group
+        .add_members(
+            alice_provider,
+            &alice_signer,
+            &[bob_kpb.key_package().clone()],
+        )
+        .expect("Could not add member to group.");
+    alice_group
+        .merge_pending_commit(alice_provider)
+        .expect("error merging pending commit");
+
+    let mut bob_group = StagedWelcome::new_from_welcome(
+        bob_provider,
+        mls_group_create_config.join_config(),
+        welcome.into_welcome().expect("expected a welcome"),
+        None,
+        &AcceptAllCredentials,
+    )
+    .and_then(|staged_join| staged_join.into_group(bob_provider))
+    .expect("error creating group from welcome");
+
+    // === Alice commits a ReInit proposal ===
+    let new_group_id = GroupId::from_slice(b"Reinitialized Group");
+    let reinit_proposal = ReInitProposal::new(
+        new_group_id.clone(),
+        ProtocolVersion::Mls10,
+        ciphersuite,
+        Extensions::empty(),
+    );
+
+    let (commit, _group_info) = alice_group
+        .reinit(alice_provider, &alice_signer, reinit_proposal.clone())
+        .expect("Could not reinitialize the group.");
+    alice_group
+        .merge_pending_commit(alice_provider)
+        .expect("error merging pending commit");
+    assert_eq!(alice_group.reinit_proposal(), Some(&reinit_proposal));
+
+    let processed_message = bob_group
+        .process_message(
+            bob_provider,
+            commit
+                .into_protocol_message()
+                .expect("Unexpected message type"),
+            &AcceptAllCredentials,
+        )
+        .expect("Could not process commit.");
+    let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
+        processed_message.into_content()
+    else {
+        panic!("Expected a StagedCommit.");
+    };
+    assert_eq!(staged_commit.reinit_proposal(), Some(&reinit_proposal));
+    bob_group
+        .merge_staged_commit(bob_provider, *staged_commit)
+        .expect("error merging staged commit");
+    assert_eq!(bob_group.reinit_proposal(), Some(&reinit_proposal));
+
+    // The old group can't be used anymore
+    let err = bob_group
+        .create_message(bob_provider, &bob_signer, b"Hello Alice")
+        .expect_err("Created a message in a reinitialized group.");
+    assert!(matches!(
+        err,
+        CreateMessageError::GroupStateError(MlsGroupStateError::UseAfterReInit)
+    ));
+
+    // === Alice creates the new group and invites Bob ===
+    let bob_kpb = KeyPackageBundle::generate(
+        bob_provider,
+        &bob_signer,
+        ciphersuite,
+        bob_credential_with_key,
+    );
+    let (mut new_alice_group, welcome, _group_info) = alice_group
+        .create_reinit_group(
+            alice_provider,
+            &alice_signer,
+            mls_group_create_config.join_config(),
+            None,
+            alice_credential_with_key,
+            &[bob_kpb.key_package().clone()],
+        )
+        .expect("Could not create the new group.");
+    assert_eq!(new_alice_group.group_id(), &new_group_id);
+    assert_eq!(new_alice_group.epoch(), GroupEpoch::from(1));
+
+    let mut new_bob_group = StagedWelcome::new_from_welcome(
+        bob_provider,
+        mls_group_create_config.join_config(),
+        welcome.into_welcome().expect("expected a welcome"),
+        None,
+        &AcceptAllCredentials,
+    )
+    .and_then(|staged_join| staged_join.into_group(bob_provider))
+    .expect("error creating group from welcome");
+
+    assert_eq!(
+        new_alice_group.epoch_authenticator(),
+        new_bob_group.epoch_authenticator()
+    );
+
+    // Alice and Bob can communicate in the new group
+    let message = new_alice_group
+        .create_message(alice_provider, &alice_signer, b"Hello Bob")
+        .expect("Could not create message.");
+    let processed_message = new_bob_group
+        .process_message(
+            bob_provider,
+            message
+                .into_protocol_message()
+                .expect("Unexpected message type"),
+            &AcceptAllCredentials,
+        )
+        .expect("Could not process message.");
+    assert!(matches!(
+        processed_message.into_content(),
+        ProcessedMessageContent::ApplicationMessage(_)
+    ));
+}
+
+// Tests that a ReInit supersedes all other pending proposals and that a
+// Welcome into the new group can only be processed by members of the old
+// group.
+#[openmls_test]
+fn reinit_supersedes_proposals_and_requires_old_group() {
+    let alice_provider = provider;
+    let charlie_provider = &Provider::default();
+
+    let (alice_credential_with_key, _alice_kpb, alice_signer, _alice_pk) =
+        setup_client("Alice", ciphersuite, alice_provider);
+    let (_charlie_credential_with_key, charlie_kpb, _charlie_signer, _charlie_pk) =
+        setup_client("Charlie", ciphersuite, charlie_provider);
+
+    let mls_group_create_config = MlsGroupCreateConfig::builder()
+        .ciphersuite(ciphersuite)
+        .use_ratchet_tree_extension(true)
+        .build();
+
+    let mut alice_group = MlsGroup::new(
+        alice_provider,
+        &alice_signer,
+        &mls_group_create_config,
+        alice_credential_with_key.clone(),
+    )
+    .expect("Error creating group.");
+
+    alice_group
+        .propose_add_member(alice_provider, &alice_signer, charlie_kpb.key_package())
+        .expect("Could not create proposal.");
+
+    let reinit_proposal = ReInitProposal::new(
+        GroupId::from_slice(b"Reinitialized Group"),
+        ProtocolVersion::Mls10,
+        ciphersuite,
+        Extensions::empty(),
+    );
+    alice_group
+        .reinit(alice_provider, &alice_signer, reinit_proposal)
+        .expect("Could not reinitialize the group.");
+
+    // Only the ReInit proposal is committed
+    let pending_commit = alice_group.pending_commit().expect("No pending commit.");
+    assert_eq!(pending_commit.queued_proposals().count(), 1);
+    assert!(pending_commit.reinit_proposal().is_some());
+    assert_eq!(pending_commit.add_proposals().count(), 0);
+
+    alice_group
+        .merge_pending_commit(alice_provider)
+        .expect("error merging pending commit");
+
+    // Charlie was never a member of the old group and doesn't have the
+    // resumption PSK.
+    let (_new_alice_group, welcome, _group_info) = alice_group
+        .create_reinit_group(
+            alice_provider,
+            &alice_signer,
+            mls_group_create_config.join_config(),
+            None,
+            alice_credential_with_key,
+            &[charlie_kpb.key_package().clone()],
+        )
+        .expect("Could not create the new group.");
+
+    let err = StagedWelcome::new_from_welcome(
+        charlie_provider,
+        mls_group_create_config.join_config(),
+        welcome.into_welcome().expect("expected a welcome"),
+        None,
+        &AcceptAllCredentials,
+    )
+    .expect_err("Joined the new group without the old group.");
+    assert!(matches!(err, WelcomeError::Psk(PskError::KeyNotFound)));
+}
@@ -0,0 +1,221 @@
+//! This module contains tests regarding the retention of key material from
+//! past epochs.
+
+use std::{cell::Cell, time::Duration};
+
+use openmls_rust_crypto::MemoryStorage;
+use openmls_test::openmls_test;
+use openmls_traits::{
+    clock::Clock,
+    storage::{StorageProvider, CURRENT_VERSION},
+    OpenMlsProvider,
+};
+
+use crate::{
+    group::{mls_group::tests_and_kats::utils::setup_client, GroupEpoch, MlsGroup, RetainedEpoch},
+    treesync::LeafNodeParameters,
+};
+
+/// A clock that only moves when the test advances it.
+#[derive(Default)]
+struct TestClock(Cell<u64>);
+
+impl TestClock {
+    fn advance(&self, seconds: u64) {
+        self.0.set(self.0.get() + seconds);
+    }
+}
+
+impl Clock for TestClock {
+    fn now(&self) -> u64 {
+        self.0.get()
+    }
+}
+
+/// A provider that reads the time from a [`TestClock`].
+struct ClockProvider<'a, P: OpenMlsProvider> {
+    provider: &'a P,
+    clock: TestClock,
+}
+
+impl<P: OpenMlsProvider> OpenMlsProvider for ClockProvider<'_, P> {
+    type CryptoProvider = P::CryptoProvider;
+    type RandProvider = P::RandProvider;
+    type StorageProvider = P::StorageProvider;
+
+    fn storage(&self) -> &Self::StorageProvider {
+        self.provider.storage()
+    }
+
+    fn crypto(&self) -> &Self::CryptoProvider {
+        self.provider.crypto()
+    }
+
+    fn rand(&self) -> &Self::RandProvider {
+        self.provider.rand()
+    }
+
+    fn clock(&self) -> &dyn Clock {
+        &self.clock
+    }
+}
+
+fn retained(epoch: u64, retired_at: Option<u64>) -> RetainedEpoch {
+    RetainedEpoch {
+        epoch: GroupEpoch::from(epoch),
+        retired_at,
+    }
+}
+
+// Test that message secrets and resumption PSKs of past epochs are deleted
+// once their epoch is older than the maximum age, both when a commit is merged
+// and when the retention policy is applied, and that stale epoch key pairs
+// are deleted.
+#[openmls_test]
+fn retention_policy() {
+    let clock_provider = ClockProvider {
+        provider,
+        clock: TestClock::default(),
+    };
+    let (alice_credential_with_key, _alice_kpb, alice_signer, _alice_pk) =
+        setup_client("Alice", ciphersuite, provider);
+
+    let mut alice_group = MlsGroup::builder()
+        .ciphersuite(ciphersuite)
+        .max_past_epochs(5)
+        .max_past_epoch_age(Duration::from_secs(60))
+        .build(&clock_provider, &alice_signer, alice_credential_with_key)
+        .expect("error creating group");
+    let group_id = alice_group.group_id().clone();
+
+    // Advance three epochs, 20 seconds apart.
+    for _ in 0..3 {
+        clock_provider.clock.advance(20);
+        alice_group
+            .self_update(
+                &clock_provider,
+                &alice_signer,
+                LeafNodeParameters::default(),
+            )
+            .expect("error creating self update");
+        alice_group
+            .merge_pending_commit(&clock_provider)
+            .expect("error merging pending commit");
+    }
+
+    let retained_key_material = alice_group
+        .retained_key_material(provider.storage())
+        .unwrap();
+    assert_eq!(retained_key_material.current_epoch, GroupEpoch::from(3));
+    assert_eq!(
+        retained_key_material.message_secrets,
+        vec![
+            retained(0, Some(20)),
+            retained(1, Some(40)),
+            retained(2, Some(60))
+        ]
+    );
+    assert_eq!(
+        retained_key_material.resumption_psks,
+        vec![
+            retained(0, Some(20)),
+            retained(1, Some(40)),
+            retained(2, Some(60)),
+            retained(3, None)
+        ]
+    );
+    assert_eq!(
+        retained_key_material.epoch_key_pairs,
+        vec![GroupEpoch::from(3)]
+    );
+    assert_eq!(
+        retained_key_material.oldest_past_epoch(),
+        Some(GroupEpoch::from(0))
+    );
+
+    // Leave the key pairs of an earlier epoch behind in the storage.
+    let key_pairs = alice_group.read_epoch_keypairs(provider.storage());
+    <MemoryStorage as StorageProvider<CURRENT_VERSION>>::write_encryption_epoch_key_pairs(
+        provider.storage(),
+        &group_id,
+        &GroupEpoch::from(1),
+        alice_group.own_leaf_index().u32(),
+        &key_pairs,
+    )
+    .unwrap();
+
+    // Without a new commit, epoch 0 becomes too old and is only deleted when
+    // the retention policy is applied.
+    clock_provider.clock.advance(30);
+    alice_group
+        .apply_retention_policy(&clock_provider)
+        .expect("error applying the retention policy");
+
+    let retained_key_material = alice_group
+        .retained_key_material(provider.storage())
+        .unwrap();
+    assert_eq!(
+        retained_key_material.message_secrets,
+        vec![retained(1, Some(40)), retained(2, Some(60))]
+    );
+    assert_eq!(
+        retained_key_material.resumption_psks,
+        vec![
+            retained(1, Some(40)),
+            retained(2, Some(60)),
+            retained(3, None)
+        ]
+    );
+    assert_eq!(
+        retained_key_material.epoch_key_pairs,
+        vec![GroupEpoch::from(3)]
+    );
+    assert_eq!(
+        retained_key_material.oldest_past_epoch(),
+        Some(GroupEpoch::from(1))
+    );
+    assert!(alice_group
+        .message_secrets_store
+        .secrets_for_epoch(0)
+        .is_none());
+
+    // The deletion was written to the storage.
+    let alice_group_loaded = MlsGroup::load(provider.storage(), &group_id)
+        .expect("error loading group")
+        .expect("group not stored");
+    assert_eq!(alice_group_loaded, alice_group);
+
+    // Merging a commit deletes the epochs that became too old in the meantime.
+    clock_provider.clock.advance(100);
+    alice_group
+        .self_update(
+            &clock_provider,
+            &alice_signer,
+            LeafNodeParameters::default(),
+        )
+        .expect("error creating self update");
+    alice_group
+        .merge_pending_commit(&clock_provider)
+        .expect("error merging pending commit");
+
+    let retained_key_material = alice_group
+        .retained_key_material(provider.storage())
+        .unwrap();
+    assert_eq!(
+        retained_key_material.message_secrets,
+        vec![retained(3, Some(190))]
+    );
+    assert_eq!(
+        retained_key_material.resumption_psks,
+        vec![retained(3, Some(190)), retained(4, None)]
+    );
+    assert_eq!(
+        retained_key_material.oldest_past_epoch(),
+        Some(GroupEpoch::from(3))
+    );
+
+    let alice_group_loaded = MlsGroup::load(provider.storage(), &group_id)
+        .expect("error loading group")
+        .expect("group not stored");
+    assert_eq!(alice_group_loaded, alice_group);
+}
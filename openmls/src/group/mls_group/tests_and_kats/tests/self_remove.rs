@@ -0,0 +1,273 @@
+//! This module contains tests for leaving a group with SelfRemove proposals.
+
+use mls_group::tests_and_kats::utils::setup_client;
+use openmls_basic_credential::SignatureKeyPair;
+use openmls_test::openmls_test;
+use openmls_traits::{types::Ciphersuite, OpenMlsProvider as _};
+
+use crate::{
+    credentials::test_utils::AcceptAllCredentials,
+    framing::*,
+    group::{errors::*, *},
+    key_packages::*,
+    messages::proposals::*,
+    storage::OpenMlsProvider,
+    treesync::node::leaf_node::Capabilities,
+};
+
+// Creates a group of Alice, Bob and Charlie. All members support SelfRemove
+// proposals if `self_remove_support` is set.
+fn setup_group<Provider: OpenMlsProvider>(
+    ciphersuite: Ciphersuite,
+    providers: [&Provider; 3],
+    self_remove_support: bool,
+) -> Vec<(MlsGroup, SignatureKeyPair)> {
+    let [alice_provider, bob_provider, charlie_provider] = providers;
+    let (alice_credential_with_key, _alice_kpb, alice_signer, _alice_pk) =
+        setup_client("Alice", ciphersuite, alice_provider);
+
+    let capabilities = if self_remove_support {
+        Capabilities::new(None, None, None, Some(&[ProposalType::SelfRemove]), None)
+    } else {
+        Capabilities::default()
+    };
+
+    let mut alice_group = MlsGroup::builder()
+        .ciphersuite(ciphersuite)
+        .with_capabilities(capabilities.clone())
+        .use_ratchet_tree_extension(true)
+        .build(alice_provider, &alice_signer, alice_credential_with_key)
+        .expect("Error creating group.");
+
+    let mut signers = vec![];
+    let mut key_packages = vec![];
+    for (name, provider) in [("Bob", bob_provider), ("Charlie", charlie_provider)] {
+        let (credential_with_key, _kpb, signer, _pk) = setup_client(name, ciphersuite, provider);
+        let kpb = KeyPackage::builder()
+            .leaf_node_capabilities(capabilities.clone())
+            .build(ciphersuite, provider, &signer, credential_with_key)
+            .expect("Could not create key package.");
+        signers.push(signer);
+        key_packages.push(kpb.key_package().clone());
+    }
+
+    let (_commit, welcome, _group_info) = alice_group
+        .add_members(alice_provider, &alice_signer, &key_packages)
+        .expect("Could not add members to group.");
+    alice_group
+        .merge_pending_commit(alice_provider)
+        .expect("error merging pending commit");
+    let welcome = welcome.into_welcome().expect("expected a welcome");
+
+    let mut groups = vec![(alice_group, alice_signer)];
+    for (provider, signer) in [bob_provider, charlie_provider].into_iter().zip(signers) {
+        let group = StagedWelcome::new_from_welcome(
+            provider,
+            &MlsGroupJoinConfig::default(),
+            welcome.clone(),
+            None,
+            &AcceptAllCredentials,
+        )
+        .and_then(|staged_join| staged_join.into_group(provider))
+        .expect("error creating group from welcome");
+        groups.push((group, signer));
+    }
+
+    groups
+}
+
+#[openmls_test]
+fn self_remove() {
+    let alice_provider = provider;
+    let bob_provider = &Provider::default();
+    let charlie_provider = &Provider::default();
+
+    let mut groups = setup_group(
+        ciphersuite,
+        [alice_provider, bob_provider, charlie_provider],
+        true,
+    );
+    let (mut charlie_group, charlie_signer) = groups.pop().unwrap();
+    let (mut bob_group, bob_signer) = groups.pop().unwrap();
+    let (mut alice_group, _alice_signer) = groups.pop().unwrap();
+    let bob_index = bob_group.own_leaf_index();
+
+    // === Bob leaves the group ===
+    let proposal = bob_group
+        .leave_group_via_self_remove(bob_provider, &bob_signer)
+        .expect("Could not create SelfRemove proposal.")
+        .into_protocol_message()
+        .expect("Unexpected message type");
+
+    for (group, provider) in [
+        (&mut alice_group, alice_provider),
+        (&mut charlie_group, charlie_provider),
+    ] {
+        let processed_message = group
+            .process_message(provider, proposal.clone(), &AcceptAllCredentials)
+            .expect("Could not process proposal.");
+        let ProcessedMessageContent::ProposalMessage(queued_proposal) =
+            processed_message.into_content()
+        else {
+            panic!("Expected a proposal.");
+        };
+        assert!(matches!(
+            queued_proposal.proposal(),
+            Proposal::SelfRemove(_)
+        ));
+        group
+            .store_pending_proposal(provider.storage(), *queued_proposal)
+            .expect("Could not store proposal.");
+    }
+
+    // === Charlie commits the SelfRemove proposal ===
+    let (commit, _welcome, _group_info) = charlie_group
+        .commit_to_pending_proposals(charlie_provider, &charlie_signer)
+        .expect("Could not commit to pending proposals.");
+    charlie_group
+        .merge_pending_commit(charlie_provider)
+        .expect("error merging pending commit");
+    let commit = commit
+        .into_protocol_message()
+        .expect("Unexpected message type");
+
+    // Alice learns that Bob left
+    let processed_message = alice_group
+        .process_message(alice_provider, commit.clone(), &AcceptAllCredentials)
+        .expect("Could not process commit.");
+    let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
+        processed_message.into_content()
+    else {
+        panic!("Expected a StagedCommit.");
+    };
+    let self_remove_proposals: Vec<_> = staged_commit.self_remove_proposals().collect();
+    assert_eq!(self_remove_proposals.len(), 1);
+    let remove_operation = RemoveOperation::from_self_remove(
+        staged_commit.self_remove_proposals().next().unwrap(),
+        &alice_group,
+    )
+    .expect("Could not create RemoveOperation.");
+    assert!(matches!(remove_operation, RemoveOperation::TheyLeft(index) if index == bob_index));
+    alice_group
+        .merge_staged_commit(alice_provider, *staged_commit)
+        .expect("error merging staged commit");
+
+    // Bob learns that the SelfRemove was committed
+    let processed_message = bob_group
+        .process_message(bob_provider, commit, &AcceptAllCredentials)
+        .expect("Could not process commit.");
+    let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
+        processed_message.into_content()
+    else {
+        panic!("Expected a StagedCommit.");
+    };
+    assert!(staged_commit.self_removed());
+    let remove_operation = RemoveOperation::from_self_remove(
+        staged_commit.self_remove_proposals().next().unwrap(),
+        &bob_group,
+    )
+    .expect("Could not create RemoveOperation.");
+    assert!(matches!(remove_operation, RemoveOperation::WeLeft));
+    bob_group
+        .merge_staged_commit(bob_provider, *staged_commit)
+        .expect("error merging staged commit");
+    assert!(!bob_group.is_active());
+
+    assert_eq!(alice_group.members().count(), 2);
+    assert!(alice_group.member(bob_index).is_none());
+    assert_eq!(
+        alice_group.epoch_authenticator(),
+        charlie_group.epoch_authenticator()
+    );
+}
+
+// A Commit that only covers SelfRemove proposals doesn't need a path.
+#[openmls_test]
+fn self_remove_without_path() {
+    let alice_provider = provider;
+    let bob_provider = &Provider::default();
+    let charlie_provider = &Provider::default();
+
+    let mut groups = setup_group(
+        ciphersuite,
+        [alice_provider, bob_provider, charlie_provider],
+        true,
+    );
+    let (mut charlie_group, charlie_signer) = groups.pop().unwrap();
+    let (mut bob_group, bob_signer) = groups.pop().unwrap();
+    let (mut alice_group, _alice_signer) = groups.pop().unwrap();
+    let bob_index = bob_group.own_leaf_index();
+
+    let proposal = bob_group
+        .leave_group_via_self_remove(bob_provider, &bob_signer)
+        .expect("Could not create SelfRemove proposal.")
+        .into_protocol_message()
+        .expect("Unexpected message type");
+    for (group, provider) in [
+        (&mut alice_group, alice_provider),
+        (&mut charlie_group, charlie_provider),
+    ] {
+        let processed_message = group
+            .process_message(provider, proposal.clone(), &AcceptAllCredentials)
+            .expect("Could not process proposal.");
+        let ProcessedMessageContent::ProposalMessage(queued_proposal) =
+            processed_message.into_content()
+        else {
+            panic!("Expected a proposal.");
+        };
+        group
+            .store_pending_proposal(provider.storage(), *queued_proposal)
+            .expect("Could not store proposal.");
+    }
+
+    // === Charlie commits the SelfRemove proposal without a path ===
+    let (commit, _welcome, _group_info) = charlie_group
+        .commit_builder()
+        .force_self_update(false)
+        .build(charlie_provider, &charlie_signer)
+        .expect("Could not commit to pending proposals.");
+    charlie_group
+        .merge_pending_commit(charlie_provider)
+        .expect("error merging pending commit");
+    let commit = commit
+        .into_protocol_message()
+        .expect("Unexpected message type");
+
+    let processed_message = alice_group
+        .process_message(alice_provider, commit, &AcceptAllCredentials)
+        .expect("Could not process commit.");
+    let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
+        processed_message.into_content()
+    else {
+        panic!("Expected a StagedCommit.");
+    };
+    assert!(staged_commit.update_path_leaf_node().is_none());
+    alice_group
+        .merge_staged_commit(alice_provider, *staged_commit)
+        .expect("error merging staged commit");
+
+    assert!(alice_group.member(bob_index).is_none());
+    assert_eq!(
+        alice_group.epoch_authenticator(),
+        charlie_group.epoch_authenticator()
+    );
+}
+
+#[openmls_test]
+fn self_remove_not_supported() {
+    let alice_provider = provider;
+    let bob_provider = &Provider::default();
+    let charlie_provider = &Provider::default();
+
+    let mut groups = setup_group(
+        ciphersuite,
+        [alice_provider, bob_provider, charlie_provider],
+        false,
+    );
+    let (mut bob_group, bob_signer) = groups.swap_remove(1);
+
+    let err = bob_group
+        .leave_group_via_self_remove(bob_provider, &bob_signer)
+        .expect_err("Created SelfRemove proposal in a group that doesn't support it.");
+    assert!(matches!(err, LeaveGroupError::SelfRemoveNotSupported));
+}
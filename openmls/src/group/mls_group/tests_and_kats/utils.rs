@@ -1,5 +1,6 @@
 //! Test utilities for (MLS group) tests.
 
+use crate::credentials::test_utils::AcceptAllCredentials;
 use openmls_basic_credential::SignatureKeyPair;
 use openmls_traits::types::HpkeCiphertext;
 
@@ -148,6 +149,7 @@ pub(crate) fn setup_alice_bob_group<Provider: OpenMlsProvider>(
             .build(),
         welcome.into_welcome().unwrap(),
         Some(group_alice.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .and_then(|staged_join| staged_join.into_group(provider))
     .expect("error creating group from welcome");
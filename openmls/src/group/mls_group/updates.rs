@@ -2,7 +2,9 @@ use errors::{ProposeSelfUpdateError, SelfUpdateError};
 use openmls_traits::{signatures::Signer, storage::StorageProvider as _};
 
 use crate::{
-    messages::group_info::GroupInfo, storage::OpenMlsProvider, treesync::LeafNodeParameters,
+    messages::group_info::GroupInfo,
+    storage::{transaction, OpenMlsProvider},
+    treesync::LeafNodeParameters,
 };
 
 use super::*;
@@ -34,38 +36,47 @@ impl MlsGroup {
     > {
         self.is_operational()?;
 
-        let params = CreateCommitParams::builder()
-            .framing_parameters(self.framing_parameters())
-            .leaf_node_parameters(leaf_node_parameters)
-            .build();
-        // Create Commit over all proposals.
-        // TODO #751
-        let create_commit_result = self.create_commit(params, provider, signer)?;
-
-        // Convert PublicMessage messages to MLSMessage and encrypt them if required by
-        // the configuration
-        let mls_message = self.content_to_mls_message(create_commit_result.commit, provider)?;
-
-        // Set the current group state to [`MlsGroupState::PendingCommit`],
-        // storing the current [`StagedCommit`] from the commit results
-        self.group_state = MlsGroupState::PendingCommit(Box::new(PendingCommitState::Member(
-            create_commit_result.staged_commit,
-        )));
-
-        provider
-            .storage()
-            .write_group_state(self.group_id(), &self.group_state)
-            .map_err(SelfUpdateError::StorageError)?;
-        self.store(provider.storage())
-            .map_err(SelfUpdateError::StorageError)?;
+        let (mls_message, welcome_option, group_info) =
+            transaction(provider.storage(), SelfUpdateError::StorageError, || {
+                let params = CreateCommitParams::builder()
+                    .framing_parameters(self.framing_parameters())
+                    .leaf_node_parameters(leaf_node_parameters)
+                    .build();
+
+                // Create Commit over all proposals.
+                // TODO #751
+                let create_commit_result = self.create_commit(params, provider, signer)?;
+
+                // Convert PublicMessage messages to MLSMessage and encrypt them if required by
+                // the configuration
+                let mls_message =
+                    self.content_to_mls_message(create_commit_result.commit, provider)?;
+
+                // Set the current group state to [`MlsGroupState::PendingCommit`],
+                // storing the current [`StagedCommit`] from the commit results
+                self.group_state = MlsGroupState::PendingCommit(Box::new(
+                    PendingCommitState::Member(create_commit_result.staged_commit),
+                ));
+
+                provider
+                    .storage()
+                    .write_group_state(self.group_id(), &self.group_state)
+                    .map_err(SelfUpdateError::StorageError)?;
+                self.store_own_state(provider.storage())
+                    .map_err(SelfUpdateError::StorageError)?;
+
+                Ok((
+                    mls_message,
+                    create_commit_result.welcome_option,
+                    create_commit_result.group_info,
+                ))
+            })?;
 
         self.reset_aad();
         Ok((
             mls_message,
-            create_commit_result
-                .welcome_option
-                .map(|w| MlsMessageOut::from_welcome(w, self.version())),
-            create_commit_result.group_info,
+            welcome_option.map(|w| MlsMessageOut::from_welcome(w, self.version())),
+            group_info,
         ))
     }
 
@@ -23,10 +23,13 @@ pub(crate) mod public_group;
 // Public
 pub use errors::*;
 pub use group_context::GroupContext;
+pub use mls_group::async_group::*;
+pub use mls_group::commit_builder::*;
 pub use mls_group::config::*;
 pub use mls_group::membership::*;
 pub use mls_group::proposal_store::*;
-pub use mls_group::staged_commit::StagedCommit;
+pub use mls_group::retention::{RetainedEpoch, RetainedKeyMaterial};
+pub use mls_group::staged_commit::{CredentialChange, StagedCommit};
 pub use mls_group::{Member, *};
 pub use public_group::*;
 
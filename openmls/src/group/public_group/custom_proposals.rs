@@ -0,0 +1,219 @@
+//! # Custom proposal handlers
+//!
+//! Applications can attach semantics to their own proposal types by
+//! registering a [`CustomProposalHandler`] with a [`PublicGroup`] or an
+//! [`MlsGroup`]. The handler validates custom proposals of its type whenever a
+//! Commit covering them is created or staged, and applies them when the Commit
+//! is merged. This allows applications to carry group policy changes as
+//! proposals that are committed atomically with membership changes.
+//!
+//! Handlers are not persisted and need to be registered again after a group
+//! was loaded from storage.
+//!
+//! [`MlsGroup`]: crate::group::MlsGroup
+
+use std::{collections::BTreeMap, fmt::Debug, sync::Arc};
+
+use tls_codec::DeserializeBytes;
+
+use super::PublicGroup;
+use crate::{
+    error::LibraryError,
+    framing::Sender,
+    group::{errors::ProposalValidationError, proposal_store::ProposalQueue, GroupContext},
+    messages::proposals::{Proposal, ProposalType},
+};
+
+/// A handler for an application-defined proposal type.
+///
+/// The payload of a [`CustomProposal`] of the handled type is decoded into
+/// [`CustomProposalHandler::Proposal`] before it is passed to the handler.
+///
+/// [`CustomProposal`]: crate::messages::proposals::CustomProposal
+pub trait CustomProposalHandler: Send + Sync + 'static {
+    /// The decoded payload of the custom proposal.
+    type Proposal: DeserializeBytes;
+
+    /// Returns the proposal type handled by this handler.
+    fn proposal_type(&self) -> u16;
+
+    /// Validates a custom proposal sent by `sender` against the current
+    /// `group_context`. Returning an error rejects the Commit covering the
+    /// proposal.
+    fn validate(
+        &self,
+        proposal: &Self::Proposal,
+        sender: &Sender,
+        group_context: &GroupContext,
+    ) -> Result<(), String>;
+
+    /// Applies a custom proposal sent by `sender` after the Commit covering it
+    /// was merged. `group_context` is the context of the new epoch.
+    fn apply(&self, proposal: &Self::Proposal, sender: &Sender, group_context: &GroupContext);
+}
+
+/// Object-safe version of [`CustomProposalHandler`] that decodes the payload.
+trait ErasedCustomProposalHandler: Send + Sync {
+    fn validate(
+        &self,
+        payload: &[u8],
+        sender: &Sender,
+        group_context: &GroupContext,
+    ) -> Result<(), ProposalValidationError>;
+
+    fn apply(
+        &self,
+        payload: &[u8],
+        sender: &Sender,
+        group_context: &GroupContext,
+    ) -> Result<(), LibraryError>;
+}
+
+impl<Handler: CustomProposalHandler> ErasedCustomProposalHandler for Handler {
+    fn validate(
+        &self,
+        payload: &[u8],
+        sender: &Sender,
+        group_context: &GroupContext,
+    ) -> Result<(), ProposalValidationError> {
+        let proposal = Handler::Proposal::tls_deserialize_exact_bytes(payload)
+            .map_err(|_| ProposalValidationError::MalformedCustomProposal)?;
+        CustomProposalHandler::validate(self, &proposal, sender, group_context)
+            .map_err(ProposalValidationError::CustomProposalRejected)
+    }
+
+    fn apply(
+        &self,
+        payload: &[u8],
+        sender: &Sender,
+        group_context: &GroupContext,
+    ) -> Result<(), LibraryError> {
+        // The payload was decoded successfully during validation.
+        let proposal = Handler::Proposal::tls_deserialize_exact_bytes(payload)
+            .map_err(|_| LibraryError::custom("Custom proposal was not validated"))?;
+        CustomProposalHandler::apply(self, &proposal, sender, group_context);
+        Ok(())
+    }
+}
+
+/// The registered [`CustomProposalHandler`]s of a group, indexed by proposal
+/// type.
+#[derive(Default, Clone)]
+pub(crate) struct CustomProposalHandlers {
+    handlers: BTreeMap<u16, Arc<dyn ErasedCustomProposalHandler>>,
+}
+
+impl CustomProposalHandlers {
+    fn get(&self, proposal_type: u16) -> Option<&dyn ErasedCustomProposalHandler> {
+        self.handlers.get(&proposal_type).map(|handler| &**handler)
+    }
+}
+
+impl Debug for CustomProposalHandlers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.handlers.keys()).finish()
+    }
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+impl PartialEq for CustomProposalHandlers {
+    fn eq(&self, other: &Self) -> bool {
+        self.handlers.keys().eq(other.handlers.keys())
+    }
+}
+
+impl PublicGroup {
+    /// Registers a [`CustomProposalHandler`] for the proposal type returned by
+    /// [`CustomProposalHandler::proposal_type()`]. A previously registered
+    /// handler for the same proposal type is replaced.
+    ///
+    /// Handlers are not persisted and need to be registered again after the
+    /// group was loaded from storage.
+    pub fn register_custom_proposal_handler(&mut self, handler: impl CustomProposalHandler) {
+        self.custom_proposal_handlers
+            .handlers
+            .insert(handler.proposal_type(), Arc::new(handler));
+    }
+
+    /// Returns the registered [`CustomProposalHandler`]s, e.g. to keep them
+    /// when the group is reloaded.
+    pub(crate) fn custom_proposal_handlers(&self) -> &CustomProposalHandlers {
+        &self.custom_proposal_handlers
+    }
+
+    /// Replaces the registered [`CustomProposalHandler`]s.
+    pub(crate) fn set_custom_proposal_handlers(&mut self, handlers: CustomProposalHandlers) {
+        self.custom_proposal_handlers = handlers;
+    }
+
+    /// Validate the custom proposals that have a registered
+    /// [`CustomProposalHandler`]. This function checks that
+    ///  - the proposal type is required by the group's
+    ///    [`RequiredCapabilitiesExtension`], so that all members support it,
+    ///  - the payload can be decoded,
+    ///  - the handler accepts the proposal.
+    ///
+    /// [`RequiredCapabilitiesExtension`]: crate::extensions::RequiredCapabilitiesExtension
+    pub(crate) fn validate_custom_proposals(
+        &self,
+        proposal_queue: &ProposalQueue,
+    ) -> Result<(), ProposalValidationError> {
+        for queued_proposal in proposal_queue.queued_proposals() {
+            let Proposal::Custom(custom_proposal) = queued_proposal.proposal() else {
+                continue;
+            };
+            let Some(handler) = self
+                .custom_proposal_handlers
+                .get(custom_proposal.proposal_type())
+            else {
+                continue;
+            };
+
+            let proposal_type = ProposalType::Custom(custom_proposal.proposal_type());
+            let is_required = self
+                .required_capabilities()
+                .is_some_and(|required_capabilities| {
+                    required_capabilities
+                        .proposal_types()
+                        .contains(&proposal_type)
+                });
+            if !is_required {
+                return Err(ProposalValidationError::CustomProposalNotRequired);
+            }
+
+            handler.validate(
+                custom_proposal.payload(),
+                queued_proposal.sender(),
+                self.group_context(),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Applies the custom proposals that have a registered
+    /// [`CustomProposalHandler`] in the order of the Commit. This must be
+    /// called after the Commit was merged.
+    pub(crate) fn apply_custom_proposals(
+        &self,
+        proposal_queue: &ProposalQueue,
+    ) -> Result<(), LibraryError> {
+        for queued_proposal in proposal_queue.queued_proposals() {
+            let Proposal::Custom(custom_proposal) = queued_proposal.proposal() else {
+                continue;
+            };
+            if let Some(handler) = self
+                .custom_proposal_handlers
+                .get(custom_proposal.proposal_type())
+            {
+                handler.apply(
+                    custom_proposal.payload(),
+                    queued_proposal.sender(),
+                    self.group_context(),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -15,7 +15,7 @@ use crate::{
     error::LibraryError,
     extensions::Extensions,
     framing::{mls_auth_content::AuthenticatedContent, public_message::InterimTranscriptHashInput},
-    group::GroupContext,
+    group::{GroupContext, Member},
     messages::{proposals::AddProposal, ConfirmationTag, EncryptedGroupSecrets},
     schedule::{psk::PreSharedKeyId, CommitSecret, JoinerSecret},
     treesync::{
@@ -153,6 +153,22 @@ impl<'a> PublicGroupDiff<'a> {
         self.diff.leaf(index)
     }
 
+    /// Returns the members of the group resulting from merging this diff.
+    pub(crate) fn members(&self) -> impl Iterator<Item = Member> + '_ {
+        (0..self.diff.leaf_count())
+            .map(LeafNodeIndex::new)
+            .filter_map(|index| {
+                self.leaf(index).map(|leaf_node| {
+                    Member::new(
+                        index,
+                        leaf_node.encryption_key().as_slice().to_vec(),
+                        leaf_node.signature_key().as_slice().to_vec(),
+                        leaf_node.credential().clone(),
+                    )
+                })
+            })
+    }
+
     /// Set the given path as the direct path of the `sender_leaf_index` and
     /// replace the [`LeafNode`] in the corresponding leaf with the given one.
     ///
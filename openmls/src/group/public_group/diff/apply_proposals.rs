@@ -46,7 +46,7 @@ impl ApplyProposalsValues {
 ///
 /// If an `own_leaf_index` is provided, `self_removed` in the returned
 /// `ApplyProposalValues` is set to `true` if that leaf index is targeted by a
-/// Remove proposal.
+/// Remove proposal or sent a SelfRemove proposal.
 ///
 /// Returns an error if the proposals have not been validated before.
 impl<'a> PublicGroupDiff<'a> {
@@ -106,6 +106,20 @@ impl<'a> PublicGroupDiff<'a> {
             }
         }
 
+        // Process self removes
+        for queued_proposal in proposal_queue.filtered_by_type(ProposalType::SelfRemove) {
+            // Only members can send SelfRemove proposals
+            let Sender::Member(removed) = *queued_proposal.sender() else {
+                // This should not happen with validated proposals
+                return Err(LibraryError::custom("SelfRemove proposal from non-member"));
+            };
+            if own_leaf_index == Some(removed) {
+                self_removed = true
+            }
+            // Blank the direct path of the leaving member
+            self.diff.blank_leaf(removed);
+        }
+
         // Process adds
         let add_proposals = proposal_queue
             .filtered_by_type(ProposalType::Add)
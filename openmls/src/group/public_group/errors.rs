@@ -30,6 +30,10 @@ pub enum CreationFromExternalError<StorageError> {
     /// See [`LeafNodeValidationError`]
     #[error(transparent)]
     LeafNodeValidation(#[from] LeafNodeValidationError),
+    /// The [`AuthenticationService`](crate::credentials::AuthenticationService)
+    /// rejected a credential in the ratchet tree or the group context.
+    #[error("The authentication service rejected a credential: {0}")]
+    CredentialRejected(String),
     /// Error writing to storage
     #[error("Error writing to storage: {0}")]
     WriteToStorageError(StorageError),
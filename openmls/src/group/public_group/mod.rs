@@ -18,6 +18,7 @@ use openmls_traits::{crypto::OpenMlsCrypto, types::Ciphersuite};
 use serde::{Deserialize, Serialize};
 
 use self::{
+    custom_proposals::CustomProposalHandlers,
     diff::{PublicGroupDiff, StagedPublicGroupDiff},
     errors::CreationFromExternalError,
 };
@@ -30,6 +31,10 @@ use crate::treesync::{node::parent_node::PlainUpdatePathNode, treekem::UpdatePat
 use crate::{
     binary_tree::{array_representation::TreeSize, LeafNodeIndex},
     ciphersuite::{hash_ref::ProposalRef, signable::Verifiable},
+    credentials::{
+        authentication::{authenticate_external_senders, authenticate_leaf_node},
+        AuthenticationService, CredentialSource,
+    },
     error::LibraryError,
     extensions::RequiredCapabilitiesExtension,
     framing::InterimTranscriptHashInput,
@@ -39,14 +44,14 @@ use crate::{
         ConfirmationTag, PathSecret,
     },
     schedule::CommitSecret,
-    storage::PublicStorageProvider,
+    storage::{transaction, PublicStorageProvider},
     treesync::{
         errors::{DerivePathError, TreeSyncFromNodesError},
         node::{
             encryption_keys::{EncryptionKey, EncryptionKeyPair},
             leaf_node::LeafNode,
         },
-        RatchetTree, RatchetTreeIn, TreeSync,
+        RatchetTree, RatchetTreeIn, StoredTreeSync, TreeSync,
     },
     versions::ProtocolVersion,
 };
@@ -54,6 +59,7 @@ use crate::{
 use crate::{framing::PublicMessage, group::MlsGroup};
 
 pub(crate) mod builder;
+mod custom_proposals;
 pub(crate) mod diff;
 pub mod errors;
 pub mod process;
@@ -62,6 +68,8 @@ pub(crate) mod staged_commit;
 mod tests;
 mod validation;
 
+pub use custom_proposals::CustomProposalHandler;
+
 /// This struct holds all public values of an MLS group.
 #[derive(Debug)]
 #[cfg_attr(any(test, feature = "test-utils"), derive(PartialEq, Clone))]
@@ -72,6 +80,8 @@ pub struct PublicGroup {
     interim_transcript_hash: Vec<u8>,
     // Most recent confirmation tag. Kept here for verification purposes.
     confirmation_tag: ConfirmationTag,
+    // Handlers registered by the application. These are not persisted.
+    custom_proposal_handlers: CustomProposalHandlers,
 }
 
 /// This is a wrapper type, because we can't implement the storage traits on `Vec<u8>`.
@@ -103,6 +113,7 @@ impl PublicGroup {
             group_context,
             interim_transcript_hash,
             confirmation_tag: initial_confirmation_tag,
+            custom_proposal_handlers: CustomProposalHandlers::default(),
         })
     }
 
@@ -110,13 +121,15 @@ impl PublicGroup {
     ///
     /// This function performs basic validation checks and returns an error if
     /// one of the checks fails. See [`CreationFromExternalError`] for more
-    /// details.
+    /// details. The credentials of all members and external senders are
+    /// authenticated with the `authentication_service`.
     pub fn from_external<StorageProvider: PublicStorageProvider>(
         crypto: &impl OpenMlsCrypto,
         storage: &StorageProvider,
         ratchet_tree: RatchetTreeIn,
         verifiable_group_info: VerifiableGroupInfo,
         proposal_store: ProposalStore,
+        authentication_service: &impl AuthenticationService,
     ) -> Result<(Self, GroupInfo), CreationFromExternalError<StorageProvider::PublicError>> {
         let ciphersuite = verifiable_group_info.ciphersuite();
 
@@ -180,6 +193,7 @@ impl PublicGroup {
             interim_transcript_hash,
             confirmation_tag: group_info.confirmation_tag().clone(),
             proposal_store,
+            custom_proposal_handlers: CustomProposalHandlers::default(),
         };
 
         // Fully check that the leaf nodes in the ratchet tree are valid
@@ -188,6 +202,21 @@ impl PublicGroup {
             .full_leaves()
             .try_for_each(|leaf_node| public_group.validate_leaf_node(leaf_node))?;
 
+        // Authenticate the credentials of all members and external senders
+        for (leaf_index, leaf_node) in public_group.treesync.full_leaves_indexed() {
+            authenticate_leaf_node(
+                authentication_service,
+                leaf_node,
+                CredentialSource::RatchetTree(leaf_index),
+            )
+            .map_err(CreationFromExternalError::CredentialRejected)?;
+        }
+        authenticate_external_senders(
+            authentication_service,
+            public_group.group_context.extensions().iter(),
+        )
+        .map_err(CreationFromExternalError::CredentialRejected)?;
+
         public_group
             .store(storage)
             .map_err(CreationFromExternalError::WriteToStorageError)?;
@@ -256,11 +285,15 @@ impl PublicGroup {
     ///
     /// **NOTE:** The caller must ensure that the group context in the `diff` is
     ///           updated before calling this function with `update_group_context`.
-    pub(crate) fn merge_diff(&mut self, diff: StagedPublicGroupDiff) {
-        self.treesync.merge_diff(diff.staged_diff);
+    ///
+    /// Returns the indices of the tree nodes that were changed, which are
+    /// passed to [`PublicGroup::store_merged`].
+    pub(crate) fn merge_diff(&mut self, diff: StagedPublicGroupDiff) -> Vec<u32> {
+        let changed_nodes = self.treesync.merge_diff(diff.staged_diff);
         self.group_context = diff.group_context;
         self.interim_transcript_hash = diff.interim_transcript_hash;
         self.confirmation_tag = diff.confirmation_tag;
+        changed_nodes
     }
 
     /// Derives [`EncryptionKeyPair`]s for the nodes in the shared direct path
@@ -362,7 +395,7 @@ impl PublicGroup {
     }
 
     /// Get treesync.
-    fn treesync(&self) -> &TreeSync {
+    pub(crate) fn treesync(&self) -> &TreeSync {
         &self.treesync
     }
 
@@ -399,16 +432,72 @@ impl PublicGroup {
     pub(crate) fn store<Storage: PublicStorageProvider>(
         &self,
         storage: &Storage,
+    ) -> Result<(), Storage::PublicError> {
+        transaction(
+            storage,
+            |e| e,
+            || {
+                let group_id = self.group_context.group_id();
+                storage.delete_tree_nodes(group_id)?;
+                for (node_index, node) in self.treesync().full_nodes() {
+                    storage.write_tree_node(group_id, node_index, &node)?;
+                }
+                self.store_values(storage)
+            },
+        )
+    }
+
+    /// Stores the [`PublicGroup`] to storage after a diff was merged, writing
+    /// only the tree nodes with the given indices as returned by
+    /// [`PublicGroup::merge_diff`].
+    ///
+    /// The whole group is stored if its tree was not stored node by node
+    /// before, e.g. because it was written by an earlier release.
+    pub(crate) fn store_merged<Storage: PublicStorageProvider>(
+        &self,
+        storage: &Storage,
+        changed_nodes: &[u32],
     ) -> Result<(), Storage::PublicError> {
         let group_id = self.group_context.group_id();
-        storage.write_tree(group_id, self.treesync())?;
+        let stored_tree: Option<StoredTreeSync> = storage.tree(group_id)?;
+        if !matches!(stored_tree, Some(StoredTreeSync::Nodes { .. })) {
+            return self.store(storage);
+        }
+
+        transaction(
+            storage,
+            |e| e,
+            || {
+                for &node_index in changed_nodes {
+                    match self.treesync().node(node_index) {
+                        Some(node) => storage.write_tree_node(group_id, node_index, &node)?,
+                        None => storage.delete_tree_node(group_id, node_index)?,
+                    }
+                }
+                self.store_values(storage)
+            },
+        )
+    }
+
+    /// Stores everything but the tree nodes.
+    fn store_values<Storage: PublicStorageProvider>(
+        &self,
+        storage: &Storage,
+    ) -> Result<(), Storage::PublicError> {
+        let group_id = self.group_context.group_id();
+        storage.write_tree(
+            group_id,
+            &StoredTreeSync::Nodes {
+                tree_size: self.treesync().tree_size().u32(),
+                tree_hash: self.treesync().tree_hash().to_vec(),
+            },
+        )?;
         storage.write_confirmation_tag(group_id, self.confirmation_tag())?;
         storage.write_context(group_id, self.group_context())?;
         storage.write_interim_transcript_hash(
             group_id,
             &InterimTranscriptHash(self.interim_transcript_hash.clone()),
-        )?;
-        Ok(())
+        )
     }
 
     /// Deletes the [`PublicGroup`] from storage.
@@ -416,12 +505,19 @@ impl PublicGroup {
         storage: &Storage,
         group_id: &GroupId,
     ) -> Result<(), Storage::PublicError> {
-        storage.delete_tree(group_id)?;
-        storage.delete_confirmation_tag(group_id)?;
-        storage.delete_context(group_id)?;
-        storage.delete_interim_transcript_hash(group_id)?;
-
-        Ok(())
+        transaction(
+            storage,
+            |e| e,
+            || {
+                storage.delete_tree(group_id)?;
+                storage.delete_tree_nodes(group_id)?;
+                storage.delete_confirmation_tag(group_id)?;
+                storage.delete_context(group_id)?;
+                storage.delete_interim_transcript_hash(group_id)?;
+
+                Ok(())
+            },
+        )
     }
 
     /// Loads the [`PublicGroup`] corresponding to a [`GroupId`] from storage.
@@ -429,7 +525,21 @@ impl PublicGroup {
         storage: &Storage,
         group_id: &GroupId,
     ) -> Result<Option<Self>, Storage::PublicError> {
-        let treesync = storage.tree(group_id)?;
+        let treesync = match storage.tree(group_id)? {
+            Some(StoredTreeSync::Nodes {
+                tree_size,
+                tree_hash,
+            }) => {
+                let nodes = storage.tree_nodes(group_id)?;
+                let treesync = TreeSync::from_stored_nodes(tree_size, tree_hash, nodes);
+                if treesync.is_none() {
+                    log::error!("The stored tree nodes don't form a tree of the stored size.");
+                }
+                treesync
+            }
+            Some(StoredTreeSync::Complete(treesync)) => Some(treesync),
+            None => None,
+        };
         let proposals: Vec<(ProposalRef, QueuedProposal)> = storage.queued_proposals(group_id)?;
         let group_context = storage.group_context(group_id)?;
         let interim_transcript_hash: Option<InterimTranscriptHash> =
@@ -448,6 +558,7 @@ impl PublicGroup {
                 group_context: group_context?,
                 interim_transcript_hash: interim_transcript_hash?.0,
                 confirmation_tag: confirmation_tag?,
+                custom_proposal_handlers: CustomProposalHandlers::default(),
             })
         };
 
@@ -6,7 +6,10 @@ use tls_codec::Serialize;
 
 use crate::{
     ciphersuite::OpenMlsSignaturePublicKey,
-    credentials::CredentialWithKey,
+    credentials::{
+        authentication::authenticate_processed_message_content, AuthenticationService,
+        CredentialWithKey,
+    },
     error::LibraryError,
     framing::{
         mls_content::FramedContentBody, ApplicationMessage, DecryptedMessage, ProcessedMessage,
@@ -130,10 +133,14 @@ impl PublicGroup {
     ///  - ValSem244
     ///  - ValSem245
     ///  - ValSem246 (as part of ValSem010)
+    ///
+    /// The credentials introduced by the message are authenticated with the
+    /// `authentication_service`.
     pub fn process_message(
         &self,
         crypto: &impl OpenMlsCrypto,
         message: impl Into<ProtocolMessage>,
+        authentication_service: &impl AuthenticationService,
     ) -> Result<ProcessedMessage, ProcessMessageError> {
         let protocol_message = message.into();
         // Checks the following semantic validation:
@@ -161,7 +168,14 @@ impl PublicGroup {
         let unverified_message = self
             .parse_message(decrypted_message, None)
             .map_err(ProcessMessageError::from)?;
-        self.process_unverified_message(crypto, unverified_message)
+        let processed_message = self.process_unverified_message(crypto, unverified_message)?;
+
+        authenticate_processed_message_content(
+            authentication_service,
+            processed_message.content(),
+        )?;
+
+        Ok(processed_message)
     }
 }
 
@@ -249,7 +263,9 @@ impl PublicGroup {
                     FramedContentBody::Application(_) => {
                         Err(ProcessMessageError::UnauthorizedExternalApplicationMessage)
                     }
-                    FramedContentBody::Proposal(Proposal::Remove(_)) => {
+                    FramedContentBody::Proposal(
+                        Proposal::Add(_) | Proposal::Remove(_) | Proposal::ReInit(_),
+                    ) => {
                         let content = ProcessedMessageContent::ProposalMessage(Box::new(
                             QueuedProposal::from_authenticated_content_by_ref(
                                 self.ciphersuite(),
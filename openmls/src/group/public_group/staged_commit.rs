@@ -1,8 +1,11 @@
 use super::{super::errors::*, *};
 use crate::{
+    credentials::CredentialWithKey,
     framing::{mls_auth_content::AuthenticatedContent, mls_content::FramedContentBody, Sender},
     group::{
-        mls_group::staged_commit::StagedCommitState, proposal_store::ProposalQueue, StagedCommit,
+        mls_group::staged_commit::{CredentialChange, StagedCommitState},
+        proposal_store::ProposalQueue,
+        StagedCommit,
     },
     messages::{proposals::ProposalOrRef, Commit},
 };
@@ -126,6 +129,8 @@ impl PublicGroup {
         // ValSem402
         // ValSem403
         self.validate_pre_shared_key_proposals(&proposal_queue)?;
+        self.validate_app_ack_proposals(&proposal_queue)?;
+        self.validate_custom_proposals(&proposal_queue)?;
 
         match sender {
             Sender::Member(leaf_index) => {
@@ -133,6 +138,8 @@ impl PublicGroup {
                 // ValSem111
                 // ValSem112
                 self.validate_update_proposals(&proposal_queue, *leaf_index)?;
+                // External commits can't contain ReInit proposals (ValSem242).
+                self.validate_reinit_proposals(&proposal_queue)?;
             }
             Sender::External(_) => {
                 // A commit cannot be issued by a pre-configured sender.
@@ -221,8 +228,66 @@ impl PublicGroup {
         };
 
         let staged_commit_state = StagedCommitState::PublicState(Box::new(staged_state));
+        let credential_changes = self.credential_changes(
+            &proposal_queue,
+            mls_content.sender(),
+            commit.path.as_ref().map(|p| p.leaf_node()),
+        );
+
+        Ok(StagedCommit::new(
+            proposal_queue,
+            staged_commit_state,
+            credential_changes,
+        ))
+    }
+
+    /// Returns the old and new credential of every leaf that is updated by
+    /// the proposals in the `proposal_queue` or by the update path of a Commit
+    /// from `committer`. Leaves that are removed by the Commit are skipped.
+    pub(crate) fn credential_changes(
+        &self,
+        proposal_queue: &ProposalQueue,
+        committer: &Sender,
+        update_path_leaf_node: Option<&LeafNode>,
+    ) -> Vec<CredentialChange> {
+        let removed: Vec<LeafNodeIndex> = proposal_queue
+            .remove_proposals()
+            .map(|remove| remove.remove_proposal().removed())
+            .collect();
+
+        let updates = proposal_queue
+            .queued_proposals()
+            .filter_map(|queued_proposal| {
+                match (queued_proposal.sender(), queued_proposal.proposal()) {
+                    (Sender::Member(leaf_index), Proposal::Update(update_proposal)) => {
+                        Some((*leaf_index, update_proposal.leaf_node()))
+                    }
+                    _ => None,
+                }
+            });
+        let path_update = match (committer, update_path_leaf_node) {
+            (Sender::Member(leaf_index), Some(leaf_node)) => Some((*leaf_index, leaf_node)),
+            _ => None,
+        };
 
-        Ok(StagedCommit::new(proposal_queue, staged_commit_state))
+        updates
+            .chain(path_update)
+            .filter(|(leaf_index, _)| !removed.contains(leaf_index))
+            .filter_map(|(leaf_index, new_leaf)| {
+                let old_leaf = self.leaf(leaf_index)?;
+                Some(CredentialChange::new(
+                    leaf_index,
+                    CredentialWithKey {
+                        credential: old_leaf.credential().clone(),
+                        signature_key: old_leaf.signature_key().clone(),
+                    },
+                    CredentialWithKey {
+                        credential: new_leaf.credential().clone(),
+                        signature_key: new_leaf.signature_key().clone(),
+                    },
+                ))
+            })
+            .collect()
     }
 
     fn stage_diff(
@@ -282,17 +347,29 @@ impl PublicGroup {
         storage: &Storage,
         staged_commit: StagedCommit,
     ) -> Result<(), MergeCommitError<Storage::PublicError>> {
-        match staged_commit.into_state() {
+        let (staged_proposal_queue, state) = staged_commit.into_parts();
+        let (changed_nodes, merged_diff) = match state {
             StagedCommitState::PublicState(staged_state) => {
-                self.merge_diff(staged_state.staged_diff);
+                (self.merge_diff(staged_state.staged_diff), true)
             }
-            StagedCommitState::GroupMember(_) => (),
-        }
+            StagedCommitState::GroupMember(_) => (vec![], false),
+        };
 
         self.proposal_store.empty();
-        storage
-            .clear_proposal_queue::<GroupId, ProposalRef>(self.group_id())
-            .map_err(MergeCommitError::StorageError)?;
-        self.store(storage).map_err(MergeCommitError::StorageError)
+        transaction(storage, MergeCommitError::StorageError, || {
+            storage
+                .clear_proposal_queue::<GroupId, ProposalRef>(self.group_id())
+                .map_err(MergeCommitError::StorageError)?;
+            self.store_merged(storage, &changed_nodes)
+                .map_err(MergeCommitError::StorageError)
+        })?;
+
+        // Let the application apply its custom proposals to the new epoch,
+        // but only once the new epoch is stored.
+        if merged_diff {
+            self.apply_custom_proposals(&staged_proposal_queue)?;
+        }
+
+        Ok(())
     }
 }
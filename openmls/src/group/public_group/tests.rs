@@ -2,6 +2,7 @@ use openmls_traits::prelude::*;
 
 use crate::{
     binary_tree::LeafNodeIndex,
+    credentials::test_utils::AcceptAllCredentials,
     framing::{
         public_message_in::PublicMessageIn, MlsMessageIn, MlsMessageOut, ProcessedMessage,
         ProcessedMessageContent, ProtocolMessage, Sender,
@@ -56,6 +57,7 @@ fn public_group<Provider: OpenMlsProvider>(ciphersuite: Ciphersuite, provider: &
         ratchet_tree.into(),
         verifiable_group_info,
         ProposalStore::new(),
+        &AcceptAllCredentials,
     )
     .unwrap();
 
@@ -73,7 +75,7 @@ fn public_group<Provider: OpenMlsProvider>(ciphersuite: Ciphersuite, provider: &
         ProtocolMessage::PublicMessage(public_message) => public_message,
     };
     let processed_message = public_group
-        .process_message(provider.crypto(), *public_message)
+        .process_message(provider.crypto(), *public_message, &AcceptAllCredentials)
         .unwrap();
 
     // Further inspection of the message can take place here ...
@@ -103,6 +105,7 @@ fn public_group<Provider: OpenMlsProvider>(ciphersuite: Ciphersuite, provider: &
         mls_group_create_config.join_config(),
         welcome,
         Some(alice_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .expect("Error creating staged join from Welcome")
     .into_group(provider)
@@ -121,6 +124,7 @@ fn public_group<Provider: OpenMlsProvider>(ciphersuite: Ciphersuite, provider: &
                 .clone()
                 .into_protocol_message()
                 .expect("Unexpected message type"),
+            &AcceptAllCredentials,
         )
         .expect("Could not process messages.");
     if let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
@@ -135,7 +139,11 @@ fn public_group<Provider: OpenMlsProvider>(ciphersuite: Ciphersuite, provider: &
 
     // The public group processes
     let ppm = public_group
-        .process_message(provider.crypto(), into_public_message(queued_messages))
+        .process_message(
+            provider.crypto(),
+            into_public_message(queued_messages),
+            &AcceptAllCredentials,
+        )
         .unwrap();
     public_group
         .merge_commit(provider.storage(), extract_staged_commit(ppm))
@@ -156,6 +164,7 @@ fn public_group<Provider: OpenMlsProvider>(ciphersuite: Ciphersuite, provider: &
         mls_group_create_config.join_config(),
         welcome,
         Some(bob_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .expect("Error creating group from Welcome")
     .into_group(provider)
@@ -174,12 +183,17 @@ fn public_group<Provider: OpenMlsProvider>(ciphersuite: Ciphersuite, provider: &
                 .clone()
                 .into_protocol_message()
                 .expect("Unexpected message type"),
+            &AcceptAllCredentials,
         )
         .expect("Could not process messages.");
 
     // The public group processes
     let ppm = public_group
-        .process_message(provider.crypto(), into_public_message(queued_messages))
+        .process_message(
+            provider.crypto(),
+            into_public_message(queued_messages),
+            &AcceptAllCredentials,
+        )
         .unwrap();
     // We have to add the proposal to the public group's proposal store.
     match ppm.into_content() {
@@ -229,6 +243,7 @@ fn public_group<Provider: OpenMlsProvider>(ciphersuite: Ciphersuite, provider: &
         .process_message(
             provider.crypto(),
             into_public_message(queued_messages.clone()),
+            &AcceptAllCredentials,
         )
         .unwrap();
     public_group
@@ -260,6 +275,7 @@ fn public_group<Provider: OpenMlsProvider>(ciphersuite: Ciphersuite, provider: &
             queued_messages
                 .into_protocol_message()
                 .expect("Unexpected message type"),
+            &AcceptAllCredentials,
         )
         .expect("Could not process messages.");
     if let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
@@ -20,13 +20,13 @@ use crate::{
     group::{
         errors::{ExternalCommitValidationError, ProposalValidationError, ValidationError},
         past_secrets::MessageSecretsStore,
-        Member,
+        GroupEpoch, Member,
     },
     messages::{
         proposals::{Proposal, ProposalOrRefType, ProposalType},
         Commit,
     },
-    schedule::errors::PskError,
+    schedule::{errors::PskError, psk::ResumptionPskUsage, Psk},
 };
 
 use crate::treesync::errors::LifetimeError;
@@ -152,9 +152,7 @@ impl PublicGroup {
         // Check that the types of all proposals are supported by all members
         for proposal in proposal_queue.queued_proposals() {
             let proposal_type = proposal.proposal().proposal_type();
-            if matches!(proposal_type, ProposalType::Custom(_))
-                && !capabilities_intersection.contains(&proposal_type)
-            {
+            if !proposal_type.is_default() && !capabilities_intersection.contains(&proposal_type) {
                 return Err(ProposalValidationError::UnsupportedProposalType);
             }
         }
@@ -368,9 +366,10 @@ impl PublicGroup {
         Ok(())
     }
 
-    /// Validate Remove proposals. This function implements the following checks:
+    /// Validate Remove and SelfRemove proposals. This function implements the following checks:
     ///  - ValSem107: Remove Proposal: Removed member must be unique among proposals
     ///  - ValSem108: Remove Proposal: Removed member must be an existing group member
+    ///  - SelfRemove proposals must be sent by members of the group
     pub(crate) fn validate_remove_proposals(
         &self,
         proposal_queue: &ProposalQueue,
@@ -386,13 +385,23 @@ impl PublicGroup {
             })
             .collect::<Result<_, _>>()?;
 
-        let remove_proposals = proposal_queue.remove_proposals();
+        // A SelfRemove proposal removes its sender.
+        let self_removed = proposal_queue
+            .self_remove_proposals()
+            .map(|self_remove_proposal| match self_remove_proposal.sender() {
+                Sender::Member(index) => Ok(*index),
+                _ => Err(ProposalValidationError::SelfRemoveFromNonMember),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let removed_members = proposal_queue
+            .remove_proposals()
+            .map(|remove_proposal| remove_proposal.remove_proposal().removed())
+            .chain(self_removed);
 
         let mut removes_set = HashSet::new();
 
         // https://validation.openmls.tech/#valn0701
-        for remove_proposal in remove_proposals {
-            let removed = remove_proposal.remove_proposal().removed();
+        for removed in removed_members {
             // The node has to be a leaf in the tree
             // ValSem108
             if !self.treesync().is_leaf_in_tree(removed) {
@@ -455,6 +464,8 @@ impl PublicGroup {
     ///
     /// * ValSem401: The nonce of a PreSharedKeyID must have length KDF.Nh.
     /// * ValSem402: PSK in proposal must be of type Resumption (with usage Application) or External.
+    ///   Resumption PSKs with usage Reinit or Branch are only allowed in the first Commit of a
+    ///   group, and there must be at most one of them.
     /// * ValSem403: Proposal list must not contain multiple PreSharedKey proposals that reference the same PreSharedKeyID.
     pub(crate) fn validate_pre_shared_key_proposals(
         &self,
@@ -465,12 +476,35 @@ impl PublicGroup {
         //              Let's do this check here until we haven't made sure.
         let mut visited_psk_ids = BTreeSet::new();
 
+        // Reinit and branch PSKs link a new group to the group it was created
+        // from, so they can only be injected into the first epoch of the group.
+        let allowed_usages: &[ResumptionPskUsage] =
+            if self.group_context().epoch() == GroupEpoch::from(0) {
+                &[
+                    ResumptionPskUsage::Application,
+                    ResumptionPskUsage::Reinit,
+                    ResumptionPskUsage::Branch,
+                ]
+            } else {
+                &[ResumptionPskUsage::Application]
+            };
+        let mut contains_reinit_or_branch_psk = false;
+
         for proposal in proposal_queue.psk_proposals() {
             let psk_id = proposal.psk_proposal().clone().into_psk_id();
 
             // ValSem401
             // ValSem402
-            let psk_id = psk_id.validate_in_proposal(self.ciphersuite())?;
+            let psk_id = psk_id.validate_in_proposal(self.ciphersuite(), allowed_usages)?;
+
+            if let Psk::Resumption(resumption_psk) = psk_id.psk() {
+                if resumption_psk.usage() != ResumptionPskUsage::Application {
+                    if contains_reinit_or_branch_psk {
+                        return Err(ProposalValidationError::MultipleReInitOrBranchPsks);
+                    }
+                    contains_reinit_or_branch_psk = true;
+                }
+            }
 
             // ValSem403 (2/2)
             if !visited_psk_ids.contains(&psk_id) {
@@ -483,6 +517,51 @@ impl PublicGroup {
         Ok(())
     }
 
+    /// Validate ReInit proposals. This function implements the following checks:
+    ///  - A ReInit proposal must be the only proposal in a Commit.
+    ///  - The protocol version in a ReInit proposal must not be lower than the
+    ///    one of the group.
+    pub(crate) fn validate_reinit_proposals(
+        &self,
+        proposal_queue: &ProposalQueue,
+    ) -> Result<(), ProposalValidationError> {
+        if let Some(reinit_proposal) = proposal_queue.reinit_proposal() {
+            if proposal_queue.queued_proposals().count() > 1 {
+                return Err(ProposalValidationError::ReInitWithOtherProposals);
+            }
+            if reinit_proposal.version() < self.version() {
+                return Err(ProposalValidationError::ReInitVersionDowngrade);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validate AppAck proposals. This function checks that
+    ///  - AppAck proposals are sent by members of the group,
+    ///  - the senders of all acknowledged messages are members of the group,
+    ///  - no message range ends before it starts.
+    pub(crate) fn validate_app_ack_proposals(
+        &self,
+        proposal_queue: &ProposalQueue,
+    ) -> Result<(), ProposalValidationError> {
+        for app_ack_proposal in proposal_queue.app_ack_proposals() {
+            if !matches!(app_ack_proposal.sender(), Sender::Member(_)) {
+                return Err(ProposalValidationError::AppAckFromNonMember);
+            }
+            for range in app_ack_proposal.app_ack_proposal().received_ranges() {
+                if self.leaf(range.sender()).is_none() {
+                    return Err(ProposalValidationError::AppAckUnknownSender);
+                }
+                if range.first_generation() > range.last_generation() {
+                    return Err(ProposalValidationError::AppAckInvalidRange);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Validate constraints on an external commit. This function implements the following checks:
     ///  - ValSem240: External Commit, inline Proposals: There MUST be at least one ExternalInit proposal.
     ///  - ValSem241: External Commit, inline Proposals: There MUST be at most one ExternalInit proposal.
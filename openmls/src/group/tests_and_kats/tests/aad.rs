@@ -1,6 +1,7 @@
 // Import necessary modules and dependencies
 use crate::{
     binary_tree::LeafNodeIndex,
+    credentials::test_utils::AcceptAllCredentials,
     framing::*,
     group::{
         tests_and_kats::utils::{generate_credential_with_key, generate_key_package},
@@ -100,6 +101,7 @@ fn test_add_member_with_aad(
             mls_group_create_config.join_config(),
             welcome.clone(),
             Some(alice_group.export_ratchet_tree().into()),
+            &AcceptAllCredentials,
         )
         .expect("Error creating staged join from Welcome")
         .into_group(provider)
@@ -125,6 +127,7 @@ fn test_add_member_with_aad(
             .process_message(
                 provider,
                 alice_message.clone().into_protocol_message().unwrap(),
+                &AcceptAllCredentials,
             )
             .expect("Error handling message");
 
@@ -149,7 +152,11 @@ fn test_add_member_with_aad(
         assert_eq!(alice_group.aad().len(), 0);
 
         let bob_processed_message = bob_group
-            .process_message(provider, commit.clone().into_protocol_message().unwrap())
+            .process_message(
+                provider,
+                commit.clone().into_protocol_message().unwrap(),
+                &AcceptAllCredentials,
+            )
             .expect("Error handling message");
 
         match bob_processed_message.into_content() {
@@ -182,7 +189,11 @@ fn test_add_member_with_aad(
         assert_eq!(alice_group.aad().len(), 0);
 
         let bob_processed_message = bob_group
-            .process_message(provider, commit.clone().into_protocol_message().unwrap())
+            .process_message(
+                provider,
+                commit.clone().into_protocol_message().unwrap(),
+                &AcceptAllCredentials,
+            )
             .expect("Error handling message");
 
         // Test the AAD was set correctly
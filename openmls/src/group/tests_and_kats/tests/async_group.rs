@@ -0,0 +1,216 @@
+//! This module contains tests for the [`AsyncMlsGroup`].
+
+use crate::group::tests_and_kats::utils::{
+    block_on, generate_credential_with_key, generate_key_package,
+};
+use crate::{
+    credentials::test_utils::AcceptAllCredentials,
+    framing::{MlsMessageIn, ProcessedMessageContent},
+    group::*,
+    treesync::LeafNodeParameters,
+};
+
+use openmls_traits::OpenMlsProvider as _;
+
+#[openmls_test::openmls_test]
+fn async_group<Provider: crate::storage::OpenMlsProvider>(
+    ciphersuite: Ciphersuite,
+    provider: &Provider,
+) {
+    let alice_provider = &Provider::default();
+    let bob_provider = &Provider::default();
+    let group_id = GroupId::from_slice(b"Test Group");
+
+    let alice_credential_with_keys = generate_credential_with_key(
+        b"Alice".to_vec(),
+        ciphersuite.signature_algorithm(),
+        alice_provider,
+    );
+    let bob_credential_with_keys = generate_credential_with_key(
+        b"Bob".to_vec(),
+        ciphersuite.signature_algorithm(),
+        bob_provider,
+    );
+    let bob_key_package = generate_key_package(
+        ciphersuite,
+        Extensions::empty(),
+        bob_provider,
+        bob_credential_with_keys.clone(),
+    );
+
+    let mls_group_create_config = MlsGroupCreateConfig::builder()
+        .ciphersuite(ciphersuite)
+        .use_ratchet_tree_extension(true)
+        .build();
+
+    // === Alice creates a group and adds Bob ===
+    let mut alice_group = MlsGroup::new_with_group_id(
+        alice_provider,
+        &alice_credential_with_keys.signer,
+        &mls_group_create_config,
+        group_id.clone(),
+        alice_credential_with_keys.credential_with_key.clone(),
+    )
+    .expect("An unexpected error occurred.");
+
+    let (_message, welcome, _group_info) = alice_group
+        .add_members(
+            alice_provider,
+            &alice_credential_with_keys.signer,
+            &[bob_key_package.key_package().clone()],
+        )
+        .expect("An unexpected error occurred.");
+    alice_group
+        .merge_pending_commit(alice_provider)
+        .expect("error merging pending commit");
+
+    let welcome: MlsMessageIn = welcome.into();
+    let welcome = welcome
+        .into_welcome()
+        .expect("expected message to be a welcome");
+    StagedWelcome::new_from_welcome(
+        bob_provider,
+        mls_group_create_config.join_config(),
+        welcome,
+        None,
+        &AcceptAllCredentials,
+    )
+    .expect("Error creating staged join from Welcome")
+    .into_group(bob_provider)
+    .expect("Error creating group from staged join");
+
+    // === Bob continues with the async group ===
+    assert!(block_on(AsyncMlsGroup::load(
+        bob_provider,
+        &GroupId::from_slice(b"Unknown")
+    ))
+    .expect("Error loading the group")
+    .is_none());
+    let mut bob_group = block_on(AsyncMlsGroup::load(bob_provider, &group_id))
+        .expect("Error loading the group")
+        .expect("The group is not stored");
+
+    // Processing Alice's commit needs Bob's encryption keys, which are not in
+    // the cache yet.
+    let (commit, _welcome, _group_info) = alice_group
+        .self_update(
+            alice_provider,
+            &alice_credential_with_keys.signer,
+            LeafNodeParameters::default(),
+        )
+        .expect("An unexpected error occurred.");
+    alice_group
+        .merge_pending_commit(alice_provider)
+        .expect("error merging pending commit");
+
+    let processed_message = block_on(bob_group.process_message(
+        bob_provider,
+        commit.into_protocol_message().unwrap(),
+        &AcceptAllCredentials,
+    ))
+    .expect("Error processing the commit");
+    let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
+        processed_message.into_content()
+    else {
+        panic!("Expected a StagedCommit.");
+    };
+    block_on(bob_group.merge_staged_commit(bob_provider, *staged_commit))
+        .expect("Error merging the commit");
+    assert_eq!(
+        alice_group.epoch_authenticator(),
+        bob_group.group().epoch_authenticator()
+    );
+
+    // === Application messages in both directions ===
+    let message = alice_group
+        .create_message(
+            alice_provider,
+            &alice_credential_with_keys.signer,
+            &[1, 2, 3],
+        )
+        .expect("An unexpected error occurred.")
+        .into_protocol_message()
+        .unwrap();
+    let processed_message =
+        block_on(bob_group.process_message(bob_provider, message.clone(), &AcceptAllCredentials))
+            .expect("Error processing the application message");
+    let ProcessedMessageContent::ApplicationMessage(application_message) =
+        processed_message.into_content()
+    else {
+        panic!("Expected an ApplicationMessage.");
+    };
+    assert_eq!(application_message.into_bytes(), vec![1, 2, 3]);
+
+    // A failed operation leaves the group and the storage as they were.
+    let stored_group = MlsGroup::load(bob_provider.storage(), &group_id)
+        .unwrap()
+        .unwrap();
+    assert!(matches!(
+        block_on(bob_group.process_message(bob_provider, message, &AcceptAllCredentials)),
+        Err(AsyncMlsGroupError::OperationError(_))
+    ));
+    assert_eq!(bob_group.group(), &stored_group);
+    assert_eq!(
+        MlsGroup::load(bob_provider.storage(), &group_id)
+            .unwrap()
+            .unwrap(),
+        stored_group
+    );
+
+    let message = block_on(bob_group.create_message(
+        bob_provider,
+        &bob_credential_with_keys.signer,
+        &[4, 5, 6],
+    ))
+    .expect("Error creating the application message");
+    let processed_message = alice_group
+        .process_message(
+            alice_provider,
+            message.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
+        .expect("Error processing the application message");
+    let ProcessedMessageContent::ApplicationMessage(application_message) =
+        processed_message.into_content()
+    else {
+        panic!("Expected an ApplicationMessage.");
+    };
+    assert_eq!(application_message.into_bytes(), vec![4, 5, 6]);
+
+    // === Bob commits ===
+    let (commit, _welcome, _group_info) = block_on(bob_group.self_update(
+        bob_provider,
+        &bob_credential_with_keys.signer,
+        LeafNodeParameters::default(),
+    ))
+    .expect("Error creating the commit");
+    block_on(bob_group.merge_pending_commit(bob_provider)).expect("Error merging the commit");
+
+    let processed_message = alice_group
+        .process_message(
+            alice_provider,
+            commit.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
+        .expect("Error processing the commit");
+    let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
+        processed_message.into_content()
+    else {
+        panic!("Expected a StagedCommit.");
+    };
+    alice_group
+        .merge_staged_commit(alice_provider, *staged_commit)
+        .expect("Error merging the commit");
+    assert_eq!(
+        alice_group.epoch_authenticator(),
+        bob_group.group().epoch_authenticator()
+    );
+
+    // The storage holds the same group as the async group.
+    assert_eq!(
+        &MlsGroup::load(bob_provider.storage(), &group_id)
+            .unwrap()
+            .unwrap(),
+        bob_group.group()
+    );
+}
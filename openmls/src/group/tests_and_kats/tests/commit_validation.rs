@@ -12,6 +12,7 @@ use crate::group::tests_and_kats::utils::{
 use crate::{
     binary_tree::LeafNodeIndex,
     ciphersuite::signable::Signable,
+    credentials::test_utils::AcceptAllCredentials,
     framing::*,
     group::*,
     messages::proposals::*,
@@ -103,6 +104,7 @@ fn validation_test_setup(
         mls_group_create_config.join_config(),
         welcome.clone(),
         Some(alice_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .expect("error creating staged join from welcome")
     .into_group(provider)
@@ -113,6 +115,7 @@ fn validation_test_setup(
         mls_group_create_config.join_config(),
         welcome,
         Some(alice_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .expect("error creating staged join from welcome")
     .into_group(provider)
@@ -237,7 +240,7 @@ fn test_valsem200() {
     let message_in = ProtocolMessage::from(signed_plaintext);
 
     let err = bob_group
-        .process_message(provider, message_in)
+        .process_message(provider, message_in, &AcceptAllCredentials)
         .expect_err("Could process unverified message despite self remove.");
 
     assert!(matches!(
@@ -247,7 +250,11 @@ fn test_valsem200() {
 
     // Positive case
     bob_group
-        .process_message(provider, ProtocolMessage::from(original_plaintext))
+        .process_message(
+            provider,
+            ProtocolMessage::from(original_plaintext),
+            &AcceptAllCredentials,
+        )
         .expect("Unexpected error.");
 }
 
@@ -389,7 +396,8 @@ fn test_valsem201() {
                 &alice_group,
                 &alice_credential.signer,
             );
-            let processed_msg = bob_group.process_message(provider, commit_wo_path);
+            let processed_msg =
+                bob_group.process_message(provider, commit_wo_path, &AcceptAllCredentials);
             assert!(matches!(
                 processed_msg.unwrap_err(),
                 ProcessMessageError::InvalidCommit(StageCommitError::RequiredPathNotFound)
@@ -397,7 +405,8 @@ fn test_valsem201() {
         }
 
         // Positive case
-        let process_message_result = bob_group.process_message(provider, commit);
+        let process_message_result =
+            bob_group.process_message(provider, commit, &AcceptAllCredentials);
         assert!(process_message_result.is_ok(), "{process_message_result:?}");
 
         // cleanup & restore for next iteration
@@ -498,7 +507,7 @@ fn test_valsem202() {
     let update_message_in = ProtocolMessage::from(plaintext);
 
     let err = bob_group
-        .process_message(provider, update_message_in)
+        .process_message(provider, update_message_in, &AcceptAllCredentials)
         .expect_err("Could process unverified message despite path length mismatch.");
 
     assert!(matches!(
@@ -519,6 +528,7 @@ fn test_valsem202() {
             original_update_plaintext
                 .try_into_protocol_message()
                 .unwrap(),
+            &AcceptAllCredentials,
         )
         .expect("Unexpected error.");
 }
@@ -581,7 +591,7 @@ fn test_valsem203() {
     let update_message_in = ProtocolMessage::from(plaintext);
 
     let err = bob_group
-        .process_message(provider, update_message_in)
+        .process_message(provider, update_message_in, &AcceptAllCredentials)
         .expect_err("Could process unverified message despite scrambled ciphertexts.");
 
     assert!(matches!(
@@ -602,6 +612,7 @@ fn test_valsem203() {
             original_update_plaintext
                 .try_into_protocol_message()
                 .unwrap(),
+            &AcceptAllCredentials,
         )
         .expect("Unexpected error.");
 }
@@ -649,7 +660,7 @@ fn test_valsem204() {
     // Let Charlie process the commit, so we can pull the post-merge tree hash
     // from them.
     let message = charlie_group
-        .process_message(provider, original_plaintext.clone())
+        .process_message(provider, original_plaintext.clone(), &AcceptAllCredentials)
         .unwrap();
     match message.into_content() {
         ProcessedMessageContent::StagedCommitMessage(staged_commit) => charlie_group
@@ -707,7 +718,7 @@ fn test_valsem204() {
     let update_message_in = ProtocolMessage::from(plaintext);
 
     let err = bob_group
-        .process_message(provider, update_message_in)
+        .process_message(provider, update_message_in, &AcceptAllCredentials)
         .expect_err("Could process unverified message despite modified public key in path.");
 
     assert!(matches!(
@@ -728,6 +739,7 @@ fn test_valsem204() {
             original_update_plaintext
                 .try_into_protocol_message()
                 .unwrap(),
+            &AcceptAllCredentials,
         )
         .expect("Unexpected error.");
 }
@@ -789,7 +801,7 @@ fn test_valsem205() {
     let update_message_in = ProtocolMessage::from(plaintext);
 
     let err = bob_group
-        .process_message(provider, update_message_in)
+        .process_message(provider, update_message_in, &AcceptAllCredentials)
         .expect_err("Could process unverified message despite confirmation tag mismatch.");
 
     assert!(matches!(
@@ -799,7 +811,11 @@ fn test_valsem205() {
 
     // Positive case
     bob_group
-        .process_message(provider, ProtocolMessage::from(original_plaintext))
+        .process_message(
+            provider,
+            ProtocolMessage::from(original_plaintext),
+            &AcceptAllCredentials,
+        )
         .expect("Unexpected error.");
 }
 
@@ -829,7 +845,11 @@ fn test_partial_proposal_commit(
         .map(|(out, _)| MlsMessageIn::from(out))
         .unwrap();
     let proposal_1 = bob_group
-        .process_message(provider, proposal_1.try_into_protocol_message().unwrap())
+        .process_message(
+            provider,
+            proposal_1.try_into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
         .unwrap();
     match proposal_1.into_content() {
         ProcessedMessageContent::ProposalMessage(p) => bob_group
@@ -848,7 +868,11 @@ fn test_partial_proposal_commit(
         .map(|(out, _)| MlsMessageIn::from(out))
         .unwrap();
     let proposal_2 = bob_group
-        .process_message(provider, proposal_2.try_into_protocol_message().unwrap())
+        .process_message(
+            provider,
+            proposal_2.try_into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
         .unwrap();
     match proposal_2.into_content() {
         ProcessedMessageContent::ProposalMessage(p) => bob_group
@@ -876,7 +900,11 @@ fn test_partial_proposal_commit(
 
     // Bob should be able to process the commit
     bob_group
-        .process_message(provider, commit.into_protocol_message().unwrap())
+        .process_message(
+            provider,
+            commit.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
         .expect("Commits with partial proposals are not supported");
     bob_group
         .merge_pending_commit(provider)
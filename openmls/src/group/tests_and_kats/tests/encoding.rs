@@ -3,6 +3,7 @@ use tls_codec::{Deserialize, Serialize};
 
 use crate::{
     binary_tree::LeafNodeIndex,
+    credentials::test_utils::AcceptAllCredentials,
     framing::*,
     group::{tests_and_kats::utils::*, *},
     key_packages::*,
@@ -311,10 +312,15 @@ fn test_welcome_message_encoding(provider: &impl crate::storage::OpenMlsProvider
         // example the RatchetTreeExtension.
         let config = MlsGroupJoinConfig::default();
         let ratchet_tree = Some(group_state.export_ratchet_tree().into());
-        let charlie_group =
-            StagedWelcome::new_from_welcome(provider, &config, welcome, ratchet_tree)
-                .unwrap()
-                .into_group(provider);
+        let charlie_group = StagedWelcome::new_from_welcome(
+            provider,
+            &config,
+            welcome,
+            ratchet_tree,
+            &AcceptAllCredentials,
+        )
+        .unwrap()
+        .into_group(provider);
         assert!(charlie_group.is_ok());
     }
 }
@@ -3,6 +3,7 @@ use openmls_test::openmls_test;
 
 use crate::{
     binary_tree::LeafNodeIndex,
+    credentials::test_utils::AcceptAllCredentials,
     framing::*,
     group::*,
     messages::{
@@ -100,6 +101,7 @@ fn validation_test_setup(
         &mls_group_config,
         welcome,
         Some(alice_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .expect("error creating group from welcome")
     .into_group(provider)
@@ -158,7 +160,11 @@ fn external_add_proposal_should_succeed<Provider: OpenMlsProvider>() {
         );
 
         let msg = alice_group
-            .process_message(provider, proposal.clone().into_protocol_message().unwrap())
+            .process_message(
+                provider,
+                proposal.clone().into_protocol_message().unwrap(),
+                &AcceptAllCredentials,
+            )
             .unwrap();
 
         match msg.into_content() {
@@ -176,7 +182,11 @@ fn external_add_proposal_should_succeed<Provider: OpenMlsProvider>() {
         }
 
         let msg = bob_group
-            .process_message(provider, proposal.into_protocol_message().unwrap())
+            .process_message(
+                provider,
+                proposal.into_protocol_message().unwrap(),
+                &AcceptAllCredentials,
+            )
             .unwrap();
 
         match msg.into_content() {
@@ -195,7 +205,11 @@ fn external_add_proposal_should_succeed<Provider: OpenMlsProvider>() {
 
         // Bob will also process the commit
         let msg = bob_group
-            .process_message(provider, commit.into_protocol_message().unwrap())
+            .process_message(
+                provider,
+                commit.into_protocol_message().unwrap(),
+                &AcceptAllCredentials,
+            )
             .unwrap();
         match msg.into_content() {
             ProcessedMessageContent::StagedCommitMessage(commit) => {
@@ -219,6 +233,7 @@ fn external_add_proposal_should_succeed<Provider: OpenMlsProvider>() {
             &mls_group_config,
             welcome,
             Some(alice_group.export_ratchet_tree().into()),
+            &AcceptAllCredentials,
         )
         .unwrap()
         .into_group(provider)
@@ -267,7 +282,11 @@ fn external_add_proposal_should_be_signed_by_key_package_it_references<
     // fails because the message was not signed by the same credential as the one in the Add proposal
     assert!(matches!(
         alice_group
-            .process_message(provider, invalid_proposal.into_protocol_message().unwrap())
+            .process_message(
+                provider,
+                invalid_proposal.into_protocol_message().unwrap(),
+                &AcceptAllCredentials
+            )
             .unwrap_err(),
         ProcessMessageError::ValidationError(ValidationError::InvalidSignature)
     ));
@@ -315,7 +334,11 @@ fn new_member_proposal_sender_should_be_reserved_for_join_proposals<Provider: Op
 
         // Finally check that the message can be processed without errors
         assert!(bob_group
-            .process_message(provider, join_proposal.into_protocol_message().unwrap())
+            .process_message(
+                provider,
+                join_proposal.into_protocol_message().unwrap(),
+                &AcceptAllCredentials
+            )
             .is_ok());
     } else {
         panic!()
@@ -332,7 +355,9 @@ fn new_member_proposal_sender_should_be_reserved_for_join_proposals<Provider: Op
     if let MlsMessageBodyIn::PublicMessage(mut plaintext) = remove_proposal.body {
         plaintext.set_sender(Sender::NewMemberProposal);
         assert!(matches!(
-            bob_group.process_message(provider, plaintext).unwrap_err(),
+            bob_group
+                .process_message(provider, plaintext, &AcceptAllCredentials)
+                .unwrap_err(),
             ProcessMessageError::ValidationError(ValidationError::NotAnExternalAddProposal)
         ));
     } else {
@@ -350,7 +375,9 @@ fn new_member_proposal_sender_should_be_reserved_for_join_proposals<Provider: Op
     if let MlsMessageBodyIn::PublicMessage(mut plaintext) = update_proposal.body {
         plaintext.set_sender(Sender::NewMemberProposal);
         assert!(matches!(
-            bob_group.process_message(provider, plaintext).unwrap_err(),
+            bob_group
+                .process_message(provider, plaintext, &AcceptAllCredentials)
+                .unwrap_err(),
             ProcessMessageError::ValidationError(ValidationError::NotAnExternalAddProposal)
         ));
     } else {
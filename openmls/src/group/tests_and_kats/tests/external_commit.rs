@@ -2,6 +2,7 @@
 use tls_codec::{Deserialize, Serialize};
 
 use crate::{
+    credentials::test_utils::AcceptAllCredentials,
     framing::{MlsMessageIn, Sender},
     group::{
         tests_and_kats::utils::generate_credential_with_key, MlsGroup, MlsGroupCreateConfig,
@@ -62,6 +63,7 @@ fn test_external_commit() {
         None,
         &[],
         bob_credential.credential_with_key.clone(),
+        &AcceptAllCredentials,
     )
     .unwrap();
     bob_group.merge_pending_commit(provider).unwrap();
@@ -83,7 +85,7 @@ fn test_external_commit() {
     // Alice processes Bob's Commit
 
     let processed_message = alice_group
-        .process_message(provider, public_message_commit)
+        .process_message(provider, public_message_commit, &AcceptAllCredentials)
         .unwrap();
 
     match processed_message.into_content() {
@@ -127,6 +129,7 @@ fn test_external_commit() {
         None,
         &[],
         charlie_credential.credential_with_key.clone(),
+        &AcceptAllCredentials,
     )
     .unwrap();
     charlie_group.merge_pending_commit(provider).unwrap();
@@ -138,7 +141,7 @@ fn test_external_commit() {
         .unwrap();
 
     let alice_processed_message = alice_group
-        .process_message(provider, charlie_commit.clone())
+        .process_message(provider, charlie_commit.clone(), &AcceptAllCredentials)
         .unwrap();
 
     match alice_processed_message.into_content() {
@@ -150,7 +153,9 @@ fn test_external_commit() {
         _ => panic!("Expected Commit message"),
     }
 
-    let bob_processed_message = bob_group.process_message(provider, charlie_commit).unwrap();
+    let bob_processed_message = bob_group
+        .process_message(provider, charlie_commit, &AcceptAllCredentials)
+        .unwrap();
 
     match bob_processed_message.into_content() {
         ProcessedMessageContent::StagedCommitMessage(staged_commit) => {
@@ -201,6 +206,7 @@ fn test_external_commit() {
         None,
         &[],
         alice_credential.credential_with_key.clone(),
+        &AcceptAllCredentials,
     )
     .unwrap();
     alice_group.merge_pending_commit(provider).unwrap();
@@ -212,7 +218,7 @@ fn test_external_commit() {
         .unwrap();
 
     let bob_processed_message = bob_group
-        .process_message(provider, alice_commit.clone())
+        .process_message(provider, alice_commit.clone(), &AcceptAllCredentials)
         .unwrap();
 
     match bob_processed_message.into_content() {
@@ -230,7 +236,7 @@ fn test_external_commit() {
     }
 
     let charlie_processed_message = charlie_group
-        .process_message(provider, alice_commit)
+        .process_message(provider, alice_commit, &AcceptAllCredentials)
         .unwrap();
 
     match charlie_processed_message.into_content() {
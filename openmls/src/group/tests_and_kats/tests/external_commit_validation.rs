@@ -8,6 +8,7 @@ use tls_codec::{Deserialize, Serialize};
 use self::utils::*;
 use crate::{
     ciphersuite::{hash_ref::ProposalRef, signable::Verifiable},
+    credentials::test_utils::AcceptAllCredentials,
     framing::{
         mls_auth_content_in::AuthenticatedContentIn, ContentType, DecryptedMessage,
         FramedContentBody, MlsMessageIn, ProtocolMessage, Sender, WireFormat,
@@ -79,7 +80,11 @@ fn test_valsem240() {
     // Have alice process the commit resulting from external init.
     // Negative case
     let err = alice_group
-        .process_message(provider, ProtocolMessage::from(public_message_commit_bad))
+        .process_message(
+            provider,
+            ProtocolMessage::from(public_message_commit_bad),
+            &AcceptAllCredentials,
+        )
         .expect_err("Could process message despite missing external init proposal.");
 
     println!("Got the error: {:?}", err);
@@ -93,7 +98,11 @@ fn test_valsem240() {
 
     // Positive case
     alice_group
-        .process_message(provider, ProtocolMessage::from(public_message_commit))
+        .process_message(
+            provider,
+            ProtocolMessage::from(public_message_commit),
+            &AcceptAllCredentials,
+        )
         .unwrap();
 }
 
@@ -144,7 +153,11 @@ fn test_valsem241() {
     // Have alice process the commit resulting from external init.
     // Negative case
     let err = alice_group
-        .process_message(provider, ProtocolMessage::from(public_message_commit_bad))
+        .process_message(
+            provider,
+            ProtocolMessage::from(public_message_commit_bad),
+            &AcceptAllCredentials,
+        )
         .expect_err("Could process message despite second ext. init proposal in commit.");
 
     assert!(matches!(
@@ -156,7 +169,11 @@ fn test_valsem241() {
 
     // Positive case
     alice_group
-        .process_message(provider, ProtocolMessage::from(public_message_commit))
+        .process_message(
+            provider,
+            ProtocolMessage::from(public_message_commit),
+            &AcceptAllCredentials,
+        )
         .expect("Unexpected error.");
 }
 
@@ -207,6 +224,7 @@ fn test_valsem242() {
         None,
         &[],
         bob_credential.credential_with_key.clone(),
+        &AcceptAllCredentials,
     )
     .unwrap();
 
@@ -295,7 +313,7 @@ fn test_valsem242() {
 
         // Negative case
         let err = alice_group
-            .process_message(provider, public_message_commit_bad)
+            .process_message(provider, public_message_commit_bad, &AcceptAllCredentials)
             .unwrap_err();
 
         assert!(matches!(
@@ -307,7 +325,11 @@ fn test_valsem242() {
 
         // Positive case
         alice_group
-            .process_message(provider, public_message_commit.clone())
+            .process_message(
+                provider,
+                public_message_commit.clone(),
+                &AcceptAllCredentials,
+            )
             .unwrap();
     }
 }
@@ -373,7 +395,11 @@ fn test_valsem244() {
     // Expect error because the message can't be processed due to the external
     // commit including an external init proposal by reference.
     let err = alice_group
-        .process_message(provider, ProtocolMessage::from(public_message_commit_bad))
+        .process_message(
+            provider,
+            ProtocolMessage::from(public_message_commit_bad),
+            &AcceptAllCredentials,
+        )
         .unwrap_err();
 
     assert!(matches!(
@@ -385,7 +411,11 @@ fn test_valsem244() {
 
     // Positive case
     alice_group
-        .process_message(provider, ProtocolMessage::from(public_message_commit))
+        .process_message(
+            provider,
+            ProtocolMessage::from(public_message_commit),
+            &AcceptAllCredentials,
+        )
         .unwrap();
 }
 
@@ -431,7 +461,11 @@ fn test_valsem245() {
     // Have alice process the commit resulting from external init.
     // Negative case
     let err = alice_group
-        .process_message(provider, ProtocolMessage::from(public_message_commit_bad))
+        .process_message(
+            provider,
+            ProtocolMessage::from(public_message_commit_bad),
+            &AcceptAllCredentials,
+        )
         .expect_err("Could process message despite missing path.");
 
     assert!(matches!(
@@ -441,7 +475,11 @@ fn test_valsem245() {
 
     // Positive case
     alice_group
-        .process_message(provider, ProtocolMessage::from(public_message_commit))
+        .process_message(
+            provider,
+            ProtocolMessage::from(public_message_commit),
+            &AcceptAllCredentials,
+        )
         .unwrap();
 }
 
@@ -502,7 +540,11 @@ fn test_valsem246() {
     // Have alice process the commit resulting from external init.
     // Negative case
     let err = alice_group
-        .process_message(provider, ProtocolMessage::from(public_message_commit_bad))
+        .process_message(
+            provider,
+            ProtocolMessage::from(public_message_commit_bad),
+            &AcceptAllCredentials,
+        )
         .expect_err("Could process message despite wrong signature.");
 
     // This shows that signature verification fails if the signature is not done
@@ -553,7 +595,11 @@ fn test_valsem246() {
     // This shows it again, since ValSem010 ensures that the signature is
     // correct (which it only is, if alice is using the credential in the path).
     alice_group
-        .process_message(provider, ProtocolMessage::from(public_message_commit))
+        .process_message(
+            provider,
+            ProtocolMessage::from(public_message_commit),
+            &AcceptAllCredentials,
+        )
         .expect("Unexpected error.");
 }
 
@@ -587,6 +633,7 @@ fn test_pure_ciphertest() {
         None,
         &[],
         bob_credential.credential_with_key.clone(),
+        &AcceptAllCredentials,
     )
     .expect("Error initializing group externally.");
 
@@ -597,7 +644,8 @@ fn test_pure_ciphertest() {
     assert!(alice_group
         .process_message(
             provider,
-            mls_message_in.try_into_protocol_message().unwrap()
+            mls_message_in.try_into_protocol_message().unwrap(),
+            &AcceptAllCredentials
         )
         .is_ok());
 }
@@ -607,6 +655,7 @@ mod utils {
     use tls_codec::{Deserialize, Serialize};
 
     use crate::{
+        credentials::test_utils::AcceptAllCredentials,
         framing::{MlsMessageIn, PublicMessage, Sender},
         group::{
             tests_and_kats::utils::{generate_credential_with_key, CredentialWithKeyAndSigner},
@@ -674,6 +723,7 @@ mod utils {
             None,
             &[],
             bob_credential.credential_with_key.clone(),
+            &AcceptAllCredentials,
         )
         .unwrap();
 
@@ -1,3 +1,4 @@
+use crate::credentials::test_utils::AcceptAllCredentials;
 use openmls_test::openmls_test;
 
 use crate::{credentials::BasicCredential, framing::*, group::*, messages::external_proposals::*};
@@ -145,6 +146,7 @@ fn external_remove_proposal_should_remove_member() {
             bob_external_remove_proposal
                 .try_into_protocol_message()
                 .unwrap(),
+            &AcceptAllCredentials,
         )
         .unwrap();
     // commit the proposal
@@ -179,6 +181,7 @@ fn external_remove_proposal_should_remove_member() {
             invalid_bob_external_remove_proposal
                 .try_into_protocol_message()
                 .unwrap(),
+            &AcceptAllCredentials,
         )
         .unwrap();
     // commit the proposal
@@ -253,6 +256,7 @@ fn external_remove_proposal_should_fail_when_invalid_external_senders_index<
             bob_external_remove_proposal
                 .try_into_protocol_message()
                 .unwrap(),
+            &AcceptAllCredentials,
         )
         .unwrap_err();
     assert!(matches!(
@@ -313,6 +317,7 @@ fn external_remove_proposal_should_fail_when_invalid_signature() {
             bob_external_remove_proposal
                 .try_into_protocol_message()
                 .unwrap(),
+            &AcceptAllCredentials,
         )
         .unwrap_err();
     assert!(matches!(
@@ -360,6 +365,7 @@ fn external_remove_proposal_should_fail_when_no_external_senders() {
             bob_external_remove_proposal
                 .try_into_protocol_message()
                 .unwrap(),
+            &AcceptAllCredentials,
         )
         .unwrap_err();
     assert!(matches!(
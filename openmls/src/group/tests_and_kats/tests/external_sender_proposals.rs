@@ -0,0 +1,209 @@
+//! Tests for Add and ReInit proposals from external senders.
+
+use openmls_test::openmls_test;
+
+use crate::{
+    credentials::test_utils::AcceptAllCredentials,
+    framing::*,
+    group::*,
+    messages::{external_proposals::*, proposals::ReInitProposal},
+    versions::ProtocolVersion,
+};
+
+use openmls_traits::types::Ciphersuite;
+
+use crate::group::tests_and_kats::utils::*;
+
+// Creates a group of Alice with the delivery service as the only external
+// sender.
+fn new_test_group(
+    ciphersuite: Ciphersuite,
+    provider: &impl crate::storage::OpenMlsProvider,
+    ds_credential_with_key: &CredentialWithKeyAndSigner,
+) -> (MlsGroup, CredentialWithKeyAndSigner) {
+    let alice_credential_with_key =
+        generate_credential_with_key("Alice".into(), ciphersuite.signature_algorithm(), provider);
+
+    let external_senders = vec![ExternalSender::new(
+        ds_credential_with_key
+            .credential_with_key
+            .signature_key
+            .clone(),
+        ds_credential_with_key
+            .credential_with_key
+            .credential
+            .clone(),
+    )];
+    let mls_group_config = MlsGroupCreateConfig::builder()
+        .wire_format_policy(PURE_PLAINTEXT_WIRE_FORMAT_POLICY)
+        .ciphersuite(ciphersuite)
+        .with_group_context_extensions(Extensions::single(Extension::ExternalSenders(
+            external_senders,
+        )))
+        .unwrap()
+        .build();
+
+    let alice_group = MlsGroup::new_with_group_id(
+        provider,
+        &alice_credential_with_key.signer,
+        &mls_group_config,
+        GroupId::from_slice(b"Test Group"),
+        alice_credential_with_key.credential_with_key.clone(),
+    )
+    .unwrap();
+
+    (alice_group, alice_credential_with_key)
+}
+
+// Processes an external proposal and stores it in the proposal store.
+fn process_and_store_proposal(
+    group: &mut MlsGroup,
+    provider: &impl crate::storage::OpenMlsProvider,
+    proposal: MlsMessageOut,
+) {
+    let proposal: MlsMessageIn = proposal.into();
+    let processed_message = group
+        .process_message(
+            provider,
+            proposal.try_into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
+        .unwrap();
+    assert!(matches!(processed_message.sender(), Sender::External(_)));
+    let ProcessedMessageContent::ProposalMessage(queued_proposal) =
+        processed_message.into_content()
+    else {
+        panic!("Not a proposal");
+    };
+    group
+        .store_pending_proposal(provider.storage(), *queued_proposal)
+        .unwrap();
+}
+
+#[openmls_test]
+fn external_add_proposal_should_add_member() {
+    let ds_credential_with_key = generate_credential_with_key(
+        "delivery-service".into(),
+        ciphersuite.signature_algorithm(),
+        provider,
+    );
+    let (mut alice_group, alice_credential) =
+        new_test_group(ciphersuite, provider, &ds_credential_with_key);
+
+    // The delivery service proposes to add Bob
+    let bob_credential_with_key =
+        generate_credential_with_key("Bob".into(), ciphersuite.signature_algorithm(), provider);
+    let bob_key_package = generate_key_package(
+        ciphersuite,
+        Extensions::empty(),
+        provider,
+        bob_credential_with_key,
+    );
+    let proposal = ExternalProposal::new_add::<Provider>(
+        bob_key_package.key_package().clone(),
+        alice_group.group_id().clone(),
+        alice_group.epoch(),
+        &ds_credential_with_key.signer,
+        SenderExtensionIndex::new(0),
+    )
+    .unwrap();
+    process_and_store_proposal(&mut alice_group, provider, proposal);
+
+    // Alice commits the proposal
+    let (_commit, welcome, _group_info) = alice_group
+        .commit_to_pending_proposals(provider, &alice_credential.signer)
+        .unwrap();
+    alice_group.merge_pending_commit(provider).unwrap();
+    assert_eq!(alice_group.members().count(), 2);
+
+    // Bob joins the group
+    let bob_group = StagedWelcome::new_from_welcome(
+        provider,
+        &MlsGroupJoinConfig::default(),
+        welcome.unwrap().into_welcome().unwrap(),
+        Some(alice_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
+    )
+    .and_then(|staged_welcome| staged_welcome.into_group(provider))
+    .unwrap();
+    assert_eq!(
+        alice_group.epoch_authenticator(),
+        bob_group.epoch_authenticator()
+    );
+}
+
+#[openmls_test]
+fn external_add_proposal_should_fail_when_invalid_external_senders_index() {
+    let ds_credential_with_key = generate_credential_with_key(
+        "delivery-service".into(),
+        ciphersuite.signature_algorithm(),
+        provider,
+    );
+    let (mut alice_group, _alice_credential) =
+        new_test_group(ciphersuite, provider, &ds_credential_with_key);
+
+    let bob_credential_with_key =
+        generate_credential_with_key("Bob".into(), ciphersuite.signature_algorithm(), provider);
+    let bob_key_package = generate_key_package(
+        ciphersuite,
+        Extensions::empty(),
+        provider,
+        bob_credential_with_key,
+    );
+    let proposal: MlsMessageIn = ExternalProposal::new_add::<Provider>(
+        bob_key_package.key_package().clone(),
+        alice_group.group_id().clone(),
+        alice_group.epoch(),
+        &ds_credential_with_key.signer,
+        SenderExtensionIndex::new(10), // invalid sender index
+    )
+    .unwrap()
+    .into();
+
+    let error = alice_group
+        .process_message(
+            provider,
+            proposal.try_into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        ProcessMessageError::ValidationError(ValidationError::UnauthorizedExternalSender)
+    ));
+}
+
+#[openmls_test]
+fn external_reinit_proposal_should_reinit_group() {
+    let ds_credential_with_key = generate_credential_with_key(
+        "delivery-service".into(),
+        ciphersuite.signature_algorithm(),
+        provider,
+    );
+    let (mut alice_group, alice_credential) =
+        new_test_group(ciphersuite, provider, &ds_credential_with_key);
+
+    // The delivery service proposes to reinitialize the group
+    let reinit_proposal = ReInitProposal::new(
+        GroupId::from_slice(b"Reinitialized Group"),
+        ProtocolVersion::Mls10,
+        ciphersuite,
+        Extensions::empty(),
+    );
+    let proposal = ExternalProposal::new_reinit::<Provider>(
+        reinit_proposal.clone(),
+        alice_group.group_id().clone(),
+        alice_group.epoch(),
+        &ds_credential_with_key.signer,
+        SenderExtensionIndex::new(0),
+    )
+    .unwrap();
+    process_and_store_proposal(&mut alice_group, provider, proposal);
+
+    // Alice commits the proposal
+    alice_group
+        .commit_to_pending_proposals(provider, &alice_credential.signer)
+        .unwrap();
+    alice_group.merge_pending_commit(provider).unwrap();
+    assert_eq!(alice_group.reinit_proposal(), Some(&reinit_proposal));
+}
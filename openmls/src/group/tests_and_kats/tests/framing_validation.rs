@@ -5,7 +5,8 @@ use openmls_traits::prelude::{openmls_types::Ciphersuite, *};
 use tls_codec::{Deserialize, Serialize};
 
 use crate::{
-    binary_tree::LeafNodeIndex, framing::*, group::*, key_packages::*, treesync::LeafNodeParameters,
+    binary_tree::LeafNodeIndex, credentials::test_utils::AcceptAllCredentials, framing::*,
+    group::*, key_packages::*, treesync::LeafNodeParameters,
 };
 
 use crate::group::tests_and_kats::utils::{
@@ -91,6 +92,7 @@ fn validation_test_setup(
         mls_group_create_config.join_config(),
         welcome,
         Some(alice_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .expect("error creating bob's group from welcome")
     .into_group(provider)
@@ -142,7 +144,7 @@ fn test_valsem002() {
     let message_in = ProtocolMessage::from(plaintext);
 
     let err = bob_group
-        .process_message(provider, message_in)
+        .process_message(provider, message_in, &AcceptAllCredentials)
         .expect_err("Could parse message despite wrong group ID.");
 
     assert!(matches!(
@@ -152,7 +154,11 @@ fn test_valsem002() {
 
     // Positive case
     bob_group
-        .process_message(provider, ProtocolMessage::from(original_message))
+        .process_message(
+            provider,
+            ProtocolMessage::from(original_message),
+            &AcceptAllCredentials,
+        )
         .expect("Unexpected error.");
 }
 
@@ -183,7 +189,11 @@ fn test_valsem003() {
         .expect("Could not merge commit.");
 
     let processed_message = bob_group
-        .process_message(provider, message.into_protocol_message().unwrap())
+        .process_message(
+            provider,
+            message.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
         .expect("Could not process message.");
 
     if let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
@@ -218,7 +228,7 @@ fn test_valsem003() {
     // Set the epoch too high
     plaintext.set_epoch(current_epoch.as_u64() + 1);
     let err = bob_group
-        .process_message(provider, plaintext.clone())
+        .process_message(provider, plaintext.clone(), &AcceptAllCredentials)
         .expect_err("Could parse message despite wrong epoch.");
     assert!(matches!(
         err,
@@ -228,7 +238,7 @@ fn test_valsem003() {
     // Set the epoch too low
     plaintext.set_epoch(current_epoch.as_u64() - 1);
     let err = bob_group
-        .process_message(provider, plaintext)
+        .process_message(provider, plaintext, &AcceptAllCredentials)
         .expect_err("Could parse message despite wrong epoch.");
     assert!(matches!(
         err,
@@ -237,7 +247,7 @@ fn test_valsem003() {
 
     // Positive case
     let processed_msg = bob_group
-        .process_message(provider, original_message.clone())
+        .process_message(provider, original_message.clone(), &AcceptAllCredentials)
         .unwrap();
 
     if let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
@@ -251,7 +261,8 @@ fn test_valsem003() {
     }
 
     // Processing a commit twice should fail i.e. an epoch can only be used once in a commit message
-    let process_twice = bob_group.process_message(provider, original_message);
+    let process_twice =
+        bob_group.process_message(provider, original_message, &AcceptAllCredentials);
     assert!(matches!(
         process_twice.unwrap_err(),
         ProcessMessageError::ValidationError(ValidationError::WrongEpoch)
@@ -305,7 +316,7 @@ fn test_valsem004() {
     let message_in = ProtocolMessage::from(plaintext);
 
     let err = bob_group
-        .process_message(provider, message_in)
+        .process_message(provider, message_in, &AcceptAllCredentials)
         .expect_err("Could parse message despite wrong sender.");
 
     assert!(matches!(
@@ -315,7 +326,11 @@ fn test_valsem004() {
 
     // Positive case
     bob_group
-        .process_message(provider, ProtocolMessage::from(original_message))
+        .process_message(
+            provider,
+            ProtocolMessage::from(original_message),
+            &AcceptAllCredentials,
+        )
         .expect("Unexpected error.");
 }
 
@@ -365,7 +380,7 @@ fn test_valsem005() {
     let message_in = ProtocolMessage::from(plaintext);
 
     let err = bob_group
-        .process_message(provider, message_in)
+        .process_message(provider, message_in, &AcceptAllCredentials)
         .expect_err("Could parse message despite unencrypted application message.");
 
     assert!(matches!(
@@ -375,7 +390,11 @@ fn test_valsem005() {
 
     // Positive case
     bob_group
-        .process_message(provider, ProtocolMessage::from(original_message))
+        .process_message(
+            provider,
+            ProtocolMessage::from(original_message),
+            &AcceptAllCredentials,
+        )
         .expect("Unexpected error.");
 }
 
@@ -411,7 +430,7 @@ fn test_valsem006() {
     let message_in = ProtocolMessage::from(ciphertext);
 
     let err = bob_group
-        .process_message(provider, message_in)
+        .process_message(provider, message_in, &AcceptAllCredentials)
         .expect_err("Could parse message despite garbled ciphertext.");
 
     assert!(matches!(
@@ -423,7 +442,11 @@ fn test_valsem006() {
 
     // Positive case
     bob_group
-        .process_message(provider, ProtocolMessage::from(original_message))
+        .process_message(
+            provider,
+            ProtocolMessage::from(original_message),
+            &AcceptAllCredentials,
+        )
         .expect("Unexpected error.");
 }
 
@@ -463,7 +486,7 @@ fn test_valsem007() {
     let message_in = ProtocolMessage::from(plaintext);
 
     let err = bob_group
-        .process_message(provider, message_in)
+        .process_message(provider, message_in, &AcceptAllCredentials)
         .expect_err("Could parse message despite missing membership tag.");
 
     assert!(matches!(
@@ -473,7 +496,11 @@ fn test_valsem007() {
 
     // Positive case
     bob_group
-        .process_message(provider, ProtocolMessage::from(original_message))
+        .process_message(
+            provider,
+            ProtocolMessage::from(original_message),
+            &AcceptAllCredentials,
+        )
         .expect("Unexpected error.");
 }
 
@@ -522,7 +549,7 @@ fn test_valsem008() {
     let message_in = ProtocolMessage::from(plaintext);
 
     let err = bob_group
-        .process_message(provider, message_in)
+        .process_message(provider, message_in, &AcceptAllCredentials)
         .expect_err("Could process message despite wrong membership tag.");
 
     assert!(matches!(
@@ -532,7 +559,11 @@ fn test_valsem008() {
 
     // Positive case
     bob_group
-        .process_message(provider, ProtocolMessage::from(original_message))
+        .process_message(
+            provider,
+            ProtocolMessage::from(original_message),
+            &AcceptAllCredentials,
+        )
         .expect("Unexpected error.");
 }
 
@@ -582,7 +613,7 @@ fn test_valsem009() {
     let message_in = ProtocolMessage::from(plaintext);
 
     let err = bob_group
-        .process_message(provider, message_in)
+        .process_message(provider, message_in, &AcceptAllCredentials)
         .expect_err("Could parse message despite missing confirmation tag.");
 
     assert!(matches!(
@@ -592,7 +623,11 @@ fn test_valsem009() {
 
     // Positive case
     bob_group
-        .process_message(provider, ProtocolMessage::from(original_message))
+        .process_message(
+            provider,
+            ProtocolMessage::from(original_message),
+            &AcceptAllCredentials,
+        )
         .expect("Unexpected error.");
 }
 
@@ -644,7 +679,7 @@ fn test_valsem010() {
     let message_in = ProtocolMessage::from(plaintext);
 
     let err = bob_group
-        .process_message(provider, message_in)
+        .process_message(provider, message_in, &AcceptAllCredentials)
         .expect_err("Could process message despite wrong signature.");
 
     assert!(matches!(
@@ -654,6 +689,10 @@ fn test_valsem010() {
 
     // Positive case
     bob_group
-        .process_message(provider, ProtocolMessage::from(original_message))
+        .process_message(
+            provider,
+            ProtocolMessage::from(original_message),
+            &AcceptAllCredentials,
+        )
         .expect("Unexpected error.");
 }
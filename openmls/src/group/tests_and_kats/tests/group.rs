@@ -1,3 +1,4 @@
+use crate::credentials::test_utils::AcceptAllCredentials;
 use crate::{framing::*, group::*, test_utils::*, *};
 use mls_group::tests_and_kats::utils::{setup_alice_bob, setup_alice_bob_group, setup_client};
 use treesync::{node::leaf_node::Capabilities, LeafNodeParameters};
@@ -64,6 +65,7 @@ fn create_commit_optional_path(
         &MlsGroupJoinConfig::default(),
         welcome.into_welcome().unwrap(),
         Some(ratchet_tree.into()),
+        &AcceptAllCredentials,
     )
     .unwrap()
     .into_group(provider)
@@ -183,6 +185,7 @@ fn group_operations() {
         .process_message(
             provider,
             mls_cipertext_alice.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
         )
         .unwrap();
 
@@ -215,7 +218,11 @@ fn group_operations() {
         .expect("error merging commit");
 
     let processed_message = alice_group
-        .process_message(provider, commit_message.into_protocol_message().unwrap())
+        .process_message(
+            provider,
+            commit_message.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
         .unwrap();
     match processed_message.into_content() {
         ProcessedMessageContent::StagedCommitMessage(staged_commit) => {
@@ -253,7 +260,11 @@ fn group_operations() {
         .expect("error merging commit");
 
     let processed_message = bob_group
-        .process_message(provider, commit_message.into_protocol_message().unwrap())
+        .process_message(
+            provider,
+            commit_message.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
         .unwrap();
 
     match processed_message.into_content() {
@@ -280,6 +291,7 @@ fn group_operations() {
         .process_message(
             provider,
             bob_update_proposal.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
         )
         .unwrap()
         .into_content()
@@ -309,7 +321,11 @@ fn group_operations() {
 
     alice_group.merge_pending_commit(provider).unwrap();
 
-    match bob_group.process_message(provider, commit_message.into_protocol_message().unwrap()) {
+    match bob_group.process_message(
+        provider,
+        commit_message.into_protocol_message().unwrap(),
+        &AcceptAllCredentials,
+    ) {
         Ok(processed_message) => match processed_message.into_content() {
             ProcessedMessageContent::StagedCommitMessage(staged_commit) => {
                 bob_group
@@ -337,7 +353,11 @@ fn group_operations() {
 
     bob_group.merge_pending_commit(provider).unwrap();
 
-    match alice_group.process_message(provider, commit_message.into_protocol_message().unwrap()) {
+    match alice_group.process_message(
+        provider,
+        commit_message.into_protocol_message().unwrap(),
+        &AcceptAllCredentials,
+    ) {
         Ok(processed_message) => match processed_message.into_content() {
             ProcessedMessageContent::StagedCommitMessage(staged_commit) => {
                 alice_group
@@ -359,6 +379,7 @@ fn group_operations() {
         &config,
         welcome.into_welcome().unwrap(),
         Some(ratchet_tree.into()),
+        &AcceptAllCredentials,
     )
     .unwrap()
     .into_group(provider)
@@ -387,6 +408,7 @@ fn group_operations() {
                 .clone()
                 .into_protocol_message()
                 .unwrap(),
+            &AcceptAllCredentials,
         )
         .unwrap();
 
@@ -398,6 +420,7 @@ fn group_operations() {
         .process_message(
             provider,
             mls_ciphertext_charlie.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
         )
         .unwrap();
 
@@ -428,6 +451,7 @@ fn group_operations() {
         .process_message(
             provider,
             commit_message.clone().into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
         )
         .unwrap()
         .into_content()
@@ -441,7 +465,11 @@ fn group_operations() {
     };
 
     match bob_group
-        .process_message(provider, commit_message.into_protocol_message().unwrap())
+        .process_message(
+            provider,
+            commit_message.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
         .unwrap()
         .into_content()
     {
@@ -486,6 +514,7 @@ fn group_operations() {
         .process_message(
             provider,
             commit_message.clone().into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
         )
         .unwrap()
         .into_content()
@@ -499,7 +528,11 @@ fn group_operations() {
     };
 
     match bob_group
-        .process_message(provider, commit_message.into_protocol_message().unwrap())
+        .process_message(
+            provider,
+            commit_message.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
         .unwrap()
         .into_content()
     {
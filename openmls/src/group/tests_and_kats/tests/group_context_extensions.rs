@@ -7,6 +7,7 @@ use tls_codec::{Deserialize as _, Serialize as _};
 
 use crate::{
     ciphersuite::hash_ref::ProposalRef,
+    credentials::test_utils::AcceptAllCredentials,
     credentials::CredentialWithKey,
     framing::*,
     group::*,
@@ -138,6 +139,7 @@ fn setup<Provider: crate::storage::OpenMlsProvider + Default>(
         alice.group.configuration(),
         welcome,
         Some(alice.group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .expect("Error creating staged join from Welcome")
     .into_group(&bob_party.provider)
@@ -186,7 +188,7 @@ impl<Provider: crate::storage::OpenMlsProvider> MemberState<Provider> {
 
         let processed_msg = self
             .group
-            .process_message(&self.party.provider, msg)
+            .process_message(&self.party.provider, msg, &AcceptAllCredentials)
             .unwrap_or_else(|err| panic!("error processing message at {}: {err}", self.party.name));
 
         match processed_msg.into_content() {
@@ -212,7 +214,7 @@ impl<Provider: crate::storage::OpenMlsProvider> MemberState<Provider> {
 
         let processed_msg = self
             .group
-            .process_message(&self.party.provider, msg)
+            .process_message(&self.party.provider, msg, &AcceptAllCredentials)
             .unwrap_or_else(|err| panic!("error processing message at {}: {err}", self.party.name));
 
         match processed_msg.into_content() {
@@ -245,7 +247,7 @@ impl<Provider: crate::storage::OpenMlsProvider> MemberState<Provider> {
         );
 
         self.group
-            .process_message(&self.party.provider, msg)
+            .process_message(&self.party.provider, msg, &AcceptAllCredentials)
             .expect_err(&err_msg)
     }
 
@@ -385,6 +387,7 @@ fn self_update_happy_case_simple() {
         alice_group.configuration(),
         welcome,
         Some(alice_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .expect("Error creating staged join from Welcome")
     .into_group(&bob_party.provider)
@@ -402,6 +405,7 @@ fn self_update_happy_case_simple() {
         .process_message(
             &alice_party.provider,
             update_proposal_msg.clone().into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
         )
         .unwrap()
         .into_content()
@@ -420,6 +424,7 @@ fn self_update_happy_case_simple() {
         .process_message(
             &bob_party.provider,
             commit_msg.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
         )
         .unwrap();
 
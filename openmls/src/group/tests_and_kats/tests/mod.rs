@@ -1,12 +1,14 @@
 //! Unit tests for the core group
 
 mod aad;
+mod async_group;
 mod commit_validation;
 mod encoding;
 mod external_add_proposal;
 mod external_commit;
 mod external_commit_validation;
 mod external_remove_proposal;
+mod external_sender_proposals;
 mod framing;
 mod framing_validation;
 mod group;
@@ -2,6 +2,7 @@
 
 use crate::group::tests_and_kats::utils::{generate_credential_with_key, generate_key_package};
 use crate::{
+    credentials::test_utils::AcceptAllCredentials,
     framing::{MessageDecryptionError, MlsMessageIn, ProcessedMessageContent},
     group::*,
     treesync::LeafNodeParameters,
@@ -81,6 +82,7 @@ fn test_past_secrets_in_group<Provider: crate::storage::OpenMlsProvider>(
             mls_group_create_config.join_config(),
             welcome,
             Some(alice_group.export_ratchet_tree().into()),
+            &AcceptAllCredentials,
         )
         .expect("Error creating staged join from Welcome")
         .into_group(bob_provider)
@@ -121,7 +123,11 @@ fn test_past_secrets_in_group<Provider: crate::storage::OpenMlsProvider>(
 
         for update_commit in update_commits {
             let bob_processed_message = bob_group
-                .process_message(bob_provider, update_commit.into_protocol_message().unwrap())
+                .process_message(
+                    bob_provider,
+                    update_commit.into_protocol_message().unwrap(),
+                    &AcceptAllCredentials,
+                )
                 .expect("An unexpected error occurred.");
 
             if let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
@@ -144,7 +150,11 @@ fn test_past_secrets_in_group<Provider: crate::storage::OpenMlsProvider>(
         // The first messages should fail
         for application_message in application_messages.iter().take(max_epochs / 2) {
             let err = bob_group
-                .process_message(bob_provider, application_message.clone())
+                .process_message(
+                    bob_provider,
+                    application_message.clone(),
+                    &AcceptAllCredentials,
+                )
                 .expect_err("An unexpected error occurred.");
             assert!(matches!(
                 err,
@@ -157,7 +167,11 @@ fn test_past_secrets_in_group<Provider: crate::storage::OpenMlsProvider>(
         // The last messages should not fail
         for application_message in application_messages.iter().skip(max_epochs / 2) {
             let bob_processed_message = bob_group
-                .process_message(bob_provider, application_message.clone())
+                .process_message(
+                    bob_provider,
+                    application_message.clone(),
+                    &AcceptAllCredentials,
+                )
                 .expect("An unexpected error occurred.");
 
             if let ProcessedMessageContent::ApplicationMessage(application_message) =
@@ -2,6 +2,7 @@
 //! https://book.openmls.tech/message_validation.html#semantic-validation-of-proposals-covered-by-a-commit
 
 use crate::{
+    credentials::test_utils::AcceptAllCredentials,
     storage::OpenMlsProvider,
     test_utils::frankenstein::*,
     treesync::{errors::LeafNodeValidationError, LeafNodeParameters},
@@ -177,6 +178,7 @@ fn validation_test_setup(
         &mls_group_config,
         welcome,
         Some(alice_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .unwrap()
     .into_group(provider)
@@ -394,7 +396,7 @@ fn test_valsem101a() {
 
     // Have bob process the resulting plaintext
     let err = bob_group
-        .process_message(provider, update_message_in)
+        .process_message(provider, update_message_in, &AcceptAllCredentials)
         .expect_err("Could process message despite modified public key in path.");
 
     assert!(matches!(
@@ -415,6 +417,7 @@ fn test_valsem101a() {
             original_update_plaintext
                 .try_into_protocol_message()
                 .unwrap(),
+            &AcceptAllCredentials,
         )
         .expect("Unexpected error.");
 }
@@ -561,7 +564,7 @@ fn test_valsem102() {
 
     // Have bob process the resulting plaintext
     let err = bob_group
-        .process_message(provider, update_message_in)
+        .process_message(provider, update_message_in, &AcceptAllCredentials)
         .expect_err("Could process message despite modified encryption key in path.");
 
     assert!(matches!(
@@ -582,6 +585,7 @@ fn test_valsem102() {
             original_update_plaintext
                 .try_into_protocol_message()
                 .unwrap(),
+            &AcceptAllCredentials,
         )
         .expect("Unexpected error.");
 }
@@ -813,7 +817,7 @@ fn test_valsem101b() {
             KeyUniqueness::NegativeSameKey => {
                 // Have bob process the resulting plaintext
                 let err = bob_group
-                    .process_message(provider, verifiable_plaintext)
+                    .process_message(provider, verifiable_plaintext, &AcceptAllCredentials)
                     .expect_err("Could process message despite modified public key in path.");
 
                 assert_eq!(
@@ -825,7 +829,7 @@ fn test_valsem101b() {
             }
             KeyUniqueness::PositiveSameKeyWithRemove => {
                 bob_group
-                    .process_message(provider, verifiable_plaintext)
+                    .process_message(provider, verifiable_plaintext, &AcceptAllCredentials)
                     .expect(
                         "Could not process message despite having a remove proposal in the commit",
                     );
@@ -839,7 +843,7 @@ fn test_valsem101b() {
 
         // Positive case
         bob_group
-            .process_message(provider, original_update_plaintext)
+            .process_message(provider, original_update_plaintext, &AcceptAllCredentials)
             .expect("Unexpected error.");
     } */
 }
@@ -971,7 +975,7 @@ fn test_valsem103_valsem104(ciphersuite: Ciphersuite, provider: &impl OpenMlsPro
 
     // Have bob process the resulting plaintext
     let err = bob_group
-        .process_message(provider, update_message_in)
+        .process_message(provider, update_message_in, &AcceptAllCredentials)
         .expect_err("Could process message despite modified public key in path.");
 
     assert!(matches!(
@@ -992,6 +996,7 @@ fn test_valsem103_valsem104(ciphersuite: Ciphersuite, provider: &impl OpenMlsPro
             original_update_plaintext
                 .try_into_protocol_message()
                 .unwrap(),
+            &AcceptAllCredentials,
         )
         .expect("Unexpected error.");
 }
@@ -1042,6 +1047,15 @@ fn test_valsem105() {
     // proposal with the relevant KeyPackage artificially afterwards, so that we
     // can have Bob try to process it.
 
+    // A ciphersuite that's not the one we're testing right now, for key
+    // packages that don't support the ciphersuite of the group.
+    let unsupported_ciphersuite = match ciphersuite {
+        Ciphersuite::MLS_256_DHKEMX448_CHACHA20POLY1305_SHA512_Ed448 => {
+            Ciphersuite::MLS_256_DHKEMX448_AES256GCM_SHA512_Ed448
+        }
+        _ => Ciphersuite::MLS_256_DHKEMX448_CHACHA20POLY1305_SHA512_Ed448,
+    };
+
     // We begin with the creation of KeyPackages
     for key_package_version in [
         KeyPackageTestVersion::WrongCiphersuite,
@@ -1088,7 +1102,7 @@ fn test_valsem105() {
             }
             KeyPackageTestVersion::UnsupportedCiphersuite => {
                 franken_key_package.leaf_node.capabilities.ciphersuites =
-                    vec![Ciphersuite::MLS_256_DHKEMX448_CHACHA20POLY1305_SHA512_Ed448.into()];
+                    vec![unsupported_ciphersuite.into()];
             }
             KeyPackageTestVersion::ValidTestCase => (),
         };
@@ -1125,7 +1139,7 @@ fn test_valsem105() {
                 }
                 KeyPackageTestVersion::UnsupportedCiphersuite => {
                     franken_key_package.leaf_node.capabilities.ciphersuites =
-                        vec![Ciphersuite::MLS_256_DHKEMX448_CHACHA20POLY1305_SHA512_Ed448.into()];
+                        vec![unsupported_ciphersuite.into()];
                 }
                 KeyPackageTestVersion::ValidTestCase => (),
             };
@@ -1255,7 +1269,7 @@ fn test_valsem105() {
 
             // Have bob process the resulting plaintext
             let err = bob_group
-                .process_message(provider, update_message_in)
+                .process_message(provider, update_message_in, &AcceptAllCredentials)
                 .expect_err("Could process message despite injected add proposal.");
 
             match key_package_version {
@@ -1374,6 +1388,7 @@ fn test_valsem105() {
                     original_update_plaintext
                         .try_into_protocol_message()
                         .unwrap(),
+                    &AcceptAllCredentials,
                 )
                 .unwrap();
         }
@@ -1648,7 +1663,7 @@ fn test_valsem108() {
 
     // Have bob process the resulting plaintext
     let err = bob_group
-        .process_message(provider, update_message_in)
+        .process_message(provider, update_message_in, &AcceptAllCredentials)
         .expect_err("Could process message despite modified public key in path.");
 
     assert!(matches!(
@@ -1669,6 +1684,7 @@ fn test_valsem108() {
             original_update_plaintext
                 .try_into_protocol_message()
                 .unwrap(),
+            &AcceptAllCredentials,
         )
         .expect("Unexpected error.");
 }
@@ -1768,7 +1784,7 @@ fn test_valsem110() {
 
     // Have Alice process this proposal.
     if let ProcessedMessageContent::ProposalMessage(proposal) = alice_group
-        .process_message(provider, protocol_message)
+        .process_message(provider, protocol_message, &AcceptAllCredentials)
         .expect("error processing proposal")
         .into_content()
     {
@@ -1851,7 +1867,7 @@ fn test_valsem110() {
 
     // Have bob process the resulting plaintext
     let err = bob_group
-        .process_message(provider, update_message_in)
+        .process_message(provider, update_message_in, &AcceptAllCredentials)
         .expect_err("Could process message despite modified public key in path.");
 
     assert!(matches!(
@@ -1950,7 +1966,7 @@ fn test_valsem111() {
 
     // Have bob process the resulting plaintext
     let err = bob_group
-        .process_message(provider, update_message_in)
+        .process_message(provider, update_message_in, &AcceptAllCredentials)
         .expect_err("Could process message despite modified public key in path.");
 
     assert!(matches!(
@@ -2020,7 +2036,7 @@ fn test_valsem111() {
 
     // Have bob process the resulting plaintext
     let err = bob_group
-        .process_message(provider, update_message_in)
+        .process_message(provider, update_message_in, &AcceptAllCredentials)
         .expect_err("Could process message despite modified public key in path.");
 
     assert!(matches!(
@@ -2041,6 +2057,7 @@ fn test_valsem111() {
             original_update_plaintext
                 .try_into_protocol_message()
                 .unwrap(),
+            &AcceptAllCredentials,
         )
         .expect("Unexpected error.");
 }
@@ -2095,7 +2112,7 @@ fn test_valsem112() {
 
     // Have bob process the resulting plaintext
     let err = bob_group
-        .process_message(provider, update_message_in)
+        .process_message(provider, update_message_in, &AcceptAllCredentials)
         .expect_err("Could parse message despite modified public key in path.");
 
     assert!(matches!(
@@ -2109,7 +2126,11 @@ fn test_valsem112() {
 
     // Positive case
     bob_group
-        .process_message(provider, ProtocolMessage::from(original_plaintext))
+        .process_message(
+            provider,
+            ProtocolMessage::from(original_plaintext),
+            &AcceptAllCredentials,
+        )
         .expect("Unexpected error.");
 }
 
@@ -2194,6 +2215,7 @@ fn valsem113() {
             &mls_group_config,
             welcome.into_welcome().unwrap(),
             Some(alice_group.export_ratchet_tree().into()),
+            &AcceptAllCredentials,
         )
         .unwrap();
 
@@ -2231,6 +2253,7 @@ fn valsem113() {
             .process_message(
                 provider,
                 custom_proposal_message.into_protocol_message().unwrap(),
+                &AcceptAllCredentials,
             )
             .unwrap();
 
@@ -2243,7 +2266,11 @@ fn valsem113() {
             panic!("Unexpected message type");
         }
 
-        let result = bob_group.process_message(provider, commit.into_protocol_message().unwrap());
+        let result = bob_group.process_message(
+            provider,
+            commit.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        );
 
         // If the proposal is unsupported, we expect an error here.
         let _processed_message = if matches!(test_mode, TestMode::Unsupported) {
@@ -2387,7 +2414,11 @@ fn test_valsem401_valsem402() {
 
         for psk_proposal in proposals.into_iter() {
             let processed_message = bob_group
-                .process_message(&bob_provider, psk_proposal.into_protocol_message().unwrap())
+                .process_message(
+                    &bob_provider,
+                    psk_proposal.into_protocol_message().unwrap(),
+                    &AcceptAllCredentials,
+                )
                 .unwrap();
 
             match processed_message.into_content() {
@@ -2403,7 +2434,11 @@ fn test_valsem401_valsem402() {
         assert_eq!(
             expected_error,
             bob_group
-                .process_message(&bob_provider, commit.into_protocol_message().unwrap())
+                .process_message(
+                    &bob_provider,
+                    commit.into_protocol_message().unwrap(),
+                    &AcceptAllCredentials
+                )
                 .unwrap_err(),
         );
 
@@ -1,5 +1,6 @@
 //! This module tests the classification of remove operations with RemoveOperation
 
+use crate::credentials::test_utils::AcceptAllCredentials;
 use crate::group::tests_and_kats::utils::{generate_credential_with_key, generate_key_package};
 use crate::{framing::*, group::*};
 use openmls_traits::prelude::*;
@@ -88,6 +89,7 @@ fn remove_blank() {
         mls_group_create_config.join_config(),
         welcome.clone(),
         Some(alice_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .expect("Error creating staged join from Welcome")
     .into_group(bob_provider)
@@ -207,6 +209,7 @@ fn test_remove_operation_variants() {
             mls_group_create_config.join_config(),
             welcome.clone(),
             Some(alice_group.export_ratchet_tree().into()),
+            &AcceptAllCredentials,
         )
         .expect("Error creating staged join from Welcome")
         .into_group(provider)
@@ -217,6 +220,7 @@ fn test_remove_operation_variants() {
             mls_group_create_config.join_config(),
             welcome,
             Some(alice_group.export_ratchet_tree().into()),
+            &AcceptAllCredentials,
         )
         .expect("Error creating staged join from Welcome")
         .into_group(provider)
@@ -247,7 +251,11 @@ fn test_remove_operation_variants() {
                 // Alice & Charlie store the pending proposal
                 for group in [&mut alice_group, &mut charlie_group] {
                     let processed_message = group
-                        .process_message(provider, message.clone().into_protocol_message().unwrap())
+                        .process_message(
+                            provider,
+                            message.clone().into_protocol_message().unwrap(),
+                            &AcceptAllCredentials,
+                        )
                         .expect("Could not process message.");
 
                     match processed_message.into_content() {
@@ -308,7 +316,11 @@ fn test_remove_operation_variants() {
         // === Remove operation from Bob's perspective ===
 
         let bob_processed_message = bob_group
-            .process_message(provider, message.clone().into_protocol_message().unwrap())
+            .process_message(
+                provider,
+                message.clone().into_protocol_message().unwrap(),
+                &AcceptAllCredentials,
+            )
             .expect("Could not process message.");
 
         match bob_processed_message.into_content() {
@@ -361,7 +373,7 @@ fn test_remove_operation_variants() {
         let protocol_message = message.into_protocol_message().unwrap();
 
         let charlie_processed_message = charlie_group
-            .process_message(provider, protocol_message)
+            .process_message(provider, protocol_message, &AcceptAllCredentials)
             .expect("Could not process message.");
 
         match charlie_processed_message.into_content() {
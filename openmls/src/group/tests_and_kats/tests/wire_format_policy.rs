@@ -1,5 +1,6 @@
 //! This module tests the different values for `WireFormatPolicy`
 
+use crate::credentials::test_utils::AcceptAllCredentials;
 use openmls_traits::{signatures::Signer, types::Ciphersuite};
 
 use crate::{framing::*, group::*, treesync::LeafNodeParameters};
@@ -80,10 +81,16 @@ fn receive_message(
         .into_welcome()
         .expect("expected message to be a welcome");
 
-    let mut bob_group = StagedWelcome::new_from_welcome(provider, &mls_group_config, welcome, None)
-        .expect("error creating bob's staged join from welcome")
-        .into_group(provider)
-        .expect("error creating bob's group from staged join");
+    let mut bob_group = StagedWelcome::new_from_welcome(
+        provider,
+        &mls_group_config,
+        welcome,
+        None,
+        &AcceptAllCredentials,
+    )
+    .expect("error creating bob's staged join from welcome")
+    .into_group(provider)
+    .expect("error creating bob's group from staged join");
 
     let (message, _welcome, _group_info) = bob_group
         .self_update(
@@ -111,7 +118,11 @@ fn test_wire_policy_positive(
             &alice_credential_with_key_and_signer.signer,
         );
         alice_group
-            .process_message(provider, message.try_into_protocol_message().unwrap())
+            .process_message(
+                provider,
+                message.try_into_protocol_message().unwrap(),
+                &AcceptAllCredentials,
+            )
             .expect("An unexpected error occurred.");
     }
 }
@@ -143,7 +154,11 @@ fn test_wire_policy_negative(
             &alice_credential_with_key_and_signer.signer,
         );
         let err = alice_group
-            .process_message(provider, message.try_into_protocol_message().unwrap())
+            .process_message(
+                provider,
+                message.try_into_protocol_message().unwrap(),
+                &AcceptAllCredentials,
+            )
             .expect_err("An unexpected error occurred.");
         assert!(matches!(err, ProcessMessageError::IncompatibleWireFormat));
     }
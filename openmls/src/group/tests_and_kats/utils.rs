@@ -4,7 +4,13 @@
 //! Most tests require to set up groups, clients, credentials, and identities.
 //! This module implements helpers to do that.
 
-use std::{cell::RefCell, collections::HashMap};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    future::Future,
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
 
 use openmls_basic_credential::SignatureKeyPair;
 use openmls_traits::{signatures::Signer, types::SignatureScheme};
@@ -12,8 +18,8 @@ use rand::{rngs::OsRng, RngCore};
 use tls_codec::Serialize;
 
 use crate::{
-    ciphersuite::signable::Signable, credentials::*, framing::*, group::*, key_packages::*,
-    messages::ConfirmationTag, test_utils::*, *,
+    ciphersuite::signable::Signable, credentials::test_utils::AcceptAllCredentials, credentials::*,
+    framing::*, group::*, key_packages::*, messages::ConfirmationTag, test_utils::*, *,
 };
 
 use self::storage::OpenMlsProvider;
@@ -191,6 +197,7 @@ pub(crate) fn setup(
                     &join_config,
                     welcome.clone(),
                     ratchet_tree,
+                    &AcceptAllCredentials,
                 )
                 .unwrap()
                 .into_group(provider)
@@ -361,3 +368,16 @@ pub(crate) fn resign_external_commit(
 
     public_message.into()
 }
+
+/// Polls the future until it is ready. This is enough for the futures of an
+/// [`AsyncMlsGroup`](crate::group::AsyncMlsGroup) on a synchronous storage,
+/// because they never wait.
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
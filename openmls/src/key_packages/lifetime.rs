@@ -87,6 +87,20 @@ impl Lifetime {
         }
     }
 
+    /// Returns true if the "not after" timestamp of this lifetime has passed.
+    pub fn has_expired(&self) -> bool {
+        match SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+        {
+            Ok(elapsed) => elapsed >= self.not_after,
+            Err(_) => {
+                log::error!("SystemTime before UNIX EPOCH.");
+                false
+            }
+        }
+    }
+
     /// ValSem(openmls/annotations#32):
     /// Applications MUST define a maximum total lifetime that is acceptable for a LeafNode,
     /// and reject any LeafNode where the total lifetime is longer than this duration.
@@ -122,11 +136,13 @@ mod tests {
         // A freshly created extensions must be valid.
         let ext = Lifetime::default();
         assert!(ext.is_valid());
+        assert!(!ext.has_expired());
 
         // An extension without lifetime is invalid (waiting for 1 second).
         let ext = Lifetime::new(0);
         std::thread::sleep(std::time::Duration::from_secs(1));
         assert!(!ext.is_valid());
+        assert!(ext.has_expired());
 
         // Test (de)serializing invalid extension
         let serialized = ext
@@ -118,6 +118,23 @@
 //!    _ => unreachable!("Unexpected message type."),
 //! };
 //!
+//! // Maxim validates the credentials of the group members with the
+//! // authentication service of the application. This one only checks that
+//! // every member uses a basic credential.
+//! struct BasicCredentialsOnly;
+//!
+//! impl AuthenticationService for BasicCredentialsOnly {
+//!     fn authenticate(
+//!         &self,
+//!         credential: &CredentialWithKey,
+//!         _source: CredentialSource,
+//!     ) -> Result<(), String> {
+//!         BasicCredential::try_from(credential.credential.clone())
+//!             .map(|_| ())
+//!             .map_err(|e| e.to_string())
+//!     }
+//! }
+//!
 //! // Now Maxim can build a staged join for the group in order to inspect the welcome
 //! let maxim_staged_join = StagedWelcome::new_from_welcome(
 //!     provider,
@@ -126,6 +143,7 @@
 //!     // The public tree is need and transferred out of band.
 //!     // It is also possible to use the [`RatchetTreeExtension`]
 //!     Some(sasha_group.export_ratchet_tree().into()),
+//!     &BasicCredentialsOnly,
 //! )
 //! .expect("Error creating a staged join from Welcome");
 //!
@@ -1,16 +1,16 @@
 //! External Proposals
 //!
-//! Contains the types and methods to build external proposal to add/remove a client from a MLS group
-//!
-//! `Add` (from external sender), `Remove` & `ReInit are not yet implemented`
+//! Contains the types and methods to build external proposals to add/remove a client to/from an MLS
+//! group or to reinitialize an MLS group
 
 use crate::{
     binary_tree::LeafNodeIndex,
     extensions::SenderExtensionIndex,
     framing::{mls_auth_content::AuthenticatedContent, MlsMessageOut, PublicMessage},
     group::{
-        errors::ProposeRemoveMemberError, mls_group::errors::ProposeAddMemberError, GroupEpoch,
-        GroupId,
+        errors::{ProposalError, ProposeRemoveMemberError},
+        mls_group::errors::ProposeAddMemberError,
+        GroupEpoch, GroupId,
     },
     key_packages::KeyPackage,
     messages::{AddProposal, Proposal},
@@ -18,7 +18,7 @@ use crate::{
 };
 use openmls_traits::signatures::Signer;
 
-use super::proposals::RemoveProposal;
+use super::proposals::{ReInitProposal, RemoveProposal};
 
 /// External Add Proposal where sender is [NewMemberProposal](crate::prelude::Sender::NewMemberProposal). A client
 /// outside the group can request joining the group. This proposal should then be committed by a
@@ -27,7 +27,7 @@ use super::proposals::RemoveProposal;
 pub struct JoinProposal;
 
 /// External Proposal where sender is [External](crate::prelude::Sender::External). A party
-/// outside the group can request to add or remove a member to the group. This proposal should then
+/// outside the group can request to add or remove a member to the group or to reinitialize the group. This proposal should then
 /// be committed by a group member. The sender must be pre configured within the group through the [crate::extensions::ExternalSendersExtension]
 pub struct ExternalProposal;
 
@@ -60,6 +60,35 @@ impl JoinProposal {
 }
 
 impl ExternalProposal {
+    /// Creates an external Add proposal. For delivery services requesting to add a client.
+    /// This proposal will have to be committed later by a group member.
+    ///
+    /// # Arguments
+    /// * `key_package` - of the client to add
+    /// * `group_id` - unique group identifier of the group to join
+    /// * `epoch` - group's epoch
+    /// * `signer` - of the sender to sign the message
+    /// * `sender` - index of the sender of the proposal (in the [crate::extensions::ExternalSendersExtension] array
+    ///   from the Group Context)
+    pub fn new_add<Provider: OpenMlsProvider>(
+        key_package: KeyPackage,
+        group_id: GroupId,
+        epoch: GroupEpoch,
+        signer: &impl Signer,
+        sender_index: SenderExtensionIndex,
+    ) -> Result<MlsMessageOut, ProposeAddMemberError<Provider::StorageError>> {
+        AuthenticatedContent::new_external_proposal(
+            Proposal::Add(AddProposal { key_package }),
+            group_id,
+            epoch,
+            signer,
+            sender_index,
+        )
+        .map(PublicMessage::from)
+        .map(MlsMessageOut::from)
+        .map_err(ProposeAddMemberError::from)
+    }
+
     /// Creates an external Remove proposal. For delivery services requesting to remove a client.
     /// This proposal will have to be committed later by a group member.
     ///
@@ -88,4 +117,33 @@ impl ExternalProposal {
         .map(MlsMessageOut::from)
         .map_err(ProposeRemoveMemberError::from)
     }
+
+    /// Creates an external ReInit proposal. For delivery services requesting to reinitialize the
+    /// group. This proposal will have to be committed later by a group member.
+    ///
+    /// # Arguments
+    /// * `reinit_proposal` - parameters of the new group
+    /// * `group_id` - unique group identifier of the group to reinitialize
+    /// * `epoch` - group's epoch
+    /// * `signer` - of the sender to sign the message
+    /// * `sender` - index of the sender of the proposal (in the [crate::extensions::ExternalSendersExtension] array
+    ///   from the Group Context)
+    pub fn new_reinit<Provider: OpenMlsProvider>(
+        reinit_proposal: ReInitProposal,
+        group_id: GroupId,
+        epoch: GroupEpoch,
+        signer: &impl Signer,
+        sender_index: SenderExtensionIndex,
+    ) -> Result<MlsMessageOut, ProposalError<Provider::StorageError>> {
+        AuthenticatedContent::new_external_proposal(
+            Proposal::ReInit(reinit_proposal),
+            group_id,
+            epoch,
+            signer,
+            sender_index,
+        )
+        .map(PublicMessage::from)
+        .map(MlsMessageOut::from)
+        .map_err(ProposalError::from)
+    }
 }
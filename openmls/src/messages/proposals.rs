@@ -14,7 +14,7 @@ use tls_codec::{
 
 use crate::{
     binary_tree::array_representation::LeafNodeIndex,
-    ciphersuite::hash_ref::{make_proposal_ref, KeyPackageRef, ProposalRef},
+    ciphersuite::hash_ref::{make_proposal_ref, ProposalRef},
     error::LibraryError,
     extensions::Extensions,
     framing::{
@@ -68,6 +68,7 @@ use crate::{
 /// | Value  | Name    | Recommended | Path Required | Reference | Notes                        |
 /// |:=======|:========|:============|:==============|:==========|:=============================|
 /// | 0x0008 | app_ack | Y           | Y             | RFC XXXX  | draft-ietf-mls-extensions-00 |
+/// | 0x000A | self_remove | Y       | N             | RFC XXXX  | draft-ietf-mls-extensions-05 |
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Serialize, Deserialize, Hash)]
 #[allow(missing_docs)]
 pub enum ProposalType {
@@ -79,6 +80,7 @@ pub enum ProposalType {
     ExternalInit,
     GroupContextExtensions,
     AppAck,
+    SelfRemove,
     Custom(u16),
 }
 
@@ -93,7 +95,7 @@ impl ProposalType {
             | ProposalType::Reinit
             | ProposalType::ExternalInit
             | ProposalType::GroupContextExtensions => true,
-            ProposalType::AppAck | ProposalType::Custom(_) => false,
+            ProposalType::AppAck | ProposalType::SelfRemove | ProposalType::Custom(_) => false,
         }
     }
 }
@@ -157,6 +159,7 @@ impl From<u16> for ProposalType {
             6 => ProposalType::ExternalInit,
             7 => ProposalType::GroupContextExtensions,
             8 => ProposalType::AppAck,
+            10 => ProposalType::SelfRemove,
             other => ProposalType::Custom(other),
         }
     }
@@ -173,6 +176,7 @@ impl From<ProposalType> for u16 {
             ProposalType::ExternalInit => 6,
             ProposalType::GroupContextExtensions => 7,
             ProposalType::AppAck => 8,
+            ProposalType::SelfRemove => 10,
             ProposalType::Custom(id) => id,
         }
     }
@@ -213,6 +217,7 @@ pub enum Proposal {
     // TODO(#916): `AppAck` is not in draft-ietf-mls-protocol-17 but
     //             was moved to `draft-ietf-mls-extensions-00`.
     AppAck(AppAckProposal),
+    SelfRemove(SelfRemoveProposal),
     Custom(CustomProposal),
 }
 
@@ -228,6 +233,7 @@ impl Proposal {
             Proposal::ExternalInit(_) => ProposalType::ExternalInit,
             Proposal::GroupContextExtensions(_) => ProposalType::GroupContextExtensions,
             Proposal::AppAck(_) => ProposalType::AppAck,
+            Proposal::SelfRemove(_) => ProposalType::SelfRemove,
             Proposal::Custom(CustomProposal {
                 proposal_type,
                 payload: _,
@@ -398,6 +404,44 @@ pub struct ReInitProposal {
     pub(crate) extensions: Extensions,
 }
 
+impl ReInitProposal {
+    /// Create a new ReInit proposal for a successor group with the given
+    /// parameters.
+    pub fn new(
+        group_id: GroupId,
+        version: ProtocolVersion,
+        ciphersuite: Ciphersuite,
+        extensions: Extensions,
+    ) -> Self {
+        Self {
+            group_id,
+            version,
+            ciphersuite,
+            extensions,
+        }
+    }
+
+    /// Returns the [`GroupId`] of the new group.
+    pub fn group_id(&self) -> &GroupId {
+        &self.group_id
+    }
+
+    /// Returns the [`ProtocolVersion`] of the new group.
+    pub fn version(&self) -> ProtocolVersion {
+        self.version
+    }
+
+    /// Returns the [`Ciphersuite`] of the new group.
+    pub fn ciphersuite(&self) -> Ciphersuite {
+        self.ciphersuite
+    }
+
+    /// Returns the group context [`Extensions`] of the new group.
+    pub fn extensions(&self) -> &Extensions {
+        &self.extensions
+    }
+}
+
 /// ExternalInit Proposal.
 ///
 /// An ExternalInit proposal is used by new members that want to join a group by using an external
@@ -440,14 +484,21 @@ impl From<Vec<u8>> for ExternalInitProposal {
     }
 }
 
-// TODO: #291 Implement AppAck
-
 /// AppAck Proposal.
 ///
-/// This is not yet supported.
+/// An AppAck proposal is used to acknowledge the receipt of application
+/// messages in the current epoch.
+///
+/// ```c
+/// // draft-ietf-mls-extensions-00
+/// struct {
+///     MessageRange received_ranges<V>;
+/// } AppAck;
+/// ```
 #[derive(
     Debug,
     PartialEq,
+    Eq,
     Clone,
     Serialize,
     Deserialize,
@@ -460,6 +511,50 @@ pub struct AppAckProposal {
     received_ranges: Vec<MessageRange>,
 }
 
+impl AppAckProposal {
+    /// Create a new [`AppAckProposal`] from the ranges of received messages.
+    pub fn new(received_ranges: Vec<MessageRange>) -> Self {
+        Self { received_ranges }
+    }
+
+    /// Returns the ranges of received messages.
+    pub fn received_ranges(&self) -> &[MessageRange] {
+        &self.received_ranges
+    }
+}
+
+/// SelfRemove Proposal.
+///
+/// A SelfRemove proposal requests that the sender of the proposal be removed
+/// from the group. It is used by members that want to leave the group and can
+/// be committed by any other member.
+///
+/// ```c
+/// // draft-ietf-mls-extensions-05
+/// struct {} SelfRemove;
+/// ```
+#[derive(
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Clone,
+    Serialize,
+    Deserialize,
+    TlsDeserialize,
+    TlsDeserializeBytes,
+    TlsSerialize,
+    TlsSize,
+)]
+pub struct SelfRemoveProposal {}
+
+impl SelfRemoveProposal {
+    /// Create a new [`SelfRemoveProposal`].
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
 /// GroupContextExtensions Proposal.
 ///
 /// A GroupContextExtensions proposal is used to update the list of extensions in the GroupContext
@@ -604,10 +699,14 @@ impl ProposalRef {
     }
 }
 
-/// TODO: #291 Implement AppAck
-/// ```text
+/// A range of application message generations of a single sender, used in
+/// [`AppAckProposal`]s. Both `first_generation` and `last_generation` are
+/// inclusive.
+///
+/// ```c
+/// // draft-ietf-mls-extensions-00
 /// struct {
-///     KeyPackageRef sender;
+///     uint32 sender;
 ///     uint32 first_generation;
 ///     uint32 last_generation;
 /// } MessageRange;
@@ -615,7 +714,9 @@ impl ProposalRef {
 #[derive(
     Debug,
     PartialEq,
+    Eq,
     Clone,
+    Copy,
     Serialize,
     Deserialize,
     TlsDeserialize,
@@ -623,12 +724,47 @@ impl ProposalRef {
     TlsSerialize,
     TlsSize,
 )]
-pub(crate) struct MessageRange {
-    sender: KeyPackageRef,
+pub struct MessageRange {
+    sender: LeafNodeIndex,
     first_generation: u32,
     last_generation: u32,
 }
 
+impl MessageRange {
+    /// Create a new [`MessageRange`] for the messages with the generations
+    /// `first_generation` to `last_generation` (inclusive) sent by `sender`.
+    pub fn new(sender: LeafNodeIndex, first_generation: u32, last_generation: u32) -> Self {
+        Self {
+            sender,
+            first_generation,
+            last_generation,
+        }
+    }
+
+    /// Returns the leaf index of the sender of the messages.
+    pub fn sender(&self) -> LeafNodeIndex {
+        self.sender
+    }
+
+    /// Returns the generation of the first message in the range.
+    pub fn first_generation(&self) -> u32 {
+        self.first_generation
+    }
+
+    /// Returns the generation of the last message in the range.
+    pub fn last_generation(&self) -> u32 {
+        self.last_generation
+    }
+
+    /// Returns `true` if the range contains the message with the given
+    /// `sender` and `generation`.
+    pub fn contains(&self, sender: LeafNodeIndex, generation: u32) -> bool {
+        self.sender == sender
+            && self.first_generation <= generation
+            && generation <= self.last_generation
+    }
+}
+
 /// A custom proposal with semantics to be implemented by the application.
 #[derive(
     Debug,
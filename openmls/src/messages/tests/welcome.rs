@@ -7,6 +7,7 @@ use crate::{
     ciphersuite::{
         hash_ref::KeyPackageRef, hpke, signable::Signable, AeadKey, AeadNonce, Mac, Secret,
     },
+    credentials::test_utils::AcceptAllCredentials,
     extensions::Extensions,
     group::{
         errors::WelcomeError, mls_group::tests_and_kats::utils::setup_client, GroupContext,
@@ -158,6 +159,7 @@ fn test_welcome_context_mismatch(
         mls_group_create_config.join_config(),
         welcome,
         Some(alice_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .expect_err("Created a staged join from an invalid Welcome.");
 
@@ -182,6 +184,7 @@ fn test_welcome_context_mismatch(
         mls_group_create_config.join_config(),
         original_welcome,
         Some(alice_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .expect("Error creating staged join from a valid Welcome.")
     .into_group(provider)
@@ -364,7 +367,11 @@ fn test_welcome_processing() {
 
     // Stage the welcome
     let staged_welcome = processed_welcome
-        .into_staged_welcome(provider, Some(alice_group.export_ratchet_tree().into()))
+        .into_staged_welcome(
+            provider,
+            Some(alice_group.export_ratchet_tree().into()),
+            &AcceptAllCredentials,
+        )
         .unwrap();
     let _group = staged_welcome
         .into_group(provider)
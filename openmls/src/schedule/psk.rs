@@ -6,7 +6,7 @@ use tls_codec::{Serialize as TlsSerializeTrait, VLBytes};
 
 use super::*;
 use crate::{
-    group::{GroupEpoch, GroupId},
+    group::{GroupContext, GroupEpoch, GroupId},
     schedule::psk::store::ResumptionPskStore,
     storage::{OpenMlsProvider, StorageProvider},
 };
@@ -296,13 +296,19 @@ impl PreSharedKeyId {
 
     // ----- Validation ----------------------------------------------------------------------------
 
-    pub(crate) fn validate_in_proposal(self, ciphersuite: Ciphersuite) -> Result<Self, PskError> {
+    /// Validate this `PreSharedKeyId` when used in a proposal. Resumption PSKs
+    /// must have one of the `allowed_usages`.
+    pub(crate) fn validate_in_proposal(
+        self,
+        ciphersuite: Ciphersuite,
+        allowed_usages: &[ResumptionPskUsage],
+    ) -> Result<Self, PskError> {
         // ValSem402
         match self.psk() {
             Psk::Resumption(resumption_psk) => {
-                if resumption_psk.usage != ResumptionPskUsage::Application {
+                if !allowed_usages.contains(&resumption_psk.usage) {
                     return Err(PskError::UsageMismatch {
-                        allowed: vec![ResumptionPskUsage::Application],
+                        allowed: allowed_usages.to_vec(),
                         got: resumption_psk.usage,
                     });
                 }
@@ -458,8 +464,22 @@ pub(crate) fn load_psks<'p, Storage: StorageProvider>(
 
         match &psk_id.psk {
             Psk::Resumption(resumption) => {
-                if let Some(psk_bundle) = resumption_psk_store.get(resumption.psk_epoch()) {
-                    psk_bundles.push((psk_id, psk_bundle.secret.clone()));
+                let secret = match resumption.usage() {
+                    ResumptionPskUsage::Application => resumption_psk_store
+                        .get(resumption.psk_epoch())
+                        .map(|psk_bundle| psk_bundle.secret.clone()),
+                    // Reinit and branch PSKs are taken from the group the new
+                    // group is created from.
+                    ResumptionPskUsage::Reinit | ResumptionPskUsage::Branch => {
+                        load_group_resumption_psk(
+                            storage,
+                            resumption.psk_group_id(),
+                            resumption.psk_epoch(),
+                        )?
+                    }
+                };
+                if let Some(secret) = secret {
+                    psk_bundles.push((psk_id, secret));
                 } else {
                     return Err(PskError::KeyNotFound);
                 }
@@ -480,6 +500,36 @@ pub(crate) fn load_psks<'p, Storage: StorageProvider>(
     Ok(psk_bundles)
 }
 
+/// Loads the resumption PSK of the group with the given id from the `storage`.
+///
+/// The PSK is taken from the resumption PSK store of the group, or from its
+/// current epoch secrets if `epoch` is the current epoch of the group. The
+/// latter is the case for the PSK of a reinit or branch, so that these work
+/// even if the group doesn't keep past resumption PSKs.
+fn load_group_resumption_psk<Storage: StorageProvider>(
+    storage: &Storage,
+    group_id: &GroupId,
+    epoch: GroupEpoch,
+) -> Result<Option<Secret>, PskError> {
+    let psk_store: Option<ResumptionPskStore> = storage
+        .resumption_psk_store(group_id)
+        .map_err(|_| PskError::Storage)?;
+    if let Some(psk) = psk_store.as_ref().and_then(|store| store.get(epoch)) {
+        return Ok(Some(psk.secret.clone()));
+    }
+
+    let group_context: Option<GroupContext> = storage
+        .group_context(group_id)
+        .map_err(|_| PskError::Storage)?;
+    if group_context.map(|context| context.epoch()) != Some(epoch) {
+        return Ok(None);
+    }
+    let epoch_secrets: Option<GroupEpochSecrets> = storage
+        .group_epoch_secrets(group_id)
+        .map_err(|_| PskError::Storage)?;
+    Ok(epoch_secrets.map(|secrets| secrets.resumption_psk().secret.clone()))
+}
+
 /// This module contains a store that can hold a rollover list of resumption PSKs.
 pub mod store {
     use serde::{Deserialize, Serialize};
@@ -495,6 +545,11 @@ pub mod store {
         max_number_of_secrets: usize,
         resumption_psk: Vec<(GroupEpoch, ResumptionPskSecret)>,
         cursor: usize,
+        // The time at which the epochs of the stored resumption PSKs were
+        // first seen to be over. Only recorded if the group has a maximum age
+        // for past epochs.
+        #[serde(default)]
+        retired_at: Vec<(GroupEpoch, u64)>,
     }
 
     impl ResumptionPskStore {
@@ -504,6 +559,7 @@ pub mod store {
                 max_number_of_secrets,
                 resumption_psk: vec![],
                 cursor: 0,
+                retired_at: vec![],
             }
         }
 
@@ -520,6 +576,34 @@ pub mod store {
                 self.cursor += 1;
                 self.cursor %= self.resumption_psk.len();
                 self.resumption_psk[self.cursor] = item;
+                self.remove_stale_retirements();
+            }
+        }
+
+        /// Deletes the resumption PSKs of epochs before `current_epoch` that
+        /// ended more than `max_age` seconds before `now`. Epochs whose end
+        /// hasn't been recorded yet are recorded as ended at `now`.
+        pub(crate) fn expire(&mut self, current_epoch: GroupEpoch, now: u64, max_age: u64) {
+            let unrecorded: Vec<GroupEpoch> = self
+                .resumption_psk
+                .iter()
+                .map(|(epoch, _)| *epoch)
+                .filter(|epoch| *epoch < current_epoch && self.retired_at(*epoch).is_none())
+                .collect();
+            self.retired_at
+                .extend(unrecorded.into_iter().map(|epoch| (epoch, now)));
+
+            let expired: Vec<GroupEpoch> = self
+                .retired_at
+                .iter()
+                .filter(|(_, retired_at)| now.saturating_sub(*retired_at) > max_age)
+                .map(|(epoch, _)| *epoch)
+                .collect();
+            if !expired.is_empty() {
+                self.resumption_psk
+                    .retain(|(epoch, _)| !expired.contains(epoch));
+                self.cursor = self.resumption_psk.len();
+                self.remove_stale_retirements();
             }
         }
 
@@ -531,6 +615,28 @@ pub mod store {
                 .find(|&(e, _s)| e == &epoch)
                 .map(|(_e, s)| s)
         }
+
+        /// Returns the epochs for which resumption PSKs are stored, together
+        /// with the time at which they ended, if it was recorded.
+        pub(crate) fn epochs(&self) -> impl Iterator<Item = (GroupEpoch, Option<u64>)> + '_ {
+            self.resumption_psk
+                .iter()
+                .map(|(epoch, _)| (*epoch, self.retired_at(*epoch)))
+        }
+
+        fn retired_at(&self, epoch: GroupEpoch) -> Option<u64> {
+            self.retired_at
+                .iter()
+                .find(|(e, _)| *e == epoch)
+                .map(|(_, retired_at)| *retired_at)
+        }
+
+        /// Forgets the end of epochs that are no longer in the store.
+        fn remove_stale_retirements(&mut self) {
+            let resumption_psk = &self.resumption_psk;
+            self.retired_at
+                .retain(|(epoch, _)| resumption_psk.iter().any(|(e, _)| e == epoch));
+        }
     }
 
     #[cfg(test)]
@@ -16,7 +16,7 @@ use crate::{
     ciphersuite::hash_ref::ProposalRef,
     group::{GroupContext, GroupId, InterimTranscriptHash},
     messages::ConfirmationTag,
-    treesync::{LeafNode, TreeSync},
+    treesync::{LeafNode, Node, StoredTreeSync, TreeSync},
 };
 use crate::{
     group::{past_secrets::MessageSecretsStore, GroupEpoch},
@@ -28,8 +28,16 @@ use crate::{
     treesync::{node::encryption_keys::EncryptionKeyPair, EncryptionKey},
 };
 
+pub(crate) mod cache;
+pub mod export;
+pub mod gc;
+#[cfg(test)]
+mod kat_migration;
 #[cfg(test)]
 pub mod kat_storage_stability;
+pub mod migration;
+
+pub use cache::StorageCacheError;
 
 /// A convenience trait for the current version of the storage.
 /// Throughout the code, this one should be used instead of `openmls_traits::storage::StorageProvider`.
@@ -49,6 +57,42 @@ impl<P: openmls_traits::public_storage::PublicStorageProvider<CURRENT_VERSION>>
 {
 }
 
+/// Runs `f` in a storage transaction.
+///
+/// The transaction is committed if `f` succeeds and rolled back otherwise, so
+/// that a state transition that touches several values is either stored
+/// completely or not at all.
+///
+/// A rollback only restores the storage. If `f` changed an [`MlsGroup`]
+/// before it failed, the group has to be reloaded with [`MlsGroup::load`]
+/// to match the storage again.
+///
+/// [`MlsGroup`]: crate::group::MlsGroup
+/// [`MlsGroup::load`]: crate::group::MlsGroup::load
+pub(crate) fn transaction<
+    Storage: openmls_traits::public_storage::PublicStorageProvider<CURRENT_VERSION>,
+    T,
+    E,
+>(
+    storage: &Storage,
+    storage_error: impl Fn(Storage::PublicError) -> E,
+    f: impl FnOnce() -> Result<T, E>,
+) -> Result<T, E> {
+    storage.begin_transaction().map_err(&storage_error)?;
+    match f() {
+        Ok(value) => {
+            storage.commit_transaction().map_err(storage_error)?;
+            Ok(value)
+        }
+        Err(e) => {
+            if let Err(rollback_error) = storage.rollback_transaction() {
+                log::error!("Failed to roll back storage transaction: {rollback_error:?}");
+            }
+            Err(e)
+        }
+    }
+}
+
 /// A convenience trait for the OpenMLS provider that defines the storage provider
 /// for the current version of storage.
 /// Throughout the code, this one should be used instead of `openmls_traits::OpenMlsProvider`.
@@ -79,6 +123,12 @@ impl traits::QueuedProposal<CURRENT_VERSION> for QueuedProposal {}
 impl Entity<CURRENT_VERSION> for TreeSync {}
 impl traits::TreeSync<CURRENT_VERSION> for TreeSync {}
 
+impl Entity<CURRENT_VERSION> for StoredTreeSync {}
+impl traits::TreeSync<CURRENT_VERSION> for StoredTreeSync {}
+
+impl Entity<CURRENT_VERSION> for Node {}
+impl traits::TreeNode<CURRENT_VERSION> for Node {}
+
 impl Key<CURRENT_VERSION> for GroupId {}
 impl traits::GroupId<CURRENT_VERSION> for GroupId {}
 
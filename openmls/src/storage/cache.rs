@@ -0,0 +1,863 @@
+//! A synchronous cache in front of an asynchronous storage.
+//!
+//! The group operations of OpenMLS access the storage synchronously. To run
+//! them on top of an
+//! [`AsyncStorageProvider`](openmls_traits::async_storage::AsyncStorageProvider),
+//! the values of a group are read into a [`StorageCache`] and the operation is
+//! run against the cache. The cache records
+//!
+//! - the values the operation wrote or deleted, which are written to the
+//!   asynchronous storage once the operation succeeded, and
+//! - the values the operation tried to read that are not in the cache. These
+//!   are read from the asynchronous storage and the operation is run again.
+//!
+//! All values are kept in their JSON serialization.
+
+use std::{collections::HashMap, sync::RwLock};
+
+use openmls_traits::storage::{traits, StorageProvider, CURRENT_VERSION};
+use serde::{de::DeserializeOwned, Serialize};
+use thiserror::Error;
+
+/// The kinds of values in the cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Kind {
+    JoinConfig,
+    OwnLeafNodes,
+    QueuedProposals,
+    Tree,
+    /// The indices of the stored tree nodes of a group. The nodes themselves
+    /// are cached as [`Kind::TreeNode`] when the indices are read from the
+    /// storage.
+    TreeNodes,
+    InterimTranscriptHash,
+    Context,
+    ConfirmationTag,
+    GroupState,
+    MessageSecrets,
+    ResumptionPskStore,
+    OwnLeafIndex,
+    EpochSecrets,
+    TreeNode,
+    EncryptionKeyPair,
+    EpochKeyPairs,
+    KeyPackage,
+    Psk,
+}
+
+impl Kind {
+    /// The kinds of values that are stored under the group id.
+    pub(crate) const GROUP: [Kind; 13] = [
+        Kind::JoinConfig,
+        Kind::OwnLeafNodes,
+        Kind::QueuedProposals,
+        Kind::Tree,
+        Kind::TreeNodes,
+        Kind::InterimTranscriptHash,
+        Kind::Context,
+        Kind::ConfirmationTag,
+        Kind::GroupState,
+        Kind::MessageSecrets,
+        Kind::ResumptionPskStore,
+        Kind::OwnLeafIndex,
+        Kind::EpochSecrets,
+    ];
+}
+
+/// The kind and serialized key of a value.
+pub(crate) type CacheKey = (Kind, Vec<u8>);
+
+/// Serialized values by their key. `None` means that there is no value.
+pub(crate) type CacheValues = HashMap<CacheKey, Option<Vec<u8>>>;
+
+/// Errors thrown by the [`StorageCache`].
+#[derive(Error, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StorageCacheError {
+    /// The value has not been read from the storage yet.
+    #[error("The value has not been read from the storage yet.")]
+    NotCached,
+    /// A value could not be serialized or deserialized.
+    #[error("Error serializing value.")]
+    SerializationError,
+    /// The method is not supported by the cache.
+    #[error("The method is not supported by the cache.")]
+    UnsupportedMethod,
+    /// No transaction is in progress.
+    #[error("No transaction is in progress.")]
+    NoTransaction,
+}
+
+/// A storage that caches values of another storage and records the changes
+/// of an operation. See the [module documentation](self).
+#[derive(Debug, Default)]
+pub(crate) struct StorageCache {
+    /// The values as they are in the storage.
+    values: RwLock<CacheValues>,
+    /// The values the current operation wrote (`Some`) or deleted (`None`).
+    changes: RwLock<CacheValues>,
+    /// Snapshots of the changes at the beginning of each open transaction.
+    transactions: RwLock<Vec<CacheValues>>,
+    /// The values the current operation tried to read that are not cached.
+    misses: RwLock<Vec<CacheKey>>,
+}
+
+impl StorageCache {
+    /// Adds a value that was read from the storage.
+    pub(crate) fn insert(&self, key: CacheKey, value: Option<Vec<u8>>) {
+        self.values.write().unwrap().insert(key, value);
+    }
+
+    /// Returns the values the current operation tried to read that are not
+    /// cached, and forgets about them.
+    pub(crate) fn take_misses(&self) -> Vec<CacheKey> {
+        std::mem::take(&mut *self.misses.write().unwrap())
+    }
+
+    /// Returns the changes of the current operation.
+    pub(crate) fn changes(&self) -> CacheValues {
+        self.changes.read().unwrap().clone()
+    }
+
+    /// Applies the changes of the current operation to the cached values,
+    /// after they were written to the storage.
+    pub(crate) fn apply_changes(&self) {
+        let changes = std::mem::take(&mut *self.changes.write().unwrap());
+        self.values.write().unwrap().extend(changes);
+        self.transactions.write().unwrap().clear();
+    }
+
+    /// Discards the changes of the current operation.
+    pub(crate) fn discard_changes(&self) {
+        self.changes.write().unwrap().clear();
+        self.transactions.write().unwrap().clear();
+    }
+
+    fn get(&self, key: CacheKey) -> Result<Option<Vec<u8>>, StorageCacheError> {
+        if let Some(value) = self.changes.read().unwrap().get(&key) {
+            return Ok(value.clone());
+        }
+        if let Some(value) = self.values.read().unwrap().get(&key) {
+            return Ok(value.clone());
+        }
+
+        self.misses.write().unwrap().push(key);
+        Err(StorageCacheError::NotCached)
+    }
+
+    fn read<V: DeserializeOwned>(
+        &self,
+        kind: Kind,
+        key: &impl Serialize,
+    ) -> Result<Option<V>, StorageCacheError> {
+        self.get((kind, serialize(key)?))?
+            .map(|value| deserialize(&value))
+            .transpose()
+    }
+
+    fn read_list<V: DeserializeOwned>(
+        &self,
+        kind: Kind,
+        key: &impl Serialize,
+    ) -> Result<Vec<V>, StorageCacheError> {
+        Ok(self.read(kind, key)?.unwrap_or_default())
+    }
+
+    fn write(
+        &self,
+        kind: Kind,
+        key: &impl Serialize,
+        value: &impl Serialize,
+    ) -> Result<(), StorageCacheError> {
+        let key = (kind, serialize(key)?);
+        self.changes
+            .write()
+            .unwrap()
+            .insert(key, Some(serialize(value)?));
+        Ok(())
+    }
+
+    fn delete(&self, kind: Kind, key: &impl Serialize) -> Result<(), StorageCacheError> {
+        let key = (kind, serialize(key)?);
+        self.changes.write().unwrap().insert(key, None);
+        Ok(())
+    }
+
+    /// Changes a list value with `f`. The elements are kept as JSON values, so
+    /// that they can be compared without knowing their type.
+    fn update_list(
+        &self,
+        kind: Kind,
+        key: &impl Serialize,
+        f: impl FnOnce(&mut Vec<serde_json::Value>),
+    ) -> Result<(), StorageCacheError> {
+        let mut list: Vec<serde_json::Value> = self.read_list(kind, key)?;
+        f(&mut list);
+        self.write(kind, key, &list)
+    }
+}
+
+/// Serializes a key or value for the cache.
+pub(crate) fn serialize(value: &impl Serialize) -> Result<Vec<u8>, StorageCacheError> {
+    serde_json::to_vec(value).map_err(|_| StorageCacheError::SerializationError)
+}
+
+/// Deserializes a key or value from the cache.
+pub(crate) fn deserialize<V: DeserializeOwned>(value: &[u8]) -> Result<V, StorageCacheError> {
+    serde_json::from_slice(value).map_err(|_| StorageCacheError::SerializationError)
+}
+
+fn to_json_value(value: &impl Serialize) -> Result<serde_json::Value, StorageCacheError> {
+    serde_json::to_value(value).map_err(|_| StorageCacheError::SerializationError)
+}
+
+impl StorageProvider<CURRENT_VERSION> for StorageCache {
+    type Error = StorageCacheError;
+
+    fn begin_transaction(&self) -> Result<(), Self::Error> {
+        let snapshot = self.changes.read().unwrap().clone();
+        self.transactions.write().unwrap().push(snapshot);
+        Ok(())
+    }
+
+    fn commit_transaction(&self) -> Result<(), Self::Error> {
+        self.transactions
+            .write()
+            .unwrap()
+            .pop()
+            .map(|_| ())
+            .ok_or(StorageCacheError::NoTransaction)
+    }
+
+    fn rollback_transaction(&self) -> Result<(), Self::Error> {
+        let snapshot = self
+            .transactions
+            .write()
+            .unwrap()
+            .pop()
+            .ok_or(StorageCacheError::NoTransaction)?;
+        *self.changes.write().unwrap() = snapshot;
+        Ok(())
+    }
+
+    fn write_mls_join_config<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        MlsGroupJoinConfig: traits::MlsGroupJoinConfig<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        config: &MlsGroupJoinConfig,
+    ) -> Result<(), Self::Error> {
+        self.write(Kind::JoinConfig, group_id, config)
+    }
+
+    fn append_own_leaf_node<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        LeafNode: traits::LeafNode<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        leaf_node: &LeafNode,
+    ) -> Result<(), Self::Error> {
+        let leaf_node = to_json_value(leaf_node)?;
+        self.update_list(Kind::OwnLeafNodes, group_id, |leaf_nodes| {
+            leaf_nodes.push(leaf_node)
+        })
+    }
+
+    fn queue_proposal<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
+        QueuedProposal: traits::QueuedProposal<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        proposal_ref: &ProposalRef,
+        proposal: &QueuedProposal,
+    ) -> Result<(), Self::Error> {
+        let proposal = to_json_value(&(proposal_ref, proposal))?;
+        self.update_list(Kind::QueuedProposals, group_id, |proposals| {
+            proposals.push(proposal)
+        })
+    }
+
+    fn write_tree<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        TreeSync: traits::TreeSync<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        tree: &TreeSync,
+    ) -> Result<(), Self::Error> {
+        self.write(Kind::Tree, group_id, tree)
+    }
+
+    fn write_tree_node<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        TreeNode: traits::TreeNode<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        node_index: u32,
+        node: &TreeNode,
+    ) -> Result<(), Self::Error> {
+        self.update_list(Kind::TreeNodes, group_id, |node_indices| {
+            if !node_indices.contains(&node_index.into()) {
+                node_indices.push(node_index.into())
+            }
+        })?;
+        self.write(Kind::TreeNode, &(group_id, node_index), node)
+    }
+
+    fn write_interim_transcript_hash<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        InterimTranscriptHash: traits::InterimTranscriptHash<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        interim_transcript_hash: &InterimTranscriptHash,
+    ) -> Result<(), Self::Error> {
+        self.write(
+            Kind::InterimTranscriptHash,
+            group_id,
+            interim_transcript_hash,
+        )
+    }
+
+    fn write_context<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        GroupContext: traits::GroupContext<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_context: &GroupContext,
+    ) -> Result<(), Self::Error> {
+        self.write(Kind::Context, group_id, group_context)
+    }
+
+    fn write_confirmation_tag<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ConfirmationTag: traits::ConfirmationTag<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        confirmation_tag: &ConfirmationTag,
+    ) -> Result<(), Self::Error> {
+        self.write(Kind::ConfirmationTag, group_id, confirmation_tag)
+    }
+
+    fn write_group_state<
+        GroupState: traits::GroupState<CURRENT_VERSION>,
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_state: &GroupState,
+    ) -> Result<(), Self::Error> {
+        self.write(Kind::GroupState, group_id, group_state)
+    }
+
+    fn write_message_secrets<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        MessageSecrets: traits::MessageSecrets<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        message_secrets: &MessageSecrets,
+    ) -> Result<(), Self::Error> {
+        self.write(Kind::MessageSecrets, group_id, message_secrets)
+    }
+
+    fn write_resumption_psk_store<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ResumptionPskStore: traits::ResumptionPskStore<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        resumption_psk_store: &ResumptionPskStore,
+    ) -> Result<(), Self::Error> {
+        self.write(Kind::ResumptionPskStore, group_id, resumption_psk_store)
+    }
+
+    fn write_own_leaf_index<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        LeafNodeIndex: traits::LeafNodeIndex<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        own_leaf_index: &LeafNodeIndex,
+    ) -> Result<(), Self::Error> {
+        self.write(Kind::OwnLeafIndex, group_id, own_leaf_index)
+    }
+
+    fn write_group_epoch_secrets<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        GroupEpochSecrets: traits::GroupEpochSecrets<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_epoch_secrets: &GroupEpochSecrets,
+    ) -> Result<(), Self::Error> {
+        self.write(Kind::EpochSecrets, group_id, group_epoch_secrets)
+    }
+
+    fn write_signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<CURRENT_VERSION>,
+        SignatureKeyPair: traits::SignatureKeyPair<CURRENT_VERSION>,
+    >(
+        &self,
+        _public_key: &SignaturePublicKey,
+        _signature_key_pair: &SignatureKeyPair,
+    ) -> Result<(), Self::Error> {
+        Err(StorageCacheError::UnsupportedMethod)
+    }
+
+    fn write_encryption_key_pair<
+        EncryptionKey: traits::EncryptionKey<CURRENT_VERSION>,
+        HpkeKeyPair: traits::HpkeKeyPair<CURRENT_VERSION>,
+    >(
+        &self,
+        public_key: &EncryptionKey,
+        key_pair: &HpkeKeyPair,
+    ) -> Result<(), Self::Error> {
+        self.write(Kind::EncryptionKeyPair, public_key, key_pair)
+    }
+
+    fn write_encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        EpochKey: traits::EpochKey<CURRENT_VERSION>,
+        HpkeKeyPair: traits::HpkeKeyPair<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+        key_pairs: &[HpkeKeyPair],
+    ) -> Result<(), Self::Error> {
+        self.write(
+            Kind::EpochKeyPairs,
+            &(group_id, epoch, leaf_index),
+            &key_pairs,
+        )
+    }
+
+    fn write_key_package<
+        HashReference: traits::HashReference<CURRENT_VERSION>,
+        KeyPackage: traits::KeyPackage<CURRENT_VERSION>,
+    >(
+        &self,
+        hash_ref: &HashReference,
+        key_package: &KeyPackage,
+    ) -> Result<(), Self::Error> {
+        self.write(Kind::KeyPackage, hash_ref, key_package)
+    }
+
+    fn write_psk<
+        PskId: traits::PskId<CURRENT_VERSION>,
+        PskBundle: traits::PskBundle<CURRENT_VERSION>,
+    >(
+        &self,
+        psk_id: &PskId,
+        psk: &PskBundle,
+    ) -> Result<(), Self::Error> {
+        self.write(Kind::Psk, psk_id, psk)
+    }
+
+    fn mls_group_join_config<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        MlsGroupJoinConfig: traits::MlsGroupJoinConfig<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<MlsGroupJoinConfig>, Self::Error> {
+        self.read(Kind::JoinConfig, group_id)
+    }
+
+    fn own_leaf_nodes<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        LeafNode: traits::LeafNode<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<LeafNode>, Self::Error> {
+        self.read_list(Kind::OwnLeafNodes, group_id)
+    }
+
+    fn queued_proposal_refs<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<ProposalRef>, Self::Error> {
+        let proposals: Vec<(ProposalRef, serde_json::Value)> =
+            self.read_list(Kind::QueuedProposals, group_id)?;
+        Ok(proposals
+            .into_iter()
+            .map(|(proposal_ref, _)| proposal_ref)
+            .collect())
+    }
+
+    fn queued_proposals<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
+        QueuedProposal: traits::QueuedProposal<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<(ProposalRef, QueuedProposal)>, Self::Error> {
+        self.read_list(Kind::QueuedProposals, group_id)
+    }
+
+    fn tree<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        TreeSync: traits::TreeSync<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<TreeSync>, Self::Error> {
+        self.read(Kind::Tree, group_id)
+    }
+
+    fn tree_nodes<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        TreeNode: traits::TreeNode<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<(u32, TreeNode)>, Self::Error> {
+        let node_indices: Vec<u32> = self.read_list(Kind::TreeNodes, group_id)?;
+        node_indices
+            .into_iter()
+            .map(|node_index| {
+                let node = self
+                    .read(Kind::TreeNode, &(group_id, node_index))?
+                    .ok_or(StorageCacheError::NotCached)?;
+                Ok((node_index, node))
+            })
+            .collect()
+    }
+
+    fn group_context<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        GroupContext: traits::GroupContext<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<GroupContext>, Self::Error> {
+        self.read(Kind::Context, group_id)
+    }
+
+    fn interim_transcript_hash<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        InterimTranscriptHash: traits::InterimTranscriptHash<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<InterimTranscriptHash>, Self::Error> {
+        self.read(Kind::InterimTranscriptHash, group_id)
+    }
+
+    fn confirmation_tag<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ConfirmationTag: traits::ConfirmationTag<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<ConfirmationTag>, Self::Error> {
+        self.read(Kind::ConfirmationTag, group_id)
+    }
+
+    fn group_state<
+        GroupState: traits::GroupState<CURRENT_VERSION>,
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<GroupState>, Self::Error> {
+        self.read(Kind::GroupState, group_id)
+    }
+
+    fn message_secrets<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        MessageSecrets: traits::MessageSecrets<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<MessageSecrets>, Self::Error> {
+        self.read(Kind::MessageSecrets, group_id)
+    }
+
+    fn resumption_psk_store<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ResumptionPskStore: traits::ResumptionPskStore<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<ResumptionPskStore>, Self::Error> {
+        self.read(Kind::ResumptionPskStore, group_id)
+    }
+
+    fn own_leaf_index<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        LeafNodeIndex: traits::LeafNodeIndex<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<LeafNodeIndex>, Self::Error> {
+        self.read(Kind::OwnLeafIndex, group_id)
+    }
+
+    fn group_epoch_secrets<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        GroupEpochSecrets: traits::GroupEpochSecrets<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<GroupEpochSecrets>, Self::Error> {
+        self.read(Kind::EpochSecrets, group_id)
+    }
+
+    fn signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<CURRENT_VERSION>,
+        SignatureKeyPair: traits::SignatureKeyPair<CURRENT_VERSION>,
+    >(
+        &self,
+        _public_key: &SignaturePublicKey,
+    ) -> Result<Option<SignatureKeyPair>, Self::Error> {
+        Err(StorageCacheError::UnsupportedMethod)
+    }
+
+    fn encryption_key_pair<
+        HpkeKeyPair: traits::HpkeKeyPair<CURRENT_VERSION>,
+        EncryptionKey: traits::EncryptionKey<CURRENT_VERSION>,
+    >(
+        &self,
+        public_key: &EncryptionKey,
+    ) -> Result<Option<HpkeKeyPair>, Self::Error> {
+        self.read(Kind::EncryptionKeyPair, public_key)
+    }
+
+    fn encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        EpochKey: traits::EpochKey<CURRENT_VERSION>,
+        HpkeKeyPair: traits::HpkeKeyPair<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+    ) -> Result<Vec<HpkeKeyPair>, Self::Error> {
+        self.read_list(Kind::EpochKeyPairs, &(group_id, epoch, leaf_index))
+    }
+
+    fn key_package<
+        KeyPackageRef: traits::HashReference<CURRENT_VERSION>,
+        KeyPackage: traits::KeyPackage<CURRENT_VERSION>,
+    >(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> Result<Option<KeyPackage>, Self::Error> {
+        self.read(Kind::KeyPackage, hash_ref)
+    }
+
+    fn psk<PskBundle: traits::PskBundle<CURRENT_VERSION>, PskId: traits::PskId<CURRENT_VERSION>>(
+        &self,
+        psk_id: &PskId,
+    ) -> Result<Option<PskBundle>, Self::Error> {
+        self.read(Kind::Psk, psk_id)
+    }
+
+    fn group_ids<GroupId: traits::GroupId<CURRENT_VERSION> + DeserializeOwned>(
+        &self,
+    ) -> Result<Vec<GroupId>, Self::Error> {
+        Err(StorageCacheError::UnsupportedMethod)
+    }
+
+    fn encryption_epoch_key_pair_ids<
+        GroupId: traits::GroupId<CURRENT_VERSION> + DeserializeOwned,
+        EpochKey: traits::EpochKey<CURRENT_VERSION> + DeserializeOwned,
+    >(
+        &self,
+    ) -> Result<Vec<(GroupId, EpochKey, u32)>, Self::Error> {
+        Err(StorageCacheError::UnsupportedMethod)
+    }
+
+    fn key_package_refs<
+        KeyPackageRef: traits::HashReference<CURRENT_VERSION> + DeserializeOwned,
+    >(
+        &self,
+    ) -> Result<Vec<KeyPackageRef>, Self::Error> {
+        Err(StorageCacheError::UnsupportedMethod)
+    }
+
+    fn psk_ids<PskId: traits::PskId<CURRENT_VERSION> + DeserializeOwned>(
+        &self,
+    ) -> Result<Vec<PskId>, Self::Error> {
+        Err(StorageCacheError::UnsupportedMethod)
+    }
+
+    fn remove_proposal<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        proposal_ref: &ProposalRef,
+    ) -> Result<(), Self::Error> {
+        let proposal_ref = to_json_value(proposal_ref)?;
+        self.update_list(Kind::QueuedProposals, group_id, |proposals| {
+            proposals.retain(|proposal| proposal.get(0) != Some(&proposal_ref))
+        })
+    }
+
+    fn delete_own_leaf_nodes<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(Kind::OwnLeafNodes, group_id)
+    }
+
+    fn delete_group_config<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(Kind::JoinConfig, group_id)
+    }
+
+    fn delete_tree<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(Kind::Tree, group_id)
+    }
+
+    fn delete_tree_node<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+        node_index: u32,
+    ) -> Result<(), Self::Error> {
+        self.update_list(Kind::TreeNodes, group_id, |node_indices| {
+            node_indices.retain(|index| *index != node_index)
+        })?;
+        self.delete(Kind::TreeNode, &(group_id, node_index))
+    }
+
+    fn delete_tree_nodes<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        let node_indices: Vec<u32> = self.read_list(Kind::TreeNodes, group_id)?;
+        for node_index in node_indices {
+            self.delete(Kind::TreeNode, &(group_id, node_index))?;
+        }
+        self.write(Kind::TreeNodes, group_id, &Vec::<u32>::new())
+    }
+
+    fn delete_confirmation_tag<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(Kind::ConfirmationTag, group_id)
+    }
+
+    fn delete_group_state<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(Kind::GroupState, group_id)
+    }
+
+    fn delete_context<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(Kind::Context, group_id)
+    }
+
+    fn delete_interim_transcript_hash<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(Kind::InterimTranscriptHash, group_id)
+    }
+
+    fn delete_message_secrets<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(Kind::MessageSecrets, group_id)
+    }
+
+    fn delete_all_resumption_psk_secrets<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(Kind::ResumptionPskStore, group_id)
+    }
+
+    fn delete_own_leaf_index<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(Kind::OwnLeafIndex, group_id)
+    }
+
+    fn delete_group_epoch_secrets<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(Kind::EpochSecrets, group_id)
+    }
+
+    fn clear_proposal_queue<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(Kind::QueuedProposals, group_id)
+    }
+
+    fn delete_signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<CURRENT_VERSION>,
+    >(
+        &self,
+        _public_key: &SignaturePublicKey,
+    ) -> Result<(), Self::Error> {
+        Err(StorageCacheError::UnsupportedMethod)
+    }
+
+    fn delete_encryption_key_pair<EncryptionKey: traits::EncryptionKey<CURRENT_VERSION>>(
+        &self,
+        public_key: &EncryptionKey,
+    ) -> Result<(), Self::Error> {
+        self.delete(Kind::EncryptionKeyPair, public_key)
+    }
+
+    fn delete_encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        EpochKey: traits::EpochKey<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+    ) -> Result<(), Self::Error> {
+        self.delete(Kind::EpochKeyPairs, &(group_id, epoch, leaf_index))
+    }
+
+    fn delete_key_package<KeyPackageRef: traits::HashReference<CURRENT_VERSION>>(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> Result<(), Self::Error> {
+        self.delete(Kind::KeyPackage, hash_ref)
+    }
+
+    fn delete_psk<PskKey: traits::PskId<CURRENT_VERSION>>(
+        &self,
+        psk_id: &PskKey,
+    ) -> Result<(), Self::Error> {
+        self.delete(Kind::Psk, psk_id)
+    }
+}
@@ -0,0 +1,404 @@
+//! Export and import of single groups.
+//!
+//! [`export_group`] reads all values [`MlsGroup::load`] needs for a group,
+//! including the encryption key pairs of the current epoch and of pending
+//! updates of the own leaf, and serializes them into a self-contained blob. [`import_group`] writes the values from
+//! such a blob to another storage, e.g. on a new device or after switching to
+//! a different [`StorageProvider`](openmls_traits::storage::StorageProvider).
+//!
+//! The blob starts with a two byte format version and a flag that indicates
+//! whether the rest of it is encrypted. An encrypted blob is sealed with
+//! ChaCha20Poly1305 under a 32 byte key that the application provides. The
+//! export also records the storage version it was created with.
+//!
+//! Signature key pairs, key packages and PSKs are not part of a group and are
+//! not exported.
+//!
+//! [`MlsGroup::load`]: crate::group::MlsGroup::load
+
+use openmls_traits::{
+    crypto::OpenMlsCrypto,
+    random::OpenMlsRand,
+    types::{AeadType, CryptoError},
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::{
+    migration::{stored_version, MigrationError, StoredGroup},
+    transaction, OpenMlsProvider, CURRENT_VERSION,
+};
+use crate::group::GroupId;
+
+/// The version of the export format.
+pub const EXPORT_FORMAT_VERSION: u16 = 1;
+
+/// The length of a key that encrypts an export.
+pub const EXPORT_KEY_LENGTH: usize = 32;
+
+const AEAD: AeadType = AeadType::ChaCha20Poly1305;
+const NONCE_LENGTH: usize = 12;
+const HEADER_LENGTH: usize = 3;
+
+const PLAINTEXT: u8 = 0;
+const ENCRYPTED: u8 = 1;
+
+/// Group export error
+#[derive(Error, Debug, PartialEq, Clone)]
+pub enum GroupExportError<StorageError> {
+    /// No group with the given id is stored.
+    #[error("No group with the given id is stored.")]
+    GroupNotFound,
+    /// A group with the same id is already stored.
+    #[error("A group with the same id is already stored.")]
+    GroupAlreadyExists,
+    /// The export is malformed.
+    #[error("The export is malformed.")]
+    MalformedExport,
+    /// The export was created with an unsupported format or storage version.
+    #[error("The export was created with the unsupported version {0}.")]
+    UnsupportedVersion(u16),
+    /// The export is encrypted, but no key was provided.
+    #[error("The export is encrypted, but no key was provided.")]
+    MissingKey,
+    /// A key was provided, but the export is not encrypted.
+    #[error("A key was provided, but the export is not encrypted.")]
+    NotEncrypted,
+    /// The export could not be encrypted or decrypted.
+    #[error("The export could not be encrypted or decrypted: {0}")]
+    CryptoError(CryptoError),
+    /// See [`MigrationError`] for more details.
+    #[error(transparent)]
+    MigrationError(#[from] MigrationError<StorageError>),
+}
+
+/// The content of an export.
+#[derive(Serialize, Deserialize)]
+struct GroupExport {
+    storage_version: u16,
+    group_id: GroupId,
+    group: StoredGroup,
+}
+
+/// Exports the group with the given id from the storage of the `provider`.
+///
+/// If an `encryption_key` is given, the export is encrypted with it and can
+/// only be imported with the same key.
+pub fn export_group<Provider: OpenMlsProvider>(
+    provider: &Provider,
+    group_id: &GroupId,
+    encryption_key: Option<&[u8; EXPORT_KEY_LENGTH]>,
+) -> Result<Vec<u8>, GroupExportError<Provider::StorageError>> {
+    let group = StoredGroup::read::<CURRENT_VERSION, _>(provider.storage(), group_id)?
+        .ok_or(GroupExportError::GroupNotFound)?;
+    let payload = serde_json::to_vec(&GroupExport {
+        storage_version: CURRENT_VERSION,
+        group_id: group_id.clone(),
+        group,
+    })
+    .map_err(|e| MigrationError::InvalidValue(e.to_string()))?;
+
+    let mut export = EXPORT_FORMAT_VERSION.to_be_bytes().to_vec();
+    match encryption_key {
+        Some(key) => {
+            export.push(ENCRYPTED);
+            let nonce = provider
+                .rand()
+                .random_vec(NONCE_LENGTH)
+                .map_err(|_| GroupExportError::CryptoError(CryptoError::InsufficientRandomness))?;
+            let ciphertext = provider
+                .crypto()
+                .aead_encrypt(AEAD, key, &payload, &nonce, &export)
+                .map_err(GroupExportError::CryptoError)?;
+            export.extend_from_slice(&nonce);
+            export.extend_from_slice(&ciphertext);
+        }
+        None => {
+            export.push(PLAINTEXT);
+            export.extend_from_slice(&payload);
+        }
+    }
+
+    Ok(export)
+}
+
+/// Imports a group that was exported with [`export_group`] into the storage
+/// of the `provider` and returns its id.
+///
+/// The `encryption_key` must be the key the group was exported with, or `None`
+/// if the export is not encrypted. The import fails if a group with the same
+/// id is already stored. All values are written in a single transaction.
+pub fn import_group<Provider: OpenMlsProvider>(
+    provider: &Provider,
+    export: &[u8],
+    encryption_key: Option<&[u8; EXPORT_KEY_LENGTH]>,
+) -> Result<GroupId, GroupExportError<Provider::StorageError>> {
+    if export.len() < HEADER_LENGTH {
+        return Err(GroupExportError::MalformedExport);
+    }
+    let (header, body) = export.split_at(HEADER_LENGTH);
+    let format_version = u16::from_be_bytes([header[0], header[1]]);
+    if format_version != EXPORT_FORMAT_VERSION {
+        return Err(GroupExportError::UnsupportedVersion(format_version));
+    }
+
+    let payload = match (header[2], encryption_key) {
+        (ENCRYPTED, Some(key)) => {
+            if body.len() < NONCE_LENGTH {
+                return Err(GroupExportError::MalformedExport);
+            }
+            let (nonce, ciphertext) = body.split_at(NONCE_LENGTH);
+            provider
+                .crypto()
+                .aead_decrypt(AEAD, key, ciphertext, nonce, header)
+                .map_err(GroupExportError::CryptoError)?
+        }
+        (ENCRYPTED, None) => return Err(GroupExportError::MissingKey),
+        (PLAINTEXT, Some(_)) => return Err(GroupExportError::NotEncrypted),
+        (PLAINTEXT, None) => body.to_vec(),
+        _ => return Err(GroupExportError::MalformedExport),
+    };
+
+    let GroupExport {
+        storage_version,
+        group_id,
+        group,
+    } = serde_json::from_slice(&payload).map_err(|_| GroupExportError::MalformedExport)?;
+    if storage_version != CURRENT_VERSION {
+        return Err(GroupExportError::UnsupportedVersion(storage_version));
+    }
+
+    let storage = provider.storage();
+    if stored_version(storage, &group_id)
+        .map_err(MigrationError::StorageError)?
+        .is_some()
+    {
+        return Err(GroupExportError::GroupAlreadyExists);
+    }
+    transaction(storage, MigrationError::StorageError, || {
+        group.write::<CURRENT_VERSION, _>(storage, &group_id)
+    })?;
+
+    Ok(group_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use openmls_test::openmls_test;
+    use openmls_traits::OpenMlsProvider as _;
+
+    use super::*;
+    use crate::{
+        credentials::test_utils::AcceptAllCredentials,
+        framing::*,
+        group::{mls_group::tests_and_kats::utils::setup_client, *},
+        treesync::LeafNodeParameters,
+    };
+
+    #[openmls_test]
+    fn export_and_import() {
+        let alice_provider = provider;
+        let bob_provider = &Provider::default();
+        let new_alice_provider = &Provider::default();
+
+        let (alice_credential_with_key, _alice_kpb, alice_signer, _alice_pk) =
+            setup_client("Alice", ciphersuite, alice_provider);
+        let (_bob_credential_with_key, bob_kpb, bob_signer, _bob_pk) =
+            setup_client("Bob", ciphersuite, bob_provider);
+
+        let mls_group_create_config = MlsGroupCreateConfig::builder()
+            .ciphersuite(ciphersuite)
+            .use_ratchet_tree_extension(true)
+            .build();
+
+        let mut alice_group = MlsGroup::new(
+            alice_provider,
+            &alice_signer,
+            &mls_group_create_config,
+            alice_credential_with_key,
+        )
+        .unwrap();
+        let (_commit, welcome, _group_info) = alice_group
+            .add_members(
+                alice_provider,
+                &alice_signer,
+                &[bob_kpb.key_package().clone()],
+            )
+            .unwrap();
+        alice_group.merge_pending_commit(alice_provider).unwrap();
+
+        let mut bob_group = StagedWelcome::new_from_welcome(
+            bob_provider,
+            mls_group_create_config.join_config(),
+            welcome.into_welcome().unwrap(),
+            None,
+            &AcceptAllCredentials,
+        )
+        .and_then(|staged_join| staged_join.into_group(bob_provider))
+        .unwrap();
+
+        // === Alice moves the group to a new device ===
+        let key = [7; EXPORT_KEY_LENGTH];
+        let export = export_group(alice_provider, alice_group.group_id(), Some(&key)).unwrap();
+
+        assert_eq!(
+            import_group(new_alice_provider, &export, None),
+            Err(GroupExportError::MissingKey)
+        );
+        assert!(matches!(
+            import_group(new_alice_provider, &export, Some(&[8; EXPORT_KEY_LENGTH])),
+            Err(GroupExportError::CryptoError(_))
+        ));
+        assert_eq!(
+            import_group(new_alice_provider, &export[..2], Some(&key)),
+            Err(GroupExportError::MalformedExport)
+        );
+
+        let group_id = import_group(new_alice_provider, &export, Some(&key)).unwrap();
+        assert_eq!(&group_id, alice_group.group_id());
+        assert_eq!(
+            import_group(new_alice_provider, &export, Some(&key)),
+            Err(GroupExportError::GroupAlreadyExists)
+        );
+
+        let mut new_alice_group = MlsGroup::load(new_alice_provider.storage(), &group_id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(new_alice_group, alice_group);
+
+        // The imported group can process a commit, which needs the encryption
+        // keys of the current epoch.
+        let (commit, _welcome, _group_info) = bob_group
+            .self_update(bob_provider, &bob_signer, LeafNodeParameters::default())
+            .unwrap();
+        bob_group.merge_pending_commit(bob_provider).unwrap();
+
+        let processed_message = new_alice_group
+            .process_message(
+                new_alice_provider,
+                commit.into_protocol_message().unwrap(),
+                &AcceptAllCredentials,
+            )
+            .unwrap();
+        let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
+            processed_message.into_content()
+        else {
+            panic!("Expected a StagedCommit.");
+        };
+        new_alice_group
+            .merge_staged_commit(new_alice_provider, *staged_commit)
+            .unwrap();
+        assert_eq!(
+            new_alice_group.epoch_authenticator(),
+            bob_group.epoch_authenticator()
+        );
+
+        // An unencrypted export.
+        let export = export_group(bob_provider, bob_group.group_id(), None).unwrap();
+        assert_eq!(
+            import_group(alice_provider, &export, Some(&key)),
+            Err(GroupExportError::NotEncrypted)
+        );
+        let bob_provider_copy = &Provider::default();
+        import_group(bob_provider_copy, &export, None).unwrap();
+        let bob_group_copy = MlsGroup::load(bob_provider_copy.storage(), bob_group.group_id())
+            .unwrap()
+            .unwrap();
+        assert_eq!(bob_group_copy, bob_group);
+    }
+
+    #[openmls_test]
+    fn export_with_pending_update() {
+        let alice_provider = provider;
+        let bob_provider = &Provider::default();
+        let new_alice_provider = &Provider::default();
+
+        let (alice_credential_with_key, _alice_kpb, alice_signer, _alice_pk) =
+            setup_client("Alice", ciphersuite, alice_provider);
+        let (_bob_credential_with_key, bob_kpb, bob_signer, _bob_pk) =
+            setup_client("Bob", ciphersuite, bob_provider);
+
+        let mls_group_create_config = MlsGroupCreateConfig::builder()
+            .ciphersuite(ciphersuite)
+            .use_ratchet_tree_extension(true)
+            .build();
+
+        let mut alice_group = MlsGroup::new(
+            alice_provider,
+            &alice_signer,
+            &mls_group_create_config,
+            alice_credential_with_key,
+        )
+        .unwrap();
+        let (_commit, welcome, _group_info) = alice_group
+            .add_members(
+                alice_provider,
+                &alice_signer,
+                &[bob_kpb.key_package().clone()],
+            )
+            .unwrap();
+        alice_group.merge_pending_commit(alice_provider).unwrap();
+
+        let mut bob_group = StagedWelcome::new_from_welcome(
+            bob_provider,
+            mls_group_create_config.join_config(),
+            welcome.into_welcome().unwrap(),
+            None,
+            &AcceptAllCredentials,
+        )
+        .and_then(|staged_join| staged_join.into_group(bob_provider))
+        .unwrap();
+
+        // === Alice proposes an update and moves the group to a new device ===
+        let (proposal, _proposal_ref) = alice_group
+            .propose_self_update(alice_provider, &alice_signer, LeafNodeParameters::default())
+            .unwrap();
+        let export = export_group(alice_provider, alice_group.group_id(), None).unwrap();
+        let group_id = import_group(new_alice_provider, &export, None).unwrap();
+        let mut new_alice_group = MlsGroup::load(new_alice_provider.storage(), &group_id)
+            .unwrap()
+            .unwrap();
+
+        // Bob commits the update. Processing the commit needs the encryption
+        // key pair of Alice's new leaf node.
+        let processed_message = bob_group
+            .process_message(
+                bob_provider,
+                proposal.into_protocol_message().unwrap(),
+                &AcceptAllCredentials,
+            )
+            .unwrap();
+        let ProcessedMessageContent::ProposalMessage(queued_proposal) =
+            processed_message.into_content()
+        else {
+            panic!("Expected a proposal.");
+        };
+        bob_group
+            .store_pending_proposal(bob_provider.storage(), *queued_proposal)
+            .unwrap();
+        let (commit, _welcome, _group_info) = bob_group
+            .commit_to_pending_proposals(bob_provider, &bob_signer)
+            .unwrap();
+        bob_group.merge_pending_commit(bob_provider).unwrap();
+
+        let processed_message = new_alice_group
+            .process_message(
+                new_alice_provider,
+                commit.into_protocol_message().unwrap(),
+                &AcceptAllCredentials,
+            )
+            .unwrap();
+        let ProcessedMessageContent::StagedCommitMessage(staged_commit) =
+            processed_message.into_content()
+        else {
+            panic!("Expected a StagedCommit.");
+        };
+        new_alice_group
+            .merge_staged_commit(new_alice_provider, *staged_commit)
+            .unwrap();
+        assert_eq!(
+            new_alice_group.epoch_authenticator(),
+            bob_group.epoch_authenticator()
+        );
+    }
+}
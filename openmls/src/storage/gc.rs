@@ -0,0 +1,247 @@
+//! Garbage collection of stored key material.
+//!
+//! Groups and key packages leave values in storage that nothing reads anymore,
+//! e.g. when a group is removed from storage without [`MlsGroup::delete()`],
+//! or when a key package expires before anyone used it. [`collect_garbage`]
+//! lists the stored values with the listing methods of the
+//! [`StorageProvider`](openmls_traits::storage::StorageProvider) and removes
+//!
+//! - encryption epoch key pairs of groups that are not in storage, and of
+//!   epochs or leaves other than the current epoch and own leaf of a group,
+//! - key packages whose lifetime has expired, and
+//! - resumption PSKs of groups that are not in storage.
+//!
+//! External PSKs are never removed, because only the application knows whether
+//! they are still needed.
+//!
+//! [`MlsGroup::delete()`]: crate::group::MlsGroup::delete
+
+use std::collections::HashMap;
+
+use crate::{
+    binary_tree::LeafNodeIndex,
+    ciphersuite::hash_ref::KeyPackageRef,
+    group::{GroupContext, GroupEpoch, GroupId},
+    prelude::KeyPackageBundle,
+    schedule::Psk,
+};
+
+use super::{transaction, StorageProvider};
+
+/// The values that were removed by [`collect_garbage`].
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct GarbageCollectionReport {
+    /// The group id, epoch and leaf index of the removed encryption epoch key
+    /// pairs.
+    pub epoch_key_pairs: Vec<(GroupId, GroupEpoch, u32)>,
+    /// The references of the removed key packages.
+    pub key_packages: Vec<KeyPackageRef>,
+    /// The ids of the removed PSKs.
+    pub psks: Vec<Psk>,
+}
+
+impl GarbageCollectionReport {
+    /// Returns true if nothing was removed.
+    pub fn is_empty(&self) -> bool {
+        self.epoch_key_pairs.is_empty() && self.key_packages.is_empty() && self.psks.is_empty()
+    }
+}
+
+/// Removes all values from `storage` that no stored group references anymore
+/// and returns what was removed. See the [module documentation](self) for the
+/// values that are considered.
+///
+/// All values are removed in a single transaction.
+pub fn collect_garbage<Storage: StorageProvider>(
+    storage: &Storage,
+) -> Result<GarbageCollectionReport, Storage::Error> {
+    transaction(
+        storage,
+        |e| e,
+        || {
+            let mut report = GarbageCollectionReport::default();
+
+            // The current epoch and own leaf of every stored group. If either
+            // is missing, the group is incomplete and we don't know which key
+            // pairs it needs, so we keep all of them.
+            let mut groups = HashMap::new();
+            for group_id in storage.group_ids::<GroupId>()? {
+                let context: Option<GroupContext> = storage.group_context(&group_id)?;
+                let own_leaf_index: Option<LeafNodeIndex> = storage.own_leaf_index(&group_id)?;
+                let current = context
+                    .zip(own_leaf_index)
+                    .map(|(context, own_leaf_index)| (context.epoch(), own_leaf_index.u32()));
+                groups.insert(group_id, current);
+            }
+
+            for (group_id, epoch, leaf_index) in
+                storage.encryption_epoch_key_pair_ids::<GroupId, GroupEpoch>()?
+            {
+                let referenced = match groups.get(&group_id) {
+                    Some(Some(current)) => current == &(epoch, leaf_index),
+                    Some(None) => true,
+                    None => false,
+                };
+                if !referenced {
+                    storage.delete_encryption_epoch_key_pairs(&group_id, &epoch, leaf_index)?;
+                    report.epoch_key_pairs.push((group_id, epoch, leaf_index));
+                }
+            }
+
+            for key_package_ref in storage.key_package_refs::<KeyPackageRef>()? {
+                let key_package: Option<KeyPackageBundle> =
+                    storage.key_package(&key_package_ref)?;
+                let expired = key_package
+                    .is_some_and(|key_package| key_package.key_package().life_time().has_expired());
+                if expired {
+                    storage.delete_key_package(&key_package_ref)?;
+                    report.key_packages.push(key_package_ref);
+                }
+            }
+
+            for psk in storage.psk_ids::<Psk>()? {
+                let referenced = match &psk {
+                    Psk::External(_) => true,
+                    Psk::Resumption(resumption_psk) => {
+                        groups.contains_key(resumption_psk.psk_group_id())
+                    }
+                };
+                if !referenced {
+                    storage.delete_psk(&psk)?;
+                    report.psks.push(psk);
+                }
+            }
+
+            Ok(report)
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use openmls_test::openmls_test;
+    use openmls_traits::{
+        storage::{StorageProvider, CURRENT_VERSION},
+        OpenMlsProvider as _,
+    };
+
+    use super::*;
+    use crate::{
+        group::{mls_group::tests_and_kats::utils::setup_client, *},
+        key_packages::*,
+        schedule::{psk::ResumptionPskUsage, PreSharedKeyId},
+        treesync::{node::encryption_keys::EncryptionKeyPair, LeafNodeParameters},
+    };
+
+    #[openmls_test]
+    fn garbage_collection() {
+        let (alice_credential_with_key, _alice_kpb, alice_signer, _alice_pk) =
+            setup_client("Alice", ciphersuite, provider);
+
+        let mls_group_create_config = MlsGroupCreateConfig::builder()
+            .ciphersuite(ciphersuite)
+            .build();
+
+        // Alice's group is kept, the other group is removed from storage by
+        // the application, but its key pairs are left behind.
+        let mut alice_group = MlsGroup::new(
+            provider,
+            &alice_signer,
+            &mls_group_create_config,
+            alice_credential_with_key.clone(),
+        )
+        .unwrap();
+        alice_group
+            .self_update(provider, &alice_signer, LeafNodeParameters::default())
+            .unwrap();
+        alice_group.merge_pending_commit(provider).unwrap();
+
+        let removed_group = MlsGroup::new(
+            provider,
+            &alice_signer,
+            &mls_group_create_config,
+            alice_credential_with_key.clone(),
+        )
+        .unwrap();
+        StorageProvider::<CURRENT_VERSION>::delete_group_state(
+            provider.storage(),
+            removed_group.group_id(),
+        )
+        .unwrap();
+
+        // Key pairs of an old epoch of Alice's group.
+        StorageProvider::<CURRENT_VERSION>::write_encryption_epoch_key_pairs::<
+            _,
+            _,
+            EncryptionKeyPair,
+        >(
+            provider.storage(),
+            alice_group.group_id(),
+            &GroupEpoch::from(0),
+            0,
+            &[],
+        )
+        .unwrap();
+
+        // An expired key package. The one of `setup_client` is still valid.
+        let expired_key_package = KeyPackage::builder()
+            .key_package_lifetime(Lifetime::new(0))
+            .build(
+                ciphersuite,
+                provider,
+                &alice_signer,
+                alice_credential_with_key,
+            )
+            .unwrap();
+        let expired_key_package_ref = expired_key_package
+            .key_package()
+            .hash_ref(provider.crypto())
+            .unwrap();
+
+        // PSKs
+        let external_psk = PreSharedKeyId::external(b"external".to_vec(), vec![]);
+        let alice_psk = PreSharedKeyId::resumption(
+            ResumptionPskUsage::Application,
+            alice_group.group_id().clone(),
+            GroupEpoch::from(0),
+            vec![],
+        );
+        let removed_psk = PreSharedKeyId::resumption(
+            ResumptionPskUsage::Application,
+            removed_group.group_id().clone(),
+            GroupEpoch::from(0),
+            vec![],
+        );
+        for psk_id in [&external_psk, &alice_psk, &removed_psk] {
+            psk_id.store(provider, &[1; 32]).unwrap();
+        }
+
+        let report = collect_garbage(provider.storage()).unwrap();
+
+        assert_eq!(report.epoch_key_pairs.len(), 2);
+        assert!(report.epoch_key_pairs.contains(&(
+            alice_group.group_id().clone(),
+            GroupEpoch::from(0),
+            0
+        )));
+        assert!(report.epoch_key_pairs.contains(&(
+            removed_group.group_id().clone(),
+            GroupEpoch::from(0),
+            0
+        )));
+        assert_eq!(report.key_packages, vec![expired_key_package_ref]);
+        assert_eq!(report.psks, vec![removed_psk.psk().clone()]);
+
+        // Everything else is still referenced.
+        assert!(collect_garbage(provider.storage()).unwrap().is_empty());
+
+        // Alice's group still works.
+        let mut alice_group = MlsGroup::load(provider.storage(), alice_group.group_id())
+            .unwrap()
+            .unwrap();
+        alice_group
+            .self_update(provider, &alice_signer, LeafNodeParameters::default())
+            .unwrap();
+        alice_group.merge_pending_commit(provider).unwrap();
+    }
+}
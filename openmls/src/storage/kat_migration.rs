@@ -0,0 +1,175 @@
+//! This module tests the storage migration with the storages of the storage
+//! stability KAT.
+//!
+//! The storages were written with storage version 1, the ones of the first
+//! ciphersuites before the tree nodes were stored individually. The tests
+//! migrate the group in each of them and check that the group that is loaded
+//! afterwards matches the group before the migration.
+
+use std::collections::HashMap;
+
+use base64::Engine;
+use openmls_memory_storage::MemoryStorage;
+use openmls_test::openmls_test;
+use openmls_traits::storage::StorageProvider;
+
+use super::{
+    kat_storage_stability::KatData,
+    migration::{migrate_group, migrate_group_step, stored_version, StoredGroup},
+    CURRENT_VERSION,
+};
+use crate::{prelude::*, treesync::StoredTreeSync};
+
+/// Loads the stored states of the storage stability vectors, if there are any
+/// for the `ciphersuite`.
+fn load_storages(ciphersuite: Ciphersuite) -> Option<(GroupId, Vec<MemoryStorage>)> {
+    let base64_engine = base64::engine::GeneralPurpose::new(
+        &base64::alphabet::URL_SAFE,
+        base64::engine::GeneralPurposeConfig::new(),
+    );
+
+    let mut data: HashMap<Ciphersuite, KatData> = {
+        let file = std::fs::File::open("test_vectors/storage-stability.json").unwrap();
+        serde_json::from_reader(file).unwrap()
+    };
+    let KatData { group_id, storages } = data.remove(&ciphersuite)?;
+
+    let storages = storages
+        .iter()
+        .map(|storage| {
+            let storage = base64_engine.decode(storage).unwrap();
+            MemoryStorage::deserialize(&mut storage.as_slice()).unwrap()
+        })
+        .collect();
+
+    Some((group_id, storages))
+}
+
+/// Migrating the groups of all stored states keeps them intact.
+#[openmls_test]
+fn migrate_kat_storages() {
+    let Some((group_id, storages)) = load_storages(ciphersuite) else {
+        return;
+    };
+
+    for storage in storages {
+        let group = MlsGroup::load(&storage, &group_id).unwrap().unwrap();
+        assert_eq!(
+            stored_version(&storage, &group_id).unwrap(),
+            Some(CURRENT_VERSION)
+        );
+
+        assert_eq!(
+            migrate_group(&storage, &group_id).unwrap(),
+            Some(CURRENT_VERSION)
+        );
+        let migrated_group = MlsGroup::load(&storage, &group_id).unwrap().unwrap();
+        assert_eq!(group, migrated_group);
+
+        // Rewriting every value in its serialized form keeps the group intact
+        // as well.
+        let stored_group = StoredGroup::read::<CURRENT_VERSION, _>(&storage, &group_id)
+            .unwrap()
+            .unwrap();
+        assert!(
+            migrate_group_step::<CURRENT_VERSION, CURRENT_VERSION, _, _>(
+                &storage,
+                &group_id,
+                |_| Ok(())
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            StoredGroup::read::<CURRENT_VERSION, _>(&storage, &group_id)
+                .unwrap()
+                .unwrap(),
+            stored_group
+        );
+        let migrated_group = MlsGroup::load(&storage, &group_id).unwrap().unwrap();
+        assert_eq!(group, migrated_group);
+    }
+}
+
+/// Migrating a group whose tree is stored as a whole stores the tree node by
+/// node.
+#[openmls_test]
+fn migrate_complete_trees() {
+    let Some((group_id, storages)) = load_storages(ciphersuite) else {
+        return;
+    };
+    let stored_tree = |storage: &MemoryStorage| -> StoredTreeSync {
+        <MemoryStorage as StorageProvider<CURRENT_VERSION>>::tree(storage, &group_id)
+            .unwrap()
+            .unwrap()
+    };
+
+    let mut complete_trees = 0;
+    for storage in storages {
+        let group = MlsGroup::load(&storage, &group_id).unwrap().unwrap();
+        if matches!(stored_tree(&storage), StoredTreeSync::Complete(_)) {
+            complete_trees += 1;
+        }
+
+        migrate_group(&storage, &group_id).unwrap();
+        assert!(matches!(
+            stored_tree(&storage),
+            StoredTreeSync::Nodes { .. }
+        ));
+        let migrated_group = MlsGroup::load(&storage, &group_id).unwrap().unwrap();
+        assert_eq!(group, migrated_group);
+    }
+
+    // The vectors of this ciphersuite were written before the tree nodes were
+    // stored individually.
+    if ciphersuite == Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519 {
+        assert!(complete_trees > 0);
+    }
+}
+
+/// A migration step converts the stored values of a group.
+#[openmls_test]
+fn migration_step() {
+    let Some((group_id, storages)) = load_storages(ciphersuite) else {
+        return;
+    };
+    let storage = storages.last().unwrap();
+    let group = MlsGroup::load(storage, &group_id).unwrap().unwrap();
+    assert_ne!(group.configuration().padding_size(), 16);
+
+    assert!(
+        migrate_group_step::<CURRENT_VERSION, CURRENT_VERSION, _, _>(
+            storage,
+            &group_id,
+            |stored_group| {
+                stored_group.join_config.0["padding_size"] = 16.into();
+                Ok(())
+            }
+        )
+        .unwrap()
+    );
+
+    let migrated_group = MlsGroup::load(storage, &group_id).unwrap().unwrap();
+    assert_eq!(migrated_group.configuration().padding_size(), 16);
+    assert_eq!(migrated_group.epoch(), group.epoch());
+    assert_eq!(
+        migrated_group.pending_proposals().count(),
+        group.pending_proposals().count()
+    );
+    assert_eq!(
+        migrated_group.export_ratchet_tree(),
+        group.export_ratchet_tree()
+    );
+
+    // There is nothing to migrate for groups that aren't stored.
+    let unknown_group_id = GroupId::from_slice(b"unknown group");
+    assert_eq!(stored_version(storage, &unknown_group_id).unwrap(), None);
+    assert_eq!(migrate_group(storage, &unknown_group_id).unwrap(), None);
+    assert!(
+        !migrate_group_step::<CURRENT_VERSION, CURRENT_VERSION, _, _>(
+            storage,
+            &unknown_group_id,
+            |_| Ok(())
+        )
+        .unwrap()
+    );
+}
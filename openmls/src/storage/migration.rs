@@ -0,0 +1,669 @@
+//! Migration of stored groups between storage versions.
+//!
+//! The [`StorageProvider`] trait is generic over a storage `VERSION`. When a
+//! release of OpenMLS changes the serialized form of a stored type so that it
+//! can't read the values of earlier releases anymore, it increments
+//! [`CURRENT_VERSION`] and adds a migration step from the previous version to
+//! [`migrate_group`]. A step reads all values of a group at the old version,
+//! converts them and writes them at the new version. The steps are applied
+//! one after the other, so that a group written by any older release can be
+//! upgraded, even if several releases were skipped.
+//!
+//! Changes that OpenMLS can still read the old values for keep the version.
+//! They may add a step within the current version that brings old values into
+//! the new form, like the step that stores a tree that was stored as a whole
+//! node by node.
+//!
+//! A storage provider that can be migrated implements `StorageProvider` for
+//! every version it has stored values with, and keeps the values of different
+//! versions apart. Values are converted in their serialized form, i.e. as
+//! [`serde_json::Value`]s, so the provider must use a self-describing
+//! serialization format such as JSON.
+//!
+//! [`migrate_group`] only migrates values that belong to a group, including
+//! the encryption key pairs of pending updates of the own leaf. Key packages,
+//! signature key pairs and PSKs are addressed by keys that only the
+//! application knows. The application migrates them one by one with
+//! [`migrate_key_package`], [`migrate_signature_key_pair`] and [`migrate_psk`].
+//!
+//! [`StorageProvider`]: openmls_traits::storage::StorageProvider
+
+use openmls_traits::storage::{traits, Entity, Key, StorageProvider, CURRENT_VERSION};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    ciphersuite::hash_ref::KeyPackageRef, group::GroupId, schedule::Psk,
+    treesync::StoredTreeSync,
+};
+
+/// The storage versions this release of OpenMLS can read, from the newest to
+/// the oldest.
+pub const SUPPORTED_VERSIONS: &[u16] = &[CURRENT_VERSION];
+
+/// Migration error
+#[derive(Error, Debug, PartialEq, Clone)]
+pub enum MigrationError<StorageError> {
+    /// Some values of the group are missing in storage.
+    #[error("Some values of the group are missing in storage.")]
+    IncompleteGroup,
+    /// A stored value could not be converted.
+    #[error("A stored value could not be converted: {0}")]
+    InvalidValue(String),
+    /// An error occurred in the storage provider.
+    #[error("An error occurred in the storage provider.")]
+    StorageError(StorageError),
+}
+
+/// A stored value in its serialized form.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct StoredValue(pub serde_json::Value);
+
+impl StoredValue {
+    fn new<StorageError>(value: &impl Serialize) -> Result<Self, MigrationError<StorageError>> {
+        serde_json::to_value(value)
+            .map(Self)
+            .map_err(|e| MigrationError::InvalidValue(e.to_string()))
+    }
+}
+
+impl<const VERSION: u16> Key<VERSION> for StoredValue {}
+impl<const VERSION: u16> Entity<VERSION> for StoredValue {}
+impl<const VERSION: u16> traits::GroupId<VERSION> for StoredValue {}
+impl<const VERSION: u16> traits::EpochKey<VERSION> for StoredValue {}
+impl<const VERSION: u16> traits::ProposalRef<VERSION> for StoredValue {}
+impl<const VERSION: u16> traits::QueuedProposal<VERSION> for StoredValue {}
+impl<const VERSION: u16> traits::TreeSync<VERSION> for StoredValue {}
+impl<const VERSION: u16> traits::TreeNode<VERSION> for StoredValue {}
+impl<const VERSION: u16> traits::GroupContext<VERSION> for StoredValue {}
+impl<const VERSION: u16> traits::InterimTranscriptHash<VERSION> for StoredValue {}
+impl<const VERSION: u16> traits::ConfirmationTag<VERSION> for StoredValue {}
+impl<const VERSION: u16> traits::HpkeKeyPair<VERSION> for StoredValue {}
+impl<const VERSION: u16> traits::EncryptionKey<VERSION> for StoredValue {}
+impl<const VERSION: u16> traits::GroupState<VERSION> for StoredValue {}
+impl<const VERSION: u16> traits::GroupEpochSecrets<VERSION> for StoredValue {}
+impl<const VERSION: u16> traits::LeafNodeIndex<VERSION> for StoredValue {}
+impl<const VERSION: u16> traits::MessageSecrets<VERSION> for StoredValue {}
+impl<const VERSION: u16> traits::ResumptionPskStore<VERSION> for StoredValue {}
+impl<const VERSION: u16> traits::MlsGroupJoinConfig<VERSION> for StoredValue {}
+impl<const VERSION: u16> traits::LeafNode<VERSION> for StoredValue {}
+impl<const VERSION: u16> traits::HashReference<VERSION> for StoredValue {}
+impl<const VERSION: u16> traits::KeyPackage<VERSION> for StoredValue {}
+impl<const VERSION: u16> traits::SignaturePublicKey<VERSION> for StoredValue {}
+impl<const VERSION: u16> traits::SignatureKeyPair<VERSION> for StoredValue {}
+impl<const VERSION: u16> traits::PskId<VERSION> for StoredValue {}
+impl<const VERSION: u16> traits::PskBundle<VERSION> for StoredValue {}
+
+/// All values that are stored for a group, in their serialized form.
+///
+/// A migration step converts the values of a group from one storage version
+/// to the next by modifying this struct.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredGroup {
+    /// The `MlsGroupJoinConfig`.
+    pub join_config: StoredValue,
+    /// The own leaf nodes of pending updates.
+    pub own_leaf_nodes: Vec<StoredValue>,
+    /// The encryption key pairs of the own leaf nodes with their public keys.
+    #[serde(default)]
+    pub own_leaf_node_key_pairs: Vec<(StoredValue, StoredValue)>,
+    /// The queued proposals with their `ProposalRef`s.
+    pub queued_proposals: Vec<(StoredValue, StoredValue)>,
+    /// The `TreeSync` tree, or its size and tree hash if the nodes are stored
+    /// individually.
+    pub tree: StoredValue,
+    /// The individually stored nodes of the tree with their indices.
+    #[serde(default)]
+    pub tree_nodes: Vec<(u32, StoredValue)>,
+    /// The interim transcript hash.
+    pub interim_transcript_hash: StoredValue,
+    /// The `GroupContext`.
+    pub context: StoredValue,
+    /// The confirmation tag.
+    pub confirmation_tag: StoredValue,
+    /// The `MlsGroupState`.
+    pub group_state: StoredValue,
+    /// The `MessageSecretsStore`.
+    pub message_secrets: StoredValue,
+    /// The `ResumptionPskStore`.
+    pub resumption_psk_store: StoredValue,
+    /// The own `LeafNodeIndex`.
+    pub own_leaf_index: StoredValue,
+    /// The `GroupEpochSecrets`.
+    pub group_epoch_secrets: StoredValue,
+    /// The encryption key pairs of the current epoch.
+    pub epoch_key_pairs: Vec<StoredValue>,
+}
+
+impl StoredGroup {
+    /// Reads the values of the group with the given id at storage version
+    /// `VERSION`. Returns `None` if no group is stored at this version.
+    pub fn read<const VERSION: u16, Storage: StorageProvider<VERSION>>(
+        storage: &Storage,
+        group_id: &GroupId,
+    ) -> Result<Option<Self>, MigrationError<Storage::Error>> {
+        let group_id = StoredValue::new(group_id)?;
+        let storage_error = MigrationError::StorageError;
+
+        let group_state: Option<StoredValue> =
+            storage.group_state(&group_id).map_err(storage_error)?;
+        let Some(group_state) = group_state else {
+            return Ok(None);
+        };
+        let required = |value: Option<StoredValue>| value.ok_or(MigrationError::IncompleteGroup);
+
+        let context: StoredValue =
+            required(storage.group_context(&group_id).map_err(storage_error)?)?;
+        let own_leaf_index: StoredValue =
+            required(storage.own_leaf_index(&group_id).map_err(storage_error)?)?;
+        let (epoch, leaf_index) = Self::epoch_key_pairs_id(&context, &own_leaf_index)?;
+
+        let own_leaf_nodes: Vec<StoredValue> =
+            storage.own_leaf_nodes(&group_id).map_err(storage_error)?;
+        let mut own_leaf_node_key_pairs = Vec::with_capacity(own_leaf_nodes.len());
+        for leaf_node in &own_leaf_nodes {
+            let public_key = Self::encryption_key(leaf_node)?;
+            let key_pair = required(
+                storage
+                    .encryption_key_pair(&public_key)
+                    .map_err(storage_error)?,
+            )?;
+            own_leaf_node_key_pairs.push((public_key, key_pair));
+        }
+
+        // The storage returns the tree nodes in any order.
+        let mut tree_nodes: Vec<(u32, StoredValue)> =
+            storage.tree_nodes(&group_id).map_err(storage_error)?;
+        tree_nodes.sort_by_key(|(node_index, _)| *node_index);
+
+        Ok(Some(Self {
+            join_config: required(
+                storage
+                    .mls_group_join_config(&group_id)
+                    .map_err(storage_error)?,
+            )?,
+            own_leaf_nodes,
+            own_leaf_node_key_pairs,
+            queued_proposals: storage.queued_proposals(&group_id).map_err(storage_error)?,
+            tree: required(storage.tree(&group_id).map_err(storage_error)?)?,
+            tree_nodes,
+            interim_transcript_hash: required(
+                storage
+                    .interim_transcript_hash(&group_id)
+                    .map_err(storage_error)?,
+            )?,
+            confirmation_tag: required(
+                storage.confirmation_tag(&group_id).map_err(storage_error)?,
+            )?,
+            group_state,
+            message_secrets: required(storage.message_secrets(&group_id).map_err(storage_error)?)?,
+            resumption_psk_store: required(
+                storage
+                    .resumption_psk_store(&group_id)
+                    .map_err(storage_error)?,
+            )?,
+            group_epoch_secrets: required(
+                storage
+                    .group_epoch_secrets(&group_id)
+                    .map_err(storage_error)?,
+            )?,
+            epoch_key_pairs: storage
+                .encryption_epoch_key_pairs(&group_id, &epoch, leaf_index)
+                .map_err(storage_error)?,
+            context,
+            own_leaf_index,
+        }))
+    }
+
+    /// Writes the values of the group with the given id at storage version
+    /// `VERSION`.
+    pub fn write<const VERSION: u16, Storage: StorageProvider<VERSION>>(
+        &self,
+        storage: &Storage,
+        group_id: &GroupId,
+    ) -> Result<(), MigrationError<Storage::Error>> {
+        let group_id = StoredValue::new(group_id)?;
+        let (epoch, leaf_index) = Self::epoch_key_pairs_id(&self.context, &self.own_leaf_index)?;
+
+        let write = || -> Result<(), Storage::Error> {
+            storage.write_mls_join_config(&group_id, &self.join_config)?;
+            for leaf_node in &self.own_leaf_nodes {
+                storage.append_own_leaf_node(&group_id, leaf_node)?;
+            }
+            for (public_key, key_pair) in &self.own_leaf_node_key_pairs {
+                storage.write_encryption_key_pair(public_key, key_pair)?;
+            }
+            for (proposal_ref, proposal) in &self.queued_proposals {
+                storage.queue_proposal(&group_id, proposal_ref, proposal)?;
+            }
+            storage.write_tree(&group_id, &self.tree)?;
+            for (node_index, node) in &self.tree_nodes {
+                storage.write_tree_node(&group_id, *node_index, node)?;
+            }
+            storage.write_interim_transcript_hash(&group_id, &self.interim_transcript_hash)?;
+            storage.write_context(&group_id, &self.context)?;
+            storage.write_confirmation_tag(&group_id, &self.confirmation_tag)?;
+            storage.write_group_state(&group_id, &self.group_state)?;
+            storage.write_message_secrets(&group_id, &self.message_secrets)?;
+            storage.write_resumption_psk_store(&group_id, &self.resumption_psk_store)?;
+            storage.write_own_leaf_index(&group_id, &self.own_leaf_index)?;
+            storage.write_group_epoch_secrets(&group_id, &self.group_epoch_secrets)?;
+            storage.write_encryption_epoch_key_pairs(
+                &group_id,
+                &epoch,
+                leaf_index,
+                &self.epoch_key_pairs,
+            )
+        };
+        write().map_err(MigrationError::StorageError)
+    }
+
+    /// Deletes the values of the group with the given id at storage version
+    /// `VERSION`.
+    pub fn delete<const VERSION: u16, Storage: StorageProvider<VERSION>>(
+        &self,
+        storage: &Storage,
+        group_id: &GroupId,
+    ) -> Result<(), MigrationError<Storage::Error>> {
+        let group_id = StoredValue::new(group_id)?;
+        let (epoch, leaf_index) = Self::epoch_key_pairs_id(&self.context, &self.own_leaf_index)?;
+
+        let delete = || -> Result<(), Storage::Error> {
+            storage.delete_group_config(&group_id)?;
+            storage.delete_own_leaf_nodes(&group_id)?;
+            for (public_key, _) in &self.own_leaf_node_key_pairs {
+                storage.delete_encryption_key_pair(public_key)?;
+            }
+            storage.clear_proposal_queue::<StoredValue, StoredValue>(&group_id)?;
+            storage.delete_tree(&group_id)?;
+            storage.delete_tree_nodes(&group_id)?;
+            storage.delete_interim_transcript_hash(&group_id)?;
+            storage.delete_context(&group_id)?;
+            storage.delete_confirmation_tag(&group_id)?;
+            storage.delete_group_state(&group_id)?;
+            storage.delete_message_secrets(&group_id)?;
+            storage.delete_all_resumption_psk_secrets(&group_id)?;
+            storage.delete_own_leaf_index(&group_id)?;
+            storage.delete_group_epoch_secrets(&group_id)?;
+            storage.delete_encryption_epoch_key_pairs(&group_id, &epoch, leaf_index)
+        };
+        delete().map_err(MigrationError::StorageError)
+    }
+
+    /// The public encryption key of a leaf node, which its key pair is stored
+    /// under.
+    fn encryption_key<StorageError>(
+        leaf_node: &StoredValue,
+    ) -> Result<StoredValue, MigrationError<StorageError>> {
+        leaf_node
+            .0
+            .get("payload")
+            .and_then(|payload| payload.get("encryption_key"))
+            .cloned()
+            .map(StoredValue)
+            .ok_or_else(|| MigrationError::InvalidValue("leaf node without encryption key".into()))
+    }
+
+    /// The epoch and leaf index the encryption key pairs of the current epoch
+    /// are stored under.
+    fn epoch_key_pairs_id<StorageError>(
+        context: &StoredValue,
+        own_leaf_index: &StoredValue,
+    ) -> Result<(StoredValue, u32), MigrationError<StorageError>> {
+        let epoch =
+            context.0.get("epoch").cloned().ok_or_else(|| {
+                MigrationError::InvalidValue("group context without epoch".into())
+            })?;
+        let leaf_index = own_leaf_index
+            .0
+            .as_u64()
+            .and_then(|leaf_index| u32::try_from(leaf_index).ok())
+            .ok_or_else(|| MigrationError::InvalidValue("invalid own leaf index".into()))?;
+
+        Ok((StoredValue(epoch), leaf_index))
+    }
+}
+
+/// Migrates the group with the given id from storage version `FROM` to
+/// storage version `TO`.
+///
+/// The values of the group are read at version `FROM`, converted with
+/// `migrate`, deleted at version `FROM` and written at version `TO` in a
+/// single transaction. Returns `false` if no group is stored at version
+/// `FROM`.
+pub fn migrate_group_step<const FROM: u16, const TO: u16, Storage, StorageError>(
+    storage: &Storage,
+    group_id: &GroupId,
+    migrate: impl FnOnce(&mut StoredGroup) -> Result<(), MigrationError<StorageError>>,
+) -> Result<bool, MigrationError<StorageError>>
+where
+    Storage:
+        StorageProvider<FROM, Error = StorageError> + StorageProvider<TO, Error = StorageError>,
+    StorageError: std::fmt::Debug,
+{
+    let Some(mut stored_group) = StoredGroup::read::<FROM, _>(storage, group_id)? else {
+        return Ok(false);
+    };
+    migrate(&mut stored_group)?;
+
+    in_transaction::<TO, _, _>(storage, || {
+        stored_group
+            .delete::<FROM, _>(storage, group_id)
+            .and_then(|_| stored_group.write::<TO, _>(storage, group_id))
+    })?;
+
+    Ok(true)
+}
+
+/// Migrates the key package bundle with the given reference from storage
+/// version `FROM` to storage version `TO`.
+///
+/// The key package bundle is read at version `FROM`, converted with
+/// `migrate`, deleted at version `FROM` and written at version `TO` in a
+/// single transaction. Returns `false` if no key package is stored under the
+/// reference at version `FROM`.
+pub fn migrate_key_package<const FROM: u16, const TO: u16, Storage, StorageError>(
+    storage: &Storage,
+    key_package_ref: &KeyPackageRef,
+    migrate: impl FnOnce(&mut StoredValue) -> Result<(), MigrationError<StorageError>>,
+) -> Result<bool, MigrationError<StorageError>>
+where
+    Storage:
+        StorageProvider<FROM, Error = StorageError> + StorageProvider<TO, Error = StorageError>,
+    StorageError: std::fmt::Debug,
+{
+    let key = StoredValue::new(key_package_ref)?;
+    let value = <Storage as StorageProvider<FROM>>::key_package(storage, &key)
+        .map_err(MigrationError::StorageError)?;
+    migrate_value::<TO, _, _>(storage, value, migrate, |value| {
+        <Storage as StorageProvider<FROM>>::delete_key_package(storage, &key)?;
+        <Storage as StorageProvider<TO>>::write_key_package(storage, &key, value)
+    })
+}
+
+/// Migrates the signature key pair that is stored under the given
+/// `public_key` from storage version `FROM` to storage version `TO`.
+///
+/// The `public_key` is the key the application stored the key pair under,
+/// e.g. the `StorageId` of an `openmls_basic_credential::SignatureKeyPair`.
+/// Otherwise this works like [`migrate_key_package`].
+pub fn migrate_signature_key_pair<const FROM: u16, const TO: u16, Storage, StorageError>(
+    storage: &Storage,
+    public_key: &impl Serialize,
+    migrate: impl FnOnce(&mut StoredValue) -> Result<(), MigrationError<StorageError>>,
+) -> Result<bool, MigrationError<StorageError>>
+where
+    Storage:
+        StorageProvider<FROM, Error = StorageError> + StorageProvider<TO, Error = StorageError>,
+    StorageError: std::fmt::Debug,
+{
+    let key = StoredValue::new(public_key)?;
+    let value = <Storage as StorageProvider<FROM>>::signature_key_pair(storage, &key)
+        .map_err(MigrationError::StorageError)?;
+    migrate_value::<TO, _, _>(storage, value, migrate, |value| {
+        <Storage as StorageProvider<FROM>>::delete_signature_key_pair(storage, &key)?;
+        <Storage as StorageProvider<TO>>::write_signature_key_pair(storage, &key, value)
+    })
+}
+
+/// Migrates the external PSK with the given id from storage version `FROM` to
+/// storage version `TO`. Otherwise this works like [`migrate_key_package`].
+pub fn migrate_psk<const FROM: u16, const TO: u16, Storage, StorageError>(
+    storage: &Storage,
+    psk_id: &Psk,
+    migrate: impl FnOnce(&mut StoredValue) -> Result<(), MigrationError<StorageError>>,
+) -> Result<bool, MigrationError<StorageError>>
+where
+    Storage:
+        StorageProvider<FROM, Error = StorageError> + StorageProvider<TO, Error = StorageError>,
+    StorageError: std::fmt::Debug,
+{
+    let key = StoredValue::new(psk_id)?;
+    let value = <Storage as StorageProvider<FROM>>::psk(storage, &key)
+        .map_err(MigrationError::StorageError)?;
+    migrate_value::<TO, _, _>(storage, value, migrate, |value| {
+        <Storage as StorageProvider<FROM>>::delete_psk(storage, &key)?;
+        <Storage as StorageProvider<TO>>::write_psk(storage, &key, value)
+    })
+}
+
+/// Converts a single stored `value` with `migrate` and replaces the old value
+/// with it using `replace` in a transaction at version `TO`.
+fn migrate_value<const TO: u16, Storage, StorageError>(
+    storage: &Storage,
+    value: Option<StoredValue>,
+    migrate: impl FnOnce(&mut StoredValue) -> Result<(), MigrationError<StorageError>>,
+    replace: impl FnOnce(&StoredValue) -> Result<(), StorageError>,
+) -> Result<bool, MigrationError<StorageError>>
+where
+    Storage: StorageProvider<TO, Error = StorageError>,
+    StorageError: std::fmt::Debug,
+{
+    let Some(mut value) = value else {
+        return Ok(false);
+    };
+    migrate(&mut value)?;
+
+    in_transaction::<TO, _, _>(storage, || {
+        replace(&value).map_err(MigrationError::StorageError)
+    })?;
+
+    Ok(true)
+}
+
+/// Runs `f` in a transaction of the storage at version `VERSION`, which is
+/// rolled back if `f` fails.
+fn in_transaction<const VERSION: u16, Storage, StorageError>(
+    storage: &Storage,
+    f: impl FnOnce() -> Result<(), MigrationError<StorageError>>,
+) -> Result<(), MigrationError<StorageError>>
+where
+    Storage: StorageProvider<VERSION, Error = StorageError>,
+    StorageError: std::fmt::Debug,
+{
+    storage
+        .begin_transaction()
+        .map_err(MigrationError::StorageError)?;
+    match f() {
+        Ok(()) => storage
+            .commit_transaction()
+            .map_err(MigrationError::StorageError),
+        Err(e) => {
+            if let Err(rollback_error) = storage.rollback_transaction() {
+                log::error!("Failed to roll back storage transaction: {rollback_error:?}");
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Returns the storage version the group with the given id is stored with,
+/// or `None` if it isn't stored with any of the [`SUPPORTED_VERSIONS`].
+pub fn stored_version<Storage: super::StorageProvider>(
+    storage: &Storage,
+    group_id: &GroupId,
+) -> Result<Option<u16>, Storage::Error> {
+    let group_state: Option<StoredValue> = storage.group_state(group_id)?;
+    Ok(group_state.map(|_| CURRENT_VERSION))
+}
+
+/// Migrates the group with the given id to the [`CURRENT_VERSION`] of the
+/// storage, applying the migration steps of all releases since the group was
+/// stored.
+///
+/// Returns the storage version the group was stored with before the
+/// migration, or `None` if it isn't stored with any of the
+/// [`SUPPORTED_VERSIONS`]. Call this before [`MlsGroup::load`] after
+/// upgrading OpenMLS.
+///
+/// [`MlsGroup::load`]: crate::group::MlsGroup::load
+pub fn migrate_group<Storage: super::StorageProvider>(
+    storage: &Storage,
+    group_id: &GroupId,
+) -> Result<Option<u16>, MigrationError<Storage::Error>> {
+    let stored_version = stored_version(storage, group_id).map_err(MigrationError::StorageError)?;
+
+    // The migration steps from older versions go here, from the oldest to
+    // the newest, e.g.
+    // `if stored_version < Some(2) { migrate_group_step::<1, 2, _, _>(storage, group_id, v1_to_v2)?; }`
+    // There are no older versions yet.
+
+    // Steps within the current version.
+    let tree: Option<StoredTreeSync> = storage
+        .tree(group_id)
+        .map_err(MigrationError::StorageError)?;
+    if matches!(tree, Some(StoredTreeSync::Complete(_))) {
+        migrate_group_step::<CURRENT_VERSION, CURRENT_VERSION, _, _>(
+            storage,
+            group_id,
+            store_tree_nodes,
+        )?;
+    }
+
+    Ok(stored_version)
+}
+
+/// Converts a tree that is stored as a whole, as releases before the nodes
+/// were stored individually did, into its individual nodes.
+fn store_tree_nodes<StorageError>(
+    stored_group: &mut StoredGroup,
+) -> Result<(), MigrationError<StorageError>> {
+    let tree = serde_json::from_value(stored_group.tree.0.clone())
+        .map_err(|e| MigrationError::InvalidValue(e.to_string()))?;
+    let StoredTreeSync::Complete(treesync) = tree else {
+        return Ok(());
+    };
+
+    stored_group.tree_nodes = treesync
+        .full_nodes()
+        .map(|(node_index, node)| Ok((node_index, StoredValue::new(&node)?)))
+        .collect::<Result<_, _>>()?;
+    stored_group.tree = StoredValue::new(&StoredTreeSync::Nodes {
+        tree_size: treesync.tree_size().u32(),
+        tree_hash: treesync.tree_hash().to_vec(),
+    })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use openmls_basic_credential::SignatureKeyPair;
+    use openmls_test::openmls_test;
+    use openmls_traits::OpenMlsProvider as _;
+
+    use super::*;
+    use crate::{
+        group::mls_group::tests_and_kats::utils::setup_client,
+        key_packages::KeyPackageBundle,
+        schedule::{ExternalPsk, PreSharedKeyId},
+    };
+
+    #[openmls_test]
+    fn migrate_values_outside_of_groups() {
+        let storage = provider.storage();
+        let (_credential_with_key, key_package_bundle, signer, _pk) =
+            setup_client("Alice", ciphersuite, provider);
+
+        // Key package
+        let key_package_ref = key_package_bundle
+            .key_package()
+            .hash_ref(provider.crypto())
+            .unwrap();
+        let mut migrated = None;
+        assert!(
+            migrate_key_package::<CURRENT_VERSION, CURRENT_VERSION, _, _>(
+                storage,
+                &key_package_ref,
+                |value| {
+                    migrated = Some(value.clone());
+                    Ok(())
+                },
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            migrated.unwrap(),
+            StoredValue::new::<()>(&key_package_bundle).unwrap()
+        );
+        let stored: KeyPackageBundle = storage.key_package(&key_package_ref).unwrap().unwrap();
+        assert_eq!(stored.key_package(), key_package_bundle.key_package());
+
+        // A failing conversion leaves the stored value untouched.
+        let result = migrate_key_package::<CURRENT_VERSION, CURRENT_VERSION, _, _>(
+            storage,
+            &key_package_ref,
+            |_| Err(MigrationError::InvalidValue("test".to_string())),
+        );
+        assert!(matches!(result, Err(MigrationError::InvalidValue(_))));
+        assert!(storage
+            .key_package::<_, KeyPackageBundle>(&key_package_ref)
+            .unwrap()
+            .is_some());
+
+        // Signature key pair
+        assert!(
+            migrate_signature_key_pair::<CURRENT_VERSION, CURRENT_VERSION, _, _>(
+                storage,
+                &signer.id(),
+                |_| Ok(()),
+            )
+            .unwrap()
+        );
+        let stored = SignatureKeyPair::read(
+            storage,
+            signer.public(),
+            ciphersuite.signature_algorithm(),
+        )
+        .unwrap();
+        assert_eq!(stored.public(), signer.public());
+
+        // External PSK
+        let psk_id = PreSharedKeyId::new(
+            ciphersuite,
+            provider.rand(),
+            Psk::External(ExternalPsk::new(b"external psk".to_vec())),
+        )
+        .unwrap();
+        psk_id.store(provider, &[1u8; 32]).unwrap();
+        assert!(
+            migrate_psk::<CURRENT_VERSION, CURRENT_VERSION, _, _>(storage, psk_id.psk(), |_| Ok(
+                ()
+            ))
+            .unwrap()
+        );
+        assert!(storage
+            .psk::<crate::schedule::psk::PskBundle, _>(psk_id.psk())
+            .unwrap()
+            .is_some());
+
+        // Values that aren't stored are not migrated.
+        let unknown_psk = Psk::External(ExternalPsk::new(b"unknown psk".to_vec()));
+        assert!(!migrate_psk::<CURRENT_VERSION, CURRENT_VERSION, _, _>(
+            storage,
+            &unknown_psk,
+            |_| panic!("nothing to migrate")
+        )
+        .unwrap());
+        let (_, other_key_package_bundle, other_signer, _) =
+            setup_client("Bob", ciphersuite, &Provider::default());
+        let other_key_package_ref = other_key_package_bundle
+            .key_package()
+            .hash_ref(provider.crypto())
+            .unwrap();
+        assert!(!migrate_key_package::<CURRENT_VERSION, CURRENT_VERSION, _, _>(
+            storage,
+            &other_key_package_ref,
+            |_| panic!("nothing to migrate")
+        )
+        .unwrap());
+        assert!(
+            !migrate_signature_key_pair::<CURRENT_VERSION, CURRENT_VERSION, _, _>(
+                storage,
+                &other_signer.id(),
+                |_| panic!("nothing to migrate")
+            )
+            .unwrap()
+        );
+    }
+}
@@ -75,6 +75,7 @@ impl Size for FrankenProposal {
                 FrankenProposal::ExternalInit(p) => p.tls_serialized_len(),
                 FrankenProposal::GroupContextExtensions(p) => p.tls_serialized_len(),
                 FrankenProposal::AppAck(p) => p.tls_serialized_len(),
+                FrankenProposal::SelfRemove => 0,
                 FrankenProposal::Custom(p) => p.tls_serialized_len(),
             }
     }
@@ -92,6 +93,7 @@ impl Serialize for FrankenProposal {
             FrankenProposal::ExternalInit(p) => p.tls_serialize(writer),
             FrankenProposal::GroupContextExtensions(p) => p.tls_serialize(writer),
             FrankenProposal::AppAck(p) => p.tls_serialize(writer),
+            FrankenProposal::SelfRemove => Ok(0),
             FrankenProposal::Custom(p) => p.payload.tls_serialize(writer),
         }
         .map(|l| written + l)
@@ -129,6 +131,7 @@ impl Deserialize for FrankenProposal {
             FrankenProposalType::AppAck => {
                 FrankenProposal::AppAck(FrankenAppAckProposal::tls_deserialize(bytes)?)
             }
+            FrankenProposalType::SelfRemove => FrankenProposal::SelfRemove,
             FrankenProposalType::Custom(_) => {
                 let payload = VLBytes::tls_deserialize(bytes)?;
                 let custom_proposal = FrankenCustomProposal {
@@ -12,6 +12,7 @@ pub enum FrankenProposalType {
     ExternalInit,
     GroupContextExtensions,
     AppAck,
+    SelfRemove,
     Custom(u16),
 }
 
@@ -26,6 +27,7 @@ impl From<u16> for FrankenProposalType {
             6 => FrankenProposalType::ExternalInit,
             7 => FrankenProposalType::GroupContextExtensions,
             8 => FrankenProposalType::AppAck,
+            10 => FrankenProposalType::SelfRemove,
             other => FrankenProposalType::Custom(other),
         }
     }
@@ -42,6 +44,7 @@ impl From<FrankenProposalType> for u16 {
             FrankenProposalType::ExternalInit => 6,
             FrankenProposalType::GroupContextExtensions => 7,
             FrankenProposalType::AppAck => 8,
+            FrankenProposalType::SelfRemove => 10,
             FrankenProposalType::Custom(id) => id,
         }
     }
@@ -60,6 +63,7 @@ impl FrankenProposal {
                 FrankenProposalType::GroupContextExtensions
             }
             FrankenProposal::AppAck(_) => FrankenProposalType::AppAck,
+            FrankenProposal::SelfRemove => FrankenProposalType::SelfRemove,
             FrankenProposal::Custom(FrankenCustomProposal {
                 proposal_type,
                 payload: _,
@@ -79,6 +83,7 @@ pub enum FrankenProposal {
     ExternalInit(FrankenExternalInitProposal),
     GroupContextExtensions(Vec<FrankenExtension>),
     AppAck(FrankenAppAckProposal),
+    SelfRemove,
     Custom(FrankenCustomProposal),
 }
 
@@ -183,7 +188,7 @@ pub struct FrankenAppAckProposal {
     Debug, Clone, PartialEq, Eq, TlsSerialize, TlsDeserialize, TlsDeserializeBytes, TlsSize,
 )]
 pub struct FrankenMessageRange {
-    pub sender: VLBytes,
+    pub sender: u32,
     pub first_generation: u32,
     pub last_generation: u32,
 }
@@ -0,0 +1,95 @@
+//! The group operations of the storage stability KAT.
+//!
+//! They are shared by the KAT, which serializes the memory storage after every
+//! step, and the tests of other storages, which check that the group they
+//! load after every step matches the group in memory.
+
+use openmls_traits::types::Ciphersuite;
+
+use crate::{
+    prelude::{test_utils::new_credential, *},
+    storage::OpenMlsProvider,
+};
+
+/// Performs the group operations of the storage stability KAT with Alice,
+/// Bob and Charlie using the given providers, and calls `check` with Alice's
+/// group after every step. Returns Alice's group.
+///
+/// The steps are: create the group, add Bob (pending and merged), update the
+/// group context extensions (pending and merged), and propose to add Charlie.
+pub fn run_storage_stability<Provider: OpenMlsProvider>(
+    ciphersuite: Ciphersuite,
+    alice_provider: &Provider,
+    bob_provider: &Provider,
+    charlie_provider: &Provider,
+    mut check: impl FnMut(&MlsGroup),
+) -> MlsGroup {
+    let (alice_cwk, alice_signer) =
+        new_credential(alice_provider, b"alice", ciphersuite.signature_algorithm());
+    let (bob_cwk, bob_signer) =
+        new_credential(bob_provider, b"bob", ciphersuite.signature_algorithm());
+    let (charlie_cwk, charlie_signer) = new_credential(
+        charlie_provider,
+        b"charlie",
+        ciphersuite.signature_algorithm(),
+    );
+
+    let capabilities = Capabilities::new(
+        None,
+        None,
+        Some(&[ExtensionType::Unknown(0xf042)]),
+        None,
+        None,
+    );
+
+    let mut alice_group = MlsGroup::builder()
+        .ciphersuite(ciphersuite)
+        .with_capabilities(capabilities.clone())
+        .build(alice_provider, &alice_signer, alice_cwk)
+        .expect("error creating group using builder");
+    check(&alice_group);
+
+    // Pending commit that adds Bob.
+    let bob_kpb = KeyPackageBuilder::new()
+        .leaf_node_capabilities(capabilities.clone())
+        .build(ciphersuite, bob_provider, &bob_signer, bob_cwk)
+        .unwrap();
+    alice_group
+        .add_members(
+            alice_provider,
+            &alice_signer,
+            &[bob_kpb.key_package().to_owned()],
+        )
+        .unwrap();
+    check(&alice_group);
+
+    alice_group.merge_pending_commit(alice_provider).unwrap();
+    check(&alice_group);
+
+    // Pending commit that updates the group context extensions.
+    alice_group
+        .update_group_context_extensions(
+            alice_provider,
+            Extensions::single(Extension::RequiredCapabilities(
+                RequiredCapabilitiesExtension::new(&[ExtensionType::Unknown(0xf042)], &[], &[]),
+            )),
+            &alice_signer,
+        )
+        .unwrap();
+    check(&alice_group);
+
+    alice_group.merge_pending_commit(alice_provider).unwrap();
+    check(&alice_group);
+
+    // Pending proposal that adds Charlie.
+    let charlie_kpb = KeyPackageBuilder::new()
+        .leaf_node_capabilities(capabilities)
+        .build(ciphersuite, charlie_provider, &charlie_signer, charlie_cwk)
+        .unwrap();
+    alice_group
+        .propose_add_member(alice_provider, &alice_signer, charlie_kpb.key_package())
+        .unwrap();
+    check(&alice_group);
+
+    alice_group
+}
@@ -15,6 +15,7 @@ use super::OpenMlsRustCrypto;
 use crate::{
     binary_tree::array_representation::LeafNodeIndex,
     ciphersuite::hash_ref::KeyPackageRef,
+    credentials::test_utils::AcceptAllCredentials,
     credentials::*,
     extensions::*,
     framing::*,
@@ -126,6 +127,7 @@ impl<Provider: OpenMlsProvider> Client<Provider> {
             &mls_group_config,
             welcome,
             ratchet_tree,
+            &AcceptAllCredentials,
         )?;
         let new_group = staged_join.into_group(&self.provider)?;
         self.groups
@@ -158,7 +160,7 @@ impl<Provider: OpenMlsProvider> Client<Provider> {
             }
             // Process the message.
             let processed_message = group_state
-                .process_message(&self.provider, message.clone())
+                .process_message(&self.provider, message.clone(), &AcceptAllCredentials)
                 .map_err(ClientError::ProcessMessageError)?;
 
             match processed_message.into_content() {
@@ -12,6 +12,7 @@ use crate::{
         LeafNodeIndex,
     },
     framing::*,
+    messages::proposals::MessageRange,
     schedule::*,
     tree::sender_ratchet::*,
 };
@@ -122,6 +123,10 @@ pub(crate) struct SecretTree {
     handshake_sender_ratchets: Vec<Option<SenderRatchet>>,
     application_sender_ratchets: Vec<Option<SenderRatchet>>,
     size: TreeSize,
+    // Generations of the application messages received from other members,
+    // sorted by sender and generation.
+    #[serde(default)]
+    received_application_messages: Vec<MessageRange>,
 }
 
 impl SecretTree {
@@ -148,6 +153,7 @@ impl SecretTree {
             handshake_sender_ratchets,
             application_sender_ratchets,
             size,
+            received_application_messages: Vec::new(),
         };
 
         // Set the encryption secret in the root node. We ignore the Result
@@ -175,6 +181,72 @@ impl SecretTree {
         }
     }
 
+    /// Get the generation of the own application SenderRatchet, i.e. the
+    /// number of application messages sent in this epoch.
+    pub(crate) fn own_application_generation(&self) -> u32 {
+        match self.ratchet_opt(self.own_index, SecretType::ApplicationSecret) {
+            Ok(Some(sender_ratchet)) => sender_ratchet.generation(),
+            _ => 0,
+        }
+    }
+
+    /// Get the ranges of application messages received from other members,
+    /// sorted by sender and generation.
+    pub(crate) fn received_application_messages(&self) -> &[MessageRange] {
+        &self.received_application_messages
+    }
+
+    /// Records that the application message with the given `generation` was
+    /// received from the member at leaf `index`. Adjacent generations are
+    /// merged into a single [`MessageRange`].
+    pub(crate) fn record_received_application_message(
+        &mut self,
+        index: LeafNodeIndex,
+        generation: u32,
+    ) {
+        let ranges = &mut self.received_application_messages;
+        // The first range that doesn't end before the generation.
+        let position = ranges.partition_point(|range| {
+            (range.sender(), range.last_generation()) < (index, generation)
+        });
+        let next = ranges
+            .get(position)
+            .filter(|range| range.sender() == index)
+            .copied();
+        if next.is_some_and(|next| next.first_generation() <= generation) {
+            // The message was recorded before.
+            return;
+        }
+        let previous = position
+            .checked_sub(1)
+            .and_then(|position| ranges.get(position))
+            .filter(|range| range.sender() == index)
+            .copied();
+
+        // Neither overflows, because the previous range ends before and the
+        // next range starts after the generation.
+        let extends_previous =
+            previous.filter(|previous| previous.last_generation() + 1 == generation);
+        let extends_next = next.filter(|next| next.first_generation() == generation + 1);
+        match (extends_previous, extends_next) {
+            (Some(previous), Some(next)) => {
+                ranges[position - 1] =
+                    MessageRange::new(index, previous.first_generation(), next.last_generation());
+                ranges.remove(position);
+            }
+            (Some(previous), None) => {
+                ranges[position - 1] =
+                    MessageRange::new(index, previous.first_generation(), generation);
+            }
+            (None, Some(next)) => {
+                ranges[position] = MessageRange::new(index, generation, next.last_generation());
+            }
+            (None, None) => {
+                ranges.insert(position, MessageRange::new(index, generation, generation));
+            }
+        }
+    }
+
     /// Initializes a specific SenderRatchet pair for a given index by
     /// calculating and deleting the appropriate values in the SecretTree
     fn initialize_sender_ratchets(
@@ -66,6 +66,7 @@ use serde::{self, Deserialize, Serialize};
 
 use crate::{
     binary_tree::array_representation::LeafNodeIndex,
+    credentials::test_utils::AcceptAllCredentials,
     credentials::{BasicCredential, CredentialWithKey},
     framing::{mls_auth_content::AuthenticatedContent, mls_content::FramedContentBody, *},
     group::*,
@@ -257,7 +258,11 @@ pub fn run_test_vector(
         ) {
             // check that the proposal in proposal_pub == proposal
             let processed_message = group
-                .process_message(provider, proposal_pub.into_protocol_message().unwrap())
+                .process_message(
+                    provider,
+                    proposal_pub.into_protocol_message().unwrap(),
+                    &AcceptAllCredentials,
+                )
                 .unwrap();
             match processed_message.content() {
                 ProcessedMessageContent::ProposalMessage(p) => {
@@ -471,7 +476,11 @@ pub fn run_test_vector(
         ) {
             // check that the proposal in proposal_pub == proposal
             let processed_message = group
-                .process_message(provider, application_priv.into_protocol_message().unwrap())
+                .process_message(
+                    provider,
+                    application_priv.into_protocol_message().unwrap(),
+                    &AcceptAllCredentials,
+                )
                 .unwrap();
             match processed_message.into_content() {
                 ProcessedMessageContent::ApplicationMessage(a) => {
@@ -2,6 +2,7 @@ use openmls_traits::random::OpenMlsRand;
 
 use crate::{
     binary_tree::{array_representation::TreeSize, LeafNodeIndex},
+    messages::proposals::MessageRange,
     schedule::EncryptionSecret,
     test_utils::*,
     tree::{secret_tree::*, sender_ratchet::SenderRatchetConfiguration},
@@ -256,3 +257,37 @@ fn secret_tree() {
         application_secret_nonce.as_slice()
     );
 }
+
+// This tests that received application messages are merged into ranges
+#[openmls_test::openmls_test]
+fn received_application_messages() {
+    let encryption_secret = EncryptionSecret::random(ciphersuite, provider.rand());
+    let mut secret_tree = SecretTree::new(
+        encryption_secret,
+        TreeSize::from_leaf_count(3u32),
+        LeafNodeIndex::new(2u32),
+    );
+    let alice = LeafNodeIndex::new(0u32);
+    let bob = LeafNodeIndex::new(1u32);
+
+    for (sender, generation) in [(bob, 3), (alice, 5), (alice, 1), (alice, 3), (alice, 1)] {
+        secret_tree.record_received_application_message(sender, generation);
+    }
+    assert_eq!(
+        secret_tree.received_application_messages(),
+        &[
+            MessageRange::new(alice, 1, 1),
+            MessageRange::new(alice, 3, 3),
+            MessageRange::new(alice, 5, 5),
+            MessageRange::new(bob, 3, 3),
+        ]
+    );
+
+    for (sender, generation) in [(alice, 2), (alice, 4), (alice, 0), (bob, 2), (bob, 4)] {
+        secret_tree.record_received_application_message(sender, generation);
+    }
+    assert_eq!(
+        secret_tree.received_application_messages(),
+        &[MessageRange::new(alice, 0, 5), MessageRange::new(bob, 2, 4)]
+    );
+}
@@ -47,7 +47,9 @@ use crate::binary_tree::array_representation::ParentNodeIndex;
 use crate::{binary_tree::array_representation::level, test_utils::bytes_to_hex};
 use crate::{
     binary_tree::{
-        array_representation::{is_node_in_tree, tree::TreeNode, LeafNodeIndex, TreeSize},
+        array_representation::{
+            is_node_in_tree, tree::TreeNode, LeafNodeIndex, TreeNodeIndex, TreeSize, MAX_TREE_SIZE,
+        },
         MlsBinaryTree, MlsBinaryTreeError,
     },
     ciphersuite::{signable::Verifiable, Secret},
@@ -359,6 +361,28 @@ impl fmt::Display for RatchetTree {
     }
 }
 
+/// The value OpenMLS writes to the storage with `write_tree`.
+///
+/// The nodes of the tree are stored individually with `write_tree_node`, so
+/// that merging a commit only writes the nodes it changed. Groups written by
+/// earlier releases have the [`TreeSync`] stored as a whole, which is read as
+/// [`StoredTreeSync::Complete`] and replaced with the individual nodes when the
+/// group is stored the next time or migrated with
+/// [`migrate_group`](crate::storage::migration::migrate_group).
+///
+/// This doesn't increment the storage version, because the values of earlier
+/// releases can still be read. Groups stored node by node can't be read by
+/// earlier releases, though.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+#[allow(clippy::large_enum_variant)]
+pub(crate) enum StoredTreeSync {
+    /// The size and tree hash of a tree whose nodes are stored individually.
+    Nodes { tree_size: u32, tree_hash: Vec<u8> },
+    /// A complete tree.
+    Complete(TreeSync),
+}
+
 /// The [`TreeSync`] struct holds an `MlsBinaryTree` instance, which contains
 /// the state that is synced across the group, as well as the [`LeafNodeIndex`]
 /// pointing to the leaf of this group member and the current hash of the tree.
@@ -430,10 +454,96 @@ impl TreeSync {
 
     /// Merge the given diff into this `TreeSync` instance, refreshing the
     /// `tree_hash` value in the process.
-    pub(crate) fn merge_diff(&mut self, tree_sync_diff: StagedTreeSyncDiff) {
+    ///
+    /// Returns the indices of the nodes that were changed by the diff or
+    /// removed from the tree, in the array representation of the tree.
+    pub(crate) fn merge_diff(&mut self, tree_sync_diff: StagedTreeSyncDiff) -> Vec<u32> {
         let (diff, new_tree_hash) = tree_sync_diff.into_parts();
         self.tree_hash = new_tree_hash;
-        self.tree.merge_diff(diff);
+        self.tree
+            .merge_diff(diff)
+            .iter()
+            .map(TreeNodeIndex::u32)
+            .collect()
+    }
+
+    /// Returns the node with the given index in the array representation of
+    /// the tree, or `None` if the node is blank or not in the tree.
+    pub(crate) fn node(&self, node_index: u32) -> Option<Node> {
+        if node_index >= self.tree_size().u32() {
+            return None;
+        }
+        match TreeNodeIndex::new(node_index) {
+            TreeNodeIndex::Leaf(leaf_index) => self
+                .tree
+                .leaf(leaf_index)
+                .node()
+                .clone()
+                .map(Node::LeafNode),
+            TreeNodeIndex::Parent(parent_index) => self
+                .tree
+                .parent_by_index(parent_index)
+                .node()
+                .clone()
+                .map(Node::ParentNode),
+        }
+    }
+
+    /// Returns an iterator over the non-blank nodes of the tree and their
+    /// indices in the array representation of the tree.
+    pub(crate) fn full_nodes(&self) -> impl Iterator<Item = (u32, Node)> + '_ {
+        let leaves = self.tree.leaves().filter_map(|(leaf_index, leaf)| {
+            let node_index = TreeNodeIndex::from(leaf_index).u32();
+            leaf.node()
+                .clone()
+                .map(|leaf| (node_index, Node::LeafNode(leaf)))
+        });
+        let parents = self.tree.parents().filter_map(|(parent_index, parent)| {
+            let node_index = TreeNodeIndex::from(parent_index).u32();
+            parent
+                .node()
+                .clone()
+                .map(|parent| (node_index, Node::ParentNode(parent)))
+        });
+        leaves.chain(parents)
+    }
+
+    /// Rebuilds a tree of the given size from its non-blank `nodes`, as
+    /// written to the storage with their indices in the array representation
+    /// of the tree. The nodes are not verified, since they were verified
+    /// before they were stored.
+    ///
+    /// Returns `None` if the nodes don't form a tree of the given size.
+    pub(crate) fn from_stored_nodes(
+        tree_size: u32,
+        tree_hash: Vec<u8>,
+        nodes: Vec<(u32, Node)>,
+    ) -> Option<Self> {
+        if tree_size > MAX_TREE_SIZE {
+            return None;
+        }
+        let mut ts_nodes: Vec<TreeNode<TreeSyncLeafNode, TreeSyncParentNode>> = (0..tree_size)
+            .map(|node_index| {
+                if node_index % 2 == 0 {
+                    TreeNode::Leaf(TreeSyncLeafNode::blank())
+                } else {
+                    TreeNode::Parent(TreeSyncParentNode::blank())
+                }
+            })
+            .collect();
+        for (node_index, node) in nodes {
+            let ts_node = TreeSyncNode::from(node).into();
+            match (ts_nodes.get_mut(node_index as usize), ts_node) {
+                (Some(slot @ TreeNode::Leaf(_)), ts_node @ TreeNode::Leaf(_))
+                | (Some(slot @ TreeNode::Parent(_)), ts_node @ TreeNode::Parent(_)) => {
+                    *slot = ts_node
+                }
+                _ => return None,
+            }
+        }
+        let tree = MlsBinaryTree::new(ts_nodes).ok()?;
+
+        Some(Self { tree, tree_hash })
     }
 
     /// Create an empty diff based on this [`TreeSync`] instance all operations
@@ -550,6 +660,13 @@ impl TreeSync {
             .filter_map(|(_, tsn)| tsn.node().as_ref())
     }
 
+    /// Returns an iterator over the full leaves and their indices.
+    pub(crate) fn full_leaves_indexed(&self) -> impl Iterator<Item = (LeafNodeIndex, &LeafNode)> {
+        self.tree
+            .leaves()
+            .filter_map(|(index, tsn)| tsn.node().as_ref().map(|node| (index, node)))
+    }
+
     /// Returns the index of the last full leaf in the tree.
     fn rightmost_full_leaf(&self) -> LeafNodeIndex {
         let mut index = LeafNodeIndex::new(0);
@@ -295,6 +295,14 @@ pub(super) fn default_ciphersuites() -> Vec<Ciphersuite> {
         Ciphersuite::MLS_128_DHKEMP256_AES128GCM_SHA256_P256,
         Ciphersuite::MLS_128_DHKEMX25519_CHACHA20POLY1305_SHA256_Ed25519,
         Ciphersuite::MLS_256_XWING_CHACHA20POLY1305_SHA256_Ed25519,
+        Ciphersuite::MLS_256_DHKEMP384_AES256GCM_SHA384_P384,
+        Ciphersuite::MLS_256_DHKEMP521_AES256GCM_SHA512_P521,
+        Ciphersuite::MLS_256_DHKEMX448_AES256GCM_SHA512_Ed448,
+        Ciphersuite::MLS_256_DHKEMX448_CHACHA20POLY1305_SHA512_Ed448,
+        Ciphersuite::MLS_128_MLKEM768_AES128GCM_SHA256_Ed25519,
+        Ciphersuite::MLS_256_MLKEM1024_AES256GCM_SHA512_Ed25519,
+        Ciphersuite::MLS_128_MLKEM768_AES128GCM_SHA256_MLDSA65,
+        Ciphersuite::MLS_256_MLKEM1024_AES256GCM_SHA512_MLDSA87,
     ]
 }
 
@@ -1,4 +1,5 @@
 use crate::{
+    credentials::test_utils::AcceptAllCredentials,
     group::{
         tests_and_kats::utils::{generate_credential_with_key, CredentialWithKeyAndSigner},
         MlsGroup, MlsGroupCreateConfig,
@@ -111,6 +112,7 @@ fn that_commit_secret_is_derived_from_end_of_update_path_not_root() {
             mls_group_create_config.join_config(),
             welcome,
             None,
+            &AcceptAllCredentials,
         )
         .expect("Staging the join failed.")
         .into_group(&charlie.provider)
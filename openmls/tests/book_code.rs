@@ -1,4 +1,5 @@
 use openmls::{
+    credentials::test_utils::AcceptAllCredentials,
     prelude::{tls_codec::*, CustomProposal, *},
     test_utils::*,
     *,
@@ -275,8 +276,14 @@ fn book_operations() {
         .expect("expected the message to be a welcome message");
 
     // ANCHOR: bob_joins_with_welcome
-    let staged_join = StagedWelcome::new_from_welcome(provider, &mls_group_config, welcome, None)
-        .expect("Error constructing staged join");
+    let staged_join = StagedWelcome::new_from_welcome(
+        provider,
+        &mls_group_config,
+        welcome,
+        None,
+        &AcceptAllCredentials,
+    )
+    .expect("Error constructing staged join");
     let mut bob_group = staged_join
         .into_group(provider)
         .expect("Error joining group from StagedWelcome");
@@ -301,6 +308,7 @@ fn book_operations() {
         None, // No special extensions
         &[],
         dave_credential,
+        &AcceptAllCredentials,
     )
     .expect("Error joining from external commit");
     dave_group
@@ -341,7 +349,7 @@ fn book_operations() {
         .try_into_protocol_message()
         .expect("Expected a PublicMessage or a PrivateMessage");
     let processed_message = bob_group
-        .process_message(provider, protocol_message)
+        .process_message(provider, protocol_message, &AcceptAllCredentials)
         .expect("Could not process message.");
     // ANCHOR_END: process_message
 
@@ -382,7 +390,7 @@ fn book_operations() {
 
     // ANCHOR: inspect_aad
     let processed_message = bob_group
-        .process_message(provider, protocol_message)
+        .process_message(provider, protocol_message, &AcceptAllCredentials)
         .expect("Could not process message.");
 
     assert_eq!(processed_message.aad(), b"Additional Authenticated Data");
@@ -401,6 +409,7 @@ fn book_operations() {
             mls_message_out
                 .into_protocol_message()
                 .expect("Unexpected message type"),
+            &AcceptAllCredentials,
         )
         .expect("Could not process message.");
 
@@ -452,6 +461,7 @@ fn book_operations() {
             mls_message_out
                 .into_protocol_message()
                 .expect("Unexpected message type"),
+            &AcceptAllCredentials,
         )
         .expect("Could not process message.");
 
@@ -500,6 +510,7 @@ fn book_operations() {
             mls_message_out
                 .into_protocol_message()
                 .expect("Unexpected message type"),
+            &AcceptAllCredentials,
         )
         .expect("Could not process message.");
 
@@ -558,6 +569,7 @@ fn book_operations() {
             queued_message
                 .into_protocol_message()
                 .expect("Unexpected message type"),
+            &AcceptAllCredentials,
         )
         .expect("Could not process message.");
     bob_group
@@ -585,6 +597,7 @@ fn book_operations() {
         mls_group_create_config.join_config(),
         welcome,
         Some(bob_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .expect("Error building StagedWelcome")
     .into_group(provider)
@@ -623,6 +636,7 @@ fn book_operations() {
                 .clone()
                 .into_protocol_message()
                 .expect("Unexpected message type"),
+            &AcceptAllCredentials,
         )
         .expect("Could not process message.");
     let _bob_processed_message = bob_group
@@ -631,6 +645,7 @@ fn book_operations() {
             queued_message
                 .into_protocol_message()
                 .expect("Unexpected message type"),
+            &AcceptAllCredentials,
         )
         .expect("Could not process message.");
 
@@ -650,6 +665,7 @@ fn book_operations() {
                 .clone()
                 .into_protocol_message()
                 .expect("Unexpected message type"),
+            &AcceptAllCredentials,
         )
         .expect("Could not process message.");
     let bob_processed_message = bob_group
@@ -658,6 +674,7 @@ fn book_operations() {
             queued_message
                 .into_protocol_message()
                 .expect("Unexpected message type"),
+            &AcceptAllCredentials,
         )
         .expect("Could not process message.");
     charlie_group
@@ -750,6 +767,7 @@ fn book_operations() {
                 .clone()
                 .into_protocol_message()
                 .expect("Unexpected message type"),
+            &AcceptAllCredentials,
         )
         .expect("Could not process message.");
 
@@ -773,6 +791,7 @@ fn book_operations() {
             mls_message_out
                 .into_protocol_message()
                 .expect("Unexpected message type"),
+            &AcceptAllCredentials,
         )
         .expect("Could not process message.");
     let charlies_leaf_index = charlie_group.own_leaf_index();
@@ -907,6 +926,7 @@ fn book_operations() {
             mls_message_out
                 .into_protocol_message()
                 .expect("Unexpected message type"),
+            &AcceptAllCredentials,
         )
         .expect("Could not process message.");
 
@@ -965,6 +985,7 @@ fn book_operations() {
             mls_message_out
                 .into_protocol_message()
                 .expect("Unexpected message type"),
+            &AcceptAllCredentials,
         )
         .expect("Could not process message.");
 
@@ -1010,6 +1031,7 @@ fn book_operations() {
             queued_message
                 .into_protocol_message()
                 .expect("Unexpected message type"),
+            &AcceptAllCredentials,
         )
         .expect("Could not process message.");
 
@@ -1051,6 +1073,7 @@ fn book_operations() {
         mls_group_create_config.join_config(),
         welcome,
         Some(alice_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .expect("Error creating StagedWelcome")
     .into_group(provider)
@@ -1088,6 +1111,7 @@ fn book_operations() {
             queued_message
                 .into_protocol_message()
                 .expect("Unexpected message type"),
+            &AcceptAllCredentials,
         )
         .expect("Could not process message.");
 
@@ -1138,6 +1162,7 @@ fn book_operations() {
             queued_message
                 .into_protocol_message()
                 .expect("Unexpected message type"),
+            &AcceptAllCredentials,
         )
         .expect("Could not process message.");
 
@@ -1199,6 +1224,7 @@ fn book_operations() {
             queued_message
                 .into_protocol_message()
                 .expect("Unexpected message type"),
+            &AcceptAllCredentials,
         )
         .expect("Could not process message.");
 
@@ -1269,6 +1295,7 @@ fn book_operations() {
             proposal
                 .into_protocol_message()
                 .expect("Unexpected message type."),
+            &AcceptAllCredentials,
         )
         .expect("Could not process message.");
     match alice_processed_message.into_content() {
@@ -1295,6 +1322,7 @@ fn book_operations() {
                 mls_group_create_config.join_config(),
                 welcome,
                 None,
+                &AcceptAllCredentials,
             )
             .expect("Bob could not stage the the group join")
             .into_group(provider)
@@ -1336,6 +1364,7 @@ fn book_operations() {
             proposal
                 .into_protocol_message()
                 .expect("Unexpected message type."),
+            &AcceptAllCredentials,
         )
         .expect("Could not process message.");
     match alice_processed_message.into_content() {
@@ -1391,6 +1420,7 @@ fn book_operations() {
         mls_group_create_config.join_config(),
         welcome,
         Some(alice_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .expect("Could not create StagedWelcome from Welcome");
 
@@ -1516,6 +1546,7 @@ fn custom_proposal_usage(
         &MlsGroupJoinConfig::default(),
         welcome.into_welcome().unwrap(),
         Some(alice_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .unwrap();
 
@@ -1537,6 +1568,7 @@ fn custom_proposal_usage(
         .process_message(
             provider,
             custom_proposal_message.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
         )
         .unwrap();
 
@@ -1555,7 +1587,11 @@ fn custom_proposal_usage(
         .unwrap();
 
     let processed_message = bob_group
-        .process_message(provider, commit.into_protocol_message().unwrap())
+        .process_message(
+            provider,
+            commit.into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
         .unwrap();
 
     let staged_commit = match processed_message.into_content() {
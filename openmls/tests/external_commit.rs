@@ -1,5 +1,5 @@
 use openmls::{
-    credentials::test_utils::new_credential,
+    credentials::test_utils::{new_credential, AcceptAllCredentials},
     messages::group_info::VerifiableGroupInfo,
     prelude::{tls_codec::*, *},
     treesync::LeafNodeParameters,
@@ -88,6 +88,7 @@ fn test_external_commit() {
             None,
             b"",
             bob_credential,
+            &AcceptAllCredentials,
         )
         .unwrap();
     }
@@ -107,6 +108,7 @@ fn test_external_commit() {
             None,
             b"",
             bob_credential,
+            &AcceptAllCredentials,
         )
         .unwrap_err();
 
@@ -151,6 +153,7 @@ fn test_group_info() {
         None,
         b"",
         bob_credential,
+        &AcceptAllCredentials,
     )
     .map(|(group, msg, group_info)| (group, MlsMessageIn::from(msg), group_info))
     .unwrap();
@@ -158,7 +161,11 @@ fn test_group_info() {
 
     // let alice process bob's new client
     let msg = alice_group
-        .process_message(provider, msg.try_into_protocol_message().unwrap())
+        .process_message(
+            provider,
+            msg.try_into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
         .unwrap()
         .into_content();
     match msg {
@@ -175,7 +182,11 @@ fn test_group_info() {
         .into();
 
     let msg = alice_group
-        .process_message(provider, message.try_into_protocol_message().unwrap())
+        .process_message(
+            provider,
+            message.try_into_protocol_message().unwrap(),
+            &AcceptAllCredentials,
+        )
         .unwrap();
     let decrypted = match msg.into_content() {
         ProcessedMessageContent::ApplicationMessage(msg) => msg.into_bytes(),
@@ -202,6 +213,7 @@ fn test_group_info() {
         None,
         b"",
         bob_credential,
+        &AcceptAllCredentials,
     )
     .unwrap();
     bob_group.merge_pending_commit(provider).unwrap();
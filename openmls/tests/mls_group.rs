@@ -1,5 +1,8 @@
 use openmls::{
-    prelude::{test_utils::new_credential, *},
+    prelude::{
+        test_utils::{new_credential, AcceptAllCredentials},
+        *,
+    },
     storage::OpenMlsProvider,
     treesync::LeafNodeParameters,
 };
@@ -135,6 +138,7 @@ fn mls_group_operations() {
             mls_group_create_config.join_config(),
             welcome,
             Some(alice_group.export_ratchet_tree().into()),
+            &AcceptAllCredentials,
         )
         .expect("Error creating StagedWelcome from Welcome")
         .into_group(bob_provider)
@@ -162,6 +166,7 @@ fn mls_group_operations() {
                     .clone()
                     .into_protocol_message()
                     .expect("Unexpected message type"),
+                &AcceptAllCredentials,
             )
             .expect("Could not process message.");
         let sender = processed_message.credential().clone();
@@ -195,6 +200,7 @@ fn mls_group_operations() {
                     .clone()
                     .into_protocol_message()
                     .expect("Unexpected message type"),
+                &AcceptAllCredentials,
             )
             .expect("Could not process message.");
 
@@ -243,6 +249,7 @@ fn mls_group_operations() {
                     .clone()
                     .into_protocol_message()
                     .expect("Unexpected message type"),
+                &AcceptAllCredentials,
             )
             .expect("Could not process message.");
 
@@ -288,6 +295,7 @@ fn mls_group_operations() {
                     .clone()
                     .into_protocol_message()
                     .expect("Unexpected message type"),
+                &AcceptAllCredentials,
             )
             .expect("Could not process message.");
 
@@ -340,6 +348,7 @@ fn mls_group_operations() {
                     .clone()
                     .into_protocol_message()
                     .expect("Unexpected message type"),
+                &AcceptAllCredentials,
             )
             .expect("Could not process message.");
         bob_group
@@ -367,6 +376,7 @@ fn mls_group_operations() {
             mls_group_create_config.join_config(),
             welcome,
             Some(bob_group.export_ratchet_tree().into()),
+            &AcceptAllCredentials,
         )
         .expect("Error creating staged join from Welcome")
         .into_group(charlie_provider)
@@ -404,6 +414,7 @@ fn mls_group_operations() {
                     .clone()
                     .into_protocol_message()
                     .expect("Unexpected message type"),
+                &AcceptAllCredentials,
             )
             .expect("Could not process message.");
         let _bob_processed_message = bob_group
@@ -413,6 +424,7 @@ fn mls_group_operations() {
                     .clone()
                     .into_protocol_message()
                     .expect("Unexpected message type"),
+                &AcceptAllCredentials,
             )
             .expect("Could not process message.");
 
@@ -432,6 +444,7 @@ fn mls_group_operations() {
                     .clone()
                     .into_protocol_message()
                     .expect("Unexpected message type"),
+                &AcceptAllCredentials,
             )
             .expect("Could not process message.");
         let bob_processed_message = bob_group
@@ -441,6 +454,7 @@ fn mls_group_operations() {
                     .clone()
                     .into_protocol_message()
                     .expect("Unexpected message type"),
+                &AcceptAllCredentials,
             )
             .expect("Could not process message.");
         charlie_group
@@ -518,6 +532,7 @@ fn mls_group_operations() {
                     .clone()
                     .into_protocol_message()
                     .expect("Unexpected message type"),
+                &AcceptAllCredentials,
             )
             .expect("Could not process message.");
         let bob_processed_message = bob_group
@@ -527,6 +542,7 @@ fn mls_group_operations() {
                     .clone()
                     .into_protocol_message()
                     .expect("Unexpected message type"),
+                &AcceptAllCredentials,
             )
             .expect("Could not process message.");
         charlie_group
@@ -633,6 +649,7 @@ fn mls_group_operations() {
                     .clone()
                     .into_protocol_message()
                     .expect("Unexpected message type"),
+                &AcceptAllCredentials,
             )
             .expect("Could not process message.");
 
@@ -672,6 +689,7 @@ fn mls_group_operations() {
                     .clone()
                     .into_protocol_message()
                     .expect("Unexpected message type"),
+                &AcceptAllCredentials,
             )
             .expect("Could not process message.");
 
@@ -714,6 +732,7 @@ fn mls_group_operations() {
                     .clone()
                     .into_protocol_message()
                     .expect("Unexpected message type"),
+                &AcceptAllCredentials,
             )
             .expect("Could not process message.");
 
@@ -754,6 +773,7 @@ fn mls_group_operations() {
             mls_group_create_config.join_config(),
             welcome,
             Some(alice_group.export_ratchet_tree().into()),
+            &AcceptAllCredentials,
         )
         .expect("Error creating staged join from Welcome")
         .into_group(bob_provider)
@@ -792,6 +812,7 @@ fn mls_group_operations() {
                     .clone()
                     .into_protocol_message()
                     .expect("Unexpected message type"),
+                &AcceptAllCredentials,
             )
             .expect("Could not process message.");
         let sender = bob_processed_message.credential().clone();
@@ -824,6 +845,7 @@ fn mls_group_operations() {
                     .clone()
                     .into_protocol_message()
                     .expect("Unexpected message type"),
+                &AcceptAllCredentials,
             )
             .expect("Could not process message.");
 
@@ -882,6 +904,7 @@ fn mls_group_operations() {
                     .clone()
                     .into_protocol_message()
                     .expect("Unexpected message type"),
+                &AcceptAllCredentials,
             )
             .expect("Could not process message.");
 
@@ -953,6 +976,7 @@ fn mls_group_operations() {
             mls_group_create_config.join_config(),
             welcome,
             Some(alice_group.export_ratchet_tree().into()),
+            &AcceptAllCredentials,
         )
         .expect("Could not create staged join from Welcome")
         .into_group(bob_provider)
@@ -1183,6 +1207,7 @@ fn mls_group_ratchet_tree_extension(
             mls_group_create_config.join_config(),
             welcome,
             None,
+            &AcceptAllCredentials,
         )
         .expect("Error creating staged join from Welcome")
         .into_group(provider)
@@ -1234,6 +1259,7 @@ fn mls_group_ratchet_tree_extension(
             mls_group_create_config.join_config(),
             welcome,
             None,
+            &AcceptAllCredentials,
         )
         .expect_err("Could join a group without a ratchet tree");
 
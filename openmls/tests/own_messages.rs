@@ -1,4 +1,5 @@
 //! Test that decryptoin of own messages fails.
+use openmls::credentials::test_utils::AcceptAllCredentials;
 use openmls::prelude::*;
 use openmls_test::openmls_test;
 use test_utils::new_credential;
@@ -94,6 +95,7 @@ fn own_messages_attempted_decryption() {
         mls_group_create_config.join_config(),
         welcome,
         Some(alice_group.export_ratchet_tree().into()),
+        &AcceptAllCredentials,
     )
     .expect("Error creating StagedWelcome from Welcome")
     .into_group(bob_provider)
@@ -112,6 +114,7 @@ fn own_messages_attempted_decryption() {
                 .clone()
                 .into_protocol_message()
                 .expect("Unexpected message type"),
+            &AcceptAllCredentials,
         )
         .expect("Could not process message.");
     let sender = processed_message.credential().clone();
@@ -140,6 +143,7 @@ fn own_messages_attempted_decryption() {
             queued_message
                 .into_protocol_message()
                 .expect("Unexpected message type"),
+            &AcceptAllCredentials,
         )
         .expect_err("Expected error.");
 
@@ -0,0 +1,219 @@
+//! The signature scheme Ed448 ([RFC 8032], Section 5.2) on top of the curve
+//! arithmetic of `ed448-goldilocks-plus`, which has no signing API of its own.
+//!
+//! The private key of a key pair is the 57-byte secret of RFC 8032. Signatures
+//! use an empty context string.
+//!
+//! Signing only handles secrets with constant-time operations of
+//! `ed448-goldilocks-plus`: scalar multiplications use a fixed window whose
+//! table lookups are `subtle` conditional selects, and scalar arithmetic works
+//! on a fixed number of limbs. The secret scalar, the nonce and the expanded
+//! private key are zeroized when signing returns.
+//!
+//! [RFC 8032]: https://www.rfc-editor.org/rfc/rfc8032.html#section-5.2
+
+use ed448_goldilocks_plus::{
+    CompressedEdwardsY, EdwardsPoint, Scalar, ScalarBytes, WideScalarBytes,
+};
+use openmls_traits::types::CryptoError;
+use sha3::{
+    digest::{ExtendableOutput, Update, XofReader},
+    Shake256,
+};
+use zeroize::{Zeroize, Zeroizing};
+
+/// The length of a private or public key.
+pub(crate) const ED448_KEY_LENGTH: usize = 57;
+
+/// The length of a signature.
+const ED448_SIGNATURE_LENGTH: usize = 2 * ED448_KEY_LENGTH;
+
+/// `dom4(0, "")`, the prefix of all hashes for pure Ed448 without context.
+const DOM4: &[u8] = b"SigEd448\x00\x00";
+
+/// `SHAKE256(dom4 || parts, 114)`, reduced modulo the group order.
+fn hash_to_scalar(parts: &[&[u8]]) -> Scalar {
+    let mut hasher = Shake256::default();
+    hasher.update(DOM4);
+    for part in parts {
+        hasher.update(part);
+    }
+    let mut digest = WideScalarBytes::default();
+    hasher.finalize_xof().read(&mut digest);
+    let scalar = Scalar::from_bytes_mod_order_wide(&digest);
+    digest.as_mut_slice().zeroize();
+    scalar
+}
+
+/// Expands the private `key` into the secret scalar and the prefix for the
+/// nonces.
+fn expand(
+    key: &[u8],
+) -> Result<(Zeroizing<Scalar>, Zeroizing<[u8; ED448_KEY_LENGTH]>), CryptoError> {
+    if key.len() != ED448_KEY_LENGTH {
+        return Err(CryptoError::InvalidLength);
+    }
+    let mut h = Zeroizing::new([0u8; 2 * ED448_KEY_LENGTH]);
+    let mut hasher = Shake256::default();
+    hasher.update(key);
+    hasher.finalize_xof().read(h.as_mut_slice());
+
+    // Prune the first half to get the secret scalar.
+    let mut s = WideScalarBytes::default();
+    s[..ED448_KEY_LENGTH].copy_from_slice(&h[..ED448_KEY_LENGTH]);
+    s[0] &= 0xfc;
+    s[55] |= 0x80;
+    s[56] = 0;
+    let scalar = Zeroizing::new(Scalar::from_bytes_mod_order_wide(&s));
+    s.as_mut_slice().zeroize();
+
+    let mut prefix = Zeroizing::new([0u8; ED448_KEY_LENGTH]);
+    prefix.copy_from_slice(&h[ED448_KEY_LENGTH..]);
+    Ok((scalar, prefix))
+}
+
+/// Returns the public key for the private `key`.
+pub(crate) fn ed448_public_key(key: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    let (s, _) = expand(key)?;
+    Ok((EdwardsPoint::GENERATOR * *s)
+        .compress()
+        .to_bytes()
+        .to_vec())
+}
+
+pub(crate) fn ed448_sign(key: &[u8], data: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    let (s, prefix) = expand(key)?;
+    let public_key = (EdwardsPoint::GENERATOR * *s).compress();
+
+    let r = Zeroizing::new(hash_to_scalar(&[prefix.as_slice(), data]));
+    let big_r = (EdwardsPoint::GENERATOR * *r).compress();
+    let k = hash_to_scalar(&[big_r.as_bytes(), public_key.as_bytes(), data]);
+
+    let mut signature = big_r.to_bytes().to_vec();
+    signature.extend_from_slice(&(*r + k * *s).to_bytes_rfc_8032());
+    Ok(signature)
+}
+
+pub(crate) fn ed448_verify(data: &[u8], pk: &[u8], signature: &[u8]) -> Result<(), CryptoError> {
+    let pk = <[u8; ED448_KEY_LENGTH]>::try_from(pk).map_err(|_| CryptoError::CryptoLibraryError)?;
+    let a = Option::<EdwardsPoint>::from(CompressedEdwardsY(pk).decompress())
+        .ok_or(CryptoError::CryptoLibraryError)?;
+    if signature.len() != ED448_SIGNATURE_LENGTH {
+        return Err(CryptoError::InvalidSignature);
+    }
+    let (big_r_bytes, s_bytes) = signature.split_at(ED448_KEY_LENGTH);
+    let big_r_bytes = <[u8; ED448_KEY_LENGTH]>::try_from(big_r_bytes)
+        .map_err(|_| CryptoError::InvalidSignature)?;
+    let big_r = Option::<EdwardsPoint>::from(CompressedEdwardsY(big_r_bytes).decompress())
+        .ok_or(CryptoError::InvalidSignature)?;
+    // `Scalar::from_canonical_bytes` does not check the last byte on its own,
+    // which RFC 8032 requires to be zero.
+    if s_bytes[ED448_KEY_LENGTH - 1] != 0 {
+        return Err(CryptoError::InvalidSignature);
+    }
+    let mut s = ScalarBytes::default();
+    s.copy_from_slice(s_bytes);
+    let s = Option::<Scalar>::from(Scalar::from_canonical_bytes(&s))
+        .ok_or(CryptoError::InvalidSignature)?;
+
+    let k = hash_to_scalar(&[&big_r_bytes, &pk, data]);
+    if EdwardsPoint::GENERATOR * s == big_r + a * k {
+        Ok(())
+    } else {
+        Err(CryptoError::InvalidSignature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestVector {
+        secret_key: &'static str,
+        public_key: &'static str,
+        message: &'static str,
+        signature: &'static str,
+    }
+
+    /// The test vectors of [RFC 8032, Section 7.4] without a context.
+    ///
+    /// [RFC 8032, Section 7.4]: https://www.rfc-editor.org/rfc/rfc8032.html#section-7.4
+    const TEST_VECTORS: &[TestVector] = &[
+        // -----Blank
+        TestVector {
+            secret_key: "6c82a562cb808d10d632be89c8513ebf6c929f34ddfa8c9f63c9960ef6e348a3528c8a3fcc2f044e39a3fc5b94492f8f032e7549a20098f95b",
+            public_key: "5fd7449b59b461fd2ce787ec616ad46a1da1342485a70e1f8a0ea75d80e96778edf124769b46c7061bd6783df1e50f6cd1fa1abeafe8256180",
+            message: "",
+            signature: "533a37f6bbe457251f023c0d88f976ae2dfb504a843e34d2074fd823d41a591f2b233f034f628281f2fd7a22ddd47d7828c59bd0a21bfd3980ff0d2028d4b18a9df63e006c5d1c2d345b925d8dc00b4104852db99ac5c7cdda8530a113a0f4dbb61149f05a7363268c71d95808ff2e652600",
+        },
+        // -----1 octet
+        TestVector {
+            secret_key: "c4eab05d357007c632f3dbb48489924d552b08fe0c353a0d4a1f00acda2c463afbea67c5e8d2877c5e3bc397a659949ef8021e954e0a12274e",
+            public_key: "43ba28f430cdff456ae531545f7ecd0ac834a55d9358c0372bfa0c6c6798c0866aea01eb00742802b8438ea4cb82169c235160627b4c3a9480",
+            message: "03",
+            signature: "26b8f91727bd62897af15e41eb43c377efb9c610d48f2335cb0bd0087810f4352541b143c4b981b7e18f62de8ccdf633fc1bf037ab7cd779805e0dbcc0aae1cbcee1afb2e027df36bc04dcecbf154336c19f0af7e0a6472905e799f1953d2a0ff3348ab21aa4adafd1d234441cf807c03a00",
+        },
+        // -----11 octets
+        TestVector {
+            secret_key: "cd23d24f714274e744343237b93290f511f6425f98e64459ff203e8985083ffdf60500553abc0e05cd02184bdb89c4ccd67e187951267eb328",
+            public_key: "dcea9e78f35a1bf3499a831b10b86c90aac01cd84b67a0109b55a36e9328b1e365fce161d71ce7131a543ea4cb5f7e9f1d8b00696447001400",
+            message: "0c3e544074ec63b0265e0c",
+            signature: "1f0a8888ce25e8d458a21130879b840a9089d999aaba039eaf3e3afa090a09d389dba82c4ff2ae8ac5cdfb7c55e94d5d961a29fe0109941e00b8dbdeea6d3b051068df7254c0cdc129cbe62db2dc957dbb47b51fd3f213fb8698f064774250a5028961c9bf8ffd973fe5d5c206492b140e00",
+        },
+    ];
+
+    fn decode(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn rfc_8032_test_vectors() {
+        for test_vector in TEST_VECTORS {
+            let secret_key = decode(test_vector.secret_key);
+            let public_key = decode(test_vector.public_key);
+            let message = decode(test_vector.message);
+            let signature = decode(test_vector.signature);
+
+            assert_eq!(ed448_public_key(&secret_key).unwrap(), public_key);
+            assert_eq!(ed448_sign(&secret_key, &message).unwrap(), signature);
+            ed448_verify(&message, &public_key, &signature).unwrap();
+        }
+    }
+
+    #[test]
+    fn reject_invalid_signatures() {
+        let test_vector = &TEST_VECTORS[1];
+        let public_key = decode(test_vector.public_key);
+        let message = decode(test_vector.message);
+        let signature = decode(test_vector.signature);
+
+        assert_eq!(
+            ed448_verify(b"other message", &public_key, &signature),
+            Err(CryptoError::InvalidSignature)
+        );
+
+        // RFC 8032 requires the last byte of S to be zero.
+        let mut nonzero_last_byte = signature.clone();
+        nonzero_last_byte[ED448_SIGNATURE_LENGTH - 1] ^= 0xff;
+        assert_eq!(
+            ed448_verify(&message, &public_key, &nonzero_last_byte),
+            Err(CryptoError::InvalidSignature)
+        );
+
+        // S must be smaller than the group order.
+        let mut non_canonical_s = signature.clone();
+        non_canonical_s[ED448_SIGNATURE_LENGTH - 2] = 0xff;
+        assert_eq!(
+            ed448_verify(&message, &public_key, &non_canonical_s),
+            Err(CryptoError::InvalidSignature)
+        );
+
+        assert_eq!(
+            ed448_verify(&message, &public_key, &signature[1..]),
+            Err(CryptoError::InvalidSignature)
+        );
+    }
+}
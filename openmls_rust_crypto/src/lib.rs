@@ -6,7 +6,13 @@
 pub use openmls_memory_storage::{MemoryStorage, MemoryStorageError};
 use openmls_traits::OpenMlsProvider;
 
+mod ed448;
+#[cfg(feature = "post-quantum")]
+mod pq_kem;
+#[cfg(feature = "post-quantum")]
+mod pq_sig;
 mod provider;
+mod rfc9180;
 pub use provider::*;
 
 #[derive(Default, Debug)]
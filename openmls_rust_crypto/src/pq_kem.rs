@@ -0,0 +1,186 @@
+//! The post-quantum KEMs ML-KEM ([FIPS 203]) and X-Wing ([draft 02]) for HPKE.
+//!
+//! The private key of an ML-KEM key pair is the 64-byte seed `d || z` of the
+//! key generation. `DeriveKeyPair` expands the input keying material to that
+//! seed with SHAKE256.
+//!
+//! X-Wing combines ML-KEM-768 with X25519. Its private key is the expanded
+//! ML-KEM-768 decapsulation key, followed by the X25519 private and public
+//! key.
+//!
+//! [FIPS 203]: https://csrc.nist.gov/pubs/fips/203/final
+//! [draft 02]: https://datatracker.ietf.org/doc/draft-connolly-cfrg-xwing-kem/02/
+
+use kem::{Decapsulate, Encapsulate};
+use ml_kem::{Ciphertext, EncodedSizeUser, KemCore, MlKem768, B32};
+use openmls_traits::{random::OpenMlsRand, types::CryptoError};
+use rand::SeedableRng;
+use sha3::{
+    digest::{ExtendableOutput, Update, XofReader},
+    Digest, Sha3_256, Shake256,
+};
+use x25519_dalek::{PublicKey as X25519PublicKey, StaticSecret};
+
+use crate::RustCrypto;
+
+/// The length of the seed of an ML-KEM key pair.
+const ML_KEM_SEED_LENGTH: usize = 64;
+
+/// The length of an expanded ML-KEM-768 decapsulation key.
+const ML_KEM_768_PRIVATE_KEY_LENGTH: usize = 2400;
+
+/// The length of an ML-KEM-768 encapsulation key.
+const ML_KEM_768_PUBLIC_KEY_LENGTH: usize = 1184;
+
+/// The length of an ML-KEM-768 ciphertext.
+const ML_KEM_768_CIPHERTEXT_LENGTH: usize = 1088;
+
+/// The length of X25519 keys and shared secrets.
+const X25519_LENGTH: usize = 32;
+
+/// The label of the X-Wing combiner, `\.//^\`.
+const X_WING_LABEL: &[u8] = b"\\.//^\\";
+
+/// Returns `length` bytes of SHAKE256 output for the `input`.
+fn shake256(input: &[u8], length: usize) -> Vec<u8> {
+    let mut hasher = Shake256::default();
+    hasher.update(input);
+    let mut output = vec![0u8; length];
+    hasher.finalize_xof().read(&mut output);
+    output
+}
+
+/// Generates the ML-KEM key pair for the 64-byte `seed`.
+fn ml_kem_key_pair<K: KemCore>(
+    seed: &[u8],
+) -> Result<(K::DecapsulationKey, K::EncapsulationKey), CryptoError> {
+    if seed.len() != ML_KEM_SEED_LENGTH {
+        return Err(CryptoError::InvalidLength);
+    }
+    let d = B32::try_from(&seed[..32]).map_err(|_| CryptoError::InvalidLength)?;
+    let z = B32::try_from(&seed[32..]).map_err(|_| CryptoError::InvalidLength)?;
+    Ok(K::generate_deterministic(&d, &z))
+}
+
+/// Returns 32 random bytes.
+fn random_seed(crypto: &RustCrypto) -> Result<[u8; 32], CryptoError> {
+    crypto
+        .random_array()
+        .map_err(|_| CryptoError::InsufficientRandomness)
+}
+
+/// Returns the shared secret for the ciphertext `ct`.
+fn ml_kem_decapsulate<K: KemCore>(
+    dk: &K::DecapsulationKey,
+    ct: &[u8],
+) -> Result<Vec<u8>, CryptoError> {
+    let ct = Ciphertext::<K>::try_from(ct).map_err(|_| CryptoError::InvalidLength)?;
+    let ss = dk
+        .decapsulate(&ct)
+        .map_err(|_| CryptoError::CryptoLibraryError)?;
+    Ok(ss.to_vec())
+}
+
+/// `DeriveKeyPair` for ML-KEM. Returns the private and the public key.
+pub(crate) fn ml_kem_derive_key_pair<K: KemCore>(
+    ikm: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), CryptoError> {
+    let seed = shake256(ikm, ML_KEM_SEED_LENGTH);
+    let (_, ek) = ml_kem_key_pair::<K>(&seed)?;
+    Ok((seed, ek.as_bytes().to_vec()))
+}
+
+/// Returns the shared secret and the encapsulated key.
+pub(crate) fn ml_kem_encap<K: KemCore>(
+    crypto: &RustCrypto,
+    pk_r: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), CryptoError> {
+    let pk_r = ml_kem::Encoded::<K::EncapsulationKey>::try_from(pk_r)
+        .map_err(|_| CryptoError::InvalidPublicKey)?;
+    let pk_r = K::EncapsulationKey::from_bytes(&pk_r);
+    let mut rng = rand_chacha::ChaCha20Rng::from_seed(random_seed(crypto)?);
+    let (ct, ss) = pk_r
+        .encapsulate(&mut rng)
+        .map_err(|_| CryptoError::CryptoLibraryError)?;
+    Ok((ss.to_vec(), ct.to_vec()))
+}
+
+pub(crate) fn ml_kem_decap<K: KemCore>(enc: &[u8], sk_r: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    let (dk, _) = ml_kem_key_pair::<K>(sk_r)?;
+    ml_kem_decapsulate::<K>(&dk, enc)
+}
+
+/// The X-Wing combiner.
+fn x_wing_combiner(ss_m: &[u8], ss_x: &[u8], ct_x: &[u8], pk_x: &[u8]) -> Vec<u8> {
+    Sha3_256::new()
+        .chain_update(X_WING_LABEL)
+        .chain_update(ss_m)
+        .chain_update(ss_x)
+        .chain_update(ct_x)
+        .chain_update(pk_x)
+        .finalize()
+        .to_vec()
+}
+
+fn x25519_private_key(bytes: &[u8]) -> Result<StaticSecret, CryptoError> {
+    let bytes: [u8; X25519_LENGTH] = bytes.try_into().map_err(|_| CryptoError::InvalidLength)?;
+    Ok(StaticSecret::from(bytes))
+}
+
+fn x25519_public_key(bytes: &[u8]) -> Result<X25519PublicKey, CryptoError> {
+    let bytes: [u8; X25519_LENGTH] = bytes
+        .try_into()
+        .map_err(|_| CryptoError::InvalidPublicKey)?;
+    Ok(X25519PublicKey::from(bytes))
+}
+
+/// `DeriveKeyPair` for X-Wing. Returns the private and the public key.
+pub(crate) fn x_wing_derive_key_pair(ikm: &[u8]) -> Result<(Vec<u8>, Vec<u8>), CryptoError> {
+    let seed = shake256(ikm, ML_KEM_SEED_LENGTH + X25519_LENGTH);
+    let (dk_m, ek_m) = ml_kem_key_pair::<MlKem768>(&seed[..ML_KEM_SEED_LENGTH])?;
+    let sk_x = x25519_private_key(&seed[ML_KEM_SEED_LENGTH..])?;
+    let pk_x = X25519PublicKey::from(&sk_x);
+
+    let private_key = [dk_m.as_bytes().as_slice(), sk_x.as_bytes(), pk_x.as_bytes()].concat();
+    let public_key = [ek_m.as_bytes().as_slice(), pk_x.as_bytes()].concat();
+    Ok((private_key, public_key))
+}
+
+/// Returns the shared secret and the encapsulated key.
+pub(crate) fn x_wing_encap(
+    crypto: &RustCrypto,
+    pk_r: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), CryptoError> {
+    if pk_r.len() != ML_KEM_768_PUBLIC_KEY_LENGTH + X25519_LENGTH {
+        return Err(CryptoError::InvalidPublicKey);
+    }
+    let (pk_m, pk_x) = pk_r.split_at(ML_KEM_768_PUBLIC_KEY_LENGTH);
+
+    let ek_x = StaticSecret::from(random_seed(crypto)?);
+    let ct_x = X25519PublicKey::from(&ek_x);
+    let ss_x = ek_x.diffie_hellman(&x25519_public_key(pk_x)?);
+    let (ss_m, ct_m) = ml_kem_encap::<MlKem768>(crypto, pk_m)?;
+
+    let shared_secret = x_wing_combiner(&ss_m, ss_x.as_bytes(), ct_x.as_bytes(), pk_x);
+    let enc = [ct_m.as_slice(), ct_x.as_bytes()].concat();
+    Ok((shared_secret, enc))
+}
+
+pub(crate) fn x_wing_decap(enc: &[u8], sk_r: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    if enc.len() != ML_KEM_768_CIPHERTEXT_LENGTH + X25519_LENGTH
+        || sk_r.len() != ML_KEM_768_PRIVATE_KEY_LENGTH + 2 * X25519_LENGTH
+    {
+        return Err(CryptoError::InvalidLength);
+    }
+    let (ct_m, ct_x) = enc.split_at(ML_KEM_768_CIPHERTEXT_LENGTH);
+    let (sk_m, sk_x) = sk_r.split_at(ML_KEM_768_PRIVATE_KEY_LENGTH);
+    let (sk_x, pk_x) = sk_x.split_at(X25519_LENGTH);
+
+    let sk_m = ml_kem::Encoded::<<MlKem768 as KemCore>::DecapsulationKey>::try_from(sk_m)
+        .map_err(|_| CryptoError::InvalidLength)?;
+    let sk_m = <MlKem768 as KemCore>::DecapsulationKey::from_bytes(&sk_m);
+    let ss_m = ml_kem_decapsulate::<MlKem768>(&sk_m, ct_m)?;
+    let ss_x = x25519_private_key(sk_x)?.diffie_hellman(&x25519_public_key(ct_x)?);
+
+    Ok(x_wing_combiner(&ss_m, ss_x.as_bytes(), ct_x, pk_x))
+}
@@ -0,0 +1,52 @@
+//! The post-quantum signature scheme ML-DSA ([FIPS 204]).
+//!
+//! The private key of a key pair is the 32-byte seed `ξ` of the key
+//! generation. Signatures are deterministic and use an empty context string.
+//!
+//! [FIPS 204]: https://csrc.nist.gov/pubs/fips/204/final
+
+use ml_dsa::{
+    signature::{Signer, Verifier},
+    EncodedSignature, EncodedVerifyingKey, KeyGen, MlDsaParams, Signature, VerifyingKey, B32,
+};
+use openmls_traits::types::CryptoError;
+
+/// The length of the seed of a key pair.
+pub(crate) const ML_DSA_SEED_LENGTH: usize = 32;
+
+/// Generates the key pair for the `seed`. Returns the private and the public
+/// key.
+pub(crate) fn ml_dsa_key_gen<P: MlDsaParams>(seed: [u8; ML_DSA_SEED_LENGTH]) -> (Vec<u8>, Vec<u8>) {
+    let key_pair = P::key_gen_internal(&B32::from(seed));
+    (seed.to_vec(), key_pair.verifying_key().encode().to_vec())
+}
+
+/// Signs `data` with the private `key`.
+///
+/// The private key is only the seed, so every call runs the key generation
+/// again to expand it into the signing key. For ML-DSA-65 this roughly doubles
+/// the cost of a signature. The provider doesn't keep the expanded keys because
+/// they are much larger than the seeds and would have to be cached per key.
+pub(crate) fn ml_dsa_sign<P: MlDsaParams>(key: &[u8], data: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    let seed = B32::try_from(key).map_err(|_| CryptoError::InvalidLength)?;
+    let signature: Signature<P> = P::key_gen_internal(&seed)
+        .signing_key()
+        .try_sign(data)
+        .map_err(|_| CryptoError::SigningError)?;
+    Ok(signature.encode().to_vec())
+}
+
+pub(crate) fn ml_dsa_verify<P: MlDsaParams>(
+    data: &[u8],
+    pk: &[u8],
+    signature: &[u8],
+) -> Result<(), CryptoError> {
+    let pk = EncodedVerifyingKey::<P>::try_from(pk).map_err(|_| CryptoError::CryptoLibraryError)?;
+    let pk = VerifyingKey::<P>::decode(&pk);
+    let signature = EncodedSignature::<P>::try_from(signature)
+        .ok()
+        .and_then(|signature| Signature::<P>::decode(&signature))
+        .ok_or(CryptoError::InvalidSignature)?;
+    pk.verify(data, &signature)
+        .map_err(|_| CryptoError::InvalidSignature)
+}
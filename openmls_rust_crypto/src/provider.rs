@@ -5,11 +5,14 @@ use aes_gcm::{
     Aes128Gcm, Aes256Gcm, KeyInit,
 };
 use chacha20poly1305::ChaCha20Poly1305;
+use ecdsa::hazmat::{bits2field, SignPrimitive};
 use ed25519_dalek::Signer;
 use hkdf::Hkdf;
 use hpke::Hpke;
 use hpke_rs_crypto::types as hpke_types;
 use hpke_rs_rust_crypto::HpkeRustCrypto;
+#[cfg(feature = "post-quantum")]
+use ml_dsa::{MlDsa44, MlDsa65, MlDsa87};
 use openmls_traits::{
     crypto::OpenMlsCrypto,
     random::OpenMlsRand,
@@ -22,10 +25,16 @@ use p256::{
     ecdsa::{signature::Verifier, Signature, SigningKey, VerifyingKey},
     EncodedPoint,
 };
+use p521::NistP521;
 use rand::{RngCore, SeedableRng};
+use rfc6979::HmacDrbg;
 use sha2::{Digest, Sha256, Sha384, Sha512};
 use tls_codec::SecretVLBytes;
 
+#[cfg(feature = "post-quantum")]
+use crate::pq_sig;
+use crate::{ed448, rfc9180};
+
 #[derive(Debug)]
 pub struct RustCrypto {
     rng: RwLock<rand_chacha::ChaCha20Rng>,
@@ -56,8 +65,8 @@ fn kem_mode(kem: HpkeKemType) -> hpke_types::KemAlgorithm {
         HpkeKemType::DhKemP521 => hpke_types::KemAlgorithm::DhKemP521,
         HpkeKemType::DhKem25519 => hpke_types::KemAlgorithm::DhKem25519,
         HpkeKemType::DhKem448 => hpke_types::KemAlgorithm::DhKem448,
-        HpkeKemType::XWingKemDraft2 => {
-            unimplemented!("XWingKemDraft1 is not supported by the RustCrypto provider.")
+        HpkeKemType::MlKem768 | HpkeKemType::MlKem1024 | HpkeKemType::XWingKemDraft2 => {
+            unreachable!("{kem:?} is implemented in the rfc9180 module.")
         }
     }
 }
@@ -86,17 +95,42 @@ impl OpenMlsCrypto for RustCrypto {
         match ciphersuite {
             Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519
             | Ciphersuite::MLS_128_DHKEMX25519_CHACHA20POLY1305_SHA256_Ed25519
-            | Ciphersuite::MLS_128_DHKEMP256_AES128GCM_SHA256_P256 => Ok(()),
+            | Ciphersuite::MLS_128_DHKEMP256_AES128GCM_SHA256_P256
+            | Ciphersuite::MLS_256_DHKEMP384_AES256GCM_SHA384_P384
+            | Ciphersuite::MLS_256_DHKEMP521_AES256GCM_SHA512_P521
+            | Ciphersuite::MLS_256_DHKEMX448_AES256GCM_SHA512_Ed448
+            | Ciphersuite::MLS_256_DHKEMX448_CHACHA20POLY1305_SHA512_Ed448 => Ok(()),
+            #[cfg(feature = "post-quantum")]
+            Ciphersuite::MLS_256_XWING_CHACHA20POLY1305_SHA256_Ed25519
+            | Ciphersuite::MLS_128_MLKEM768_AES128GCM_SHA256_Ed25519
+            | Ciphersuite::MLS_256_MLKEM1024_AES256GCM_SHA512_Ed25519
+            | Ciphersuite::MLS_128_MLKEM768_AES128GCM_SHA256_MLDSA65
+            | Ciphersuite::MLS_256_MLKEM1024_AES256GCM_SHA512_MLDSA87 => Ok(()),
+            #[cfg(not(feature = "post-quantum"))]
             _ => Err(CryptoError::UnsupportedCiphersuite),
         }
     }
 
     fn supported_ciphersuites(&self) -> Vec<Ciphersuite> {
-        vec![
+        #[allow(unused_mut)]
+        let mut ciphersuites = vec![
             Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519,
             Ciphersuite::MLS_128_DHKEMX25519_CHACHA20POLY1305_SHA256_Ed25519,
             Ciphersuite::MLS_128_DHKEMP256_AES128GCM_SHA256_P256,
-        ]
+            Ciphersuite::MLS_256_DHKEMP384_AES256GCM_SHA384_P384,
+            Ciphersuite::MLS_256_DHKEMP521_AES256GCM_SHA512_P521,
+            Ciphersuite::MLS_256_DHKEMX448_AES256GCM_SHA512_Ed448,
+            Ciphersuite::MLS_256_DHKEMX448_CHACHA20POLY1305_SHA512_Ed448,
+        ];
+        #[cfg(feature = "post-quantum")]
+        ciphersuites.extend([
+            Ciphersuite::MLS_256_XWING_CHACHA20POLY1305_SHA256_Ed25519,
+            Ciphersuite::MLS_128_MLKEM768_AES128GCM_SHA256_Ed25519,
+            Ciphersuite::MLS_256_MLKEM1024_AES256GCM_SHA512_Ed25519,
+            Ciphersuite::MLS_128_MLKEM768_AES128GCM_SHA256_MLDSA65,
+            Ciphersuite::MLS_256_MLKEM1024_AES256GCM_SHA512_MLDSA87,
+        ]);
+        ciphersuites
     }
 
     fn hkdf_extract(
@@ -241,6 +275,27 @@ impl OpenMlsCrypto for RustCrypto {
                 let pk = k.verifying_key().to_encoded_point(false).as_bytes().into();
                 Ok((k.to_bytes().as_slice().into(), pk))
             }
+            SignatureScheme::ECDSA_SECP384R1_SHA384 => {
+                let mut rng = self
+                    .rng
+                    .write()
+                    .map_err(|_| CryptoError::InsufficientRandomness)?;
+                let k = p384::ecdsa::SigningKey::random(&mut *rng);
+                let pk = k.verifying_key().to_encoded_point(false).as_bytes().into();
+                Ok((k.to_bytes().as_slice().into(), pk))
+            }
+            SignatureScheme::ECDSA_SECP521R1_SHA512 => {
+                let mut rng = self
+                    .rng
+                    .write()
+                    .map_err(|_| CryptoError::InsufficientRandomness)?;
+                let k = p521::ecdsa::SigningKey::random(&mut *rng);
+                let pk = p521::ecdsa::VerifyingKey::from(&k)
+                    .to_encoded_point(false)
+                    .as_bytes()
+                    .into();
+                Ok((k.to_bytes().as_slice().into(), pk))
+            }
             SignatureScheme::ED25519 => {
                 let mut rng = self
                     .rng
@@ -250,7 +305,38 @@ impl OpenMlsCrypto for RustCrypto {
                 let pk = sk.verifying_key().to_bytes().into();
                 Ok((sk.to_bytes().into(), pk))
             }
-            _ => Err(CryptoError::UnsupportedSignatureScheme),
+            SignatureScheme::ED448 => {
+                let mut rng = self
+                    .rng
+                    .write()
+                    .map_err(|_| CryptoError::InsufficientRandomness)?;
+                let mut sk = [0u8; ed448::ED448_KEY_LENGTH];
+                rng.try_fill_bytes(&mut sk)
+                    .map_err(|_| CryptoError::InsufficientRandomness)?;
+                let pk = ed448::ed448_public_key(&sk)?;
+                Ok((sk.to_vec(), pk))
+            }
+            #[cfg(feature = "post-quantum")]
+            SignatureScheme::ML_DSA_44
+            | SignatureScheme::ML_DSA_65
+            | SignatureScheme::ML_DSA_87 => {
+                let mut rng = self
+                    .rng
+                    .write()
+                    .map_err(|_| CryptoError::InsufficientRandomness)?;
+                let mut seed = [0u8; pq_sig::ML_DSA_SEED_LENGTH];
+                rng.try_fill_bytes(&mut seed)
+                    .map_err(|_| CryptoError::InsufficientRandomness)?;
+                Ok(match alg {
+                    SignatureScheme::ML_DSA_44 => pq_sig::ml_dsa_key_gen::<MlDsa44>(seed),
+                    SignatureScheme::ML_DSA_65 => pq_sig::ml_dsa_key_gen::<MlDsa65>(seed),
+                    _ => pq_sig::ml_dsa_key_gen::<MlDsa87>(seed),
+                })
+            }
+            #[cfg(not(feature = "post-quantum"))]
+            SignatureScheme::ML_DSA_44
+            | SignatureScheme::ML_DSA_65
+            | SignatureScheme::ML_DSA_87 => Err(CryptoError::UnsupportedSignatureScheme),
         }
     }
 
@@ -273,6 +359,22 @@ impl OpenMlsCrypto for RustCrypto {
                 )
                 .map_err(|_| CryptoError::InvalidSignature)
             }
+            SignatureScheme::ECDSA_SECP384R1_SHA384 => {
+                let k = p384::ecdsa::VerifyingKey::from_sec1_bytes(pk)
+                    .map_err(|_| CryptoError::CryptoLibraryError)?;
+                let signature = p384::ecdsa::Signature::from_der(signature)
+                    .map_err(|_| CryptoError::InvalidSignature)?;
+                k.verify(data, &signature)
+                    .map_err(|_| CryptoError::InvalidSignature)
+            }
+            SignatureScheme::ECDSA_SECP521R1_SHA512 => {
+                let k = p521::ecdsa::VerifyingKey::from_sec1_bytes(pk)
+                    .map_err(|_| CryptoError::CryptoLibraryError)?;
+                let signature = p521::ecdsa::Signature::from_der(signature)
+                    .map_err(|_| CryptoError::InvalidSignature)?;
+                k.verify(data, &signature)
+                    .map_err(|_| CryptoError::InvalidSignature)
+            }
             SignatureScheme::ED25519 => {
                 let k = ed25519_dalek::VerifyingKey::try_from(pk)
                     .map_err(|_| CryptoError::CryptoLibraryError)?;
@@ -284,7 +386,17 @@ impl OpenMlsCrypto for RustCrypto {
                 k.verify_strict(data, &ed25519_dalek::Signature::from(sig))
                     .map_err(|_| CryptoError::InvalidSignature)
             }
-            _ => Err(CryptoError::UnsupportedSignatureScheme),
+            SignatureScheme::ED448 => ed448::ed448_verify(data, pk, signature),
+            #[cfg(feature = "post-quantum")]
+            SignatureScheme::ML_DSA_44 => pq_sig::ml_dsa_verify::<MlDsa44>(data, pk, signature),
+            #[cfg(feature = "post-quantum")]
+            SignatureScheme::ML_DSA_65 => pq_sig::ml_dsa_verify::<MlDsa65>(data, pk, signature),
+            #[cfg(feature = "post-quantum")]
+            SignatureScheme::ML_DSA_87 => pq_sig::ml_dsa_verify::<MlDsa87>(data, pk, signature),
+            #[cfg(not(feature = "post-quantum"))]
+            SignatureScheme::ML_DSA_44
+            | SignatureScheme::ML_DSA_65
+            | SignatureScheme::ML_DSA_87 => Err(CryptoError::UnsupportedSignatureScheme),
         }
     }
 
@@ -301,13 +413,34 @@ impl OpenMlsCrypto for RustCrypto {
                 let signature: Signature = k.sign(data);
                 Ok(signature.to_der().to_bytes().into())
             }
+            SignatureScheme::ECDSA_SECP384R1_SHA384 => {
+                let k = p384::ecdsa::SigningKey::from_slice(key)
+                    .map_err(|_| CryptoError::CryptoLibraryError)?;
+                let signature: p384::ecdsa::Signature = k.sign(data);
+                Ok(signature.to_der().to_bytes().into())
+            }
+            SignatureScheme::ECDSA_SECP521R1_SHA512 => {
+                let signature =
+                    sign_p521(key, data).map_err(|_| CryptoError::CryptoLibraryError)?;
+                Ok(signature.to_der().to_bytes().into())
+            }
             SignatureScheme::ED25519 => {
                 let k = ed25519_dalek::SigningKey::try_from(key)
                     .map_err(|_| CryptoError::CryptoLibraryError)?;
                 let signature = k.sign(data);
                 Ok(signature.to_bytes().into())
             }
-            _ => Err(CryptoError::UnsupportedSignatureScheme),
+            SignatureScheme::ED448 => ed448::ed448_sign(key, data),
+            #[cfg(feature = "post-quantum")]
+            SignatureScheme::ML_DSA_44 => pq_sig::ml_dsa_sign::<MlDsa44>(key, data),
+            #[cfg(feature = "post-quantum")]
+            SignatureScheme::ML_DSA_65 => pq_sig::ml_dsa_sign::<MlDsa65>(key, data),
+            #[cfg(feature = "post-quantum")]
+            SignatureScheme::ML_DSA_87 => pq_sig::ml_dsa_sign::<MlDsa87>(key, data),
+            #[cfg(not(feature = "post-quantum"))]
+            SignatureScheme::ML_DSA_44
+            | SignatureScheme::ML_DSA_65
+            | SignatureScheme::ML_DSA_87 => Err(CryptoError::UnsupportedSignatureScheme),
         }
     }
 
@@ -319,6 +452,9 @@ impl OpenMlsCrypto for RustCrypto {
         aad: &[u8],
         ptxt: &[u8],
     ) -> Result<types::HpkeCiphertext, CryptoError> {
+        if rfc9180::supports(&config) {
+            return rfc9180::seal(self, config, pk_r, info, aad, ptxt);
+        }
         let (kem_output, ciphertext) = hpke_from_config(config)
             .seal(&pk_r.into(), info, aad, ptxt, None, None, None)
             .map_err(|e| match e {
@@ -339,6 +475,9 @@ impl OpenMlsCrypto for RustCrypto {
         info: &[u8],
         aad: &[u8],
     ) -> Result<Vec<u8>, CryptoError> {
+        if rfc9180::supports(&config) {
+            return rfc9180::open(self, config, input, sk_r, info, aad);
+        }
         hpke_from_config(config)
             .open(
                 input.kem_output.as_slice(),
@@ -361,6 +500,16 @@ impl OpenMlsCrypto for RustCrypto {
         exporter_context: &[u8],
         exporter_length: usize,
     ) -> Result<(Vec<u8>, ExporterSecret), CryptoError> {
+        if rfc9180::supports(&config) {
+            return rfc9180::setup_sender_and_export(
+                self,
+                config,
+                pk_r,
+                info,
+                exporter_context,
+                exporter_length,
+            );
+        }
         let (kem_output, context) = hpke_from_config(config)
             .setup_sender(&pk_r.into(), info, None, None, None)
             .map_err(|_| CryptoError::SenderSetupError)?;
@@ -379,6 +528,17 @@ impl OpenMlsCrypto for RustCrypto {
         exporter_context: &[u8],
         exporter_length: usize,
     ) -> Result<ExporterSecret, CryptoError> {
+        if rfc9180::supports(&config) {
+            return rfc9180::setup_receiver_and_export(
+                self,
+                config,
+                enc,
+                sk_r,
+                info,
+                exporter_context,
+                exporter_length,
+            );
+        }
         let context = hpke_from_config(config)
             .setup_receiver(enc, &sk_r.into(), info, None, None, None)
             .map_err(|_| CryptoError::ReceiverSetupError)?;
@@ -393,6 +553,9 @@ impl OpenMlsCrypto for RustCrypto {
         config: HpkeConfig,
         ikm: &[u8],
     ) -> Result<types::HpkeKeyPair, CryptoError> {
+        if rfc9180::supports(&config) {
+            return rfc9180::derive_key_pair(self, config, ikm);
+        }
         let kp = hpke_from_config(config)
             .derive_key_pair(ikm)
             .map_err(|e| match e {
@@ -407,6 +570,36 @@ impl OpenMlsCrypto for RustCrypto {
     }
 }
 
+/// Signs the `data` with a P-521 signing `key`.
+///
+/// The signing key of `p521` picks random nonces. Like for the other ECDSA
+/// schemes, the nonce is derived deterministically as in RFC 6979 instead.
+/// The `rfc6979` crate requires the digest to be as long as the field, which
+/// doesn't hold for SHA-512 and P-521, so `k` is generated here.
+fn sign_p521(key: &[u8], data: &[u8]) -> Result<p521::ecdsa::Signature, ecdsa::Error> {
+    let key = p521::ecdsa::SigningKey::from_slice(key)?;
+    let x = key.as_nonzero_scalar().as_ref();
+    let z = bits2field::<NistP521>(&Sha512::digest(data))?;
+
+    let mut drbg = HmacDrbg::<Sha512>::new(&x.to_bytes(), &z, &[]);
+    loop {
+        // bits2int: keep the leftmost 521 of the 528 generated bits.
+        let mut t = p521::FieldBytes::default();
+        drbg.fill_bytes(&mut t);
+        let mut k = p521::FieldBytes::default();
+        for i in (1..k.len()).rev() {
+            k[i] = (t[i - 1] << 1) | (t[i] >> 7);
+        }
+        k[0] = t[0] >> 7;
+
+        let k = Option::<p521::NonZeroScalar>::from(p521::NonZeroScalar::from_repr(k));
+        if let Some(k) = k {
+            let (signature, _) = x.try_sign_prehashed(*k, &z)?;
+            return Ok(signature);
+        }
+    }
+}
+
 fn hpke_from_config(config: HpkeConfig) -> Hpke<HpkeRustCrypto> {
     Hpke::<HpkeRustCrypto>::new(
         hpke::Mode::Base,
@@ -0,0 +1,544 @@
+//! HPKE ([RFC 9180]) in base mode for the DHKEMs on P-384, P-521 and X448 and
+//! for the post-quantum KEMs.
+//!
+//! hpke-rs doesn't implement these KEMs with RustCrypto primitives. This module
+//! implements the DHKEMs and the HPKE key schedule on top of the HKDF and AEAD
+//! functions of the [`RustCrypto`] provider. The post-quantum KEMs are in
+//! `crate::pq_kem` and require the `post-quantum` feature.
+//!
+//! [RFC 9180]: https://www.rfc-editor.org/rfc/rfc9180.html
+
+use elliptic_curve::{
+    ecdh,
+    sec1::{FromEncodedPoint, ModulusSize, ToEncodedPoint},
+    AffinePoint, CurveArithmetic, FieldBytesSize, PublicKey, SecretKey,
+};
+#[cfg(feature = "post-quantum")]
+use ml_kem::{MlKem1024, MlKem768};
+use openmls_traits::{
+    crypto::OpenMlsCrypto,
+    random::OpenMlsRand,
+    types::{
+        AeadType, CryptoError, ExporterSecret, HashType, HpkeAeadType, HpkeCiphertext, HpkeConfig,
+        HpkeKdfType, HpkeKemType, HpkeKeyPair,
+    },
+};
+use p384::NistP384;
+use p521::NistP521;
+
+#[cfg(feature = "post-quantum")]
+use crate::pq_kem;
+use crate::RustCrypto;
+
+const VERSION_LABEL: &[u8] = b"HPKE-v1";
+const MODE_BASE: u8 = 0x00;
+const NONCE_LENGTH: usize = 12;
+
+/// The KEMs implemented by this module.
+#[derive(Debug, Clone, Copy)]
+enum Kem {
+    Dh(DhKem),
+    #[cfg(feature = "post-quantum")]
+    MlKem768,
+    #[cfg(feature = "post-quantum")]
+    MlKem1024,
+    #[cfg(feature = "post-quantum")]
+    XWing,
+}
+
+impl Kem {
+    fn new(kem: HpkeKemType) -> Option<Self> {
+        match kem {
+            HpkeKemType::DhKemP384 => Some(Self::Dh(DhKem::P384)),
+            HpkeKemType::DhKemP521 => Some(Self::Dh(DhKem::P521)),
+            HpkeKemType::DhKem448 => Some(Self::Dh(DhKem::X448)),
+            #[cfg(feature = "post-quantum")]
+            HpkeKemType::MlKem768 => Some(Self::MlKem768),
+            #[cfg(feature = "post-quantum")]
+            HpkeKemType::MlKem1024 => Some(Self::MlKem1024),
+            #[cfg(feature = "post-quantum")]
+            HpkeKemType::XWingKemDraft2 => Some(Self::XWing),
+            _ => None,
+        }
+    }
+
+    /// Returns the private and the public key.
+    fn derive_key_pair(
+        self,
+        crypto: &RustCrypto,
+        ikm: &[u8],
+    ) -> Result<(Vec<u8>, Vec<u8>), CryptoError> {
+        match self {
+            Self::Dh(kem) => kem.derive_key_pair(crypto, ikm),
+            #[cfg(feature = "post-quantum")]
+            Self::MlKem768 => pq_kem::ml_kem_derive_key_pair::<MlKem768>(ikm),
+            #[cfg(feature = "post-quantum")]
+            Self::MlKem1024 => pq_kem::ml_kem_derive_key_pair::<MlKem1024>(ikm),
+            #[cfg(feature = "post-quantum")]
+            Self::XWing => pq_kem::x_wing_derive_key_pair(ikm),
+        }
+    }
+
+    /// Returns the shared secret and the encapsulated key.
+    fn encap(self, crypto: &RustCrypto, pk_r: &[u8]) -> Result<(Vec<u8>, Vec<u8>), CryptoError> {
+        match self {
+            Self::Dh(kem) => kem.encap(crypto, pk_r),
+            #[cfg(feature = "post-quantum")]
+            Self::MlKem768 => pq_kem::ml_kem_encap::<MlKem768>(crypto, pk_r),
+            #[cfg(feature = "post-quantum")]
+            Self::MlKem1024 => pq_kem::ml_kem_encap::<MlKem1024>(crypto, pk_r),
+            #[cfg(feature = "post-quantum")]
+            Self::XWing => pq_kem::x_wing_encap(crypto, pk_r),
+        }
+    }
+
+    fn decap(self, crypto: &RustCrypto, enc: &[u8], sk_r: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        match self {
+            Self::Dh(kem) => kem.decap(crypto, enc, sk_r),
+            #[cfg(feature = "post-quantum")]
+            Self::MlKem768 => pq_kem::ml_kem_decap::<MlKem768>(enc, sk_r),
+            #[cfg(feature = "post-quantum")]
+            Self::MlKem1024 => pq_kem::ml_kem_decap::<MlKem1024>(enc, sk_r),
+            #[cfg(feature = "post-quantum")]
+            Self::XWing => pq_kem::x_wing_decap(enc, sk_r),
+        }
+    }
+}
+
+/// The DHKEMs implemented by this module.
+#[derive(Debug, Clone, Copy)]
+enum DhKem {
+    P384,
+    P521,
+    X448,
+}
+
+impl DhKem {
+    fn suite_id(self) -> Vec<u8> {
+        let kem_id = match self {
+            Self::P384 => HpkeKemType::DhKemP384,
+            Self::P521 => HpkeKemType::DhKemP521,
+            Self::X448 => HpkeKemType::DhKem448,
+        };
+        [b"KEM".as_slice(), &(kem_id as u16).to_be_bytes()].concat()
+    }
+
+    /// The hash function of the KDF used by the KEM.
+    fn hash(self) -> HashType {
+        match self {
+            Self::P384 => HashType::Sha2_384,
+            Self::P521 | Self::X448 => HashType::Sha2_512,
+        }
+    }
+
+    /// The length of a private key (`Nsk`).
+    fn private_key_length(self) -> usize {
+        match self {
+            Self::P384 => 48,
+            Self::P521 => 66,
+            Self::X448 => 56,
+        }
+    }
+
+    /// The length of a public key (`Npk`), which is uncompressed for the NIST
+    /// curves.
+    fn public_key_length(self) -> usize {
+        match self {
+            Self::P384 | Self::P521 => 2 * self.private_key_length() + 1,
+            Self::X448 => 56,
+        }
+    }
+
+    /// The mask applied to the first byte of a candidate private key in
+    /// `DeriveKeyPair`. Only used for the NIST curves.
+    fn bitmask(self) -> u8 {
+        match self {
+            Self::P384 => 0xFF,
+            Self::P521 => 0x01,
+            Self::X448 => 0xFF,
+        }
+    }
+
+    fn is_private_key(self, candidate: &[u8]) -> bool {
+        match self {
+            Self::P384 => SecretKey::<NistP384>::from_slice(candidate).is_ok(),
+            Self::P521 => SecretKey::<NistP521>::from_slice(candidate).is_ok(),
+            Self::X448 => candidate.len() == 56,
+        }
+    }
+
+    fn public_key(self, private_key: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        match self {
+            Self::P384 => public_key::<NistP384>(private_key),
+            Self::P521 => public_key::<NistP521>(private_key),
+            Self::X448 => {
+                let private_key =
+                    x448::Secret::from_bytes(private_key).ok_or(CryptoError::InvalidLength)?;
+                Ok(x448::PublicKey::from(&private_key).as_bytes().to_vec())
+            }
+        }
+    }
+
+    fn dh(self, private_key: &[u8], public_key: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        if public_key.len() != self.public_key_length() {
+            return Err(CryptoError::InvalidPublicKey);
+        }
+        match self {
+            Self::P384 => dh::<NistP384>(private_key, public_key),
+            Self::P521 => dh::<NistP521>(private_key, public_key),
+            Self::X448 => {
+                let private_key =
+                    x448::Secret::from_bytes(private_key).ok_or(CryptoError::InvalidLength)?;
+                let public_key =
+                    x448::PublicKey::from_bytes(public_key).ok_or(CryptoError::InvalidPublicKey)?;
+                let shared_secret = private_key
+                    .as_diffie_hellman(&public_key)
+                    .ok_or(CryptoError::InvalidPublicKey)?;
+                Ok(shared_secret.as_bytes().to_vec())
+            }
+        }
+    }
+
+    /// `DeriveKeyPair` of RFC 9180, Section 7.1.3.
+    fn derive_key_pair(
+        self,
+        crypto: &RustCrypto,
+        ikm: &[u8],
+    ) -> Result<(Vec<u8>, Vec<u8>), CryptoError> {
+        let suite_id = self.suite_id();
+        let dkp_prk = labeled_extract(crypto, self.hash(), &suite_id, &[], b"dkp_prk", ikm)?;
+        if let Self::X448 = self {
+            // Every 56-byte string is an X448 private key, so there's no need
+            // for rejection sampling.
+            let private_key = labeled_expand(
+                crypto,
+                self.hash(),
+                &suite_id,
+                &dkp_prk,
+                b"sk",
+                &[],
+                self.private_key_length(),
+            )?;
+            let public_key = self.public_key(&private_key)?;
+            return Ok((private_key, public_key));
+        }
+        for counter in 0..=u8::MAX {
+            let mut candidate = labeled_expand(
+                crypto,
+                self.hash(),
+                &suite_id,
+                &dkp_prk,
+                b"candidate",
+                &[counter],
+                self.private_key_length(),
+            )?;
+            candidate[0] &= self.bitmask();
+            if self.is_private_key(&candidate) {
+                let public_key = self.public_key(&candidate)?;
+                return Ok((candidate, public_key));
+            }
+        }
+        Err(CryptoError::CryptoLibraryError)
+    }
+
+    /// `ExtractAndExpand` of RFC 9180, Section 4.1.
+    fn extract_and_expand(
+        self,
+        crypto: &RustCrypto,
+        dh: &[u8],
+        kem_context: &[u8],
+    ) -> Result<Vec<u8>, CryptoError> {
+        let suite_id = self.suite_id();
+        let eae_prk = labeled_extract(crypto, self.hash(), &suite_id, &[], b"eae_prk", dh)?;
+        labeled_expand(
+            crypto,
+            self.hash(),
+            &suite_id,
+            &eae_prk,
+            b"shared_secret",
+            kem_context,
+            self.hash().size(),
+        )
+    }
+
+    /// Returns the shared secret and the encapsulated key.
+    fn encap(self, crypto: &RustCrypto, pk_r: &[u8]) -> Result<(Vec<u8>, Vec<u8>), CryptoError> {
+        let ikm = crypto
+            .random_vec(self.private_key_length())
+            .map_err(|_| CryptoError::InsufficientRandomness)?;
+        let (sk_e, enc) = self.derive_key_pair(crypto, &ikm)?;
+        let dh = self.dh(&sk_e, pk_r)?;
+        let kem_context = [enc.as_slice(), pk_r].concat();
+        let shared_secret = self.extract_and_expand(crypto, &dh, &kem_context)?;
+        Ok((shared_secret, enc))
+    }
+
+    fn decap(self, crypto: &RustCrypto, enc: &[u8], sk_r: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let dh = self.dh(sk_r, enc)?;
+        let pk_r = self.public_key(sk_r)?;
+        let kem_context = [enc, pk_r.as_slice()].concat();
+        self.extract_and_expand(crypto, &dh, &kem_context)
+    }
+}
+
+fn public_key<C>(private_key: &[u8]) -> Result<Vec<u8>, CryptoError>
+where
+    C: CurveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    FieldBytesSize<C>: ModulusSize,
+{
+    let private_key =
+        SecretKey::<C>::from_slice(private_key).map_err(|_| CryptoError::InvalidLength)?;
+    Ok(private_key
+        .public_key()
+        .to_encoded_point(false)
+        .as_bytes()
+        .to_vec())
+}
+
+fn dh<C>(private_key: &[u8], public_key: &[u8]) -> Result<Vec<u8>, CryptoError>
+where
+    C: CurveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    FieldBytesSize<C>: ModulusSize,
+{
+    let private_key =
+        SecretKey::<C>::from_slice(private_key).map_err(|_| CryptoError::InvalidLength)?;
+    let public_key =
+        PublicKey::<C>::from_sec1_bytes(public_key).map_err(|_| CryptoError::InvalidPublicKey)?;
+    let shared_secret =
+        ecdh::diffie_hellman(private_key.to_nonzero_scalar(), public_key.as_affine());
+    Ok(shared_secret.raw_secret_bytes().to_vec())
+}
+
+fn labeled_extract(
+    crypto: &RustCrypto,
+    hash: HashType,
+    suite_id: &[u8],
+    salt: &[u8],
+    label: &[u8],
+    ikm: &[u8],
+) -> Result<Vec<u8>, CryptoError> {
+    let labeled_ikm = [VERSION_LABEL, suite_id, label, ikm].concat();
+    Ok(crypto
+        .hkdf_extract(hash, salt, &labeled_ikm)?
+        .as_slice()
+        .to_vec())
+}
+
+fn labeled_expand(
+    crypto: &RustCrypto,
+    hash: HashType,
+    suite_id: &[u8],
+    prk: &[u8],
+    label: &[u8],
+    info: &[u8],
+    length: usize,
+) -> Result<Vec<u8>, CryptoError> {
+    let length_bytes = u16::try_from(length)
+        .map_err(|_| CryptoError::InvalidLength)?
+        .to_be_bytes();
+    let labeled_info = [&length_bytes, VERSION_LABEL, suite_id, label, info].concat();
+    Ok(crypto
+        .hkdf_expand(hash, prk, &labeled_info, length)?
+        .as_slice()
+        .to_vec())
+}
+
+/// The context of RFC 9180, Section 5.1, for a single message.
+struct Context {
+    aead: Option<AeadType>,
+    key: Vec<u8>,
+    base_nonce: Vec<u8>,
+    exporter_secret: Vec<u8>,
+    hash: HashType,
+    suite_id: Vec<u8>,
+}
+
+impl Context {
+    /// `KeyScheduleS` and `KeyScheduleR` in base mode.
+    fn new(
+        crypto: &RustCrypto,
+        config: &HpkeConfig,
+        shared_secret: &[u8],
+        info: &[u8],
+    ) -> Result<Self, CryptoError> {
+        let HpkeConfig(kem, kdf, aead) = *config;
+        let suite_id = [
+            b"HPKE".as_slice(),
+            &(kem as u16).to_be_bytes(),
+            &(kdf as u16).to_be_bytes(),
+            &(aead as u16).to_be_bytes(),
+        ]
+        .concat();
+        let hash = match kdf {
+            HpkeKdfType::HkdfSha256 => HashType::Sha2_256,
+            HpkeKdfType::HkdfSha384 => HashType::Sha2_384,
+            HpkeKdfType::HkdfSha512 => HashType::Sha2_512,
+        };
+        let aead = match aead {
+            HpkeAeadType::AesGcm128 => Some(AeadType::Aes128Gcm),
+            HpkeAeadType::AesGcm256 => Some(AeadType::Aes256Gcm),
+            HpkeAeadType::ChaCha20Poly1305 => Some(AeadType::ChaCha20Poly1305),
+            HpkeAeadType::Export => None,
+        };
+
+        let psk_id_hash = labeled_extract(crypto, hash, &suite_id, &[], b"psk_id_hash", &[])?;
+        let info_hash = labeled_extract(crypto, hash, &suite_id, &[], b"info_hash", info)?;
+        let key_schedule_context = [&[MODE_BASE], psk_id_hash.as_slice(), &info_hash].concat();
+        let secret = labeled_extract(crypto, hash, &suite_id, shared_secret, b"secret", &[])?;
+        let expand = |label: &[u8], length| {
+            labeled_expand(
+                crypto,
+                hash,
+                &suite_id,
+                &secret,
+                label,
+                &key_schedule_context,
+                length,
+            )
+        };
+
+        let (key, base_nonce) = match aead {
+            Some(aead) => (
+                expand(b"key", aead.key_size())?,
+                expand(b"base_nonce", NONCE_LENGTH)?,
+            ),
+            None => (Vec::new(), Vec::new()),
+        };
+        let exporter_secret = expand(b"exp", hash.size())?;
+
+        Ok(Self {
+            aead,
+            key,
+            base_nonce,
+            exporter_secret,
+            hash,
+            suite_id,
+        })
+    }
+
+    /// Seals the first and only message of the context, which uses the base
+    /// nonce.
+    fn seal(&self, crypto: &RustCrypto, aad: &[u8], ptxt: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let aead = self.aead.ok_or(CryptoError::UnsupportedAeadAlgorithm)?;
+        crypto.aead_encrypt(aead, &self.key, ptxt, &self.base_nonce, aad)
+    }
+
+    /// Opens the first and only message of the context, which uses the base
+    /// nonce.
+    fn open(&self, crypto: &RustCrypto, aad: &[u8], ctxt: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let aead = self.aead.ok_or(CryptoError::UnsupportedAeadAlgorithm)?;
+        crypto.aead_decrypt(aead, &self.key, ctxt, &self.base_nonce, aad)
+    }
+
+    fn export(
+        &self,
+        crypto: &RustCrypto,
+        exporter_context: &[u8],
+        length: usize,
+    ) -> Result<Vec<u8>, CryptoError> {
+        labeled_expand(
+            crypto,
+            self.hash,
+            &self.suite_id,
+            &self.exporter_secret,
+            b"sec",
+            exporter_context,
+            length,
+        )
+    }
+}
+
+/// Returns `true` if this module, rather than hpke-rs, is responsible for the
+/// KEM of the `config`. The post-quantum KEMs are rejected by this module when
+/// the `post-quantum` feature is disabled.
+pub(crate) fn supports(config: &HpkeConfig) -> bool {
+    !matches!(config.0, HpkeKemType::DhKemP256 | HpkeKemType::DhKem25519)
+}
+
+fn kem(config: &HpkeConfig) -> Result<Kem, CryptoError> {
+    Kem::new(config.0).ok_or(CryptoError::UnsupportedCiphersuite)
+}
+
+pub(crate) fn seal(
+    crypto: &RustCrypto,
+    config: HpkeConfig,
+    pk_r: &[u8],
+    info: &[u8],
+    aad: &[u8],
+    ptxt: &[u8],
+) -> Result<HpkeCiphertext, CryptoError> {
+    let (shared_secret, enc) = kem(&config)?.encap(crypto, pk_r)?;
+    let ciphertext =
+        Context::new(crypto, &config, &shared_secret, info)?.seal(crypto, aad, ptxt)?;
+    Ok(HpkeCiphertext {
+        kem_output: enc.into(),
+        ciphertext: ciphertext.into(),
+    })
+}
+
+pub(crate) fn open(
+    crypto: &RustCrypto,
+    config: HpkeConfig,
+    input: &HpkeCiphertext,
+    sk_r: &[u8],
+    info: &[u8],
+    aad: &[u8],
+) -> Result<Vec<u8>, CryptoError> {
+    let shared_secret = kem(&config)?
+        .decap(crypto, input.kem_output.as_slice(), sk_r)
+        .map_err(|_| CryptoError::HpkeDecryptionError)?;
+    Context::new(crypto, &config, &shared_secret, info)?
+        .open(crypto, aad, input.ciphertext.as_slice())
+        .map_err(|_| CryptoError::HpkeDecryptionError)
+}
+
+pub(crate) fn setup_sender_and_export(
+    crypto: &RustCrypto,
+    config: HpkeConfig,
+    pk_r: &[u8],
+    info: &[u8],
+    exporter_context: &[u8],
+    exporter_length: usize,
+) -> Result<(Vec<u8>, ExporterSecret), CryptoError> {
+    let (shared_secret, enc) = kem(&config)?
+        .encap(crypto, pk_r)
+        .map_err(|_| CryptoError::SenderSetupError)?;
+    let exported_secret = Context::new(crypto, &config, &shared_secret, info)
+        .map_err(|_| CryptoError::SenderSetupError)?
+        .export(crypto, exporter_context, exporter_length)
+        .map_err(|_| CryptoError::ExporterError)?;
+    Ok((enc, exported_secret.into()))
+}
+
+pub(crate) fn setup_receiver_and_export(
+    crypto: &RustCrypto,
+    config: HpkeConfig,
+    enc: &[u8],
+    sk_r: &[u8],
+    info: &[u8],
+    exporter_context: &[u8],
+    exporter_length: usize,
+) -> Result<ExporterSecret, CryptoError> {
+    let shared_secret = kem(&config)?
+        .decap(crypto, enc, sk_r)
+        .map_err(|_| CryptoError::ReceiverSetupError)?;
+    let exported_secret = Context::new(crypto, &config, &shared_secret, info)
+        .map_err(|_| CryptoError::ReceiverSetupError)?
+        .export(crypto, exporter_context, exporter_length)
+        .map_err(|_| CryptoError::ExporterError)?;
+    Ok(exported_secret.into())
+}
+
+pub(crate) fn derive_key_pair(
+    crypto: &RustCrypto,
+    config: HpkeConfig,
+    ikm: &[u8],
+) -> Result<HpkeKeyPair, CryptoError> {
+    let (private, public) = kem(&config)?.derive_key_pair(crypto, ikm)?;
+    Ok(HpkeKeyPair {
+        private: private.into(),
+        public,
+    })
+}
@@ -0,0 +1,959 @@
+//! # OpenMLS SQLite Storage
+//!
+//! A [`StorageProvider`] for OpenMLS that persists the state of groups and
+//! key material in a SQLite database.
+//!
+//! Every kind of entity is stored in its own table. Values that belong to a
+//! group are indexed by the group id. Keys and values are serialized with
+//! `serde_json`, like in the memory storage.
+//!
+//! Transactions are mapped to SQLite savepoints, so that the state of a group
+//! is updated atomically when OpenMLS merges a commit.
+//!
+//! The schema is created and upgraded by [`SqliteStorage::new()`], which
+//! applies all pending migrations to the database. See [`SCHEMA_VERSION`].
+
+use std::{
+    path::Path,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use openmls_traits::storage::*;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{de::DeserializeOwned, Serialize};
+
+mod migrations;
+
+pub use migrations::SCHEMA_VERSION;
+
+/// A storage that is backed by a SQLite database.
+#[derive(Debug)]
+pub struct SqliteStorage {
+    connection: Mutex<Connection>,
+}
+
+/// Errors thrown by the SQLite storage.
+#[derive(thiserror::Error, Debug)]
+pub enum SqliteStorageError {
+    /// An error of the database.
+    #[error("Database error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+    /// A key or value could not be (de)serialized.
+    #[error("Error serializing value: {0}")]
+    SerializationError(#[from] serde_json::Error),
+    /// The schema of the database is newer than the schema supported by this
+    /// version of the storage.
+    #[error("Unsupported schema version {0}.")]
+    UnsupportedSchemaVersion(u32),
+}
+
+impl SqliteStorage {
+    /// Create a new storage that uses the given `connection`.
+    ///
+    /// The schema of the database is created or upgraded to
+    /// [`SCHEMA_VERSION`].
+    pub fn new(mut connection: Connection) -> Result<Self, SqliteStorageError> {
+        migrations::migrate(&mut connection)?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    /// Open the database at the given `path`, creating it if it doesn't
+    /// exist. See [`SqliteStorage::new()`].
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, SqliteStorageError> {
+        Self::new(Connection::open(path)?)
+    }
+
+    /// Create a storage that is backed by a new in-memory database.
+    pub fn open_in_memory() -> Result<Self, SqliteStorageError> {
+        Self::new(Connection::open_in_memory()?)
+    }
+
+    /// Returns the schema version of the database.
+    pub fn schema_version(&self) -> Result<u32, SqliteStorageError> {
+        Ok(migrations::schema_version(&self.connection())?)
+    }
+
+    /// Consume the storage and return the underlying connection.
+    pub fn into_connection(self) -> Connection {
+        self.connection
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn connection(&self) -> MutexGuard<'_, Connection> {
+        // A panic while holding the lock can't leave the database in an
+        // inconsistent state, because every write is a single statement.
+        self.connection
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Internal helper to abstract write operations.
+    fn write(
+        &self,
+        table: &Table,
+        key: &impl Serialize,
+        value: &impl Serialize,
+    ) -> Result<(), SqliteStorageError> {
+        let key = serde_json::to_vec(key)?;
+        let value = serde_json::to_vec(value)?;
+        self.connection()
+            .prepare_cached(&format!(
+                "INSERT OR REPLACE INTO {} ({}, {}) VALUES (?1, ?2)",
+                table.name, table.key, table.value
+            ))?
+            .execute(params![key, value])?;
+        Ok(())
+    }
+
+    /// Internal helper to abstract read operations.
+    fn read<V: DeserializeOwned>(
+        &self,
+        table: &Table,
+        key: &impl Serialize,
+    ) -> Result<Option<V>, SqliteStorageError> {
+        let key = serde_json::to_vec(key)?;
+        let value: Option<Vec<u8>> = self
+            .connection()
+            .prepare_cached(&format!(
+                "SELECT {} FROM {} WHERE {} = ?1",
+                table.value, table.name, table.key
+            ))?
+            .query_row(params![key], |row| row.get(0))
+            .optional()?;
+        value
+            .map(|value| serde_json::from_slice(&value))
+            .transpose()
+            .map_err(SqliteStorageError::from)
+    }
+
+    /// Internal helper to list the keys of a table.
+    fn keys<K: DeserializeOwned>(&self, table: &Table) -> Result<Vec<K>, SqliteStorageError> {
+        let connection = self.connection();
+        let mut statement =
+            connection.prepare_cached(&format!("SELECT {} FROM {}", table.key, table.name))?;
+        let keys = statement
+            .query_map([], |row| row.get::<_, Vec<u8>>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        keys.iter()
+            .map(|key| serde_json::from_slice(key))
+            .collect::<Result<_, _>>()
+            .map_err(SqliteStorageError::from)
+    }
+
+    /// Internal helper to abstract delete operations.
+    fn delete(&self, table: &Table, key: &impl Serialize) -> Result<(), SqliteStorageError> {
+        let key = serde_json::to_vec(key)?;
+        self.connection()
+            .prepare_cached(&format!(
+                "DELETE FROM {} WHERE {} = ?1",
+                table.name, table.key
+            ))?
+            .execute(params![key])?;
+        Ok(())
+    }
+}
+
+/// A table that maps a key to a single value.
+struct Table {
+    name: &'static str,
+    key: &'static str,
+    value: &'static str,
+}
+
+impl Table {
+    /// A table that stores a single value per group.
+    const fn group_data(name: &'static str) -> Self {
+        Self {
+            name,
+            key: "group_id",
+            value: "value",
+        }
+    }
+}
+
+const KEY_PACKAGES: Table = Table {
+    name: "openmls_key_packages",
+    key: "key_package_ref",
+    value: "key_package",
+};
+const PSKS: Table = Table {
+    name: "openmls_psks",
+    key: "psk_id",
+    value: "psk_bundle",
+};
+const ENCRYPTION_KEY_PAIRS: Table = Table {
+    name: "openmls_encryption_key_pairs",
+    key: "public_key",
+    value: "key_pair",
+};
+const SIGNATURE_KEY_PAIRS: Table = Table {
+    name: "openmls_signature_key_pairs",
+    key: "public_key",
+    value: "key_pair",
+};
+
+// related to PublicGroup
+const TREES: Table = Table::group_data("openmls_trees");
+const GROUP_CONTEXTS: Table = Table::group_data("openmls_group_contexts");
+const INTERIM_TRANSCRIPT_HASHES: Table = Table::group_data("openmls_interim_transcript_hashes");
+const CONFIRMATION_TAGS: Table = Table::group_data("openmls_confirmation_tags");
+
+// related to MlsGroup
+const JOIN_CONFIGS: Table = Table::group_data("openmls_join_configs");
+const GROUP_STATES: Table = Table::group_data("openmls_group_states");
+const MESSAGE_SECRETS: Table = Table::group_data("openmls_message_secrets");
+const RESUMPTION_PSK_STORES: Table = Table::group_data("openmls_resumption_psk_stores");
+const OWN_LEAF_INDICES: Table = Table::group_data("openmls_own_leaf_indices");
+const GROUP_EPOCH_SECRETS: Table = Table::group_data("openmls_group_epoch_secrets");
+
+impl StorageProvider<CURRENT_VERSION> for SqliteStorage {
+    type Error = SqliteStorageError;
+
+    fn begin_transaction(&self) -> Result<(), Self::Error> {
+        self.connection().execute_batch("SAVEPOINT openmls")?;
+        Ok(())
+    }
+
+    fn commit_transaction(&self) -> Result<(), Self::Error> {
+        self.connection().execute_batch("RELEASE openmls")?;
+        Ok(())
+    }
+
+    fn rollback_transaction(&self) -> Result<(), Self::Error> {
+        self.connection()
+            .execute_batch("ROLLBACK TO openmls; RELEASE openmls")?;
+        Ok(())
+    }
+
+    fn write_mls_join_config<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        MlsGroupJoinConfig: traits::MlsGroupJoinConfig<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        config: &MlsGroupJoinConfig,
+    ) -> Result<(), Self::Error> {
+        self.write(&JOIN_CONFIGS, group_id, config)
+    }
+
+    fn append_own_leaf_node<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        LeafNode: traits::LeafNode<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        leaf_node: &LeafNode,
+    ) -> Result<(), Self::Error> {
+        let group_id = serde_json::to_vec(group_id)?;
+        let leaf_node = serde_json::to_vec(leaf_node)?;
+        self.connection()
+            .prepare_cached(
+                "INSERT INTO openmls_own_leaf_nodes (group_id, leaf_node) VALUES (?1, ?2)",
+            )?
+            .execute(params![group_id, leaf_node])?;
+        Ok(())
+    }
+
+    fn queue_proposal<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
+        QueuedProposal: traits::QueuedProposal<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        proposal_ref: &ProposalRef,
+        proposal: &QueuedProposal,
+    ) -> Result<(), Self::Error> {
+        let group_id = serde_json::to_vec(group_id)?;
+        let proposal_ref = serde_json::to_vec(proposal_ref)?;
+        let proposal = serde_json::to_vec(proposal)?;
+        self.connection()
+            .prepare_cached(
+                "INSERT OR REPLACE INTO openmls_proposals (group_id, proposal_ref, proposal)
+                VALUES (?1, ?2, ?3)",
+            )?
+            .execute(params![group_id, proposal_ref, proposal])?;
+        Ok(())
+    }
+
+    fn write_tree<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        TreeSync: traits::TreeSync<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        tree: &TreeSync,
+    ) -> Result<(), Self::Error> {
+        self.write(&TREES, group_id, tree)
+    }
+
+    fn write_tree_node<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        TreeNode: traits::TreeNode<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        node_index: u32,
+        node: &TreeNode,
+    ) -> Result<(), Self::Error> {
+        let group_id = serde_json::to_vec(group_id)?;
+        let node = serde_json::to_vec(node)?;
+        self.connection()
+            .prepare_cached(
+                "INSERT OR REPLACE INTO openmls_tree_nodes
+                (group_id, node_index, node) VALUES (?1, ?2, ?3)",
+            )?
+            .execute(params![group_id, node_index, node])?;
+        Ok(())
+    }
+
+    fn write_interim_transcript_hash<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        InterimTranscriptHash: traits::InterimTranscriptHash<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        interim_transcript_hash: &InterimTranscriptHash,
+    ) -> Result<(), Self::Error> {
+        self.write(
+            &INTERIM_TRANSCRIPT_HASHES,
+            group_id,
+            interim_transcript_hash,
+        )
+    }
+
+    fn write_context<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        GroupContext: traits::GroupContext<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_context: &GroupContext,
+    ) -> Result<(), Self::Error> {
+        self.write(&GROUP_CONTEXTS, group_id, group_context)
+    }
+
+    fn write_confirmation_tag<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ConfirmationTag: traits::ConfirmationTag<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        confirmation_tag: &ConfirmationTag,
+    ) -> Result<(), Self::Error> {
+        self.write(&CONFIRMATION_TAGS, group_id, confirmation_tag)
+    }
+
+    fn write_group_state<
+        GroupState: traits::GroupState<CURRENT_VERSION>,
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_state: &GroupState,
+    ) -> Result<(), Self::Error> {
+        self.write(&GROUP_STATES, group_id, group_state)
+    }
+
+    fn write_message_secrets<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        MessageSecrets: traits::MessageSecrets<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        message_secrets: &MessageSecrets,
+    ) -> Result<(), Self::Error> {
+        self.write(&MESSAGE_SECRETS, group_id, message_secrets)
+    }
+
+    fn write_resumption_psk_store<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ResumptionPskStore: traits::ResumptionPskStore<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        resumption_psk_store: &ResumptionPskStore,
+    ) -> Result<(), Self::Error> {
+        self.write(&RESUMPTION_PSK_STORES, group_id, resumption_psk_store)
+    }
+
+    fn write_own_leaf_index<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        LeafNodeIndex: traits::LeafNodeIndex<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        own_leaf_index: &LeafNodeIndex,
+    ) -> Result<(), Self::Error> {
+        self.write(&OWN_LEAF_INDICES, group_id, own_leaf_index)
+    }
+
+    fn write_group_epoch_secrets<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        GroupEpochSecrets: traits::GroupEpochSecrets<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_epoch_secrets: &GroupEpochSecrets,
+    ) -> Result<(), Self::Error> {
+        self.write(&GROUP_EPOCH_SECRETS, group_id, group_epoch_secrets)
+    }
+
+    fn write_signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<CURRENT_VERSION>,
+        SignatureKeyPair: traits::SignatureKeyPair<CURRENT_VERSION>,
+    >(
+        &self,
+        public_key: &SignaturePublicKey,
+        signature_key_pair: &SignatureKeyPair,
+    ) -> Result<(), Self::Error> {
+        self.write(&SIGNATURE_KEY_PAIRS, public_key, signature_key_pair)
+    }
+
+    fn write_encryption_key_pair<
+        EncryptionKey: traits::EncryptionKey<CURRENT_VERSION>,
+        HpkeKeyPair: traits::HpkeKeyPair<CURRENT_VERSION>,
+    >(
+        &self,
+        public_key: &EncryptionKey,
+        key_pair: &HpkeKeyPair,
+    ) -> Result<(), Self::Error> {
+        self.write(&ENCRYPTION_KEY_PAIRS, public_key, key_pair)
+    }
+
+    fn write_encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        EpochKey: traits::EpochKey<CURRENT_VERSION>,
+        HpkeKeyPair: traits::HpkeKeyPair<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+        key_pairs: &[HpkeKeyPair],
+    ) -> Result<(), Self::Error> {
+        let group_id = serde_json::to_vec(group_id)?;
+        let epoch = serde_json::to_vec(epoch)?;
+        let key_pairs = serde_json::to_vec(key_pairs)?;
+        self.connection()
+            .prepare_cached(
+                "INSERT OR REPLACE INTO openmls_epoch_key_pairs
+                (group_id, epoch_id, leaf_index, key_pairs) VALUES (?1, ?2, ?3, ?4)",
+            )?
+            .execute(params![group_id, epoch, leaf_index, key_pairs])?;
+        Ok(())
+    }
+
+    fn write_key_package<
+        HashReference: traits::HashReference<CURRENT_VERSION>,
+        KeyPackage: traits::KeyPackage<CURRENT_VERSION>,
+    >(
+        &self,
+        hash_ref: &HashReference,
+        key_package: &KeyPackage,
+    ) -> Result<(), Self::Error> {
+        self.write(&KEY_PACKAGES, hash_ref, key_package)
+    }
+
+    fn write_psk<
+        PskId: traits::PskId<CURRENT_VERSION>,
+        PskBundle: traits::PskBundle<CURRENT_VERSION>,
+    >(
+        &self,
+        psk_id: &PskId,
+        psk: &PskBundle,
+    ) -> Result<(), Self::Error> {
+        self.write(&PSKS, psk_id, psk)
+    }
+
+    fn mls_group_join_config<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        MlsGroupJoinConfig: traits::MlsGroupJoinConfig<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<MlsGroupJoinConfig>, Self::Error> {
+        self.read(&JOIN_CONFIGS, group_id)
+    }
+
+    fn own_leaf_nodes<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        LeafNode: traits::LeafNode<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<LeafNode>, Self::Error> {
+        let group_id = serde_json::to_vec(group_id)?;
+        let connection = self.connection();
+        let mut statement = connection.prepare_cached(
+            "SELECT leaf_node FROM openmls_own_leaf_nodes WHERE group_id = ?1 ORDER BY id",
+        )?;
+        let leaf_nodes = statement.query_map(params![group_id], |row| row.get::<_, Vec<u8>>(0))?;
+        leaf_nodes
+            .map(|leaf_node| Ok(serde_json::from_slice(&leaf_node?)?))
+            .collect()
+    }
+
+    fn queued_proposal_refs<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<ProposalRef>, Self::Error> {
+        let group_id = serde_json::to_vec(group_id)?;
+        let connection = self.connection();
+        let mut statement = connection.prepare_cached(
+            "SELECT proposal_ref FROM openmls_proposals WHERE group_id = ?1 ORDER BY id",
+        )?;
+        let proposal_refs =
+            statement.query_map(params![group_id], |row| row.get::<_, Vec<u8>>(0))?;
+        proposal_refs
+            .map(|proposal_ref| Ok(serde_json::from_slice(&proposal_ref?)?))
+            .collect()
+    }
+
+    fn queued_proposals<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
+        QueuedProposal: traits::QueuedProposal<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<(ProposalRef, QueuedProposal)>, Self::Error> {
+        let group_id = serde_json::to_vec(group_id)?;
+        let connection = self.connection();
+        let mut statement = connection.prepare_cached(
+            "SELECT proposal_ref, proposal FROM openmls_proposals
+            WHERE group_id = ?1 ORDER BY id",
+        )?;
+        let proposals = statement.query_map(params![group_id], |row| {
+            Ok((row.get::<_, Vec<u8>>(0)?, row.get::<_, Vec<u8>>(1)?))
+        })?;
+        proposals
+            .map(|proposal| {
+                let (proposal_ref, proposal) = proposal?;
+                Ok((
+                    serde_json::from_slice(&proposal_ref)?,
+                    serde_json::from_slice(&proposal)?,
+                ))
+            })
+            .collect()
+    }
+
+    fn tree<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        TreeSync: traits::TreeSync<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<TreeSync>, Self::Error> {
+        self.read(&TREES, group_id)
+    }
+
+    fn tree_nodes<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        TreeNode: traits::TreeNode<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<(u32, TreeNode)>, Self::Error> {
+        let group_id = serde_json::to_vec(group_id)?;
+        let connection = self.connection();
+        let mut statement = connection.prepare_cached(
+            "SELECT node_index, node FROM openmls_tree_nodes WHERE group_id = ?1",
+        )?;
+        let nodes = statement.query_map(params![group_id], |row| {
+            Ok((row.get::<_, u32>(0)?, row.get::<_, Vec<u8>>(1)?))
+        })?;
+        nodes
+            .map(|node| {
+                let (node_index, node) = node?;
+                Ok((node_index, serde_json::from_slice(&node)?))
+            })
+            .collect()
+    }
+
+    fn group_context<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        GroupContext: traits::GroupContext<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<GroupContext>, Self::Error> {
+        self.read(&GROUP_CONTEXTS, group_id)
+    }
+
+    fn interim_transcript_hash<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        InterimTranscriptHash: traits::InterimTranscriptHash<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<InterimTranscriptHash>, Self::Error> {
+        self.read(&INTERIM_TRANSCRIPT_HASHES, group_id)
+    }
+
+    fn confirmation_tag<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ConfirmationTag: traits::ConfirmationTag<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<ConfirmationTag>, Self::Error> {
+        self.read(&CONFIRMATION_TAGS, group_id)
+    }
+
+    fn group_state<
+        GroupState: traits::GroupState<CURRENT_VERSION>,
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<GroupState>, Self::Error> {
+        self.read(&GROUP_STATES, group_id)
+    }
+
+    fn message_secrets<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        MessageSecrets: traits::MessageSecrets<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<MessageSecrets>, Self::Error> {
+        self.read(&MESSAGE_SECRETS, group_id)
+    }
+
+    fn resumption_psk_store<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ResumptionPskStore: traits::ResumptionPskStore<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<ResumptionPskStore>, Self::Error> {
+        self.read(&RESUMPTION_PSK_STORES, group_id)
+    }
+
+    fn own_leaf_index<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        LeafNodeIndex: traits::LeafNodeIndex<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<LeafNodeIndex>, Self::Error> {
+        self.read(&OWN_LEAF_INDICES, group_id)
+    }
+
+    fn group_epoch_secrets<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        GroupEpochSecrets: traits::GroupEpochSecrets<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<GroupEpochSecrets>, Self::Error> {
+        self.read(&GROUP_EPOCH_SECRETS, group_id)
+    }
+
+    fn signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<CURRENT_VERSION>,
+        SignatureKeyPair: traits::SignatureKeyPair<CURRENT_VERSION>,
+    >(
+        &self,
+        public_key: &SignaturePublicKey,
+    ) -> Result<Option<SignatureKeyPair>, Self::Error> {
+        self.read(&SIGNATURE_KEY_PAIRS, public_key)
+    }
+
+    fn encryption_key_pair<
+        HpkeKeyPair: traits::HpkeKeyPair<CURRENT_VERSION>,
+        EncryptionKey: traits::EncryptionKey<CURRENT_VERSION>,
+    >(
+        &self,
+        public_key: &EncryptionKey,
+    ) -> Result<Option<HpkeKeyPair>, Self::Error> {
+        self.read(&ENCRYPTION_KEY_PAIRS, public_key)
+    }
+
+    fn encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        EpochKey: traits::EpochKey<CURRENT_VERSION>,
+        HpkeKeyPair: traits::HpkeKeyPair<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+    ) -> Result<Vec<HpkeKeyPair>, Self::Error> {
+        let group_id = serde_json::to_vec(group_id)?;
+        let epoch = serde_json::to_vec(epoch)?;
+        let key_pairs: Option<Vec<u8>> = self
+            .connection()
+            .prepare_cached(
+                "SELECT key_pairs FROM openmls_epoch_key_pairs
+                WHERE group_id = ?1 AND epoch_id = ?2 AND leaf_index = ?3",
+            )?
+            .query_row(params![group_id, epoch, leaf_index], |row| row.get(0))
+            .optional()?;
+        match key_pairs {
+            Some(key_pairs) => Ok(serde_json::from_slice(&key_pairs)?),
+            None => Ok(vec![]),
+        }
+    }
+
+    fn key_package<
+        KeyPackageRef: traits::HashReference<CURRENT_VERSION>,
+        KeyPackage: traits::KeyPackage<CURRENT_VERSION>,
+    >(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> Result<Option<KeyPackage>, Self::Error> {
+        self.read(&KEY_PACKAGES, hash_ref)
+    }
+
+    fn psk<PskBundle: traits::PskBundle<CURRENT_VERSION>, PskId: traits::PskId<CURRENT_VERSION>>(
+        &self,
+        psk_id: &PskId,
+    ) -> Result<Option<PskBundle>, Self::Error> {
+        self.read(&PSKS, psk_id)
+    }
+
+    fn group_ids<GroupId: traits::GroupId<CURRENT_VERSION> + DeserializeOwned>(
+        &self,
+    ) -> Result<Vec<GroupId>, Self::Error> {
+        self.keys(&GROUP_STATES)
+    }
+
+    fn encryption_epoch_key_pair_ids<
+        GroupId: traits::GroupId<CURRENT_VERSION> + DeserializeOwned,
+        EpochKey: traits::EpochKey<CURRENT_VERSION> + DeserializeOwned,
+    >(
+        &self,
+    ) -> Result<Vec<(GroupId, EpochKey, u32)>, Self::Error> {
+        let connection = self.connection();
+        let mut statement = connection
+            .prepare_cached("SELECT group_id, epoch_id, leaf_index FROM openmls_epoch_key_pairs")?;
+        let ids = statement
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, Vec<u8>>(0)?,
+                    row.get::<_, Vec<u8>>(1)?,
+                    row.get::<_, u32>(2)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        ids.iter()
+            .map(|(group_id, epoch, leaf_index)| {
+                Ok((
+                    serde_json::from_slice(group_id)?,
+                    serde_json::from_slice(epoch)?,
+                    *leaf_index,
+                ))
+            })
+            .collect()
+    }
+
+    fn key_package_refs<
+        KeyPackageRef: traits::HashReference<CURRENT_VERSION> + DeserializeOwned,
+    >(
+        &self,
+    ) -> Result<Vec<KeyPackageRef>, Self::Error> {
+        self.keys(&KEY_PACKAGES)
+    }
+
+    fn psk_ids<PskId: traits::PskId<CURRENT_VERSION> + DeserializeOwned>(
+        &self,
+    ) -> Result<Vec<PskId>, Self::Error> {
+        self.keys(&PSKS)
+    }
+
+    fn remove_proposal<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        proposal_ref: &ProposalRef,
+    ) -> Result<(), Self::Error> {
+        let group_id = serde_json::to_vec(group_id)?;
+        let proposal_ref = serde_json::to_vec(proposal_ref)?;
+        self.connection()
+            .prepare_cached(
+                "DELETE FROM openmls_proposals WHERE group_id = ?1 AND proposal_ref = ?2",
+            )?
+            .execute(params![group_id, proposal_ref])?;
+        Ok(())
+    }
+
+    fn delete_own_leaf_nodes<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        let group_id = serde_json::to_vec(group_id)?;
+        self.connection()
+            .prepare_cached("DELETE FROM openmls_own_leaf_nodes WHERE group_id = ?1")?
+            .execute(params![group_id])?;
+        Ok(())
+    }
+
+    fn delete_group_config<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(&JOIN_CONFIGS, group_id)
+    }
+
+    fn delete_tree<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(&TREES, group_id)
+    }
+
+    fn delete_tree_node<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+        node_index: u32,
+    ) -> Result<(), Self::Error> {
+        let group_id = serde_json::to_vec(group_id)?;
+        self.connection()
+            .prepare_cached(
+                "DELETE FROM openmls_tree_nodes WHERE group_id = ?1 AND node_index = ?2",
+            )?
+            .execute(params![group_id, node_index])?;
+        Ok(())
+    }
+
+    fn delete_tree_nodes<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        let group_id = serde_json::to_vec(group_id)?;
+        self.connection()
+            .prepare_cached("DELETE FROM openmls_tree_nodes WHERE group_id = ?1")?
+            .execute(params![group_id])?;
+        Ok(())
+    }
+
+    fn delete_confirmation_tag<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(&CONFIRMATION_TAGS, group_id)
+    }
+
+    fn delete_group_state<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(&GROUP_STATES, group_id)
+    }
+
+    fn delete_context<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(&GROUP_CONTEXTS, group_id)
+    }
+
+    fn delete_interim_transcript_hash<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(&INTERIM_TRANSCRIPT_HASHES, group_id)
+    }
+
+    fn delete_message_secrets<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(&MESSAGE_SECRETS, group_id)
+    }
+
+    fn delete_all_resumption_psk_secrets<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(&RESUMPTION_PSK_STORES, group_id)
+    }
+
+    fn delete_own_leaf_index<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(&OWN_LEAF_INDICES, group_id)
+    }
+
+    fn delete_group_epoch_secrets<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(&GROUP_EPOCH_SECRETS, group_id)
+    }
+
+    fn clear_proposal_queue<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        let group_id = serde_json::to_vec(group_id)?;
+        self.connection()
+            .prepare_cached("DELETE FROM openmls_proposals WHERE group_id = ?1")?
+            .execute(params![group_id])?;
+        Ok(())
+    }
+
+    fn delete_signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<CURRENT_VERSION>,
+    >(
+        &self,
+        public_key: &SignaturePublicKey,
+    ) -> Result<(), Self::Error> {
+        self.delete(&SIGNATURE_KEY_PAIRS, public_key)
+    }
+
+    fn delete_encryption_key_pair<EncryptionKey: traits::EncryptionKey<CURRENT_VERSION>>(
+        &self,
+        public_key: &EncryptionKey,
+    ) -> Result<(), Self::Error> {
+        self.delete(&ENCRYPTION_KEY_PAIRS, public_key)
+    }
+
+    fn delete_encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        EpochKey: traits::EpochKey<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+    ) -> Result<(), Self::Error> {
+        let group_id = serde_json::to_vec(group_id)?;
+        let epoch = serde_json::to_vec(epoch)?;
+        self.connection()
+            .prepare_cached(
+                "DELETE FROM openmls_epoch_key_pairs
+                WHERE group_id = ?1 AND epoch_id = ?2 AND leaf_index = ?3",
+            )?
+            .execute(params![group_id, epoch, leaf_index])?;
+        Ok(())
+    }
+
+    fn delete_key_package<KeyPackageRef: traits::HashReference<CURRENT_VERSION>>(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> Result<(), Self::Error> {
+        self.delete(&KEY_PACKAGES, hash_ref)
+    }
+
+    fn delete_psk<PskKey: traits::PskId<CURRENT_VERSION>>(
+        &self,
+        psk_id: &PskKey,
+    ) -> Result<(), Self::Error> {
+        self.delete(&PSKS, psk_id)
+    }
+}
@@ -0,0 +1,125 @@
+//! Schema migrations of the SQLite storage.
+//!
+//! The schema version of a database is stored in its `user_version` pragma.
+//! Every entry in [`MIGRATIONS`] upgrades the schema by one version. New
+//! migrations must only ever be appended.
+
+use rusqlite::Connection;
+
+use crate::SqliteStorageError;
+
+/// The migrations, where the migration at index `i` upgrades the schema from
+/// version `i` to version `i + 1`.
+const MIGRATIONS: &[fn(&Connection) -> rusqlite::Result<()>] = &[initial_schema, tree_nodes];
+
+/// The schema version this crate works with.
+pub const SCHEMA_VERSION: u32 = MIGRATIONS.len() as u32;
+
+fn initial_schema(connection: &Connection) -> rusqlite::Result<()> {
+    // Tables that store a single value per group.
+    for table in [
+        "openmls_trees",
+        "openmls_group_contexts",
+        "openmls_interim_transcript_hashes",
+        "openmls_confirmation_tags",
+        "openmls_join_configs",
+        "openmls_group_states",
+        "openmls_message_secrets",
+        "openmls_resumption_psk_stores",
+        "openmls_own_leaf_indices",
+        "openmls_group_epoch_secrets",
+    ] {
+        connection.execute_batch(&format!(
+            "CREATE TABLE {table} (
+                group_id BLOB PRIMARY KEY,
+                value BLOB NOT NULL
+            );"
+        ))?;
+    }
+
+    connection.execute_batch(
+        "CREATE TABLE openmls_proposals (
+            id INTEGER PRIMARY KEY,
+            group_id BLOB NOT NULL,
+            proposal_ref BLOB NOT NULL,
+            proposal BLOB NOT NULL,
+            UNIQUE (group_id, proposal_ref)
+        );
+
+        CREATE TABLE openmls_own_leaf_nodes (
+            id INTEGER PRIMARY KEY,
+            group_id BLOB NOT NULL,
+            leaf_node BLOB NOT NULL
+        );
+        CREATE INDEX openmls_own_leaf_nodes_group_id ON openmls_own_leaf_nodes (group_id);
+
+        CREATE TABLE openmls_epoch_key_pairs (
+            group_id BLOB NOT NULL,
+            epoch_id BLOB NOT NULL,
+            leaf_index INTEGER NOT NULL,
+            key_pairs BLOB NOT NULL,
+            PRIMARY KEY (group_id, epoch_id, leaf_index)
+        );
+
+        CREATE TABLE openmls_signature_key_pairs (
+            public_key BLOB PRIMARY KEY,
+            key_pair BLOB NOT NULL
+        );
+
+        CREATE TABLE openmls_encryption_key_pairs (
+            public_key BLOB PRIMARY KEY,
+            key_pair BLOB NOT NULL
+        );
+
+        CREATE TABLE openmls_key_packages (
+            key_package_ref BLOB PRIMARY KEY,
+            key_package BLOB NOT NULL
+        );
+
+        CREATE TABLE openmls_psks (
+            psk_id BLOB PRIMARY KEY,
+            psk_bundle BLOB NOT NULL
+        );",
+    )
+}
+
+/// Adds the table that stores the nodes of trees individually.
+fn tree_nodes(connection: &Connection) -> rusqlite::Result<()> {
+    connection.execute_batch(
+        "CREATE TABLE openmls_tree_nodes (
+            group_id BLOB NOT NULL,
+            node_index INTEGER NOT NULL,
+            node BLOB NOT NULL,
+            PRIMARY KEY (group_id, node_index)
+        );",
+    )
+}
+
+/// Returns the schema version of the database.
+pub(crate) fn schema_version(connection: &Connection) -> rusqlite::Result<u32> {
+    connection.query_row("PRAGMA user_version", [], |row| row.get(0))
+}
+
+/// Upgrade the schema of the database to [`SCHEMA_VERSION`].
+///
+/// All pending migrations are applied in a single transaction. Returns an
+/// error if the database has a newer schema than this crate supports.
+pub(crate) fn migrate(connection: &mut Connection) -> Result<(), SqliteStorageError> {
+    let transaction = connection.transaction()?;
+    let version = schema_version(&transaction)?;
+    if version > SCHEMA_VERSION {
+        return Err(SqliteStorageError::UnsupportedSchemaVersion(version));
+    }
+
+    for (from_version, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        log::debug!(
+            "Migrating storage schema from version {from_version} to {}",
+            from_version + 1
+        );
+        migration(&transaction)?;
+    }
+    transaction.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+
+    transaction.commit()?;
+    Ok(())
+}
@@ -0,0 +1,74 @@
+//! Runs the group operations of the storage stability KAT with a SQLite
+//! storage and checks after every step that the group loaded from a reopened
+//! database matches the group in memory.
+
+use std::path::Path;
+
+use openmls::{prelude::*, test_utils::storage_stability::run_storage_stability};
+use openmls_rust_crypto::RustCrypto;
+use openmls_sqlite_storage::SqliteStorage;
+use openmls_traits::OpenMlsProvider;
+
+struct SqliteProvider {
+    crypto: RustCrypto,
+    storage: SqliteStorage,
+}
+
+impl SqliteProvider {
+    fn open(path: &Path) -> Self {
+        Self {
+            crypto: RustCrypto::default(),
+            storage: SqliteStorage::open(path).unwrap(),
+        }
+    }
+}
+
+impl OpenMlsProvider for SqliteProvider {
+    type CryptoProvider = RustCrypto;
+    type RandProvider = RustCrypto;
+    type StorageProvider = SqliteStorage;
+
+    fn storage(&self) -> &Self::StorageProvider {
+        &self.storage
+    }
+
+    fn crypto(&self) -> &Self::CryptoProvider {
+        &self.crypto
+    }
+
+    fn rand(&self) -> &Self::RandProvider {
+        &self.crypto
+    }
+}
+
+fn check_stored_group_equality(path: &Path, group: &MlsGroup) {
+    let provider = SqliteProvider::open(path);
+    let loaded_group = MlsGroup::load(provider.storage(), group.group_id())
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(group, &loaded_group);
+}
+
+#[test]
+fn storage_stability() {
+    let ciphersuite = Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519;
+    let dir = tempfile::tempdir().unwrap();
+    let alice_path = dir.path().join("alice.db");
+
+    let mut alice_group = run_storage_stability(
+        ciphersuite,
+        &SqliteProvider::open(&alice_path),
+        &SqliteProvider::open(&dir.path().join("bob.db")),
+        &SqliteProvider::open(&dir.path().join("charlie.db")),
+        |group| check_stored_group_equality(&alice_path, group),
+    );
+
+    // Deleting the group removes it from the database.
+    let provider = SqliteProvider::open(&alice_path);
+    alice_group.delete(provider.storage()).unwrap();
+    let provider = SqliteProvider::open(&alice_path);
+    assert!(MlsGroup::load(provider.storage(), alice_group.group_id())
+        .unwrap()
+        .is_none());
+}
@@ -0,0 +1,96 @@
+use openmls_memory_storage::test_suite;
+use openmls_sqlite_storage::{SqliteStorage, SqliteStorageError, SCHEMA_VERSION};
+use openmls_traits::storage::{
+    traits::{self},
+    Entity, Key, StorageProvider, CURRENT_VERSION,
+};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+// Test types
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+struct TestGroupId(Vec<u8>);
+impl traits::GroupId<CURRENT_VERSION> for TestGroupId {}
+impl Key<CURRENT_VERSION> for TestGroupId {}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+struct TestTree(Vec<u8>);
+impl traits::TreeSync<CURRENT_VERSION> for TestTree {}
+impl Entity<CURRENT_VERSION> for TestTree {}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+struct LeafNode(Vec<u8>);
+impl traits::LeafNode<CURRENT_VERSION> for LeafNode {}
+impl Entity<CURRENT_VERSION> for LeafNode {}
+
+/// The storage passes the storage test suite of `openmls_memory_storage`.
+#[test]
+fn storage_provider() {
+    test_suite::run(|| SqliteStorage::open_in_memory().unwrap());
+}
+
+/// Values survive closing and reopening the database.
+#[test]
+fn persistence() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("openmls.db");
+    let group_id = TestGroupId(b"TestGroupId".to_vec());
+    let tree = TestTree(b"TestTree".to_vec());
+
+    let storage = SqliteStorage::open(&path).unwrap();
+    assert_eq!(storage.schema_version().unwrap(), SCHEMA_VERSION);
+    storage.write_tree(&group_id, &tree).unwrap();
+    drop(storage);
+
+    let storage = SqliteStorage::open(&path).unwrap();
+    let tree_read: Option<TestTree> = storage.tree(&group_id).unwrap();
+    assert_eq!(Some(tree), tree_read);
+}
+
+/// Committed transactions are persisted, rolled back ones are not.
+#[test]
+fn transactions() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("openmls.db");
+    let group_id = TestGroupId(b"TestGroupId".to_vec());
+    let tree = TestTree(b"TestTree".to_vec());
+    let leaf_node = LeafNode(b"LeafNode".to_vec());
+
+    let storage = SqliteStorage::open(&path).unwrap();
+    storage.begin_transaction().unwrap();
+    storage.write_tree(&group_id, &tree).unwrap();
+    storage.commit_transaction().unwrap();
+
+    // A rolled back transaction discards its writes, including those of a
+    // committed nested transaction.
+    storage.begin_transaction().unwrap();
+    storage.delete_tree(&group_id).unwrap();
+    storage.begin_transaction().unwrap();
+    storage.append_own_leaf_node(&group_id, &leaf_node).unwrap();
+    storage.commit_transaction().unwrap();
+    storage.rollback_transaction().unwrap();
+
+    let storage = SqliteStorage::open(&path).unwrap();
+    let tree_read: Option<TestTree> = storage.tree(&group_id).unwrap();
+    assert_eq!(Some(tree), tree_read);
+    let leaf_nodes_read: Vec<LeafNode> = storage.own_leaf_nodes(&group_id).unwrap();
+    assert!(leaf_nodes_read.is_empty());
+}
+
+/// A database with a newer schema is rejected.
+#[test]
+fn newer_schema_version() {
+    let connection = Connection::open_in_memory().unwrap();
+    connection
+        .pragma_update(None, "user_version", SCHEMA_VERSION + 1)
+        .unwrap();
+    assert!(matches!(
+        SqliteStorage::new(connection),
+        Err(SqliteStorageError::UnsupportedSchemaVersion(version)) if version == SCHEMA_VERSION + 1
+    ));
+
+    // Migrating an up-to-date database is a no-op.
+    let connection = SqliteStorage::open_in_memory().unwrap().into_connection();
+    let storage = SqliteStorage::new(connection).unwrap();
+    assert_eq!(storage.schema_version().unwrap(), SCHEMA_VERSION);
+}
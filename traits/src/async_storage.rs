@@ -0,0 +1,1266 @@
+//! This module describes the asynchronous variant of the storage provider.
+//!
+//! The [`AsyncStorageProvider`] has the same methods as the
+//! [`StorageProvider`], but all of them return futures, so that a storage
+//! that talks to a database over the network doesn't block the thread it
+//! runs on. Every [`StorageProvider`] that is [`Sync`] is also an
+//! [`AsyncStorageProvider`] whose futures complete immediately.
+
+use std::future::Future;
+
+use serde::de::DeserializeOwned;
+
+use crate::storage::{traits, StorageProvider};
+
+/// AsyncStorageProvider describes an asynchronous storage backing OpenMLS.
+///
+/// The semantics of all methods are the same as those of the respective
+/// methods of [`StorageProvider`]. The returned futures must be [`Send`], so
+/// that they can be run on a multi-threaded executor.
+pub trait AsyncStorageProvider<const VERSION: u16>: Sync {
+    /// An opaque error returned by all methods on this trait.
+    type Error: core::fmt::Debug + std::error::Error;
+
+    /// Get the version of this provider.
+    fn version() -> u16 {
+        VERSION
+    }
+
+    //
+    //    ---   transactions   ---
+    //
+
+    /// See [`StorageProvider::begin_transaction`].
+    fn begin_transaction(&self) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        async { Ok(()) }
+    }
+
+    /// See [`StorageProvider::commit_transaction`].
+    fn commit_transaction(&self) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        async { Ok(()) }
+    }
+
+    /// See [`StorageProvider::rollback_transaction`].
+    fn rollback_transaction(&self) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        async { Ok(()) }
+    }
+
+    //
+    //    ---   setters/writers/enqueuers for group state  ---
+    //
+    /// See [`StorageProvider::write_mls_join_config`].
+    fn write_mls_join_config<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        MlsGroupJoinConfig: traits::MlsGroupJoinConfig<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        config: &MlsGroupJoinConfig,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::append_own_leaf_node`].
+    fn append_own_leaf_node<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        LeafNode: traits::LeafNode<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        leaf_node: &LeafNode,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::queue_proposal`].
+    fn queue_proposal<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        ProposalRef: traits::ProposalRef<VERSION> + Sync,
+        QueuedProposal: traits::QueuedProposal<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        proposal_ref: &ProposalRef,
+        proposal: &QueuedProposal,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_tree`].
+    fn write_tree<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        TreeSync: traits::TreeSync<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        tree: &TreeSync,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_tree_node`].
+    fn write_tree_node<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        TreeNode: traits::TreeNode<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        node_index: u32,
+        node: &TreeNode,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_interim_transcript_hash`].
+    fn write_interim_transcript_hash<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        InterimTranscriptHash: traits::InterimTranscriptHash<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        interim_transcript_hash: &InterimTranscriptHash,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_context`].
+    fn write_context<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        GroupContext: traits::GroupContext<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_context: &GroupContext,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_confirmation_tag`].
+    fn write_confirmation_tag<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        ConfirmationTag: traits::ConfirmationTag<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        confirmation_tag: &ConfirmationTag,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_group_state`].
+    fn write_group_state<
+        GroupState: traits::GroupState<VERSION> + Sync,
+        GroupId: traits::GroupId<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_state: &GroupState,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_message_secrets`].
+    fn write_message_secrets<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        MessageSecrets: traits::MessageSecrets<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        message_secrets: &MessageSecrets,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_resumption_psk_store`].
+    fn write_resumption_psk_store<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        ResumptionPskStore: traits::ResumptionPskStore<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        resumption_psk_store: &ResumptionPskStore,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_own_leaf_index`].
+    fn write_own_leaf_index<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        LeafNodeIndex: traits::LeafNodeIndex<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        own_leaf_index: &LeafNodeIndex,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_group_epoch_secrets`].
+    fn write_group_epoch_secrets<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        GroupEpochSecrets: traits::GroupEpochSecrets<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_epoch_secrets: &GroupEpochSecrets,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    //
+    //    ---   setters/writers/enqueuers for crypto objects  ---
+    //
+    /// See [`StorageProvider::write_signature_key_pair`].
+    fn write_signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<VERSION> + Sync,
+        SignatureKeyPair: traits::SignatureKeyPair<VERSION> + Sync,
+    >(
+        &self,
+        public_key: &SignaturePublicKey,
+        signature_key_pair: &SignatureKeyPair,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_encryption_key_pair`].
+    fn write_encryption_key_pair<
+        EncryptionKey: traits::EncryptionKey<VERSION> + Sync,
+        HpkeKeyPair: traits::HpkeKeyPair<VERSION> + Sync,
+    >(
+        &self,
+        public_key: &EncryptionKey,
+        key_pair: &HpkeKeyPair,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_encryption_epoch_key_pairs`].
+    fn write_encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        EpochKey: traits::EpochKey<VERSION> + Sync,
+        HpkeKeyPair: traits::HpkeKeyPair<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+        key_pairs: &[HpkeKeyPair],
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_key_package`].
+    fn write_key_package<
+        HashReference: traits::HashReference<VERSION> + Sync,
+        KeyPackage: traits::KeyPackage<VERSION> + Sync,
+    >(
+        &self,
+        hash_ref: &HashReference,
+        key_package: &KeyPackage,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::write_psk`].
+    fn write_psk<
+        PskId: traits::PskId<VERSION> + Sync,
+        PskBundle: traits::PskBundle<VERSION> + Sync,
+    >(
+        &self,
+        psk_id: &PskId,
+        psk: &PskBundle,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    //
+    //    ---   getters for group state  ---
+    //
+    /// See [`StorageProvider::mls_group_join_config`].
+    fn mls_group_join_config<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        MlsGroupJoinConfig: traits::MlsGroupJoinConfig<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<MlsGroupJoinConfig>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::own_leaf_nodes`].
+    fn own_leaf_nodes<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        LeafNode: traits::LeafNode<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Vec<LeafNode>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::queued_proposal_refs`].
+    fn queued_proposal_refs<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        ProposalRef: traits::ProposalRef<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Vec<ProposalRef>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::queued_proposals`].
+    fn queued_proposals<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        ProposalRef: traits::ProposalRef<VERSION> + Sync,
+        QueuedProposal: traits::QueuedProposal<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Vec<(ProposalRef, QueuedProposal)>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::tree`].
+    fn tree<GroupId: traits::GroupId<VERSION> + Sync, TreeSync: traits::TreeSync<VERSION> + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<TreeSync>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::tree_nodes`].
+    fn tree_nodes<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        TreeNode: traits::TreeNode<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Vec<(u32, TreeNode)>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::group_context`].
+    fn group_context<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        GroupContext: traits::GroupContext<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<GroupContext>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::interim_transcript_hash`].
+    fn interim_transcript_hash<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        InterimTranscriptHash: traits::InterimTranscriptHash<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<InterimTranscriptHash>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::confirmation_tag`].
+    fn confirmation_tag<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        ConfirmationTag: traits::ConfirmationTag<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<ConfirmationTag>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::group_state`].
+    fn group_state<
+        GroupState: traits::GroupState<VERSION> + Sync,
+        GroupId: traits::GroupId<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<GroupState>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::message_secrets`].
+    fn message_secrets<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        MessageSecrets: traits::MessageSecrets<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<MessageSecrets>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::resumption_psk_store`].
+    fn resumption_psk_store<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        ResumptionPskStore: traits::ResumptionPskStore<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<ResumptionPskStore>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::own_leaf_index`].
+    fn own_leaf_index<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        LeafNodeIndex: traits::LeafNodeIndex<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<LeafNodeIndex>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::group_epoch_secrets`].
+    fn group_epoch_secrets<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        GroupEpochSecrets: traits::GroupEpochSecrets<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<Option<GroupEpochSecrets>, Self::Error>> + Send;
+
+    //
+    //    ---   getter for crypto objects  ---
+    //
+    /// See [`StorageProvider::signature_key_pair`].
+    fn signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<VERSION> + Sync,
+        SignatureKeyPair: traits::SignatureKeyPair<VERSION> + Sync,
+    >(
+        &self,
+        public_key: &SignaturePublicKey,
+    ) -> impl Future<Output = Result<Option<SignatureKeyPair>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::encryption_key_pair`].
+    fn encryption_key_pair<
+        HpkeKeyPair: traits::HpkeKeyPair<VERSION> + Sync,
+        EncryptionKey: traits::EncryptionKey<VERSION> + Sync,
+    >(
+        &self,
+        public_key: &EncryptionKey,
+    ) -> impl Future<Output = Result<Option<HpkeKeyPair>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::encryption_epoch_key_pairs`].
+    fn encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        EpochKey: traits::EpochKey<VERSION> + Sync,
+        HpkeKeyPair: traits::HpkeKeyPair<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+    ) -> impl Future<Output = Result<Vec<HpkeKeyPair>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::key_package`].
+    fn key_package<
+        KeyPackageRef: traits::HashReference<VERSION> + Sync,
+        KeyPackage: traits::KeyPackage<VERSION> + Sync,
+    >(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> impl Future<Output = Result<Option<KeyPackage>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::psk`].
+    fn psk<PskBundle: traits::PskBundle<VERSION> + Sync, PskId: traits::PskId<VERSION> + Sync>(
+        &self,
+        psk_id: &PskId,
+    ) -> impl Future<Output = Result<Option<PskBundle>, Self::Error>> + Send;
+
+    //
+    //    ---   listing   ---
+    //
+    /// See [`StorageProvider::group_ids`].
+    fn group_ids<GroupId: traits::GroupId<VERSION> + Sync + DeserializeOwned>(
+        &self,
+    ) -> impl Future<Output = Result<Vec<GroupId>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::encryption_epoch_key_pair_ids`].
+    fn encryption_epoch_key_pair_ids<
+        GroupId: traits::GroupId<VERSION> + Sync + DeserializeOwned,
+        EpochKey: traits::EpochKey<VERSION> + Sync + DeserializeOwned,
+    >(
+        &self,
+    ) -> impl Future<Output = Result<Vec<(GroupId, EpochKey, u32)>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::key_package_refs`].
+    fn key_package_refs<KeyPackageRef: traits::HashReference<VERSION> + Sync + DeserializeOwned>(
+        &self,
+    ) -> impl Future<Output = Result<Vec<KeyPackageRef>, Self::Error>> + Send;
+
+    /// See [`StorageProvider::psk_ids`].
+    fn psk_ids<PskId: traits::PskId<VERSION> + Sync + DeserializeOwned>(
+        &self,
+    ) -> impl Future<Output = Result<Vec<PskId>, Self::Error>> + Send;
+
+    //
+    //     ---    deleters for group state    ---
+    //
+    /// See [`StorageProvider::remove_proposal`].
+    fn remove_proposal<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        ProposalRef: traits::ProposalRef<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        proposal_ref: &ProposalRef,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_own_leaf_nodes`].
+    fn delete_own_leaf_nodes<GroupId: traits::GroupId<VERSION> + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_group_config`].
+    fn delete_group_config<GroupId: traits::GroupId<VERSION> + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_tree`].
+    fn delete_tree<GroupId: traits::GroupId<VERSION> + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_tree_node`].
+    fn delete_tree_node<GroupId: traits::GroupId<VERSION> + Sync>(
+        &self,
+        group_id: &GroupId,
+        node_index: u32,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_tree_nodes`].
+    fn delete_tree_nodes<GroupId: traits::GroupId<VERSION> + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_confirmation_tag`].
+    fn delete_confirmation_tag<GroupId: traits::GroupId<VERSION> + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_group_state`].
+    fn delete_group_state<GroupId: traits::GroupId<VERSION> + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_context`].
+    fn delete_context<GroupId: traits::GroupId<VERSION> + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_interim_transcript_hash`].
+    fn delete_interim_transcript_hash<GroupId: traits::GroupId<VERSION> + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_message_secrets`].
+    fn delete_message_secrets<GroupId: traits::GroupId<VERSION> + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_all_resumption_psk_secrets`].
+    fn delete_all_resumption_psk_secrets<GroupId: traits::GroupId<VERSION> + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_own_leaf_index`].
+    fn delete_own_leaf_index<GroupId: traits::GroupId<VERSION> + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_group_epoch_secrets`].
+    fn delete_group_epoch_secrets<GroupId: traits::GroupId<VERSION> + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::clear_proposal_queue`].
+    fn clear_proposal_queue<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        ProposalRef: traits::ProposalRef<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    //
+    //    ---   deleters for crypto objects   ---
+    //
+    /// See [`StorageProvider::delete_signature_key_pair`].
+    fn delete_signature_key_pair<SignaturePublicKey: traits::SignaturePublicKey<VERSION> + Sync>(
+        &self,
+        public_key: &SignaturePublicKey,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_encryption_key_pair`].
+    fn delete_encryption_key_pair<EncryptionKey: traits::EncryptionKey<VERSION> + Sync>(
+        &self,
+        public_key: &EncryptionKey,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_encryption_epoch_key_pairs`].
+    fn delete_encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        EpochKey: traits::EpochKey<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_key_package`].
+    fn delete_key_package<KeyPackageRef: traits::HashReference<VERSION> + Sync>(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// See [`StorageProvider::delete_psk`].
+    fn delete_psk<PskKey: traits::PskId<VERSION> + Sync>(
+        &self,
+        psk_id: &PskKey,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+}
+
+impl<const VERSION: u16, S: StorageProvider<VERSION> + Sync> AsyncStorageProvider<VERSION> for S {
+    type Error = S::Error;
+
+    async fn begin_transaction(&self) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::begin_transaction(self)
+    }
+
+    async fn commit_transaction(&self) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::commit_transaction(self)
+    }
+
+    async fn rollback_transaction(&self) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::rollback_transaction(self)
+    }
+
+    async fn write_mls_join_config<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        MlsGroupJoinConfig: traits::MlsGroupJoinConfig<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        config: &MlsGroupJoinConfig,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::write_mls_join_config::<GroupId, MlsGroupJoinConfig>(
+            self, group_id, config,
+        )
+    }
+
+    async fn append_own_leaf_node<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        LeafNode: traits::LeafNode<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        leaf_node: &LeafNode,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::append_own_leaf_node::<GroupId, LeafNode>(
+            self, group_id, leaf_node,
+        )
+    }
+
+    async fn queue_proposal<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        ProposalRef: traits::ProposalRef<VERSION> + Sync,
+        QueuedProposal: traits::QueuedProposal<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        proposal_ref: &ProposalRef,
+        proposal: &QueuedProposal,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::queue_proposal::<GroupId, ProposalRef, QueuedProposal>(
+            self,
+            group_id,
+            proposal_ref,
+            proposal,
+        )
+    }
+
+    async fn write_tree<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        TreeSync: traits::TreeSync<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        tree: &TreeSync,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::write_tree::<GroupId, TreeSync>(self, group_id, tree)
+    }
+
+    async fn write_tree_node<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        TreeNode: traits::TreeNode<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        node_index: u32,
+        node: &TreeNode,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::write_tree_node::<GroupId, TreeNode>(
+            self, group_id, node_index, node,
+        )
+    }
+
+    async fn write_interim_transcript_hash<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        InterimTranscriptHash: traits::InterimTranscriptHash<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        interim_transcript_hash: &InterimTranscriptHash,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::write_interim_transcript_hash::<
+            GroupId,
+            InterimTranscriptHash,
+        >(self, group_id, interim_transcript_hash)
+    }
+
+    async fn write_context<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        GroupContext: traits::GroupContext<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_context: &GroupContext,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::write_context::<GroupId, GroupContext>(
+            self,
+            group_id,
+            group_context,
+        )
+    }
+
+    async fn write_confirmation_tag<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        ConfirmationTag: traits::ConfirmationTag<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        confirmation_tag: &ConfirmationTag,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::write_confirmation_tag::<GroupId, ConfirmationTag>(
+            self,
+            group_id,
+            confirmation_tag,
+        )
+    }
+
+    async fn write_group_state<
+        GroupState: traits::GroupState<VERSION> + Sync,
+        GroupId: traits::GroupId<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_state: &GroupState,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::write_group_state::<GroupState, GroupId>(
+            self,
+            group_id,
+            group_state,
+        )
+    }
+
+    async fn write_message_secrets<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        MessageSecrets: traits::MessageSecrets<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        message_secrets: &MessageSecrets,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::write_message_secrets::<GroupId, MessageSecrets>(
+            self,
+            group_id,
+            message_secrets,
+        )
+    }
+
+    async fn write_resumption_psk_store<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        ResumptionPskStore: traits::ResumptionPskStore<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        resumption_psk_store: &ResumptionPskStore,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::write_resumption_psk_store::<GroupId, ResumptionPskStore>(
+            self,
+            group_id,
+            resumption_psk_store,
+        )
+    }
+
+    async fn write_own_leaf_index<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        LeafNodeIndex: traits::LeafNodeIndex<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        own_leaf_index: &LeafNodeIndex,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::write_own_leaf_index::<GroupId, LeafNodeIndex>(
+            self,
+            group_id,
+            own_leaf_index,
+        )
+    }
+
+    async fn write_group_epoch_secrets<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        GroupEpochSecrets: traits::GroupEpochSecrets<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_epoch_secrets: &GroupEpochSecrets,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::write_group_epoch_secrets::<GroupId, GroupEpochSecrets>(
+            self,
+            group_id,
+            group_epoch_secrets,
+        )
+    }
+
+    async fn write_signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<VERSION> + Sync,
+        SignatureKeyPair: traits::SignatureKeyPair<VERSION> + Sync,
+    >(
+        &self,
+        public_key: &SignaturePublicKey,
+        signature_key_pair: &SignatureKeyPair,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::write_signature_key_pair::<
+            SignaturePublicKey,
+            SignatureKeyPair,
+        >(self, public_key, signature_key_pair)
+    }
+
+    async fn write_encryption_key_pair<
+        EncryptionKey: traits::EncryptionKey<VERSION> + Sync,
+        HpkeKeyPair: traits::HpkeKeyPair<VERSION> + Sync,
+    >(
+        &self,
+        public_key: &EncryptionKey,
+        key_pair: &HpkeKeyPair,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::write_encryption_key_pair::<EncryptionKey, HpkeKeyPair>(
+            self, public_key, key_pair,
+        )
+    }
+
+    async fn write_encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        EpochKey: traits::EpochKey<VERSION> + Sync,
+        HpkeKeyPair: traits::HpkeKeyPair<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+        key_pairs: &[HpkeKeyPair],
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::write_encryption_epoch_key_pairs::<
+            GroupId,
+            EpochKey,
+            HpkeKeyPair,
+        >(self, group_id, epoch, leaf_index, key_pairs)
+    }
+
+    async fn write_key_package<
+        HashReference: traits::HashReference<VERSION> + Sync,
+        KeyPackage: traits::KeyPackage<VERSION> + Sync,
+    >(
+        &self,
+        hash_ref: &HashReference,
+        key_package: &KeyPackage,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::write_key_package::<HashReference, KeyPackage>(
+            self,
+            hash_ref,
+            key_package,
+        )
+    }
+
+    async fn write_psk<
+        PskId: traits::PskId<VERSION> + Sync,
+        PskBundle: traits::PskBundle<VERSION> + Sync,
+    >(
+        &self,
+        psk_id: &PskId,
+        psk: &PskBundle,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::write_psk::<PskId, PskBundle>(self, psk_id, psk)
+    }
+
+    async fn mls_group_join_config<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        MlsGroupJoinConfig: traits::MlsGroupJoinConfig<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<MlsGroupJoinConfig>, Self::Error> {
+        <S as StorageProvider<VERSION>>::mls_group_join_config::<GroupId, MlsGroupJoinConfig>(
+            self, group_id,
+        )
+    }
+
+    async fn own_leaf_nodes<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        LeafNode: traits::LeafNode<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<LeafNode>, Self::Error> {
+        <S as StorageProvider<VERSION>>::own_leaf_nodes::<GroupId, LeafNode>(self, group_id)
+    }
+
+    async fn queued_proposal_refs<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        ProposalRef: traits::ProposalRef<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<ProposalRef>, Self::Error> {
+        <S as StorageProvider<VERSION>>::queued_proposal_refs::<GroupId, ProposalRef>(
+            self, group_id,
+        )
+    }
+
+    async fn queued_proposals<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        ProposalRef: traits::ProposalRef<VERSION> + Sync,
+        QueuedProposal: traits::QueuedProposal<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<(ProposalRef, QueuedProposal)>, Self::Error> {
+        <S as StorageProvider<VERSION>>::queued_proposals::<GroupId, ProposalRef, QueuedProposal>(
+            self, group_id,
+        )
+    }
+
+    async fn tree<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        TreeSync: traits::TreeSync<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<TreeSync>, Self::Error> {
+        <S as StorageProvider<VERSION>>::tree::<GroupId, TreeSync>(self, group_id)
+    }
+
+    async fn tree_nodes<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        TreeNode: traits::TreeNode<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<(u32, TreeNode)>, Self::Error> {
+        <S as StorageProvider<VERSION>>::tree_nodes::<GroupId, TreeNode>(self, group_id)
+    }
+
+    async fn group_context<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        GroupContext: traits::GroupContext<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<GroupContext>, Self::Error> {
+        <S as StorageProvider<VERSION>>::group_context::<GroupId, GroupContext>(self, group_id)
+    }
+
+    async fn interim_transcript_hash<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        InterimTranscriptHash: traits::InterimTranscriptHash<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<InterimTranscriptHash>, Self::Error> {
+        <S as StorageProvider<VERSION>>::interim_transcript_hash::<GroupId, InterimTranscriptHash>(
+            self, group_id,
+        )
+    }
+
+    async fn confirmation_tag<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        ConfirmationTag: traits::ConfirmationTag<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<ConfirmationTag>, Self::Error> {
+        <S as StorageProvider<VERSION>>::confirmation_tag::<GroupId, ConfirmationTag>(
+            self, group_id,
+        )
+    }
+
+    async fn group_state<
+        GroupState: traits::GroupState<VERSION> + Sync,
+        GroupId: traits::GroupId<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<GroupState>, Self::Error> {
+        <S as StorageProvider<VERSION>>::group_state::<GroupState, GroupId>(self, group_id)
+    }
+
+    async fn message_secrets<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        MessageSecrets: traits::MessageSecrets<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<MessageSecrets>, Self::Error> {
+        <S as StorageProvider<VERSION>>::message_secrets::<GroupId, MessageSecrets>(self, group_id)
+    }
+
+    async fn resumption_psk_store<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        ResumptionPskStore: traits::ResumptionPskStore<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<ResumptionPskStore>, Self::Error> {
+        <S as StorageProvider<VERSION>>::resumption_psk_store::<GroupId, ResumptionPskStore>(
+            self, group_id,
+        )
+    }
+
+    async fn own_leaf_index<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        LeafNodeIndex: traits::LeafNodeIndex<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<LeafNodeIndex>, Self::Error> {
+        <S as StorageProvider<VERSION>>::own_leaf_index::<GroupId, LeafNodeIndex>(self, group_id)
+    }
+
+    async fn group_epoch_secrets<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        GroupEpochSecrets: traits::GroupEpochSecrets<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<GroupEpochSecrets>, Self::Error> {
+        <S as StorageProvider<VERSION>>::group_epoch_secrets::<GroupId, GroupEpochSecrets>(
+            self, group_id,
+        )
+    }
+
+    async fn signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<VERSION> + Sync,
+        SignatureKeyPair: traits::SignatureKeyPair<VERSION> + Sync,
+    >(
+        &self,
+        public_key: &SignaturePublicKey,
+    ) -> Result<Option<SignatureKeyPair>, Self::Error> {
+        <S as StorageProvider<VERSION>>::signature_key_pair::<SignaturePublicKey, SignatureKeyPair>(
+            self, public_key,
+        )
+    }
+
+    async fn encryption_key_pair<
+        HpkeKeyPair: traits::HpkeKeyPair<VERSION> + Sync,
+        EncryptionKey: traits::EncryptionKey<VERSION> + Sync,
+    >(
+        &self,
+        public_key: &EncryptionKey,
+    ) -> Result<Option<HpkeKeyPair>, Self::Error> {
+        <S as StorageProvider<VERSION>>::encryption_key_pair::<HpkeKeyPair, EncryptionKey>(
+            self, public_key,
+        )
+    }
+
+    async fn encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        EpochKey: traits::EpochKey<VERSION> + Sync,
+        HpkeKeyPair: traits::HpkeKeyPair<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+    ) -> Result<Vec<HpkeKeyPair>, Self::Error> {
+        <S as StorageProvider<VERSION>>::encryption_epoch_key_pairs::<GroupId, EpochKey, HpkeKeyPair>(
+            self, group_id, epoch, leaf_index,
+        )
+    }
+
+    async fn key_package<
+        KeyPackageRef: traits::HashReference<VERSION> + Sync,
+        KeyPackage: traits::KeyPackage<VERSION> + Sync,
+    >(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> Result<Option<KeyPackage>, Self::Error> {
+        <S as StorageProvider<VERSION>>::key_package::<KeyPackageRef, KeyPackage>(self, hash_ref)
+    }
+
+    async fn psk<
+        PskBundle: traits::PskBundle<VERSION> + Sync,
+        PskId: traits::PskId<VERSION> + Sync,
+    >(
+        &self,
+        psk_id: &PskId,
+    ) -> Result<Option<PskBundle>, Self::Error> {
+        <S as StorageProvider<VERSION>>::psk::<PskBundle, PskId>(self, psk_id)
+    }
+
+    async fn group_ids<GroupId: traits::GroupId<VERSION> + Sync + DeserializeOwned>(
+        &self,
+    ) -> Result<Vec<GroupId>, Self::Error> {
+        <S as StorageProvider<VERSION>>::group_ids::<GroupId>(self)
+    }
+
+    async fn encryption_epoch_key_pair_ids<
+        GroupId: traits::GroupId<VERSION> + Sync + DeserializeOwned,
+        EpochKey: traits::EpochKey<VERSION> + Sync + DeserializeOwned,
+    >(
+        &self,
+    ) -> Result<Vec<(GroupId, EpochKey, u32)>, Self::Error> {
+        <S as StorageProvider<VERSION>>::encryption_epoch_key_pair_ids::<GroupId, EpochKey>(self)
+    }
+
+    async fn key_package_refs<
+        KeyPackageRef: traits::HashReference<VERSION> + Sync + DeserializeOwned,
+    >(
+        &self,
+    ) -> Result<Vec<KeyPackageRef>, Self::Error> {
+        <S as StorageProvider<VERSION>>::key_package_refs::<KeyPackageRef>(self)
+    }
+
+    async fn psk_ids<PskId: traits::PskId<VERSION> + Sync + DeserializeOwned>(
+        &self,
+    ) -> Result<Vec<PskId>, Self::Error> {
+        <S as StorageProvider<VERSION>>::psk_ids::<PskId>(self)
+    }
+
+    async fn remove_proposal<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        ProposalRef: traits::ProposalRef<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        proposal_ref: &ProposalRef,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::remove_proposal::<GroupId, ProposalRef>(
+            self,
+            group_id,
+            proposal_ref,
+        )
+    }
+
+    async fn delete_own_leaf_nodes<GroupId: traits::GroupId<VERSION> + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::delete_own_leaf_nodes::<GroupId>(self, group_id)
+    }
+
+    async fn delete_group_config<GroupId: traits::GroupId<VERSION> + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::delete_group_config::<GroupId>(self, group_id)
+    }
+
+    async fn delete_tree<GroupId: traits::GroupId<VERSION> + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::delete_tree::<GroupId>(self, group_id)
+    }
+
+    async fn delete_tree_node<GroupId: traits::GroupId<VERSION> + Sync>(
+        &self,
+        group_id: &GroupId,
+        node_index: u32,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::delete_tree_node::<GroupId>(self, group_id, node_index)
+    }
+
+    async fn delete_tree_nodes<GroupId: traits::GroupId<VERSION> + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::delete_tree_nodes::<GroupId>(self, group_id)
+    }
+
+    async fn delete_confirmation_tag<GroupId: traits::GroupId<VERSION> + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::delete_confirmation_tag::<GroupId>(self, group_id)
+    }
+
+    async fn delete_group_state<GroupId: traits::GroupId<VERSION> + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::delete_group_state::<GroupId>(self, group_id)
+    }
+
+    async fn delete_context<GroupId: traits::GroupId<VERSION> + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::delete_context::<GroupId>(self, group_id)
+    }
+
+    async fn delete_interim_transcript_hash<GroupId: traits::GroupId<VERSION> + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::delete_interim_transcript_hash::<GroupId>(self, group_id)
+    }
+
+    async fn delete_message_secrets<GroupId: traits::GroupId<VERSION> + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::delete_message_secrets::<GroupId>(self, group_id)
+    }
+
+    async fn delete_all_resumption_psk_secrets<GroupId: traits::GroupId<VERSION> + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::delete_all_resumption_psk_secrets::<GroupId>(
+            self, group_id,
+        )
+    }
+
+    async fn delete_own_leaf_index<GroupId: traits::GroupId<VERSION> + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::delete_own_leaf_index::<GroupId>(self, group_id)
+    }
+
+    async fn delete_group_epoch_secrets<GroupId: traits::GroupId<VERSION> + Sync>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::delete_group_epoch_secrets::<GroupId>(self, group_id)
+    }
+
+    async fn clear_proposal_queue<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        ProposalRef: traits::ProposalRef<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::clear_proposal_queue::<GroupId, ProposalRef>(
+            self, group_id,
+        )
+    }
+
+    async fn delete_signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<VERSION> + Sync,
+    >(
+        &self,
+        public_key: &SignaturePublicKey,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::delete_signature_key_pair::<SignaturePublicKey>(
+            self, public_key,
+        )
+    }
+
+    async fn delete_encryption_key_pair<EncryptionKey: traits::EncryptionKey<VERSION> + Sync>(
+        &self,
+        public_key: &EncryptionKey,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::delete_encryption_key_pair::<EncryptionKey>(
+            self, public_key,
+        )
+    }
+
+    async fn delete_encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<VERSION> + Sync,
+        EpochKey: traits::EpochKey<VERSION> + Sync,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::delete_encryption_epoch_key_pairs::<GroupId, EpochKey>(
+            self, group_id, epoch, leaf_index,
+        )
+    }
+
+    async fn delete_key_package<KeyPackageRef: traits::HashReference<VERSION> + Sync>(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::delete_key_package::<KeyPackageRef>(self, hash_ref)
+    }
+
+    async fn delete_psk<PskKey: traits::PskId<VERSION> + Sync>(
+        &self,
+        psk_id: &PskKey,
+    ) -> Result<(), Self::Error> {
+        <S as StorageProvider<VERSION>>::delete_psk::<PskKey>(self, psk_id)
+    }
+}
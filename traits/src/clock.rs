@@ -0,0 +1,36 @@
+//! # Clock
+//!
+//! This module defines the [`Clock`] that OpenMLS uses to read the current
+//! time, e.g. to enforce the maximum age of key material from past epochs.
+
+#[cfg(all(target_arch = "wasm32", feature = "js"))]
+use fluvio_wasm_timer::{SystemTime, UNIX_EPOCH};
+#[cfg(not(all(target_arch = "wasm32", feature = "js")))]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A source of the current time.
+///
+/// Applications can provide their own clock through the
+/// [`OpenMlsProvider`](crate::OpenMlsProvider), e.g. to use a trusted time
+/// source or to control the time in tests.
+pub trait Clock {
+    /// Returns the current time in seconds since the UNIX epoch.
+    fn now(&self) -> u64;
+}
+
+/// A [`Clock`] that reads the time of the system.
+///
+/// On `wasm32-unknown-unknown` the system time is only available with the
+/// `js` feature, which reads it from JavaScript. Without it, this clock panics
+/// when it is read.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default()
+    }
+}
@@ -13,6 +13,24 @@ pub trait PublicStorageProvider<const VERSION: u16> {
         VERSION
     }
 
+    /// Begins a transaction.
+    ///
+    /// See [`StorageProvider::begin_transaction`] for details. The default implementation does
+    /// nothing.
+    fn begin_transaction(&self) -> Result<(), Self::PublicError> {
+        Ok(())
+    }
+
+    /// Commits the innermost transaction.
+    fn commit_transaction(&self) -> Result<(), Self::PublicError> {
+        Ok(())
+    }
+
+    /// Discards all writes and deletions of the innermost transaction.
+    fn rollback_transaction(&self) -> Result<(), Self::PublicError> {
+        Ok(())
+    }
+
     /// Write the TreeSync tree.
     fn write_tree<
         GroupId: crate::storage::traits::GroupId<VERSION>,
@@ -23,6 +41,19 @@ pub trait PublicStorageProvider<const VERSION: u16> {
         tree: &TreeSync,
     ) -> Result<(), Self::PublicError>;
 
+    /// Write a node of the tree.
+    ///
+    /// See [`StorageProvider::write_tree_node`] for details.
+    fn write_tree_node<
+        GroupId: crate::storage::traits::GroupId<VERSION>,
+        TreeNode: crate::storage::traits::TreeNode<VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        node_index: u32,
+        node: &TreeNode,
+    ) -> Result<(), Self::PublicError>;
+
     /// Write the interim transcript hash.
     fn write_interim_transcript_hash<
         GroupId: crate::storage::traits::GroupId<VERSION>,
@@ -84,6 +115,16 @@ pub trait PublicStorageProvider<const VERSION: u16> {
         group_id: &GroupId,
     ) -> Result<Option<TreeSync>, Self::PublicError>;
 
+    /// Returns all nodes of the tree of the group with group id `group_id`
+    /// with their node index, in any order.
+    fn tree_nodes<
+        GroupId: crate::storage::traits::GroupId<VERSION>,
+        TreeNode: crate::storage::traits::TreeNode<VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<(u32, TreeNode)>, Self::PublicError>;
+
     /// Returns the group context for the group with group id `group_id`.
     fn group_context<
         GroupId: crate::storage::traits::GroupId<VERSION>,
@@ -117,6 +158,19 @@ pub trait PublicStorageProvider<const VERSION: u16> {
         group_id: &GroupId,
     ) -> Result<(), Self::PublicError>;
 
+    /// Deletes the node with index `node_index` of the tree from storage
+    fn delete_tree_node<GroupId: crate::storage::traits::GroupId<VERSION>>(
+        &self,
+        group_id: &GroupId,
+        node_index: u32,
+    ) -> Result<(), Self::PublicError>;
+
+    /// Deletes all nodes of the tree from storage
+    fn delete_tree_nodes<GroupId: crate::storage::traits::GroupId<VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::PublicError>;
+
     /// Deletes the confirmation tag from storage
     fn delete_confirmation_tag<GroupId: crate::storage::traits::GroupId<VERSION>>(
         &self,
@@ -161,6 +215,18 @@ where
 {
     type PublicError = <T as StorageProvider<VERSION>>::Error;
 
+    fn begin_transaction(&self) -> Result<(), Self::PublicError> {
+        <Self as StorageProvider<VERSION>>::begin_transaction(self)
+    }
+
+    fn commit_transaction(&self) -> Result<(), Self::PublicError> {
+        <Self as StorageProvider<VERSION>>::commit_transaction(self)
+    }
+
+    fn rollback_transaction(&self) -> Result<(), Self::PublicError> {
+        <Self as StorageProvider<VERSION>>::rollback_transaction(self)
+    }
+
     fn write_tree<
         GroupId: crate::storage::traits::GroupId<VERSION>,
         TreeSync: crate::storage::traits::TreeSync<VERSION>,
@@ -172,6 +238,18 @@ where
         <Self as StorageProvider<VERSION>>::write_tree(self, group_id, tree)
     }
 
+    fn write_tree_node<
+        GroupId: crate::storage::traits::GroupId<VERSION>,
+        TreeNode: crate::storage::traits::TreeNode<VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        node_index: u32,
+        node: &TreeNode,
+    ) -> Result<(), Self::PublicError> {
+        <Self as StorageProvider<VERSION>>::write_tree_node(self, group_id, node_index, node)
+    }
+
     fn write_interim_transcript_hash<
         GroupId: crate::storage::traits::GroupId<VERSION>,
         InterimTranscriptHash: crate::storage::traits::InterimTranscriptHash<VERSION>,
@@ -243,6 +321,16 @@ where
         <Self as StorageProvider<VERSION>>::tree(self, group_id)
     }
 
+    fn tree_nodes<
+        GroupId: crate::storage::traits::GroupId<VERSION>,
+        TreeNode: crate::storage::traits::TreeNode<VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<(u32, TreeNode)>, Self::PublicError> {
+        <Self as StorageProvider<VERSION>>::tree_nodes(self, group_id)
+    }
+
     fn group_context<
         GroupId: crate::storage::traits::GroupId<VERSION>,
         GroupContext: crate::storage::traits::GroupContext<VERSION>,
@@ -280,6 +368,21 @@ where
         <Self as StorageProvider<VERSION>>::delete_tree(self, group_id)
     }
 
+    fn delete_tree_node<GroupId: crate::storage::traits::GroupId<VERSION>>(
+        &self,
+        group_id: &GroupId,
+        node_index: u32,
+    ) -> Result<(), Self::PublicError> {
+        <Self as StorageProvider<VERSION>>::delete_tree_node(self, group_id, node_index)
+    }
+
+    fn delete_tree_nodes<GroupId: crate::storage::traits::GroupId<VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::PublicError> {
+        <Self as StorageProvider<VERSION>>::delete_tree_nodes(self, group_id)
+    }
+
     fn delete_confirmation_tag<GroupId: crate::storage::traits::GroupId<VERSION>>(
         &self,
         group_id: &GroupId,
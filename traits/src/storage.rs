@@ -35,6 +35,41 @@ pub trait StorageProvider<const VERSION: u16> {
         VERSION
     }
 
+    //
+    //    ---   transactions   ---
+    //
+
+    /// Begins a transaction.
+    ///
+    /// OpenMLS wraps every state transition that writes or deletes more than one value, e.g.
+    /// merging a commit, in a transaction and ends it with either
+    /// [`commit_transaction`](Self::commit_transaction) or
+    /// [`rollback_transaction`](Self::rollback_transaction). A provider that persists the state
+    /// should apply all writes and deletions of a transaction atomically, so that the stored
+    /// group state is always consistent, even if the application crashes during a state
+    /// transition.
+    ///
+    /// Transactions can be nested. Changes of a nested transaction only take effect when all
+    /// enclosing transactions are committed as well.
+    ///
+    /// The default implementation does nothing, i.e. all writes and deletions take effect
+    /// immediately.
+    fn begin_transaction(&self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Commits the innermost transaction started with
+    /// [`begin_transaction`](Self::begin_transaction).
+    fn commit_transaction(&self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Discards all writes and deletions of the innermost transaction started with
+    /// [`begin_transaction`](Self::begin_transaction).
+    fn rollback_transaction(&self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
     //
     //    ---   setters/writers/enqueuers for group state  ---
     //
@@ -75,12 +110,25 @@ pub trait StorageProvider<const VERSION: u16> {
     ) -> Result<(), Self::Error>;
 
     /// Write the TreeSync tree.
+    ///
+    /// OpenMLS only writes the size and the tree hash of the tree here. The
+    /// nodes are written individually with [`Self::write_tree_node`].
     fn write_tree<GroupId: traits::GroupId<VERSION>, TreeSync: traits::TreeSync<VERSION>>(
         &self,
         group_id: &GroupId,
         tree: &TreeSync,
     ) -> Result<(), Self::Error>;
 
+    /// Write the node with index `node_index` of the tree of the group with
+    /// group id `group_id`. The index is the position of the node in the array
+    /// representation of the tree. Blank nodes are not written.
+    fn write_tree_node<GroupId: traits::GroupId<VERSION>, TreeNode: traits::TreeNode<VERSION>>(
+        &self,
+        group_id: &GroupId,
+        node_index: u32,
+        node: &TreeNode,
+    ) -> Result<(), Self::Error>;
+
     /// Write the interim transcript hash.
     fn write_interim_transcript_hash<
         GroupId: traits::GroupId<VERSION>,
@@ -284,6 +332,14 @@ pub trait StorageProvider<const VERSION: u16> {
         group_id: &GroupId,
     ) -> Result<Option<TreeSync>, Self::Error>;
 
+    /// Returns all nodes of the tree of the group with group id `group_id`
+    /// with their node index, in any order, or an empty vector if none are
+    /// stored.
+    fn tree_nodes<GroupId: traits::GroupId<VERSION>, TreeNode: traits::TreeNode<VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<(u32, TreeNode)>, Self::Error>;
+
     /// Returns the group context for the group with group id `group_id`.
     fn group_context<
         GroupId: traits::GroupId<VERSION>,
@@ -413,6 +469,35 @@ pub trait StorageProvider<const VERSION: u16> {
         psk_id: &PskId,
     ) -> Result<Option<PskBundle>, Self::Error>;
 
+    //
+    //    ---   listing   ---
+    //
+    // The listings are used to find values that are not referenced by any group anymore. A
+    // provider must return all matching keys, since anything missing is never cleaned up.
+
+    /// Returns the ids of all groups that have a stored group state.
+    fn group_ids<GroupId: traits::GroupId<VERSION> + DeserializeOwned>(
+        &self,
+    ) -> Result<Vec<GroupId>, Self::Error>;
+
+    /// Returns the group id, epoch and leaf index of all stored encryption epoch key pairs.
+    fn encryption_epoch_key_pair_ids<
+        GroupId: traits::GroupId<VERSION> + DeserializeOwned,
+        EpochKey: traits::EpochKey<VERSION> + DeserializeOwned,
+    >(
+        &self,
+    ) -> Result<Vec<(GroupId, EpochKey, u32)>, Self::Error>;
+
+    /// Returns the hash references of all stored key packages.
+    fn key_package_refs<KeyPackageRef: traits::HashReference<VERSION> + DeserializeOwned>(
+        &self,
+    ) -> Result<Vec<KeyPackageRef>, Self::Error>;
+
+    /// Returns the identifiers of all stored PSKs.
+    fn psk_ids<PskId: traits::PskId<VERSION> + DeserializeOwned>(
+        &self,
+    ) -> Result<Vec<PskId>, Self::Error>;
+
     //
     //     ---    deleters for group state    ---
     //
@@ -445,6 +530,19 @@ pub trait StorageProvider<const VERSION: u16> {
         group_id: &GroupId,
     ) -> Result<(), Self::Error>;
 
+    /// Deletes the node with index `node_index` of the tree from storage
+    fn delete_tree_node<GroupId: traits::GroupId<VERSION>>(
+        &self,
+        group_id: &GroupId,
+        node_index: u32,
+    ) -> Result<(), Self::Error>;
+
+    /// Deletes all nodes of the tree from storage
+    fn delete_tree_nodes<GroupId: traits::GroupId<VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error>;
+
     /// Deletes the confirmation tag from storage
     fn delete_confirmation_tag<GroupId: traits::GroupId<VERSION>>(
         &self,
@@ -605,6 +703,7 @@ pub mod traits {
     pub trait KeyPackage<const VERSION: u16>: Entity<VERSION> {}
     pub trait MlsGroupJoinConfig<const VERSION: u16>: Entity<VERSION> {}
     pub trait LeafNode<const VERSION: u16>: Entity<VERSION> {}
+    pub trait TreeNode<const VERSION: u16>: Entity<VERSION> {}
 
     // traits for types that implement both
     pub trait ProposalRef<const VERSION: u16>: Entity<VERSION> + Key<VERSION> {}
@@ -3,6 +3,8 @@
 //! This module defines a number of traits that are used by the public
 //! API of OpenMLS.
 
+pub mod async_storage;
+pub mod clock;
 pub mod crypto;
 pub mod public_storage;
 pub mod random;
@@ -38,5 +40,63 @@ pub trait OpenMlsProvider {
 
     /// Get the randomness provider.
     fn rand(&self) -> &Self::RandProvider;
+
+    /// Get the clock. The default is the [`SystemClock`](clock::SystemClock).
+    fn clock(&self) -> &dyn clock::Clock {
+        &clock::SystemClock
+    }
 }
 // ANCHOR_END: openmls_provider
+
+/// The asynchronous variant of the [`OpenMlsProvider`].
+///
+/// Only the storage is asynchronous. Cryptographic operations and randomness
+/// generation are bound by the CPU and don't wait for I/O, so they use the same
+/// traits as the [`OpenMlsProvider`].
+pub trait AsyncOpenMlsProvider {
+    type CryptoProvider: crypto::OpenMlsCrypto;
+    type RandProvider: random::OpenMlsRand;
+    type StorageProvider: async_storage::AsyncStorageProvider<{ storage::CURRENT_VERSION }>;
+
+    /// Get the storage provider.
+    fn storage(&self) -> &Self::StorageProvider;
+
+    /// Get the crypto provider.
+    fn crypto(&self) -> &Self::CryptoProvider;
+
+    /// Get the randomness provider.
+    fn rand(&self) -> &Self::RandProvider;
+
+    /// Get the clock. The default is the [`SystemClock`](clock::SystemClock).
+    fn clock(&self) -> &dyn clock::Clock {
+        &clock::SystemClock
+    }
+}
+
+/// Every [`OpenMlsProvider`] whose storage can be shared between threads can
+/// be used as an [`AsyncOpenMlsProvider`]. Its storage is still accessed
+/// synchronously when the futures are polled.
+impl<Provider: OpenMlsProvider> AsyncOpenMlsProvider for Provider
+where
+    Provider::StorageProvider: Sync,
+{
+    type CryptoProvider = Provider::CryptoProvider;
+    type RandProvider = Provider::RandProvider;
+    type StorageProvider = Provider::StorageProvider;
+
+    fn storage(&self) -> &Self::StorageProvider {
+        OpenMlsProvider::storage(self)
+    }
+
+    fn crypto(&self) -> &Self::CryptoProvider {
+        OpenMlsProvider::crypto(self)
+    }
+
+    fn rand(&self) -> &Self::RandProvider {
+        OpenMlsProvider::rand(self)
+    }
+
+    fn clock(&self) -> &dyn clock::Clock {
+        OpenMlsProvider::clock(self)
+    }
+}
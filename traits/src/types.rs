@@ -101,6 +101,12 @@ pub enum SignatureScheme {
     ED25519 = 0x0807,
     /// ED448
     ED448 = 0x0808,
+    /// ML-DSA-44
+    ML_DSA_44 = 0x0904,
+    /// ML-DSA-65
+    ML_DSA_65 = 0x0905,
+    /// ML-DSA-87
+    ML_DSA_87 = 0x0906,
 }
 
 impl TryFrom<u16> for SignatureScheme {
@@ -113,6 +119,9 @@ impl TryFrom<u16> for SignatureScheme {
             0x0603 => Ok(SignatureScheme::ECDSA_SECP521R1_SHA512),
             0x0807 => Ok(SignatureScheme::ED25519),
             0x0808 => Ok(SignatureScheme::ED448),
+            0x0904 => Ok(SignatureScheme::ML_DSA_44),
+            0x0905 => Ok(SignatureScheme::ML_DSA_65),
+            0x0906 => Ok(SignatureScheme::ML_DSA_87),
             _ => Err(format!("Unsupported SignatureScheme: {value}")),
         }
     }
@@ -180,10 +189,32 @@ pub enum HpkeKemType {
     /// DH KEM on x448
     DhKem448 = 0x0021,
 
+    /// ML-KEM-768
+    MlKem768 = 0x0041,
+
+    /// ML-KEM-1024
+    MlKem1024 = 0x0042,
+
     /// XWing combiner for ML-KEM and X25519
     XWingKemDraft2 = 0x004D,
 }
 
+impl HpkeKemType {
+    /// Returns the length of the KEM output (`Nenc`) in bytes.
+    pub const fn enc_length(&self) -> usize {
+        match self {
+            HpkeKemType::DhKemP256 => 65,
+            HpkeKemType::DhKemP384 => 97,
+            HpkeKemType::DhKemP521 => 133,
+            HpkeKemType::DhKem25519 => 32,
+            HpkeKemType::DhKem448 => 56,
+            HpkeKemType::MlKem768 => 1088,
+            HpkeKemType::MlKem1024 => 1568,
+            HpkeKemType::XWingKemDraft2 => 1120,
+        }
+    }
+}
+
 /// KDF Types for HPKE
 #[derive(PartialEq, Eq, Copy, Clone, Debug, Serialize, Deserialize)]
 #[repr(u16)]
@@ -374,6 +405,36 @@ pub enum Ciphersuite {
 
     /// X-WING KEM draft-01 | Chacha20Poly1305 | SHA2-256 | Ed25519
     MLS_256_XWING_CHACHA20POLY1305_SHA256_Ed25519 = 0x004D,
+
+    /// ML-KEM-768 | AES-GCM 128 | SHA2-256 | Ed25519
+    ///
+    /// A provisional suite from the MLS post-quantum ciphersuites draft. IANA
+    /// didn't assign a value yet, so this uses one from the private use range.
+    /// It only interoperates with peers that use the same value and changes
+    /// once a value is assigned. The hybrid suite of the draft is
+    /// [`Ciphersuite::MLS_256_XWING_CHACHA20POLY1305_SHA256_Ed25519`].
+    MLS_128_MLKEM768_AES128GCM_SHA256_Ed25519 = 0xF001,
+
+    /// ML-KEM-1024 | AES-GCM 256 | SHA2-512 | Ed25519
+    ///
+    /// A provisional suite from the MLS post-quantum ciphersuites draft. IANA
+    /// didn't assign a value yet, so this uses one from the private use range.
+    /// It only interoperates with peers that use the same value and changes
+    /// once a value is assigned. The hybrid suite of the draft is
+    /// [`Ciphersuite::MLS_256_XWING_CHACHA20POLY1305_SHA256_Ed25519`].
+    MLS_256_MLKEM1024_AES256GCM_SHA512_Ed25519 = 0xF002,
+
+    /// ML-KEM-768 | AES-GCM 128 | SHA2-256 | ML-DSA-65
+    ///
+    /// From the MLS post-quantum ciphersuites draft. IANA didn't assign a value
+    /// yet, so this uses one from the private use range.
+    MLS_128_MLKEM768_AES128GCM_SHA256_MLDSA65 = 0xF003,
+
+    /// ML-KEM-1024 | AES-GCM 256 | SHA2-512 | ML-DSA-87
+    ///
+    /// From the MLS post-quantum ciphersuites draft. IANA didn't assign a value
+    /// yet, so this uses one from the private use range.
+    MLS_256_MLKEM1024_AES256GCM_SHA512_MLDSA87 = 0xF004,
 }
 
 impl core::fmt::Display for Ciphersuite {
@@ -410,6 +471,10 @@ impl TryFrom<u16> for Ciphersuite {
             0x0006 => Ok(Ciphersuite::MLS_256_DHKEMX448_CHACHA20POLY1305_SHA512_Ed448),
             0x0007 => Ok(Ciphersuite::MLS_256_DHKEMP384_AES256GCM_SHA384_P384),
             0x004D => Ok(Ciphersuite::MLS_256_XWING_CHACHA20POLY1305_SHA256_Ed25519),
+            0xF001 => Ok(Ciphersuite::MLS_128_MLKEM768_AES128GCM_SHA256_Ed25519),
+            0xF002 => Ok(Ciphersuite::MLS_256_MLKEM1024_AES256GCM_SHA512_Ed25519),
+            0xF003 => Ok(Ciphersuite::MLS_128_MLKEM768_AES128GCM_SHA256_MLDSA65),
+            0xF004 => Ok(Ciphersuite::MLS_256_MLKEM1024_AES256GCM_SHA512_MLDSA87),
             _ => Err(Self::Error::DecodingError(format!(
                 "{v} is not a valid ciphersuite value"
             ))),
@@ -467,11 +532,15 @@ impl Ciphersuite {
             Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519
             | Ciphersuite::MLS_128_DHKEMP256_AES128GCM_SHA256_P256
             | Ciphersuite::MLS_128_DHKEMX25519_CHACHA20POLY1305_SHA256_Ed25519
-            | Ciphersuite::MLS_256_XWING_CHACHA20POLY1305_SHA256_Ed25519 => HashType::Sha2_256,
+            | Ciphersuite::MLS_256_XWING_CHACHA20POLY1305_SHA256_Ed25519
+            | Ciphersuite::MLS_128_MLKEM768_AES128GCM_SHA256_Ed25519
+            | Ciphersuite::MLS_128_MLKEM768_AES128GCM_SHA256_MLDSA65 => HashType::Sha2_256,
             Ciphersuite::MLS_256_DHKEMP384_AES256GCM_SHA384_P384 => HashType::Sha2_384,
             Ciphersuite::MLS_256_DHKEMX448_AES256GCM_SHA512_Ed448
             | Ciphersuite::MLS_256_DHKEMP521_AES256GCM_SHA512_P521
-            | Ciphersuite::MLS_256_DHKEMX448_CHACHA20POLY1305_SHA512_Ed448 => HashType::Sha2_512,
+            | Ciphersuite::MLS_256_DHKEMX448_CHACHA20POLY1305_SHA512_Ed448
+            | Ciphersuite::MLS_256_MLKEM1024_AES256GCM_SHA512_Ed25519
+            | Ciphersuite::MLS_256_MLKEM1024_AES256GCM_SHA512_MLDSA87 => HashType::Sha2_512,
         }
     }
 
@@ -481,9 +550,9 @@ impl Ciphersuite {
         match self {
             Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519
             | Ciphersuite::MLS_128_DHKEMX25519_CHACHA20POLY1305_SHA256_Ed25519
-            | Ciphersuite::MLS_256_XWING_CHACHA20POLY1305_SHA256_Ed25519 => {
-                SignatureScheme::ED25519
-            }
+            | Ciphersuite::MLS_256_XWING_CHACHA20POLY1305_SHA256_Ed25519
+            | Ciphersuite::MLS_128_MLKEM768_AES128GCM_SHA256_Ed25519
+            | Ciphersuite::MLS_256_MLKEM1024_AES256GCM_SHA512_Ed25519 => SignatureScheme::ED25519,
             Ciphersuite::MLS_128_DHKEMP256_AES128GCM_SHA256_P256 => {
                 SignatureScheme::ECDSA_SECP256R1_SHA256
             }
@@ -497,6 +566,8 @@ impl Ciphersuite {
             Ciphersuite::MLS_256_DHKEMP384_AES256GCM_SHA384_P384 => {
                 SignatureScheme::ECDSA_SECP384R1_SHA384
             }
+            Ciphersuite::MLS_128_MLKEM768_AES128GCM_SHA256_MLDSA65 => SignatureScheme::ML_DSA_65,
+            Ciphersuite::MLS_256_MLKEM1024_AES256GCM_SHA512_MLDSA87 => SignatureScheme::ML_DSA_87,
         }
     }
 
@@ -505,7 +576,9 @@ impl Ciphersuite {
     pub const fn aead_algorithm(&self) -> AeadType {
         match self {
             Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519
-            | Ciphersuite::MLS_128_DHKEMP256_AES128GCM_SHA256_P256 => AeadType::Aes128Gcm,
+            | Ciphersuite::MLS_128_DHKEMP256_AES128GCM_SHA256_P256
+            | Ciphersuite::MLS_128_MLKEM768_AES128GCM_SHA256_Ed25519
+            | Ciphersuite::MLS_128_MLKEM768_AES128GCM_SHA256_MLDSA65 => AeadType::Aes128Gcm,
             Ciphersuite::MLS_128_DHKEMX25519_CHACHA20POLY1305_SHA256_Ed25519
             | Ciphersuite::MLS_256_DHKEMX448_CHACHA20POLY1305_SHA512_Ed448
             | Ciphersuite::MLS_256_XWING_CHACHA20POLY1305_SHA256_Ed25519 => {
@@ -513,7 +586,9 @@ impl Ciphersuite {
             }
             Ciphersuite::MLS_256_DHKEMX448_AES256GCM_SHA512_Ed448
             | Ciphersuite::MLS_256_DHKEMP521_AES256GCM_SHA512_P521
-            | Ciphersuite::MLS_256_DHKEMP384_AES256GCM_SHA384_P384 => AeadType::Aes256Gcm,
+            | Ciphersuite::MLS_256_DHKEMP384_AES256GCM_SHA384_P384
+            | Ciphersuite::MLS_256_MLKEM1024_AES256GCM_SHA512_Ed25519
+            | Ciphersuite::MLS_256_MLKEM1024_AES256GCM_SHA512_MLDSA87 => AeadType::Aes256Gcm,
         }
     }
 
@@ -524,13 +599,15 @@ impl Ciphersuite {
             Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519
             | Ciphersuite::MLS_128_DHKEMP256_AES128GCM_SHA256_P256
             | Ciphersuite::MLS_128_DHKEMX25519_CHACHA20POLY1305_SHA256_Ed25519
-            | Self::MLS_256_XWING_CHACHA20POLY1305_SHA256_Ed25519 => HpkeKdfType::HkdfSha256,
+            | Self::MLS_256_XWING_CHACHA20POLY1305_SHA256_Ed25519
+            | Ciphersuite::MLS_128_MLKEM768_AES128GCM_SHA256_Ed25519
+            | Ciphersuite::MLS_128_MLKEM768_AES128GCM_SHA256_MLDSA65 => HpkeKdfType::HkdfSha256,
             Ciphersuite::MLS_256_DHKEMP384_AES256GCM_SHA384_P384 => HpkeKdfType::HkdfSha384,
             Ciphersuite::MLS_256_DHKEMX448_AES256GCM_SHA512_Ed448
             | Ciphersuite::MLS_256_DHKEMP521_AES256GCM_SHA512_P521
-            | Ciphersuite::MLS_256_DHKEMX448_CHACHA20POLY1305_SHA512_Ed448 => {
-                HpkeKdfType::HkdfSha512
-            }
+            | Ciphersuite::MLS_256_DHKEMX448_CHACHA20POLY1305_SHA512_Ed448
+            | Ciphersuite::MLS_256_MLKEM1024_AES256GCM_SHA512_Ed25519
+            | Ciphersuite::MLS_256_MLKEM1024_AES256GCM_SHA512_MLDSA87 => HpkeKdfType::HkdfSha512,
         }
     }
 
@@ -550,6 +627,10 @@ impl Ciphersuite {
             Ciphersuite::MLS_256_XWING_CHACHA20POLY1305_SHA256_Ed25519 => {
                 HpkeKemType::XWingKemDraft2
             }
+            Ciphersuite::MLS_128_MLKEM768_AES128GCM_SHA256_Ed25519
+            | Ciphersuite::MLS_128_MLKEM768_AES128GCM_SHA256_MLDSA65 => HpkeKemType::MlKem768,
+            Ciphersuite::MLS_256_MLKEM1024_AES256GCM_SHA512_Ed25519
+            | Ciphersuite::MLS_256_MLKEM1024_AES256GCM_SHA512_MLDSA87 => HpkeKemType::MlKem1024,
         }
     }
 
@@ -558,14 +639,18 @@ impl Ciphersuite {
     pub const fn hpke_aead_algorithm(&self) -> HpkeAeadType {
         match self {
             Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519
-            | Ciphersuite::MLS_128_DHKEMP256_AES128GCM_SHA256_P256 => HpkeAeadType::AesGcm128,
+            | Ciphersuite::MLS_128_DHKEMP256_AES128GCM_SHA256_P256
+            | Ciphersuite::MLS_128_MLKEM768_AES128GCM_SHA256_Ed25519
+            | Ciphersuite::MLS_128_MLKEM768_AES128GCM_SHA256_MLDSA65 => HpkeAeadType::AesGcm128,
             Ciphersuite::MLS_128_DHKEMX25519_CHACHA20POLY1305_SHA256_Ed25519
             | Ciphersuite::MLS_256_XWING_CHACHA20POLY1305_SHA256_Ed25519 => {
                 HpkeAeadType::ChaCha20Poly1305
             }
             Ciphersuite::MLS_256_DHKEMX448_AES256GCM_SHA512_Ed448
             | Ciphersuite::MLS_256_DHKEMP384_AES256GCM_SHA384_P384
-            | Ciphersuite::MLS_256_DHKEMP521_AES256GCM_SHA512_P521 => HpkeAeadType::AesGcm256,
+            | Ciphersuite::MLS_256_DHKEMP521_AES256GCM_SHA512_P521
+            | Ciphersuite::MLS_256_MLKEM1024_AES256GCM_SHA512_Ed25519
+            | Ciphersuite::MLS_256_MLKEM1024_AES256GCM_SHA512_MLDSA87 => HpkeAeadType::AesGcm256,
             Ciphersuite::MLS_256_DHKEMX448_CHACHA20POLY1305_SHA512_Ed448 => {
                 HpkeAeadType::ChaCha20Poly1305
             }
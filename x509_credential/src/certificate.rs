@@ -0,0 +1,270 @@
+//! Parsing of the parts of X.509 certificates (RFC 5280) that are needed to
+//! validate certificate chains.
+
+use std::time::Duration;
+
+use p256::ecdsa::signature::Verifier;
+use spki::{
+    der::{
+        asn1::{AnyRef, BitStringRef, ContextSpecific, GeneralizedTime, OctetStringRef, UtcTime},
+        Decode, Reader, SliceReader, Tag, TagNumber,
+    },
+    AlgorithmIdentifierRef, ObjectIdentifier, SubjectPublicKeyInfoRef,
+};
+
+use crate::X509CredentialError;
+
+const ED25519: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.101.112");
+const EC_PUBLIC_KEY: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.2.1");
+const SECP256R1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.3.1.7");
+const ECDSA_WITH_SHA256: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.2");
+
+const KEY_USAGE: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.5.29.15");
+const SUBJECT_ALT_NAME: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.5.29.17");
+const BASIC_CONSTRAINTS: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.5.29.19");
+const EXTENDED_KEY_USAGE: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.5.29.37");
+
+/// Bit positions in the key usage extension.
+const DIGITAL_SIGNATURE: usize = 0;
+const KEY_CERT_SIGN: usize = 5;
+
+/// The public key of a certificate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum PublicKey {
+    Ed25519(ed25519_dalek::VerifyingKey),
+    P256(p256::ecdsa::VerifyingKey),
+}
+
+impl PublicKey {
+    fn from_spki(spki: &SubjectPublicKeyInfoRef<'_>) -> Result<Self, X509CredentialError> {
+        let key_bytes = spki
+            .subject_public_key
+            .as_bytes()
+            .ok_or(X509CredentialError::UnsupportedAlgorithm)?;
+        match spki.algorithm.oid {
+            ED25519 => {
+                let key_bytes = key_bytes
+                    .try_into()
+                    .map_err(|_| X509CredentialError::UnsupportedAlgorithm)?;
+                ed25519_dalek::VerifyingKey::from_bytes(key_bytes)
+                    .map(Self::Ed25519)
+                    .map_err(|_| X509CredentialError::UnsupportedAlgorithm)
+            }
+            EC_PUBLIC_KEY if spki.algorithm.parameters_oid().ok() == Some(SECP256R1) => {
+                p256::ecdsa::VerifyingKey::from_sec1_bytes(key_bytes)
+                    .map(Self::P256)
+                    .map_err(|_| X509CredentialError::UnsupportedAlgorithm)
+            }
+            _ => Err(X509CredentialError::UnsupportedAlgorithm),
+        }
+    }
+
+    /// Returns `true` if `signature_key` is the encoding of this key used by
+    /// OpenMLS, i.e. the raw key for Ed25519 and a SEC1 point for P-256.
+    pub(crate) fn matches(&self, signature_key: &[u8]) -> bool {
+        match self {
+            PublicKey::Ed25519(key) => key.as_bytes().as_slice() == signature_key,
+            PublicKey::P256(key) => p256::ecdsa::VerifyingKey::from_sec1_bytes(signature_key)
+                .is_ok_and(|signature_key| signature_key == *key),
+        }
+    }
+
+    /// Verify the `signature` on `message` created with `algorithm`.
+    fn verify(
+        &self,
+        algorithm: ObjectIdentifier,
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<(), X509CredentialError> {
+        match (self, algorithm) {
+            (PublicKey::Ed25519(key), ED25519) => {
+                let signature = ed25519_dalek::Signature::from_slice(signature)
+                    .map_err(|_| X509CredentialError::InvalidSignature)?;
+                key.verify_strict(message, &signature)
+                    .map_err(|_| X509CredentialError::InvalidSignature)
+            }
+            (PublicKey::P256(key), ECDSA_WITH_SHA256) => {
+                let signature = p256::ecdsa::Signature::from_der(signature)
+                    .map_err(|_| X509CredentialError::InvalidSignature)?;
+                key.verify(message, &signature)
+                    .map_err(|_| X509CredentialError::InvalidSignature)
+            }
+            _ => Err(X509CredentialError::UnsupportedAlgorithm),
+        }
+    }
+}
+
+/// The basic constraints extension of a certificate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BasicConstraints {
+    pub(crate) ca: bool,
+    pub(crate) path_len: Option<u32>,
+}
+
+/// A parsed X.509 certificate that borrows from its DER encoding.
+#[derive(Debug, Clone)]
+pub(crate) struct ParsedCertificate<'a> {
+    tbs_certificate: &'a [u8],
+    signature_algorithm: ObjectIdentifier,
+    signature: &'a [u8],
+    /// The DER encoded issuer name.
+    pub(crate) issuer: &'a [u8],
+    /// The DER encoded subject name.
+    pub(crate) subject: &'a [u8],
+    /// Start of the validity period in seconds since the Unix epoch.
+    pub(crate) not_before: Duration,
+    /// End of the validity period in seconds since the Unix epoch.
+    pub(crate) not_after: Duration,
+    pub(crate) public_key: PublicKey,
+    /// The bits of the key usage extension, if present.
+    key_usage: Option<Vec<bool>>,
+    pub(crate) basic_constraints: Option<BasicConstraints>,
+}
+
+impl<'a> ParsedCertificate<'a> {
+    /// Parse a DER encoded certificate.
+    pub(crate) fn parse(der_bytes: &'a [u8]) -> Result<Self, X509CredentialError> {
+        let mut reader = SliceReader::new(der_bytes)?;
+        let (tbs_certificate, signature_algorithm, signature) = reader.sequence(|reader| {
+            let tbs_certificate = reader.tlv_bytes()?;
+            let signature_algorithm = AlgorithmIdentifierRef::decode(reader)?;
+            let signature = BitStringRef::decode(reader)?;
+            Ok((tbs_certificate, signature_algorithm.oid, signature))
+        })?;
+        reader.finish(())?;
+        let signature = signature
+            .as_bytes()
+            .ok_or(X509CredentialError::InvalidSignature)?;
+
+        let mut reader = SliceReader::new(tbs_certificate)?;
+        let (issuer, subject, not_before, not_after, spki, extensions) =
+            reader.sequence(|reader| {
+                let _version = ContextSpecific::<u8>::decode_explicit(reader, TagNumber::N0)?;
+                let _serial_number = AnyRef::decode(reader)?;
+                let _signature = AlgorithmIdentifierRef::decode(reader)?;
+                let issuer = reader.tlv_bytes()?;
+                let (not_before, not_after) =
+                    reader.sequence(|reader| Ok((decode_time(reader)?, decode_time(reader)?)))?;
+                let subject = reader.tlv_bytes()?;
+                let spki = SubjectPublicKeyInfoRef::decode(reader)?;
+                // The unique identifiers [1] and [2] are skipped.
+                let extensions =
+                    ContextSpecific::<AnyRef<'_>>::decode_explicit(reader, TagNumber::N3)?
+                        .map(|extensions| decode_extensions(extensions.value))
+                        .transpose()?
+                        .unwrap_or_default();
+                Ok((issuer, subject, not_before, not_after, spki, extensions))
+            })?;
+        reader.finish(())?;
+
+        let mut key_usage = None;
+        let mut basic_constraints = None;
+        for (extension_id, critical, value) in extensions {
+            match extension_id {
+                KEY_USAGE => {
+                    key_usage = Some(BitStringRef::from_der(value)?.bits().collect());
+                }
+                BASIC_CONSTRAINTS => {
+                    basic_constraints = Some(decode_basic_constraints(value)?);
+                }
+                // These extensions restrict the use of the certificate by the
+                // application and are not relevant for the chain.
+                SUBJECT_ALT_NAME | EXTENDED_KEY_USAGE => {}
+                _ if critical => return Err(X509CredentialError::UnsupportedCriticalExtension),
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            tbs_certificate,
+            signature_algorithm,
+            signature,
+            issuer,
+            subject,
+            not_before,
+            not_after,
+            public_key: PublicKey::from_spki(&spki)?,
+            key_usage,
+            basic_constraints,
+        })
+    }
+
+    /// Verify that this certificate was signed by `issuer`.
+    pub(crate) fn verify_signature(
+        &self,
+        issuer: &ParsedCertificate<'_>,
+    ) -> Result<(), X509CredentialError> {
+        issuer.public_key.verify(
+            self.signature_algorithm,
+            self.tbs_certificate,
+            self.signature,
+        )
+    }
+
+    /// Returns `true` if the certificate may be used for digital signatures.
+    /// This is the case if the certificate does not restrict its key usage.
+    pub(crate) fn allows_digital_signature(&self) -> bool {
+        self.allows_key_usage(DIGITAL_SIGNATURE)
+    }
+
+    /// Returns `true` if the certificate may be used to sign certificates.
+    pub(crate) fn allows_certificate_signing(&self) -> bool {
+        self.allows_key_usage(KEY_CERT_SIGN)
+    }
+
+    fn allows_key_usage(&self, bit: usize) -> bool {
+        match &self.key_usage {
+            Some(key_usage) => key_usage.get(bit).copied().unwrap_or(false),
+            None => true,
+        }
+    }
+}
+
+/// Decode a `Time`, which is either a `UTCTime` or a `GeneralizedTime`.
+fn decode_time<'a, R: Reader<'a>>(reader: &mut R) -> spki::der::Result<Duration> {
+    match reader.peek_tag()? {
+        Tag::UtcTime => Ok(UtcTime::decode(reader)?.to_unix_duration()),
+        Tag::GeneralizedTime => Ok(GeneralizedTime::decode(reader)?.to_unix_duration()),
+        tag => Err(tag.unexpected_error(None)),
+    }
+}
+
+/// Decode the `Extensions` sequence into tuples of extension id, criticality
+/// and the DER encoded extension value.
+fn decode_extensions(
+    extensions: AnyRef<'_>,
+) -> spki::der::Result<Vec<(ObjectIdentifier, bool, &[u8])>> {
+    extensions.sequence(|reader| {
+        let mut decoded = Vec::new();
+        while !reader.is_finished() {
+            decoded.push(reader.sequence(|reader| {
+                let extension_id = ObjectIdentifier::decode(reader)?;
+                let critical = if reader.peek_tag()? == Tag::Boolean {
+                    bool::decode(reader)?
+                } else {
+                    false
+                };
+                let value = OctetStringRef::decode(reader)?;
+                Ok((extension_id, critical, value.as_bytes()))
+            })?);
+        }
+        Ok(decoded)
+    })
+}
+
+/// Decode the value of a basic constraints extension.
+fn decode_basic_constraints(value: &[u8]) -> spki::der::Result<BasicConstraints> {
+    AnyRef::from_der(value)?.sequence(|reader| {
+        let ca = if !reader.is_finished() && reader.peek_tag()? == Tag::Boolean {
+            bool::decode(reader)?
+        } else {
+            false
+        };
+        let path_len = if !reader.is_finished() {
+            Some(u32::decode(reader)?)
+        } else {
+            None
+        };
+        Ok(BasicConstraints { ca, path_len })
+    })
+}
@@ -0,0 +1,62 @@
+//! X.509 credential errors
+//!
+//! This module exposes [`X509CredentialError`].
+
+use thiserror::Error;
+
+/// An error that occurs when parsing or validating an [`super::X509Credential`].
+#[derive(Error, Debug, PartialEq, Clone)]
+pub enum X509CredentialError {
+    /// TLS codec error
+    #[error(transparent)]
+    TlsCodecError(#[from] tls_codec::Error),
+    /// Wrong credential type
+    #[error("Wrong credential type.")]
+    WrongCredentialType,
+    /// The credential does not contain any certificate.
+    #[error("The credential does not contain any certificate.")]
+    EmptyChain,
+    /// A certificate is not a well-formed DER encoded X.509 certificate.
+    #[error("Malformed certificate: {0}")]
+    MalformedCertificate(#[from] spki::der::Error),
+    /// A certificate uses an unsupported key or signature algorithm.
+    #[error("A certificate uses an unsupported key or signature algorithm.")]
+    UnsupportedAlgorithm,
+    /// A certificate contains a critical extension that is not supported.
+    #[error("A certificate contains an unsupported critical extension.")]
+    UnsupportedCriticalExtension,
+    /// The public key of the leaf certificate is not the signature key of the
+    /// leaf node.
+    #[error("The leaf certificate does not certify the signature key.")]
+    SignatureKeyMismatch,
+    /// The issuer of a certificate is not the subject of the next certificate
+    /// in the chain.
+    #[error("The issuer of a certificate does not match the next certificate.")]
+    IssuerMismatch,
+    /// The signature on a certificate is invalid.
+    #[error("Invalid certificate signature.")]
+    InvalidSignature,
+    /// A certificate is not valid yet.
+    #[error("A certificate is not valid yet.")]
+    NotYetValid,
+    /// A certificate has expired.
+    #[error("A certificate has expired.")]
+    Expired,
+    /// A certificate that issues another certificate is not a certificate
+    /// authority.
+    #[error("An issuing certificate is not a certificate authority.")]
+    NotACertificateAuthority,
+    /// The path length constraint of a certificate authority is violated.
+    #[error("The path length constraint of a certificate authority is violated.")]
+    PathLengthExceeded,
+    /// The key usage of a certificate does not permit its use.
+    #[error("The key usage of a certificate does not permit its use.")]
+    KeyUsageNotPermitted,
+    /// The leaf certificates of a credential and its successor have different
+    /// subjects.
+    #[error("The new leaf certificate has a different subject than the old one.")]
+    SubjectMismatch,
+    /// The chain does not end at a trust anchor.
+    #[error("The certificate chain does not end at a trust anchor.")]
+    UntrustedChain,
+}
@@ -0,0 +1,271 @@
+//! # X.509 Credential
+//!
+//! An implementation of the X.509 credential from the MLS spec.
+//!
+//! An [`X509Credential`] carries a chain of DER encoded certificates, starting
+//! with the certificate of the client. An [`X509Validator`] checks such a
+//! chain against a set of trust anchors and ensures that the leaf certificate
+//! certifies the signature key of the leaf node it is used in. Applications
+//! should validate every X.509 credential they encounter, e.g. in key
+//! packages, in the ratchet tree of a group they join, and in proposals and
+//! commits that add or update members.
+//!
+//! For now this credential uses only RustCrypto and supports Ed25519 and ECDSA
+//! P-256 with SHA-256 for certificate keys and signatures.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use openmls::credentials::{
+    AuthenticationService, Certificate, Credential, CredentialSource, CredentialType,
+    CredentialWithKey,
+};
+use tls_codec::Serialize;
+
+mod certificate;
+mod error;
+
+use certificate::ParsedCertificate;
+pub use error::X509CredentialError;
+
+/// An X.509 credential.
+///
+/// The credential contains a non-empty chain of certificates, starting with
+/// the leaf certificate. Each certificate is followed by the certificate of
+/// its issuer. The last certificate is either a trust anchor or issued by one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct X509Credential {
+    certificates: Vec<Certificate>,
+}
+
+impl X509Credential {
+    /// Create a new X.509 credential from a chain of DER encoded certificates,
+    /// starting with the leaf certificate.
+    ///
+    /// Returns an error if the chain is empty or if a certificate can't be
+    /// parsed. The chain is not validated.
+    pub fn new(certificates: Vec<Vec<u8>>) -> Result<Self, X509CredentialError> {
+        Self::from_certificates(certificates.into_iter().map(Certificate::new).collect())
+    }
+
+    fn from_certificates(certificates: Vec<Certificate>) -> Result<Self, X509CredentialError> {
+        if certificates.is_empty() {
+            return Err(X509CredentialError::EmptyChain);
+        }
+        for certificate in &certificates {
+            ParsedCertificate::parse(certificate.cert_data())?;
+        }
+        Ok(Self { certificates })
+    }
+
+    /// Get the certificate chain, starting with the leaf certificate.
+    pub fn certificates(&self) -> &[Certificate] {
+        &self.certificates
+    }
+
+    /// Get the leaf certificate.
+    pub fn leaf_certificate(&self) -> &Certificate {
+        // The chain is never empty.
+        &self.certificates[0]
+    }
+}
+
+impl TryFrom<X509Credential> for Credential {
+    type Error = X509CredentialError;
+
+    fn try_from(credential: X509Credential) -> Result<Self, Self::Error> {
+        let serialized_credential = credential.certificates.tls_serialize_detached()?;
+        Ok(Credential::new(CredentialType::X509, serialized_credential))
+    }
+}
+
+impl TryFrom<Credential> for X509Credential {
+    type Error = X509CredentialError;
+
+    fn try_from(credential: Credential) -> Result<Self, Self::Error> {
+        if credential.credential_type() != CredentialType::X509 {
+            return Err(X509CredentialError::WrongCredentialType);
+        }
+        Self::from_certificates(credential.deserialized()?)
+    }
+}
+
+/// Validates X.509 credentials against a set of trust anchors.
+///
+/// A credential is valid if
+/// - the public key of the leaf certificate is the signature key of the leaf
+///   node the credential is used in,
+/// - the leaf certificate may be used for digital signatures,
+/// - every certificate is issued by the next one in the chain, and the last
+///   certificate is a trust anchor or issued by one,
+/// - every issuing certificate is a certificate authority that may sign
+///   certificates and whose path length constraint is respected,
+/// - every certificate, including the trust anchor, is valid at the time of
+///   validation.
+#[derive(Debug, Clone)]
+pub struct X509Validator {
+    trust_anchors: Vec<Vec<u8>>,
+}
+
+impl X509Validator {
+    /// Create a new validator that trusts the given DER encoded certificates.
+    ///
+    /// Returns an error if a trust anchor can't be parsed.
+    pub fn new(
+        trust_anchors: impl IntoIterator<Item = Vec<u8>>,
+    ) -> Result<Self, X509CredentialError> {
+        let trust_anchors: Vec<Vec<u8>> = trust_anchors.into_iter().collect();
+        for trust_anchor in &trust_anchors {
+            ParsedCertificate::parse(trust_anchor)?;
+        }
+        Ok(Self { trust_anchors })
+    }
+
+    /// Validate the X.509 `credential` of a leaf node with the given
+    /// `signature_key` at the given `time`.
+    ///
+    /// Returns the [`X509Credential`] if it is valid.
+    pub fn validate(
+        &self,
+        credential: &Credential,
+        signature_key: &[u8],
+        time: SystemTime,
+    ) -> Result<X509Credential, X509CredentialError> {
+        let credential = X509Credential::try_from(credential.clone())?;
+        let chain = credential
+            .certificates()
+            .iter()
+            .map(|certificate| ParsedCertificate::parse(certificate.cert_data()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let now = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+
+        // The chain is never empty.
+        let leaf = &chain[0];
+        if !leaf.public_key.matches(signature_key) {
+            return Err(X509CredentialError::SignatureKeyMismatch);
+        }
+        if !leaf.allows_digital_signature() {
+            return Err(X509CredentialError::KeyUsageNotPermitted);
+        }
+
+        for certificate in &chain {
+            check_validity(certificate, now)?;
+        }
+        for (intermediates, pair) in chain.windows(2).enumerate() {
+            check_issuer(&pair[0], &pair[1], intermediates)?;
+        }
+
+        // The last certificate is either a trust anchor itself or issued by
+        // one.
+        let last_certificate = credential.certificates.last().map(Certificate::cert_data);
+        if self
+            .trust_anchors
+            .iter()
+            .any(|trust_anchor| Some(trust_anchor.as_slice()) == last_certificate)
+        {
+            return Ok(credential);
+        }
+        let last = &chain[chain.len() - 1];
+        let mut error = X509CredentialError::UntrustedChain;
+        for trust_anchor in &self.trust_anchors {
+            let trust_anchor = ParsedCertificate::parse(trust_anchor)?;
+            if trust_anchor.subject != last.issuer {
+                continue;
+            }
+            match check_validity(&trust_anchor, now)
+                .and_then(|_| check_issuer(last, &trust_anchor, chain.len() - 1))
+            {
+                Ok(()) => return Ok(credential),
+                Err(e) => error = e,
+            }
+        }
+
+        Err(error)
+    }
+
+    /// Check that the `new` credential of a member is an acceptable successor
+    /// of its `old` credential, i.e. that both leaf certificates have the
+    /// same subject. The new credential must be validated separately.
+    pub fn validate_succession(
+        &self,
+        old: &Credential,
+        new: &Credential,
+    ) -> Result<(), X509CredentialError> {
+        let old = X509Credential::try_from(old.clone())?;
+        let new = X509Credential::try_from(new.clone())?;
+        let old_leaf = ParsedCertificate::parse(old.leaf_certificate().cert_data())?;
+        let new_leaf = ParsedCertificate::parse(new.leaf_certificate().cert_data())?;
+        if old_leaf.subject != new_leaf.subject {
+            return Err(X509CredentialError::SubjectMismatch);
+        }
+        Ok(())
+    }
+}
+
+/// The validator can be used as the [`AuthenticationService`] of a group in
+/// which all members use X.509 credentials. Credentials are validated at the
+/// current system time, and a member may only replace its credential with one
+/// for the same subject.
+impl AuthenticationService for X509Validator {
+    fn authenticate(
+        &self,
+        credential: &CredentialWithKey,
+        _source: CredentialSource,
+    ) -> Result<(), String> {
+        self.validate(
+            &credential.credential,
+            credential.signature_key.as_slice(),
+            SystemTime::now(),
+        )
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+    }
+
+    fn authenticate_succession(
+        &self,
+        old: &CredentialWithKey,
+        new: &CredentialWithKey,
+    ) -> Result<(), String> {
+        self.validate_succession(&old.credential, &new.credential)
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Check that `certificate` is valid at `now`, given in seconds since the Unix
+/// epoch.
+fn check_validity(
+    certificate: &ParsedCertificate<'_>,
+    now: std::time::Duration,
+) -> Result<(), X509CredentialError> {
+    if now < certificate.not_before {
+        return Err(X509CredentialError::NotYetValid);
+    }
+    if now > certificate.not_after {
+        return Err(X509CredentialError::Expired);
+    }
+    Ok(())
+}
+
+/// Check that `issuer` issued `certificate`. `intermediates` is the number of
+/// intermediate certificates between the leaf certificate and `issuer`.
+fn check_issuer(
+    certificate: &ParsedCertificate<'_>,
+    issuer: &ParsedCertificate<'_>,
+    intermediates: usize,
+) -> Result<(), X509CredentialError> {
+    if certificate.issuer != issuer.subject {
+        return Err(X509CredentialError::IssuerMismatch);
+    }
+    let Some(basic_constraints) = issuer.basic_constraints.filter(|bc| bc.ca) else {
+        return Err(X509CredentialError::NotACertificateAuthority);
+    };
+    if !issuer.allows_certificate_signing() {
+        return Err(X509CredentialError::KeyUsageNotPermitted);
+    }
+    if basic_constraints
+        .path_len
+        .is_some_and(|path_len| intermediates > path_len as usize)
+    {
+        return Err(X509CredentialError::PathLengthExceeded);
+    }
+    certificate.verify_signature(issuer)
+}
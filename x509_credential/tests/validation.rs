@@ -0,0 +1,403 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use ed25519_dalek::{Signer, SigningKey};
+use openmls::credentials::{BasicCredential, Credential};
+use openmls_x509_credential::{X509Credential, X509CredentialError, X509Validator};
+use spki::{
+    der::{asn1::GeneralizedTime, Encode},
+    ObjectIdentifier,
+};
+
+const DAY: u64 = 24 * 60 * 60;
+/// 2024-01-01
+const NOW: u64 = 1_704_067_200;
+
+const DIGITAL_SIGNATURE: u8 = 0x80;
+const KEY_CERT_SIGN: u8 = 0x04;
+
+fn tlv(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut out = vec![tag];
+    let len = content.len();
+    if len < 0x80 {
+        out.push(len as u8);
+    } else {
+        let len_bytes: Vec<u8> = len
+            .to_be_bytes()
+            .into_iter()
+            .skip_while(|b| *b == 0)
+            .collect();
+        out.push(0x80 | len_bytes.len() as u8);
+        out.extend(len_bytes);
+    }
+    out.extend_from_slice(content);
+    out
+}
+
+fn sequence(parts: &[&[u8]]) -> Vec<u8> {
+    tlv(0x30, &parts.concat())
+}
+
+fn oid(oid: &str) -> Vec<u8> {
+    ObjectIdentifier::new_unwrap(oid).to_der().unwrap()
+}
+
+fn bit_string(bytes: &[u8]) -> Vec<u8> {
+    tlv(0x03, &[&[0u8], bytes].concat())
+}
+
+fn name(common_name: &str) -> Vec<u8> {
+    let attribute = sequence(&[&oid("2.5.4.3"), &tlv(0x0c, common_name.as_bytes())]);
+    sequence(&[&tlv(0x31, &attribute)])
+}
+
+fn time(seconds: u64) -> Vec<u8> {
+    GeneralizedTime::from_unix_duration(Duration::from_secs(seconds))
+        .unwrap()
+        .to_der()
+        .unwrap()
+}
+
+fn ed25519_spki(key: &SigningKey) -> Vec<u8> {
+    sequence(&[
+        &sequence(&[&oid("1.3.101.112")]),
+        &bit_string(key.verifying_key().as_bytes()),
+    ])
+}
+
+fn p256_spki(key: &p256::ecdsa::SigningKey) -> Vec<u8> {
+    sequence(&[
+        &sequence(&[&oid("1.2.840.10045.2.1"), &oid("1.2.840.10045.3.1.7")]),
+        &bit_string(key.verifying_key().to_encoded_point(false).as_bytes()),
+    ])
+}
+
+/// A certificate to be signed with Ed25519.
+struct TestCertificate {
+    subject: &'static str,
+    issuer: &'static str,
+    not_before: u64,
+    not_after: u64,
+    spki: Vec<u8>,
+    /// `Some(path_len)` for certificate authorities.
+    ca: Option<Option<u8>>,
+    key_usage: u8,
+}
+
+impl TestCertificate {
+    fn leaf(subject: &'static str, issuer: &'static str, spki: Vec<u8>) -> Self {
+        Self {
+            subject,
+            issuer,
+            not_before: NOW - DAY,
+            not_after: NOW + DAY,
+            spki,
+            ca: None,
+            key_usage: DIGITAL_SIGNATURE,
+        }
+    }
+
+    fn ca(subject: &'static str, issuer: &'static str, key: &SigningKey) -> Self {
+        Self {
+            ca: Some(None),
+            key_usage: KEY_CERT_SIGN,
+            ..Self::leaf(subject, issuer, ed25519_spki(key))
+        }
+    }
+
+    fn sign(&self, issuer_key: &SigningKey) -> Vec<u8> {
+        let algorithm = sequence(&[&oid("1.3.101.112")]);
+        let basic_constraints = match self.ca {
+            Some(Some(path_len)) => sequence(&[&[0x01, 0x01, 0xff], &[0x02, 0x01, path_len]]),
+            Some(None) => sequence(&[&[0x01, 0x01, 0xff]]),
+            None => sequence(&[]),
+        };
+        let extensions = sequence(&[
+            &sequence(&[
+                &oid("2.5.29.19"),
+                &[0x01, 0x01, 0xff],
+                &tlv(0x04, &basic_constraints),
+            ]),
+            &sequence(&[
+                &oid("2.5.29.15"),
+                &[0x01, 0x01, 0xff],
+                &tlv(0x04, &bit_string(&[self.key_usage])),
+            ]),
+        ]);
+        let tbs_certificate = sequence(&[
+            &tlv(0xa0, &[0x02, 0x01, 0x02]),
+            &[0x02, 0x01, 0x01],
+            &algorithm,
+            &name(self.issuer),
+            &sequence(&[&time(self.not_before), &time(self.not_after)]),
+            &name(self.subject),
+            &self.spki,
+            &tlv(0xa3, &extensions),
+        ]);
+        let signature = issuer_key.sign(&tbs_certificate);
+        sequence(&[
+            &tbs_certificate,
+            &algorithm,
+            &bit_string(&signature.to_bytes()),
+        ])
+    }
+}
+
+fn now() -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(NOW)
+}
+
+struct Pki {
+    root: Vec<u8>,
+    intermediate_key: SigningKey,
+    intermediate: Vec<u8>,
+    leaf_key: SigningKey,
+}
+
+impl Pki {
+    fn new() -> Self {
+        let root_key = SigningKey::from_bytes(&[1; 32]);
+        let intermediate_key = SigningKey::from_bytes(&[2; 32]);
+        let leaf_key = SigningKey::from_bytes(&[3; 32]);
+        Self {
+            root: TestCertificate::ca("Root", "Root", &root_key).sign(&root_key),
+            intermediate: TestCertificate::ca("Intermediate", "Root", &intermediate_key)
+                .sign(&root_key),
+            intermediate_key,
+            leaf_key,
+        }
+    }
+
+    fn validator(&self) -> X509Validator {
+        X509Validator::new([self.root.clone()]).unwrap()
+    }
+
+    fn leaf(&self) -> TestCertificate {
+        TestCertificate::leaf("Alice", "Intermediate", ed25519_spki(&self.leaf_key))
+    }
+
+    fn credential(&self, leaf: &TestCertificate) -> Credential {
+        X509Credential::new(vec![
+            leaf.sign(&self.intermediate_key),
+            self.intermediate.clone(),
+        ])
+        .unwrap()
+        .try_into()
+        .unwrap()
+    }
+
+    fn signature_key(&self) -> Vec<u8> {
+        self.leaf_key.verifying_key().to_bytes().to_vec()
+    }
+}
+
+#[test]
+fn valid_chain() {
+    let pki = Pki::new();
+    let credential = pki.credential(&pki.leaf());
+    let x509_credential = pki
+        .validator()
+        .validate(&credential, &pki.signature_key(), now())
+        .unwrap();
+    assert_eq!(x509_credential.certificates().len(), 2);
+    assert_eq!(
+        X509Credential::try_from(credential).unwrap(),
+        x509_credential
+    );
+
+    // The chain may include the trust anchor.
+    let credential: Credential = X509Credential::new(vec![
+        pki.leaf().sign(&pki.intermediate_key),
+        pki.intermediate.clone(),
+        pki.root.clone(),
+    ])
+    .unwrap()
+    .try_into()
+    .unwrap();
+    pki.validator()
+        .validate(&credential, &pki.signature_key(), now())
+        .unwrap();
+}
+
+#[test]
+fn p256_leaf() {
+    let pki = Pki::new();
+    let leaf_key = p256::ecdsa::SigningKey::from_slice(&[4; 32]).unwrap();
+    let leaf = TestCertificate::leaf("Alice", "Intermediate", p256_spki(&leaf_key));
+    let signature_key = leaf_key.verifying_key().to_encoded_point(false);
+    pki.validator()
+        .validate(&pki.credential(&leaf), signature_key.as_bytes(), now())
+        .unwrap();
+}
+
+#[test]
+fn wrong_credential() {
+    let pki = Pki::new();
+    let basic: Credential = BasicCredential::new(b"Alice".to_vec()).into();
+    assert_eq!(
+        pki.validator()
+            .validate(&basic, &pki.signature_key(), now())
+            .unwrap_err(),
+        X509CredentialError::WrongCredentialType
+    );
+    assert_eq!(
+        X509Credential::new(vec![]).unwrap_err(),
+        X509CredentialError::EmptyChain
+    );
+    assert!(matches!(
+        X509Credential::new(vec![b"Alice".to_vec()]).unwrap_err(),
+        X509CredentialError::MalformedCertificate(_)
+    ));
+}
+
+#[test]
+fn signature_key_mismatch() {
+    let pki = Pki::new();
+    let other_key = SigningKey::from_bytes(&[5; 32]);
+    assert_eq!(
+        pki.validator()
+            .validate(
+                &pki.credential(&pki.leaf()),
+                other_key.verifying_key().as_bytes(),
+                now(),
+            )
+            .unwrap_err(),
+        X509CredentialError::SignatureKeyMismatch
+    );
+}
+
+#[test]
+fn untrusted_chain() {
+    let pki = Pki::new();
+    let other_root_key = SigningKey::from_bytes(&[6; 32]);
+    let other_root =
+        TestCertificate::ca("Other Root", "Other Root", &other_root_key).sign(&other_root_key);
+    let validator = X509Validator::new([other_root]).unwrap();
+    assert_eq!(
+        validator
+            .validate(&pki.credential(&pki.leaf()), &pki.signature_key(), now())
+            .unwrap_err(),
+        X509CredentialError::UntrustedChain
+    );
+
+    // A certificate authority with the same name but a different key.
+    let forged_root = TestCertificate::ca("Root", "Root", &other_root_key).sign(&other_root_key);
+    let validator = X509Validator::new([forged_root]).unwrap();
+    assert_eq!(
+        validator
+            .validate(&pki.credential(&pki.leaf()), &pki.signature_key(), now())
+            .unwrap_err(),
+        X509CredentialError::InvalidSignature
+    );
+}
+
+#[test]
+fn validity_period() {
+    let pki = Pki::new();
+    let credential = pki.credential(&pki.leaf());
+    let validator = pki.validator();
+    assert_eq!(
+        validator
+            .validate(
+                &credential,
+                &pki.signature_key(),
+                now() + Duration::from_secs(2 * DAY)
+            )
+            .unwrap_err(),
+        X509CredentialError::Expired
+    );
+    assert_eq!(
+        validator
+            .validate(
+                &credential,
+                &pki.signature_key(),
+                now() - Duration::from_secs(2 * DAY)
+            )
+            .unwrap_err(),
+        X509CredentialError::NotYetValid
+    );
+}
+
+#[test]
+fn key_usage_and_constraints() {
+    let pki = Pki::new();
+    let validator = pki.validator();
+
+    // The leaf certificate must allow digital signatures.
+    let leaf = TestCertificate {
+        key_usage: KEY_CERT_SIGN,
+        ..pki.leaf()
+    };
+    assert_eq!(
+        validator
+            .validate(&pki.credential(&leaf), &pki.signature_key(), now())
+            .unwrap_err(),
+        X509CredentialError::KeyUsageNotPermitted
+    );
+
+    // Only certificate authorities may issue certificates.
+    let alice_key = SigningKey::from_bytes(&[7; 32]);
+    let alice = TestCertificate::leaf("Alice", "Intermediate", ed25519_spki(&alice_key))
+        .sign(&pki.intermediate_key);
+    let bob = TestCertificate::leaf("Bob", "Alice", ed25519_spki(&pki.leaf_key));
+    let credential: Credential =
+        X509Credential::new(vec![bob.sign(&alice_key), alice, pki.intermediate.clone()])
+            .unwrap()
+            .try_into()
+            .unwrap();
+    assert_eq!(
+        validator
+            .validate(&credential, &pki.signature_key(), now())
+            .unwrap_err(),
+        X509CredentialError::NotACertificateAuthority
+    );
+
+    // The root does not allow intermediate certificate authorities.
+    let root_key = SigningKey::from_bytes(&[1; 32]);
+    let root = TestCertificate {
+        ca: Some(Some(0)),
+        ..TestCertificate::ca("Root", "Root", &root_key)
+    }
+    .sign(&root_key);
+    let validator = X509Validator::new([root]).unwrap();
+    assert_eq!(
+        validator
+            .validate(&pki.credential(&pki.leaf()), &pki.signature_key(), now())
+            .unwrap_err(),
+        X509CredentialError::PathLengthExceeded
+    );
+}
+
+#[test]
+fn succession() {
+    let pki = Pki::new();
+    let validator = pki.validator();
+    let credential = pki.credential(&pki.leaf());
+
+    // A renewed certificate for the same subject is a valid successor.
+    let renewed = pki.credential(&TestCertificate {
+        not_after: NOW + 30 * DAY,
+        ..pki.leaf()
+    });
+    validator
+        .validate_succession(&credential, &renewed)
+        .unwrap();
+
+    let bob = pki.credential(&TestCertificate::leaf(
+        "Bob",
+        "Intermediate",
+        ed25519_spki(&pki.leaf_key),
+    ));
+    assert_eq!(
+        validator
+            .validate_succession(&credential, &bob)
+            .unwrap_err(),
+        X509CredentialError::SubjectMismatch
+    );
+    let basic: Credential = BasicCredential::new(b"Alice".to_vec()).into();
+    assert_eq!(
+        validator
+            .validate_succession(&credential, &basic)
+            .unwrap_err(),
+        X509CredentialError::WrongCredentialType
+    );
+}